@@ -0,0 +1,56 @@
+//! The built-in targets measured by criterion
+//!
+//! Run with `cargo bench -p schema-registry-benchmarks --features criterion`.
+//! After criterion's own report, the estimates are converted back into
+//! results and written to `benchmarks/output/raw/criterion_latest.json`.
+
+use criterion::{criterion_group, Criterion};
+use schema_registry_benchmarks::adapters::compatibility::CompatibilityBenchmark;
+use schema_registry_benchmarks::adapters::storage::StorageBenchmark;
+use schema_registry_benchmarks::adapters::validation::ValidationBenchmark;
+use schema_registry_benchmarks::adapters::BenchTarget;
+use schema_registry_benchmarks::criterion_adapter::{
+    bench_target_with_criterion, default_criterion_dir, result_from_criterion,
+};
+use schema_registry_benchmarks::io;
+use std::path::Path;
+
+fn targets() -> Vec<Box<dyn BenchTarget>> {
+    vec![
+        Box::new(StorageBenchmark::new()),
+        Box::new(ValidationBenchmark::new()),
+        Box::new(CompatibilityBenchmark::new()),
+    ]
+}
+
+fn bench_builtin_targets(c: &mut Criterion) {
+    for target in targets() {
+        bench_target_with_criterion(c, target.as_ref());
+    }
+}
+
+criterion_group!(benches, bench_builtin_targets);
+
+fn main() {
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+
+    // Cargo runs benches from the package directory, criterion writes under the workspace
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let criterion_dir = workspace.join(default_criterion_dir());
+    let results: Vec<_> = targets()
+        .iter()
+        .filter_map(|target| match result_from_criterion(&criterion_dir, target.id()) {
+            Ok(result) => Some(result),
+            Err(e) => {
+                eprintln!("Skipping {}: {:#}", target.id(), e);
+                None
+            }
+        })
+        .collect();
+    let layout = io::OutputLayout::default();
+    let path = layout.raw_dir.join("criterion_latest.json");
+    if let Err(e) = io::ensure_output_dirs(&layout).and_then(|()| io::write_json(&results, &path)) {
+        eprintln!("Failed to write {}: {:#}", path.display(), e);
+    }
+}
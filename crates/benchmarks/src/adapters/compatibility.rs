@@ -1,60 +1,330 @@
 //! Compatibility checking benchmarks
-
-use super::BenchTarget;
+//!
+//! Each operation runs the real checker over a workload generated from
+//! [`BenchConfig::seed`]: for JSON Schema and Avro, a schema paired with
+//! each of its [`Change`]s for the backward, forward and full checks, and a
+//! chain of [`CHAIN_LENGTH`] versions whose latest is checked against all
+//! earlier ones for the transitive check. Protobuf is left out because the
+//! checker doesn't analyze it. How many of the checked pairs were compatible
+//! is reported, so drift in the generated workload shows across runs.
+//!
+//! The top-level metrics cover both formats; each format's share is under
+//! `by_format`, keyed by [`format_key`].
+//!
+//! Before timing, fixture pairs with known breaking changes are checked once,
+//! and the run fails if the reported violation kinds drift, so checker
+//! regressions surface here and not just timing changes.
+
+use super::{BenchConfig, BenchTarget};
+use crate::metrics::{format_key, CompatibilityFormatMetrics, CompatibilityMetrics};
+use crate::observer::record_iteration;
+use crate::sampling::{PoolSlot, SeriesId};
+use crate::workload::SchemaWorkloadGenerator;
 use crate::BenchmarkResult;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use async_trait::async_trait;
-use serde_json::json;
+use schema_registry_compatibility::check_content;
+use schema_registry_core::traits::CompatibilityViolation;
+use schema_registry_core::{CompatibilityMode, SerializationFormat};
+use serde_json::{json, Value};
 use std::time::Instant;
 
-/// Benchmark for compatibility checking operations
-pub struct CompatibilityBenchmark;
+/// Versions in each transitive chain, the original schema included
+pub const CHAIN_LENGTH: usize = 10;
+
+/// Formats of the generated workload
+const FORMATS: [SerializationFormat; 2] = [SerializationFormat::JsonSchema, SerializationFormat::Avro];
+
+/// Modes of the pairwise checks, in [`SERIES`] order
+const PAIR_MODES: [CompatibilityMode; 3] = [
+    CompatibilityMode::Backward,
+    CompatibilityMode::Forward,
+    CompatibilityMode::Full,
+];
+
+const JSON_USER_V1: &str = r#"{
+    "type": "object",
+    "properties": {"id": {"type": "string"}, "score": {"type": "number"}}
+}"#;
+
+const JSON_USER_V2: &str = r#"{
+    "type": "object",
+    "properties": {"id": {"type": "string"}, "score": {"type": "number"}, "tag": {"type": "string"}}
+}"#;
+
+/// Adds a required field and narrows `score` to integer
+const JSON_USER_V3: &str = r#"{
+    "type": "object",
+    "properties": {
+        "id": {"type": "string"},
+        "score": {"type": "integer"},
+        "tag": {"type": "string"},
+        "email": {"type": "string"}
+    },
+    "required": ["email"]
+}"#;
+
+const AVRO_USER_V1: &str = r#"{
+    "type": "record", "name": "User", "namespace": "com.example",
+    "fields": [
+        {"name": "id", "type": "string"},
+        {"name": "age", "type": "int"},
+        {"name": "status", "type": {"type": "enum", "name": "Status", "symbols": ["ACTIVE", "SUSPENDED", "DELETED"]}}
+    ]
+}"#;
+
+/// Drops `age` (no default) and the DELETED symbol
+const AVRO_USER_V2: &str = r#"{
+    "type": "record", "name": "User", "namespace": "com.example",
+    "fields": [
+        {"name": "id", "type": "string"},
+        {"name": "status", "type": {"type": "enum", "name": "Status", "symbols": ["ACTIVE", "SUSPENDED"]}}
+    ]
+}"#;
+
+/// Sample series recorded per measured iteration
+pub(crate) const SERIES: [&str; 4] = ["backward", "forward", "full", "transitive"];
+
+/// A schema change the generated workload makes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    /// Add an optional field (with a null default in Avro)
+    AddOptionalField,
+    /// Remove the last field
+    RemoveField,
+    /// Change the type of the last primitive field
+    ChangeType,
+}
 
-impl CompatibilityBenchmark {
-    /// Create a new compatibility benchmark
-    pub fn new() -> Self {
-        Self
+/// Changes paired with each generated schema, and cycled through along a chain
+const CHANGES: [Change; 3] = [Change::AddOptionalField, Change::RemoveField, Change::ChangeType];
+
+/// A different primitive than `kind`, in both JSON Schema and Avro
+fn changed_type(kind: &str) -> &'static str {
+    if kind == "boolean" {
+        "string"
+    } else {
+        "boolean"
     }
+}
 
-    /// Simulate backward compatibility check
-    async fn bench_backward_check(&self) -> f64 {
-        let start = Instant::now();
+/// `schema` after `change`; `n` keeps the names of added fields unique along a chain
+fn evolve(format: SerializationFormat, schema: &Value, change: Change, n: usize) -> Value {
+    let mut evolved = schema.clone();
+    match format {
+        SerializationFormat::Avro => {
+            let Some(fields) = evolved.get_mut("fields").and_then(Value::as_array_mut) else {
+                return evolved;
+            };
+            match change {
+                Change::AddOptionalField => {
+                    fields.push(json!({"name": format!("added_{}", n), "type": ["null", "string"], "default": null}));
+                }
+                Change::RemoveField => {
+                    if fields.len() > 1 {
+                        fields.pop();
+                    }
+                }
+                Change::ChangeType => match fields.last_mut().and_then(|field| field.get_mut("type")) {
+                    Some(Value::String(kind)) => *kind = changed_type(kind).to_string(),
+                    // Optional fields are ["null", type] unions
+                    Some(Value::Array(branches)) => {
+                        if let Some(Value::String(kind)) = branches.last_mut() {
+                            *kind = changed_type(kind).to_string();
+                        }
+                    }
+                    _ => {}
+                },
+            }
+        }
+        _ => {
+            let Some(properties) = evolved.get_mut("properties").and_then(Value::as_object_mut) else {
+                return evolved;
+            };
+            match change {
+                Change::AddOptionalField => {
+                    properties.insert(format!("added_{}", n), json!({"type": "string"}));
+                }
+                Change::RemoveField => {
+                    let last = properties.keys().next_back().cloned();
+                    if let Some(name) = last.filter(|_| properties.len() > 1) {
+                        properties.remove(&name);
+                        if let Some(required) = evolved.get_mut("required").and_then(Value::as_array_mut) {
+                            required.retain(|field| field != name.as_str());
+                        }
+                    }
+                }
+                Change::ChangeType => {
+                    let primitive = properties.values_mut().rev().find(|property| property["type"] != "object");
+                    if let Some(Value::String(kind)) = primitive.and_then(|property| property.get_mut("type")) {
+                        *kind = changed_type(kind).to_string();
+                    }
+                }
+            }
+        }
+    }
+    evolved
+}
 
-        // Simulate backward compatibility check (in production, this would use actual checker)
-        tokio::time::sleep(tokio::time::Duration::from_micros(90)).await;
+/// Generated schema pairs and version chain of one format, checked on every iteration
+struct FormatWorkload {
+    format: SerializationFormat,
+    /// Old and new schema for each of [`CHANGES`]
+    pairs: Vec<(String, String)>,
+    /// Versions of one schema, oldest first
+    chain: Vec<String>,
+}
 
-        start.elapsed().as_secs_f64() * 1000.0 // Convert to milliseconds
+impl FormatWorkload {
+    /// Pairs and chain of the next schema `generator` gives in `format`
+    fn generate(format: SerializationFormat, generator: &mut SchemaWorkloadGenerator) -> Result<Self> {
+        let base: Value = serde_json::from_str(&generator.generate(format))?;
+        let pairs = CHANGES
+            .iter()
+            .map(|&change| (base.to_string(), evolve(format, &base, change, 0).to_string()))
+            .collect();
+
+        let mut chain = vec![base];
+        for n in 1..CHAIN_LENGTH {
+            let next = evolve(format, &chain[n - 1], CHANGES[(n - 1) % CHANGES.len()], n);
+            chain.push(next);
+        }
+        Ok(Self {
+            format,
+            pairs,
+            chain: chain.iter().map(Value::to_string).collect(),
+        })
     }
 
-    /// Simulate forward compatibility check
-    async fn bench_forward_check(&self) -> f64 {
-        let start = Instant::now();
+    /// Pairs one pass over the workload checks, in all four operations
+    fn checked_pairs(&self) -> usize {
+        self.pairs.len() * PAIR_MODES.len() + self.chain.len().saturating_sub(1)
+    }
+}
 
-        // Simulate forward compatibility check (in production, this would use actual checker)
-        tokio::time::sleep(tokio::time::Duration::from_micros(85)).await;
+/// The workload for `seed`, in [`FORMATS`] order; the same seed always gives the same schemas
+fn generate_workload(seed: u64) -> Result<[FormatWorkload; FORMATS.len()]> {
+    let mut generator = SchemaWorkloadGenerator::new(seed);
+    let [json_schema, avro] = FORMATS;
+    Ok([
+        FormatWorkload::generate(json_schema, &mut generator)?,
+        FormatWorkload::generate(avro, &mut generator)?,
+    ])
+}
 
-        start.elapsed().as_secs_f64() * 1000.0 // Convert to milliseconds
-    }
+/// Benchmark for compatibility checking operations
+pub struct CompatibilityBenchmark {
+    samples: PoolSlot,
+}
 
-    /// Simulate full compatibility check
-    async fn bench_full_check(&self) -> f64 {
-        let start = Instant::now();
+impl CompatibilityBenchmark {
+    /// Create a new compatibility benchmark
+    pub fn new() -> Self {
+        Self {
+            samples: PoolSlot::new(),
+        }
+    }
 
-        // Simulate full compatibility check (in production, this would use actual checker)
-        tokio::time::sleep(tokio::time::Duration::from_micros(120)).await;
+    /// Check the fixture pairs and fail if the checker reports other violation kinds
+    fn verify_fixtures(&self) -> Result<()> {
+        // A JSON Schema change adding a required field
+        let violations = check_content(
+            JSON_USER_V3,
+            JSON_USER_V2,
+            SerializationFormat::JsonSchema,
+            CompatibilityMode::Backward,
+        )?;
+        expect_kinds("backward", &violations, &["REQUIRED_FIELD_ADDED", "TYPE_NARROWED"])?;
+
+        // An Avro change removing a field without default
+        let violations = check_content(
+            AVRO_USER_V2,
+            AVRO_USER_V1,
+            SerializationFormat::Avro,
+            CompatibilityMode::Forward,
+        )?;
+        expect_kinds("forward", &violations, &["FIELD_REMOVED_WITHOUT_DEFAULT"])?;
+
+        // The same Avro change; the enum shrink only breaks backward
+        let violations = check_content(
+            AVRO_USER_V2,
+            AVRO_USER_V1,
+            SerializationFormat::Avro,
+            CompatibilityMode::Full,
+        )?;
+        expect_kinds("full", &violations, &["ENUM_SYMBOL_REMOVED", "FIELD_REMOVED_WITHOUT_DEFAULT"])?;
+
+        // The JSON Schema change against all history
+        let mut violations = Vec::new();
+        for previous in [JSON_USER_V1, JSON_USER_V2] {
+            violations.extend(check_content(
+                JSON_USER_V3,
+                previous,
+                SerializationFormat::JsonSchema,
+                CompatibilityMode::BackwardTransitive,
+            )?);
+        }
+        expect_kinds(
+            "transitive",
+            &violations,
+            &["REQUIRED_FIELD_ADDED", "TYPE_NARROWED", "REQUIRED_FIELD_ADDED", "TYPE_NARROWED"],
+        )
+    }
 
-        start.elapsed().as_secs_f64() * 1000.0 // Convert to milliseconds
+    /// Check every generated pair in `mode`; returns the time and how many were compatible
+    fn bench_pairs(&self, workload: &FormatWorkload, mode: CompatibilityMode) -> Result<(f64, usize)> {
+        let start = Instant::now();
+        let mut compatible = 0;
+        for (old, new) in &workload.pairs {
+            compatible += usize::from(check_content(new, old, workload.format, mode)?.is_empty());
+        }
+        Ok((start.elapsed().as_secs_f64() * 1000.0, compatible)) // Convert to milliseconds
     }
 
-    /// Simulate transitive compatibility check
-    async fn bench_transitive_check(&self) -> f64 {
+    /// Check the latest version of the chain against all earlier ones, backward-transitively
+    fn bench_transitive(&self, workload: &FormatWorkload) -> Result<(f64, usize)> {
         let start = Instant::now();
+        let mut compatible = 0;
+        if let Some((latest, previous)) = workload.chain.split_last() {
+            for old in previous {
+                let violations =
+                    check_content(latest, old, workload.format, CompatibilityMode::BackwardTransitive)?;
+                compatible += usize::from(violations.is_empty());
+            }
+        }
+        Ok((start.elapsed().as_secs_f64() * 1000.0, compatible)) // Convert to milliseconds
+    }
 
-        // Simulate transitive compatibility check (in production, this would use actual checker)
-        tokio::time::sleep(tokio::time::Duration::from_micros(200)).await;
+    /// Time each of [`SERIES`] on `workload`; returns the times and how many pairs were compatible
+    fn bench_format(&self, workload: &FormatWorkload) -> Result<([f64; SERIES.len()], usize)> {
+        let mut timings = [0.0; SERIES.len()];
+        let mut compatible = 0;
+        for (timing, mode) in timings.iter_mut().zip(PAIR_MODES) {
+            let (ms, count) = self.bench_pairs(workload, mode)?;
+            *timing = ms;
+            compatible += count;
+        }
+        let (ms, count) = self.bench_transitive(workload)?;
+        timings[PAIR_MODES.len()] = ms;
+        Ok((timings, compatible + count))
+    }
+}
 
-        start.elapsed().as_secs_f64() * 1000.0 // Convert to milliseconds
+/// Fail when the checker's violation kinds differ from the fixture's expectation
+fn expect_kinds(operation: &str, violations: &[CompatibilityViolation], expected: &[&str]) -> Result<()> {
+    let mut actual: Vec<&str> = violations.iter().map(|v| v.code()).collect();
+    let mut expected = expected.to_vec();
+    actual.sort_unstable();
+    expected.sort_unstable();
+    if actual != expected {
+        bail!(
+            "{} compatibility check regressed: expected {:?}, got {:?}",
+            operation,
+            expected,
+            actual
+        );
     }
+    Ok(())
 }
 
 impl Default for CompatibilityBenchmark {
@@ -73,52 +343,90 @@ impl BenchTarget for CompatibilityBenchmark {
         "Benchmarks for schema compatibility checking (backward, forward, full, transitive)"
     }
 
+    fn tags(&self) -> &[&str] {
+        &["compatibility", "fast"]
+    }
+
+    fn formats(&self) -> &[SerializationFormat] {
+        &FORMATS
+    }
+
     async fn run(&self) -> Result<BenchmarkResult> {
-        // Run multiple iterations for more accurate measurements
-        let iterations = 10;
-        let mut backward_times = Vec::new();
-        let mut forward_times = Vec::new();
-        let mut full_times = Vec::new();
-        let mut transitive_times = Vec::new();
-
-        for _ in 0..iterations {
-            backward_times.push(self.bench_backward_check().await);
-            forward_times.push(self.bench_forward_check().await);
-            full_times.push(self.bench_full_check().await);
-            transitive_times.push(self.bench_transitive_check().await);
-        }
+        self.run_with_config(&BenchConfig::default()).await
+    }
 
-        // Calculate statistics
-        let avg_backward = backward_times.iter().sum::<f64>() / backward_times.len() as f64;
-        let avg_forward = forward_times.iter().sum::<f64>() / forward_times.len() as f64;
-        let avg_full = full_times.iter().sum::<f64>() / full_times.len() as f64;
-        let avg_transitive = transitive_times.iter().sum::<f64>() / transitive_times.len() as f64;
-
-        let metrics = json!({
-            "iterations": iterations,
-            "backward": {
-                "avg_ms": format!("{:.3}", avg_backward),
-                "min_ms": format!("{:.3}", backward_times.iter().cloned().fold(f64::INFINITY, f64::min)),
-                "max_ms": format!("{:.3}", backward_times.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
-            },
-            "forward": {
-                "avg_ms": format!("{:.3}", avg_forward),
-                "min_ms": format!("{:.3}", forward_times.iter().cloned().fold(f64::INFINITY, f64::min)),
-                "max_ms": format!("{:.3}", forward_times.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
-            },
-            "full": {
-                "avg_ms": format!("{:.3}", avg_full),
-                "min_ms": format!("{:.3}", full_times.iter().cloned().fold(f64::INFINITY, f64::min)),
-                "max_ms": format!("{:.3}", full_times.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
-            },
-            "transitive": {
-                "avg_ms": format!("{:.3}", avg_transitive),
-                "min_ms": format!("{:.3}", transitive_times.iter().cloned().fold(f64::INFINITY, f64::min)),
-                "max_ms": format!("{:.3}", transitive_times.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
+    async fn run_with_config(&self, cfg: &BenchConfig) -> Result<BenchmarkResult> {
+        cfg.validate()?;
+
+        let mut samples = self.samples.take();
+        samples.begin_run(cfg.sample_capacity());
+        samples.set_outlier_policy(cfg.outliers);
+        let series = SERIES.map(|label| samples.series(label));
+        let [backward, forward, full, transitive] = series;
+
+        self.verify_fixtures()?;
+        let workload = generate_workload(cfg.seed)?;
+        let format_series: [[SeriesId; SERIES.len()]; FORMATS.len()] = workload
+            .each_ref()
+            .map(|format| SERIES.map(|operation| samples.series_path(&[format_key(format.format), operation])));
+
+        // Every pass checks the same pairs, so any measured pass gives the counts
+        let mut compatible_pairs = [0; FORMATS.len()];
+        let mut iterations = cfg.iterations();
+        for i in iterations.by_ref() {
+            let mut timings = [[0.0; SERIES.len()]; FORMATS.len()];
+            let mut compatible = [0; FORMATS.len()];
+            for ((format, timing), count) in workload.iter().zip(&mut timings).zip(&mut compatible) {
+                (*timing, *count) = self.bench_format(format)?;
             }
-        });
+            let totals: [f64; SERIES.len()] =
+                std::array::from_fn(|operation| timings.iter().map(|timing| timing[operation]).sum());
+            record_iteration(i, cfg.is_warmup(i), totals.iter().sum());
+            if cfg.is_warmup(i) {
+                continue;
+            }
+            for (id, ms) in series.iter().zip(totals) {
+                samples.record(*id, ms);
+            }
+            for (ids, timing) in format_series.iter().zip(&timings) {
+                for (id, ms) in ids.iter().zip(timing) {
+                    samples.record(*id, *ms);
+                }
+            }
+            compatible_pairs = compatible;
+        }
 
-        Ok(BenchmarkResult::new(self.id().to_string(), metrics))
+        let by_format = workload
+            .iter()
+            .zip(&format_series)
+            .zip(compatible_pairs)
+            .map(|((format, [backward, forward, full, transitive]), compatible_pairs)| {
+                let metrics = CompatibilityFormatMetrics {
+                    checked_pairs: format.checked_pairs(),
+                    compatible_pairs,
+                    backward: samples.metrics(*backward),
+                    forward: samples.metrics(*forward),
+                    full: samples.metrics(*full),
+                    transitive: samples.metrics(*transitive),
+                };
+                (format_key(format.format).to_string(), metrics)
+            })
+            .collect();
+        let metrics = CompatibilityMetrics {
+            counts: iterations.counts(),
+            checked_pairs: workload.iter().map(FormatWorkload::checked_pairs).sum(),
+            compatible_pairs: compatible_pairs.iter().sum(),
+            backward: samples.metrics(backward),
+            forward: samples.metrics(forward),
+            full: samples.metrics(full),
+            transitive: samples.metrics(transitive),
+            by_format,
+        };
+        let result = BenchmarkResult::new(self.id().to_string(), serde_json::to_value(metrics)?)
+            .with_raw_samples(&samples);
+        self.samples.restore(samples);
+
+        Ok(result)
     }
 }
 
@@ -170,53 +478,119 @@ mod tests {
         // Check backward metrics
         let backward = result.metrics.get("backward").unwrap();
         assert!(backward.get("avg_ms").is_some());
+        assert!(backward.get("p95_ms").is_some());
         assert!(backward.get("min_ms").is_some());
         assert!(backward.get("max_ms").is_some());
 
         // Check forward metrics
         let forward = result.metrics.get("forward").unwrap();
         assert!(forward.get("avg_ms").is_some());
+        assert!(forward.get("p95_ms").is_some());
         assert!(forward.get("min_ms").is_some());
         assert!(forward.get("max_ms").is_some());
 
         // Check full metrics
         let full = result.metrics.get("full").unwrap();
         assert!(full.get("avg_ms").is_some());
+        assert!(full.get("p95_ms").is_some());
         assert!(full.get("min_ms").is_some());
         assert!(full.get("max_ms").is_some());
 
         // Check transitive metrics
         let transitive = result.metrics.get("transitive").unwrap();
         assert!(transitive.get("avg_ms").is_some());
+        assert!(transitive.get("p95_ms").is_some());
         assert!(transitive.get("min_ms").is_some());
         assert!(transitive.get("max_ms").is_some());
     }
 
-    #[tokio::test]
-    async fn test_bench_backward_check() {
-        let bench = CompatibilityBenchmark::new();
-        let duration = bench.bench_backward_check().await;
-        assert!(duration > 0.0);
+    #[test]
+    fn test_fixtures_report_the_expected_violations() {
+        CompatibilityBenchmark::new().verify_fixtures().unwrap();
     }
 
-    #[tokio::test]
-    async fn test_bench_forward_check() {
+    #[test]
+    fn test_bench_pairs() {
         let bench = CompatibilityBenchmark::new();
-        let duration = bench.bench_forward_check().await;
-        assert!(duration > 0.0);
+        for workload in generate_workload(0).unwrap() {
+            for mode in PAIR_MODES {
+                let (duration, compatible) = bench.bench_pairs(&workload, mode).unwrap();
+                assert!(duration > 0.0);
+                assert!(compatible < workload.pairs.len(), "{:?} {:?}", workload.format, mode);
+            }
+            let (duration, compatible) = bench.bench_transitive(&workload).unwrap();
+            assert!(duration > 0.0);
+            assert!(compatible < CHAIN_LENGTH - 1, "{:?}", workload.format);
+        }
     }
 
-    #[tokio::test]
-    async fn test_bench_full_check() {
-        let bench = CompatibilityBenchmark::new();
-        let duration = bench.bench_full_check().await;
-        assert!(duration > 0.0);
+    #[test]
+    fn test_workload_pairs_and_chains() {
+        let [json_schema, avro] = generate_workload(0).unwrap();
+        assert_eq!(json_schema.format, SerializationFormat::JsonSchema);
+        for workload in [&json_schema, &avro] {
+            assert_eq!(workload.pairs.len(), CHANGES.len());
+            assert_eq!(workload.chain.len(), CHAIN_LENGTH);
+            assert_eq!(workload.checked_pairs(), 3 * 3 + CHAIN_LENGTH - 1);
+        }
+        assert_eq!(generate_workload(0).unwrap()[1].pairs, avro.pairs);
+
+        // Avro: a field with a default reads old data; a changed type doesn't
+        let backward = |(old, new): &(String, String)| {
+            check_content(new, old, SerializationFormat::Avro, CompatibilityMode::Backward).unwrap()
+        };
+        assert!(backward(&avro.pairs[0]).is_empty());
+        assert!(!backward(&avro.pairs[2]).is_empty());
+    }
+
+    #[test]
+    fn test_evolve_json_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"a": {"type": "object"}, "b": {"type": "integer"}},
+            "required": ["b"]
+        });
+        let format = SerializationFormat::JsonSchema;
+
+        let added = evolve(format, &schema, Change::AddOptionalField, 3);
+        assert_eq!(added["properties"]["added_3"], json!({"type": "string"}));
+        assert_eq!(added["required"], json!(["b"]));
+
+        let removed = evolve(format, &schema, Change::RemoveField, 0);
+        assert!(removed["properties"].get("b").is_none());
+        assert_eq!(removed["required"], json!([]));
+
+        let changed = evolve(format, &schema, Change::ChangeType, 0);
+        assert_eq!(changed["properties"]["b"]["type"], "boolean");
+        assert_eq!(changed["properties"]["a"]["type"], "object");
     }
 
     #[tokio::test]
-    async fn test_bench_transitive_check() {
+    async fn test_reports_compatible_pairs() {
         let bench = CompatibilityBenchmark::new();
-        let duration = bench.bench_transitive_check().await;
-        assert!(duration > 0.0);
+        let result = bench.run_with_config(&BenchConfig::new(0, 2)).await.unwrap();
+        let metrics: CompatibilityMetrics = serde_json::from_value(result.metrics.clone()).unwrap();
+
+        assert_eq!(metrics.checked_pairs, 2 * (3 * 3 + CHAIN_LENGTH - 1));
+        assert!(metrics.compatible_pairs > 0 && metrics.compatible_pairs < metrics.checked_pairs);
+        assert_eq!(result.metrics["compatible_pairs"]["unit"], "count");
+
+        let formats: Vec<_> = metrics.by_format.keys().map(String::as_str).collect();
+        assert_eq!(formats, vec!["avro", "json_schema"]);
+        let avro = &metrics.by_format["avro"];
+        assert_eq!(avro.checked_pairs * 2, metrics.checked_pairs);
+        assert!(avro.backward.max_ms > 0.0 && avro.backward.max_ms <= metrics.backward.max_ms);
+        assert!(result.metrics_for_format(SerializationFormat::Protobuf).is_none());
+    }
+
+    #[test]
+    fn test_expect_kinds_detects_regression() {
+        let violations = vec![CompatibilityViolation::new(
+            schema_registry_core::CompatViolation::RequiredFieldAdded { field: "email".to_string() },
+            "$",
+        )];
+        assert!(expect_kinds("backward", &violations, &["REQUIRED_FIELD_ADDED"]).is_ok());
+        assert!(expect_kinds("backward", &violations, &["TYPE_NARROWED"]).is_err());
+        assert!(expect_kinds("backward", &[], &["REQUIRED_FIELD_ADDED"]).is_err());
     }
 }
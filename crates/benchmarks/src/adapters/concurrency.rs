@@ -0,0 +1,267 @@
+//! Concurrency and contention benchmarks
+//!
+//! Spawns increasing numbers of tasks that all read the same schema from one
+//! [`EmbeddedRegistry`], so the storage lock is contended, and reports
+//! throughput and latency at each concurrency level. Single-task latency
+//! alone hides that contention.
+//!
+//! Levels above [`ConcurrencyBenchmark::max_tasks`], by default the number
+//! of available cores, are clamped to it: with more tasks than runtime
+//! worker threads the extra tasks only queue. Spawned tasks run on the
+//! ambient tokio runtime, so on a current-thread runtime they interleave
+//! rather than contend.
+
+use super::{BenchConfig, BenchTarget};
+use crate::metrics::{ConcurrencyLevelMetrics, ConcurrencyMetrics};
+use crate::observer::record_iteration;
+use crate::sampling::{PoolSlot, SeriesId};
+use crate::BenchmarkResult;
+use anyhow::Result;
+use async_trait::async_trait;
+use schema_registry_core::embedded::EmbeddedRegistry;
+use schema_registry_core::{RegisteredSchema, SchemaInput, SerializationFormat};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+
+/// Concurrency levels run unless configured otherwise
+pub const DEFAULT_LEVELS: [usize; 4] = [1, 8, 32, 128];
+
+/// Reads each task performs per level and iteration
+pub const OPERATIONS_PER_TASK: usize = 25;
+
+/// Seed for the registry's id generator, so runs store identical data
+const REGISTRY_SEED: u64 = 0xC0C0;
+
+/// Benchmark for throughput and latency under concurrent reads
+pub struct ConcurrencyBenchmark {
+    registry: Arc<EmbeddedRegistry>,
+    hot: OnceCell<RegisteredSchema>,
+    levels: Vec<usize>,
+    max_tasks: usize,
+    samples: PoolSlot,
+}
+
+impl ConcurrencyBenchmark {
+    /// Create a new concurrency benchmark with [`DEFAULT_LEVELS`], bounded by the available cores
+    pub fn new() -> Self {
+        Self {
+            registry: Arc::new(EmbeddedRegistry::builder().with_seed(REGISTRY_SEED).build()),
+            hot: OnceCell::new(),
+            levels: DEFAULT_LEVELS.to_vec(),
+            max_tasks: std::thread::available_parallelism().map_or(1, |cores| cores.get()),
+            samples: PoolSlot::new(),
+        }
+    }
+
+    /// Run these numbers of concurrent tasks instead of [`DEFAULT_LEVELS`]
+    pub fn with_levels(mut self, levels: impl IntoIterator<Item = usize>) -> Self {
+        self.levels = levels.into_iter().collect();
+        self
+    }
+
+    /// Allow up to `max_tasks` concurrent tasks (at least one), whatever the core count
+    pub fn with_max_tasks(mut self, max_tasks: usize) -> Self {
+        self.max_tasks = max_tasks.max(1);
+        self
+    }
+
+    /// Most tasks any level runs
+    pub fn max_tasks(&self) -> usize {
+        self.max_tasks
+    }
+
+    /// Task counts actually run: the configured levels clamped to
+    /// [`max_tasks`](Self::max_tasks), ascending and without duplicates
+    pub fn effective_levels(&self) -> Vec<usize> {
+        let mut levels: Vec<usize> = self.levels.iter().map(|&tasks| tasks.clamp(1, self.max_tasks)).collect();
+        levels.sort_unstable();
+        levels.dedup();
+        levels
+    }
+
+    /// The schema every task reads, registered on first use
+    async fn hot_schema(&self) -> Result<&RegisteredSchema> {
+        self.hot
+            .get_or_try_init(|| async {
+                let content = serde_json::json!({
+                    "type": "object",
+                    "properties": {"id": {"type": "string"}, "amount": {"type": "number"}}
+                });
+                let input = SchemaInput::new(
+                    "bench.concurrency",
+                    "hot_subject",
+                    SerializationFormat::JsonSchema,
+                    content.to_string(),
+                );
+                self.registry.register(input).await
+            })
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Run `tasks` tasks of [`OPERATIONS_PER_TASK`] reads each; returns the
+    /// wall time and every read's latency in milliseconds
+    async fn bench_level(&self, schema: &RegisteredSchema, tasks: usize) -> Result<(Duration, Vec<Vec<f64>>)> {
+        let start = Instant::now();
+        let handles: Vec<_> = (0..tasks)
+            .map(|_| {
+                let registry = Arc::clone(&self.registry);
+                let id = schema.id;
+                tokio::spawn(async move {
+                    let mut latencies = Vec::with_capacity(OPERATIONS_PER_TASK);
+                    for _ in 0..OPERATIONS_PER_TASK {
+                        let start = Instant::now();
+                        registry.get(id, None).await?;
+                        latencies.push(start.elapsed().as_secs_f64() * 1000.0); // Convert to milliseconds
+                    }
+                    Ok::<_, anyhow::Error>(latencies)
+                })
+            })
+            .collect();
+
+        let mut latencies = Vec::with_capacity(tasks);
+        for handle in handles {
+            latencies.push(handle.await??);
+        }
+        Ok((start.elapsed(), latencies))
+    }
+}
+
+impl Default for ConcurrencyBenchmark {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wall time and operation count accumulated at one level over the measured iterations
+#[derive(Default)]
+struct LevelTotals {
+    wall: Duration,
+    operations: usize,
+}
+
+#[async_trait]
+impl BenchTarget for ConcurrencyBenchmark {
+    fn id(&self) -> &str {
+        "concurrency_scaling"
+    }
+
+    fn description(&self) -> &str {
+        "Throughput and p99 latency of concurrent reads of one schema at increasing task counts"
+    }
+
+    fn tags(&self) -> &[&str] {
+        &["concurrency", "slow"]
+    }
+
+    async fn run(&self) -> Result<BenchmarkResult> {
+        self.run_with_config(&BenchConfig::default()).await
+    }
+
+    async fn run_with_config(&self, cfg: &BenchConfig) -> Result<BenchmarkResult> {
+        cfg.validate()?;
+
+        let schema = self.hot_schema().await?;
+        let levels = self.effective_levels();
+        let mut totals: Vec<LevelTotals> = levels.iter().map(|_| LevelTotals::default()).collect();
+
+        let mut samples = self.samples.take();
+        samples.begin_run(cfg.sample_capacity());
+        samples.set_outlier_policy(cfg.outliers);
+        let series: Vec<SeriesId> = levels
+            .iter()
+            .map(|tasks| samples.series(&format!("tasks_{}", tasks)))
+            .collect();
+
+        let mut iterations = cfg.iterations();
+        for i in iterations.by_ref() {
+            let mut iteration_ms = 0.0;
+            for ((&tasks, &id), level) in levels.iter().zip(&series).zip(&mut totals) {
+                let (wall, latencies) = self.bench_level(schema, tasks).await?;
+                iteration_ms += wall.as_secs_f64() * 1000.0;
+                if cfg.is_warmup(i) {
+                    continue;
+                }
+                level.wall += wall;
+                level.operations += tasks * OPERATIONS_PER_TASK;
+                // Outside the timed section: a level records more samples than iterations
+                samples.reserve(tasks * OPERATIONS_PER_TASK);
+                for latency in latencies.into_iter().flatten() {
+                    samples.record(id, latency);
+                }
+            }
+            record_iteration(i, cfg.is_warmup(i), iteration_ms);
+        }
+
+        let throughput = |level: &LevelTotals| {
+            let seconds = level.wall.as_secs_f64();
+            if seconds > 0.0 {
+                level.operations as f64 / seconds
+            } else {
+                0.0
+            }
+        };
+        let baseline = totals.first().map_or(0.0, throughput);
+        let level_metrics: Vec<ConcurrencyLevelMetrics> = levels
+            .iter()
+            .zip(&series)
+            .zip(&totals)
+            .map(|((&tasks, &id), level)| {
+                let throughput_ops_per_sec = throughput(level);
+                ConcurrencyLevelMetrics {
+                    tasks,
+                    operations: level.operations,
+                    throughput_ops_per_sec,
+                    scalability_factor: if baseline > 0.0 { throughput_ops_per_sec / baseline } else { 0.0 },
+                    latency: samples.metrics(id),
+                }
+            })
+            .collect();
+        let metrics = ConcurrencyMetrics {
+            counts: iterations.counts(),
+            max_tasks: self.max_tasks,
+            scalability_factor: level_metrics.last().map_or(0.0, |level| level.scalability_factor),
+            levels: level_metrics,
+        };
+        let result = BenchmarkResult::new(self.id().to_string(), serde_json::to_value(metrics)?)
+            .with_raw_samples(&samples);
+        self.samples.restore(samples);
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_are_bounded_by_max_tasks() {
+        let bench = ConcurrencyBenchmark::new().with_max_tasks(16);
+        assert_eq!(bench.effective_levels(), vec![1, 8, 16]);
+
+        let bench = ConcurrencyBenchmark::new().with_levels([4, 0, 2, 4]).with_max_tasks(128);
+        assert_eq!(bench.effective_levels(), vec![1, 2, 4]);
+        assert_eq!(ConcurrencyBenchmark::new().with_max_tasks(0).max_tasks(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_reports_each_level() {
+        let bench = ConcurrencyBenchmark::new().with_levels([1, 4]).with_max_tasks(4);
+        let result = bench.run_with_config(&BenchConfig::new(1, 2)).await.unwrap();
+        let metrics: ConcurrencyMetrics = serde_json::from_value(result.metrics.clone()).unwrap();
+
+        assert_eq!(metrics.max_tasks, 4);
+        let tasks: Vec<_> = metrics.levels.iter().map(|level| level.tasks).collect();
+        assert_eq!(tasks, vec![1, 4]);
+        assert_eq!(metrics.levels[1].operations, 2 * 4 * OPERATIONS_PER_TASK);
+        assert_eq!(metrics.levels[0].scalability_factor, 1.0);
+        assert_eq!(metrics.scalability_factor, metrics.levels[1].scalability_factor);
+        for level in &metrics.levels {
+            assert!(level.throughput_ops_per_sec > 0.0);
+            assert!(level.latency.p99_ms >= level.latency.p50_ms);
+        }
+        assert!(result.metrics["levels"][0]["p99_ms"].get("unit").is_some());
+    }
+}
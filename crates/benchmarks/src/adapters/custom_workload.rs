@@ -0,0 +1,351 @@
+//! Benchmarks of user-defined workloads
+//!
+//! A [`CustomWorkloadBenchmark`] runs a [`WorkloadPlan`] against an
+//! [`EmbeddedRegistry`]. Every warmup and measured iteration is one pass of
+//! the whole plan from an empty registry; each step's operations run with
+//! the step's concurrency, and results are reported per step kind.
+
+use super::storage::schema_of_size;
+use super::{BenchConfig, BenchTarget};
+use crate::metrics::{WorkloadMetrics, WorkloadOperationMetrics};
+use crate::observer::record_iteration;
+use crate::sampling::{PoolSlot, SamplePool, SeriesId};
+use crate::workload::{Operation, Phase, StepKind, WorkloadPlan};
+use crate::BenchmarkResult;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use schema_registry_compatibility::CompatibilityCheckerImpl;
+use schema_registry_core::embedded::EmbeddedRegistry;
+use schema_registry_core::traits::SchemaStorage;
+use schema_registry_core::{RegisteredSchema, SchemaInput, SerializationFormat};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Seed for the registry's id generator, so passes store identical data
+const REGISTRY_SEED: u64 = 0x5EED;
+
+/// Benchmark running a [`WorkloadPlan`]
+///
+/// The target id is `workload_<plan name>`.
+pub struct CustomWorkloadBenchmark {
+    id: String,
+    description: String,
+    plan: WorkloadPlan,
+    registry: EmbeddedRegistry,
+    samples: PoolSlot,
+}
+
+impl CustomWorkloadBenchmark {
+    /// Run `plan` against an in-memory registry with the real compatibility checker
+    pub fn new(plan: WorkloadPlan) -> Self {
+        let registry = EmbeddedRegistry::builder()
+            .with_compatibility_checker(Arc::new(CompatibilityCheckerImpl::new()))
+            .with_seed(REGISTRY_SEED)
+            .build();
+        Self::with_registry(plan, registry)
+    }
+
+    /// Run `plan` against `registry`, e.g. one built with another validator or checker
+    ///
+    /// The registry is restored to its current state after every pass.
+    pub fn with_registry(plan: WorkloadPlan, registry: EmbeddedRegistry) -> Self {
+        Self {
+            id: format!("workload_{}", plan.name),
+            description: format!(
+                "Custom workload `{}`: {} steps of registry operations from a workload plan",
+                plan.name,
+                plan.steps.len()
+            ),
+            plan,
+            registry,
+            samples: PoolSlot::new(),
+        }
+    }
+
+    /// Load the plan in `path` and run it with [`new`](Self::new)
+    pub fn from_file(path: &Path) -> Result<Self> {
+        Ok(Self::new(WorkloadPlan::load(path)?))
+    }
+
+    pub fn plan(&self) -> &WorkloadPlan {
+        &self.plan
+    }
+
+    /// Run every phase once, recording latencies and wall time when `record` is set
+    async fn run_pass(&self, phases: &[Phase], mut record: Option<&mut PassRecorder<'_>>) -> Result<f64> {
+        let namespace = format!("bench.workload.{}", self.plan.name);
+        let mut subjects: Vec<RegisteredSchema> = Vec::new();
+        let start = Instant::now();
+
+        for phase in phases {
+            let phase_start = Instant::now();
+            let subjects_before = &subjects;
+            let completed: Vec<(f64, Option<RegisteredSchema>)> = stream::iter(phase.operations.iter().copied())
+                .map(|operation| self.execute(&namespace, operation, subjects_before))
+                .buffered(phase.concurrency)
+                .try_collect()
+                .await?;
+            let phase_time = phase_start.elapsed();
+
+            if let Some(recorder) = record.as_deref_mut() {
+                recorder.phase(phase.kind, phase_time, completed.iter().map(|(ms, _)| *ms));
+            }
+            subjects.extend(completed.into_iter().filter_map(|(_, registered)| registered));
+        }
+        Ok(start.elapsed().as_secs_f64() * 1000.0) // Convert to milliseconds
+    }
+
+    /// Perform one operation, returning its latency and any schema it registered
+    async fn execute(
+        &self,
+        namespace: &str,
+        operation: Operation,
+        subjects: &[RegisteredSchema],
+    ) -> Result<(f64, Option<RegisteredSchema>)> {
+        match operation {
+            Operation::Register { subject, size } => {
+                let content = String::from_utf8(schema_of_size(size))?;
+                let input = SchemaInput::new(
+                    namespace,
+                    format!("subject_{}", subject),
+                    SerializationFormat::JsonSchema,
+                    content,
+                );
+
+                let start = Instant::now();
+                let schema = self.registry.register(input).await?;
+                Ok((start.elapsed().as_secs_f64() * 1000.0, Some(schema)))
+            }
+            Operation::Read { subject } => {
+                let schema = &subjects[subject];
+
+                let start = Instant::now();
+                let read = self.registry.get(schema.id, None).await?;
+                let elapsed = start.elapsed().as_secs_f64() * 1000.0;
+
+                anyhow::ensure!(read.content_hash == schema.content_hash, "read returned different content");
+                Ok((elapsed, None))
+            }
+            Operation::Update { subject } => {
+                let mut updated = subjects[subject].clone();
+                updated.description = format!("updated {}", updated.name);
+
+                let start = Instant::now();
+                self.registry.storage().update(updated).await?;
+                Ok((start.elapsed().as_secs_f64() * 1000.0, None))
+            }
+            Operation::CompatCheck { subject, mode } => {
+                let schema = &subjects[subject];
+                let mut input = SchemaInput::new(
+                    namespace,
+                    schema.name.clone(),
+                    SerializationFormat::JsonSchema,
+                    with_optional_property(&schema.content)?,
+                );
+                input.compatibility_mode = mode;
+
+                let start = Instant::now();
+                self.registry.check_compatibility(&input).await?;
+                Ok((start.elapsed().as_secs_f64() * 1000.0, None))
+            }
+            Operation::Sleep(duration) => {
+                tokio::time::sleep(duration).await;
+                Ok((0.0, None))
+            }
+        }
+    }
+}
+
+/// `content` with one more optional string property, the candidate of a compat-check
+fn with_optional_property(content: &str) -> Result<String> {
+    let mut schema: Value = serde_json::from_str(content).context("registered schema is not JSON")?;
+    let properties = schema
+        .get_mut("properties")
+        .and_then(Value::as_object_mut)
+        .context("registered schema has no properties")?;
+    properties.insert("workload_candidate".to_string(), json!({"type": "string"}));
+    Ok(schema.to_string())
+}
+
+/// Where a measured pass records its latencies and step wall times
+struct PassRecorder<'a> {
+    samples: &'a mut SamplePool,
+    series: [SeriesId; StepKind::MEASURED.len()],
+    wall: [Duration; StepKind::MEASURED.len()],
+}
+
+impl PassRecorder<'_> {
+    fn phase(&mut self, kind: StepKind, wall: Duration, latencies: impl Iterator<Item = f64>) {
+        let Some(index) = StepKind::MEASURED.iter().position(|measured| *measured == kind) else {
+            return;
+        };
+        self.wall[index] += wall;
+        for latency in latencies {
+            self.samples.record(self.series[index], latency);
+        }
+    }
+}
+
+#[async_trait]
+impl BenchTarget for CustomWorkloadBenchmark {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn tags(&self) -> &[&str] {
+        &["workload"]
+    }
+
+    async fn run(&self) -> Result<BenchmarkResult> {
+        self.run_with_config(&BenchConfig::default()).await
+    }
+
+    async fn run_with_config(&self, cfg: &BenchConfig) -> Result<BenchmarkResult> {
+        cfg.validate()?;
+
+        let phases = self.plan.phases();
+        let most_operations = StepKind::MEASURED
+            .iter()
+            .map(|kind| self.plan.operation_count(*kind))
+            .max()
+            .unwrap_or(0);
+
+        // Passes may stop on a time budget, so buffers grow one pass at a time
+        let mut samples = self.samples.take();
+        samples.begin_run(0);
+        samples.set_outlier_policy(cfg.outliers);
+        let series = StepKind::MEASURED.map(|kind| samples.series(kind.name()));
+        let mut recorder = PassRecorder {
+            samples: &mut samples,
+            series,
+            wall: [Duration::ZERO; StepKind::MEASURED.len()],
+        };
+
+        let start_state = self.registry.snapshot();
+        let mut iterations = cfg.iterations();
+        for i in iterations.by_ref() {
+            let warmup = cfg.is_warmup(i);
+            if !warmup {
+                recorder.samples.reserve(most_operations);
+            }
+            let pass = self
+                .run_pass(&phases, if warmup { None } else { Some(&mut recorder) })
+                .await;
+            self.registry.restore(&start_state);
+            record_iteration(i, warmup, pass?);
+        }
+
+        let wall = recorder.wall;
+        let counts = iterations.counts();
+        let mut operations = BTreeMap::new();
+        for (index, kind) in StepKind::MEASURED.iter().enumerate() {
+            let count = self.plan.operation_count(*kind) * counts.iterations;
+            if count == 0 {
+                continue;
+            }
+            let seconds = wall[index].as_secs_f64();
+            operations.insert(
+                kind.name().to_string(),
+                WorkloadOperationMetrics {
+                    latency: samples.metrics(series[index]),
+                    operations: count,
+                    throughput_ops_per_sec: if seconds > 0.0 { count as f64 / seconds } else { 0.0 },
+                },
+            );
+        }
+        let metrics = WorkloadMetrics {
+            counts,
+            seed: self.plan.seed,
+            operations,
+        };
+        let result = BenchmarkResult::new(self.id.clone(), serde_json::to_value(metrics)?)
+            .with_raw_samples(&samples)
+            .with_seed(self.plan.seed);
+        self.samples.restore(samples);
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schema_registry_core::metric_names;
+
+    const PLAN: &str = r#"
+version: 1
+name: mixed
+seed: 3
+concurrency: 2
+steps:
+  - register: { count: 4, size: 512B }
+  - read: { count: 12, keys: { zipfian: { exponent: 1.1 } } }
+  - update: { count: 3 }
+  - sleep: { duration: 1ms }
+  - compat-check: { count: 5, mode: backward }
+"#;
+
+    fn bench() -> CustomWorkloadBenchmark {
+        CustomWorkloadBenchmark::new(WorkloadPlan::parse(PLAN).unwrap())
+    }
+
+    #[test]
+    fn test_id_comes_from_plan_name() {
+        let bench = bench();
+        assert_eq!(bench.id(), "workload_mixed");
+        assert!(bench.description().contains("mixed"));
+    }
+
+    #[tokio::test]
+    async fn test_reports_latency_and_throughput_per_step_kind() {
+        let bench = bench();
+        let result = bench.run_with_config(&BenchConfig::new(1, 2)).await.unwrap();
+        let metrics: WorkloadMetrics = serde_json::from_value(result.metrics.clone()).unwrap();
+
+        assert_eq!(metrics.seed, 3);
+        assert_eq!(result.seed, Some(3));
+        assert_eq!(
+            metrics.operations.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["compat-check", "read", "register", "update"]
+        );
+        assert_eq!(metrics.operations["read"].operations, 24);
+        assert_eq!(metrics.operations["register"].operations, 8);
+        for (kind, op) in &metrics.operations {
+            assert!(op.throughput_ops_per_sec > 0.0, "{}", kind);
+            assert!(op.latency.max_ms >= op.latency.min_ms, "{}", kind);
+        }
+
+        // Every pass starts from an empty registry
+        assert!(bench.registry.storage().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_emits_catalogued_metrics() {
+        let result = bench().run_with_config(&BenchConfig::new(0, 1)).await.unwrap();
+        let operations = result.metrics["operations"].as_object().unwrap();
+        for op in operations.values() {
+            for key in op.as_object().unwrap().keys() {
+                assert!(metric_names::is_catalogued(key), "{}", key);
+            }
+        }
+        for key in ["iterations", "warmup_iterations", "seed"] {
+            assert!(metric_names::is_catalogued(key), "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_candidate_adds_optional_property() {
+        let candidate = with_optional_property(r#"{"type":"object","properties":{"id":{"type":"string"}}}"#).unwrap();
+        let candidate: Value = serde_json::from_str(&candidate).unwrap();
+        assert!(candidate["properties"]["workload_candidate"].is_object());
+        assert!(candidate.get("required").is_none());
+    }
+}
@@ -1,12 +1,226 @@
 //! Benchmark adapters for Schema Registry operations
 
 pub mod storage;
+pub mod storage_backend;
 pub mod validation;
 pub mod compatibility;
+pub mod concurrency;
+pub mod registration_log;
+pub mod reporting;
+pub mod schema_size;
+pub mod custom_workload;
 
 use async_trait::async_trait;
+use crate::metrics::IterationCounts;
+use crate::stats::OutlierPolicy;
+use storage_backend::StorageBackend;
+use crate::filter::BenchmarkFilter;
 use crate::BenchmarkResult;
 use anyhow::Result;
+use schema_registry_core::SerializationFormat;
+use std::time::{Duration, Instant};
+
+/// Default number of discarded warmup iterations
+pub const DEFAULT_WARMUP_ITERATIONS: usize = 2;
+
+/// Default number of measured iterations
+pub const DEFAULT_MEASURED_ITERATIONS: usize = 10;
+
+/// Fewest measured samples a [`SamplingMode::Time`] loop collects, however long they take
+pub const MIN_TIME_SAMPLES: usize = 5;
+
+/// Most measured samples a [`SamplingMode::Time`] loop collects
+///
+/// Sample buffers are reserved before the loop starts, so time-based loops
+/// need an upper bound too.
+pub const MAX_TIME_SAMPLES: usize = 10_000;
+
+/// How long a target keeps sampling each measured loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SamplingMode {
+    /// Exactly [`BenchConfig::measured_iterations`] samples
+    #[default]
+    Iterations,
+    /// Sample until the budget is spent, collecting between
+    /// [`MIN_TIME_SAMPLES`] and [`MAX_TIME_SAMPLES`] samples
+    ///
+    /// The budget covers measured iterations only: warmup iterations run
+    /// first, all of them, and the clock starts at the first measured one.
+    /// Targets with several measured loops give each loop the full budget.
+    Time(Duration),
+}
+
+/// Iteration counts for a single target run
+///
+/// Warmup iterations run the same operations as measured ones, but their
+/// samples are discarded so cold caches and lazy initialisation don't skew
+/// the reported minimums.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchConfig {
+    /// Iterations run first and discarded
+    pub warmup_iterations: usize,
+    /// Iterations whose samples are reported; at least 1
+    ///
+    /// Ignored in [`SamplingMode::Time`].
+    pub measured_iterations: usize,
+    /// Whether measured loops stop after a count or a time budget
+    pub sampling: SamplingMode,
+    /// Samples left out of the reported statistics
+    pub outliers: OutlierPolicy,
+    /// Seed for generated inputs, e.g. a [`SchemaWorkloadGenerator`](crate::SchemaWorkloadGenerator)
+    ///
+    /// The runner records it in every result as [`BenchmarkResult::seed`]
+    /// unless the target recorded a seed of its own.
+    pub seed: u64,
+    /// Where `storage_operations` persists schemas
+    pub storage_backend: StorageBackend,
+}
+
+impl BenchConfig {
+    pub fn new(warmup_iterations: usize, measured_iterations: usize) -> Self {
+        Self {
+            warmup_iterations,
+            measured_iterations,
+            sampling: SamplingMode::Iterations,
+            outliers: OutlierPolicy::None,
+            seed: 0,
+            storage_backend: StorageBackend::Memory,
+        }
+    }
+
+    /// Sample for `budget` per measured loop instead of a fixed count
+    pub fn with_sample_time(mut self, budget: Duration) -> Self {
+        self.sampling = SamplingMode::Time(budget);
+        self
+    }
+
+    /// Drop the samples `outliers` rejects before computing statistics
+    pub fn with_outliers(mut self, outliers: OutlierPolicy) -> Self {
+        self.outliers = outliers;
+        self
+    }
+
+    /// Generate inputs from `seed`
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Persist the storage benchmark's schemas in `backend`
+    pub fn with_storage_backend(mut self, backend: StorageBackend) -> Self {
+        self.storage_backend = backend;
+        self
+    }
+
+    /// Warmup plus measured iterations, in [`SamplingMode::Iterations`]
+    pub fn total_iterations(&self) -> usize {
+        self.warmup_iterations + self.measured_iterations
+    }
+
+    /// Most measured samples one loop can collect; reserve sample buffers for this many
+    pub fn sample_capacity(&self) -> usize {
+        match self.sampling {
+            SamplingMode::Iterations => self.measured_iterations,
+            SamplingMode::Time(_) => MAX_TIME_SAMPLES,
+        }
+    }
+
+    /// Reject configurations that would produce no samples
+    pub fn validate(&self) -> Result<()> {
+        match self.sampling {
+            SamplingMode::Iterations => anyhow::ensure!(
+                self.measured_iterations >= 1,
+                "measured iterations must be at least 1, got {}",
+                self.measured_iterations
+            ),
+            SamplingMode::Time(budget) => {
+                anyhow::ensure!(!budget.is_zero(), "sample time must be greater than zero")
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the zero-based `iteration` is a warmup iteration
+    pub fn is_warmup(&self, iteration: usize) -> bool {
+        iteration < self.warmup_iterations
+    }
+
+    /// Zero-based iteration numbers of one sampling loop, warmup first
+    ///
+    /// ```ignore
+    /// let mut iterations = cfg.iterations();
+    /// for i in iterations.by_ref() {
+    ///     let ms = time_operation();
+    ///     if !cfg.is_warmup(i) {
+    ///         samples.record(series, ms);
+    ///     }
+    /// }
+    /// let counts = iterations.counts();
+    /// ```
+    pub fn iterations(&self) -> SampleLoop {
+        SampleLoop {
+            cfg: *self,
+            next: 0,
+            measuring_since: None,
+        }
+    }
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_WARMUP_ITERATIONS, DEFAULT_MEASURED_ITERATIONS)
+    }
+}
+
+/// Iterator over the iterations of one sampling loop; see [`BenchConfig::iterations`]
+#[derive(Debug, Clone)]
+pub struct SampleLoop {
+    cfg: BenchConfig,
+    next: usize,
+    /// When the first measured iteration started, in [`SamplingMode::Time`]
+    measuring_since: Option<Instant>,
+}
+
+impl SampleLoop {
+    /// Measured iterations handed out so far
+    pub fn measured(&self) -> usize {
+        self.next.saturating_sub(self.cfg.warmup_iterations)
+    }
+
+    /// Counts to report once the loop is done
+    pub fn counts(&self) -> IterationCounts {
+        IterationCounts {
+            warmup_iterations: self.next.min(self.cfg.warmup_iterations),
+            iterations: self.measured(),
+            sample_time_ms: match self.cfg.sampling {
+                SamplingMode::Iterations => None,
+                SamplingMode::Time(budget) => Some(budget.as_millis() as u64),
+            },
+        }
+    }
+}
+
+impl Iterator for SampleLoop {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.next >= self.cfg.warmup_iterations {
+            let measured = self.measured();
+            let more = match self.cfg.sampling {
+                SamplingMode::Iterations => measured < self.cfg.measured_iterations,
+                SamplingMode::Time(budget) => {
+                    let since = *self.measuring_since.get_or_insert_with(Instant::now);
+                    measured < MIN_TIME_SAMPLES || (measured < MAX_TIME_SAMPLES && since.elapsed() < budget)
+                }
+            };
+            if !more {
+                return None;
+            }
+        }
+        self.next += 1;
+        Some(self.next - 1)
+    }
+}
 
 /// Trait for benchmark targets
 ///
@@ -20,8 +234,69 @@ pub trait BenchTarget: Send + Sync {
     /// Human-readable description of what this benchmark measures
     fn description(&self) -> &str;
 
+    /// Prepare fixtures before [`run_with_config`](Self::run_with_config)
+    ///
+    /// The runner calls this before each run and reports its duration as
+    /// [`BenchmarkResult::setup_ms`]; it is not part of the measured metrics.
+    /// An error fails the target without running it.
+    async fn setup(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Release what [`setup`](Self::setup) created
+    ///
+    /// The runner calls this after every run that reached `setup`, including
+    /// runs that failed or timed out.
+    async fn teardown(&self) -> Result<()> {
+        Ok(())
+    }
+
     /// Run the benchmark and return results
     async fn run(&self) -> Result<BenchmarkResult>;
+
+    /// Run the benchmark with explicit iteration counts
+    ///
+    /// The default ignores `cfg` and calls [`run`](Self::run), so existing
+    /// targets keep working. The built-in targets override it, discard warmup
+    /// samples and report both counts in their metrics.
+    async fn run_with_config(&self, cfg: &BenchConfig) -> Result<BenchmarkResult> {
+        let _ = cfg;
+        self.run().await
+    }
+
+    /// Version of the target's metrics layout
+    ///
+    /// Bump it when renaming, removing or retyping a metric key, so
+    /// [`shape::compare`](crate::shape::compare) reports the change as
+    /// intended rather than as drift.
+    fn version(&self) -> u32 {
+        1
+    }
+
+    /// Ids of targets that must run before this one
+    ///
+    /// Sharding keeps a target on the same shard as the targets it runs after
+    /// and orders it behind them; see [`crate::shard`].
+    fn run_after(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Labels for selecting groups of targets, e.g. `fast` or `slow`
+    ///
+    /// See [`BenchmarkFilter::with_tag`](crate::BenchmarkFilter::with_tag).
+    fn tags(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Schema formats the target measures separately
+    ///
+    /// A target declaring formats reports each one's metrics in an object
+    /// under [`BY_FORMAT_KEY`](crate::metrics::BY_FORMAT_KEY), keyed by
+    /// [`format_key`](crate::metrics::format_key); see
+    /// [`BenchmarkResult::metrics_for_format`].
+    fn formats(&self) -> &[SerializationFormat] {
+        &[]
+    }
 }
 
 /// Get all registered benchmark targets
@@ -29,22 +304,82 @@ pub trait BenchTarget: Send + Sync {
 /// This function returns a collection of all available benchmark targets
 /// that can be executed.
 pub fn all_targets() -> Vec<Box<dyn BenchTarget>> {
-    vec![
+    let targets: Vec<Box<dyn BenchTarget>> = vec![
         Box::new(storage::StorageBenchmark::new()),
         Box::new(validation::ValidationBenchmark::new()),
         Box::new(compatibility::CompatibilityBenchmark::new()),
-    ]
+        Box::new(registration_log::RegistrationLogBenchmark::new()),
+        Box::new(reporting::ReportingBenchmark::new()),
+        Box::new(concurrency::ConcurrencyBenchmark::new()),
+        Box::new(schema_size::SchemaSizeBenchmark::new()),
+    ];
+    #[cfg(feature = "auto-register")]
+    let targets = crate::auto_register::append_registered(targets);
+    targets
+}
+
+/// Built-in targets left out of [`all_targets`] because they take far longer than the rest
+///
+/// They run only when a filter asks for them by id, pattern or tag; see [`with_extended`].
+pub fn extended_targets() -> Vec<Box<dyn BenchTarget>> {
+    vec![Box::new(schema_size::SchemaSizeBenchmark::large())]
+}
+
+/// `targets` plus the [`extended_targets`] that `filter` includes by id, pattern or tag
+///
+/// A filter without include patterns or include tags adds nothing, so the
+/// default run stays at [`all_targets`].
+pub fn with_extended(mut targets: Vec<Box<dyn BenchTarget>>, filter: &BenchmarkFilter) -> Vec<Box<dyn BenchTarget>> {
+    if filter.include.is_empty() && filter.include_tags.is_empty() {
+        return targets;
+    }
+    for target in extended_targets() {
+        if filter.selects(target.as_ref()) && !targets.iter().any(|t| t.id() == target.id()) {
+            targets.push(target);
+        }
+    }
+    targets
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use schema_registry_core::metric_names;
+    use serde_json::Value;
 
     #[test]
     fn test_all_targets_returns_targets() {
         let targets = all_targets();
         assert!(!targets.is_empty());
-        assert_eq!(targets.len(), 3);
+        assert_eq!(targets.len(), 7);
+        assert!(targets.iter().all(|t| t.id() != "schema_size_large"));
+    }
+
+    #[test]
+    fn test_with_extended_adds_targets_the_filter_asks_for() {
+        let ids = |filter: &BenchmarkFilter| -> Vec<String> {
+            with_extended(all_targets(), filter).iter().map(|t| t.id().to_string()).collect()
+        };
+
+        assert_eq!(ids(&BenchmarkFilter::new()).len(), all_targets().len());
+        assert!(!ids(&BenchmarkFilter::new().with_exclude("storage")).contains(&"schema_size_large".to_string()));
+        assert!(!ids(&BenchmarkFilter::new().with_include("storage")).contains(&"schema_size_large".to_string()));
+        assert!(ids(&BenchmarkFilter::new().with_include("schema_size_large")).contains(&"schema_size_large".to_string()));
+        assert!(ids(&BenchmarkFilter::new().with_include("schema_size*")).contains(&"schema_size_large".to_string()));
+        assert!(ids(&BenchmarkFilter::new().with_tag("slow")).contains(&"schema_size_large".to_string()));
+
+        let selected = BenchmarkFilter::new().with_include("schema_size*");
+        let targets = selected.apply(with_extended(all_targets(), &selected)).unwrap();
+        let ids: Vec<&str> = targets.iter().map(|t| t.id()).collect();
+        assert_eq!(ids, vec!["schema_size", "schema_size_large"]);
+    }
+
+    #[test]
+    fn test_extended_targets_are_distinct_from_all_targets() {
+        let defaults = all_targets();
+        for target in extended_targets() {
+            assert!(defaults.iter().all(|t| t.id() != target.id()), "{} is in all_targets", target.id());
+        }
     }
 
     #[test]
@@ -60,6 +395,19 @@ mod tests {
         assert_eq!(ids.len(), sorted_ids.len(), "Benchmark target IDs must be unique");
     }
 
+    #[test]
+    fn test_all_targets_are_tagged_fast_or_slow() {
+        for target in all_targets() {
+            let tags = target.tags();
+            assert!(
+                tags.contains(&"fast") != tags.contains(&"slow"),
+                "{} must be tagged exactly one of fast/slow, has {:?}",
+                target.id(),
+                tags
+            );
+        }
+    }
+
     #[test]
     fn test_all_targets_have_descriptions() {
         let targets = all_targets();
@@ -69,4 +417,108 @@ mod tests {
             assert!(!target.description().is_empty(), "Target description should not be empty");
         }
     }
+
+    /// Collect leaf keys; nested objects other than measurements are per-operation groups,
+    /// and arrays lists of them, not metrics
+    fn metric_keys<'a>(metrics: &'a Value, keys: &mut Vec<&'a str>) {
+        if let Value::Object(map) = metrics {
+            for (key, value) in map {
+                match value {
+                    Value::Object(_) if !crate::units::is_measurement(value) => metric_keys(value, keys),
+                    Value::Array(items) => items.iter().for_each(|item| metric_keys(item, keys)),
+                    _ => keys.push(key),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bench_config_warmup_window() {
+        let cfg = BenchConfig::new(2, 3);
+        assert_eq!(cfg.total_iterations(), 5);
+        assert!(cfg.is_warmup(1));
+        assert!(!cfg.is_warmup(2));
+
+        let mut iterations = cfg.iterations();
+        assert_eq!(iterations.by_ref().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(iterations.counts().iterations, 3);
+        assert_eq!(iterations.counts().sample_time_ms, None);
+    }
+
+    #[test]
+    fn test_time_sampling_collects_at_least_the_minimum() {
+        // A budget too short for any sample still yields MIN_TIME_SAMPLES
+        let cfg = BenchConfig::new(2, 1).with_sample_time(Duration::from_nanos(1));
+        let mut iterations = cfg.iterations();
+        let all: Vec<usize> = iterations.by_ref().collect();
+        assert_eq!(all.len(), 2 + MIN_TIME_SAMPLES);
+        assert_eq!(iterations.counts().warmup_iterations, 2);
+        assert_eq!(iterations.counts().iterations, MIN_TIME_SAMPLES);
+        assert_eq!(iterations.counts().sample_time_ms, Some(0));
+    }
+
+    #[test]
+    fn test_time_sampling_runs_until_budget() {
+        let budget = Duration::from_millis(30);
+        let cfg = BenchConfig::new(0, 1).with_sample_time(budget);
+        let start = Instant::now();
+        let mut iterations = cfg.iterations();
+        for _ in iterations.by_ref() {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(start.elapsed() >= budget);
+        assert!(iterations.counts().iterations > MIN_TIME_SAMPLES);
+        assert!(iterations.counts().iterations <= MAX_TIME_SAMPLES);
+
+        assert!(BenchConfig::new(0, 0).with_sample_time(Duration::ZERO).validate().is_err());
+        assert!(BenchConfig::new(0, 0).with_sample_time(budget).validate().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_targets_report_configured_iterations() {
+        let cfg = BenchConfig::new(1, 3);
+        for target in all_targets() {
+            let result = target.run_with_config(&cfg).await.unwrap();
+            assert_eq!(result.metrics["warmup_iterations"]["value"], 1, "{}", target.id());
+            assert_eq!(result.metrics["iterations"]["value"], 3, "{}", target.id());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_time_sampling_reports_samples_collected() {
+        let cfg = BenchConfig::new(1, 1).with_sample_time(Duration::from_millis(20));
+        let result = validation::ValidationBenchmark::new().run_with_config(&cfg).await.unwrap();
+
+        assert_eq!(result.metrics["warmup_iterations"]["value"], 1);
+        assert_eq!(result.metrics["sample_time_ms"], serde_json::json!({"value": 20, "unit": "milliseconds"}));
+        assert!(result.metrics["iterations"]["value"].as_u64().unwrap() >= MIN_TIME_SAMPLES as u64);
+    }
+
+    #[tokio::test]
+    async fn test_targets_reject_zero_iterations() {
+        let cfg = BenchConfig::new(1, 0);
+        assert!(cfg.validate().is_err());
+        for target in all_targets() {
+            assert!(target.run_with_config(&cfg).await.is_err(), "{}", target.id());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_all_targets_emit_catalogued_metrics() {
+        for target in all_targets() {
+            let result = target.run().await.unwrap();
+            let mut keys = Vec::new();
+            metric_keys(&result.metrics, &mut keys);
+
+            assert!(!keys.is_empty(), "{} emitted no metrics", target.id());
+            for key in keys {
+                assert!(
+                    metric_names::is_catalogued(key),
+                    "{} emitted {} which is not in metric_names::catalog()",
+                    target.id(),
+                    key
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,165 @@
+//! Registration log overhead benchmarks
+//!
+//! Registers the same sequence of new subjects into two embedded registries,
+//! one without a registration log and one appending to a log in a temporary
+//! directory, and reports how much slower the logged registrations are.
+
+use super::{BenchConfig, BenchTarget};
+use crate::metrics::RegistrationLogMetrics;
+use crate::observer::record_iteration;
+use crate::sampling::PoolSlot;
+use crate::BenchmarkResult;
+use anyhow::Result;
+use async_trait::async_trait;
+use schema_registry_core::embedded::EmbeddedRegistry;
+use schema_registry_core::registration_log::{RegistrationLog, RegistrationLogConfig};
+use schema_registry_core::{SchemaInput, SerializationFormat};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Seed for both registries' id generators, so they store identical data
+const REGISTRY_SEED: u64 = 0x10C;
+
+/// Sample series recorded per measured iteration
+pub(crate) const SERIES: [&str; 2] = ["unlogged", "logged"];
+
+/// Benchmark for the cost of the registration log
+pub struct RegistrationLogBenchmark {
+    samples: PoolSlot,
+}
+
+impl RegistrationLogBenchmark {
+    /// Create a new registration log benchmark
+    pub fn new() -> Self {
+        Self {
+            samples: PoolSlot::new(),
+        }
+    }
+
+    /// Register subject `n` and return the elapsed milliseconds
+    async fn bench_register(&self, registry: &EmbeddedRegistry, n: usize) -> Result<f64> {
+        let content = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "string"},
+                format!("field_{}", n): {"type": "integer"}
+            }
+        });
+        let input = SchemaInput::new(
+            "bench.registration_log",
+            format!("subject_{}", n),
+            SerializationFormat::JsonSchema,
+            content.to_string(),
+        );
+
+        let start = Instant::now();
+        registry.register(input).await?;
+        Ok(start.elapsed().as_secs_f64() * 1000.0) // Convert to milliseconds
+    }
+}
+
+impl Default for RegistrationLogBenchmark {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Removes the log directory when the run ends, including on error
+struct TempDir(PathBuf);
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[async_trait]
+impl BenchTarget for RegistrationLogBenchmark {
+    fn id(&self) -> &str {
+        "registration_log_overhead"
+    }
+
+    fn description(&self) -> &str {
+        "Overhead of appending each registration to the replayable registration log"
+    }
+
+    fn tags(&self) -> &[&str] {
+        &["storage", "io", "slow"]
+    }
+
+    async fn run(&self) -> Result<BenchmarkResult> {
+        self.run_with_config(&BenchConfig::default()).await
+    }
+
+    async fn run_with_config(&self, cfg: &BenchConfig) -> Result<BenchmarkResult> {
+        cfg.validate()?;
+
+        let dir = TempDir(std::env::temp_dir().join(format!("registration-log-bench-{}", uuid::Uuid::new_v4())));
+        let log = RegistrationLog::open(RegistrationLogConfig::new(dir.0.join("reg.wal")))?;
+        let unlogged = EmbeddedRegistry::builder().with_seed(REGISTRY_SEED).build();
+        let logged = EmbeddedRegistry::builder()
+            .with_seed(REGISTRY_SEED)
+            .with_registration_log(Arc::new(log))
+            .build();
+
+        let mut samples = self.samples.take();
+        samples.begin_run(cfg.sample_capacity());
+        samples.set_outlier_policy(cfg.outliers);
+        let [unlogged_series, logged_series] = SERIES.map(|label| samples.series(label));
+
+        let mut iterations = cfg.iterations();
+        for i in iterations.by_ref() {
+            let unlogged_ms = self.bench_register(&unlogged, i).await?;
+            let logged_ms = self.bench_register(&logged, i).await?;
+            record_iteration(i, cfg.is_warmup(i), unlogged_ms + logged_ms);
+            if cfg.is_warmup(i) {
+                continue;
+            }
+            samples.record(unlogged_series, unlogged_ms);
+            samples.record(logged_series, logged_ms);
+        }
+
+        let unlogged = samples.metrics(unlogged_series);
+        let logged = samples.metrics(logged_series);
+        let overhead_percent = if unlogged.avg_ms > 0.0 {
+            (logged.avg_ms / unlogged.avg_ms - 1.0) * 100.0
+        } else {
+            0.0
+        };
+        let metrics = RegistrationLogMetrics {
+            counts: iterations.counts(),
+            unlogged,
+            logged,
+            overhead_percent,
+        };
+
+        let result = BenchmarkResult::new(self.id().to_string(), serde_json::to_value(metrics)?)
+            .with_raw_samples(&samples);
+        self.samples.restore(samples);
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registration_log_benchmark_new() {
+        let bench = RegistrationLogBenchmark::new();
+        assert_eq!(bench.id(), "registration_log_overhead");
+        assert!(bench.description().contains("registration log"));
+    }
+
+    #[tokio::test]
+    async fn test_registration_log_benchmark_run() {
+        let result = RegistrationLogBenchmark::new().run().await.unwrap();
+
+        assert_eq!(result.target_id, "registration_log_overhead");
+        assert!(result.metrics["unlogged"].get("p95_ms").is_some());
+        assert!(result.metrics["logged"].get("p95_ms").is_some());
+        assert!(result.metrics["overhead_percent"]["value"].is_f64());
+    }
+}
@@ -0,0 +1,201 @@
+//! Report generation and result I/O benchmarks
+//!
+//! Times the crate's own reporting path over synthetic result sets of
+//! [`RESULT_SET_SIZES`] entries: rendering the markdown summary and table,
+//! and writing and reading the results as JSON through a temporary file.
+
+use super::{BenchConfig, BenchTarget};
+use crate::metrics::{OperationMetrics, ReportingMetrics, ReportingOperationMetrics};
+use crate::observer::record_iteration;
+use crate::sampling::{PoolSlot, SeriesId};
+use crate::{io, markdown, BenchmarkResult};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Number of results in each synthetic result set
+pub const RESULT_SET_SIZES: [usize; 3] = [10, 100, 1000];
+
+/// Operations timed per result set, in series order
+pub(crate) const OPERATIONS: [&str; 4] = ["generate_summary", "generate_table", "write_json", "read_json"];
+
+/// Benchmark for report generation and result file I/O
+pub struct ReportingBenchmark {
+    samples: PoolSlot,
+}
+
+impl ReportingBenchmark {
+    /// Create a new reporting benchmark
+    pub fn new() -> Self {
+        Self {
+            samples: PoolSlot::new(),
+        }
+    }
+
+    /// Time each reporting operation over `results`, in milliseconds, in [`OPERATIONS`] order
+    fn bench_result_set(&self, results: &[BenchmarkResult], path: &Path) -> Result<[f64; OPERATIONS.len()]> {
+        let start = Instant::now();
+        std::hint::black_box(markdown::generate_summary(results));
+        let summary_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let start = Instant::now();
+        std::hint::black_box(markdown::generate_table(results));
+        let table_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let start = Instant::now();
+        io::write_json(results, path)?;
+        let write_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let start = Instant::now();
+        std::hint::black_box(io::read_json(path)?);
+        let read_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        Ok([summary_ms, table_ms, write_ms, read_ms])
+    }
+}
+
+impl Default for ReportingBenchmark {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `count` results shaped like the built-in targets', with varying timings
+pub fn synthetic_results(count: usize) -> Vec<BenchmarkResult> {
+    (0..count)
+        .map(|i| {
+            let base = 0.05 + (i % 17) as f64 * 0.01;
+            let samples: Vec<f64> = (0..10).map(|n| base + n as f64 * 0.002).collect();
+            let operation = OperationMetrics::from_samples(&samples);
+            let metrics = json!({
+                "iterations": {"value": samples.len(), "unit": "count"},
+                "warmup_iterations": {"value": 2, "unit": "count"},
+                "write": operation,
+                "read": OperationMetrics::from_samples(&samples[..5]),
+                "update": operation,
+            });
+            BenchmarkResult::new(format!("synthetic_{:04}", i), metrics)
+        })
+        .collect()
+}
+
+/// Removes the results file when the run ends, including on error
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[async_trait]
+impl BenchTarget for ReportingBenchmark {
+    fn id(&self) -> &str {
+        "report_generation"
+    }
+
+    fn description(&self) -> &str {
+        "Markdown summary and table generation and JSON result I/O over 10, 100 and 1000 results"
+    }
+
+    fn tags(&self) -> &[&str] {
+        &["reporting", "io", "slow"]
+    }
+
+    async fn run(&self) -> Result<BenchmarkResult> {
+        self.run_with_config(&BenchConfig::default()).await
+    }
+
+    async fn run_with_config(&self, cfg: &BenchConfig) -> Result<BenchmarkResult> {
+        cfg.validate()?;
+
+        let file = TempFile(std::env::temp_dir().join(format!("reporting-bench-{}.json", uuid::Uuid::new_v4())));
+        let result_sets: Vec<(String, Vec<BenchmarkResult>)> = RESULT_SET_SIZES
+            .iter()
+            .map(|&size| (size.to_string(), synthetic_results(size)))
+            .collect();
+
+        let mut samples = self.samples.take();
+        samples.begin_run(cfg.sample_capacity());
+        samples.set_outlier_policy(cfg.outliers);
+        let series: Vec<[SeriesId; OPERATIONS.len()]> = result_sets
+            .iter()
+            .map(|(size, _)| OPERATIONS.map(|operation| samples.series_path(&[size.as_str(), operation])))
+            .collect();
+
+        let mut iterations = cfg.iterations();
+        for i in iterations.by_ref() {
+            let mut timings = [[0.0; OPERATIONS.len()]; RESULT_SET_SIZES.len()];
+            for ((_, results), timing) in result_sets.iter().zip(&mut timings) {
+                *timing = self.bench_result_set(results, &file.0)?;
+            }
+            record_iteration(i, cfg.is_warmup(i), timings.iter().flatten().sum());
+            if cfg.is_warmup(i) {
+                continue;
+            }
+            for (ids, timings) in series.iter().zip(&timings) {
+                for (id, ms) in ids.iter().zip(timings) {
+                    samples.record(*id, *ms);
+                }
+            }
+        }
+
+        let results: BTreeMap<String, ReportingOperationMetrics> = result_sets
+            .iter()
+            .zip(&series)
+            .map(|((size, _), [summary, table, write, read])| {
+                let operations = ReportingOperationMetrics {
+                    generate_summary: samples.metrics(*summary),
+                    generate_table: samples.metrics(*table),
+                    write_json: samples.metrics(*write),
+                    read_json: samples.metrics(*read),
+                };
+                (size.clone(), operations)
+            })
+            .collect();
+        let metrics = ReportingMetrics {
+            counts: iterations.counts(),
+            results,
+        };
+        let result = BenchmarkResult::new(self.id().to_string(), serde_json::to_value(metrics)?)
+            .with_raw_samples(&samples);
+        self.samples.restore(samples);
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthetic_results_render_and_round_trip() {
+        let results = synthetic_results(3);
+        assert_eq!(results[2].target_id, "synthetic_0002");
+        assert!(markdown::generate_table(&results).contains("synthetic_0001"));
+        assert!(results[0].metrics["write"]["avg_ms"]["value"].as_f64().unwrap() > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_reports_every_operation_per_result_set() {
+        let bench = ReportingBenchmark::new();
+        let result = bench.run_with_config(&BenchConfig::new(0, 2)).await.unwrap();
+        assert_eq!(result.target_id, "report_generation");
+
+        let sizes: Vec<_> = result.metrics["results"].as_object().unwrap().keys().cloned().collect();
+        assert_eq!(sizes, vec!["10", "100", "1000"]);
+        for size in sizes {
+            for operation in OPERATIONS {
+                let metrics = &result.metrics["results"][&size][operation];
+                for key in ["avg_ms", "min_ms", "max_ms", "p50_ms", "p95_ms", "p99_ms"] {
+                    assert!(metrics.get(key).is_some(), "{size}.{operation} lacks {key}");
+                }
+                assert!(metrics["max_ms"]["value"].as_f64().unwrap() > 0.0, "{size}.{operation}");
+            }
+        }
+    }
+}
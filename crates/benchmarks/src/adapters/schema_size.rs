@@ -0,0 +1,293 @@
+//! Validation latency by schema size
+//!
+//! Validates generated JSON Schema, Avro and Protobuf schemas of growing
+//! size and reports latency and bytes processed per second for each size
+//! bucket and format. Throughput that drops from one bucket to the next
+//! points at validation work that grows faster than the schema.
+//!
+//! Schemas come from a [`SchemaWorkloadGenerator`] with a fixed seed, made
+//! as large as fits in the bucket. Buckets are capped at the engine's
+//! [`max_schema_size`](ValidationConfig::max_schema_size), so no bucket
+//! measures the size check rejecting the schema instead of validation.
+//!
+//! The 1MB bucket is a target of its own, [`SchemaSizeBenchmark::large`],
+//! tagged `slow`. It is one of the [`extended_targets`](super::extended_targets),
+//! so it runs only when a filter names it or includes the `slow` tag.
+
+use super::{BenchConfig, BenchTarget};
+use crate::metrics::{SchemaSizeFormatMetrics, SchemaSizeMetrics};
+use crate::observer::record_iteration;
+use crate::sampling::{PoolSlot, SeriesId};
+use crate::workload::SchemaWorkloadGenerator;
+use crate::BenchmarkResult;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use schema_registry_core::SerializationFormat;
+use schema_registry_validation::engine::ValidationEngine;
+use schema_registry_validation::types::ValidationConfig;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+/// Size buckets of [`SchemaSizeBenchmark::new`]: label and size in bytes
+pub const SIZE_BUCKETS: [(&str, usize); 2] = [("1kb", 1024), ("100kb", 100 * 1024)];
+
+/// Size bucket of [`SchemaSizeBenchmark::large`]
+pub const LARGE_SIZE_BUCKET: (&str, usize) = ("1mb", 1024 * 1024);
+
+/// Formats validated in every bucket, with their metrics keys
+const FORMATS: [(&str, SerializationFormat); 3] = [
+    ("json_schema", SerializationFormat::JsonSchema),
+    ("avro", SerializationFormat::Avro),
+    ("protobuf", SerializationFormat::Protobuf),
+];
+
+/// Seed of the generated schemas, so every run validates the same text
+const SCHEMA_SEED: u64 = 0x51CE;
+
+/// A generated schema and the bucket it was sized for
+struct SizedSchema {
+    bucket: &'static str,
+    format: &'static str,
+    serialization: SerializationFormat,
+    content: String,
+}
+
+/// Benchmark for validation latency and throughput by schema size
+pub struct SchemaSizeBenchmark {
+    id: &'static str,
+    description: &'static str,
+    buckets: Vec<(&'static str, usize)>,
+    tags: &'static [&'static str],
+    config: ValidationConfig,
+    schemas: OnceLock<Vec<SizedSchema>>,
+    samples: PoolSlot,
+}
+
+impl SchemaSizeBenchmark {
+    /// The `schema_size` target over [`SIZE_BUCKETS`]
+    pub fn new() -> Self {
+        Self {
+            id: "schema_size",
+            description: "Validation latency and bytes per second of 1KB and 100KB JSON Schema, Avro and Protobuf schemas",
+            buckets: SIZE_BUCKETS.to_vec(),
+            tags: &["validation", "size", "fast"],
+            config: ValidationConfig::default(),
+            schemas: OnceLock::new(),
+            samples: PoolSlot::new(),
+        }
+    }
+
+    /// The `schema_size_large` target over [`LARGE_SIZE_BUCKET`], tagged `slow`
+    pub fn large() -> Self {
+        Self {
+            id: "schema_size_large",
+            description: "Validation latency and bytes per second of 1MB JSON Schema, Avro and Protobuf schemas",
+            buckets: vec![LARGE_SIZE_BUCKET],
+            tags: &["validation", "size", "slow"],
+            ..Self::new()
+        }
+    }
+
+    /// Validate with `config` instead of the default; its
+    /// [`max_schema_size`](ValidationConfig::max_schema_size) caps the buckets
+    pub fn with_config(mut self, config: ValidationConfig) -> Self {
+        self.config = config;
+        self.schemas = OnceLock::new();
+        self
+    }
+
+    /// Size the schemas of `bucket` are generated to fit in
+    pub fn bucket_limit(&self, bucket: usize) -> usize {
+        bucket.min(self.config.max_schema_size)
+    }
+
+    /// One schema per bucket and format, generated on first use
+    fn schemas(&self) -> Result<&[SizedSchema]> {
+        if let Some(schemas) = self.schemas.get() {
+            return Ok(schemas);
+        }
+        let mut schemas = Vec::with_capacity(self.buckets.len() * FORMATS.len());
+        for &(bucket, size) in &self.buckets {
+            let limit = self.bucket_limit(size);
+            for (format, serialization) in FORMATS {
+                let content = schema_within(serialization, limit)
+                    .ok_or_else(|| anyhow!("no generated {} schema fits in {} bytes", format, limit))?;
+                schemas.push(SizedSchema {
+                    bucket,
+                    format,
+                    serialization,
+                    content,
+                });
+            }
+        }
+        Ok(self.schemas.get_or_init(|| schemas))
+    }
+}
+
+impl Default for SchemaSizeBenchmark {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The largest generated schema in `format` of at most `max_bytes` bytes
+///
+/// Searches over the field count of a fresh generator with the fixed seed,
+/// so the same limit always gives the same schema.
+fn schema_within(format: SerializationFormat, max_bytes: usize) -> Option<String> {
+    let render = |fields: usize| {
+        SchemaWorkloadGenerator::new(SCHEMA_SEED)
+            .with_fields(fields)
+            .generate(format)
+    };
+    if render(1).len() > max_bytes {
+        return None;
+    }
+    // Invariant: `fits` fits and `overflows` doesn't
+    let (mut fits, mut overflows) = (1, 2);
+    while render(overflows).len() <= max_bytes {
+        fits = overflows;
+        overflows *= 2;
+    }
+    while overflows - fits > 1 {
+        let mid = fits + (overflows - fits) / 2;
+        if render(mid).len() <= max_bytes {
+            fits = mid;
+        } else {
+            overflows = mid;
+        }
+    }
+    Some(render(fits))
+}
+
+#[async_trait]
+impl BenchTarget for SchemaSizeBenchmark {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn description(&self) -> &str {
+        self.description
+    }
+
+    fn tags(&self) -> &[&str] {
+        self.tags
+    }
+
+    async fn run(&self) -> Result<BenchmarkResult> {
+        self.run_with_config(&BenchConfig::default()).await
+    }
+
+    async fn run_with_config(&self, cfg: &BenchConfig) -> Result<BenchmarkResult> {
+        cfg.validate()?;
+
+        let engine = ValidationEngine::with_config(self.config.clone());
+        let schemas = self.schemas()?;
+
+        let mut samples = self.samples.take();
+        samples.begin_run(cfg.sample_capacity());
+        samples.set_outlier_policy(cfg.outliers);
+        let series: Vec<SeriesId> = schemas
+            .iter()
+            .map(|schema| samples.series_path(&[schema.bucket, schema.format]))
+            .collect();
+
+        let mut iterations = cfg.iterations();
+        for i in iterations.by_ref() {
+            let mut timings = Vec::with_capacity(schemas.len());
+            for schema in schemas {
+                let start = Instant::now();
+                std::hint::black_box(engine.validate(&schema.content, schema.serialization.into()).await?);
+                timings.push(start.elapsed().as_secs_f64() * 1000.0); // Convert to milliseconds
+            }
+            record_iteration(i, cfg.is_warmup(i), timings.iter().sum());
+            if cfg.is_warmup(i) {
+                continue;
+            }
+            for (id, ms) in series.iter().zip(timings) {
+                samples.record(*id, ms);
+            }
+        }
+
+        let mut sizes: BTreeMap<String, BTreeMap<String, SchemaSizeFormatMetrics>> = BTreeMap::new();
+        for (schema, id) in schemas.iter().zip(&series) {
+            let latency = samples.metrics(*id);
+            let schema_bytes = schema.content.len();
+            let bytes_per_sec = if latency.avg_ms > 0.0 {
+                schema_bytes as f64 / (latency.avg_ms / 1000.0)
+            } else {
+                0.0
+            };
+            sizes.entry(schema.bucket.to_string()).or_default().insert(
+                schema.format.to_string(),
+                SchemaSizeFormatMetrics {
+                    schema_bytes,
+                    bytes_per_sec,
+                    latency,
+                },
+            );
+        }
+        let metrics = SchemaSizeMetrics {
+            counts: iterations.counts(),
+            sizes,
+        };
+        let result = BenchmarkResult::new(self.id().to_string(), serde_json::to_value(metrics)?)
+            .with_raw_samples(&samples);
+        self.samples.restore(samples);
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schemas_fill_but_fit_their_bucket() {
+        for (_, size) in SIZE_BUCKETS {
+            for (format, serialization) in FORMATS {
+                let schema = schema_within(serialization, size).unwrap();
+                assert!(schema.len() <= size, "{format}: {} > {size}", schema.len());
+                assert!(schema.len() > size / 2, "{format}: {} of {size}", schema.len());
+                assert_eq!(schema_within(serialization, size).unwrap(), schema);
+            }
+        }
+        assert_eq!(schema_within(SerializationFormat::Avro, 16), None);
+    }
+
+    #[test]
+    fn test_buckets_respect_max_schema_size() {
+        let config = ValidationConfig {
+            max_schema_size: 4096,
+            ..ValidationConfig::default()
+        };
+        let bench = SchemaSizeBenchmark::large().with_config(config);
+        assert_eq!(bench.bucket_limit(LARGE_SIZE_BUCKET.1), 4096);
+        assert!(bench.schemas().unwrap().iter().all(|schema| schema.content.len() <= 4096));
+        assert_eq!(SchemaSizeBenchmark::large().bucket_limit(LARGE_SIZE_BUCKET.1), 1024 * 1024);
+    }
+
+    #[tokio::test]
+    async fn test_reports_each_bucket_and_format() {
+        let bench = SchemaSizeBenchmark::new();
+        let result = bench.run_with_config(&BenchConfig::new(0, 2)).await.unwrap();
+        let metrics: SchemaSizeMetrics = serde_json::from_value(result.metrics.clone()).unwrap();
+
+        let buckets: Vec<_> = metrics.sizes.keys().cloned().collect();
+        assert_eq!(buckets, vec!["100kb", "1kb"]);
+        for (bucket, formats) in &metrics.sizes {
+            let formats: Vec<_> = formats.keys().map(String::as_str).collect();
+            assert_eq!(formats, vec!["avro", "json_schema", "protobuf"], "{bucket}");
+        }
+        let small = &metrics.sizes["1kb"]["protobuf"];
+        let large = &metrics.sizes["100kb"]["protobuf"];
+        assert!(large.schema_bytes > 50 * small.schema_bytes);
+        assert!(small.bytes_per_sec > 0.0 && large.bytes_per_sec > 0.0);
+        assert_eq!(
+            result.metrics["sizes"]["1kb"]["avro"]["bytes_per_sec"]["unit"],
+            "bytes_per_sec"
+        );
+    }
+}
@@ -1,49 +1,435 @@
 //! Storage operation benchmarks
 
-use super::BenchTarget;
+use super::storage_backend::{DiskStorage, StorageBackend};
+use super::{BenchConfig, BenchTarget};
+use crate::metrics::{CacheTtlMetrics, CodecMetrics, StorageMetrics, SubjectLayoutMetrics};
+use crate::observer::record_iteration;
+use crate::sampling::{PoolSlot, SamplePool};
 use crate::BenchmarkResult;
 use anyhow::Result;
 use async_trait::async_trait;
-use serde_json::json;
-use std::time::Instant;
+use schema_registry_compatibility::CompatibilityCheckerImpl;
+use schema_registry_core::embedded::EmbeddedRegistry;
+use schema_registry_core::traits::SchemaStorage;
+use schema_registry_core::{RegisteredSchema, SchemaInput, SerializationFormat};
+use schema_registry_core::config_manager_adapter::StorageConfig;
+use schema_registry_core::pagination::PageRequest;
+use schema_registry_storage::blob::{BlobStore, FsBlobStore};
+use schema_registry_storage::compression::{decode_blob, encode_blob_with, Codec};
+use schema_registry_storage::fs_layout::{FsLayout, DEFAULT_PREFIX_LEN};
+use schema_registry_storage::read_cache::{CachingStorage, TtlPolicy, WriteHistory};
+use serde_json::{json, Map};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Seed for the registry's id generator, so runs store identical data
+const REGISTRY_SEED: u64 = 0x5EED;
+
+/// Schema-size buckets for the codec comparison: name and approximate size in bytes
+const SIZE_BUCKETS: [(&str, usize); 3] = [("small", 256), ("medium", 4 * 1024), ("large", 64 * 1024)];
+
+/// Workload classes for the adaptive cache TTL: name and mean seconds between writes
+const TTL_WORKLOAD: [(&str, u64); 3] = [("hot", 15), ("warm", 300), ("cold", 4 * 3600)];
+
+/// Simulated length of the adaptive cache TTL workload
+const TTL_WORKLOAD_SECS: u64 = 24 * 3600;
+
+/// Subjects written to the blob store for the layout comparison
+const MANY_SUBJECTS: usize = 1_000;
+
+/// Keys per page when listing the blob store
+const LIST_PAGE_SIZE: usize = 100;
+
+/// Sleeps standing in for write, cold read, warm read and update with [`StorageBackend::Simulated`]
+const SIMULATED_MICROS: [u64; 4] = [150, 60, 10, 80];
+
+/// Sample series recorded per measured iteration of the registry operations
+pub(crate) const SERIES: [&str; 4] = ["write", "read_cold", "read_warm", "update"];
+
+/// JSON Schema of roughly `bytes` bytes
+pub(crate) fn schema_of_size(bytes: usize) -> Vec<u8> {
+    let mut properties = Map::new();
+    let mut len = 0;
+    while len < bytes {
+        let name = format!("field_{}", properties.len());
+        let field = json!({"type": "string", "description": format!("Field number {} of the record", properties.len())});
+        len += name.len() + field.to_string().len() + 4;
+        properties.insert(name, field);
+    }
+    json!({"type": "object", "properties": properties}).to_string().into_bytes()
+}
+
+/// Fresh working directory path, distinct for every benchmark instance in the process
+fn new_work_dir() -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    std::env::temp_dir().join(format!(
+        "schema-registry-bench-storage-{}-{}",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// Path of the fixture schema for a size bucket
+fn fixture_path(work_dir: &Path, bucket: &str) -> PathBuf {
+    work_dir.join(format!("{}.json", bucket))
+}
+
+/// Storage the registry operations of one run go through
+struct RunStorage {
+    backend: StorageBackend,
+    /// Read cache over the backend's storage
+    cache: CachingStorage<Arc<dyn SchemaStorage>>,
+    /// Directory of a disk backend, removed when the run ends
+    disk_root: Option<PathBuf>,
+}
+
+impl RunStorage {
+    /// Read back the latest version of a schema through the read cache
+    async fn bench_read(&self, schema: &RegisteredSchema) -> Result<f64> {
+        let start = Instant::now();
+
+        let read = self.cache.retrieve(schema.id, None).await?;
+        anyhow::ensure!(read.content_hash == schema.content_hash, "read returned different content");
+
+        Ok(start.elapsed().as_secs_f64() * 1000.0) // Convert to milliseconds
+    }
+
+    /// Read a schema with nothing cached, so the read goes to storage
+    async fn bench_read_cold(&self, schema: &RegisteredSchema) -> Result<f64> {
+        self.cache.clear();
+        self.bench_read(schema).await
+    }
+
+    /// Read a schema again; returns the time and whether the cache answered
+    async fn bench_read_warm(&self, schema: &RegisteredSchema) -> Result<(f64, bool)> {
+        let hits = self.cache.metrics().hits();
+        let read_ms = self.bench_read(schema).await?;
+        Ok((read_ms, self.cache.metrics().hits() > hits))
+    }
+
+    /// Update a stored schema's description in place
+    async fn bench_update(&self, schema: &RegisteredSchema) -> Result<f64> {
+        let mut updated = schema.clone();
+        updated.description = format!("updated {}", schema.name);
+
+        let start = Instant::now();
+        self.cache.update(updated).await?;
+
+        Ok(start.elapsed().as_secs_f64() * 1000.0) // Convert to milliseconds
+    }
+}
+
+impl Drop for RunStorage {
+    fn drop(&mut self) {
+        if let Some(root) = &self.disk_root {
+            let _ = std::fs::remove_dir_all(root);
+        }
+    }
+}
+
+/// Timings of one iteration's registry operations, in milliseconds
+struct OperationTimes {
+    write: f64,
+    read_cold: f64,
+    read_warm: f64,
+    update: f64,
+    /// Whether the cache answered the warm read; `None` when simulated
+    warm_hit: Option<bool>,
+}
+
+/// Sleep for each of [`SIMULATED_MICROS`] in turn
+async fn simulated_operations() -> OperationTimes {
+    let mut times = [0.0; 4];
+    for (ms, micros) in times.iter_mut().zip(SIMULATED_MICROS) {
+        let start = Instant::now();
+        tokio::time::sleep(Duration::from_micros(micros)).await;
+        *ms = start.elapsed().as_secs_f64() * 1000.0;
+    }
+    let [write, read_cold, read_warm, update] = times;
+    OperationTimes {
+        write,
+        read_cold,
+        read_warm,
+        update,
+        warm_hit: None,
+    }
+}
 
 /// Benchmark for storage operations
-pub struct StorageBenchmark;
+///
+/// Operations run through an [`EmbeddedRegistry`], so writes include the
+/// validation, compatibility and lifecycle steps of a real registration.
+/// Where the schemas are persisted follows [`BenchConfig::storage_backend`]:
+/// the registry's own in-memory storage, or also a [`DiskStorage`] in a
+/// temporary directory, which writes then include. With
+/// [`StorageBackend::Simulated`] the registry operations are fixed sleeps.
+///
+/// Reads go through a [`CachingStorage`] over the backend, with the TTL
+/// policy of the configured [`StorageConfig`]: `read_cold` clears the cache
+/// before reading, `read_warm` reads the same schema again right after.
+///
+/// [`setup`](BenchTarget::setup) writes the codec comparison's fixture
+/// schemas to a working directory, which teardown removes. When `run` is
+/// called without setup, the fixtures are generated in memory instead.
+pub struct StorageBenchmark {
+    registry: EmbeddedRegistry,
+    ttl_policy: TtlPolicy,
+    samples: PoolSlot,
+    work_dir: PathBuf,
+}
 
 impl StorageBenchmark {
     /// Create a new storage benchmark
     pub fn new() -> Self {
-        Self
+        let registry = EmbeddedRegistry::builder()
+            .with_compatibility_checker(Arc::new(CompatibilityCheckerImpl::new()))
+            .with_seed(REGISTRY_SEED)
+            .build();
+        Self {
+            registry,
+            ttl_policy: TtlPolicy::default(),
+            samples: PoolSlot::new(),
+            work_dir: new_work_dir(),
+        }
     }
 
-    /// Simulate a storage write operation
-    async fn bench_write(&self) -> f64 {
-        let start = Instant::now();
+    /// Cache reads with the TTL policy of `config` instead of the default
+    /// `cache_ttl_seconds`; fails if its adaptive TTL bounds are invalid
+    pub fn with_storage_config(mut self, config: &StorageConfig) -> Result<Self> {
+        self.ttl_policy = TtlPolicy::from_config(config)?;
+        Ok(self)
+    }
+
+    /// Storage for a run persisting to `backend`
+    fn run_storage(&self, backend: StorageBackend) -> RunStorage {
+        let disk_root = (backend == StorageBackend::Disk).then(new_work_dir);
+        let storage: Arc<dyn SchemaStorage> = match &disk_root {
+            Some(root) => Arc::new(DiskStorage::new(root.clone())),
+            None => self.registry.storage(),
+        };
+        RunStorage {
+            backend,
+            cache: CachingStorage::new(storage, self.ttl_policy),
+            disk_root,
+        }
+    }
 
-        // Simulate storage write (in production, this would use actual storage)
-        tokio::time::sleep(tokio::time::Duration::from_micros(100)).await;
+    /// Directory setup writes fixtures to
+    pub fn work_dir(&self) -> &Path {
+        &self.work_dir
+    }
 
-        start.elapsed().as_secs_f64() * 1000.0 // Convert to milliseconds
+    /// Fixture schema of about `size` bytes for `bucket`
+    fn fixture(&self, bucket: &str, size: usize) -> Result<Vec<u8>> {
+        let path = fixture_path(&self.work_dir, bucket);
+        if path.exists() {
+            Ok(std::fs::read(path)?)
+        } else {
+            Ok(schema_of_size(size))
+        }
     }
 
-    /// Simulate a storage read operation
-    async fn bench_read(&self) -> f64 {
+    /// Register a new schema subject, and persist it unless `storage` is the registry's
+    async fn bench_write(&self, n: usize, storage: &RunStorage) -> Result<(f64, RegisteredSchema)> {
+        let content = json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "string"},
+                format!("field_{}", n): {"type": "integer"}
+            },
+            "required": ["id"]
+        });
+        let input = SchemaInput::new(
+            "bench.storage",
+            format!("subject_{}", n),
+            SerializationFormat::JsonSchema,
+            content.to_string(),
+        );
+
         let start = Instant::now();
+        let schema = self.registry.register(input).await?;
+        if storage.backend == StorageBackend::Disk {
+            storage.cache.store(schema.clone()).await?;
+        }
+        Ok((start.elapsed().as_secs_f64() * 1000.0, schema)) // Convert to milliseconds
+    }
 
-        // Simulate storage read (in production, this would use actual storage)
-        tokio::time::sleep(tokio::time::Duration::from_micros(50)).await;
+    /// Write, read cold, read warm and update schema `n` in `storage`
+    async fn bench_operations(&self, n: usize, storage: &RunStorage) -> Result<OperationTimes> {
+        if storage.backend == StorageBackend::Simulated {
+            return Ok(simulated_operations().await);
+        }
+        let (write, schema) = self.bench_write(n, storage).await?;
+        let read_cold = storage.bench_read_cold(&schema).await?;
+        let (read_warm, hit) = storage.bench_read_warm(&schema).await?;
+        let update = storage.bench_update(&schema).await?;
+        Ok(OperationTimes {
+            write,
+            read_cold,
+            read_warm,
+            update,
+            warm_hit: Some(hit),
+        })
+    }
 
-        start.elapsed().as_secs_f64() * 1000.0 // Convert to milliseconds
+    /// Encode and decode each size bucket with every codec compiled in
+    fn bench_codecs(
+        &self,
+        cfg: &BenchConfig,
+        samples: &mut SamplePool,
+    ) -> Result<BTreeMap<String, BTreeMap<String, CodecMetrics>>> {
+        let mut buckets = BTreeMap::new();
+        for (bucket, size) in SIZE_BUCKETS {
+            let content = self.fixture(bucket, size)?;
+            let mut codecs = BTreeMap::new();
+
+            for codec in Codec::available() {
+                let encode = samples.series_path(&[bucket, codec.name(), "encode"]);
+                let decode = samples.series_path(&[bucket, codec.name(), "decode"]);
+                let mut blob = Vec::new();
+
+                for i in cfg.iterations() {
+                    let start = Instant::now();
+                    blob = encode_blob_with(&content, codec)?;
+                    let encode_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+                    let start = Instant::now();
+                    let decoded = decode_blob(&blob)?;
+                    let decode_ms = start.elapsed().as_secs_f64() * 1000.0;
+                    anyhow::ensure!(decoded == content, "{} round trip changed the content", codec);
+
+                    if !cfg.is_warmup(i) {
+                        samples.record(encode, encode_ms);
+                        samples.record(decode, decode_ms);
+                    }
+                }
+
+                codecs.insert(
+                    codec.name().to_string(),
+                    CodecMetrics {
+                        compression_ratio: blob.len() as f64 / content.len() as f64,
+                        encode: samples.metrics(encode),
+                        decode: samples.metrics(decode),
+                    },
+                );
+            }
+            buckets.insert(bucket.to_string(), codecs);
+        }
+        Ok(buckets)
     }
 
-    /// Simulate a storage update operation
-    async fn bench_update(&self) -> f64 {
-        let start = Instant::now();
+    /// Replay a day of mixed writes against the adaptive cache TTL policy
+    ///
+    /// Write times are simulated, with intervals jittered by up to 20% around
+    /// each class's mean, so the run takes no wall-clock time. Fails unless
+    /// every class settles near half its write interval within the configured
+    /// bounds.
+    fn bench_adaptive_ttl(&self) -> Result<BTreeMap<String, CacheTtlMetrics>> {
+        let config = StorageConfig {
+            adaptive_cache_ttl: true,
+            ..StorageConfig::default()
+        };
+        let TtlPolicy::Adaptive(bounds) = TtlPolicy::from_config(&config)? else {
+            anyhow::bail!("adaptive cache TTL is not enabled");
+        };
+
+        let start = tokio::time::Instant::now();
+        let end = start + Duration::from_secs(TTL_WORKLOAD_SECS);
+        let mut jitter = REGISTRY_SEED;
+        let mut classes = BTreeMap::new();
+
+        for (class, interval) in TTL_WORKLOAD {
+            let mut history = WriteHistory::new(start);
+            let mut at = start;
+            loop {
+                // xorshift64: a fixed sequence of jitter factors in 0.8..1.2
+                jitter ^= jitter << 13;
+                jitter ^= jitter >> 7;
+                jitter ^= jitter << 17;
+                let factor = 0.8 + 0.4 * (jitter % 1000) as f64 / 1000.0;
+                at += Duration::from_secs(interval).mul_f64(factor);
+                if at > end {
+                    break;
+                }
+                history.record(at);
+            }
 
-        // Simulate storage update (in production, this would use actual storage)
-        tokio::time::sleep(tokio::time::Duration::from_micros(80)).await;
+            let ttl = bounds.ttl(Some(&history), end);
+            let expected = Duration::from_secs(interval)
+                .mul_f64(bounds.write_interval_fraction)
+                .clamp(bounds.min_ttl, bounds.max_ttl);
+            let error = (ttl.as_secs_f64() - expected.as_secs_f64()).abs() / expected.as_secs_f64();
+            anyhow::ensure!(
+                error <= 0.25,
+                "{} subjects settled on a {:?} TTL, expected about {:?}",
+                class,
+                ttl,
+                expected
+            );
+
+            classes.insert(class.to_string(), CacheTtlMetrics { ttl_seconds: ttl.as_secs_f64() });
+        }
+        Ok(classes)
+    }
 
-        start.elapsed().as_secs_f64() * 1000.0 // Convert to milliseconds
+    /// List and read a blob store holding [`MANY_SUBJECTS`] subjects, flat and sharded
+    ///
+    /// The flat layout keeps every subject in the root directory; the sharded
+    /// one fans them out over hashed subdirectories. The stores are written
+    /// below the working directory and removed afterwards, along with the
+    /// working directory if setup didn't create it.
+    async fn bench_many_subjects(
+        &self,
+        cfg: &BenchConfig,
+        samples: &mut SamplePool,
+    ) -> Result<BTreeMap<String, SubjectLayoutMetrics>> {
+        let layouts = [("flat", FsLayout::Flat), ("sharded", FsLayout::sharded(DEFAULT_PREFIX_LEN)?)];
+        let blob = schema_of_size(SIZE_BUCKETS[0].1);
+        // Keys are built once, so the measured loop only picks one
+        let keys: Vec<String> = (0..MANY_SUBJECTS).map(|n| format!("bench.storage/subject_{}/1.0.0", n)).collect();
+        let owns_work_dir = !self.work_dir.exists();
+        let stores = self.work_dir.join("many_subjects");
+        let mut results = BTreeMap::new();
+
+        for (name, layout) in layouts {
+            let root = stores.join(name);
+            let store = FsBlobStore::new(&root).with_layout(layout);
+            for key in &keys {
+                store.write(key, &blob).await?;
+            }
+
+            let list = samples.series_path(&["many_subjects", name, "list"]);
+            let read = samples.series_path(&["many_subjects", name, "read"]);
+            for i in cfg.iterations() {
+                let start = Instant::now();
+                let page = store.list(&PageRequest::new(LIST_PAGE_SIZE)).await?;
+                let list_ms = start.elapsed().as_secs_f64() * 1000.0;
+                anyhow::ensure!(page.items.len() == LIST_PAGE_SIZE, "{} store listed {} keys", name, page.items.len());
+
+                let key = &keys[(i * 7919) % MANY_SUBJECTS];
+                let start = Instant::now();
+                store.read(key).await?;
+                let read_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+                if !cfg.is_warmup(i) {
+                    samples.record(list, list_ms);
+                    samples.record(read, read_ms);
+                }
+            }
+            results.insert(
+                name.to_string(),
+                SubjectLayoutMetrics {
+                    list: samples.metrics(list),
+                    read: samples.metrics(read),
+                },
+            );
+        }
+
+        tokio::fs::remove_dir_all(if owns_work_dir { &self.work_dir } else { &stores }).await?;
+        Ok(results)
     }
 }
 
@@ -60,47 +446,90 @@ impl BenchTarget for StorageBenchmark {
     }
 
     fn description(&self) -> &str {
-        "Benchmarks for schema storage operations (read, write, update)"
+        "Benchmarks for schema storage operations (read, write, update) through the embedded registry, blob codecs by schema size, adaptive cache TTL convergence, and blob store listing with many subjects by directory layout"
+    }
+
+    fn tags(&self) -> &[&str] {
+        &["storage", "slow"]
+    }
+
+    /// Version 2 added `many_subjects`; version 3 split `read` into `read_cold` and `read_warm`
+    fn version(&self) -> u32 {
+        3
+    }
+
+    async fn setup(&self) -> Result<()> {
+        std::fs::create_dir_all(&self.work_dir)?;
+        for (bucket, size) in SIZE_BUCKETS {
+            std::fs::write(fixture_path(&self.work_dir, bucket), schema_of_size(size))?;
+        }
+        Ok(())
+    }
+
+    async fn teardown(&self) -> Result<()> {
+        if self.work_dir.exists() {
+            std::fs::remove_dir_all(&self.work_dir)?;
+        }
+        Ok(())
     }
 
     async fn run(&self) -> Result<BenchmarkResult> {
-        // Run multiple iterations for more accurate measurements
-        let iterations = 10;
-        let mut write_times = Vec::new();
-        let mut read_times = Vec::new();
-        let mut update_times = Vec::new();
-
-        for _ in 0..iterations {
-            write_times.push(self.bench_write().await);
-            read_times.push(self.bench_read().await);
-            update_times.push(self.bench_update().await);
-        }
-
-        // Calculate statistics
-        let avg_write = write_times.iter().sum::<f64>() / write_times.len() as f64;
-        let avg_read = read_times.iter().sum::<f64>() / read_times.len() as f64;
-        let avg_update = update_times.iter().sum::<f64>() / update_times.len() as f64;
-
-        let metrics = json!({
-            "iterations": iterations,
-            "write": {
-                "avg_ms": format!("{:.3}", avg_write),
-                "min_ms": format!("{:.3}", write_times.iter().cloned().fold(f64::INFINITY, f64::min)),
-                "max_ms": format!("{:.3}", write_times.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
-            },
-            "read": {
-                "avg_ms": format!("{:.3}", avg_read),
-                "min_ms": format!("{:.3}", read_times.iter().cloned().fold(f64::INFINITY, f64::min)),
-                "max_ms": format!("{:.3}", read_times.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
-            },
-            "update": {
-                "avg_ms": format!("{:.3}", avg_update),
-                "min_ms": format!("{:.3}", update_times.iter().cloned().fold(f64::INFINITY, f64::min)),
-                "max_ms": format!("{:.3}", update_times.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
-            }
-        });
+        self.run_with_config(&BenchConfig::default()).await
+    }
 
-        Ok(BenchmarkResult::new(self.id().to_string(), metrics))
+    async fn run_with_config(&self, cfg: &BenchConfig) -> Result<BenchmarkResult> {
+        cfg.validate()?;
+
+        let mut samples = self.samples.take();
+        samples.begin_run(cfg.sample_capacity());
+        samples.set_outlier_policy(cfg.outliers);
+        let [write, read_cold, read_warm, update] = SERIES.map(|label| samples.series(label));
+
+        // Each run starts from an empty registry
+        let empty = self.registry.snapshot();
+        let storage = self.run_storage(cfg.storage_backend);
+        let (mut warm_reads, mut warm_hits) = (0_usize, 0_usize);
+        let mut iterations = cfg.iterations();
+        for n in iterations.by_ref() {
+            let times = self.bench_operations(n, &storage).await?;
+            record_iteration(
+                n,
+                cfg.is_warmup(n),
+                times.write + times.read_cold + times.read_warm + times.update,
+            );
+            if cfg.is_warmup(n) {
+                continue;
+            }
+            samples.record(write, times.write);
+            samples.record(read_cold, times.read_cold);
+            samples.record(read_warm, times.read_warm);
+            samples.record(update, times.update);
+            if let Some(hit) = times.warm_hit {
+                warm_reads += 1;
+                warm_hits += usize::from(hit);
+            }
+        }
+        drop(storage);
+        self.registry.restore(&empty);
+
+        let warm = samples.metrics(read_warm);
+        let metrics = StorageMetrics {
+            counts: iterations.counts(),
+            write: samples.metrics(write),
+            read: warm,
+            read_cold: samples.metrics(read_cold),
+            read_warm: warm,
+            cache_hit_ratio: (warm_reads > 0).then_some(warm_hits as f64 / warm_reads as f64),
+            update: samples.metrics(update),
+            compression: self.bench_codecs(cfg, &mut samples)?,
+            adaptive_ttl: self.bench_adaptive_ttl()?,
+            many_subjects: self.bench_many_subjects(cfg, &mut samples).await?,
+        };
+        let result = BenchmarkResult::new(self.id().to_string(), serde_json::to_value(metrics)?)
+            .with_raw_samples(&samples);
+        self.samples.restore(samples);
+
+        Ok(result)
     }
 }
 
@@ -127,6 +556,24 @@ mod tests {
         assert!(bench.description().contains("storage"));
     }
 
+    #[tokio::test]
+    async fn test_setup_writes_fixtures_and_teardown_removes_them() {
+        let bench = StorageBenchmark::new();
+        assert_ne!(bench.work_dir(), StorageBenchmark::new().work_dir());
+
+        bench.setup().await.unwrap();
+        for (bucket, size) in SIZE_BUCKETS {
+            assert_eq!(bench.fixture(bucket, size).unwrap(), schema_of_size(size));
+        }
+        let result = bench.run_with_config(&BenchConfig::new(0, 1)).await.unwrap();
+        assert!(result.metrics["compression"]["large"].is_object());
+
+        bench.teardown().await.unwrap();
+        assert!(!bench.work_dir().exists());
+        // Tearing down twice is harmless
+        bench.teardown().await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_storage_benchmark_run() {
         let bench = StorageBenchmark::new();
@@ -151,18 +598,21 @@ mod tests {
         // Check write metrics
         let write = result.metrics.get("write").unwrap();
         assert!(write.get("avg_ms").is_some());
+        assert!(write.get("p95_ms").is_some());
         assert!(write.get("min_ms").is_some());
         assert!(write.get("max_ms").is_some());
 
         // Check read metrics
         let read = result.metrics.get("read").unwrap();
         assert!(read.get("avg_ms").is_some());
+        assert!(read.get("p95_ms").is_some());
         assert!(read.get("min_ms").is_some());
         assert!(read.get("max_ms").is_some());
 
         // Check update metrics
         let update = result.metrics.get("update").unwrap();
         assert!(update.get("avg_ms").is_some());
+        assert!(update.get("p95_ms").is_some());
         assert!(update.get("min_ms").is_some());
         assert!(update.get("max_ms").is_some());
     }
@@ -170,21 +620,151 @@ mod tests {
     #[tokio::test]
     async fn test_bench_write() {
         let bench = StorageBenchmark::new();
-        let duration = bench.bench_write().await;
+        let storage = bench.run_storage(StorageBackend::Memory);
+        let (duration, schema) = bench.bench_write(0, &storage).await.unwrap();
         assert!(duration > 0.0);
+        assert_eq!(schema.name, "subject_0");
     }
 
     #[tokio::test]
     async fn test_bench_read() {
         let bench = StorageBenchmark::new();
-        let duration = bench.bench_read().await;
+        let storage = bench.run_storage(StorageBackend::Memory);
+        let (_, schema) = bench.bench_write(0, &storage).await.unwrap();
+        let duration = storage.bench_read(&schema).await.unwrap();
         assert!(duration > 0.0);
     }
 
+    #[tokio::test]
+    async fn test_warm_reads_hit_the_cache_until_the_ttl_expires() {
+        let bench = StorageBenchmark::new();
+        let result = bench.run_with_config(&BenchConfig::new(0, 3)).await.unwrap();
+        let metrics: StorageMetrics = serde_json::from_value(result.metrics).unwrap();
+        assert_eq!(metrics.read, metrics.read_warm);
+        assert!(metrics.read_cold.max_ms > 0.0);
+        assert_eq!(metrics.cache_hit_ratio, Some(1.0));
+
+        // A zero TTL expires every entry as it is cached
+        let config = StorageConfig {
+            cache_ttl_seconds: 0,
+            ..StorageConfig::default()
+        };
+        let bench = StorageBenchmark::new().with_storage_config(&config).unwrap();
+        let storage = bench.run_storage(StorageBackend::Memory);
+        let (_, schema) = bench.bench_write(0, &storage).await.unwrap();
+        storage.bench_read_cold(&schema).await.unwrap();
+        let (_, hit) = storage.bench_read_warm(&schema).await.unwrap();
+        assert!(!hit);
+    }
+
     #[tokio::test]
     async fn test_bench_update() {
         let bench = StorageBenchmark::new();
-        let duration = bench.bench_update().await;
+        let storage = bench.run_storage(StorageBackend::Memory);
+        let (_, schema) = bench.bench_write(0, &storage).await.unwrap();
+        let duration = storage.bench_update(&schema).await.unwrap();
         assert!(duration > 0.0);
+
+        let stored = bench.registry.get(schema.id, None).await.unwrap();
+        assert_eq!(stored.description, "updated subject_0");
+    }
+
+    #[tokio::test]
+    async fn test_disk_backend_persists_files_for_the_run() {
+        let bench = StorageBenchmark::new();
+        let storage = bench.run_storage(StorageBackend::Disk);
+        let root = storage.disk_root.clone().unwrap();
+        let (_, schema) = bench.bench_write(0, &storage).await.unwrap();
+        assert!(root.join(schema.id.to_string()).is_dir());
+        storage.bench_update(&schema).await.unwrap();
+        assert_ne!(bench.registry.get(schema.id, None).await.unwrap().description, "updated subject_0");
+        drop(storage);
+        assert!(!root.exists());
+
+        let cfg = BenchConfig::new(0, 2).with_storage_backend(StorageBackend::Disk);
+        let metrics: StorageMetrics =
+            serde_json::from_value(bench.run_with_config(&cfg).await.unwrap().metrics).unwrap();
+        assert!(metrics.read_cold.max_ms > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_simulated_backend_skips_storage() {
+        let bench = StorageBenchmark::new();
+        let cfg = BenchConfig::new(0, 2).with_storage_backend(StorageBackend::Simulated);
+        let result = bench.run_with_config(&cfg).await.unwrap();
+        let metrics: StorageMetrics = serde_json::from_value(result.metrics.clone()).unwrap();
+
+        assert!(metrics.write.min_ms >= 0.15);
+        assert_eq!(metrics.cache_hit_ratio, None);
+    }
+
+    #[tokio::test]
+    async fn test_run_leaves_registry_empty() {
+        let bench = StorageBenchmark::new();
+        bench.run().await.unwrap();
+        assert!(bench.registry.storage().is_empty());
+    }
+
+    #[test]
+    fn test_schema_of_size_is_close_to_target() {
+        for (_, size) in SIZE_BUCKETS {
+            let len = schema_of_size(size).len();
+            assert!(len >= size && len < size + 256, "{} bytes for a {} byte bucket", len, size);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_storage_benchmark_compares_codecs_per_bucket() {
+        let bench = StorageBenchmark::new();
+        let result = bench.run().await.unwrap();
+
+        let compression = result.metrics.get("compression").unwrap();
+        for (bucket, _) in SIZE_BUCKETS {
+            for codec in Codec::available() {
+                let stats = &compression[bucket][codec.name()];
+                assert!(stats.get("compression_ratio").is_some(), "{} / {}", bucket, codec);
+                assert!(stats["encode"].get("avg_ms").is_some());
+                assert!(stats["encode"].get("p95_ms").is_some());
+                assert!(stats["decode"].get("avg_ms").is_some());
+                assert!(stats["decode"].get("p95_ms").is_some());
+            }
+        }
+        // Large JSON Schemas compress well
+        let ratio = compression["large"]["gzip"]["compression_ratio"]["value"].as_f64().unwrap();
+        assert!(ratio < 0.5);
+
+        let typed: StorageMetrics = serde_json::from_value(result.metrics).unwrap();
+        assert_eq!(typed.adaptive_ttl.len(), TTL_WORKLOAD.len());
+        assert_eq!(typed.counts.iterations, 10);
+        assert!(typed.write.p95_ms >= typed.write.p50_ms);
+    }
+
+    #[tokio::test]
+    async fn test_many_subjects_compares_layouts() {
+        let bench = StorageBenchmark::new();
+        let mut samples = SamplePool::new();
+        samples.begin_run(BenchConfig::new(0, 3).sample_capacity());
+        let layouts = bench.bench_many_subjects(&BenchConfig::new(0, 3), &mut samples).await.unwrap();
+
+        assert_eq!(layouts.keys().collect::<Vec<_>>(), vec!["flat", "sharded"]);
+        for metrics in layouts.values() {
+            assert!(metrics.list.p95_ms > 0.0);
+            assert!(metrics.read.p95_ms > 0.0);
+        }
+        // Run without setup: nothing is left behind
+        assert!(!bench.work_dir().exists());
+    }
+
+    #[test]
+    fn test_adaptive_ttl_orders_classes_by_write_frequency() {
+        let bench = StorageBenchmark::new();
+        let ttls = bench.bench_adaptive_ttl().unwrap();
+
+        let hot = ttls["hot"].ttl_seconds;
+        let warm = ttls["warm"].ttl_seconds;
+        let cold = ttls["cold"].ttl_seconds;
+        assert!(hot < warm && warm < cold, "{} / {} / {}", hot, warm, cold);
+        // Written every few hours: held at the upper bound
+        assert_eq!(cold, StorageConfig::default().cache_max_ttl_seconds as f64);
     }
 }
@@ -0,0 +1,264 @@
+//! Storage backends of the storage benchmark
+//!
+//! [`StorageBackend`] picks where `storage_operations` persists the schemas
+//! it registers: the embedded registry's in-memory storage, a
+//! [`DiskStorage`] with one JSON file per schema version, or nowhere at all
+//! for quick smoke runs. It travels in [`BenchConfig`](super::BenchConfig),
+//! and the CLI reads it from `--storage-backend` or [`STORAGE_BACKEND_ENV`].
+
+use async_trait::async_trait;
+use schema_registry_core::error::{Error, Result};
+use schema_registry_core::pagination::{paginate, Page, PageRequest};
+use schema_registry_core::traits::SchemaStorage;
+use schema_registry_core::versioning::SemanticVersion;
+use schema_registry_core::RegisteredSchema;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Environment variable the CLI reads the storage backend from
+pub const STORAGE_BACKEND_ENV: &str = "BENCH_STORAGE_BACKEND";
+
+/// Where the storage benchmark persists schemas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StorageBackend {
+    /// The embedded registry's in-memory storage
+    #[default]
+    Memory,
+    /// A [`DiskStorage`] in a temporary directory
+    Disk,
+    /// Fixed sleeps instead of storage calls; for smoke runs, the timings are meaningless
+    Simulated,
+}
+
+impl StorageBackend {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StorageBackend::Memory => "memory",
+            StorageBackend::Disk => "disk",
+            StorageBackend::Simulated => "simulated",
+        }
+    }
+}
+
+impl fmt::Display for StorageBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for StorageBackend {
+    type Err = String;
+
+    fn from_str(raw: &str) -> std::result::Result<Self, Self::Err> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "memory" => Ok(StorageBackend::Memory),
+            "disk" => Ok(StorageBackend::Disk),
+            "simulated" => Ok(StorageBackend::Simulated),
+            _ => Err(format!("unknown storage backend '{}' (expected memory, disk or simulated)", raw)),
+        }
+    }
+}
+
+/// Schema storage with one JSON file per version, `<root>/<id>/<version>.json`
+///
+/// Lookups other than by id scan every file, which is fine for the few
+/// hundred schemas a benchmark run writes.
+#[derive(Debug)]
+pub struct DiskStorage {
+    root: PathBuf,
+}
+
+impl DiskStorage {
+    /// Storage in `root`, created on the first write
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Directory the schema files are written to
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, id: Uuid, version: &SemanticVersion) -> PathBuf {
+        self.root.join(id.to_string()).join(format!("{}.json", version))
+    }
+
+    async fn read(&self, path: &Path) -> Result<RegisteredSchema> {
+        let bytes = tokio::fs::read(path).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    async fn write(&self, schema: &RegisteredSchema) -> Result<()> {
+        let path = self.path(schema.id, &schema.version);
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        tokio::fs::write(&path, serde_json::to_vec(schema)?).await?;
+        Ok(())
+    }
+
+    /// Versions stored for `id`, ascending
+    async fn versions(&self, id: Uuid) -> Result<Vec<SemanticVersion>> {
+        let dir = self.root.join(id.to_string());
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut versions = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name();
+            let version = name.to_string_lossy().strip_suffix(".json").and_then(|v| v.parse::<SemanticVersion>().ok());
+            versions.extend(version);
+        }
+        versions.sort();
+        Ok(versions)
+    }
+
+    /// Every stored schema version
+    async fn all(&self) -> Result<Vec<RegisteredSchema>> {
+        let mut dirs = match tokio::fs::read_dir(&self.root).await {
+            Ok(dirs) => dirs,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut schemas = Vec::new();
+        while let Some(dir) = dirs.next_entry().await? {
+            let Ok(id) = dir.file_name().to_string_lossy().parse::<Uuid>() else {
+                continue;
+            };
+            for version in self.versions(id).await? {
+                schemas.push(self.read(&self.path(id, &version)).await?);
+            }
+        }
+        Ok(schemas)
+    }
+}
+
+#[async_trait]
+impl SchemaStorage for DiskStorage {
+    async fn store(&self, schema: RegisteredSchema) -> Result<()> {
+        if tokio::fs::metadata(self.path(schema.id, &schema.version)).await.is_ok() {
+            return Err(Error::SchemaAlreadyExists(format!("{}@{}", schema.id, schema.version)));
+        }
+        self.write(&schema).await
+    }
+
+    async fn retrieve(&self, id: Uuid, version: Option<SemanticVersion>) -> Result<RegisteredSchema> {
+        let version = match version {
+            Some(version) => version,
+            None => self
+                .versions(id)
+                .await?
+                .pop()
+                .ok_or_else(|| Error::SchemaNotFound(id.to_string()))?,
+        };
+        match self.read(&self.path(id, &version)).await {
+            Err(Error::IoError(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(Error::SchemaNotFound(format!("{}@{}", id, version)))
+            }
+            result => result,
+        }
+    }
+
+    async fn retrieve_by_hash(&self, content_hash: &str) -> Result<Option<RegisteredSchema>> {
+        Ok(self
+            .all()
+            .await?
+            .into_iter()
+            .find(|schema| schema.content_hash == content_hash))
+    }
+
+    async fn update(&self, schema: RegisteredSchema) -> Result<()> {
+        if tokio::fs::metadata(self.path(schema.id, &schema.version)).await.is_err() {
+            return Err(Error::SchemaNotFound(format!("{}@{}", schema.id, schema.version)));
+        }
+        self.write(&schema).await
+    }
+
+    async fn delete(&self, id: Uuid, version: SemanticVersion) -> Result<()> {
+        match tokio::fs::remove_file(self.path(id, &version)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(Error::SchemaNotFound(format!("{}@{}", id, version)))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn list_versions(&self, id: Uuid) -> Result<Vec<SemanticVersion>> {
+        self.versions(id).await
+    }
+
+    async fn find_by_name(&self, namespace: &str, name: &str) -> Result<Vec<RegisteredSchema>> {
+        Ok(self
+            .all()
+            .await?
+            .into_iter()
+            .filter(|schema| schema.namespace == namespace && schema.name == name)
+            .collect())
+    }
+
+    async fn list_subjects(&self, page: &PageRequest) -> Result<Page<String>> {
+        let subjects: BTreeSet<String> = self
+            .all()
+            .await?
+            .iter()
+            .map(|schema| schema.fully_qualified_name())
+            .collect();
+        paginate(subjects, page, |subject| subject.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schema_registry_core::embedded::EmbeddedRegistry;
+    use schema_registry_core::{SchemaInput, SerializationFormat};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_backend() {
+        assert_eq!("disk".parse(), Ok(StorageBackend::Disk));
+        assert_eq!(" Memory ".parse(), Ok(StorageBackend::Memory));
+        assert_eq!(StorageBackend::Simulated.to_string().parse(), Ok(StorageBackend::Simulated));
+        assert!("s3".parse::<StorageBackend>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_disk_storage_round_trips_schemas() {
+        let dir = TempDir::new().unwrap();
+        let storage = DiskStorage::new(dir.path().join("schemas"));
+        let registry = EmbeddedRegistry::new();
+        let input = SchemaInput::new(
+            "bench.disk",
+            "subject",
+            SerializationFormat::JsonSchema,
+            r#"{"type": "object"}"#.to_string(),
+        );
+        let schema = registry.register(input).await.unwrap();
+
+        assert!(storage.retrieve(schema.id, None).await.is_err());
+        storage.store(schema.clone()).await.unwrap();
+        assert!(storage.store(schema.clone()).await.is_err());
+
+        let read = storage.retrieve(schema.id, None).await.unwrap();
+        assert_eq!(read.content_hash, schema.content_hash);
+        assert!(storage.retrieve_by_hash(&schema.content_hash).await.unwrap().is_some());
+        assert_eq!(storage.list_versions(schema.id).await.unwrap(), vec![schema.version.clone()]);
+
+        let mut updated = schema.clone();
+        updated.description = "updated".to_string();
+        storage.update(updated).await.unwrap();
+        assert_eq!(storage.retrieve(schema.id, None).await.unwrap().description, "updated");
+
+        let subjects = storage.list_subjects(&PageRequest::new(10)).await.unwrap();
+        assert_eq!(subjects.items, vec!["bench.disk.subject"]);
+
+        storage.delete(schema.id, schema.version.clone()).await.unwrap();
+        assert!(storage.retrieve(schema.id, None).await.is_err());
+    }
+}
@@ -1,49 +1,84 @@
 //! Validation operation benchmarks
-
-use super::BenchTarget;
+//!
+//! Runs the real [`ValidationEngine`], with a [`PolicyBasedValidationRule`]
+//! over the default [`SchemaPolicies`], on JSON Schema, Avro and Protobuf
+//! schemas from a [`SchemaWorkloadGenerator`] seeded with
+//! [`BenchConfig::seed`]. A schema the engine rejects is timed like any
+//! other and counted in `invalid_schemas`; it doesn't fail the run.
+//!
+//! Each format's metrics are under `by_format`, keyed by [`format_key`].
+
+use super::{BenchConfig, BenchTarget};
+use crate::metrics::{format_key, ValidationFormatMetrics, ValidationMetrics};
+use crate::observer::record_iteration;
+use crate::sampling::PoolSlot;
+use crate::workload::SchemaWorkloadGenerator;
 use crate::BenchmarkResult;
 use anyhow::Result;
 use async_trait::async_trait;
-use serde_json::json;
+use schema_registry_core::config_manager_adapter::SchemaPolicies;
+use schema_registry_core::SerializationFormat;
+use schema_registry_validation::config_integration::PolicyBasedValidationRule;
+use schema_registry_validation::engine::ValidationEngine;
+use std::sync::Arc;
 use std::time::Instant;
 
+/// Sample series recorded per measured iteration
+pub(crate) const SERIES: [&str; 3] = ["json_schema", "avro", "protobuf"];
+
+/// Formats validated, in [`SERIES`] order
+const FORMATS: [SerializationFormat; 3] = [
+    SerializationFormat::JsonSchema,
+    SerializationFormat::Avro,
+    SerializationFormat::Protobuf,
+];
+
+/// Fields per generated record, giving schemas of a few KB with the default depth
+const SCHEMA_FIELDS: usize = 20;
+
 /// Benchmark for validation operations
-pub struct ValidationBenchmark;
+pub struct ValidationBenchmark {
+    engine: ValidationEngine,
+    /// Schemas validated instead of generated ones, by [`FORMATS`] index
+    overrides: [Option<String>; 3],
+    samples: PoolSlot,
+}
 
 impl ValidationBenchmark {
     /// Create a new validation benchmark
     pub fn new() -> Self {
-        Self
+        let mut engine = ValidationEngine::new();
+        engine.add_rule(Arc::new(PolicyBasedValidationRule::new(SchemaPolicies::default())));
+        Self {
+            engine,
+            overrides: Default::default(),
+            samples: PoolSlot::new(),
+        }
     }
 
-    /// Simulate JSON schema validation
-    async fn bench_json_validation(&self) -> f64 {
-        let start = Instant::now();
-
-        // Simulate JSON schema validation (in production, this would use actual validator)
-        tokio::time::sleep(tokio::time::Duration::from_micros(75)).await;
-
-        start.elapsed().as_secs_f64() * 1000.0 // Convert to milliseconds
+    /// Validate `content` as the `format` schema instead of a generated one
+    pub fn with_schema(mut self, format: SerializationFormat, content: impl Into<String>) -> Self {
+        if let Some(index) = FORMATS.iter().position(|&f| f == format) {
+            self.overrides[index] = Some(content.into());
+        }
+        self
     }
 
-    /// Simulate Avro schema validation
-    async fn bench_avro_validation(&self) -> f64 {
-        let start = Instant::now();
-
-        // Simulate Avro schema validation (in production, this would use actual validator)
-        tokio::time::sleep(tokio::time::Duration::from_micros(60)).await;
-
-        start.elapsed().as_secs_f64() * 1000.0 // Convert to milliseconds
+    /// The schema validated for each of [`FORMATS`]
+    fn schemas(&self, seed: u64) -> [String; 3] {
+        let mut generator = SchemaWorkloadGenerator::new(seed).with_fields(SCHEMA_FIELDS);
+        std::array::from_fn(|i| {
+            self.overrides[i]
+                .clone()
+                .unwrap_or_else(|| generator.generate(FORMATS[i]))
+        })
     }
 
-    /// Simulate Protobuf schema validation
-    async fn bench_protobuf_validation(&self) -> f64 {
+    /// Validate `schema` once; returns the time and whether the engine accepted it
+    async fn bench_validation(&self, schema: &str, format: SerializationFormat) -> Result<(f64, bool)> {
         let start = Instant::now();
-
-        // Simulate Protobuf schema validation (in production, this would use actual validator)
-        tokio::time::sleep(tokio::time::Duration::from_micros(70)).await;
-
-        start.elapsed().as_secs_f64() * 1000.0 // Convert to milliseconds
+        let result = self.engine.validate(schema, format.into()).await?;
+        Ok((start.elapsed().as_secs_f64() * 1000.0, result.is_valid)) // Convert to milliseconds
     }
 }
 
@@ -63,44 +98,71 @@ impl BenchTarget for ValidationBenchmark {
         "Benchmarks for schema validation across different formats (JSON, Avro, Protobuf)"
     }
 
+    fn tags(&self) -> &[&str] {
+        &["validation", "fast"]
+    }
+
+    fn formats(&self) -> &[SerializationFormat] {
+        &FORMATS
+    }
+
+    /// Version 2 moved the per-format metrics under `by_format`
+    fn version(&self) -> u32 {
+        2
+    }
+
     async fn run(&self) -> Result<BenchmarkResult> {
-        // Run multiple iterations for more accurate measurements
-        let iterations = 10;
-        let mut json_times = Vec::new();
-        let mut avro_times = Vec::new();
-        let mut protobuf_times = Vec::new();
-
-        for _ in 0..iterations {
-            json_times.push(self.bench_json_validation().await);
-            avro_times.push(self.bench_avro_validation().await);
-            protobuf_times.push(self.bench_protobuf_validation().await);
-        }
+        self.run_with_config(&BenchConfig::default()).await
+    }
 
-        // Calculate statistics
-        let avg_json = json_times.iter().sum::<f64>() / json_times.len() as f64;
-        let avg_avro = avro_times.iter().sum::<f64>() / avro_times.len() as f64;
-        let avg_protobuf = protobuf_times.iter().sum::<f64>() / protobuf_times.len() as f64;
-
-        let metrics = json!({
-            "iterations": iterations,
-            "json_schema": {
-                "avg_ms": format!("{:.3}", avg_json),
-                "min_ms": format!("{:.3}", json_times.iter().cloned().fold(f64::INFINITY, f64::min)),
-                "max_ms": format!("{:.3}", json_times.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
-            },
-            "avro": {
-                "avg_ms": format!("{:.3}", avg_avro),
-                "min_ms": format!("{:.3}", avro_times.iter().cloned().fold(f64::INFINITY, f64::min)),
-                "max_ms": format!("{:.3}", avro_times.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
-            },
-            "protobuf": {
-                "avg_ms": format!("{:.3}", avg_protobuf),
-                "min_ms": format!("{:.3}", protobuf_times.iter().cloned().fold(f64::INFINITY, f64::min)),
-                "max_ms": format!("{:.3}", protobuf_times.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
+    async fn run_with_config(&self, cfg: &BenchConfig) -> Result<BenchmarkResult> {
+        cfg.validate()?;
+
+        let mut samples = self.samples.take();
+        samples.begin_run(cfg.sample_capacity());
+        samples.set_outlier_policy(cfg.outliers);
+        let series = SERIES.map(|label| samples.series(label));
+        let schemas = self.schemas(cfg.seed);
+
+        let mut invalid = [0_usize; FORMATS.len()];
+        let mut iterations = cfg.iterations();
+        for i in iterations.by_ref() {
+            let mut timings = [(0.0, true); 3];
+            for ((timing, schema), format) in timings.iter_mut().zip(&schemas).zip(FORMATS) {
+                *timing = self.bench_validation(schema, format).await?;
+            }
+            record_iteration(i, cfg.is_warmup(i), timings.iter().map(|(ms, _)| ms).sum());
+            if cfg.is_warmup(i) {
+                continue;
             }
-        });
+            for ((id, (ms, valid)), count) in series.iter().zip(timings).zip(&mut invalid) {
+                samples.record(*id, ms);
+                *count += usize::from(!valid);
+            }
+        }
 
-        Ok(BenchmarkResult::new(self.id().to_string(), metrics))
+        let by_format = FORMATS
+            .iter()
+            .zip(&series)
+            .zip(invalid)
+            .map(|((&format, &id), invalid_schemas)| {
+                let metrics = ValidationFormatMetrics {
+                    invalid_schemas,
+                    latency: samples.metrics(id),
+                };
+                (format_key(format).to_string(), metrics)
+            })
+            .collect();
+        let metrics = ValidationMetrics {
+            counts: iterations.counts(),
+            invalid_schemas: invalid.iter().sum(),
+            by_format,
+        };
+        let result = BenchmarkResult::new(self.id().to_string(), serde_json::to_value(metrics)?)
+            .with_raw_samples(&samples);
+        self.samples.restore(samples);
+
+        Ok(result)
     }
 }
 
@@ -138,9 +200,10 @@ mod tests {
 
         // Verify metrics structure
         assert!(result.metrics.get("iterations").is_some());
-        assert!(result.metrics.get("json_schema").is_some());
-        assert!(result.metrics.get("avro").is_some());
-        assert!(result.metrics.get("protobuf").is_some());
+        for format in bench.formats() {
+            assert!(result.metrics_for_format(*format).is_some(), "{:?}", format);
+        }
+        assert!(result.metrics.get("avro").is_none());
     }
 
     #[tokio::test]
@@ -149,42 +212,54 @@ mod tests {
         let result = bench.run().await.unwrap();
 
         // Check JSON schema metrics
-        let json = result.metrics.get("json_schema").unwrap();
+        let json = result.metrics_for_format(SerializationFormat::JsonSchema).unwrap();
         assert!(json.get("avg_ms").is_some());
+        assert!(json.get("p95_ms").is_some());
         assert!(json.get("min_ms").is_some());
         assert!(json.get("max_ms").is_some());
 
         // Check Avro metrics
-        let avro = result.metrics.get("avro").unwrap();
+        let avro = result.metrics_for_format(SerializationFormat::Avro).unwrap();
         assert!(avro.get("avg_ms").is_some());
+        assert!(avro.get("p95_ms").is_some());
         assert!(avro.get("min_ms").is_some());
         assert!(avro.get("max_ms").is_some());
 
         // Check Protobuf metrics
-        let protobuf = result.metrics.get("protobuf").unwrap();
+        let protobuf = result.metrics_for_format(SerializationFormat::Protobuf).unwrap();
         assert!(protobuf.get("avg_ms").is_some());
+        assert!(protobuf.get("p95_ms").is_some());
         assert!(protobuf.get("min_ms").is_some());
         assert!(protobuf.get("max_ms").is_some());
     }
 
     #[tokio::test]
-    async fn test_bench_json_validation() {
+    async fn test_bench_validation() {
         let bench = ValidationBenchmark::new();
-        let duration = bench.bench_json_validation().await;
-        assert!(duration > 0.0);
+        for (format, schema) in FORMATS.into_iter().zip(bench.schemas(0)) {
+            let (duration, _) = bench.bench_validation(&schema, format).await.unwrap();
+            assert!(duration > 0.0, "{:?}", format);
+        }
     }
 
-    #[tokio::test]
-    async fn test_bench_avro_validation() {
+    #[test]
+    fn test_schemas_follow_the_seed() {
         let bench = ValidationBenchmark::new();
-        let duration = bench.bench_avro_validation().await;
-        assert!(duration > 0.0);
+        assert_eq!(bench.schemas(7), bench.schemas(7));
+        assert_ne!(bench.schemas(7), bench.schemas(8));
+        assert!(bench.schemas(0).iter().all(|schema| schema.len() > 1024));
     }
 
     #[tokio::test]
-    async fn test_bench_protobuf_validation() {
-        let bench = ValidationBenchmark::new();
-        let duration = bench.bench_protobuf_validation().await;
-        assert!(duration > 0.0);
+    async fn test_invalid_schemas_are_counted_not_fatal() {
+        let bench = ValidationBenchmark::new().with_schema(SerializationFormat::Avro, "{not avro");
+        assert_eq!(bench.schemas(0)[1], "{not avro");
+
+        let result = bench.run_with_config(&BenchConfig::new(1, 3)).await.unwrap();
+        let metrics: ValidationMetrics = serde_json::from_value(result.metrics.clone()).unwrap();
+        assert!(metrics.invalid_schemas >= 3);
+        assert_eq!(metrics.by_format["avro"].invalid_schemas, 3);
+        assert!(metrics.by_format["avro"].latency.max_ms > 0.0);
+        assert_eq!(result.metrics["invalid_schemas"]["unit"], "count");
     }
 }
@@ -0,0 +1,226 @@
+//! Archiving raw benchmark results to object storage
+//!
+//! Needs the `object-store` feature. [`ObjectStoreSink`] is a
+//! [`ResultSink`] that uploads the results as JSON, and optionally the
+//! markdown summary, under a key rendered from a template:
+//!
+//! | Placeholder | Value |
+//! |-------------|-------|
+//! | `{date}` | Date of the earliest result, `2024-05-01` |
+//! | `{timestamp}` | Time of the earliest result, `20240501_120000` |
+//! | `{git_sha}` | Short commit of the run environment, or `unknown` |
+//! | `{branch}` | Git branch of the run environment, or `unknown` |
+//!
+//! S3 credentials and region come from the standard `AWS_*` environment.
+
+use crate::sink::ResultSink;
+use crate::{markdown, BenchmarkResult};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use object_store::path::Path as ObjectPath;
+use object_store::{ObjectStore, PutPayload};
+use std::sync::Arc;
+
+/// Key of the raw results unless [`ObjectStoreSink::with_key_template`] sets another
+pub const DEFAULT_KEY_TEMPLATE: &str = "benchmarks/{date}/{timestamp}_{git_sha}.json";
+
+/// Uploads results to an object store such as an S3 bucket
+#[derive(Debug, Clone)]
+pub struct ObjectStoreSink {
+    store: Arc<dyn ObjectStore>,
+    /// Where the sink uploads, for [`ResultSink::name`]
+    location: String,
+    /// Key prefix every rendered key is placed under
+    prefix: String,
+    key_template: String,
+    include_summary: bool,
+}
+
+impl ObjectStoreSink {
+    /// Upload to `store`, naming the sink `location` in reports
+    pub fn new(store: Arc<dyn ObjectStore>, location: impl Into<String>) -> Self {
+        Self {
+            store,
+            location: location.into(),
+            prefix: String::new(),
+            key_template: DEFAULT_KEY_TEMPLATE.to_string(),
+            include_summary: false,
+        }
+    }
+
+    /// Sink for a URL such as `s3://bucket/prefix`, `file:///srv/archive` or `memory://`
+    ///
+    /// The path of an `s3://` URL becomes the key prefix; credentials and
+    /// region are read from the environment.
+    pub fn from_url(url: &str) -> Result<Self> {
+        let Some((scheme, rest)) = url.split_once("://") else {
+            bail!("Invalid archive URL '{}': expected e.g. s3://bucket/prefix", url);
+        };
+        let store: Arc<dyn ObjectStore> = match scheme {
+            "s3" => {
+                let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+                if bucket.is_empty() {
+                    bail!("Invalid archive URL '{}': missing bucket", url);
+                }
+                let store = object_store::aws::AmazonS3Builder::from_env()
+                    .with_bucket_name(bucket)
+                    .build()
+                    .with_context(|| format!("Failed to configure S3 for {}", url))?;
+                return Ok(Self::new(Arc::new(store), url).with_prefix(prefix));
+            }
+            "file" => {
+                std::fs::create_dir_all(rest).with_context(|| format!("Failed to create directory: {}", rest))?;
+                let store = object_store::local::LocalFileSystem::new_with_prefix(rest)
+                    .with_context(|| format!("Failed to open archive directory {}", rest))?;
+                Arc::new(store)
+            }
+            "memory" => Arc::new(object_store::memory::InMemory::new()),
+            _ => bail!("Unsupported archive URL '{}': expected s3://, file:// or memory://", url),
+        };
+        Ok(Self::new(store, url))
+    }
+
+    /// Place every key under `prefix`
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into().trim_matches('/').to_string();
+        self
+    }
+
+    /// Render keys of the raw results from `template`, see the [module docs](self)
+    pub fn with_key_template(mut self, template: impl Into<String>) -> Self {
+        self.key_template = template.into();
+        self
+    }
+
+    /// Also upload the markdown summary, next to the results with an `.md` extension
+    pub fn with_summary(mut self, include_summary: bool) -> Self {
+        self.include_summary = include_summary;
+        self
+    }
+
+    /// Key of the raw results for `results`
+    pub fn results_key(&self, results: &[BenchmarkResult]) -> Result<String> {
+        let key = render_key(&self.key_template, results)?;
+        Ok(match self.prefix.is_empty() {
+            true => key,
+            false => format!("{}/{}", self.prefix, key),
+        })
+    }
+
+    async fn upload(&self, key: &str, content: Vec<u8>) -> Result<()> {
+        let path = ObjectPath::parse(key).with_context(|| format!("Invalid archive key '{}'", key))?;
+        self.store
+            .put(&path, PutPayload::from(content))
+            .await
+            .with_context(|| format!("Failed to upload {} to {}", key, self.location))?;
+        Ok(())
+    }
+}
+
+/// `template` with its placeholders filled in from `results`
+fn render_key(template: &str, results: &[BenchmarkResult]) -> Result<String> {
+    let timestamp = results.iter().map(|result| result.timestamp).min().unwrap_or_else(Utc::now);
+    let environment = results.iter().find_map(|result| result.environment.as_ref());
+    let git_sha = environment.and_then(|env| env.short_commit()).unwrap_or("unknown");
+    let branch = environment.and_then(|env| env.git_branch.as_deref()).unwrap_or("unknown");
+
+    let mut key = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        key.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            bail!("Unclosed placeholder in key template '{}'", template);
+        };
+        match &rest[start + 1..start + end] {
+            "date" => key.push_str(&timestamp.format("%Y-%m-%d").to_string()),
+            "timestamp" => key.push_str(&timestamp.format("%Y%m%d_%H%M%S").to_string()),
+            "git_sha" => key.push_str(git_sha),
+            "branch" => key.push_str(&branch.replace('/', "-")),
+            other => bail!("Unknown placeholder {{{}}} in key template '{}'", other, template),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    key.push_str(rest);
+    Ok(key)
+}
+
+#[async_trait]
+impl ResultSink for ObjectStoreSink {
+    fn name(&self) -> String {
+        self.location.clone()
+    }
+
+    async fn publish(&self, results: &[BenchmarkResult]) -> Result<()> {
+        let key = self.results_key(results)?;
+        let json = serde_json::to_vec_pretty(results).context("Failed to serialize benchmark results")?;
+        self.upload(&key, json).await?;
+
+        if self.include_summary {
+            let stem = key.strip_suffix(".json").unwrap_or(&key);
+            let summary = markdown::generate_summary(results);
+            self.upload(&format!("{}.md", stem), summary.into_bytes()).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RunEnvironment;
+    use chrono::TimeZone;
+    use object_store::memory::InMemory;
+    use serde_json::json;
+
+    fn results() -> Vec<BenchmarkResult> {
+        let mut result = BenchmarkResult::new("storage".to_string(), json!({"write": {"p95_ms": 2.0}}));
+        result.timestamp = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap();
+        result.environment = Some(RunEnvironment {
+            git_commit: Some("0123456789abcdef0123".to_string()),
+            git_branch: Some("feature/archive".to_string()),
+            ..RunEnvironment::default()
+        });
+        vec![result]
+    }
+
+    async fn read(store: &InMemory, key: &str) -> Vec<u8> {
+        store.get(&ObjectPath::parse(key).unwrap()).await.unwrap().bytes().await.unwrap().to_vec()
+    }
+
+    #[test]
+    fn test_render_key() {
+        assert_eq!(
+            render_key("{branch}/{date}/{timestamp}_{git_sha}.json", &results()).unwrap(),
+            "feature-archive/2024-05-01/20240501_123000_0123456789ab.json"
+        );
+        assert_eq!(render_key("{git_sha}.json", &[]).unwrap(), "unknown.json");
+        assert!(render_key("{commit}.json", &results()).is_err());
+        assert!(render_key("{date.json", &results()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_uploads_results_and_summary() {
+        let store = Arc::new(InMemory::new());
+        let sink = ObjectStoreSink::new(store.clone(), "memory://")
+            .with_prefix("/ci/")
+            .with_key_template("{date}/{git_sha}.json")
+            .with_summary(true);
+
+        sink.publish(&results()).await.unwrap();
+
+        let uploaded: Vec<BenchmarkResult> =
+            serde_json::from_slice(&read(&store, "ci/2024-05-01/0123456789ab.json").await).unwrap();
+        assert_eq!(uploaded[0].target_id, "storage");
+        let summary = String::from_utf8(read(&store, "ci/2024-05-01/0123456789ab.md").await).unwrap();
+        assert!(summary.contains("storage"));
+    }
+
+    #[test]
+    fn test_from_url() {
+        assert_eq!(ObjectStoreSink::from_url("memory://").unwrap().name(), "memory://");
+        assert!(ObjectStoreSink::from_url("ftp://host/results").is_err());
+        assert!(ObjectStoreSink::from_url("bucket/prefix").is_err());
+        assert!(ObjectStoreSink::from_url("s3:///prefix").is_err());
+    }
+}
@@ -0,0 +1,158 @@
+//! Link-time registration of benchmark targets
+//!
+//! With the `auto-register` feature, any crate linked into the benchmark
+//! binary can add targets to [`adapters::all_targets`] by naming a
+//! constructor, instead of every caller collecting them by hand:
+//!
+//! ```ignore
+//! fn cache_target() -> Box<dyn BenchTarget> {
+//!     Box::new(CacheBenchmark::new())
+//! }
+//!
+//! schema_registry_benchmarks::register_bench_target!(cache_target);
+//! ```
+//!
+//! Registered targets follow the built-in ones, sorted by id so that link
+//! order never changes result order. An id registered twice panics when the
+//! targets are collected, naming both registrations.
+//!
+//! [`adapters::all_targets`]: crate::adapters::all_targets
+
+use crate::adapters::BenchTarget;
+use std::collections::HashMap;
+
+#[doc(hidden)]
+pub use inventory;
+
+/// Source named for the targets listed in `adapters::all_targets`
+const BUILTIN_SOURCE: &str = "built-in adapters";
+
+/// Target constructor submitted by [`register_bench_target!`](crate::register_bench_target)
+pub struct TargetRegistration {
+    constructor: fn() -> Box<dyn BenchTarget>,
+    source: &'static str,
+}
+
+impl TargetRegistration {
+    /// `source` names the registration in duplicate-id panics
+    pub const fn new(constructor: fn() -> Box<dyn BenchTarget>, source: &'static str) -> Self {
+        Self { constructor, source }
+    }
+
+    /// Path of the constructor that was registered
+    pub fn source(&self) -> &'static str {
+        self.source
+    }
+}
+
+inventory::collect!(TargetRegistration);
+
+/// Register a `fn() -> Box<dyn BenchTarget>` with [`adapters::all_targets`](crate::adapters::all_targets)
+#[macro_export]
+macro_rules! register_bench_target {
+    ($constructor:path) => {
+        $crate::auto_register::inventory::submit! {
+            $crate::auto_register::TargetRegistration::new(
+                $constructor,
+                concat!(module_path!(), "::", stringify!($constructor)),
+            )
+        }
+    };
+}
+
+/// `builtin` followed by every registered target
+pub(crate) fn append_registered(builtin: Vec<Box<dyn BenchTarget>>) -> Vec<Box<dyn BenchTarget>> {
+    let registered = inventory::iter::<TargetRegistration>
+        .into_iter()
+        .map(|registration| (registration.source, (registration.constructor)()))
+        .collect();
+    merge(builtin, registered)
+}
+
+fn merge(
+    builtin: Vec<Box<dyn BenchTarget>>,
+    mut registered: Vec<(&'static str, Box<dyn BenchTarget>)>,
+) -> Vec<Box<dyn BenchTarget>> {
+    registered.sort_by(|(a_source, a), (b_source, b)| a.id().cmp(b.id()).then(a_source.cmp(b_source)));
+
+    let mut sources: HashMap<&str, &str> = builtin.iter().map(|target| (target.id(), BUILTIN_SOURCE)).collect();
+    for (source, target) in &registered {
+        if let Some(first) = sources.insert(target.id(), *source) {
+            panic!(
+                "benchmark target id `{}` is registered twice: by {} and by {}",
+                target.id(),
+                first,
+                source
+            );
+        }
+    }
+
+    builtin
+        .into_iter()
+        .chain(registered.into_iter().map(|(_, target)| target))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BenchmarkResult;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    struct Named(&'static str);
+
+    #[async_trait]
+    impl BenchTarget for Named {
+        fn id(&self) -> &str {
+            self.0
+        }
+
+        fn description(&self) -> &str {
+            "auto-registered"
+        }
+
+        async fn run(&self) -> Result<BenchmarkResult> {
+            Ok(BenchmarkResult::new(self.0.to_string(), json!({"avg_ms": 1.0})))
+        }
+    }
+
+    fn named(id: &'static str) -> Box<dyn BenchTarget> {
+        Box::new(Named(id))
+    }
+
+    fn ids(targets: &[Box<dyn BenchTarget>]) -> Vec<&str> {
+        targets.iter().map(|target| target.id()).collect()
+    }
+
+    #[test]
+    fn test_registered_targets_follow_builtins_sorted_by_id() {
+        let builtin = vec![named("storage"), named("validation")];
+        let registered = vec![
+            ("plugin_b::target", named("zeta")),
+            ("plugin_a::target", named("alpha")),
+        ];
+
+        let targets = merge(builtin, registered);
+        assert_eq!(ids(&targets), vec!["storage", "validation", "alpha", "zeta"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "benchmark target id `alpha` is registered twice: by plugin_a::first and by plugin_b::second")]
+    fn test_duplicate_registered_ids_panic_with_both_sources() {
+        merge(
+            Vec::new(),
+            vec![
+                ("plugin_b::second", named("alpha")),
+                ("plugin_a::first", named("alpha")),
+            ],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "registered twice: by built-in adapters and by plugin::storage")]
+    fn test_registered_id_clashing_with_builtin_panics() {
+        merge(vec![named("storage")], vec![("plugin::storage", named("storage"))]);
+    }
+}
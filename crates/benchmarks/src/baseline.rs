@@ -0,0 +1,331 @@
+//! Named baselines
+//!
+//! A baseline is a set of results kept under a name (`main`, `v0.3.0`, ...)
+//! so later runs can be compared against it. Baselines live in
+//! `benchmarks/output/baselines/<name>.json` together with when they were
+//! created, the commit they measure and an optional note:
+//!
+//! ```json
+//! {
+//!   "name": "main",
+//!   "created_at": "2025-06-01T12:00:00Z",
+//!   "git_sha": "4f1c...",
+//!   "note": "after the cache rewrite",
+//!   "format_version": 3,
+//!   "results": [ ... ]
+//! }
+//! ```
+//!
+//! Loading checks `format_version` and upgrades the results with
+//! [`migrations`](crate::io::migrations), so baselines saved by older builds
+//! stay usable and ones saved by newer builds are rejected.
+
+use crate::io::{self, migrations};
+use crate::BenchmarkResult;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory of the default [`BaselineStore`]
+pub fn default_baselines_dir() -> PathBuf {
+    io::OutputLayout::default().baselines_dir
+}
+
+/// Everything about a baseline except its results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineMetadata {
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    /// Commit the results were measured at, from the results' environment
+    /// or else the current checkout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_sha: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Results file the baseline was promoted from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub promoted_from: Option<String>,
+    /// Result format version of `results` when saved
+    pub format_version: u32,
+}
+
+/// A named, saved set of results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    #[serde(flatten)]
+    pub metadata: BaselineMetadata,
+    pub results: Vec<BenchmarkResult>,
+}
+
+/// Directory of named baselines
+#[derive(Debug, Clone)]
+pub struct BaselineStore {
+    dir: PathBuf,
+}
+
+impl Default for BaselineStore {
+    fn default() -> Self {
+        Self::new(default_baselines_dir())
+    }
+}
+
+impl BaselineStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Store in the baselines directory of `layout`
+    pub fn for_layout(layout: &io::OutputLayout) -> Self {
+        Self::new(layout.baselines_dir.clone())
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// File of the baseline called `name`
+    pub fn path(&self, name: &str) -> Result<PathBuf> {
+        validate_name(name)?;
+        Ok(self.dir.join(format!("{}.json", name)))
+    }
+
+    /// Save `results` as `name`, replacing any baseline of that name
+    pub fn save(&self, results: &[BenchmarkResult], name: &str, note: Option<&str>) -> Result<Baseline> {
+        self.write(results.to_vec(), name, note, None)
+    }
+
+    /// Save the results file `from_run` (e.g. `raw/latest.json`) as `to_name`
+    pub fn promote(&self, from_run: &Path, to_name: &str, note: Option<&str>) -> Result<Baseline> {
+        let results = io::read_json(from_run)?;
+        self.write(results, to_name, note, Some(from_run.display().to_string()))
+    }
+
+    fn write(
+        &self,
+        results: Vec<BenchmarkResult>,
+        name: &str,
+        note: Option<&str>,
+        promoted_from: Option<String>,
+    ) -> Result<Baseline> {
+        let path = self.path(name)?;
+        let git_sha = results
+            .iter()
+            .find_map(|result| result.environment.as_ref()?.git_commit.clone())
+            .or_else(crate::environment::git_commit);
+        let baseline = Baseline {
+            metadata: BaselineMetadata {
+                name: name.to_string(),
+                created_at: Utc::now(),
+                git_sha,
+                note: note.map(str::to_string),
+                promoted_from,
+                format_version: migrations::CURRENT_FORMAT_VERSION,
+            },
+            results,
+        };
+
+        let json = serde_json::to_string_pretty(&baseline).context("Failed to serialize baseline")?;
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create directory: {}", self.dir.display()))?;
+        fs::write(&path, json).with_context(|| format!("Failed to write baseline to {}", path.display()))?;
+        Ok(baseline)
+    }
+
+    /// Load the baseline called `name`, upgrading results saved by older builds
+    pub fn load(&self, name: &str) -> Result<Baseline> {
+        let path = self.path(name)?;
+        if !path.exists() {
+            bail!("No baseline named '{}' in {}", name, self.dir.display());
+        }
+        let baseline = read_baseline(&path)?;
+        if baseline.metadata.name != name {
+            bail!(
+                "Baseline {} is named '{}'; it was renamed by hand",
+                path.display(),
+                baseline.metadata.name
+            );
+        }
+        Ok(baseline)
+    }
+
+    /// Metadata of every baseline, sorted by name
+    pub fn list(&self) -> Result<Vec<BaselineMetadata>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to list baselines in {}", self.dir.display())),
+        };
+
+        let mut baselines = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read baseline from {}", path.display()))?;
+            let metadata: BaselineMetadata = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse baseline {}", path.display()))?;
+            baselines.push(metadata);
+        }
+        baselines.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(baselines)
+    }
+}
+
+/// Read a baseline file, checking its format version and upgrading its results
+pub fn read_baseline(path: &Path) -> Result<Baseline> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read baseline from {}", path.display()))?;
+    let document: serde_json::Value =
+        serde_json::from_str(&content).with_context(|| format!("Failed to parse baseline {}", path.display()))?;
+    baseline_from_value(document, path)
+}
+
+fn baseline_from_value(mut document: serde_json::Value, path: &Path) -> Result<Baseline> {
+    let version = document.get("format_version").and_then(serde_json::Value::as_u64);
+    match version {
+        Some(version) if version > u64::from(migrations::CURRENT_FORMAT_VERSION) => bail!(
+            "Baseline {} uses result format version {}, but this build reads versions up to {}",
+            path.display(),
+            version,
+            migrations::CURRENT_FORMAT_VERSION
+        ),
+        Some(_) => {}
+        None => bail!("Baseline {} has no format_version", path.display()),
+    }
+    if let Some(results) = document.get_mut("results") {
+        migrations::migrate_results(results)
+            .with_context(|| format!("Cannot read baseline from {}", path.display()))?;
+    }
+
+    let mut baseline: Baseline =
+        serde_json::from_value(document).with_context(|| format!("Failed to parse baseline {}", path.display()))?;
+    baseline.metadata.format_version = migrations::CURRENT_FORMAT_VERSION;
+    Ok(baseline)
+}
+
+/// Results of either a results file written by [`io::write_json`] or a saved baseline
+pub fn read_results(path: &Path) -> Result<Vec<BenchmarkResult>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read benchmark results from {}", path.display()))?;
+    let document: serde_json::Value =
+        serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+    if document.is_object() {
+        return Ok(baseline_from_value(document, path)?.results);
+    }
+    io::read_json(path)
+}
+
+/// Names become file names, so keep them to a safe character set
+fn validate_name(name: &str) -> Result<()> {
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if name.is_empty() || name.starts_with('.') || !valid_chars {
+        bail!(
+            "Invalid baseline name '{}': use letters, digits, '-', '_' and '.', not starting with '.'",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Save `results` as `name` in the default [`BaselineStore`]
+pub fn save_baseline(results: &[BenchmarkResult], name: &str) -> Result<Baseline> {
+    BaselineStore::default().save(results, name, None)
+}
+
+/// Load `name` from the default [`BaselineStore`]
+pub fn load_baseline(name: &str) -> Result<Baseline> {
+    BaselineStore::default().load(name)
+}
+
+/// Save the results file `from_run` as `to_name` in the default [`BaselineStore`]
+pub fn promote(from_run: &Path, to_name: &str) -> Result<Baseline> {
+    BaselineStore::default().promote(from_run, to_name, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn results() -> Vec<BenchmarkResult> {
+        vec![BenchmarkResult::new(
+            "storage_write".to_string(),
+            json!({"write": {"avg_ms": 1.5}}),
+        )]
+    }
+
+    #[test]
+    fn test_save_load_and_list() {
+        let dir = TempDir::new().unwrap();
+        let store = BaselineStore::new(dir.path());
+
+        store.save(&results(), "main", Some("nightly")).unwrap();
+        store.save(&results(), "v0.3.0", None).unwrap();
+
+        let loaded = store.load("main").unwrap();
+        assert_eq!(loaded.metadata.note.as_deref(), Some("nightly"));
+        assert_eq!(loaded.metadata.format_version, migrations::CURRENT_FORMAT_VERSION);
+        assert_eq!(loaded.results[0].target_id, "storage_write");
+
+        let names: Vec<_> = store.list().unwrap().into_iter().map(|b| b.name).collect();
+        assert_eq!(names, vec!["main", "v0.3.0"]);
+
+        assert!(store.load("missing").is_err());
+        assert!(store.save(&results(), "../escape", None).is_err());
+        assert!(store.save(&results(), "", None).is_err());
+    }
+
+    #[test]
+    fn test_promote_records_source() {
+        let dir = TempDir::new().unwrap();
+        let run = dir.path().join("latest.json");
+        io::write_json(&results(), &run).unwrap();
+
+        let store = BaselineStore::new(dir.path().join("baselines"));
+        let baseline = store.promote(&run, "main", None).unwrap();
+        assert_eq!(baseline.metadata.promoted_from, Some(run.display().to_string()));
+        assert_eq!(store.load("main").unwrap().results.len(), 1);
+    }
+
+    #[test]
+    fn test_read_results_accepts_baselines_and_result_files() {
+        let dir = TempDir::new().unwrap();
+        let run = dir.path().join("latest.json");
+        io::write_json(&results(), &run).unwrap();
+        let store = BaselineStore::new(dir.path());
+        store.save(&results(), "main", None).unwrap();
+
+        assert_eq!(read_results(&run).unwrap().len(), 1);
+        assert_eq!(read_results(&store.path("main").unwrap()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_load_checks_format_version() {
+        let dir = TempDir::new().unwrap();
+        let store = BaselineStore::new(dir.path());
+
+        fs::write(
+            dir.path().join("old.json"),
+            r#"{"name": "old", "created_at": "2025-01-01T00:00:00Z", "format_version": 1,
+                "results": [{"target_id": "t", "metrics": {"write": {"avg_ms": "0.5"}}, "timestamp": "2025-01-01T00:00:00Z"}]}"#,
+        )
+        .unwrap();
+        let old = store.load("old").unwrap();
+        assert_eq!(old.results[0].metrics["write"]["avg_ms"]["value"], 0.5);
+
+        fs::write(
+            dir.path().join("future.json"),
+            r#"{"name": "future", "created_at": "2030-01-01T00:00:00Z", "format_version": 99, "results": []}"#,
+        )
+        .unwrap();
+        let err = format!("{:#}", store.load("future").unwrap_err());
+        assert!(err.contains("format version 99"), "{err}");
+    }
+}
@@ -0,0 +1,587 @@
+//! Comparing benchmark results against a baseline
+//!
+//! [`compare_results`] matches a current run against a baseline (typically
+//! `latest.json` saved from main) by target id and metric path, and classifies
+//! every numeric metric. Whether a change is an improvement depends on the
+//! metric: timings and byte counts are better when lower, throughput when
+//! higher. Metrics without a better direction, such as `iterations` or
+//! `seed`, are compared but always reported as unchanged.
+//!
+//! The [`RegressionReport`] serializes to JSON and renders as markdown with
+//! [`markdown::generate_comparison`](crate::markdown::generate_comparison).
+//! For CI, [`RegressionReport::evaluate`] turns it into a pass/fail
+//! [`GateResult`] under [`RegressionThresholds`].
+//!
+//! Results collected with the `rusage-metrics` feature also carry CPU time
+//! under `resource_usage`. It is noisier to collect but less sensitive to a
+//! busy machine than wall time, so a gate can
+//! [prefer it](RegressionThresholds::with_prefer_cpu_time).
+
+use crate::metrics::RESOURCE_USAGE_KEY;
+use crate::units::{Measurement, Unit};
+use crate::BenchmarkResult;
+use schema_registry_core::metric_names::{self, MetricUnit};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+/// Relative change below which a metric counts as unchanged
+pub const DEFAULT_THRESHOLD_PERCENT: f64 = 5.0;
+
+/// Largest regression [`RegressionThresholds::default`] allows, in percent
+pub const DEFAULT_MAX_REGRESSION_PERCENT: f64 = 10.0;
+
+/// Which way a metric improves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    LowerIsBetter,
+    HigherIsBetter,
+}
+
+impl Direction {
+    /// Direction of the metric with leaf key `name`, `None` when neither is better
+    pub fn of(name: &str) -> Option<Self> {
+        // Settings echoed into the metrics rather than measurements
+        if name == metric_names::BENCH_SAMPLE_TIME_MS.name || name == metric_names::BENCH_TTL_SECONDS.name {
+            return None;
+        }
+        if name == metric_names::BENCH_OVERHEAD_PERCENT.name {
+            return Some(Self::LowerIsBetter);
+        }
+        match metric_names::lookup(name)?.unit {
+            MetricUnit::Seconds | MetricUnit::Milliseconds | MetricUnit::Bytes => Some(Self::LowerIsBetter),
+            MetricUnit::PerSecond | MetricUnit::BytesPerSecond => Some(Self::HigherIsBetter),
+            MetricUnit::Count | MetricUnit::Ratio | MetricUnit::Percent => None,
+        }
+    }
+}
+
+/// How a metric changed from the baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricStatus {
+    Improved,
+    Regressed,
+    Unchanged,
+    /// Present on only one side, or recorded in units that don't convert
+    Missing,
+}
+
+/// One metric of one target, in the baseline and the current run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricComparison {
+    pub target_id: String,
+    /// Dotted path into the target's metrics, e.g. `write.p95_ms`
+    pub path: String,
+    pub baseline: Option<f64>,
+    pub current: Option<f64>,
+    /// Unit of `baseline`, `current` and `delta`; the baseline's when both sides have one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<Unit>,
+    /// `current - baseline`, when both are present
+    pub delta: Option<f64>,
+    /// `delta` as a percentage of the baseline, absent when the baseline is zero
+    pub delta_percent: Option<f64>,
+    pub status: MetricStatus,
+}
+
+/// Every metric of a current run compared with a baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegressionReport {
+    /// Changes smaller than this percentage count as unchanged
+    pub threshold_percent: f64,
+    /// Comparisons ordered by target id, then metric path
+    pub metrics: Vec<MetricComparison>,
+    /// Targets only in the baseline
+    pub baseline_only: Vec<String>,
+    /// Targets only in the current run
+    pub current_only: Vec<String>,
+}
+
+impl RegressionReport {
+    /// Metrics with the given status
+    pub fn with_status(&self, status: MetricStatus) -> impl Iterator<Item = &MetricComparison> {
+        self.metrics.iter().filter(move |metric| metric.status == status)
+    }
+
+    /// Metrics that got worse by more than the threshold
+    pub fn regressions(&self) -> impl Iterator<Item = &MetricComparison> {
+        self.with_status(MetricStatus::Regressed)
+    }
+
+    pub fn has_regressions(&self) -> bool {
+        self.regressions().next().is_some()
+    }
+
+    /// Metrics that got worse by more than `thresholds` allow
+    ///
+    /// Only the direction and size of each change count, not the report's own
+    /// threshold, so a gate can be stricter than the classification.
+    /// Improvements never fail the gate, and neither do metrics without a
+    /// better direction or present on only one side. A regression from a zero
+    /// baseline has no percentage and always fails.
+    ///
+    /// With [`prefer_cpu_time`](RegressionThresholds::prefer_cpu_time), the
+    /// wall-time metrics of a target whose CPU time was measured on both sides
+    /// are left out, so only its CPU time and other metrics can fail the gate.
+    pub fn evaluate(&self, thresholds: &RegressionThresholds) -> GateResult {
+        let cpu_timed: BTreeSet<&str> = self
+            .metrics
+            .iter()
+            .filter(|metric| thresholds.prefer_cpu_time && is_cpu_time(&metric.path))
+            .filter(|metric| metric.baseline.is_some() && metric.current.is_some())
+            .map(|metric| metric.target_id.as_str())
+            .collect();
+        let violations = self
+            .metrics
+            .iter()
+            .filter(|metric| !(cpu_timed.contains(metric.target_id.as_str()) && is_wall_time(&metric.path)))
+            .filter_map(|metric| {
+                let (baseline, current) = (metric.baseline?, metric.current?);
+                let leaf = metric.path.rsplit('.').next().unwrap_or(&metric.path);
+                let worse = match Direction::of(leaf)? {
+                    Direction::LowerIsBetter => current > baseline,
+                    Direction::HigherIsBetter => current < baseline,
+                };
+                let allowed = thresholds.allowed_for(&metric.target_id);
+                let exceeded = metric.delta_percent.is_none_or(|percent| percent.abs() > allowed);
+                (worse && exceeded).then(|| GateViolation {
+                    target_id: metric.target_id.clone(),
+                    path: metric.path.clone(),
+                    baseline,
+                    current,
+                    delta_percent: metric.delta_percent,
+                    allowed_percent: allowed,
+                })
+            })
+            .collect();
+        GateResult { violations }
+    }
+
+    /// Number of metrics with each status
+    pub fn counts(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for metric in &self.metrics {
+            *counts.entry(status_name(metric.status)).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+/// How much each metric may regress before the gate fails
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegressionThresholds {
+    /// Largest allowed regression, in percent of the baseline
+    pub max_regression_percent: f64,
+    /// Allowed regression for specific target ids, replacing the maximum
+    #[serde(default)]
+    pub per_target_overrides: BTreeMap<String, f64>,
+    /// Gate on CPU time instead of wall time where both sides measured it
+    #[serde(default)]
+    pub prefer_cpu_time: bool,
+}
+
+impl RegressionThresholds {
+    pub fn new(max_regression_percent: f64) -> Self {
+        Self {
+            max_regression_percent,
+            per_target_overrides: BTreeMap::new(),
+            prefer_cpu_time: false,
+        }
+    }
+
+    /// Gate on CPU time instead of wall time for targets that report both
+    pub fn with_prefer_cpu_time(mut self, prefer: bool) -> Self {
+        self.prefer_cpu_time = prefer;
+        self
+    }
+
+    /// Allow `target_id` to regress by up to `percent` instead
+    pub fn with_target_override(mut self, target_id: impl Into<String>, percent: f64) -> Self {
+        self.per_target_overrides.insert(target_id.into(), percent);
+        self
+    }
+
+    /// Allowed regression of `target_id`'s metrics, in percent
+    pub fn allowed_for(&self, target_id: &str) -> f64 {
+        self.per_target_overrides
+            .get(target_id)
+            .copied()
+            .unwrap_or(self.max_regression_percent)
+    }
+}
+
+impl Default for RegressionThresholds {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_REGRESSION_PERCENT)
+    }
+}
+
+/// A metric that regressed by more than allowed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GateViolation {
+    pub target_id: String,
+    pub path: String,
+    pub baseline: f64,
+    pub current: f64,
+    /// Change in percent of the baseline, absent when the baseline is zero
+    pub delta_percent: Option<f64>,
+    pub allowed_percent: f64,
+}
+
+impl fmt::Display for GateViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {} -> {}", self.target_id, self.path, self.baseline, self.current)?;
+        match self.delta_percent {
+            Some(percent) => write!(f, " ({:+.1}%, allowed {}%)", percent, self.allowed_percent),
+            None => write!(f, " (from a zero baseline)"),
+        }
+    }
+}
+
+/// Outcome of [`RegressionReport::evaluate`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GateResult {
+    /// Every metric over its allowed regression, ordered like the report
+    pub violations: Vec<GateViolation>,
+}
+
+impl GateResult {
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Status as written in reports
+pub(crate) fn status_name(status: MetricStatus) -> &'static str {
+    match status {
+        MetricStatus::Improved => "improved",
+        MetricStatus::Regressed => "regressed",
+        MetricStatus::Unchanged => "unchanged",
+        MetricStatus::Missing => "missing",
+    }
+}
+
+/// Whether `path` is one of the CPU times under `resource_usage`
+fn is_cpu_time(path: &str) -> bool {
+    path.strip_prefix(RESOURCE_USAGE_KEY)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .is_some_and(|leaf| leaf == metric_names::BENCH_USER_CPU_MS.name || leaf == metric_names::BENCH_SYSTEM_CPU_MS.name)
+}
+
+/// Whether `path` is a timing measured on the wall clock
+fn is_wall_time(path: &str) -> bool {
+    let leaf = path.rsplit('.').next().unwrap_or(path);
+    let timed = metric_names::lookup(leaf)
+        .is_some_and(|metric| matches!(metric.unit, MetricUnit::Seconds | MetricUnit::Milliseconds));
+    timed && !is_cpu_time(path)
+}
+
+/// Numeric leaves of `metrics` by dotted path, with their units
+///
+/// Bare numbers, and numeric strings from files that predate typed metrics,
+/// get the unit implied by their key.
+pub(crate) fn numeric_leaves(metrics: &Value) -> BTreeMap<String, Measurement> {
+    fn walk(key: &str, value: &Value, path: String, out: &mut BTreeMap<String, Measurement>) {
+        if let Some(measurement) = Measurement::read(key, value) {
+            out.insert(path, measurement);
+        } else if let Value::Object(map) = value {
+            for (key, child) in map {
+                let child_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                walk(key, child, child_path, out);
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    walk("", metrics, String::new(), &mut out);
+    out
+}
+
+/// Classify the change of a metric present on both sides
+fn classify(path: &str, baseline: f64, current: f64, threshold_percent: f64) -> (Option<f64>, MetricStatus) {
+    let delta = current - baseline;
+    let delta_percent = (baseline != 0.0).then(|| delta / baseline.abs() * 100.0);
+    let leaf = path.rsplit('.').next().unwrap_or(path);
+
+    let significant = match delta_percent {
+        Some(percent) => percent.abs() >= threshold_percent,
+        None => delta != 0.0,
+    };
+    let status = match Direction::of(leaf) {
+        _ if !significant => MetricStatus::Unchanged,
+        None => MetricStatus::Unchanged,
+        Some(Direction::LowerIsBetter) if delta < 0.0 => MetricStatus::Improved,
+        Some(Direction::HigherIsBetter) if delta > 0.0 => MetricStatus::Improved,
+        Some(_) => MetricStatus::Regressed,
+    };
+    (delta_percent, status)
+}
+
+/// Compare `current` against `baseline` with [`DEFAULT_THRESHOLD_PERCENT`]
+pub fn compare_results(baseline: &[BenchmarkResult], current: &[BenchmarkResult]) -> RegressionReport {
+    compare_results_with_threshold(baseline, current, DEFAULT_THRESHOLD_PERCENT)
+}
+
+/// Compare `current` against `baseline`, counting changes under `threshold_percent` as unchanged
+///
+/// Targets on only one side are listed in the report and their metrics are
+/// reported as missing. If a side has several results for a target, the last
+/// one is used.
+pub fn compare_results_with_threshold(
+    baseline: &[BenchmarkResult],
+    current: &[BenchmarkResult],
+    threshold_percent: f64,
+) -> RegressionReport {
+    let by_id = |results: &[BenchmarkResult]| -> HashMap<String, BTreeMap<String, Measurement>> {
+        results
+            .iter()
+            .map(|result| (result.target_id.clone(), numeric_leaves(&result.metrics)))
+            .collect()
+    };
+    let baseline = by_id(baseline);
+    let current = by_id(current);
+    let ids: BTreeSet<&String> = baseline.keys().chain(current.keys()).collect();
+
+    let mut report = RegressionReport {
+        threshold_percent,
+        metrics: Vec::new(),
+        baseline_only: Vec::new(),
+        current_only: Vec::new(),
+    };
+    let empty = BTreeMap::new();
+    for id in ids {
+        let (old, new) = match (baseline.get(id), current.get(id)) {
+            (Some(old), Some(new)) => (old, new),
+            (Some(old), None) => {
+                report.baseline_only.push(id.clone());
+                (old, &empty)
+            }
+            (None, Some(new)) => {
+                report.current_only.push(id.clone());
+                (&empty, new)
+            }
+            (None, None) => continue,
+        };
+
+        let paths: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+        for path in paths {
+            let (old, new) = (old.get(path), new.get(path));
+            let unit = old.or(new).map(|measurement| measurement.unit);
+            // Current values are restated in the baseline's unit
+            let baseline = old.map(|measurement| measurement.value);
+            let current = match (old, new) {
+                (Some(old), Some(new)) => new.in_unit(old.unit),
+                (None, Some(new)) => Some(new.value),
+                (_, None) => None,
+            };
+            let (delta, delta_percent, status) = match (baseline, current) {
+                (Some(baseline), Some(current)) => {
+                    let (delta_percent, status) = classify(path, baseline, current, threshold_percent);
+                    (Some(current - baseline), delta_percent, status)
+                }
+                _ => (None, None, MetricStatus::Missing),
+            };
+            report.metrics.push(MetricComparison {
+                target_id: id.clone(),
+                path: path.clone(),
+                baseline,
+                current,
+                unit,
+                delta,
+                delta_percent,
+                status,
+            });
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn result(id: &str, metrics: Value) -> BenchmarkResult {
+        BenchmarkResult::new(id.to_string(), metrics)
+    }
+
+    fn status(report: &RegressionReport, id: &str, path: &str) -> MetricStatus {
+        report
+            .metrics
+            .iter()
+            .find(|m| m.target_id == id && m.path == path)
+            .map(|m| m.status)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_direction_from_catalogued_unit() {
+        assert_eq!(Direction::of("p95_ms"), Some(Direction::LowerIsBetter));
+        assert_eq!(Direction::of("rss_peak_bytes"), Some(Direction::LowerIsBetter));
+        assert_eq!(Direction::of("ops_per_sec"), Some(Direction::HigherIsBetter));
+        assert_eq!(Direction::of("iterations"), None);
+        assert_eq!(Direction::of("sample_time_ms"), None);
+        assert_eq!(Direction::of("not_catalogued"), None);
+    }
+
+    #[test]
+    fn test_classifies_changes_by_direction() {
+        let baseline = [result(
+            "storage",
+            json!({"iterations": 10, "write": {"p95_ms": 2.0, "avg_ms": 1.0, "ops_per_sec": 1000.0}}),
+        )];
+        let current = [result(
+            "storage",
+            json!({"iterations": 20, "write": {"p95_ms": 1.0, "avg_ms": 1.02, "ops_per_sec": 800.0}}),
+        )];
+        let report = compare_results(&baseline, &current);
+
+        assert_eq!(status(&report, "storage", "write.p95_ms"), MetricStatus::Improved);
+        assert_eq!(status(&report, "storage", "write.avg_ms"), MetricStatus::Unchanged);
+        assert_eq!(status(&report, "storage", "write.ops_per_sec"), MetricStatus::Regressed);
+        assert_eq!(status(&report, "storage", "iterations"), MetricStatus::Unchanged);
+        assert!(report.has_regressions());
+
+        let p95 = report.metrics.iter().find(|m| m.path == "write.p95_ms").unwrap();
+        assert_eq!(p95.delta, Some(-1.0));
+        assert_eq!(p95.delta_percent, Some(-50.0));
+    }
+
+    #[test]
+    fn test_legacy_numbers_compare_with_measurements_in_other_units() {
+        let baseline = [result("storage", json!({"write": {"p95_ms": 2.0}, "label": "x"}))];
+        let current = [result(
+            "storage",
+            json!({"write": {"p95_ms": {"value": 3000.0, "unit": "microseconds"}}, "label": "y"}),
+        )];
+        let report = compare_results(&baseline, &current);
+
+        assert_eq!(report.metrics.len(), 1);
+        let p95 = &report.metrics[0];
+        assert_eq!(p95.unit, Some(Unit::Milliseconds));
+        assert_eq!(p95.current, Some(3.0));
+        assert_eq!(p95.status, MetricStatus::Regressed);
+
+        let retyped = [result("storage", json!({"write": {"p95_ms": {"value": 3.0, "unit": "bytes"}}}))];
+        assert_eq!(compare_results(&baseline, &retyped).metrics[0].status, MetricStatus::Missing);
+    }
+
+    #[test]
+    fn test_targets_on_one_side_are_missing() {
+        let baseline = [result("gone", json!({"write": {"p95_ms": 1.0}}))];
+        let current = [
+            result("new", json!({"read": {"p95_ms": 1.0}})),
+            result("gone_too", json!({})),
+        ];
+        let report = compare_results(&baseline, &current);
+
+        assert_eq!(report.baseline_only, vec!["gone"]);
+        assert_eq!(report.current_only, vec!["gone_too", "new"]);
+        assert_eq!(status(&report, "gone", "write.p95_ms"), MetricStatus::Missing);
+        assert_eq!(status(&report, "new", "read.p95_ms"), MetricStatus::Missing);
+        assert!(!report.has_regressions());
+    }
+
+    #[test]
+    fn test_zero_baseline_has_no_percentage() {
+        let report = compare_results(
+            &[result("t", json!({"write": {"p95_ms": 0.0}}))],
+            &[result("t", json!({"write": {"p95_ms": 0.5}}))],
+        );
+        assert_eq!(report.metrics[0].delta_percent, None);
+        assert_eq!(report.metrics[0].status, MetricStatus::Regressed);
+    }
+
+    #[test]
+    fn test_gate_fails_only_on_regressions_over_threshold() {
+        let baseline = [
+            result("storage", json!({"write": {"p95_ms": 10.0, "ops_per_sec": 100.0}, "iterations": 10})),
+            result("validation", json!({"json_schema": {"p95_ms": 10.0}})),
+        ];
+        let current = [
+            result("storage", json!({"write": {"p95_ms": 11.5, "ops_per_sec": 900.0}, "iterations": 99})),
+            result("validation", json!({"json_schema": {"p95_ms": 11.5}})),
+        ];
+        let report = compare_results(&baseline, &current);
+
+        let gate = report.evaluate(&RegressionThresholds::new(10.0));
+        assert!(!gate.passed());
+        let failed: Vec<_> = gate.violations.iter().map(|v| (v.target_id.as_str(), v.path.as_str())).collect();
+        assert_eq!(failed, vec![("storage", "write.p95_ms"), ("validation", "json_schema.p95_ms")]);
+        assert_eq!(gate.violations[0].allowed_percent, 10.0);
+
+        let lenient = RegressionThresholds::new(10.0).with_target_override("validation", 20.0);
+        assert_eq!(report.evaluate(&lenient).violations.len(), 1);
+        assert!(report.evaluate(&RegressionThresholds::new(20.0)).passed());
+
+        // Stricter than the report's own threshold
+        let small = compare_results(
+            &[result("t", json!({"write": {"p95_ms": 10.0}}))],
+            &[result("t", json!({"write": {"p95_ms": 10.3}}))],
+        );
+        assert_eq!(small.metrics[0].status, MetricStatus::Unchanged);
+        assert!(!small.evaluate(&RegressionThresholds::new(1.0)).passed());
+    }
+
+    #[test]
+    fn test_improvements_never_fail_the_gate() {
+        let report = compare_results(
+            &[result("t", json!({"write": {"p95_ms": 10.0, "ops_per_sec": 1.0}}))],
+            &[result("t", json!({"write": {"p95_ms": 0.0, "ops_per_sec": 1e9}}))],
+        );
+        assert!(report.evaluate(&RegressionThresholds::new(0.0)).passed());
+
+        let from_zero = compare_results(
+            &[result("t", json!({"write": {"p95_ms": 0.0}}))],
+            &[result("t", json!({"write": {"p95_ms": 0.1}}))],
+        );
+        assert!(!from_zero.evaluate(&RegressionThresholds::new(1000.0)).passed());
+    }
+
+    #[test]
+    fn test_gate_prefers_cpu_time_when_both_sides_have_it() {
+        let usage = |cpu: f64| json!({"user_cpu_ms": cpu, "system_cpu_ms": 1.0});
+        let report = compare_results(
+            &[
+                result("cpu", json!({"write": {"p95_ms": 10.0}, "resource_usage": usage(20.0)})),
+                result("wall", json!({"write": {"p95_ms": 10.0}})),
+            ],
+            &[
+                result("cpu", json!({"write": {"p95_ms": 20.0}, "resource_usage": usage(20.5)})),
+                result("wall", json!({"write": {"p95_ms": 20.0}, "resource_usage": usage(20.0)})),
+            ],
+        );
+
+        let wall = report.evaluate(&RegressionThresholds::new(10.0));
+        let ids: Vec<_> = wall.violations.iter().map(|v| v.target_id.as_str()).collect();
+        assert_eq!(ids, vec!["cpu", "wall"]);
+
+        // `wall` has no baseline CPU time, so its wall time still counts
+        let cpu = report.evaluate(&RegressionThresholds::new(10.0).with_prefer_cpu_time(true));
+        let ids: Vec<_> = cpu.violations.iter().map(|v| v.target_id.as_str()).collect();
+        assert_eq!(ids, vec!["wall"]);
+
+        let slower = compare_results(
+            &[result("cpu", json!({"write": {"p95_ms": 10.0}, "resource_usage": usage(20.0)}))],
+            &[result("cpu", json!({"write": {"p95_ms": 10.0}, "resource_usage": usage(30.0)}))],
+        );
+        let gate = slower.evaluate(&RegressionThresholds::new(10.0).with_prefer_cpu_time(true));
+        assert_eq!(gate.violations[0].path, "resource_usage.user_cpu_ms");
+    }
+
+    #[test]
+    fn test_report_round_trips_through_json() {
+        let report = compare_results(
+            &[result("t", json!({"write": {"p95_ms": 1.0}}))],
+            &[result("t", json!({"write": {"p95_ms": "1.5"}}))],
+        );
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["metrics"][0]["status"], "regressed");
+
+        let read: RegressionReport = serde_json::from_value(json).unwrap();
+        assert_eq!(read, report);
+    }
+}
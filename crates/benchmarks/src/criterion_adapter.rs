@@ -0,0 +1,226 @@
+//! Running targets under criterion.rs
+//!
+//! With the `criterion` feature a [`BenchTarget`] can be measured by
+//! criterion instead of its own sampling loop, for criterion's warmup,
+//! outlier classification and bootstrapped estimates:
+//!
+//! ```ignore
+//! fn benches(c: &mut Criterion) {
+//!     bench_target_with_criterion(c, &ValidationBenchmark::new());
+//! }
+//! ```
+//!
+//! Each criterion iteration is one run of the target with a single measured
+//! iteration and no warmup (`BenchConfig::new(0, 1)`), so the timings cover
+//! one pass over all of the target's operations plus the target's own
+//! bookkeeping. The function id is the target's id.
+//!
+//! Criterion writes its analysis to `<criterion dir>/<id>/new/`.
+//! [`result_from_criterion`] reads it back into a [`BenchmarkResult`], so
+//! criterion runs flow through [`io`](crate::io) and
+//! [`markdown`](crate::markdown) like any other run.
+
+use crate::adapters::{BenchConfig, BenchTarget};
+use crate::metrics::{IterationCounts, OperationMetrics};
+use crate::stats::Summary;
+use crate::BenchmarkResult;
+use anyhow::{ensure, Context, Result};
+use criterion::Criterion;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::runtime::Runtime;
+
+/// Metrics key holding the statistics of a criterion run
+pub const CRITERION_KEY: &str = "criterion";
+
+/// Directory criterion writes to: `$CRITERION_HOME`, or else `criterion` in
+/// `$CARGO_TARGET_DIR` or `target`
+///
+/// A relative `target` is relative to the workspace root, while cargo runs
+/// benches from their package directory; see `benches/criterion_targets.rs`.
+pub fn default_criterion_dir() -> PathBuf {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map_or_else(|| PathBuf::from("target"), PathBuf::from)
+        .join("criterion")
+}
+
+/// Measure `target` with criterion as the function `target.id()`
+///
+/// The target's [`setup`](BenchTarget::setup) runs once before measuring and
+/// [`teardown`](BenchTarget::teardown) once after. Criterion has no way to
+/// report a failed iteration, so a failing setup or run panics.
+pub fn bench_target_with_criterion(c: &mut Criterion, target: &dyn BenchTarget) {
+    let runtime = Runtime::new().expect("failed to start a tokio runtime for criterion");
+    let cfg = BenchConfig::new(0, 1);
+
+    runtime
+        .block_on(target.setup())
+        .unwrap_or_else(|e| panic!("setup of {} failed: {:#}", target.id(), e));
+    c.bench_function(target.id(), |b| {
+        b.to_async(&runtime).iter(|| async move {
+            target
+                .run_with_config(&cfg)
+                .await
+                .unwrap_or_else(|e| panic!("{} failed: {:#}", target.id(), e))
+        })
+    });
+    if let Err(e) = runtime.block_on(target.teardown()) {
+        tracing::warn!("Teardown of {} failed: {:#}", target.id(), e);
+    }
+}
+
+/// A criterion point estimate, in nanoseconds
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Estimate {
+    pub point_estimate: f64,
+    pub standard_error: f64,
+    pub confidence_interval: ConfidenceInterval,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct ConfidenceInterval {
+    pub confidence_level: f64,
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+}
+
+/// Criterion's `estimates.json`
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Estimates {
+    pub mean: Estimate,
+    pub median: Estimate,
+    pub std_dev: Estimate,
+    pub median_abs_dev: Estimate,
+    /// Only estimated in linear sampling mode
+    #[serde(default)]
+    pub slope: Option<Estimate>,
+}
+
+/// Criterion's `sample.json`: each sample's iteration count and total time in nanoseconds
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Samples {
+    pub iters: Vec<f64>,
+    pub times: Vec<f64>,
+}
+
+impl Samples {
+    /// Mean time per iteration of each sample, in milliseconds
+    pub fn per_iteration_ms(&self) -> Vec<f64> {
+        self.iters
+            .iter()
+            .zip(&self.times)
+            .filter(|(iters, _)| **iters > 0.0)
+            .map(|(iters, time)| time / iters / 1e6)
+            .collect()
+    }
+
+    /// Iterations across all samples
+    pub fn total_iterations(&self) -> usize {
+        self.iters.iter().sum::<f64>() as usize
+    }
+}
+
+/// Result of `target_id` from criterion's estimates and samples
+///
+/// Mean, median and standard deviation are criterion's bootstrapped point
+/// estimates (the slope replaces the mean when criterion estimated one); the
+/// extremes and tail percentiles come from the per-iteration means of the
+/// samples. Metrics are written under [`CRITERION_KEY`].
+pub fn result_from_estimates(target_id: &str, estimates: &Estimates, samples: &Samples) -> BenchmarkResult {
+    let mut operation = OperationMetrics::from(Summary::from_samples(&samples.per_iteration_ms()));
+    let mean_ns = estimates.slope.unwrap_or(estimates.mean).point_estimate;
+    operation.avg_ms = mean_ns / 1e6;
+    operation.p50_ms = estimates.median.point_estimate / 1e6;
+    operation.std_dev_ms = estimates.std_dev.point_estimate / 1e6;
+    operation.cv_percent = if mean_ns > 0.0 {
+        estimates.std_dev.point_estimate / mean_ns * 100.0
+    } else {
+        0.0
+    };
+    operation.ops_per_sec = if mean_ns > 0.0 { 1e9 / mean_ns } else { 0.0 };
+
+    let counts = IterationCounts {
+        warmup_iterations: 0,
+        iterations: samples.total_iterations(),
+        sample_time_ms: None,
+    };
+    let mut metrics = serde_json::to_value(counts).expect("iteration counts serialize");
+    metrics[CRITERION_KEY] = serde_json::to_value(operation).expect("operation metrics serialize");
+    BenchmarkResult::new(target_id.to_string(), metrics)
+}
+
+/// Result of `target_id` from the latest criterion run in `criterion_dir`
+pub fn result_from_criterion(criterion_dir: &Path, target_id: &str) -> Result<BenchmarkResult> {
+    let dir = criterion_dir.join(target_id).join("new");
+    let estimates: Estimates = read(&dir.join("estimates.json"))?;
+    let samples: Samples = read(&dir.join("sample.json"))?;
+    ensure!(
+        samples.iters.len() == samples.times.len(),
+        "{} has {} iteration counts but {} times",
+        dir.join("sample.json").display(),
+        samples.iters.len(),
+        samples.times.len()
+    );
+    Ok(result_from_estimates(target_id, &estimates, &samples))
+}
+
+fn read<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read criterion output from {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn estimate(ns: f64) -> String {
+        format!(
+            r#"{{"confidence_interval": {{"confidence_level": 0.95, "lower_bound": {lo}, "upper_bound": {hi}}},
+                "point_estimate": {ns}, "standard_error": 1.0}}"#,
+            lo = ns - 10.0,
+            hi = ns + 10.0
+        )
+    }
+
+    #[test]
+    fn test_result_from_criterion_output() {
+        let dir = TempDir::new().unwrap();
+        let new = dir.path().join("validation_operations").join("new");
+        fs::create_dir_all(&new).unwrap();
+        fs::write(
+            new.join("estimates.json"),
+            format!(
+                r#"{{"mean": {}, "median": {}, "median_abs_dev": {}, "slope": null, "std_dev": {}}}"#,
+                estimate(2_000_000.0),
+                estimate(1_500_000.0),
+                estimate(100_000.0),
+                estimate(500_000.0)
+            ),
+        )
+        .unwrap();
+        fs::write(
+            new.join("sample.json"),
+            r#"{"sampling_mode": "Flat", "iters": [2.0, 2.0], "times": [2000000.0, 6000000.0]}"#,
+        )
+        .unwrap();
+
+        let result = result_from_criterion(dir.path(), "validation_operations").unwrap();
+        assert_eq!(result.target_id, "validation_operations");
+        assert_eq!(result.metrics["iterations"]["value"], 4);
+        let run = &result.metrics[CRITERION_KEY];
+        assert_eq!(run["avg_ms"], serde_json::json!({"value": 2.0, "unit": "milliseconds"}));
+        assert_eq!(run["p50_ms"]["value"], 1.5);
+        assert_eq!(run["min_ms"]["value"], 1.0);
+        assert_eq!(run["max_ms"]["value"], 3.0);
+        assert_eq!(run["cv_percent"]["value"], 25.0);
+        assert_eq!(run["ops_per_sec"]["value"], 500.0);
+
+        assert!(result_from_criterion(dir.path(), "missing").is_err());
+    }
+}
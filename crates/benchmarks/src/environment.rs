@@ -0,0 +1,153 @@
+//! Environment a benchmark run was produced in
+//!
+//! Timings from different machines or commits are not comparable, so every
+//! result is stamped with a [`RunEnvironment`] describing where it came from.
+//! Detection never fails: anything that can't be determined (no git checkout,
+//! no `rustc` on the path, an unreadable `/proc/cpuinfo`) is left as `None`.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::process::Command;
+
+/// Machine, toolchain and source revision of a benchmark run
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RunEnvironment {
+    /// Commit hash of the checkout the benchmarks were built from
+    #[serde(default)]
+    pub git_commit: Option<String>,
+    /// Branch of the checkout; `None` for a detached HEAD
+    #[serde(default)]
+    pub git_branch: Option<String>,
+    #[serde(default)]
+    pub hostname: Option<String>,
+    #[serde(default)]
+    pub cpu_model: Option<String>,
+    /// Logical cores available to the process
+    #[serde(default)]
+    pub cpu_cores: Option<usize>,
+    /// Operating system and architecture, e.g. `linux x86_64`
+    #[serde(default)]
+    pub os: String,
+    /// Output of `rustc --version`
+    #[serde(default)]
+    pub rustc_version: Option<String>,
+}
+
+impl RunEnvironment {
+    /// Detect the environment of the current process
+    pub fn detect() -> Self {
+        Self {
+            git_commit: git_commit(),
+            git_branch: command_output("git", &["rev-parse", "--abbrev-ref", "HEAD"]).filter(|branch| branch != "HEAD"),
+            hostname: hostname(),
+            cpu_model: cpu_model(),
+            cpu_cores: std::thread::available_parallelism().ok().map(|n| n.get()),
+            os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+            rustc_version: rustc_version(),
+        }
+    }
+
+    /// Short form of the commit hash for display, its first 12 characters
+    pub fn short_commit(&self) -> Option<&str> {
+        self.git_commit
+            .as_deref()
+            .map(|commit| commit.char_indices().nth(12).map_or(commit, |(end, _)| &commit[..end]))
+    }
+}
+
+impl fmt::Display for RunEnvironment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unknown = "unknown";
+        write!(
+            f,
+            "{} ({}) on {}, {}",
+            self.short_commit().unwrap_or(unknown),
+            self.git_branch.as_deref().unwrap_or(unknown),
+            self.hostname.as_deref().unwrap_or(unknown),
+            self.os
+        )
+    }
+}
+
+/// Trimmed stdout of a successful command, `None` if it fails or prints nothing
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Commit hash of the current checkout
+pub(crate) fn git_commit() -> Option<String> {
+    command_output("git", &["rev-parse", "HEAD"])
+}
+
+fn hostname() -> Option<String> {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|name| !name.is_empty()))
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok().map(|name| name.trim().to_string()))
+        .filter(|name| !name.is_empty())
+        .or_else(|| command_output("hostname", &[]))
+}
+
+fn cpu_model() -> Option<String> {
+    if let Ok(cpuinfo) = std::fs::read_to_string("/proc/cpuinfo") {
+        return parse_cpu_model(&cpuinfo);
+    }
+    command_output("sysctl", &["-n", "machdep.cpu.brand_string"])
+}
+
+/// Model name from `/proc/cpuinfo` contents
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        matches!(key.trim(), "model name" | "Model" | "cpu model")
+            .then(|| value.trim().to_string())
+            .filter(|model| !model.is_empty())
+    })
+}
+
+/// Version of the compiler cargo would use, honouring `$RUSTC`
+fn rustc_version() -> Option<String> {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    command_output(&rustc, &["--version"])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_fills_platform_fields() {
+        let env = RunEnvironment::detect();
+        assert!(env.os.starts_with(std::env::consts::OS));
+        assert!(env.cpu_cores.unwrap_or(1) >= 1);
+    }
+
+    #[test]
+    fn test_parse_cpu_model() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel name\t: Intel(R) Xeon(R) CPU @ 2.20GHz\n";
+        assert_eq!(parse_cpu_model(cpuinfo).as_deref(), Some("Intel(R) Xeon(R) CPU @ 2.20GHz"));
+        assert_eq!(parse_cpu_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn test_missing_fields_deserialize_as_unknown() {
+        let env: RunEnvironment = serde_json::from_str(r#"{"os": "linux x86_64"}"#).unwrap();
+        assert_eq!(env.git_commit, None);
+        assert_eq!(env.to_string(), "unknown (unknown) on unknown, linux x86_64");
+    }
+
+    #[test]
+    fn test_short_commit_cuts_on_characters() {
+        let env = |commit: &str| RunEnvironment { git_commit: Some(commit.to_string()), ..RunEnvironment::default() };
+        assert_eq!(env("0123456789abcdef").short_commit(), Some("0123456789ab"));
+        assert_eq!(env("abc").short_commit(), Some("abc"));
+        // Hand-edited results can carry anything; 12 bytes would split a character here
+        assert_eq!(env("ééééééééé").short_commit(), Some("ééééééééé"));
+        assert_eq!(env("ééééééééééééé").short_commit(), Some("éééééééééééé"));
+    }
+}
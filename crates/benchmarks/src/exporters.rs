@@ -0,0 +1,358 @@
+//! Pushing benchmark results to external systems over HTTP
+//!
+//! Needs the `http-push` feature. [`push_to_gateway`] sends the gauges of
+//! [`io::to_prometheus`](crate::io::to_prometheus) to a Prometheus
+//! Pushgateway, so results reach Prometheus without a textfile collector.
+//! [`HttpSink`] is a [`ResultSink`] that uploads the results as JSON.
+
+use crate::io::to_prometheus;
+use crate::sink::ResultSink;
+use crate::BenchmarkResult;
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// Timeout of a push unless [`PushOptions::with_timeout`] sets another
+pub const DEFAULT_PUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Retries of an [`HttpSink`] upload the server answered with a 5xx status
+pub const DEFAULT_UPLOAD_RETRIES: u32 = 3;
+
+/// Wait before the first retry of an [`HttpSink`] upload, doubled for each further retry
+pub const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Authentication and timeout of a push
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushOptions {
+    /// User name and password for HTTP basic auth
+    pub basic_auth: Option<(String, String)>,
+    /// Limit on the whole request, connecting included
+    pub timeout: Duration,
+}
+
+impl Default for PushOptions {
+    fn default() -> Self {
+        Self {
+            basic_auth: None,
+            timeout: DEFAULT_PUSH_TIMEOUT,
+        }
+    }
+}
+
+impl PushOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Authenticate with HTTP basic auth
+    pub fn with_basic_auth(mut self, user: impl Into<String>, password: impl Into<String>) -> Self {
+        self.basic_auth = Some((user.into(), password.into()));
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// `bytes` in URL-safe base64 with padding
+fn base64_url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (u32::from(b) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(char::from(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// `segment` percent-encoded for a URL path
+fn percent_encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// `name/value` path segments of a grouping label
+///
+/// Values that are empty or contain `/` use the Pushgateway's base64 form,
+/// which survives proxies that decode `%2F`; an empty value is written `=`.
+fn label_segments(name: &str, value: &str) -> String {
+    if value.is_empty() {
+        format!("/{}@base64/=", percent_encode(name))
+    } else if value.contains('/') {
+        format!("/{}@base64/{}", percent_encode(name), base64_url(value.as_bytes()))
+    } else {
+        format!("/{}/{}", percent_encode(name), percent_encode(value))
+    }
+}
+
+/// URL of the group `job` and `grouping_labels` identify on the gateway at `gateway_url`
+pub fn gateway_group_url(gateway_url: &str, job: &str, grouping_labels: &[(&str, &str)]) -> String {
+    let mut url = format!("{}/metrics", gateway_url.trim_end_matches('/'));
+    url.push_str(&label_segments("job", job));
+    for (name, value) in grouping_labels {
+        url.push_str(&label_segments(name, value));
+    }
+    url
+}
+
+/// POST the results' gauges to the Pushgateway at `gateway_url`
+///
+/// The metrics land in the group of `job` and `grouping_labels`, replacing
+/// same-named metrics pushed there before. A gateway that can't be reached,
+/// doesn't answer within the timeout or rejects the push is an error.
+pub async fn push_to_gateway(
+    results: &[BenchmarkResult],
+    gateway_url: &str,
+    job: &str,
+    grouping_labels: &[(&str, &str)],
+    options: &PushOptions,
+) -> Result<()> {
+    if job.is_empty() {
+        bail!("Pushgateway job name must not be empty");
+    }
+    let url = gateway_group_url(gateway_url, job, grouping_labels);
+    let client = reqwest::Client::builder()
+        .timeout(options.timeout)
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    let mut request = client
+        .post(&url)
+        .header(reqwest::header::CONTENT_TYPE, "text/plain; version=0.0.4")
+        .body(to_prometheus(results));
+    if let Some((user, password)) = &options.basic_auth {
+        request = request.basic_auth(user, Some(password));
+    }
+
+    let response = request
+        .send()
+        .await
+        .with_context(|| format!("Failed to push benchmark metrics to {}", url))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        bail!("Pushgateway at {} rejected the push: {} {}", url, status, body.trim());
+    }
+    Ok(())
+}
+
+/// Uploads results as a JSON array to an HTTP endpoint
+///
+/// Uploads the server answers with a 5xx status are retried with
+/// exponential backoff; other failures are returned right away.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpSink {
+    url: String,
+    bearer_token: Option<String>,
+    timeout: Duration,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl HttpSink {
+    /// POST results to `url`
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            bearer_token: None,
+            timeout: DEFAULT_PUSH_TIMEOUT,
+            retries: DEFAULT_UPLOAD_RETRIES,
+            backoff: DEFAULT_RETRY_BACKOFF,
+        }
+    }
+
+    /// Send `token` in an `Authorization: Bearer` header
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// Limit on each attempt, connecting included
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Retry a 5xx answer up to `retries` times, waiting `backoff` before the first retry
+    pub fn with_retries(mut self, retries: u32, backoff: Duration) -> Self {
+        self.retries = retries;
+        self.backoff = backoff;
+        self
+    }
+}
+
+#[async_trait]
+impl ResultSink for HttpSink {
+    fn name(&self) -> String {
+        self.url.clone()
+    }
+
+    async fn publish(&self, results: &[BenchmarkResult]) -> Result<()> {
+        let body = serde_json::to_vec(results).context("Failed to serialize benchmark results")?;
+        let client = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        let mut backoff = self.backoff;
+        for attempt in 0..=self.retries {
+            let mut request = client
+                .post(&self.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            if let Some(token) = &self.bearer_token {
+                request = request.bearer_auth(token);
+            }
+
+            let response = request
+                .send()
+                .await
+                .with_context(|| format!("Failed to upload benchmark results to {}", self.url))?;
+            let status = response.status();
+            if status.is_success() {
+                return Ok(());
+            }
+            if !status.is_server_error() || attempt == self.retries {
+                let text = response.text().await.unwrap_or_default();
+                bail!(
+                    "{} rejected the benchmark results on attempt {}: {} {}",
+                    self.url,
+                    attempt + 1,
+                    status,
+                    text.trim()
+                );
+            }
+            tracing::warn!("Upload to {} failed with {}, retrying in {:?}", self.url, status, backoff);
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+        unreachable!("the last attempt returns")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_string_contains, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn results() -> Vec<BenchmarkResult> {
+        vec![BenchmarkResult::new("storage".to_string(), json!({"write": {"p95_ms": 2.0}}))]
+    }
+
+    #[test]
+    fn test_group_url_encodes_labels() {
+        assert_eq!(base64_url(b"feature/x"), "ZmVhdHVyZS94");
+        assert_eq!(base64_url(b"ab"), "YWI=");
+        assert_eq!(
+            gateway_group_url("http://gw:9091/", "bench", &[("branch", "feature/x"), ("host", "ci 1"), ("shard", "")]),
+            "http://gw:9091/metrics/job/bench/branch@base64/ZmVhdHVyZS94/host/ci%201/shard@base64/="
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pushes_gauges_with_basic_auth() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/metrics/job/schema-registry-benchmarks/branch/main"))
+            .and(header("authorization", "Basic YmVuY2g6czNjcmV0"))
+            .and(body_string_contains(
+                "schema_registry_benchmark_p95_seconds{target=\"storage\",operation=\"write\"} 0.002",
+            ))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = PushOptions::new().with_basic_auth("bench", "s3cret");
+        push_to_gateway(&results(), &server.uri(), "schema-registry-benchmarks", &[("branch", "main")], &options)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejections_and_timeouts_are_errors() {
+        let server = MockServer::start().await;
+        Mock::given(path("/metrics/job/rejected"))
+            .respond_with(ResponseTemplate::new(400).set_body_string("text format parsing error"))
+            .mount(&server)
+            .await;
+        Mock::given(path("/metrics/job/slow"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .mount(&server)
+            .await;
+        let options = PushOptions::new().with_timeout(Duration::from_millis(200));
+
+        let err = push_to_gateway(&results(), &server.uri(), "rejected", &[], &options).await.unwrap_err();
+        assert!(err.to_string().contains("400"));
+        assert!(err.to_string().contains("text format parsing error"));
+        assert!(push_to_gateway(&results(), &server.uri(), "slow", &[], &options).await.is_err());
+        assert!(push_to_gateway(&results(), &server.uri(), "", &[], &options).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_http_sink_retries_server_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/results"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/results"))
+            .and(header("authorization", "Bearer t0ken"))
+            .and(header("content-type", "application/json"))
+            .and(body_string_contains("\"target_id\":\"storage\""))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let sink = HttpSink::new(format!("{}/results", server.uri()))
+            .with_bearer_token("t0ken")
+            .with_retries(3, Duration::from_millis(1));
+        sink.publish(&results()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_http_sink_gives_up() {
+        let server = MockServer::start().await;
+        Mock::given(path("/down"))
+            .respond_with(ResponseTemplate::new(502))
+            .expect(3)
+            .mount(&server)
+            .await;
+        Mock::given(path("/forbidden"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("bad token"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let down = HttpSink::new(format!("{}/down", server.uri())).with_retries(2, Duration::from_millis(1));
+        let err = down.publish(&results()).await.unwrap_err();
+        assert!(err.to_string().contains("on attempt 3: 502"));
+
+        let forbidden = HttpSink::new(format!("{}/forbidden", server.uri())).with_retries(2, Duration::from_millis(1));
+        let err = forbidden.publish(&results()).await.unwrap_err();
+        assert!(err.to_string().contains("on attempt 1: 401"));
+        assert!(err.to_string().contains("bad token"));
+    }
+}
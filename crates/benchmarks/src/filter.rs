@@ -0,0 +1,239 @@
+//! Benchmark target selection
+//!
+//! Filters match against [`BenchTarget::id`](crate::adapters::BenchTarget::id)
+//! using exact ids or simple glob patterns (`*` for any run of characters,
+//! `?` for a single character), and against
+//! [`BenchTarget::tags`](crate::adapters::BenchTarget::tags) by exact tag.
+
+use crate::adapters::BenchTarget;
+use thiserror::Error;
+
+/// Error raised when selecting benchmark targets
+#[derive(Debug, Error)]
+pub enum BenchmarkFilterError {
+    /// The filter excluded every registered target
+    #[error("no benchmark targets match filter {filter}")]
+    NoMatchingTargets { filter: String },
+}
+
+/// Selects benchmark targets by id and tag
+///
+/// An empty include list selects every target; with include tags, a target
+/// must also carry at least one of them. Excludes (by id or tag) are applied
+/// afterwards.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BenchmarkFilter {
+    /// Ids or glob patterns to run
+    pub include: Vec<String>,
+    /// Ids or glob patterns to skip
+    pub exclude: Vec<String>,
+    /// Tags to run
+    pub include_tags: Vec<String>,
+    /// Tags to skip
+    pub exclude_tags: Vec<String>,
+}
+
+impl BenchmarkFilter {
+    /// Create a filter that selects every target
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also run targets matching `pattern`
+    pub fn with_include(mut self, pattern: impl Into<String>) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// Skip targets matching `pattern`
+    pub fn with_exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Also require targets to carry `tag` (or another include tag)
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.include_tags.push(tag.into());
+        self
+    }
+
+    /// Skip targets carrying `tag`
+    pub fn with_exclude_tag(mut self, tag: impl Into<String>) -> Self {
+        self.exclude_tags.push(tag.into());
+        self
+    }
+
+    /// Build a filter from CLI-style specs, where a leading `!` marks an exclude
+    pub fn from_specs<I, S>(specs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        specs.into_iter().fold(Self::new(), |filter, spec| {
+            let spec = spec.as_ref().trim();
+            match spec.strip_prefix('!') {
+                Some(pattern) => filter.with_exclude(pattern),
+                None if spec.is_empty() => filter,
+                None => filter.with_include(spec),
+            }
+        })
+    }
+
+    /// Whether the filter selects every target
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty() && self.include_tags.is_empty() && self.exclude_tags.is_empty()
+    }
+
+    /// Whether the target id is selected, ignoring tags
+    pub fn matches(&self, id: &str) -> bool {
+        let included = self.include.is_empty() || self.include.iter().any(|p| glob_match(p, id));
+        included && !self.exclude.iter().any(|p| glob_match(p, id))
+    }
+
+    /// Whether a target with these tags is selected, ignoring its id
+    pub fn matches_tags(&self, tags: &[&str]) -> bool {
+        let has = |tag: &String| tags.contains(&tag.as_str());
+        let included = self.include_tags.is_empty() || self.include_tags.iter().any(has);
+        included && !self.exclude_tags.iter().any(has)
+    }
+
+    /// Whether the target is selected by both its id and its tags
+    pub fn selects(&self, target: &dyn BenchTarget) -> bool {
+        self.matches(target.id()) && self.matches_tags(target.tags())
+    }
+
+    /// Keep the selected targets, failing if none remain
+    pub fn apply(
+        &self,
+        targets: Vec<Box<dyn BenchTarget>>,
+    ) -> Result<Vec<Box<dyn BenchTarget>>, BenchmarkFilterError> {
+        if self.is_empty() {
+            return Ok(targets);
+        }
+
+        let selected: Vec<_> = targets.into_iter().filter(|t| self.selects(t.as_ref())).collect();
+        if selected.is_empty() {
+            return Err(BenchmarkFilterError::NoMatchingTargets {
+                filter: self.to_string(),
+            });
+        }
+        Ok(selected)
+    }
+}
+
+impl std::fmt::Display for BenchmarkFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let specs: Vec<String> = self
+            .include
+            .iter()
+            .cloned()
+            .chain(self.exclude.iter().map(|p| format!("!{}", p)))
+            .chain(self.include_tags.iter().map(|t| format!("tag:{}", t)))
+            .chain(self.exclude_tags.iter().map(|t| format!("!tag:{}", t)))
+            .collect();
+        write!(f, "[{}]", specs.join(","))
+    }
+}
+
+/// Match `text` against a glob `pattern` supporting `*` and `?`
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text index it is currently absorbing up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, absorbed)) => {
+                    p = star + 1;
+                    t = absorbed + 1;
+                    backtrack = Some((star, absorbed + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::all_targets;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("storage_*", "storage_operations"));
+        assert!(glob_match("*_operations", "validation_operations"));
+        assert!(glob_match("st?rage_*", "storage_operations"));
+        assert!(glob_match("storage_operations", "storage_operations"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("storage", "storage_operations"));
+        assert!(!glob_match("valid*_x", "validation_operations"));
+    }
+
+    #[test]
+    fn test_filter_include_and_exclude() {
+        let filter = BenchmarkFilter::from_specs(["*_operations", "!validation_*"]);
+        let ids: Vec<String> = filter
+            .apply(all_targets())
+            .unwrap()
+            .iter()
+            .map(|t| t.id().to_string())
+            .collect();
+
+        assert_eq!(ids, vec!["storage_operations", "compatibility_operations"]);
+    }
+
+    #[test]
+    fn test_filter_by_tags() {
+        let ids = |filter: BenchmarkFilter| -> Vec<String> {
+            filter
+                .apply(all_targets())
+                .unwrap()
+                .iter()
+                .map(|t| t.id().to_string())
+                .collect()
+        };
+
+        assert_eq!(
+            ids(BenchmarkFilter::new().with_tag("fast")),
+            vec!["validation_operations", "compatibility_operations", "schema_size"]
+        );
+        assert_eq!(
+            ids(BenchmarkFilter::new().with_tag("storage").with_exclude_tag("io")),
+            vec!["storage_operations"]
+        );
+        assert_eq!(
+            ids(BenchmarkFilter::from_specs(["*_operations"]).with_exclude_tag("fast")),
+            vec!["storage_operations"]
+        );
+
+        let err = BenchmarkFilter::new().with_tag("gpu").apply(all_targets()).err().unwrap();
+        assert_eq!(err.to_string(), "no benchmark targets match filter [tag:gpu]");
+    }
+
+    #[test]
+    fn test_empty_filter_selects_everything() {
+        let filter = BenchmarkFilter::new();
+        assert_eq!(filter.apply(all_targets()).unwrap().len(), all_targets().len());
+    }
+
+    #[test]
+    fn test_filter_matching_nothing_is_an_error() {
+        let filter = BenchmarkFilter::new().with_include("network_*");
+        let err = filter.apply(all_targets()).err().unwrap();
+        assert_eq!(err.to_string(), "no benchmark targets match filter [network_*]");
+    }
+}
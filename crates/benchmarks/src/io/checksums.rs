@@ -0,0 +1,159 @@
+//! SHA-256 checksums of written artifacts
+//!
+//! [`write_results`](super::write_results) records the checksum of every
+//! file it writes in `<root>/SHA256SUMS`, in the format of `sha256sum`, so
+//! archived results can be checked with [`verify_checksums`] or
+//! `sha256sum -c SHA256SUMS` from the root. Paths under the root are
+//! relative to it; files written elsewhere keep their full path.
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+/// Name of the checksum list in the output root
+pub const CHECKSUMS_FILE: &str = "SHA256SUMS";
+
+/// A recorded file whose contents no longer match its checksum
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChecksumMismatch {
+    pub path: PathBuf,
+    pub expected: String,
+    /// Checksum of the file now, `None` if it is missing
+    pub actual: Option<String>,
+}
+
+/// Hex SHA-256 of the file at `path`, read in chunks
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut BufReader::new(file), &mut hasher)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Name of `file` in the checksum list of `root`
+fn entry_name(root: &Path, file: &Path) -> String {
+    file.strip_prefix(root).unwrap_or(file).to_string_lossy().replace('\\', "/")
+}
+
+/// Checksums listed in `<dir>/SHA256SUMS` by file name; a missing list is empty
+fn read_checksums(dir: &Path) -> Result<BTreeMap<String, String>> {
+    let path = dir.join(CHECKSUMS_FILE);
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let mut checksums = BTreeMap::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        // `<hash>  <name>`, or `<hash> *<name>` as written in binary mode
+        let parsed = line
+            .split_once(' ')
+            .map(|(hash, name)| (hash, name.strip_prefix([' ', '*']).unwrap_or(name)))
+            .filter(|(hash, name)| hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit()) && !name.is_empty());
+        let Some((hash, name)) = parsed else {
+            bail!("Malformed line {} of {}", index + 1, path.display());
+        };
+        checksums.insert(name.to_string(), hash.to_ascii_lowercase());
+    }
+    Ok(checksums)
+}
+
+fn write_checksums(dir: &Path, checksums: &BTreeMap<String, String>) -> Result<()> {
+    let path = dir.join(CHECKSUMS_FILE);
+    let content: String = checksums.iter().map(|(name, hash)| format!("{}  {}\n", hash, name)).collect();
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Record the checksums of `files` in `<root>/SHA256SUMS`, keeping other entries
+pub fn record_checksums(root: &Path, files: &[PathBuf]) -> Result<()> {
+    let mut checksums = read_checksums(root)?;
+    for file in files {
+        checksums.insert(entry_name(root, file), sha256_file(file)?);
+    }
+    write_checksums(root, &checksums)
+}
+
+/// Drop the entries of `files` from `<root>/SHA256SUMS`, e.g. after pruning them
+pub fn forget_checksums(root: &Path, files: &[PathBuf]) -> Result<()> {
+    let mut checksums = read_checksums(root)?;
+    let before = checksums.len();
+    for file in files {
+        checksums.remove(&entry_name(root, file));
+    }
+    if checksums.len() == before {
+        return Ok(());
+    }
+    write_checksums(root, &checksums)
+}
+
+/// Recompute the checksums listed in `<dir>/SHA256SUMS` and return the files that differ
+///
+/// Files that are missing count as mismatches. A directory without a
+/// checksum list has nothing to verify.
+pub fn verify_checksums(dir: &Path) -> Result<Vec<ChecksumMismatch>> {
+    let mut mismatches = Vec::new();
+    for (name, expected) in read_checksums(dir)? {
+        let path = dir.join(&name);
+        let actual = match path.exists() {
+            true => Some(sha256_file(&path)?),
+            false => None,
+        };
+        if actual.as_ref() != Some(&expected) {
+            mismatches.push(ChecksumMismatch { path, expected, actual });
+        }
+    }
+    Ok(mismatches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detects_modified_and_missing_files() {
+        let dir = TempDir::new().unwrap();
+        let summary = dir.path().join("summary.md");
+        let latest = dir.path().join("raw/latest.json");
+        fs::create_dir_all(latest.parent().unwrap()).unwrap();
+        fs::write(&summary, "# Summary\n").unwrap();
+        fs::write(&latest, "[]").unwrap();
+
+        record_checksums(dir.path(), &[summary.clone(), latest.clone()]).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join(CHECKSUMS_FILE)).unwrap(),
+            "4f53cda18c2baa0c0354bb5f9a3ecbe5ed12ab4d8e11ba873c2f11161202b945  raw/latest.json\n\
+             88501c2f5e6660af97d9cafe49c86afa7adff4dc92cbe9e27141b4ef45642ee8  summary.md\n"
+        );
+        assert!(verify_checksums(dir.path()).unwrap().is_empty());
+
+        fs::write(&latest, "[{}]").unwrap();
+        fs::remove_file(&summary).unwrap();
+        let mismatches = verify_checksums(dir.path()).unwrap();
+        assert_eq!(mismatches.len(), 2);
+        assert_eq!(mismatches[0].path, latest);
+        assert_eq!(mismatches[0].actual, Some(sha256_file(&latest).unwrap()));
+        assert_eq!(mismatches[1].path, summary);
+        assert_eq!(mismatches[1].actual, None);
+
+        forget_checksums(dir.path(), &[summary]).unwrap();
+        assert_eq!(verify_checksums(dir.path()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_malformed_list_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        assert!(verify_checksums(dir.path()).unwrap().is_empty());
+
+        fs::write(dir.path().join(CHECKSUMS_FILE), "not a checksum\n").unwrap();
+        assert!(verify_checksums(dir.path()).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+//! CSV export of benchmark results
+//!
+//! [`write_csv`] writes one row per result: its target id and timestamp,
+//! then one column per metric, named by its dotted path into the metrics
+//! (`write.avg_ms`, `by_format.json_schema.p95_ms`). Array elements
+//! get their index as a path segment. Columns are the sorted union of every
+//! result's paths, so the header only depends on which metrics were reported.
+//!
+//! Measurements are written as their bare value, in the unit their key
+//! names. Leaves that aren't numbers, and metrics a result doesn't report,
+//! are empty cells.
+
+use crate::units;
+use crate::BenchmarkResult;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+/// Columns every row starts with, ahead of the metrics
+pub const FIXED_COLUMNS: [&str; 2] = ["target_id", "timestamp"];
+
+/// Leaves of `metrics` by dotted path, `None` for those that aren't numbers
+pub fn flatten_metrics(metrics: &Value) -> BTreeMap<String, Option<f64>> {
+    fn walk(value: &Value, path: String, out: &mut BTreeMap<String, Option<f64>>) {
+        let child_path = |key: &str| if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) };
+        match value {
+            Value::Object(map) if !units::is_measurement(value) => {
+                for (key, child) in map {
+                    walk(child, child_path(key), out);
+                }
+            }
+            Value::Array(items) => {
+                for (index, child) in items.iter().enumerate() {
+                    walk(child, child_path(&index.to_string()), out);
+                }
+            }
+            _ if path.is_empty() => {}
+            _ => {
+                out.insert(path, units::number(value));
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    walk(metrics, String::new(), &mut out);
+    out
+}
+
+/// `results` as CSV, with a header row
+pub fn to_csv(results: &[BenchmarkResult]) -> String {
+    let rows: Vec<BTreeMap<String, Option<f64>>> =
+        results.iter().map(|result| flatten_metrics(&result.metrics)).collect();
+    let columns: BTreeSet<&String> = rows.iter().flat_map(|row| row.keys()).collect();
+
+    let mut csv = String::new();
+    let header = FIXED_COLUMNS.iter().copied().chain(columns.iter().map(|column| column.as_str()));
+    push_row(&mut csv, header.map(Cow::Borrowed));
+    for (result, row) in results.iter().zip(&rows) {
+        let fixed = [Cow::Borrowed(result.target_id.as_str()), Cow::Owned(result.timestamp.to_rfc3339())];
+        let metrics = columns.iter().map(|column| match row.get(*column) {
+            Some(Some(value)) => Cow::Owned(value.to_string()),
+            _ => Cow::Borrowed(""),
+        });
+        push_row(&mut csv, fixed.into_iter().chain(metrics));
+    }
+    csv
+}
+
+/// Write `results` to a CSV file, see [`to_csv`]
+pub fn write_csv(results: &[BenchmarkResult], path: &Path) -> Result<()> {
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    fs::write(path, to_csv(results)).with_context(|| format!("Failed to write CSV to {}", path.display()))?;
+
+    Ok(())
+}
+
+fn push_row<'a>(csv: &mut String, fields: impl Iterator<Item = Cow<'a, str>>) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            csv.push(',');
+        }
+        csv.push_str(&escape(&field));
+    }
+    csv.push('\n');
+}
+
+/// `field` quoted if it contains a separator, quote or line break
+fn escape(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::reporting::synthetic_results;
+    use crate::metrics::{OperationMetrics, ValidationFormatMetrics};
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    /// Rows of `csv` by header, assuming no quoted fields
+    fn parse(csv: &str) -> Vec<BTreeMap<String, String>> {
+        let mut lines = csv.lines();
+        let header: Vec<&str> = lines.next().unwrap().split(',').collect();
+        lines
+            .map(|line| {
+                let cells: Vec<&str> = line.split(',').collect();
+                assert_eq!(cells.len(), header.len(), "{line}");
+                header.iter().map(|h| h.to_string()).zip(cells.into_iter().map(String::from)).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_flatten_uses_dotted_paths_and_measurement_values() {
+        let flat = flatten_metrics(&json!({
+            "iterations": {"value": 10, "unit": "count"},
+            "write": {"avg_ms": 0.5, "label": "hot"},
+            "levels": [{"tasks": 1}, {"tasks": 8}],
+            "legacy": "0.25",
+        }));
+        assert_eq!(flat["iterations"], Some(10.0));
+        assert_eq!(flat["write.avg_ms"], Some(0.5));
+        assert_eq!(flat["write.label"], None);
+        assert_eq!(flat["levels.1.tasks"], Some(8.0));
+        assert_eq!(flat["legacy"], Some(0.25));
+        assert_eq!(flat.len(), 6);
+    }
+
+    #[test]
+    fn test_round_trips_built_in_result_shapes() {
+        let mut results = synthetic_results(3);
+        let by_format = ValidationFormatMetrics {
+            invalid_schemas: 1,
+            latency: OperationMetrics::from_samples(&[0.1, 0.2, 0.3]),
+        };
+        results.push(BenchmarkResult::new(
+            "validation_operations".to_string(),
+            json!({"by_format": {"json_schema": by_format}, "status": "ok"}),
+        ));
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("results.csv");
+        write_csv(&results, &path).unwrap();
+        let rows = parse(&fs::read_to_string(&path).unwrap());
+
+        assert_eq!(rows.len(), results.len());
+        for (row, result) in rows.iter().zip(&results) {
+            assert_eq!(row["target_id"], result.target_id);
+            for (path, value) in flatten_metrics(&result.metrics) {
+                let cell = &row[&path];
+                assert_eq!(cell.parse::<f64>().ok(), value, "{} {}", result.target_id, path);
+            }
+        }
+        // Columns a result doesn't report, and text, are empty
+        assert_eq!(rows[0]["by_format.json_schema.p95_ms"], "");
+        assert_eq!(rows[3]["status"], "");
+        assert_eq!(rows[3]["by_format.json_schema.invalid_schemas"], "1");
+    }
+
+    #[test]
+    fn test_header_is_stable_and_fields_are_escaped() {
+        let results = [
+            BenchmarkResult::new("b, \"quoted\"".to_string(), json!({"z": 1, "a": {"p95_ms": 2}})),
+            BenchmarkResult::new("a".to_string(), json!({"m": 3})),
+        ];
+        let csv = to_csv(&results);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("target_id,timestamp,a.p95_ms,m,z"));
+        assert!(lines.next().unwrap().starts_with("\"b, \"\"quoted\"\"\","));
+        assert_eq!(to_csv(&results).lines().next(), csv.lines().next());
+    }
+}
@@ -0,0 +1,261 @@
+//! Names of the timestamped results files
+//!
+//! A [`FilenameTemplate`] names the file [`write_results`](super::write_results)
+//! writes for each run in the raw directory. Placeholders:
+//!
+//! | Placeholder | Value |
+//! |-------------|-------|
+//! | `{prefix}` | `benchmarks` |
+//! | `{timestamp}` | Time of the write, `20250601_120000` |
+//! | `{git_sha}` | Short commit of the run environment, or `unknown` |
+//! | `{branch}` | Git branch of the run environment, or `unknown` |
+//! | `{hostname}` | Host name of the run environment, or `unknown` |
+//!
+//! Templates are checked when parsed: unknown placeholders, path separators
+//! and characters that aren't portable in file names are errors, and
+//! `{timestamp}` is required so every run gets its own file. Placeholder
+//! values have such characters replaced with `-`, so `feature/x` is written
+//! `feature-x`.
+
+use crate::RunEnvironment;
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Template of the default names, e.g. `benchmarks_20250601_120000.json`
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{prefix}_{timestamp}";
+
+/// Longest file name most file systems accept, in bytes
+pub const MAX_FILENAME_LEN: usize = 255;
+
+/// Error parsing or rendering a [`FilenameTemplate`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FilenameTemplateError {
+    #[error("unknown placeholder {{{placeholder}}} in filename template '{template}' (expected prefix, timestamp, git_sha, branch or hostname)")]
+    UnknownPlaceholder { template: String, placeholder: String },
+
+    #[error("unclosed placeholder in filename template '{0}'")]
+    Unclosed(String),
+
+    #[error("filename template '{template}' contains {character:?}, which isn't safe in file names")]
+    UnsafeCharacter { template: String, character: char },
+
+    #[error("filename template '{0}' has no {{timestamp}}, so runs would overwrite each other")]
+    MissingTimestamp(String),
+
+    #[error("file name '{name}' is longer than {} bytes", MAX_FILENAME_LEN)]
+    TooLong { name: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Prefix,
+    Timestamp,
+    GitSha,
+    Branch,
+    Hostname,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// Parsed name template of the timestamped results files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilenameTemplate {
+    template: String,
+    segments: Vec<Segment>,
+}
+
+/// Whether `c` can appear in a file name on every common platform
+fn is_portable(c: char) -> bool {
+    !c.is_control() && !matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+}
+
+/// `value` with characters that aren't safe in file names replaced by `-`
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if is_portable(c) && !c.is_whitespace() { c } else { '-' })
+        .collect()
+}
+
+impl FilenameTemplate {
+    /// Parse `template`, see the [module docs](self)
+    pub fn parse(template: &str) -> Result<Self, FilenameTemplateError> {
+        let mut segments = Vec::new();
+        let mut rest = template;
+        while !rest.is_empty() {
+            let literal_end = rest.find('{').unwrap_or(rest.len());
+            let literal = &rest[..literal_end];
+            if let Some(character) = literal.chars().find(|&c| !is_portable(c) || c == '}') {
+                return Err(FilenameTemplateError::UnsafeCharacter {
+                    template: template.to_string(),
+                    character,
+                });
+            }
+            if !literal.is_empty() {
+                segments.push(Segment::Literal(literal.to_string()));
+            }
+            rest = &rest[literal_end..];
+            if rest.is_empty() {
+                break;
+            }
+
+            let end = rest.find('}').ok_or_else(|| FilenameTemplateError::Unclosed(template.to_string()))?;
+            let placeholder = match &rest[1..end] {
+                "prefix" => Placeholder::Prefix,
+                "timestamp" => Placeholder::Timestamp,
+                "git_sha" => Placeholder::GitSha,
+                "branch" => Placeholder::Branch,
+                "hostname" => Placeholder::Hostname,
+                other => {
+                    return Err(FilenameTemplateError::UnknownPlaceholder {
+                        template: template.to_string(),
+                        placeholder: other.to_string(),
+                    })
+                }
+            };
+            segments.push(Segment::Placeholder(placeholder));
+            rest = &rest[end + 1..];
+        }
+
+        if !segments.contains(&Segment::Placeholder(Placeholder::Timestamp)) {
+            return Err(FilenameTemplateError::MissingTimestamp(template.to_string()));
+        }
+        Ok(Self {
+            template: template.to_string(),
+            segments,
+        })
+    }
+
+    /// File name of a run written at `time` in `environment`, e.g. `benchmarks_20250601_120000.json`
+    pub fn render(
+        &self,
+        prefix: &str,
+        extension: &str,
+        time: DateTime<Utc>,
+        environment: Option<&RunEnvironment>,
+    ) -> Result<String, FilenameTemplateError> {
+        let unknown = |value: Option<&str>| sanitize(value.unwrap_or("unknown"));
+        let mut name = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => name.push_str(literal),
+                Segment::Placeholder(Placeholder::Prefix) => name.push_str(&sanitize(prefix)),
+                Segment::Placeholder(Placeholder::Timestamp) => {
+                    name.push_str(&time.format("%Y%m%d_%H%M%S").to_string())
+                }
+                Segment::Placeholder(Placeholder::GitSha) => {
+                    name.push_str(&unknown(environment.and_then(|env| env.short_commit())))
+                }
+                Segment::Placeholder(Placeholder::Branch) => {
+                    name.push_str(&unknown(environment.and_then(|env| env.git_branch.as_deref())))
+                }
+                Segment::Placeholder(Placeholder::Hostname) => {
+                    name.push_str(&unknown(environment.and_then(|env| env.hostname.as_deref())))
+                }
+            }
+        }
+        name.push('.');
+        name.push_str(extension);
+
+        if name.len() > MAX_FILENAME_LEN {
+            return Err(FilenameTemplateError::TooLong { name });
+        }
+        Ok(name)
+    }
+
+    /// The template as written
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
+}
+
+impl Default for FilenameTemplate {
+    fn default() -> Self {
+        Self::parse(DEFAULT_FILENAME_TEMPLATE).expect("default filename template is valid")
+    }
+}
+
+impl FromStr for FilenameTemplate {
+    type Err = FilenameTemplateError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        Self::parse(template)
+    }
+}
+
+impl fmt::Display for FilenameTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_renders_placeholders() {
+        let environment = RunEnvironment {
+            git_commit: Some("4f2c1a9e0b7d3c5e".to_string()),
+            git_branch: Some("feature/fast path".to_string()),
+            hostname: Some("ci-runner-3".to_string()),
+            ..RunEnvironment::default()
+        };
+        let template: FilenameTemplate = "{prefix}_{branch}_{git_sha}@{hostname}_{timestamp}".parse().unwrap();
+
+        assert_eq!(
+            template.render("benchmarks", "json.gz", time(), Some(&environment)).unwrap(),
+            "benchmarks_feature-fast-path_4f2c1a9e0b7d@ci-runner-3_20250601_120000.json.gz"
+        );
+        assert_eq!(
+            FilenameTemplate::parse("{git_sha}-{timestamp}").unwrap().render("benchmarks", "json", time(), None).unwrap(),
+            "unknown-20250601_120000.json"
+        );
+        assert_eq!(
+            FilenameTemplate::default().render("benchmarks", "json", time(), None).unwrap(),
+            "benchmarks_20250601_120000.json"
+        );
+    }
+
+    #[test]
+    fn test_invalid_templates_fail_to_parse() {
+        assert!(matches!(
+            FilenameTemplate::parse("{prefix}_{commit}_{timestamp}"),
+            Err(FilenameTemplateError::UnknownPlaceholder { placeholder, .. }) if placeholder == "commit"
+        ));
+        assert!(matches!(
+            FilenameTemplate::parse("runs/{timestamp}"),
+            Err(FilenameTemplateError::UnsafeCharacter { character: '/', .. })
+        ));
+        assert!(matches!(
+            FilenameTemplate::parse("{prefix}_{git_sha}"),
+            Err(FilenameTemplateError::MissingTimestamp(_))
+        ));
+        assert!(matches!(FilenameTemplate::parse("{timestamp"), Err(FilenameTemplateError::Unclosed(_))));
+        assert!(FilenameTemplate::parse("{timestamp}}").is_err());
+    }
+
+    #[test]
+    fn test_long_names_fail_to_render() {
+        let environment = RunEnvironment {
+            git_branch: Some("b".repeat(300)),
+            ..RunEnvironment::default()
+        };
+        let template = FilenameTemplate::parse("{branch}_{timestamp}").unwrap();
+        assert!(matches!(
+            template.render("benchmarks", "json", time(), Some(&environment)),
+            Err(FilenameTemplateError::TooLong { .. })
+        ));
+    }
+}
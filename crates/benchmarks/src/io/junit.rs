@@ -0,0 +1,238 @@
+//! JUnit XML export of a benchmark run
+//!
+//! [`write_junit`] writes one `<testsuite name="schema-registry-benchmarks">`
+//! with a `<testcase>` per target, timed by the result's
+//! [`duration_ms`](crate::BenchmarkResult::duration_ms). Targets that failed
+//! or timed out, and metrics over a regression gate, are `<failure>`
+//! elements of their target's testcase, so CI servers that render JUnit
+//! reports show them next to the passing targets.
+
+use crate::compare::GateViolation;
+use crate::runner::BenchmarkRunError;
+use crate::BenchmarkResult;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::fs;
+use std::path::Path;
+
+/// Name of the test suite, also used as every testcase's class name
+pub const SUITE_NAME: &str = "schema-registry-benchmarks";
+
+/// A failure reported against a target's testcase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JunitFailure {
+    pub target_id: String,
+    /// Kind of failure, the `type` attribute: `failed`, `timed_out` or `regression`
+    pub kind: &'static str,
+    pub message: String,
+}
+
+impl From<&BenchmarkRunError> for JunitFailure {
+    fn from(error: &BenchmarkRunError) -> Self {
+        let kind = match error {
+            BenchmarkRunError::TargetFailed { .. } => "failed",
+            BenchmarkRunError::TimedOut { .. } => "timed_out",
+        };
+        Self {
+            target_id: error.target_id().to_string(),
+            kind,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<&GateViolation> for JunitFailure {
+    fn from(violation: &GateViolation) -> Self {
+        Self {
+            target_id: violation.target_id.clone(),
+            kind: "regression",
+            message: violation.to_string(),
+        }
+    }
+}
+
+/// `text` escaped for XML text and attribute values
+///
+/// Characters XML 1.0 doesn't allow, such as most control characters, are dropped.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c < ' ' || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Seconds as JUnit `time` attributes write them
+fn seconds(ms: f64) -> String {
+    format!("{:.3}", ms / 1000.0)
+}
+
+/// JUnit XML of `results` and `failures`
+///
+/// Testcases follow `results`, then targets that only appear in `failures`.
+pub fn to_junit(results: &[BenchmarkResult], failures: &[JunitFailure]) -> String {
+    let mut targets: Vec<(&str, Option<&BenchmarkResult>)> =
+        results.iter().map(|result| (result.target_id.as_str(), Some(result))).collect();
+    for failure in failures {
+        if !targets.iter().any(|(id, _)| *id == failure.target_id) {
+            targets.push((failure.target_id.as_str(), None));
+        }
+    }
+
+    let total_ms: f64 = results.iter().filter_map(|result| result.duration_ms).sum();
+    let timestamp = results.iter().map(|result| result.timestamp).min().unwrap_or_else(Utc::now);
+    let failed = targets
+        .iter()
+        .filter(|(id, _)| failures.iter().any(|failure| failure.target_id == *id))
+        .count();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\" time=\"{}\" timestamp=\"{}\">\n",
+        SUITE_NAME,
+        targets.len(),
+        failed,
+        seconds(total_ms),
+        timestamp.format("%Y-%m-%dT%H:%M:%S")
+    ));
+    for (id, result) in targets {
+        let time = seconds(result.and_then(|result| result.duration_ms).unwrap_or(0.0));
+        let testcase = format!("  <testcase name=\"{}\" classname=\"{}\" time=\"{}\"", escape(id), SUITE_NAME, time);
+        let target_failures: Vec<&JunitFailure> = failures.iter().filter(|failure| failure.target_id == id).collect();
+        if target_failures.is_empty() {
+            xml.push_str(&format!("{}/>\n", testcase));
+            continue;
+        }
+        xml.push_str(&format!("{}>\n", testcase));
+        for failure in target_failures {
+            let message = escape(&failure.message);
+            xml.push_str(&format!(
+                "    <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                failure.kind, message, message
+            ));
+        }
+        xml.push_str("  </testcase>\n");
+    }
+    xml.push_str("</testsuite>\n");
+    xml
+}
+
+/// Write `results` and `failures` as a JUnit XML report, see [`to_junit`]
+pub fn write_junit(results: &[BenchmarkResult], failures: &[JunitFailure], path: &Path) -> Result<()> {
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    fs::write(path, to_junit(results, failures))
+        .with_context(|| format!("Failed to write JUnit report to {}", path.display()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::time::Duration;
+
+    /// Elements of `xml` by name, panicking unless tags nest and attributes are quoted
+    fn parse_elements(xml: &str) -> Vec<(String, String)> {
+        let mut open: Vec<String> = Vec::new();
+        let mut elements = Vec::new();
+        let mut rest = xml;
+        while let Some(start) = rest.find('<') {
+            let text = &rest[..start];
+            assert!(!text.contains('>'), "unescaped > in {text:?}");
+            let end = rest[start..].find('>').expect("unterminated tag") + start;
+            let tag = &rest[start + 1..end];
+            rest = &rest[end + 1..];
+            assert!(!tag.contains('<'), "unescaped < in <{tag}>");
+            assert_eq!(tag.matches('"').count() % 2, 0, "unbalanced quotes in <{tag}>");
+            if tag.starts_with('?') {
+                continue;
+            }
+            if let Some(closing) = tag.strip_prefix('/') {
+                assert_eq!(open.pop().as_deref(), Some(closing));
+                continue;
+            }
+            let name = tag.split_whitespace().next().unwrap().trim_end_matches('/').to_string();
+            if !tag.ends_with('/') {
+                open.push(name.clone());
+            }
+            elements.push((name, tag.to_string()));
+        }
+        assert!(open.is_empty(), "unclosed: {open:?}");
+        elements
+    }
+
+    fn result(id: &str, duration_ms: f64) -> BenchmarkResult {
+        let mut result = BenchmarkResult::new(id.to_string(), json!({}));
+        result.duration_ms = Some(duration_ms);
+        result
+    }
+
+    #[test]
+    fn test_targets_become_testcases_with_failures() {
+        let results = [result("storage", 1500.0), result("validation", 250.0)];
+        let errors = [
+            BenchmarkRunError::TargetFailed {
+                target_id: "broken".to_string(),
+                source: anyhow::anyhow!("fixture <drift> & \"quotes\"\u{1b}[31m"),
+            },
+            BenchmarkRunError::TimedOut {
+                target_id: "slow".to_string(),
+                elapsed: Duration::from_secs(90),
+            },
+        ];
+        let violation = GateViolation {
+            target_id: "storage".to_string(),
+            path: "write.p95_ms".to_string(),
+            baseline: 2.0,
+            current: 3.0,
+            delta_percent: Some(50.0),
+            allowed_percent: 10.0,
+        };
+        let failures: Vec<JunitFailure> =
+            errors.iter().map(JunitFailure::from).chain([JunitFailure::from(&violation)]).collect();
+
+        let xml = to_junit(&results, &failures);
+        let elements = parse_elements(&xml);
+
+        assert!(elements[0].1.starts_with("testsuite name=\"schema-registry-benchmarks\" tests=\"4\" failures=\"3\""));
+        assert!(elements[0].1.contains("time=\"1.750\""));
+        let testcases: Vec<&str> = elements.iter().filter(|(name, _)| name == "testcase").map(|(_, tag)| tag.as_str()).collect();
+        assert_eq!(testcases.len(), 4);
+        assert!(testcases[0].starts_with("testcase name=\"storage\" classname=\"schema-registry-benchmarks\" time=\"1.500\""));
+        assert!(testcases[1].ends_with('/'));
+        assert_eq!(elements.iter().filter(|(name, _)| name == "failure").count(), 3);
+
+        assert!(xml.contains("type=\"regression\" message=\"storage write.p95_ms: 2 -&gt; 3 (+50.0%, allowed 10%)\""));
+        assert!(xml.contains("fixture &lt;drift&gt; &amp; &quot;quotes&quot;[31m"));
+        assert!(!xml.contains('\u{1b}'));
+        assert!(xml.contains("<testcase name=\"slow\" classname=\"schema-registry-benchmarks\" time=\"0.000\">"));
+        assert!(xml.contains("type=\"timed_out\""));
+    }
+
+    #[test]
+    fn test_write_junit_without_failures() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("reports/junit.xml");
+        write_junit(&[result("storage", 10.0)], &[], &path).unwrap();
+
+        let xml = fs::read_to_string(&path).unwrap();
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(xml.contains("failures=\"0\""));
+        assert_eq!(parse_elements(&xml).len(), 2);
+    }
+}
@@ -0,0 +1,241 @@
+//! Where a run's artifacts are written
+//!
+//! [`OutputLayout`] names every path the writers and readers in
+//! [`io`](super) use, so a run can be redirected as a whole, or one artifact
+//! at a time, without touching the environment.
+
+use super::filename::FilenameTemplate;
+use crate::markdown::{Badge, TARGET_PAGES_DIR};
+use super::lock::DEFAULT_LOCK_TIMEOUT;
+use super::manifest::MANIFEST_FILE;
+use super::{default_output_dir, timestamped_filename};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Name of the copy of the newest results in the raw directory
+pub const LATEST_FILE: &str = "latest.json";
+
+/// Paths of a run's artifacts
+///
+/// [`OutputLayout::new`] puts everything under one root:
+///
+/// ```text
+/// <root>/summary.md
+/// <root>/summary.html
+/// <root>/results.csv
+/// <root>/report.html
+/// <root>/history.jsonl
+/// <root>/manifest.json
+/// <root>/.lock
+/// <root>/badges/<name>.json
+/// <root>/targets/<id>.md
+/// <root>/targets/<id>.html
+/// <root>/raw/latest.json
+/// <root>/raw/benchmarks_YYYYMMDD_HHMMSS.json(.gz)
+/// <root>/baselines/<name>.json
+/// ```
+///
+/// `summary.html` is `summary.md` rendered, with
+/// [`summary_html`](Self::summary_html), and so are the target pages'
+/// `.html` files; `report.html` is the sortable report of
+/// [`write_html`](super::write_html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputLayout {
+    pub root: PathBuf,
+    /// Directory of `latest.json` and the timestamped results files
+    pub raw_dir: PathBuf,
+    /// Directory of the named baselines
+    pub baselines_dir: PathBuf,
+    pub summary_file: PathBuf,
+    /// JSON Lines file [`write_results`](super::write_results) appends every run to
+    pub history_file: PathBuf,
+    /// Whether [`write_results`](super::write_results) appends to `history_file`
+    pub append_history: bool,
+    /// Whether the timestamped raw results are written as `.json.gz` (needs the `gzip` feature)
+    pub compress_raw: bool,
+    /// Name of the timestamped results files, `{prefix}_{timestamp}` by default
+    pub filename_template: FilenameTemplate,
+    /// How long [`write_results`](super::write_results) waits for another run's lock on the root
+    pub lock_timeout: Duration,
+    /// Badges [`write_results`](super::write_results) writes to the badges directory
+    pub badges: Vec<Badge>,
+    /// Whether [`write_results`](super::write_results) writes a detail page per target to the targets directory
+    pub target_pages: bool,
+    /// Whether [`write_results`](super::write_results) also writes the summary as HTML (needs the `markdown-html` feature)
+    pub summary_html: bool,
+}
+
+impl OutputLayout {
+    /// The standard layout under `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        Self {
+            raw_dir: root.join("raw"),
+            baselines_dir: root.join("baselines"),
+            summary_file: root.join("summary.md"),
+            history_file: root.join("history.jsonl"),
+            append_history: false,
+            compress_raw: false,
+            filename_template: FilenameTemplate::default(),
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
+            badges: Vec::new(),
+            target_pages: false,
+            summary_html: false,
+            root,
+        }
+    }
+
+    /// Write raw results to `dir` instead of `<root>/raw`
+    pub fn with_raw_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.raw_dir = dir.into();
+        self
+    }
+
+    /// Keep baselines in `dir` instead of `<root>/baselines`
+    pub fn with_baselines_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.baselines_dir = dir.into();
+        self
+    }
+
+    /// Write the markdown summary to `path` instead of `<root>/summary.md`
+    pub fn with_summary_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.summary_file = path.into();
+        self
+    }
+
+    /// Append each run's results to the history file
+    pub fn with_history(mut self, append: bool) -> Self {
+        self.append_history = append;
+        self
+    }
+
+    /// Keep the history in `path` instead of `<root>/history.jsonl`
+    pub fn with_history_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.history_file = path.into();
+        self
+    }
+
+    /// Gzip the timestamped raw results; `latest.json` stays uncompressed
+    pub fn with_compressed_raw(mut self, compress: bool) -> Self {
+        self.compress_raw = compress;
+        self
+    }
+
+    /// Name the timestamped results files with `template`, e.g. `{prefix}_{timestamp}_{git_sha}`
+    pub fn with_filename_template(mut self, template: FilenameTemplate) -> Self {
+        self.filename_template = template;
+        self
+    }
+
+    /// Wait up to `timeout` for a concurrent run to finish writing; zero fails right away
+    pub fn with_lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
+    /// Also write `badge` on every run
+    pub fn with_badge(mut self, badge: Badge) -> Self {
+        self.badges.push(badge);
+        self
+    }
+
+    /// Also write one markdown page per target with its detailed metrics and history
+    ///
+    /// Render the summary with
+    /// [`SummaryOptions::with_target_pages`](crate::markdown::SummaryOptions::with_target_pages)
+    /// so it links to the pages instead of inlining the metrics.
+    pub fn with_target_pages(mut self, target_pages: bool) -> Self {
+        self.target_pages = target_pages;
+        self
+    }
+
+    /// Also write the markdown summary converted to HTML, next to it as [`summary_html_file`](Self::summary_html_file)
+    ///
+    /// With [`target_pages`](Self::target_pages) the pages are rendered too, and
+    /// the HTML summary links them instead of the markdown.
+    pub fn with_summary_html(mut self, summary_html: bool) -> Self {
+        self.summary_html = summary_html;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// `latest.json` in the raw directory
+    pub fn latest_file(&self) -> PathBuf {
+        self.raw_dir.join(LATEST_FILE)
+    }
+
+    /// `results.csv` in the root, written by [`write_csv`](super::write_csv) on request
+    pub fn csv_file(&self) -> PathBuf {
+        self.root.join("results.csv")
+    }
+
+    /// `manifest.json` in the root, the index of runs [`write_results`](super::write_results) keeps
+    pub fn manifest_file(&self) -> PathBuf {
+        self.root.join(MANIFEST_FILE)
+    }
+
+    /// `badges` in the root, where [`write_results`](super::write_results) writes the layout's badges
+    pub fn badges_dir(&self) -> PathBuf {
+        self.root.join("badges")
+    }
+
+    /// `targets` in the root, where [`write_results`](super::write_results) writes the target pages
+    pub fn targets_dir(&self) -> PathBuf {
+        self.root.join(TARGET_PAGES_DIR)
+    }
+
+    /// `report.html` in the root, written by [`write_html`](super::write_html) on request
+    pub fn html_file(&self) -> PathBuf {
+        self.root.join("report.html")
+    }
+
+    /// The summary file with an `.html` extension, e.g. `summary.html`, for the rendered summary
+    pub fn summary_html_file(&self) -> PathBuf {
+        self.summary_file.with_extension("html")
+    }
+
+    /// A new timestamped file in the raw directory, e.g. `benchmarks_20250601_120000.json`
+    pub fn timestamped_file(&self, prefix: &str, extension: &str) -> PathBuf {
+        self.raw_dir.join(timestamped_filename(prefix, extension))
+    }
+}
+
+impl Default for OutputLayout {
+    /// The standard layout under [`default_output_dir`]
+    fn default() -> Self {
+        Self::new(default_output_dir())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_layout_and_overrides() {
+        let layout = OutputLayout::new("out");
+        assert_eq!(layout.raw_dir, PathBuf::from("out/raw"));
+        assert_eq!(layout.baselines_dir, PathBuf::from("out/baselines"));
+        assert_eq!(layout.summary_file, PathBuf::from("out/summary.md"));
+        assert_eq!(layout.latest_file(), PathBuf::from("out/raw/latest.json"));
+        assert_eq!(layout.csv_file(), PathBuf::from("out/results.csv"));
+        assert_eq!(layout.html_file(), PathBuf::from("out/report.html"));
+        assert_eq!(layout.badges_dir(), PathBuf::from("out/badges"));
+        assert_eq!(layout.targets_dir(), PathBuf::from("out/targets"));
+        assert!(!layout.target_pages);
+        assert_eq!(layout.summary_html_file(), PathBuf::from("out/summary.html"));
+        assert!(!layout.summary_html);
+        assert_eq!(layout.history_file, PathBuf::from("out/history.jsonl"));
+        assert!(!layout.append_history);
+
+        let layout = layout.with_raw_dir("/tmp/raw").with_summary_file("SUMMARY.md");
+        assert_eq!(layout.latest_file(), PathBuf::from("/tmp/raw/latest.json"));
+        assert!(layout.timestamped_file("benchmarks", "json").starts_with("/tmp/raw"));
+        assert_eq!(layout.summary_file, PathBuf::from("SUMMARY.md"));
+        assert_eq!(layout.summary_html_file(), PathBuf::from("SUMMARY.html"));
+        assert_eq!(layout.root(), Path::new("out"));
+    }
+}
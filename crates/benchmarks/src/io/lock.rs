@@ -0,0 +1,94 @@
+//! Advisory locking of an output directory
+//!
+//! [`write_results`](super::write_results) holds an [`OutputLock`] on
+//! `<root>/.lock` while it writes, so concurrent runs sharing an output
+//! directory take turns instead of interleaving writes to `latest.json`.
+//! The lock is advisory and only writers take it: readers never wait, and
+//! the operating system releases it when the holding process exits.
+//! Taking a held lock sleeps the thread between attempts, so async code
+//! writes with [`write_results_async`](super::write_results_async) instead.
+
+use anyhow::{bail, Context, Result};
+use fs2::FileExt;
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Name of the lock file in the output directory
+pub const LOCK_FILE: &str = ".lock";
+
+/// How long a writer waits for the lock unless the layout sets another timeout
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Interval between attempts to take a held lock
+const RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Exclusive lock on an output directory, released on drop
+#[derive(Debug)]
+pub struct OutputLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl OutputLock {
+    /// Lock `dir`, waiting up to `timeout` for another writer to release it
+    ///
+    /// A zero `timeout` fails right away if the lock is held.
+    pub fn acquire(dir: &Path, timeout: Duration) -> Result<Self> {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        let path = dir.join(LOCK_FILE);
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open lock file {}", path.display()))?;
+
+        let started = Instant::now();
+        while file.try_lock_exclusive().is_err() {
+            if started.elapsed() >= timeout {
+                bail!(
+                    "Another benchmark run is writing to {} (lock file {} held for over {:?}); \
+                     wait for it to finish or use a different output directory",
+                    dir.display(),
+                    path.display(),
+                    timeout
+                );
+            }
+            std::thread::sleep(RETRY_INTERVAL.min(timeout));
+        }
+        Ok(Self { file, path })
+    }
+
+    /// Path of the lock file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for OutputLock {
+    fn drop(&mut self) {
+        if let Err(e) = FileExt::unlock(&self.file) {
+            tracing::warn!("Failed to release {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_held_lock_times_out() {
+        let dir = TempDir::new().unwrap();
+        let held = OutputLock::acquire(dir.path(), Duration::ZERO).unwrap();
+        assert_eq!(held.path(), dir.path().join(LOCK_FILE));
+
+        let err = OutputLock::acquire(dir.path(), Duration::from_millis(100)).unwrap_err();
+        assert!(err.to_string().contains("Another benchmark run is writing to"));
+
+        drop(held);
+        OutputLock::acquire(dir.path(), Duration::ZERO).unwrap();
+    }
+}
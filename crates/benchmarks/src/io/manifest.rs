@@ -0,0 +1,159 @@
+//! Index of the runs in an output directory
+//!
+//! [`write_results`](super::write_results) appends a [`ManifestEntry`] per
+//! run to `<root>/manifest.json`, so tools can list runs without opening
+//! every raw file:
+//!
+//! ```json
+//! {
+//!   "runs": [
+//!     {
+//!       "file": "benchmarks_20250601_120000.json",
+//!       "timestamp": "2025-06-01T12:00:00Z",
+//!       "target_ids": ["storage_operations", "validation_operations"],
+//!       "git_sha": "4f2c1a9e...",
+//!       "result_count": 2
+//!     }
+//!   ]
+//! }
+//! ```
+
+use crate::BenchmarkResult;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Name of the manifest in the output root
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// One run in the manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Name of the run's timestamped file in the raw directory
+    pub file: String,
+    /// Time of the run's earliest result
+    pub timestamp: DateTime<Utc>,
+    pub target_ids: Vec<String>,
+    /// Commit the run was built from, when the results carry an environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_sha: Option<String>,
+    pub result_count: usize,
+}
+
+impl ManifestEntry {
+    /// Entry for `results` written to the raw file `file`
+    pub fn new(file: impl Into<String>, results: &[BenchmarkResult]) -> Self {
+        Self {
+            file: file.into(),
+            timestamp: results.iter().map(|result| result.timestamp).min().unwrap_or_else(Utc::now),
+            target_ids: results.iter().map(|result| result.target_id.clone()).collect(),
+            git_sha: results
+                .iter()
+                .find_map(|result| result.environment.as_ref()?.git_commit.clone()),
+            result_count: results.len(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    runs: Vec<ManifestEntry>,
+}
+
+/// Runs listed in the manifest at `path`, oldest first
+///
+/// A missing manifest lists no runs; an unreadable one is an error.
+pub fn read_manifest(path: &Path) -> Result<Vec<ManifestEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read manifest {}", path.display()))?;
+    let manifest: Manifest =
+        serde_json::from_str(&content).with_context(|| format!("Failed to parse manifest {}", path.display()))?;
+    Ok(manifest.runs)
+}
+
+/// Append `entry` to the manifest at `path`
+///
+/// A missing manifest is created. A corrupt one is moved aside to
+/// `manifest.json.corrupt` with a warning and a new manifest is started, so
+/// a damaged index never stops results from being written. The manifest is
+/// replaced in one rename, so readers see either the old or the new list.
+pub fn append_manifest(entry: ManifestEntry, path: &Path) -> Result<()> {
+    let mut runs = match read_manifest(path) {
+        Ok(runs) => runs,
+        Err(e) => {
+            let aside = path.with_extension("json.corrupt");
+            tracing::warn!("Starting a new manifest, moved {} to {}: {:#}", path.display(), aside.display(), e);
+            fs::rename(path, &aside)
+                .with_context(|| format!("Failed to move corrupt manifest to {}", aside.display()))?;
+            Vec::new()
+        }
+    };
+    runs.push(entry);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let json = serde_json::to_string_pretty(&Manifest { runs }).context("Failed to serialize manifest")?;
+    let partial = path.with_extension("json.tmp");
+    fs::write(&partial, json).with_context(|| format!("Failed to write manifest {}", partial.display()))?;
+    fs::rename(&partial, path).with_context(|| format!("Failed to replace manifest {}", path.display()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RunEnvironment;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn results() -> Vec<BenchmarkResult> {
+        let mut storage = BenchmarkResult::new("storage".to_string(), json!({}));
+        storage.environment = Some(RunEnvironment {
+            git_commit: Some("4f2c1a9e".to_string()),
+            ..RunEnvironment::default()
+        });
+        vec![storage, BenchmarkResult::new("validation".to_string(), json!({}))]
+    }
+
+    #[test]
+    fn test_appends_entries() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(MANIFEST_FILE);
+        assert!(read_manifest(&path).unwrap().is_empty());
+
+        append_manifest(ManifestEntry::new("benchmarks_1.json", &results()), &path).unwrap();
+        append_manifest(ManifestEntry::new("benchmarks_2.json", &results()[1..]), &path).unwrap();
+
+        let runs = read_manifest(&path).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].file, "benchmarks_1.json");
+        assert_eq!(runs[0].target_ids, ["storage", "validation"]);
+        assert_eq!(runs[0].git_sha.as_deref(), Some("4f2c1a9e"));
+        assert_eq!(runs[0].result_count, 2);
+        assert_eq!(runs[1].git_sha, None);
+    }
+
+    #[test]
+    fn test_corrupt_manifest_is_moved_aside() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(MANIFEST_FILE);
+        fs::write(&path, "{\"runs\": [{\"file\": ").unwrap();
+        assert!(read_manifest(&path).is_err());
+
+        append_manifest(ManifestEntry::new("benchmarks_1.json", &results()), &path).unwrap();
+
+        assert_eq!(read_manifest(&path).unwrap().len(), 1);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("manifest.json.corrupt")).unwrap(),
+            "{\"runs\": [{\"file\": "
+        );
+    }
+}
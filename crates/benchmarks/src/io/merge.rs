@@ -0,0 +1,170 @@
+//! Combining results files from several machines
+//!
+//! [`merge_results`] concatenates the results of several files, such as the
+//! `latest.json` of each CI machine. A target id that appears in more than
+//! one input is handled by a [`DuplicatePolicy`].
+
+use super::read_json;
+use crate::BenchmarkResult;
+use anyhow::{bail, Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// What [`merge_results`] does with a target id found in several inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicatePolicy {
+    /// Fail, naming the target and the inputs it appears in
+    #[default]
+    Error,
+    /// Keep every copy, renamed `<target_id>@<source>` after its input file's stem
+    Suffix,
+}
+
+impl FromStr for DuplicatePolicy {
+    type Err = String;
+
+    fn from_str(raw: &str) -> std::result::Result<Self, Self::Err> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "error" => Ok(DuplicatePolicy::Error),
+            "suffix" => Ok(DuplicatePolicy::Suffix),
+            _ => Err(format!("unknown duplicate policy '{}' (expected error or suffix)", raw)),
+        }
+    }
+}
+
+/// Name of each input in suffixed target ids: its file stem, or `input<n>` if stems collide
+fn source_names(paths: &[PathBuf]) -> Vec<String> {
+    let stems: Vec<String> = paths
+        .iter()
+        .map(|path| {
+            let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+            let name = name.strip_suffix(".gz").unwrap_or(&name);
+            Path::new(name).file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default()
+        })
+        .collect();
+    let unique: BTreeSet<&str> = stems.iter().map(String::as_str).collect();
+    if unique.len() == stems.len() && !unique.contains("") {
+        stems
+    } else {
+        (1..=paths.len()).map(|n| format!("input{}", n)).collect()
+    }
+}
+
+/// Results of every file in `paths`, in input order
+///
+/// A target id that appears in more than one input is an error with
+/// [`DuplicatePolicy::Error`]; with [`DuplicatePolicy::Suffix`] each of its
+/// copies is renamed after the input it came from. Repeats within a single
+/// input are left alone.
+pub fn merge_results(paths: &[PathBuf], policy: DuplicatePolicy) -> Result<Vec<BenchmarkResult>> {
+    let inputs = paths
+        .iter()
+        .map(|path| read_json(path).with_context(|| format!("Failed to read {}", path.display())))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut sources: BTreeMap<&str, BTreeSet<usize>> = BTreeMap::new();
+    for (index, results) in inputs.iter().enumerate() {
+        for result in results {
+            sources.entry(result.target_id.as_str()).or_default().insert(index);
+        }
+    }
+    let duplicates: BTreeSet<String> = sources
+        .into_iter()
+        .filter(|(_, inputs)| inputs.len() > 1)
+        .map(|(id, _)| id.to_string())
+        .collect();
+
+    if policy == DuplicatePolicy::Error {
+        if let Some(id) = duplicates.first() {
+            let found_in: Vec<String> = paths
+                .iter()
+                .zip(&inputs)
+                .filter(|(_, results)| results.iter().any(|result| result.target_id == *id))
+                .map(|(path, _)| path.display().to_string())
+                .collect();
+            bail!(
+                "Target {} appears in more than one input ({}); use the suffix policy to keep every copy",
+                id,
+                found_in.join(", ")
+            );
+        }
+    }
+
+    let names = source_names(paths);
+    Ok(inputs
+        .into_iter()
+        .zip(names)
+        .flat_map(|(results, source)| {
+            let duplicates = &duplicates;
+            results.into_iter().map(move |mut result| {
+                if duplicates.contains(&result.target_id) {
+                    result.target_id = format!("{}@{}", result.target_id, source);
+                }
+                result
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::write_json;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, name: &str, ids: &[&str]) -> PathBuf {
+        let path = dir.join(name);
+        let results: Vec<BenchmarkResult> =
+            ids.iter().map(|id| BenchmarkResult::new(id.to_string(), json!({"p95_ms": 1.0}))).collect();
+        write_json(&results, &path).unwrap();
+        path
+    }
+
+    fn ids(results: &[BenchmarkResult]) -> Vec<&str> {
+        results.iter().map(|result| result.target_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_concatenates_distinct_targets() {
+        let dir = TempDir::new().unwrap();
+        let paths = [
+            write(dir.path(), "linux.json", &["storage", "validation"]),
+            write(dir.path(), "macos.json", &["compatibility"]),
+        ];
+
+        let merged = merge_results(&paths, DuplicatePolicy::Error).unwrap();
+        assert_eq!(ids(&merged), ["storage", "validation", "compatibility"]);
+    }
+
+    #[test]
+    fn test_duplicates_error_or_get_suffixes() {
+        let dir = TempDir::new().unwrap();
+        let paths = [
+            write(dir.path(), "linux.json", &["storage", "validation"]),
+            write(dir.path(), "macos.json", &["storage", "compatibility"]),
+        ];
+
+        let err = merge_results(&paths, DuplicatePolicy::Error).unwrap_err().to_string();
+        assert!(err.contains("Target storage appears in more than one input"));
+        assert!(err.contains("linux.json") && err.contains("macos.json"));
+
+        let merged = merge_results(&paths, DuplicatePolicy::Suffix).unwrap();
+        assert_eq!(ids(&merged), ["storage@linux", "validation", "storage@macos", "compatibility"]);
+    }
+
+    #[test]
+    fn test_colliding_stems_fall_back_to_positions() {
+        let dir = TempDir::new().unwrap();
+        let paths = [
+            write(dir.path(), "a/latest.json", &["storage"]),
+            write(dir.path(), "b/latest.json", &["storage"]),
+        ];
+
+        let merged = merge_results(&paths, DuplicatePolicy::Suffix).unwrap();
+        assert_eq!(ids(&merged), ["storage@input1", "storage@input2"]);
+        assert_eq!("Suffix".parse::<DuplicatePolicy>(), Ok(DuplicatePolicy::Suffix));
+        assert!("rename".parse::<DuplicatePolicy>().is_err());
+    }
+}
@@ -0,0 +1,243 @@
+//! Upgrades of archived benchmark results
+//!
+//! Every [`BenchmarkResult`](crate::BenchmarkResult) carries a
+//! `format_version`. Documents are upgraded one version at a time, as JSON,
+//! before they are deserialized, so each step only has to know the shape
+//! it replaces.
+//!
+//! | Version | Shape |
+//! |---------|-------|
+//! | 1 | No `format_version` field. Statistics may be strings (`"avg_ms": "0.105"`) and runs before warmup support have no `warmup_iterations`. |
+//! | 2 | Statistics are numbers and every result with `iterations` reports `warmup_iterations`. |
+//! | 3 | Measured values are `{"value": .., "unit": ..}` objects (see [`crate::units`]); inputs such as `seed` stay numbers. |
+
+use crate::metrics::migrate_metrics;
+use crate::units;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+/// Version written by this build
+pub const CURRENT_FORMAT_VERSION: u32 = 3;
+
+/// Version of results written before the field existed
+pub const LEGACY_FORMAT_VERSION: u32 = 1;
+
+/// `serde(default)` for `format_version`
+pub(crate) fn legacy_format_version() -> u32 {
+    LEGACY_FORMAT_VERSION
+}
+
+/// Error raised while upgrading a results document
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    /// The document is not an array of results
+    #[error("expected an array of benchmark results")]
+    NotAnArray,
+
+    /// A result is not a JSON object
+    #[error("benchmark result {index} is not an object")]
+    NotAnObject { index: usize },
+
+    /// `format_version` is not a positive integer
+    #[error("benchmark result {index} has an invalid format_version: {value}")]
+    InvalidVersion { index: usize, value: Value },
+
+    /// Written by a newer build than this one
+    #[error(
+        "benchmark result {index} ({target_id}) uses format version {found}, but this build reads \
+         versions up to {supported}; upgrade schema-registry-benchmarks to read it"
+    )]
+    UnsupportedVersion {
+        index: usize,
+        target_id: String,
+        found: u64,
+        supported: u32,
+    },
+}
+
+/// Upgrade every result in a `read_json` document to [`CURRENT_FORMAT_VERSION`]
+pub fn migrate_results(document: &mut Value) -> Result<(), MigrationError> {
+    let results = document.as_array_mut().ok_or(MigrationError::NotAnArray)?;
+    for (index, result) in results.iter_mut().enumerate() {
+        migrate_result(index, result)?;
+    }
+    Ok(())
+}
+
+/// Upgrade one serialized result in place; `index` is used in errors
+pub fn migrate_result(index: usize, result: &mut Value) -> Result<(), MigrationError> {
+    let map = result.as_object_mut().ok_or(MigrationError::NotAnObject { index })?;
+    let mut version = match map.get("format_version") {
+        None => u64::from(LEGACY_FORMAT_VERSION),
+        Some(value) => value
+            .as_u64()
+            .filter(|version| *version >= 1)
+            .ok_or_else(|| MigrationError::InvalidVersion {
+                index,
+                value: value.clone(),
+            })?,
+    };
+    if version > u64::from(CURRENT_FORMAT_VERSION) {
+        return Err(MigrationError::UnsupportedVersion {
+            index,
+            target_id: map
+                .get("target_id")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown target")
+                .to_string(),
+            found: version,
+            supported: CURRENT_FORMAT_VERSION,
+        });
+    }
+
+    while version < u64::from(CURRENT_FORMAT_VERSION) {
+        match version {
+            1 => v1_to_v2(map),
+            2 => v2_to_v3(map),
+            _ => unreachable!("no migration from format version {}", version),
+        }
+        version += 1;
+    }
+    map.insert("format_version".to_string(), Value::from(CURRENT_FORMAT_VERSION));
+    Ok(())
+}
+
+/// Numeric statistics, and zero warmup for runs that predate warmup iterations
+fn v1_to_v2(result: &mut Map<String, Value>) {
+    if let Some(metrics) = result.get_mut("metrics") {
+        migrate_metrics(metrics);
+        if let Value::Object(metrics) = metrics {
+            if metrics.contains_key("iterations") && !metrics.contains_key("warmup_iterations") {
+                metrics.insert("warmup_iterations".to_string(), Value::from(0));
+            }
+        }
+    }
+}
+
+/// Units recorded with every measured value, inferred from the keys
+fn v2_to_v3(result: &mut Map<String, Value>) {
+    if let Some(metrics) = result.get_mut("metrics") {
+        units::annotate(metrics);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BenchmarkResult;
+    use serde_json::json;
+
+    fn upgrade(mut document: Value) -> Vec<BenchmarkResult> {
+        migrate_results(&mut document).unwrap();
+        serde_json::from_value(document).unwrap()
+    }
+
+    #[test]
+    fn test_original_string_metrics_shape_upgrades() {
+        let document = json!([{
+            "target_id": "storage_operations",
+            "metrics": {
+                "iterations": 10,
+                "write": {"avg_ms": "0.105", "min_ms": "0.100", "max_ms": "0.115"}
+            },
+            "timestamp": "2025-01-01T00:00:00Z"
+        }]);
+
+        let results = upgrade(document);
+        let result = &results[0];
+        assert_eq!(result.format_version, CURRENT_FORMAT_VERSION);
+        assert_eq!(result.metrics["write"]["avg_ms"], json!({"value": 0.105, "unit": "milliseconds"}));
+        assert_eq!(result.metrics["warmup_iterations"], json!({"value": 0, "unit": "count"}));
+        assert!(!result.parallel);
+
+        // The upgraded result survives a write and re-read unchanged
+        let reread = upgrade(serde_json::to_value(&results).unwrap());
+        assert_eq!(reread[0].metrics, result.metrics);
+        assert_eq!(reread[0].format_version, CURRENT_FORMAT_VERSION);
+    }
+
+    #[test]
+    fn test_unversioned_typed_metrics_shape_upgrades() {
+        let document = json!([{
+            "target_id": "validation_operations",
+            "metrics": {
+                "iterations": 3,
+                "warmup_iterations": 1,
+                "json_schema": {"avg_ms": 0.2, "p95_ms": 0.3, "cv_percent": 4.5}
+            },
+            "timestamp": "2025-06-01T12:00:00Z",
+            "parallel": true,
+            "shard": 2,
+            "environment": {"os": "linux x86_64", "git_commit": "0123456789abcdef"}
+        }]);
+
+        let results = upgrade(document);
+        let result = &results[0];
+        assert_eq!(result.format_version, CURRENT_FORMAT_VERSION);
+        assert_eq!(result.metrics["warmup_iterations"]["value"], 1);
+        assert_eq!(result.metrics["json_schema"]["cv_percent"], json!({"value": 4.5, "unit": "percent"}));
+        assert_eq!(result.shard, Some(2));
+        assert_eq!(result.environment.as_ref().unwrap().short_commit(), Some("0123456789ab"));
+
+        let reread = upgrade(serde_json::to_value(&results).unwrap());
+        assert_eq!(reread[0].metrics, result.metrics);
+        assert!(reread[0].parallel);
+    }
+
+    #[test]
+    fn test_version_2_metrics_gain_units() {
+        let document = json!([{
+            "target_id": "workload_hot",
+            "format_version": 2,
+            "metrics": {
+                "iterations": 5,
+                "seed": 7,
+                "operations": {"read": {"p95_ms": 0.4, "operations": 50, "throughput_ops_per_sec": 1200.0}},
+                "memory": {"rss_peak_bytes": 4096}
+            },
+            "timestamp": "2025-09-01T00:00:00Z"
+        }]);
+
+        let results = upgrade(document);
+        let metrics = &results[0].metrics;
+        assert_eq!(metrics["seed"], 7);
+        assert_eq!(metrics["operations"]["read"]["p95_ms"]["unit"], "milliseconds");
+        assert_eq!(metrics["operations"]["read"]["operations"]["unit"], "count");
+        assert_eq!(metrics["operations"]["read"]["throughput_ops_per_sec"]["unit"], "ops_per_sec");
+        assert_eq!(metrics["memory"]["rss_peak_bytes"], json!({"value": 4096, "unit": "bytes"}));
+
+        let typed: crate::metrics::MemoryMetrics = serde_json::from_value(metrics["memory"].clone()).unwrap();
+        assert_eq!(typed.rss_peak_bytes, Some(4096));
+    }
+
+    #[test]
+    fn test_missing_version_deserializes_as_legacy() {
+        let result: BenchmarkResult = serde_json::from_value(json!({
+            "target_id": "t",
+            "metrics": {},
+            "timestamp": "2025-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(result.format_version, LEGACY_FORMAT_VERSION);
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let mut document = json!([{
+            "target_id": "storage_operations",
+            "format_version": CURRENT_FORMAT_VERSION + 1,
+            "metrics": {"latency": {"unit": "ms"}},
+            "timestamp": "2030-01-01T00:00:00Z"
+        }]);
+
+        let err = migrate_results(&mut document).unwrap_err();
+        assert!(matches!(err, MigrationError::UnsupportedVersion { index: 0, .. }));
+        assert!(err.to_string().contains("storage_operations"));
+
+        let mut invalid = json!([{"format_version": "two"}]);
+        assert!(matches!(
+            migrate_results(&mut invalid),
+            Err(MigrationError::InvalidVersion { .. })
+        ));
+    }
+}
@@ -0,0 +1,1042 @@
+//! I/O utilities for benchmark results
+//!
+//! Readers upgrade archived results to the current format with
+//! [`migrations`] before deserializing them.
+
+pub mod checksums;
+pub mod csv;
+pub mod filename;
+pub mod junit;
+pub mod layout;
+pub mod lock;
+pub mod manifest;
+pub mod merge;
+pub mod migrations;
+pub mod prometheus;
+pub mod retention;
+
+pub use checksums::{forget_checksums, record_checksums, verify_checksums, ChecksumMismatch};
+pub use csv::{to_csv, write_csv};
+pub use filename::{FilenameTemplate, FilenameTemplateError};
+pub use junit::{to_junit, write_junit, JunitFailure};
+pub use layout::OutputLayout;
+pub use lock::OutputLock;
+pub use manifest::{append_manifest, read_manifest, ManifestEntry};
+pub use merge::{merge_results, DuplicatePolicy};
+pub use prometheus::{to_prometheus, write_prometheus};
+pub use retention::{prune_raw_results, PruneReport, RetentionPolicy};
+
+use crate::markdown::{self, Badge, SummaryOptions};
+use crate::shard::RunFile;
+use crate::{trends, BenchmarkResult};
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Write benchmark results to a JSON file
+pub fn write_json(results: &[BenchmarkResult], path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(results)
+        .context("Failed to serialize benchmark results")?;
+
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    fs::write(path, json)
+        .with_context(|| format!("Failed to write benchmark results to {}", path.display()))?;
+
+    Ok(())
+}
+
+/// Write benchmark results to a markdown file
+pub fn write_markdown(content: &str, path: &Path) -> Result<()> {
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    fs::write(path, content)
+        .with_context(|| format!("Failed to write markdown to {}", path.display()))?;
+
+    Ok(())
+}
+
+/// Write an HTML report, see [`report::generate_html`](crate::report::generate_html)
+pub fn write_html(content: &str, path: &Path) -> Result<()> {
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    fs::write(path, content)
+        .with_context(|| format!("Failed to write HTML to {}", path.display()))?;
+
+    Ok(())
+}
+
+/// Write benchmark results to a gzip-compressed JSON file
+#[cfg(feature = "gzip")]
+pub fn write_json_gz(results: &[BenchmarkResult], path: &Path) -> Result<()> {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    let json = serde_json::to_vec(results).context("Failed to serialize benchmark results")?;
+
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    let file = fs::File::create(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    encoder
+        .write_all(&json)
+        .with_context(|| format!("Failed to write benchmark results to {}", path.display()))?;
+    encoder
+        .finish()
+        .with_context(|| format!("Failed to write benchmark results to {}", path.display()))?;
+
+    Ok(())
+}
+
+/// Whether `path` names a gzip-compressed file
+pub fn is_gzip(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("gz")
+}
+
+/// Contents of `path`, decompressed if it ends in `.gz`
+fn read_text(path: &Path) -> Result<String> {
+    if !is_gzip(path) {
+        return fs::read_to_string(path)
+            .with_context(|| format!("Failed to read benchmark results from {}", path.display()));
+    }
+    #[cfg(feature = "gzip")]
+    {
+        let file = fs::File::open(path)
+            .with_context(|| format!("Failed to read benchmark results from {}", path.display()))?;
+        let mut content = String::new();
+        flate2::read::GzDecoder::new(file)
+            .read_to_string(&mut content)
+            .with_context(|| format!("Failed to decompress benchmark results from {}", path.display()))?;
+        Ok(content)
+    }
+    #[cfg(not(feature = "gzip"))]
+    {
+        anyhow::bail!("Cannot read {}: built without the `gzip` feature", path.display())
+    }
+}
+
+/// Read benchmark results from a JSON file
+///
+/// Files ending in `.gz` are decompressed first, which needs the `gzip`
+/// feature.
+pub fn read_json(path: &Path) -> Result<Vec<BenchmarkResult>> {
+    let content = read_text(path)?;
+
+    let mut document: serde_json::Value = serde_json::from_str(&content)
+        .context("Failed to parse benchmark results JSON")?;
+    migrations::migrate_results(&mut document)
+        .with_context(|| format!("Cannot read benchmark results from {}", path.display()))?;
+
+    let results: Vec<BenchmarkResult> = serde_json::from_value(document)
+        .context("Failed to parse benchmark results JSON")?;
+
+    Ok(results)
+}
+
+/// Append `results` to a JSON Lines file, one result per line
+///
+/// Creates the file if needed and never truncates it. All lines go out in a
+/// single write. If the file doesn't end in a newline, a writer was
+/// interrupted mid-line; that line is terminated first so it can't swallow
+/// the first new result.
+pub fn append_jsonl(results: &[BenchmarkResult], path: &Path) -> Result<()> {
+    let mut lines = String::new();
+    for result in results {
+        lines.push_str(&serde_json::to_string(result).context("Failed to serialize benchmark result")?);
+        lines.push('\n');
+    }
+
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .read(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    if ends_mid_line(&mut file)? {
+        lines.insert(0, '\n');
+    }
+    file.write_all(lines.as_bytes())
+        .with_context(|| format!("Failed to append benchmark results to {}", path.display()))?;
+
+    Ok(())
+}
+
+/// Whether `file` is non-empty and its last byte isn't a newline
+fn ends_mid_line(file: &mut fs::File) -> Result<bool> {
+    let len = file.metadata()?.len();
+    if len == 0 {
+        return Ok(false);
+    }
+    let mut last = [0u8; 1];
+    file.seek(SeekFrom::Start(len - 1))?;
+    file.read_exact(&mut last)?;
+    Ok(last[0] != b'\n')
+}
+
+/// Read benchmark results from a JSON Lines file written by [`append_jsonl`]
+///
+/// Blank lines are ignored. A line that isn't valid JSON is what an
+/// interrupted writer left behind, such as a trailing partial line, and is
+/// skipped with a warning. Lines that are JSON but not a readable result are
+/// errors, as in [`read_json`].
+pub fn read_jsonl(path: &Path) -> Result<Vec<BenchmarkResult>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read benchmark results from {}", path.display()))?;
+
+    let mut results = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut result: serde_json::Value = match serde_json::from_str(line) {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::warn!("Skipping partial line {} of {}: {}", index + 1, path.display(), e);
+                continue;
+            }
+        };
+        migrations::migrate_result(index, &mut result)
+            .with_context(|| format!("Cannot read line {} of {}", index + 1, path.display()))?;
+        results.push(
+            serde_json::from_value(result)
+                .with_context(|| format!("Failed to parse line {} of {}", index + 1, path.display()))?,
+        );
+    }
+
+    Ok(results)
+}
+
+/// Write a sharded or merged run to a JSON file
+pub fn write_run_file(run: &RunFile, path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(run).context("Failed to serialize benchmark run")?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    fs::write(path, json).with_context(|| format!("Failed to write benchmark run to {}", path.display()))?;
+
+    Ok(())
+}
+
+/// Read a run written by [`write_run_file`]
+pub fn read_run_file(path: &Path) -> Result<RunFile> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read benchmark run from {}", path.display()))?;
+
+    let mut document: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse benchmark run {}", path.display()))?;
+    if let Some(results) = document.get_mut("results") {
+        migrations::migrate_results(results)
+            .with_context(|| format!("Cannot read benchmark run from {}", path.display()))?;
+    }
+
+    serde_json::from_value(document).with_context(|| format!("Failed to parse benchmark run {}", path.display()))
+}
+
+/// Every results file in the layout's raw directory with the time of its run, oldest first
+///
+/// Times come from `<prefix>_YYYYMMDD_HHMMSS.json` file names as written by
+/// [`write_results`], or else the earliest result timestamp in the file.
+/// Compressed `.json.gz` files count too, and are read with the `gzip`
+/// feature. `latest.json` is a copy of the newest run and is left out. Files that
+/// can't be read or parsed are skipped with a warning; a missing directory
+/// is an empty history.
+pub fn read_history(layout: &OutputLayout) -> Result<Vec<(DateTime<Utc>, Vec<BenchmarkResult>)>> {
+    let dir = &layout.raw_dir;
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to list results in {}", dir.display())),
+    };
+
+    let mut history = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let is_json = name.ends_with(".json") || name.ends_with(".json.gz");
+        if !is_json || name == layout::LATEST_FILE {
+            continue;
+        }
+        let results = match read_json(&path) {
+            Ok(results) => results,
+            Err(e) => {
+                tracing::warn!("Skipping {} in benchmark history: {:#}", path.display(), e);
+                continue;
+            }
+        };
+        let timestamp = filename_timestamp(&path)
+            .or_else(|| results.iter().map(|result| result.timestamp).min());
+        match timestamp {
+            Some(timestamp) => history.push((timestamp, results)),
+            None => tracing::warn!("Skipping {} in benchmark history: no timestamp", path.display()),
+        }
+    }
+    history.sort_by_key(|(timestamp, _)| *timestamp);
+    Ok(history)
+}
+
+/// Time in a name written by [`timestamped_filename`], e.g. `benchmarks_20250601_120000.json(.gz)`
+///
+/// The rightmost `YYYYMMDD_HHMMSS` in the name counts, so names from a
+/// [`FilenameTemplate`] with fields after `{timestamp}` are dated too.
+fn filename_timestamp(path: &Path) -> Option<DateTime<Utc>> {
+    let name = path.file_name()?.to_str()?;
+    let stem = name.strip_suffix(".gz").unwrap_or(name).strip_suffix(".json")?;
+    (0..=stem.len().checked_sub(15)?)
+        .rev()
+        .find_map(|start| NaiveDateTime::parse_from_str(stem.get(start..start + 15)?, "%Y%m%d_%H%M%S").ok())
+        .map(|time| time.and_utc())
+}
+
+/// Environment variable overriding [`default_output_dir`]
+pub const OUTPUT_DIR_ENV: &str = "BENCHMARK_OUTPUT_DIR";
+
+/// Get the default output directory path
+///
+/// `BENCHMARK_OUTPUT_DIR` when it is set and not empty, otherwise
+/// `benchmarks/output`.
+pub fn default_output_dir() -> PathBuf {
+    output_dir_from(std::env::var_os(OUTPUT_DIR_ENV))
+}
+
+/// The output directory `value` of [`OUTPUT_DIR_ENV`] selects
+fn output_dir_from(value: Option<std::ffi::OsString>) -> PathBuf {
+    value
+        .filter(|dir| !dir.is_empty())
+        .map_or_else(|| PathBuf::from("benchmarks/output"), PathBuf::from)
+}
+
+fn check_badge_names(badges: &[Badge]) -> Result<()> {
+    for badge in badges {
+        if Path::new(&badge.name).file_name() != Some(std::ffi::OsStr::new(&badge.name)) {
+            anyhow::bail!("Badge name '{}' isn't a plain file name", badge.name);
+        }
+    }
+    Ok(())
+}
+
+/// Write each of `badges` for `results` to `<dir>/<name>.json`, returning the paths written
+///
+/// Badge names must be plain file names; a bad one fails before anything is written.
+pub fn write_badges(results: &[BenchmarkResult], badges: &[Badge], dir: &Path) -> Result<Vec<PathBuf>> {
+    check_badge_names(badges)?;
+    if !badges.is_empty() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create badges directory: {}", dir.display()))?;
+    }
+
+    let mut written = Vec::new();
+    for badge in badges {
+        let path = dir.join(format!("{}.json", badge.name));
+        fs::write(&path, badge.render(results)).with_context(|| format!("Failed to write badge {}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Runs in the history table of each target page
+pub const TARGET_PAGE_RUNS: usize = 10;
+
+/// Write a [page](markdown::generate_target_page) per target of `results` to `dir`, returning the paths written
+///
+/// Pages are named by [`markdown::target_page_names`], which the summary's
+/// links use too. A target with several results gets the page of its last.
+pub fn write_target_pages(results: &[BenchmarkResult], options: &SummaryOptions, dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for (_, name, page) in target_pages(results, options, dir)? {
+        let path = dir.join(name);
+        write_markdown(&page, &path)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// [`write_target_pages`] rendered [as HTML](markdown::to_html_document), as `<name>.html` next to the markdown
+///
+/// These are the pages a summary run through
+/// [`markdown::link_html_target_pages`] links. Needs the `markdown-html` feature.
+#[cfg(feature = "markdown-html")]
+pub fn write_target_pages_html(results: &[BenchmarkResult], options: &SummaryOptions, dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for (target_id, name, page) in target_pages(results, options, dir)? {
+        let path = dir.join(format!("{}.html", name.strip_suffix(".md").unwrap_or(&name)));
+        write_html(&markdown::to_html_document(&page, &target_id), &path)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Target id, page name and page of each target of `results`, creating `dir` if there are any
+fn target_pages(results: &[BenchmarkResult], options: &SummaryOptions, dir: &Path) -> Result<Vec<(String, String, String)>> {
+    let names = markdown::target_page_names(results);
+    if !names.is_empty() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create target pages directory: {}", dir.display()))?;
+    }
+
+    Ok(names
+        .into_iter()
+        .filter_map(|(target_id, name)| {
+            let result = results.iter().rev().find(|result| result.target_id == target_id)?;
+            let page = markdown::generate_target_page(result, options);
+            Some((target_id, name, page))
+        })
+        .collect())
+}
+
+/// Ensure the layout's root and raw results directory exist
+pub fn ensure_output_dirs(layout: &OutputLayout) -> Result<()> {
+    fs::create_dir_all(&layout.root)
+        .with_context(|| format!("Failed to create output directory: {}", layout.root.display()))?;
+
+    fs::create_dir_all(&layout.raw_dir)
+        .with_context(|| format!("Failed to create raw results directory: {}", layout.raw_dir.display()))?;
+
+    Ok(())
+}
+
+/// Generate a timestamped filename for raw results
+pub fn timestamped_filename(prefix: &str, extension: &str) -> String {
+    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+    format!("{}_{}.{}", prefix, timestamp, extension)
+}
+
+/// Write benchmark results to both JSON and markdown formats
+///
+/// Writes the layout's summary file, `latest.json` and a timestamped copy in
+/// its raw directory, named by its
+/// [`filename_template`](OutputLayout::filename_template), and appends to its
+/// history file if it keeps one. It also writes the layout's
+/// [badges](OutputLayout::badges), and with
+/// [`target_pages`](OutputLayout::target_pages) a
+/// [page per target](write_target_pages) with the last [`TARGET_PAGE_RUNS`]
+/// runs of its history, this one included. The run is recorded in the
+/// [manifest](manifest) and the written files in the [checksums](checksums). With
+/// [`compress_raw`](OutputLayout::compress_raw) the timestamped copy is
+/// `.json.gz`, which needs the `gzip` feature; `latest.json` stays plain.
+/// With [`summary_html`](OutputLayout::summary_html) the summary is also
+/// written [as HTML](markdown::to_html_document) next to it as
+/// [`summary_html_file`](OutputLayout::summary_html_file), which needs the
+/// `markdown-html` feature. With `target_pages` as well, the target pages are
+/// [written as HTML](write_target_pages_html) too and the HTML summary
+/// [links them](markdown::link_html_target_pages).
+///
+/// Holds an [`OutputLock`] on the root while writing, waiting up to the
+/// layout's [`lock_timeout`](OutputLayout::lock_timeout) for a concurrent run.
+pub fn write_results(results: &[BenchmarkResult], summary_markdown: &str, layout: &OutputLayout) -> Result<()> {
+    ensure_output_dirs(layout)?;
+    let _lock = OutputLock::acquire(&layout.root, layout.lock_timeout)?;
+
+    // Name the timestamped copy first, so a bad name writes nothing
+    let extension = if layout.compress_raw { "json.gz" } else { "json" };
+    let environment = results.iter().find_map(|result| result.environment.as_ref());
+    let name = layout.filename_template.render("benchmarks", extension, Utc::now(), environment)?;
+    let raw_file = layout.raw_dir.join(name);
+    check_badge_names(&layout.badges)?;
+    if layout.summary_html && layout.summary_html_file() == layout.html_file() {
+        anyhow::bail!("Cannot write the summary as HTML to {}: the HTML report uses it", layout.html_file().display());
+    }
+    if layout.summary_html && layout.summary_html_file() == layout.summary_file {
+        anyhow::bail!("Cannot write the summary as HTML to {}: the summary uses it", layout.summary_file.display());
+    }
+    if layout.summary_html && !cfg!(feature = "markdown-html") {
+        anyhow::bail!("Cannot write the summary as HTML: built without the `markdown-html` feature");
+    }
+
+    // Read the history for the target pages before this run joins it
+    let page_options = if layout.target_pages {
+        let mut history = read_history(layout)?;
+        history.push((Utc::now(), results.to_vec()));
+        let trends = trends::trends(&history, trends::DEFAULT_WINDOW);
+        Some(SummaryOptions::default().with_trends(trends, TARGET_PAGE_RUNS))
+    } else {
+        None
+    };
+
+    // Write summary markdown
+    write_markdown(summary_markdown, &layout.summary_file)?;
+    #[cfg(feature = "markdown-html")]
+    if layout.summary_html {
+        let summary = if layout.target_pages {
+            markdown::link_html_target_pages(summary_markdown, results)
+        } else {
+            summary_markdown.to_string()
+        };
+        let page = markdown::to_html_document(&summary, "Schema Registry Benchmark Summary");
+        write_html(&page, &layout.summary_html_file())?;
+    }
+
+    // Write raw JSON results with timestamp
+    if layout.compress_raw {
+        #[cfg(feature = "gzip")]
+        write_json_gz(results, &raw_file)?;
+        #[cfg(not(feature = "gzip"))]
+        anyhow::bail!("Cannot compress raw results: built without the `gzip` feature");
+    } else {
+        write_json(results, &raw_file)?;
+    }
+
+    // Also write latest.json for easy access
+    write_json(results, &layout.latest_file())?;
+
+    if layout.append_history {
+        append_jsonl(results, &layout.history_file)?;
+    }
+
+    let file = raw_file.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    append_manifest(ManifestEntry::new(file, results), &layout.manifest_file())?;
+
+    let mut written = vec![layout.summary_file.clone(), raw_file, layout.latest_file(), layout.manifest_file()];
+    if layout.append_history {
+        written.push(layout.history_file.clone());
+    }
+    if layout.summary_html {
+        written.push(layout.summary_html_file());
+    }
+    written.extend(write_badges(results, &layout.badges, &layout.badges_dir())?);
+    if let Some(options) = &page_options {
+        written.extend(write_target_pages(results, options, &layout.targets_dir())?);
+        #[cfg(feature = "markdown-html")]
+        if layout.summary_html {
+            written.extend(write_target_pages_html(results, options, &layout.targets_dir())?);
+        }
+    }
+    record_checksums(&layout.root, &written)?;
+
+    Ok(())
+}
+
+/// [`write_results`] on tokio's blocking thread pool, for callers on a runtime
+///
+/// Waiting for the [`OutputLock`] sleeps the calling thread, which would stall
+/// a runtime worker for up to the layout's [`lock_timeout`](OutputLayout::lock_timeout).
+pub async fn write_results_async(
+    results: Vec<BenchmarkResult>,
+    summary_markdown: String,
+    layout: OutputLayout,
+) -> Result<()> {
+    tokio::task::spawn_blocking(move || write_results(&results, &summary_markdown, &layout))
+        .await
+        .context("Writing results panicked")?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn create_test_result(id: &str) -> BenchmarkResult {
+        BenchmarkResult::new(
+            id.to_string(),
+            json!({
+                "duration_ms": 100
+            }),
+        )
+    }
+
+    #[test]
+    fn test_write_and_read_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.json");
+
+        let results = vec![create_test_result("test")];
+
+        // Write
+        write_json(&results, &path).unwrap();
+        assert!(path.exists());
+
+        // Read
+        let read_results = read_json(&path).unwrap();
+        assert_eq!(read_results.len(), 1);
+        assert_eq!(read_results[0].target_id, "test");
+    }
+
+    #[test]
+    fn test_write_json_creates_parent_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("subdir").join("test.json");
+
+        let results = vec![create_test_result("test")];
+
+        write_json(&results, &path).unwrap();
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_write_markdown() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.md");
+
+        let content = "# Test Markdown\n\nContent here.";
+
+        write_markdown(content, &path).unwrap();
+        assert!(path.exists());
+
+        let read_content = fs::read_to_string(&path).unwrap();
+        assert_eq!(read_content, content);
+    }
+
+    #[test]
+    fn test_write_html_creates_parent_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("subdir").join("summary.html");
+        let html = crate::report::generate_html(&[create_test_result("test")], &Default::default());
+
+        write_html(&html, &path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), html);
+    }
+
+    #[test]
+    fn test_write_markdown_creates_parent_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("subdir").join("test.md");
+
+        write_markdown("test", &path).unwrap();
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_run_file_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("shard1.json");
+        let run = crate::runner::BenchmarkRun {
+            results: vec![create_test_result("test")],
+            failures: Vec::new(),
+            cancelled: false,
+        };
+        let shard = crate::shard::Shard::new(1, 2).unwrap();
+
+        write_run_file(&RunFile::new("ci-1", "default", 0).record(Some(shard), run), &path).unwrap();
+
+        let read = read_run_file(&path).unwrap();
+        assert_eq!(read.run_id, "ci-1");
+        assert_eq!(read.shards[0].shard, shard);
+        assert_eq!(read.results[0].shard, Some(1));
+    }
+
+    #[test]
+    fn test_read_json_upgrades_and_rejects_newer_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("archived.json");
+
+        fs::write(
+            &path,
+            r#"[{"target_id": "old", "metrics": {"iterations": 10, "write": {"avg_ms": "0.5"}}, "timestamp": "2025-01-01T00:00:00Z"}]"#,
+        )
+        .unwrap();
+        let results = read_json(&path).unwrap();
+        assert_eq!(results[0].format_version, migrations::CURRENT_FORMAT_VERSION);
+        assert_eq!(results[0].metrics["write"]["avg_ms"]["value"], 0.5);
+
+        fs::write(
+            &path,
+            r#"[{"target_id": "new", "format_version": 99, "metrics": {}, "timestamp": "2030-01-01T00:00:00Z"}]"#,
+        )
+        .unwrap();
+        let err = format!("{:#}", read_json(&path).unwrap_err());
+        assert!(err.contains("format version 99"), "{err}");
+    }
+
+    #[test]
+    fn test_read_history_orders_runs_and_skips_bad_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let layout = OutputLayout::new(temp_dir.path());
+        let dir = layout.raw_dir.as_path();
+        fs::create_dir_all(dir).unwrap();
+        write_json(&[create_test_result("second")], &dir.join("benchmarks_20250602_080000.json")).unwrap();
+        write_json(&[create_test_result("first")], &dir.join("benchmarks_20250601_120000.json")).unwrap();
+        write_json(&[create_test_result("latest")], &dir.join("latest.json")).unwrap();
+        fs::write(dir.join("benchmarks_20250603_000000.json"), "{ not json").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let mut unnamed = create_test_result("unnamed");
+        unnamed.timestamp = "2025-06-01T00:00:00Z".parse().unwrap();
+        write_json(&[unnamed], &dir.join("manual.json")).unwrap();
+
+        let history = read_history(&layout).unwrap();
+        let ids: Vec<_> = history.iter().map(|(_, results)| results[0].target_id.as_str()).collect();
+        assert_eq!(ids, vec!["unnamed", "first", "second"]);
+        assert_eq!(history[1].0.to_rfc3339(), "2025-06-01T12:00:00+00:00");
+
+        assert!(read_history(&OutputLayout::new(dir.join("missing"))).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_default_output_dir() {
+        assert_eq!(output_dir_from(None), PathBuf::from("benchmarks/output"));
+        assert_eq!(output_dir_from(Some("".into())), PathBuf::from("benchmarks/output"));
+        assert_eq!(output_dir_from(Some("/tmp/bench".into())), PathBuf::from("/tmp/bench"));
+    }
+
+    #[test]
+    fn test_history_appends_across_runs() {
+        let temp_dir = TempDir::new().unwrap();
+        let layout = OutputLayout::new(temp_dir.path()).with_history(true);
+
+        write_results(&[create_test_result("first")], "# One\n", &layout).unwrap();
+        write_results(&[create_test_result("second"), create_test_result("third")], "# Two\n", &layout).unwrap();
+
+        let history = read_jsonl(&layout.history_file).unwrap();
+        let ids: Vec<_> = history.iter().map(|result| result.target_id.as_str()).collect();
+        assert_eq!(ids, vec!["first", "second", "third"]);
+        assert_eq!(fs::read_to_string(&layout.history_file).unwrap().lines().count(), 3);
+
+        let without = OutputLayout::new(temp_dir.path().join("plain"));
+        write_results(&[create_test_result("first")], "# One\n", &without).unwrap();
+        assert!(!without.history_file.exists());
+    }
+
+    #[test]
+    fn test_read_jsonl_skips_partial_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("history.jsonl");
+        append_jsonl(&[create_test_result("first")], &path).unwrap();
+
+        // A writer that crashed mid-line
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"target_id": "cut", "metr"#).unwrap();
+        drop(file);
+        let ids = |path: &Path| -> Vec<String> {
+            read_jsonl(path).unwrap().into_iter().map(|result| result.target_id).collect()
+        };
+        assert_eq!(ids(&path), vec!["first"]);
+
+        append_jsonl(&[create_test_result("second")], &path).unwrap();
+        assert_eq!(ids(&path), vec!["first", "second"]);
+
+        fs::write(&path, r#"{"target_id": "new", "format_version": 99, "metrics": {}, "timestamp": "2030-01-01T00:00:00Z"}"#)
+            .unwrap();
+        assert!(read_jsonl(&path).is_err());
+    }
+
+    #[test]
+    fn test_filename_timestamp_of_compressed_files() {
+        let plain = filename_timestamp(Path::new("raw/benchmarks_20250601_120000.json"));
+        assert!(plain.is_some());
+        assert_eq!(filename_timestamp(Path::new("raw/benchmarks_20250601_120000.json.gz")), plain);
+        assert_eq!(filename_timestamp(Path::new("raw/benchmarks_20250601_120000.gz")), None);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_compressed_raw_results_mix_with_plain_ones() {
+        let temp_dir = TempDir::new().unwrap();
+        let layout = OutputLayout::new(temp_dir.path()).with_compressed_raw(true);
+        write_json(&[create_test_result("plain")], &layout.raw_dir.join("benchmarks_20250601_120000.json")).unwrap();
+        write_results(&[create_test_result("compressed")], "# Summary\n", &layout).unwrap();
+
+        let compressed: Vec<_> = fs::read_dir(&layout.raw_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| is_gzip(path))
+            .collect();
+        assert_eq!(compressed.len(), 1);
+        assert_eq!(read_json(&compressed[0]).unwrap()[0].target_id, "compressed");
+        // latest.json stays readable without decompressing
+        assert!(fs::read_to_string(layout.latest_file()).unwrap().contains("compressed"));
+
+        let history = read_history(&layout).unwrap();
+        let ids: Vec<_> = history.iter().map(|(_, results)| results[0].target_id.as_str()).collect();
+        assert_eq!(ids, vec!["plain", "compressed"]);
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    #[test]
+    fn test_compression_needs_the_gzip_feature() {
+        let temp_dir = TempDir::new().unwrap();
+        let layout = OutputLayout::new(temp_dir.path()).with_compressed_raw(true);
+        assert!(write_results(&[create_test_result("test1")], "", &layout).is_err());
+
+        let path = temp_dir.path().join("benchmarks_20250601_120000.json.gz");
+        fs::write(&path, b"not really gzip").unwrap();
+        assert!(format!("{:#}", read_json(&path).unwrap_err()).contains("gzip"));
+    }
+
+    #[test]
+    fn test_write_results_into_output_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let output_dir = temp_dir.path().join("out");
+        let results = vec![create_test_result("test1")];
+
+        write_results(&results, "# Summary\n", &OutputLayout::new(&output_dir)).unwrap();
+
+        assert_eq!(fs::read_to_string(output_dir.join("summary.md")).unwrap(), "# Summary\n");
+        assert_eq!(read_json(&output_dir.join("raw/latest.json")).unwrap()[0].target_id, "test1");
+        let files = fs::read_dir(output_dir.join("raw")).unwrap().count();
+        assert_eq!(files, 2);
+
+        let layout = OutputLayout::new(&output_dir)
+            .with_raw_dir(temp_dir.path().join("history"))
+            .with_summary_file(temp_dir.path().join("SUMMARY.md"));
+        write_results(&results, "# Again\n", &layout).unwrap();
+        assert!(temp_dir.path().join("SUMMARY.md").is_file());
+        assert_eq!(read_json(&layout.latest_file()).unwrap().len(), 1);
+        assert_eq!(fs::read_to_string(output_dir.join("summary.md")).unwrap(), "# Summary\n");
+
+        let runs = read_manifest(&output_dir.join("manifest.json")).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert!(runs[0].file.starts_with("benchmarks_") && runs[0].file.ends_with(".json"));
+        assert_eq!(runs[1].target_ids, ["test1"]);
+    }
+
+    #[test]
+    fn test_write_results_with_filename_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let template: FilenameTemplate = "{prefix}_{timestamp}_{git_sha}".parse().unwrap();
+        let layout = OutputLayout::new(temp_dir.path()).with_filename_template(template);
+        let mut result = create_test_result("test1");
+        result.environment = Some(crate::RunEnvironment {
+            git_commit: Some("4f2c1a9e0b7d3c5e".to_string()),
+            ..Default::default()
+        });
+
+        write_results(&[result], "# Summary\n", &layout).unwrap();
+
+        let runs = read_manifest(&layout.manifest_file()).unwrap();
+        assert!(runs[0].file.starts_with("benchmarks_") && runs[0].file.ends_with("_4f2c1a9e0b7d.json"));
+        let history = read_history(&layout).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].0.format("%Y%m%d_%H%M%S").to_string(), runs[0].file[11..26]);
+    }
+
+    #[test]
+    fn test_write_results_writes_badges() {
+        let temp_dir = TempDir::new().unwrap();
+        let layout = OutputLayout::new(temp_dir.path())
+            .with_badge(Badge::new("duration", "duration", "test1/duration_ms").with_thresholds(50.0, 200.0))
+            .with_badge(Badge::new("missing", "p95", "absent/p95_ms"));
+
+        write_results(&[create_test_result("test1")], "# Summary\n", &layout).unwrap();
+
+        let badge: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(layout.badges_dir().join("duration.json")).unwrap()).unwrap();
+        assert_eq!(badge, json!({"schemaVersion": 1, "label": "duration", "message": "100.000 ms", "color": "yellow"}));
+        let missing = fs::read_to_string(layout.badges_dir().join("missing.json")).unwrap();
+        assert!(missing.contains("\"message\":\"unknown\"") && missing.contains("\"color\":\"grey\""));
+        assert!(fs::read_to_string(temp_dir.path().join("SHA256SUMS")).unwrap().contains("badges/duration.json"));
+
+        let bad = OutputLayout::new(temp_dir.path().join("bad")).with_badge(Badge::new("../escape", "x", "test1/duration_ms"));
+        assert!(write_results(&[create_test_result("test1")], "# Summary\n", &bad).is_err());
+        assert!(!bad.summary_file.exists());
+    }
+
+    #[test]
+    fn test_write_results_writes_linked_target_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        let layout = OutputLayout::new(temp_dir.path()).with_target_pages(true);
+        let results = [create_test_result("storage ops"), create_test_result("schema/size")];
+        let summary = markdown::generate_summary_with(&results, &SummaryOptions::default().with_target_pages(true));
+
+        write_results(&results, &summary, &layout).unwrap();
+
+        assert!(!summary.contains("```json"));
+        let links: Vec<&str> = summary
+            .split("](")
+            .skip(1)
+            .filter_map(|rest| rest.split(')').next())
+            .filter(|link| link.starts_with("targets/"))
+            .collect();
+        assert_eq!(links, ["targets/storage-ops.md", "targets/schema-size.md"]);
+        for link in links {
+            assert!(layout.root.join(link).is_file(), "{} is missing", link);
+        }
+
+        let page = fs::read_to_string(layout.targets_dir().join("storage-ops.md")).unwrap();
+        assert!(page.starts_with("# storage ops\n"));
+        assert!(page.contains("```json") && page.contains("\"duration_ms\""));
+        assert!(page.contains("## History\n\n| Metric |"));
+        assert!(page.contains("| duration_ms (ms) | 100 |"));
+        assert!(fs::read_to_string(temp_dir.path().join("SHA256SUMS")).unwrap().contains("targets/schema-size.md"));
+    }
+
+    #[cfg(feature = "markdown-html")]
+    #[test]
+    fn test_write_results_writes_summary_html() {
+        let temp_dir = TempDir::new().unwrap();
+        let layout = OutputLayout::new(temp_dir.path()).with_summary_html(true);
+        let summary = markdown::generate_summary(&[create_test_result("test1")]);
+
+        write_results(&[create_test_result("test1")], &summary, &layout).unwrap();
+
+        let html = fs::read_to_string(layout.summary_html_file()).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<table>") && html.contains("<td>test1</td>"));
+        assert!(html.contains("<pre><code class=\"language-json\">"));
+        assert!(fs::read_to_string(temp_dir.path().join("SHA256SUMS")).unwrap().contains("summary.html"));
+        assert!(!layout.html_file().exists());
+    }
+
+    #[cfg(feature = "markdown-html")]
+    #[test]
+    fn test_write_results_renders_target_pages_with_summary_html() {
+        let temp_dir = TempDir::new().unwrap();
+        let layout = OutputLayout::new(temp_dir.path()).with_target_pages(true).with_summary_html(true);
+        let results = [create_test_result("storage ops"), create_test_result("schema.v1")];
+        let summary = markdown::generate_summary_with(&results, &SummaryOptions::default().with_target_pages(true));
+
+        write_results(&results, &summary, &layout).unwrap();
+
+        let markdown = fs::read_to_string(&layout.summary_file).unwrap();
+        assert!(markdown.contains("](targets/storage-ops.md)"));
+        let html = fs::read_to_string(layout.summary_html_file()).unwrap();
+        assert!(html.contains("href=\"targets/storage-ops.html\"") && html.contains("href=\"targets/schema.v1.html\""));
+        assert!(!html.contains(".md\""));
+
+        let page = fs::read_to_string(layout.targets_dir().join("storage-ops.html")).unwrap();
+        assert!(page.starts_with("<!DOCTYPE html>") && page.contains("<title>storage ops</title>"));
+        assert!(page.contains("<pre><code class=\"language-json\">"));
+        assert!(layout.targets_dir().join("schema.v1.md").is_file());
+        let sums = fs::read_to_string(temp_dir.path().join("SHA256SUMS")).unwrap();
+        assert!(sums.contains("targets/schema.v1.html") && sums.contains("targets/schema.v1.md"));
+    }
+
+    #[test]
+    fn test_summary_html_keeps_off_the_html_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let layout = OutputLayout::new(temp_dir.path())
+            .with_summary_file(temp_dir.path().join("report.md"))
+            .with_summary_html(true);
+        let err = write_results(&[create_test_result("test1")], "# Summary\n", &layout).unwrap_err();
+
+        assert!(err.to_string().contains("HTML report"));
+        assert!(!layout.summary_file.exists());
+
+        let layout = layout.with_summary_file(temp_dir.path().join("summary.html"));
+        let err = write_results(&[create_test_result("test1")], "# Summary\n", &layout).unwrap_err();
+        assert!(err.to_string().contains("the summary uses it"));
+        assert!(!layout.summary_file.exists());
+    }
+
+    #[cfg(not(feature = "markdown-html"))]
+    #[test]
+    fn test_summary_html_needs_the_markdown_html_feature() {
+        let temp_dir = TempDir::new().unwrap();
+        let layout = OutputLayout::new(temp_dir.path()).with_summary_html(true);
+        let err = write_results(&[create_test_result("test1")], "# Summary\n", &layout).unwrap_err();
+
+        assert!(err.to_string().contains("markdown-html"));
+        assert!(!layout.summary_file.exists());
+    }
+
+    #[test]
+    fn test_concurrent_writers_take_turns() {
+        let temp_dir = TempDir::new().unwrap();
+        let layout = OutputLayout::new(temp_dir.path()).with_history(true);
+
+        let writers: Vec<_> = (0..2)
+            .map(|writer| {
+                let layout = layout.clone();
+                std::thread::spawn(move || {
+                    let results: Vec<BenchmarkResult> =
+                        (0..200).map(|i| create_test_result(&format!("writer{}_{}", writer, i))).collect();
+                    for _ in 0..10 {
+                        write_results(&results, "# Summary\n", &layout).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(read_json(&layout.latest_file()).unwrap().len(), 200);
+        assert_eq!(read_jsonl(&layout.history_file).unwrap().len(), 2 * 10 * 200);
+        assert!(temp_dir.path().join(lock::LOCK_FILE).exists());
+    }
+
+    #[tokio::test]
+    async fn test_write_results_async_waits_without_blocking_the_runtime() {
+        let temp_dir = TempDir::new().unwrap();
+        let layout = OutputLayout::new(temp_dir.path()).with_lock_timeout(Duration::from_secs(5));
+        let held = OutputLock::acquire(temp_dir.path(), Duration::ZERO).unwrap();
+
+        // On a current-thread runtime, a writer sleeping on the runtime thread would keep this task from releasing the lock
+        let writer = tokio::spawn(write_results_async(
+            vec![create_test_result("test1")],
+            "# Summary\n".to_string(),
+            layout.clone(),
+        ));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        drop(held);
+
+        writer.await.unwrap().unwrap();
+        assert_eq!(read_json(&layout.latest_file()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_timestamped_filename() {
+        let filename = timestamped_filename("test", "json");
+        assert!(filename.starts_with("test_"));
+        assert!(filename.ends_with(".json"));
+        assert!(filename.contains("_"));
+    }
+
+    #[test]
+    fn test_timestamped_filename_format() {
+        let filename = timestamped_filename("bench", "txt");
+        // Should match pattern: bench_YYYYMMDD_HHMMSS.txt
+        let parts: Vec<&str> = filename.split('_').collect();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0], "bench");
+    }
+
+    #[test]
+    fn test_read_json_nonexistent_file() {
+        let path = PathBuf::from("/nonexistent/path/file.json");
+        let result = read_json(&path);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_write_json_invalid_path() {
+        let results = vec![create_test_result("test")];
+        // Try to write to root (should fail on most systems)
+        let path = PathBuf::from("/this/should/not/exist/test.json");
+        let result = write_json(&results, &path);
+        // May succeed or fail depending on permissions, but shouldn't panic
+        let _ = result;
+    }
+
+    #[test]
+    fn test_multiple_writes_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.json");
+
+        let results1 = vec![create_test_result("test1")];
+        write_json(&results1, &path).unwrap();
+
+        let results2 = vec![create_test_result("test2")];
+        write_json(&results2, &path).unwrap();
+
+        let read_results = read_json(&path).unwrap();
+        assert_eq!(read_results[0].target_id, "test2");
+    }
+}
@@ -0,0 +1,266 @@
+//! Prometheus export of benchmark results
+//!
+//! [`write_prometheus`] writes every numeric leaf of the results' metrics as
+//! a gauge in the OpenMetrics text format, for a node exporter textfile
+//! collector or any other scraper:
+//!
+//! ```text
+//! schema_registry_benchmark_avg_seconds{target="storage_operations",operation="write"} 0.0005
+//! ```
+//!
+//! The leaf's key names the metric, converted to Prometheus base units with
+//! [`Unit::prometheus`]: `avg_ms` becomes `avg_seconds`, `cv_percent`
+//! becomes `cv_ratio`. The dotted path of the object holding the leaf is the
+//! `operation` label, left out for top-level metrics; numbers in arrays get
+//! an `index` label. Strings and the `seed` input are skipped.
+
+use crate::units::{self, Measurement, Unit};
+use crate::BenchmarkResult;
+use anyhow::{Context, Result};
+use schema_registry_core::metric_names;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Prefix of every exported metric name
+pub const METRIC_PREFIX: &str = "schema_registry_benchmark";
+
+/// Unit suffixes stripped from a key before the Prometheus suffix is added
+const KEY_UNIT_SUFFIXES: [&str; 12] = [
+    "_bytes_per_second",
+    "_bytes_per_sec",
+    "_per_second",
+    "_per_sec",
+    "_seconds",
+    "_secs",
+    "_ms",
+    "_us",
+    "_bytes",
+    "_percent",
+    "_pct",
+    "_ratio",
+];
+
+/// A numeric leaf of a result's metrics
+struct Leaf<'a> {
+    key: &'a str,
+    /// Dotted path of the object holding the leaf, empty at the top level
+    operation: String,
+    /// Position in its array, for numbers in arrays
+    index: Option<usize>,
+    measurement: Measurement,
+}
+
+fn join(path: &str, key: &str) -> String {
+    match (path.is_empty(), key.is_empty()) {
+        (true, _) => key.to_string(),
+        (_, true) => path.to_string(),
+        _ => format!("{}.{}", path, key),
+    }
+}
+
+/// Numeric leaves of `metrics`, in document order
+fn leaves(metrics: &Value) -> Vec<Leaf<'_>> {
+    fn walk<'a>(value: &'a Value, key: &'a str, operation: &str, index: Option<usize>, out: &mut Vec<Leaf<'a>>) {
+        match value {
+            Value::Object(map) if !units::is_measurement(value) => {
+                let operation = join(operation, key);
+                for (child_key, child) in map {
+                    walk(child, child_key, &operation, None, out);
+                }
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if item.is_object() && !units::is_measurement(item) {
+                        let operation = join(&join(operation, key), &i.to_string());
+                        for (child_key, child) in item.as_object().into_iter().flatten() {
+                            walk(child, child_key, &operation, None, out);
+                        }
+                    } else {
+                        walk(item, key, operation, Some(i), out);
+                    }
+                }
+            }
+            Value::String(_) => {}
+            _ if key.is_empty() || key == metric_names::BENCH_SEED.name => {}
+            _ => {
+                if let Some(measurement) = Measurement::read(key, value) {
+                    out.push(Leaf {
+                        key,
+                        operation: operation.to_string(),
+                        index,
+                        measurement,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(metrics, "", "", None, &mut out);
+    out
+}
+
+/// Metric name of a leaf under `key` measured in `unit`
+fn metric_name(key: &str, unit: Unit) -> String {
+    let sanitized: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    let key = format!("_{}", sanitized);
+    let stem = KEY_UNIT_SUFFIXES
+        .iter()
+        .find_map(|suffix| key.strip_suffix(suffix))
+        .unwrap_or(&key);
+    let (_, suffix) = unit.prometheus(0.0);
+    format!("{}{}{}", METRIC_PREFIX, stem, suffix)
+}
+
+/// OpenMetrics `# UNIT` of a metric with the given name suffix, if it names one
+fn unit_metadata(suffix: &str) -> Option<&'static str> {
+    match suffix {
+        "_seconds" => Some("seconds"),
+        "_bytes" => Some("bytes"),
+        "_ratio" => Some("ratio"),
+        _ => None,
+    }
+}
+
+/// `text` escaped for a label value or help text
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+    match value.is_infinite() {
+        true if value > 0.0 => "+Inf".to_string(),
+        true => "-Inf".to_string(),
+        false => value.to_string(),
+    }
+}
+
+#[derive(Default)]
+struct Family {
+    unit: Option<&'static str>,
+    help: Option<&'static str>,
+    /// Label sets and values
+    samples: Vec<(String, f64)>,
+}
+
+/// `results` in the OpenMetrics text format, metric families sorted by name
+pub fn to_prometheus(results: &[BenchmarkResult]) -> String {
+    let mut families: BTreeMap<String, Family> = BTreeMap::new();
+    for result in results {
+        for leaf in leaves(&result.metrics) {
+            let (value, suffix) = leaf.measurement.unit.prometheus(leaf.measurement.value);
+            let family = families.entry(metric_name(leaf.key, leaf.measurement.unit)).or_default();
+            family.unit = unit_metadata(suffix);
+            family.help = family
+                .help
+                .or_else(|| metric_names::lookup(leaf.key).map(|metric| metric.description));
+
+            let mut labels = format!("target=\"{}\"", escape(&result.target_id));
+            if !leaf.operation.is_empty() {
+                labels.push_str(&format!(",operation=\"{}\"", escape(&leaf.operation)));
+            }
+            if let Some(index) = leaf.index {
+                labels.push_str(&format!(",index=\"{}\"", index));
+            }
+            family.samples.push((labels, value));
+        }
+    }
+
+    let mut text = String::new();
+    for (name, family) in families {
+        text.push_str(&format!("# TYPE {} gauge\n", name));
+        if let Some(unit) = family.unit {
+            text.push_str(&format!("# UNIT {} {}\n", name, unit));
+        }
+        if let Some(help) = family.help {
+            text.push_str(&format!("# HELP {} {}\n", name, escape(help)));
+        }
+        for (labels, value) in family.samples {
+            text.push_str(&format!("{}{{{}}} {}\n", name, labels, format_value(value)));
+        }
+    }
+    text.push_str("# EOF\n");
+    text
+}
+
+/// Write `results` as OpenMetrics text, see [`to_prometheus`]
+pub fn write_prometheus(results: &[BenchmarkResult], path: &Path) -> Result<()> {
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    fs::write(path, to_prometheus(results))
+        .with_context(|| format!("Failed to write Prometheus metrics to {}", path.display()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn golden_results() -> Vec<BenchmarkResult> {
+        vec![
+            BenchmarkResult::new(
+                "storage_operations".to_string(),
+                json!({
+                    "iterations": {"value": 100, "unit": "count"},
+                    "write": {
+                        "avg_ms": {"value": 500.0, "unit": "microseconds"},
+                        "p95_ms": 1.25,
+                        "cv_percent": 12.5,
+                        "ops_per_sec": 2000.0
+                    },
+                    "status": "ok",
+                    "seed": 42
+                }),
+            ),
+            BenchmarkResult::new(
+                "concurrency_scaling".to_string(),
+                json!({
+                    "levels": [
+                        {"tasks": 1, "throughput_ops_per_sec": 1000.0},
+                        {"tasks": 8, "throughput_ops_per_sec": 5000.0}
+                    ],
+                    "rss_peak_bytes": 1048576,
+                    "latencies_ms": [0.5, 1.0]
+                }),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_matches_golden_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("metrics.prom");
+        write_prometheus(&golden_results(), &path).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), include_str!("testdata/metrics.prom"));
+    }
+
+    #[test]
+    fn test_names_and_labels_are_sanitized() {
+        let result = BenchmarkResult::new(
+            "odd \"target\"\\\n".to_string(),
+            json!({"by-format": {"json.schema": {"hit-rate": 3}}}),
+        );
+        let text = to_prometheus(&[result]);
+
+        assert!(text.contains(
+            "schema_registry_benchmark_hit_rate{target=\"odd \\\"target\\\"\\\\\\n\",operation=\"by-format.json.schema\"} 3\n"
+        ));
+        assert_eq!(to_prometheus(&[]), "# EOF\n");
+    }
+}
@@ -0,0 +1,195 @@
+//! Pruning old timestamped results
+//!
+//! [`prune_raw_results`] deletes the `benchmarks_YYYYMMDD_HHMMSS.json` files
+//! (and their `.json.gz` variants) that [`write_results`](super::write_results)
+//! leaves in the raw directory, keeping the ones a [`RetentionPolicy`] asks
+//! for. Any other file, including `latest.json` and anything under the
+//! baselines directory, is never touched.
+
+use super::filename_timestamp;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Prefix of the timestamped files [`prune_raw_results`] considers
+pub const RAW_RESULTS_PREFIX: &str = "benchmarks_";
+
+/// Which timestamped result files to keep
+///
+/// A file is kept if any configured rule keeps it. With no rules set every
+/// file is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RetentionPolicy {
+    /// Keep the newest N runs
+    pub keep_last: Option<usize>,
+    /// Keep runs younger than this
+    pub keep_newer_than: Option<Duration>,
+    /// Only report what would be deleted
+    pub dry_run: bool,
+}
+
+impl RetentionPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the newest `count` runs
+    pub fn with_keep_last(mut self, count: usize) -> Self {
+        self.keep_last = Some(count);
+        self
+    }
+
+    /// Keep runs younger than `age`
+    pub fn with_keep_newer_than(mut self, age: Duration) -> Self {
+        self.keep_newer_than = Some(age);
+        self
+    }
+
+    /// Report instead of deleting
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Whether the run at `position` from the newest (0-based), written at `time`, is kept
+    fn keeps(&self, position: usize, time: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        if self.keep_last.is_none() && self.keep_newer_than.is_none() {
+            return true;
+        }
+        let recent = self.keep_last.is_some_and(|count| position < count);
+        let young = self.keep_newer_than.is_some_and(|age| {
+            // Ages beyond chrono's range keep everything
+            chrono::Duration::from_std(age)
+                .ok()
+                .is_none_or(|age| now.signed_duration_since(time) < age)
+        });
+        recent || young
+    }
+}
+
+/// Outcome of [`prune_raw_results`], newest files first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PruneReport {
+    /// Files deleted, or that would be in a dry run
+    pub deleted: Vec<PathBuf>,
+    pub retained: Vec<PathBuf>,
+    pub dry_run: bool,
+}
+
+/// Delete the timestamped results in `dir` that `policy` doesn't keep
+///
+/// Only files named `benchmarks_<timestamp>.json` or `.json.gz` are
+/// considered; a missing directory prunes nothing.
+pub fn prune_raw_results(dir: &Path, policy: &RetentionPolicy) -> Result<PruneReport> {
+    prune_raw_results_at(dir, policy, Utc::now())
+}
+
+/// [`prune_raw_results`] with ages measured from `now`
+pub fn prune_raw_results_at(dir: &Path, policy: &RetentionPolicy, now: DateTime<Utc>) -> Result<PruneReport> {
+    let mut report = PruneReport {
+        dry_run: policy.dry_run,
+        ..PruneReport::default()
+    };
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(report),
+        Err(e) => return Err(e).with_context(|| format!("Failed to list results in {}", dir.display())),
+    };
+
+    let mut runs = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let timestamped = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(RAW_RESULTS_PREFIX));
+        if let Some(time) = filename_timestamp(&path).filter(|_| timestamped) {
+            runs.push((time, path));
+        }
+    }
+    // Newest first; names break ties so the order is stable
+    runs.sort_by(|a, b| b.cmp(a));
+
+    for (position, (time, path)) in runs.into_iter().enumerate() {
+        if policy.keeps(position, time, now) {
+            report.retained.push(path);
+            continue;
+        }
+        if !policy.dry_run {
+            fs::remove_file(&path).with_context(|| format!("Failed to delete {}", path.display()))?;
+        }
+        report.deleted.push(path);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn names(paths: &[PathBuf]) -> Vec<&str> {
+        paths.iter().map(|path| path.file_name().unwrap().to_str().unwrap()).collect()
+    }
+
+    fn raw_dir() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for name in [
+            "benchmarks_20250101_000000.json",
+            "benchmarks_20250201_000000.json.gz",
+            "benchmarks_20250301_000000.json",
+            "benchmarks_20250401_000000.json",
+            "latest.json",
+            "criterion_latest.json",
+            "benchmarks_notes.json",
+            "other_20250101_000000.json",
+        ] {
+            fs::write(dir.path().join(name), "[]").unwrap();
+        }
+        fs::create_dir(dir.path().join("baselines")).unwrap();
+        fs::write(dir.path().join("baselines/benchmarks_20200101_000000.json"), "[]").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_keep_last_deletes_only_older_timestamped_files() {
+        let dir = raw_dir();
+        let report = prune_raw_results(dir.path(), &RetentionPolicy::new().with_keep_last(2)).unwrap();
+
+        assert_eq!(names(&report.retained), vec!["benchmarks_20250401_000000.json", "benchmarks_20250301_000000.json"]);
+        assert_eq!(names(&report.deleted), vec!["benchmarks_20250201_000000.json.gz", "benchmarks_20250101_000000.json"]);
+        assert!(!dir.path().join("benchmarks_20250101_000000.json").exists());
+        for kept in ["latest.json", "criterion_latest.json", "benchmarks_notes.json", "other_20250101_000000.json"] {
+            assert!(dir.path().join(kept).exists(), "{kept}");
+        }
+        assert!(dir.path().join("baselines/benchmarks_20200101_000000.json").exists());
+    }
+
+    #[test]
+    fn test_keep_newer_than_and_dry_run() {
+        let dir = raw_dir();
+        let now: DateTime<Utc> = "2025-04-15T00:00:00Z".parse().unwrap();
+        let policy = RetentionPolicy::new()
+            .with_keep_newer_than(Duration::from_secs(60 * 24 * 3600))
+            .with_dry_run(true);
+        let report = prune_raw_results_at(dir.path(), &policy, now).unwrap();
+
+        assert!(report.dry_run);
+        assert_eq!(names(&report.retained), vec!["benchmarks_20250401_000000.json", "benchmarks_20250301_000000.json"]);
+        assert_eq!(report.deleted.len(), 2);
+        assert!(report.deleted.iter().all(|path| path.exists()));
+
+        // Either rule keeps a file
+        let policy = policy.with_keep_last(3);
+        assert_eq!(prune_raw_results_at(dir.path(), &policy, now).unwrap().retained.len(), 3);
+        assert!(prune_raw_results(dir.path(), &RetentionPolicy::new()).unwrap().deleted.is_empty());
+        assert!(prune_raw_results(&dir.path().join("missing"), &policy).unwrap().retained.is_empty());
+    }
+}
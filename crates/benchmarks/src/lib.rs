@@ -7,56 +7,328 @@
 //! compatibility checking.
 
 pub mod adapters;
+#[cfg(feature = "object-store")]
+pub mod archive;
+#[cfg(feature = "auto-register")]
+pub mod auto_register;
+pub mod baseline;
+pub mod compare;
+#[cfg(feature = "criterion")]
+pub mod criterion_adapter;
+pub mod environment;
+#[cfg(feature = "http-push")]
+pub mod exporters;
+pub mod filter;
 pub mod io;
 pub mod markdown;
+#[cfg(feature = "mem-metrics")]
+pub mod memory;
+pub mod metrics;
+pub mod observer;
+pub mod registry;
+pub mod report;
+#[cfg(feature = "rusage-metrics")]
+pub mod resource_usage;
+pub mod runner;
+pub mod sampling;
+pub mod shape;
+pub mod shard;
+pub mod sink;
+pub mod stats;
+#[cfg(feature = "templates")]
+pub mod templates;
+pub mod trends;
+pub mod units;
+pub mod workload;
 
 use chrono::{DateTime, Utc};
+use futures::stream::{Stream, StreamExt};
+use schema_registry_core::SerializationFormat;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub use adapters::storage_backend::StorageBackend;
+pub use adapters::{BenchConfig, SamplingMode};
+pub use baseline::{Baseline, BaselineMetadata, BaselineStore};
+pub use compare::{compare_results, GateResult, MetricStatus, RegressionReport, RegressionThresholds};
+pub use environment::RunEnvironment;
+pub use filter::{BenchmarkFilter, BenchmarkFilterError};
+pub use metrics::{MemoryMetrics, OperationMetrics, ResourceUsageMetrics};
+pub use observer::{NdjsonObserver, ProgressObserver, StatusObserver};
+pub use registry::{BenchRegistry, BenchRegistryError};
+pub use runner::{
+    BenchProgress, BenchmarkRun, BenchmarkRunConfig, BenchmarkRunError, CancellationToken, RunMode, Runner,
+};
+pub use sampling::{PoolSlot, SamplePool, SeriesId};
+pub use shape::{MetricsShape, ShapeDrift};
+pub use shard::{RunFile, Shard, ShardError, ShardLayout};
+pub use sink::{publish_all, FileSink, ResultSink, SinkOutcome};
+pub use stats::{OutlierPolicy, Summary};
+pub use trends::{MetricTrend, TrendPoint};
+pub use units::{Measurement, Unit};
+pub use workload::{SchemaWorkloadGenerator, WorkloadError, WorkloadPlan};
 
 /// Benchmark result containing performance metrics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchmarkResult {
+    /// Serialization format of the result; see [`io::migrations`]
+    ///
+    /// Files written before the field existed are version 1.
+    #[serde(default = "io::migrations::legacy_format_version")]
+    pub format_version: u32,
     /// Unique identifier for the benchmark target
     pub target_id: String,
     /// Performance metrics in JSON format for flexibility
+    ///
+    /// Statistics written as strings by older versions are read back as numbers.
+    #[serde(deserialize_with = "metrics::deserialize_metrics")]
     pub metrics: serde_json::Value,
     /// When the benchmark was executed
     pub timestamp: DateTime<Utc>,
+    /// Whether the target ran concurrently with others, so timings may include contention
+    #[serde(default)]
+    pub parallel: bool,
+    /// Index of the shard that produced the result, for sharded runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard: Option<usize>,
+    /// Machine, toolchain and commit that produced the result
+    ///
+    /// Absent in files written before environments were recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<RunEnvironment>,
+    /// Time the target's [`setup`](adapters::BenchTarget::setup) took, excluded from the metrics
+    ///
+    /// Set by the runner; absent when the target was run directly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup_ms: Option<f64>,
+    /// Wall time of the target's setup and run, warmup included
+    ///
+    /// Set by the runner; absent when the target was run directly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<f64>,
+    /// Every measured sample by series label, before outlier rejection
+    ///
+    /// Filled only when the `raw-samples` feature is enabled, so consumers can
+    /// re-derive statistics under another policy.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub raw_samples: BTreeMap<String, Vec<f64>>,
+    /// The target's [`version`](adapters::BenchTarget::version) when it produced the result
+    ///
+    /// Set by the runner; absent when the target was run directly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_version: Option<u32>,
+    /// Fingerprint of the metric key paths and types; see [`shape`]
+    ///
+    /// Set by the runner; absent when the target was run directly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape_fingerprint: Option<String>,
+    /// Seed the target's inputs were generated from, to replay the run
+    ///
+    /// Set by the target or else by the runner from
+    /// [`BenchConfig::seed`]; absent in files written before seeds were recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
 }
 
 impl BenchmarkResult {
     /// Create a new benchmark result
     pub fn new(target_id: String, metrics: serde_json::Value) -> Self {
         Self {
+            format_version: io::migrations::CURRENT_FORMAT_VERSION,
             target_id,
             metrics,
             timestamp: Utc::now(),
+            parallel: false,
+            shard: None,
+            environment: None,
+            setup_ms: None,
+            duration_ms: None,
+            raw_samples: BTreeMap::new(),
+            target_version: None,
+            shape_fingerprint: None,
+            seed: None,
+        }
+    }
+
+    /// Attach the samples recorded in `pool` when the `raw-samples` feature is enabled
+    pub fn with_raw_samples(mut self, pool: &SamplePool) -> Self {
+        if cfg!(feature = "raw-samples") {
+            self.raw_samples = pool.raw_samples();
         }
+        self
+    }
+
+    /// Record that the target's inputs were generated from `seed`
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Metrics the target reported for `format`, if it reports formats separately
+    ///
+    /// See [`BenchTarget::formats`](adapters::BenchTarget::formats).
+    pub fn metrics_for_format(&self, format: SerializationFormat) -> Option<&serde_json::Value> {
+        self.metrics.get(metrics::BY_FORMAT_KEY)?.get(metrics::format_key(format))
+    }
+
+    /// Add `memory` to the metrics under the `memory` key
+    ///
+    /// Metrics that aren't a JSON object are left unchanged.
+    pub fn with_memory(mut self, memory: MemoryMetrics) -> Self {
+        if let serde_json::Value::Object(map) = &mut self.metrics {
+            map.insert(metrics::MEMORY_KEY.to_string(), serde_json::json!(memory));
+        }
+        self
+    }
+
+    /// Add `usage` to the metrics under the `resource_usage` key
+    ///
+    /// Metrics that aren't a JSON object are left unchanged.
+    pub fn with_resource_usage(mut self, usage: ResourceUsageMetrics) -> Self {
+        if let serde_json::Value::Object(map) = &mut self.metrics {
+            map.insert(metrics::RESOURCE_USAGE_KEY.to_string(), serde_json::json!(usage));
+        }
+        self
     }
 }
 
 /// Run all registered benchmarks
 ///
-/// This function executes all benchmark targets and returns their results.
-/// Benchmarks are run sequentially to ensure accurate measurements.
-pub async fn run_all_benchmarks() -> Vec<BenchmarkResult> {
-    let mut results = Vec::new();
-
-    // Get all registered benchmark targets
-    let targets = adapters::all_targets();
-
-    // Run each benchmark target
-    for target in targets {
-        match target.run().await {
-            Ok(result) => results.push(result),
-            Err(e) => {
-                eprintln!("Benchmark {} failed: {}", target.id(), e);
-                // Continue with other benchmarks even if one fails
-            }
-        }
+/// This function executes every target in `registry`, or the built-in
+/// targets when it is `None`, and returns their results together with any
+/// per-target failures.
+/// Benchmarks are run sequentially to ensure accurate measurements. The
+/// [`RunEnvironment`] is detected once and stamped on every result.
+pub async fn run_all_benchmarks(registry: Option<&BenchRegistry>) -> BenchmarkRun {
+    run_all_benchmarks_stream(registry).collect().await
+}
+
+/// Run all registered benchmarks, yielding each target's outcome as soon as it finishes
+///
+/// Targets run sequentially, in the same order as [`run_all_benchmarks`],
+/// which collects this stream.
+pub fn run_all_benchmarks_stream(
+    registry: Option<&BenchRegistry>,
+) -> impl Stream<Item = Result<BenchmarkResult, BenchmarkRunError>> {
+    runner::run_targets_stream(registry_targets(registry), RunMode::Sequential)
+}
+
+/// Run all registered benchmarks, reporting progress as each target starts and completes
+///
+/// Runs the same targets and returns the same [`BenchmarkRun`] as
+/// [`run_all_benchmarks`]; `on_event` only observes the run.
+pub async fn run_all_benchmarks_with_progress(
+    registry: Option<&BenchRegistry>,
+    on_event: impl Fn(BenchProgress) + Sync,
+) -> BenchmarkRun {
+    runner::run_targets_with_progress(registry_targets(registry), RunMode::Sequential, on_event).await
+}
+
+/// Run all registered benchmarks until `cancel` fires
+///
+/// Targets come from `registry` as in [`run_all_benchmarks`]. Once the token
+/// is cancelled no further targets are launched; the target in progress
+/// finishes, and the returned run holds the results gathered so far with
+/// [`BenchmarkRun::cancelled`] set.
+pub async fn run_all_benchmarks_cancellable(
+    registry: Option<&BenchRegistry>,
+    cancel: &CancellationToken,
+) -> BenchmarkRun {
+    runner::run_targets_cancellable(registry_targets(registry), RunMode::Sequential, cancel, |_| {}).await
+}
+
+/// Targets of `registry`, or the built-in targets without one
+fn registry_targets(registry: Option<&BenchRegistry>) -> Vec<Box<dyn adapters::BenchTarget>> {
+    match registry {
+        Some(registry) => registry.targets(),
+        None => adapters::all_targets(),
     }
+}
 
-    results
+/// Targets of `registry` selected by `filter`, plus the extended targets it asks for
+fn filtered_targets(
+    registry: Option<&BenchRegistry>,
+    filter: &BenchmarkFilter,
+) -> Result<Vec<Box<dyn adapters::BenchTarget>>, BenchmarkFilterError> {
+    filter.apply(adapters::with_extended(registry_targets(registry), filter))
+}
+
+/// Run the registered benchmarks selected by `filter`
+///
+/// Targets come from `registry` as in [`run_all_benchmarks`]. An empty filter
+/// behaves like [`run_all_benchmarks`]; a filter that selects no targets is an
+/// error.
+pub async fn run_benchmarks_filtered(
+    registry: Option<&BenchRegistry>,
+    filter: &BenchmarkFilter,
+) -> Result<BenchmarkRun, BenchmarkFilterError> {
+    run_benchmarks(registry, filter, RunMode::Sequential).await
+}
+
+/// Run the registered benchmarks selected by `filter` with the given mode or config
+pub async fn run_benchmarks(
+    registry: Option<&BenchRegistry>,
+    filter: &BenchmarkFilter,
+    config: impl Into<BenchmarkRunConfig>,
+) -> Result<BenchmarkRun, BenchmarkFilterError> {
+    let targets = filtered_targets(registry, filter)?;
+    Ok(runner::run_targets(targets, config).await)
+}
+
+/// Like [`run_benchmarks`], reporting progress to `on_event`
+pub async fn run_benchmarks_with_progress(
+    registry: Option<&BenchRegistry>,
+    filter: &BenchmarkFilter,
+    config: impl Into<BenchmarkRunConfig>,
+    on_event: impl Fn(BenchProgress) + Sync,
+) -> Result<BenchmarkRun, BenchmarkFilterError> {
+    let targets = filtered_targets(registry, filter)?;
+    Ok(runner::run_targets_with_progress(targets, config, on_event).await)
+}
+
+/// Like [`run_benchmarks_with_progress`], launching no new targets once `cancel` fires
+pub async fn run_benchmarks_cancellable(
+    registry: Option<&BenchRegistry>,
+    filter: &BenchmarkFilter,
+    config: impl Into<BenchmarkRunConfig>,
+    cancel: &CancellationToken,
+    on_event: impl Fn(BenchProgress) + Sync,
+) -> Result<BenchmarkRun, BenchmarkFilterError> {
+    let targets = filtered_targets(registry, filter)?;
+    Ok(runner::run_targets_cancellable(targets, config, cancel, on_event).await)
+}
+
+/// Run the targets selected by `filter` that belong to `shard`
+///
+/// Targets come from `registry` as in [`run_all_benchmarks`] and run in
+/// [`Shard::select`] order, so `run_after` constraints hold in sequential mode.
+pub async fn run_shard(
+    registry: Option<&BenchRegistry>,
+    filter: &BenchmarkFilter,
+    shard: Shard,
+    config: impl Into<BenchmarkRunConfig>,
+) -> Result<BenchmarkRun, ShardError> {
+    run_shard_with_progress(registry, filter, shard, config, |_| {}).await
+}
+
+/// Like [`run_shard`], reporting progress to `on_event`
+pub async fn run_shard_with_progress(
+    registry: Option<&BenchRegistry>,
+    filter: &BenchmarkFilter,
+    shard: Shard,
+    config: impl Into<BenchmarkRunConfig>,
+    on_event: impl Fn(BenchProgress) + Sync,
+) -> Result<BenchmarkRun, ShardError> {
+    let targets = shard.select(filtered_targets(registry, filter)?)?;
+    Ok(runner::run_targets_with_progress(targets, config, on_event).await)
+}
+
+/// Run all registered benchmarks, returning only the successful results
+///
+/// Targets come from `registry` as in [`run_all_benchmarks`]. Failed targets
+/// are dropped; use [`run_all_benchmarks`] to inspect them.
+pub async fn run_all_benchmarks_successes(registry: Option<&BenchRegistry>) -> Vec<BenchmarkResult> {
+    run_all_benchmarks(registry).await.into_results()
 }
 
 #[cfg(test)]
@@ -78,6 +350,19 @@ mod tests {
         assert!(result.timestamp <= Utc::now());
     }
 
+    #[test]
+    fn test_metrics_for_format() {
+        let result = BenchmarkResult::new(
+            "formats".to_string(),
+            json!({"avro": {"p95_ms": 1.0}, "by_format": {"avro": {"p95_ms": 2.0}}}),
+        );
+        assert_eq!(result.metrics_for_format(SerializationFormat::Avro), Some(&json!({"p95_ms": 2.0})));
+        assert_eq!(result.metrics_for_format(SerializationFormat::Protobuf), None);
+
+        let flat = BenchmarkResult::new("flat".to_string(), json!({"avro": {"p95_ms": 1.0}}));
+        assert_eq!(flat.metrics_for_format(SerializationFormat::Avro), None);
+    }
+
     #[test]
     fn test_benchmark_result_serialization() {
         let metrics = json!({"duration_ms": 100});
@@ -100,10 +385,160 @@ mod tests {
         assert_eq!(deserialized.metrics, result.metrics);
     }
 
+    #[test]
+    fn test_benchmark_result_reads_string_valued_statistics() {
+        let raw = r#"{"target_id":"old","metrics":{"write":{"avg_ms":"0.105"}},"timestamp":"2024-01-01T00:00:00Z"}"#;
+        let result: BenchmarkResult = serde_json::from_str(raw).unwrap();
+        assert_eq!(result.metrics["write"]["avg_ms"], 0.105);
+    }
+
+    #[test]
+    fn test_benchmark_result_with_memory() {
+        let memory = MemoryMetrics {
+            rss_peak_bytes: Some(4096),
+            allocated_bytes: Some(512),
+            ..Default::default()
+        };
+        let result = BenchmarkResult::new("test".to_string(), json!({"iterations": 3})).with_memory(memory);
+
+        assert_eq!(
+            result.metrics["memory"],
+            json!({"rss_peak_bytes": {"value": 4096, "unit": "bytes"}, "allocated_bytes": {"value": 512, "unit": "bytes"}})
+        );
+        assert_eq!(result.metrics["iterations"], 3);
+    }
+
+    #[test]
+    fn test_benchmark_result_with_resource_usage() {
+        let usage = ResourceUsageMetrics {
+            user_cpu_ms: 12.5,
+            system_cpu_ms: 2.0,
+            voluntary_context_switches: 4,
+            involuntary_context_switches: 1,
+        };
+        let result = BenchmarkResult::new("test".to_string(), json!({"iterations": 3})).with_resource_usage(usage);
+
+        assert_eq!(result.metrics["resource_usage"]["user_cpu_ms"], json!({"value": 12.5, "unit": "milliseconds"}));
+        assert_eq!(result.metrics["resource_usage"]["involuntary_context_switches"]["value"], 1);
+        assert_eq!(usage.cpu_ms(), 14.5);
+    }
+
+    #[test]
+    fn test_benchmark_result_without_parallel_flag_deserializes() {
+        let raw = r#"{"target_id":"old","metrics":{},"timestamp":"2024-01-01T00:00:00Z"}"#;
+        let result: BenchmarkResult = serde_json::from_str(raw).unwrap();
+        assert!(!result.parallel);
+        assert!(result.environment.is_none());
+    }
+
+    /// Target that returns at once, so runner tests don't pay for the built-in suite
+    struct StubTarget(&'static str);
+
+    #[async_trait::async_trait]
+    impl adapters::BenchTarget for StubTarget {
+        fn id(&self) -> &str {
+            self.0
+        }
+
+        fn description(&self) -> &str {
+            "stub target"
+        }
+
+        async fn run(&self) -> anyhow::Result<BenchmarkResult> {
+            Ok(BenchmarkResult::new(self.0.to_string(), json!({"avg_ms": 1.0})))
+        }
+    }
+
+    fn stub_registry() -> BenchRegistry {
+        ["stub_a", "stub_b", "stub_c"]
+            .into_iter()
+            .try_fold(BenchRegistry::new(), |registry, id| registry.with_target(Box::new(StubTarget(id))))
+            .unwrap()
+    }
+
+    fn result_ids(run: &BenchmarkRun) -> Vec<&str> {
+        run.results.iter().map(|result| result.target_id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_run_all_benchmarks_with_progress() {
+        let registry = stub_registry();
+        let started = std::sync::Mutex::new(Vec::new());
+        let run = run_all_benchmarks_with_progress(Some(&registry), |event| {
+            if let BenchProgress::TargetStarted { id } = event {
+                started.lock().unwrap().push(id);
+            }
+        })
+        .await;
+
+        assert_eq!(started.into_inner().unwrap(), registry.ids());
+        assert_eq!(result_ids(&run), registry.ids());
+    }
+
+    #[tokio::test]
+    async fn test_run_all_benchmarks_cancellable_before_start() {
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let run = run_all_benchmarks_cancellable(Some(&stub_registry()), &cancel).await;
+        assert!(run.cancelled);
+        assert!(run.results.is_empty() && run.failures.is_empty());
+    }
+
     #[tokio::test]
     async fn test_run_all_benchmarks() {
-        let results = run_all_benchmarks().await;
-        // Should return results from all registered targets
-        assert!(results.len() >= 0); // May be 0 if no targets registered
+        let run = run_all_benchmarks(None).await;
+        // Every registered target either completes or reports a failure
+        assert_eq!(run.results.len() + run.failures.len(), adapters::all_targets().len());
+        assert!(run.is_success(), "failed targets: {:?}", run.failed_ids());
+        assert!(!run.cancelled);
+        assert!(run.results.iter().all(|result| result.environment.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_run_all_benchmarks_with_registry() {
+        let registry = stub_registry();
+        let run = run_all_benchmarks(Some(&registry)).await;
+        assert_eq!(result_ids(&run), registry.ids());
+        assert_eq!(run_all_benchmarks_successes(Some(&registry)).await.len(), registry.len());
+    }
+
+    #[tokio::test]
+    async fn test_filtered_runs_use_registry() {
+        let registry = stub_registry();
+        let filter = BenchmarkFilter::new().with_exclude("stub_b");
+
+        let run = run_benchmarks_filtered(Some(&registry), &filter).await.unwrap();
+        assert_eq!(result_ids(&run), ["stub_a", "stub_c"]);
+
+        let parallel = RunMode::Parallel { max_concurrency: 2 };
+        let run = run_benchmarks(Some(&registry), &filter, parallel).await.unwrap();
+        assert!(run.results.iter().all(|result| result.parallel));
+
+        let cancel = CancellationToken::new();
+        let run = run_benchmarks_cancellable(Some(&registry), &filter, RunMode::Sequential, &cancel, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(result_ids(&run), ["stub_a", "stub_c"]);
+
+        let missing = BenchmarkFilter::new().with_include("validation*");
+        assert!(run_benchmarks_with_progress(Some(&registry), &missing, RunMode::Sequential, |_| {})
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_shard_uses_registry() {
+        let registry = stub_registry();
+        let filter = BenchmarkFilter::new();
+
+        let mut ids = Vec::new();
+        for index in 1..=2 {
+            let shard = Shard::new(index, 2).unwrap();
+            let run = run_shard(Some(&registry), &filter, shard, RunMode::Sequential).await.unwrap();
+            ids.extend(run.results.into_iter().map(|result| result.target_id));
+        }
+        ids.sort();
+        assert_eq!(ids, registry.ids());
     }
 }
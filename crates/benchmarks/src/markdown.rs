@@ -1,29 +1,538 @@
 //! Markdown generation utilities for benchmark reports
 
+use crate::compare::{
+    numeric_leaves, status_name, Direction, MetricComparison, MetricStatus, RegressionReport, RegressionThresholds,
+};
+use crate::environment::RunEnvironment;
+use crate::metrics::{MemoryMetrics, HEADLINE_KEY, MEMORY_KEY, SCHEMA_FORMATS};
+use crate::registry::BenchRegistry;
+use crate::shard::RunFile;
+use crate::stats::UNSTABLE_CV_PERCENT;
+use crate::trends::MetricTrend;
+use crate::units::{self, format_bytes, Measurement, Unit};
 use crate::BenchmarkResult;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use schema_registry_core::metric_names;
+use schema_registry_core::SerializationFormat;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
 
-/// Generate a markdown table from benchmark results
+/// Timed operations in `metrics`, as dotted paths to their statistics objects
+///
+/// An operation is any nested object carrying a p95 value, e.g. `write` or
+/// `compression.large.gzip.encode`.
+pub(crate) fn timed_operations(metrics: &Value) -> Vec<(String, &Map<String, Value>)> {
+    fn walk<'a>(value: &'a Value, path: &str, out: &mut Vec<(String, &'a Map<String, Value>)>) {
+        let Value::Object(map) = value else {
+            return;
+        };
+        if map.contains_key(metric_names::BENCH_P95_MS.name) {
+            out.push((path.to_string(), map));
+            return;
+        }
+        for (key, child) in map {
+            let child_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+            walk(child, &child_path, out);
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(metrics, "", &mut out);
+    out
+}
+
+/// Number as printed in reports: integers as-is, fractions to three decimals
+pub(crate) fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.3}", value)
+    }
+}
+
+/// Statistic as printed in tables, `-` when missing
+///
+/// Times are shown in milliseconds and percentages with one decimal,
+/// whatever unit the result recorded them in. Bare numbers and numeric
+/// strings from older result files take the unit implied by `name`.
+pub(crate) fn stat(stats: &Map<String, Value>, name: &str) -> String {
+    let Some(value) = stats.get(name) else {
+        return "-".to_string();
+    };
+    let Some(measurement) = Measurement::read(name, value) else {
+        return match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+    };
+    if measurement.unit == Unit::Percent {
+        format!("{:.1}", measurement.value)
+    } else {
+        let value = measurement.in_unit(Unit::Milliseconds).unwrap_or(measurement.value);
+        format!("{:.3}", value)
+    }
+}
+
+/// Statistic as a number in the unit [`stat`] shows it in, `None` when missing or not numeric
+pub(crate) fn stat_value(stats: &Map<String, Value>, name: &str) -> Option<f64> {
+    let measurement = Measurement::read(name, stats.get(name)?)?;
+    if measurement.unit == Unit::Percent {
+        Some(measurement.value)
+    } else {
+        Some(measurement.in_unit(Unit::Milliseconds).unwrap_or(measurement.value))
+    }
+}
+
+/// Copy of `metrics` for display: measurements as readable strings such as
+/// `1.250 ms`, other fractional numbers rounded
+fn rounded(metrics: &Value) -> Value {
+    // Only objects read as measurements; bare numbers keep their JSON form
+    if let Some(measurement) = metrics.as_object().and(Measurement::read("", metrics)) {
+        return Value::String(measurement.display());
+    }
+    match metrics {
+        Value::Number(n) if n.is_f64() => n
+            .as_f64()
+            .and_then(|value| serde_json::Number::from_f64((value * 1000.0).round() / 1000.0))
+            .map_or_else(|| metrics.clone(), Value::Number),
+        Value::Object(map) => Value::Object(map.iter().map(|(k, v)| (k.clone(), rounded(v))).collect()),
+        Value::Array(items) => Value::Array(items.iter().map(rounded).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Scaling table for results with per-concurrency-level `levels`, `None` otherwise
+fn scaling_table(metrics: &Value) -> Option<String> {
+    let levels = metrics.get("levels")?.as_array()?;
+    if levels.is_empty() {
+        return None;
+    }
+
+    let mut table = String::from("| Tasks | Throughput (ops/s) | p99 (ms) | Scalability |\n");
+    table.push_str("|-------|--------------------|----------|-------------|\n");
+    for level in levels {
+        let level = level.as_object()?;
+        let number = |name: &str| level.get(name).and_then(units::number);
+        let throughput = number(metric_names::BENCH_THROUGHPUT_OPS_PER_SEC.name)
+            .map_or_else(|| "-".to_string(), |value| format!("{:.1}", value));
+        let scalability = number(metric_names::BENCH_SCALABILITY_FACTOR.name)
+            .map_or_else(|| "-".to_string(), |value| format!("{:.2}x", value));
+        table.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            number(metric_names::BENCH_TASKS.name).map_or_else(|| "-".to_string(), format_number),
+            throughput,
+            stat(level, metric_names::BENCH_P99_MS.name),
+            scalability
+        ));
+    }
+    Some(table)
+}
+
+/// Memory line for a detailed result section, `None` without a `memory` object
+fn memory_line(metrics: &Value) -> Option<String> {
+    let memory: MemoryMetrics = serde_json::from_value(metrics.get(MEMORY_KEY)?.clone()).ok()?;
+
+    let rss = match (memory.rss_peak_bytes, memory.rss_before_bytes, memory.rss_after_bytes) {
+        (Some(peak), Some(before), Some(after)) => format!(
+            "peak RSS {} (start {}, end {})",
+            format_bytes(peak),
+            format_bytes(before),
+            format_bytes(after)
+        ),
+        (Some(peak), _, _) => format!("peak RSS {}", format_bytes(peak)),
+        (None, _, _) => "RSS not available on this platform".to_string(),
+    };
+    let allocated = match memory.allocated_bytes {
+        Some(bytes) => format!("{} allocated", format_bytes(bytes)),
+        None => "allocations not counted".to_string(),
+    };
+    Some(format!("**Memory:** {}; {}\n\n", rss, allocated))
+}
+
+/// Whether an operation's coefficient of variation marks it as noisy
+pub(crate) fn is_unstable(stats: &Map<String, Value>) -> bool {
+    stats
+        .get(metric_names::BENCH_CV_PERCENT.name)
+        .and_then(units::number)
+        .is_some_and(|cv| cv > UNSTABLE_CV_PERCENT)
+}
+
+/// `op: p95` pairs for the results table, unstable operations flagged
+fn p95_cell(metrics: &Value) -> String {
+    let operations = timed_operations(metrics);
+    if operations.is_empty() {
+        return "-".to_string();
+    }
+    operations
+        .iter()
+        .map(|(path, stats)| {
+            let flag = if is_unstable(stats) { " (unstable)" } else { "" };
+            format!("{}: {}{}", path, stat(stats, metric_names::BENCH_P95_MS.name), flag)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `op: N ops/s` pairs for operations reporting throughput, `None` if none do
+fn throughput_cell(metrics: &Value) -> Option<String> {
+    let pairs: Vec<String> = timed_operations(metrics)
+        .iter()
+        .filter_map(|(path, stats)| {
+            let ops = units::number(stats.get(metric_names::BENCH_OPS_PER_SEC.name)?)?;
+            (ops > 0.0).then(|| format!("{}: {:.1} ops/s", path, ops))
+        })
+        .collect();
+    (!pairs.is_empty()).then(|| pairs.join(", "))
+}
+
+/// Per-operation latency table for a detailed result section
+fn latency_table(metrics: &Value) -> Option<String> {
+    let operations = timed_operations(metrics);
+    if operations.is_empty() {
+        return None;
+    }
+
+    let columns = [
+        metric_names::BENCH_AVG_MS,
+        metric_names::BENCH_P50_MS,
+        metric_names::BENCH_P95_MS,
+        metric_names::BENCH_P99_MS,
+        metric_names::BENCH_MAX_MS,
+        metric_names::BENCH_STD_DEV_MS,
+        metric_names::BENCH_CV_PERCENT,
+    ];
+    let mut table = String::from("| Operation | avg | p50 | p95 | p99 | max | std dev | CV % |\n");
+    table.push_str("|-----------|-----|-----|-----|-----|-----|---------|------|\n");
+    let mut unstable = 0;
+    for (path, stats) in operations {
+        let mut cells: Vec<String> = columns.iter().map(|metric| stat(stats, metric.name)).collect();
+        if is_unstable(stats) {
+            unstable += 1;
+            if let Some(cv) = cells.last_mut() {
+                cv.push_str(" ⚠ unstable");
+            }
+        }
+        table.push_str(&format!("| {} | {} |\n", path, cells.join(" | ")));
+    }
+    if unstable > 0 {
+        table.push_str(&format!(
+            "\n{} operation(s) have CV > {}%; treat differences between runs as noise unless they exceed the spread.\n",
+            unstable, UNSTABLE_CV_PERCENT
+        ));
+    }
+    Some(table)
+}
+
+/// Heading of a format in the by-format section
+fn format_heading(format: SerializationFormat) -> &'static str {
+    match format {
+        SerializationFormat::JsonSchema => "JSON Schema",
+        SerializationFormat::Avro => "Avro",
+        SerializationFormat::Protobuf => "Protobuf",
+    }
+}
+
+/// Timed operations of every result, grouped by schema format
+///
+/// Only results whose targets report formats separately appear; `None` if
+/// there are none. See [`BenchmarkResult::metrics_for_format`].
+fn format_section(results: &[BenchmarkResult]) -> Option<String> {
+    let mut sections = String::new();
+    for format in SCHEMA_FORMATS {
+        let rows: Vec<String> = results
+            .iter()
+            .filter_map(|result| Some((result, result.metrics_for_format(format)?)))
+            .flat_map(|(result, metrics)| {
+                timed_operations(metrics).into_iter().map(move |(path, stats)| {
+                    format!(
+                        "| {} | {} | {} | {} | {} |\n",
+                        result.target_id,
+                        if path.is_empty() { "-" } else { path.as_str() },
+                        stat(stats, metric_names::BENCH_AVG_MS.name),
+                        stat(stats, metric_names::BENCH_P95_MS.name),
+                        stat(stats, metric_names::BENCH_P99_MS.name)
+                    )
+                })
+            })
+            .collect();
+        if rows.is_empty() {
+            continue;
+        }
+        sections.push_str(&format!("### {}\n\n", format_heading(format)));
+        sections.push_str("| Target | Operation | avg (ms) | p95 (ms) | p99 (ms) |\n");
+        sections.push_str("|--------|-----------|----------|----------|----------|\n");
+        sections.push_str(&rows.concat());
+        sections.push('\n');
+    }
+    (!sections.is_empty()).then(|| format!("## Results by Format\n\n{}", sections))
+}
+
+/// Most metric columns [`generate_table_with`] picks on its own
+pub const DEFAULT_MAX_COLUMNS: usize = 8;
+
+/// Layout of the results table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableStyle {
+    /// One column per numeric metric, e.g. `write.avg_ms`
+    #[default]
+    Columns,
+    /// The p95 of each operation and the whole metrics object as JSON in one cell
+    RawJson,
+}
+
+/// Options of [`generate_table_with`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableOptions {
+    pub style: TableStyle,
+    /// Metric paths to show as columns, in order; empty picks them automatically
+    pub columns: Vec<String>,
+    /// Most columns picked automatically, ignored when `columns` is set
+    pub max_columns: usize,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        Self {
+            style: TableStyle::default(),
+            columns: Vec::new(),
+            max_columns: DEFAULT_MAX_COLUMNS,
+        }
+    }
+}
+
+impl TableOptions {
+    pub fn with_style(mut self, style: TableStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_max_columns(mut self, max_columns: usize) -> Self {
+        self.max_columns = max_columns;
+        self
+    }
+}
+
+/// Metric columns for `leaves`, and how many automatic candidates were left out
+///
+/// Automatic columns are the `avg_ms` leaves, or every leaf if no result has
+/// one. Past `max_columns`, the paths most results carry win. Columns follow
+/// the first result carrying them, in path order within a result, since
+/// [`numeric_leaves`] is sorted by path.
+fn table_columns(leaves: &[BTreeMap<String, Measurement>], options: &TableOptions) -> (Vec<String>, usize) {
+    if !options.columns.is_empty() {
+        return (options.columns.clone(), 0);
+    }
+
+    fn is_avg(path: &str) -> bool {
+        path.rsplit('.').next() == Some(metric_names::BENCH_AVG_MS.name)
+    }
+
+    let mut seen: Vec<(&str, usize)> = Vec::new();
+    for (path, _) in leaves.iter().flatten() {
+        match seen.iter_mut().find(|(known, _)| *known == path.as_str()) {
+            Some((_, count)) => *count += 1,
+            None => seen.push((path.as_str(), 1)),
+        }
+    }
+    let mut candidates: Vec<(usize, &str, usize)> = seen
+        .iter()
+        .enumerate()
+        .filter(|(_, (path, _))| is_avg(path))
+        .map(|(order, (path, count))| (order, *path, *count))
+        .collect();
+    if candidates.is_empty() {
+        candidates = seen.iter().enumerate().map(|(order, (path, count))| (order, *path, *count)).collect();
+    }
+
+    let omitted = candidates.len().saturating_sub(options.max_columns);
+    candidates.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)));
+    candidates.truncate(options.max_columns);
+    candidates.sort_by_key(|(order, _, _)| *order);
+    (candidates.into_iter().map(|(_, path, _)| path.to_string()).collect(), omitted)
+}
+
+/// Column heading for a metric, with `(ms)` added to times whose path doesn't say so
+fn column_heading(path: &str, unit: Option<Unit>) -> String {
+    let time = matches!(unit, Some(Unit::Milliseconds | Unit::Microseconds | Unit::Seconds));
+    if time && !path.ends_with("_ms") {
+        format!("{} (ms)", path)
+    } else {
+        path.to_string()
+    }
+}
+
+/// Value of a metric in the unit tables show it in: times in milliseconds, others as recorded
+fn table_value(measurement: &Measurement) -> f64 {
+    measurement.in_unit(Unit::Milliseconds).unwrap_or(measurement.value)
+}
+
+/// Table cell of a metric: times in milliseconds, percentages with one decimal
+fn measurement_cell(measurement: &Measurement) -> String {
+    if measurement.unit == Unit::Percent {
+        return format!("{:.1}", measurement.value);
+    }
+    match measurement.in_unit(Unit::Milliseconds) {
+        Some(ms) => format!("{:.3}", ms),
+        None => format_number(measurement.value),
+    }
+}
+
+/// Markdown table padded so its columns line up in the source as well
+///
+/// Columns flagged in `right` are right-aligned, as numbers should be.
+fn aligned_table(header: &[String], right: &[bool], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = header
+        .iter()
+        .enumerate()
+        .map(|(column, heading)| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([heading.chars().count(), 3])
+                .max()
+                .unwrap_or(3)
+        })
+        .collect();
+    let line = |cells: &[String]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .zip(right)
+            .map(|((cell, &width), &right)| {
+                if right {
+                    format!("{:>width$}", cell, width = width)
+                } else {
+                    format!("{:<width$}", cell, width = width)
+                }
+            })
+            .collect();
+        format!("| {} |\n", padded.join(" | "))
+    };
+
+    let mut table = line(header);
+    let rule: Vec<String> = widths
+        .iter()
+        .zip(right)
+        .map(|(&width, &right)| if right { format!("{}:", "-".repeat(width - 1)) } else { "-".repeat(width) })
+        .collect();
+    table.push_str(&format!("| {} |\n", rule.join(" | ")));
+    for row in rows {
+        table.push_str(&line(row));
+    }
+    table
+}
+
+/// Generate a markdown table from benchmark results, with the default [`TableOptions`]
 pub fn generate_table(results: &[BenchmarkResult]) -> String {
+    generate_table_with(results, &TableOptions::default())
+}
+
+/// Generate a markdown table from benchmark results
+///
+/// With [`TableStyle::Columns`] each selected metric gets its own column;
+/// results without a metric show an em-dash in its column.
+pub fn generate_table_with(results: &[BenchmarkResult], options: &TableOptions) -> String {
     if results.is_empty() {
         return "No benchmark results available.\n".to_string();
     }
+    match options.style {
+        TableStyle::Columns => column_table(results, options, None),
+        TableStyle::RawJson => raw_json_table(results),
+    }
+}
+
+/// Column table of `results`, highlighting regressions against `summary`'s comparison if given
+fn column_table(results: &[BenchmarkResult], options: &TableOptions, summary: Option<&SummaryOptions>) -> String {
+    let leaves: Vec<BTreeMap<String, Measurement>> =
+        results.iter().map(|result| numeric_leaves(&result.metrics)).collect();
+    let (columns, omitted) = table_columns(&leaves, options);
+
+    let mut header = vec!["Target ID".to_string()];
+    for path in &columns {
+        let unit = leaves.iter().find_map(|leaves| leaves.get(path)).map(|measurement| measurement.unit);
+        header.push(column_heading(path, unit).replace('|', "\\|"));
+    }
+    header.push("Timestamp".to_string());
+
+    let mut right = vec![false];
+    right.extend(columns.iter().map(|_| true));
+    right.push(false);
+
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .zip(&leaves)
+        .map(|(result, leaves)| {
+            let mut row = vec![result.target_id.replace('|', "\\|")];
+            row.extend(columns.iter().map(|path| {
+                let cell = leaves.get(path).map_or_else(|| "—".to_string(), measurement_cell);
+                match summary {
+                    Some(summary) => highlighted_cell(cell, &result.target_id, path, summary),
+                    None => cell,
+                }
+            }));
+            row.push(result.timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+            row
+        })
+        .collect();
 
+    let mut output = aligned_table(&header, &right, &rows);
+    if omitted > 0 {
+        output.push_str(&format!(
+            "\n{} more metric column(s) not shown; see the detailed metrics.\n",
+            omitted
+        ));
+    }
+    output
+}
+
+/// `cell` in bold with its change, and ⚠️ past the allowed regression, if the metric regressed
+fn highlighted_cell(cell: String, target_id: &str, path: &str, options: &SummaryOptions) -> String {
+    let regression = options
+        .comparison
+        .as_ref()
+        .and_then(|report| report.regressions().find(|metric| metric.target_id == target_id && metric.path == path));
+    let Some(metric) = regression else {
+        return cell;
+    };
+
+    let mut cell = format!("**{}**", cell);
+    if let Some(percent) = metric.delta_percent {
+        cell.push_str(&format!(" ({:+.1}%)", percent));
+    }
+    if options.violations.contains(&(target_id.to_string(), path.to_string())) {
+        cell.push_str(" ⚠️");
+    }
+    cell
+}
+
+/// The table as it was before metrics had columns: p95 per operation and the metrics JSON
+fn raw_json_table(results: &[BenchmarkResult]) -> String {
     let mut output = String::new();
 
     // Header
-    output.push_str("| Target ID | Metrics | Timestamp |\n");
-    output.push_str("|-----------|---------|----------|\n");
+    output.push_str("| Target ID | p95 (ms) | Metrics | Timestamp |\n");
+    output.push_str("|-----------|----------|---------|----------|\n");
 
     // Rows
     for result in results {
-        let metrics_str = serde_json::to_string(&result.metrics)
+        let metrics_str = serde_json::to_string(&rounded(&result.metrics))
             .unwrap_or_else(|_| "{}".to_string());
         let timestamp_str = result.timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string();
 
         output.push_str(&format!(
-            "| {} | `{}` | {} |\n",
+            "| {} | {} | `{}` | {} |\n",
             result.target_id,
+            p95_cell(&result.metrics),
             metrics_str.replace('|', "\\|"), // Escape pipe characters
             timestamp_str
         ));
@@ -32,67 +541,1142 @@ pub fn generate_table(results: &[BenchmarkResult]) -> String {
     output
 }
 
+/// Run identity and, for sharded runs, the shard layout
+fn run_section(run: &RunFile) -> String {
+    let mut section = format!(
+        "**Run:** `{}` (profile `{}`, seed {})\n\n",
+        run.run_id, run.profile, run.seed
+    );
+    if let Some(count) = run.shard_count() {
+        section.push_str(&format!(
+            "**Sharding:** {} of {} shards\n\n",
+            run.shards.len(),
+            count
+        ));
+        section.push_str("| Shard | Targets |\n");
+        section.push_str("|-------|---------|\n");
+        for layout in &run.shards {
+            section.push_str(&format!("| {} | {} |\n", layout.shard, layout.targets.join(", ")));
+        }
+        section.push('\n');
+
+        let missing: Vec<String> = run.missing_shards().iter().map(ToString::to_string).collect();
+        if !missing.is_empty() {
+            section.push_str(&format!("**Missing shards:** {}\n\n", missing.join(", ")));
+        }
+    }
+    if !run.failed.is_empty() {
+        section.push_str(&format!("**Failed targets:** {}\n\n", run.failed.join(", ")));
+    }
+    section
+}
+
+/// Field list describing one environment
+fn environment_fields(env: &RunEnvironment) -> String {
+    let unknown = "unknown";
+    let commit = match (&env.git_commit, &env.git_branch) {
+        (Some(commit), Some(branch)) => format!("`{}` ({})", commit, branch),
+        (Some(commit), None) => format!("`{}`", commit),
+        (None, _) => unknown.to_string(),
+    };
+    let cpu = match (&env.cpu_model, env.cpu_cores) {
+        (Some(model), Some(cores)) => format!("{} ({} cores)", model, cores),
+        (Some(model), None) => model.clone(),
+        (None, Some(cores)) => format!("{} cores", cores),
+        (None, None) => unknown.to_string(),
+    };
+
+    let mut fields = String::new();
+    fields.push_str(&format!("- **Commit:** {}\n", commit));
+    fields.push_str(&format!("- **Host:** {}\n", env.hostname.as_deref().unwrap_or(unknown)));
+    fields.push_str(&format!("- **CPU:** {}\n", cpu));
+    fields.push_str(&format!("- **OS:** {}\n", if env.os.is_empty() { unknown } else { &env.os }));
+    fields.push_str(&format!("- **rustc:** {}\n", env.rustc_version.as_deref().unwrap_or(unknown)));
+    fields
+}
+
+/// Environments the results were produced in, `None` if none were recorded
+///
+/// Results usually share one environment. Merged shards may come from
+/// several machines; each distinct environment is then listed with its targets.
+fn environment_section(results: &[BenchmarkResult]) -> Option<String> {
+    let mut environments: Vec<(&RunEnvironment, Vec<&str>)> = Vec::new();
+    for result in results {
+        let Some(env) = &result.environment else {
+            continue;
+        };
+        match environments.iter_mut().find(|(seen, _)| *seen == env) {
+            Some((_, targets)) => targets.push(&result.target_id),
+            None => environments.push((env, vec![&result.target_id])),
+        }
+    }
+    if environments.is_empty() {
+        return None;
+    }
+
+    let mut section = String::from("## Environment\n\n");
+    if let [(env, _)] = environments.as_slice() {
+        section.push_str(&environment_fields(env));
+        section.push('\n');
+        return Some(section);
+    }
+    section.push_str(&format!(
+        "Results come from {} different environments; compare timings only within one.\n\n",
+        environments.len()
+    ));
+    for (env, targets) in environments {
+        section.push_str(&format!("**Targets:** {}\n\n", targets.join(", ")));
+        section.push_str(&environment_fields(env));
+        section.push('\n');
+    }
+    Some(section)
+}
+
+/// Order of the results in a summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortBy {
+    /// The order the results were given in, usually registration order
+    #[default]
+    Insertion,
+    TargetId,
+    /// Oldest first
+    Timestamp,
+    /// Largest `avg_ms` leaf first; results without one come last
+    SlowestFirst,
+}
+
+/// How a summary groups its results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// By the first of each target's [tags](SummaryOptions::tags), untagged targets last
+    Tag,
+    /// By the first schema format a result reports under `by_format`, others last
+    Format,
+}
+
+/// Options of [`generate_summary_with`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SummaryOptions {
+    /// Fold each target's metrics JSON into a `<details>` element, as GitHub renders it
+    pub collapsible: bool,
+    /// Order of the results in the table and the detailed metrics
+    pub sort: SortBy,
+    /// Split the table and the detailed metrics into one subsection per group
+    pub group: Option<GroupBy>,
+    /// Tags of each target id, for [`GroupBy::Tag`]; results don't record them
+    pub tags: BTreeMap<String, Vec<String>>,
+    /// Metric history for the Trends section, from [`trends`](crate::trends::trends)
+    pub trends: Vec<MetricTrend>,
+    /// Runs each trend chart shows, the newest ones
+    pub trend_runs: usize,
+    /// Comparison with a baseline; metrics it found regressed are highlighted in the table
+    pub comparison: Option<RegressionReport>,
+    /// `(target id, metric path)` of regressions past the allowed threshold, marked ⚠️
+    pub violations: BTreeSet<(String, String)>,
+    /// Add a "Contents" section linking the sections and every target's details
+    pub toc: bool,
+    /// Link each target's page in [`TARGET_PAGES_DIR`] instead of inlining its detailed metrics
+    pub target_pages: bool,
+}
+
+impl SummaryOptions {
+    pub fn with_collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = collapsible;
+        self
+    }
+
+    pub fn with_toc(mut self, toc: bool) -> Self {
+        self.toc = toc;
+        self
+    }
+
+    /// Link the pages [`write_target_pages`](crate::io::write_target_pages) writes instead of inlining the details
+    pub fn with_target_pages(mut self, target_pages: bool) -> Self {
+        self.target_pages = target_pages;
+        self
+    }
+
+    pub fn with_sort(mut self, sort: SortBy) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    pub fn with_group(mut self, group: GroupBy) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Chart the last `runs` points of each target's `avg_ms` in `trends`
+    pub fn with_trends(mut self, trends: Vec<MetricTrend>, runs: usize) -> Self {
+        self.trends = trends;
+        self.trend_runs = runs;
+        self
+    }
+
+    /// Highlight the metrics that regressed in `report`, with ⚠️ on those `thresholds` don't allow
+    ///
+    /// Only the default [`TableStyle::Columns`] table is highlighted.
+    pub fn with_comparison(mut self, report: RegressionReport, thresholds: &RegressionThresholds) -> Self {
+        self.violations = report
+            .evaluate(thresholds)
+            .violations
+            .into_iter()
+            .map(|violation| (violation.target_id, violation.path))
+            .collect();
+        self.comparison = Some(report);
+        self
+    }
+
+    /// Take the tags of every target in `registry`
+    pub fn with_tags_from(mut self, registry: &BenchRegistry) -> Self {
+        for target in registry.targets() {
+            let tags = target.tags().iter().map(ToString::to_string).collect();
+            self.tags.insert(target.id().to_string(), tags);
+        }
+        self
+    }
+}
+
+/// Largest `avg_ms` leaf of a result, in milliseconds
+fn slowest_avg_ms(result: &BenchmarkResult) -> Option<f64> {
+    numeric_leaves(&result.metrics)
+        .into_iter()
+        .filter(|(path, _)| path.rsplit('.').next() == Some(metric_names::BENCH_AVG_MS.name))
+        .filter_map(|(_, measurement)| measurement.in_unit(Unit::Milliseconds))
+        .reduce(f64::max)
+}
+
+/// `results` in the order `sort` asks for; ties keep their given order
+fn sorted_results(results: &[BenchmarkResult], sort: SortBy) -> Cow<'_, [BenchmarkResult]> {
+    if sort == SortBy::Insertion {
+        return Cow::Borrowed(results);
+    }
+    let mut sorted = results.to_vec();
+    match sort {
+        SortBy::Insertion => {}
+        SortBy::TargetId => sorted.sort_by(|a, b| a.target_id.cmp(&b.target_id)),
+        SortBy::Timestamp => sorted.sort_by_key(|result| result.timestamp),
+        SortBy::SlowestFirst => {
+            let mut keyed: Vec<(Option<f64>, BenchmarkResult)> =
+                sorted.into_iter().map(|result| (slowest_avg_ms(&result), result)).collect();
+            // `None` sorts below any time, so results without one end up last
+            keyed.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+            sorted = keyed.into_iter().map(|(_, result)| result).collect();
+        }
+    }
+    Cow::Owned(sorted)
+}
+
+/// Group of a result under `group`, `None` for the catch-all group
+fn group_name(result: &BenchmarkResult, group: GroupBy, options: &SummaryOptions) -> Option<String> {
+    match group {
+        GroupBy::Tag => options.tags.get(&result.target_id)?.first().cloned(),
+        GroupBy::Format => SCHEMA_FORMATS
+            .into_iter()
+            .find(|format| result.metrics_for_format(*format).is_some())
+            .map(|format| format_heading(format).to_string()),
+    }
+}
+
+/// `results` split into named groups in order of first appearance, with the catch-all group last
+fn grouped_results(
+    results: &[BenchmarkResult],
+    group: GroupBy,
+    options: &SummaryOptions,
+) -> Vec<(String, Vec<BenchmarkResult>)> {
+    let mut groups: Vec<(String, Vec<BenchmarkResult>)> = Vec::new();
+    let mut rest = Vec::new();
+    for result in results.iter().cloned() {
+        let Some(name) = group_name(&result, group, options) else {
+            rest.push(result);
+            continue;
+        };
+        match groups.iter_mut().find(|(known, _)| *known == name) {
+            Some((_, members)) => members.push(result),
+            None => groups.push((name, vec![result])),
+        }
+    }
+    if !rest.is_empty() {
+        let name = match group {
+            GroupBy::Tag => "Untagged",
+            GroupBy::Format => "Other",
+        };
+        groups.push((name.to_string(), rest));
+    }
+    groups
+}
+
 /// Generate a full markdown summary report
 pub fn generate_summary(results: &[BenchmarkResult]) -> String {
+    generate_summary_with(results, &SummaryOptions::default())
+}
+
+/// Generate a full markdown summary report with `options`
+pub fn generate_summary_with(results: &[BenchmarkResult], options: &SummaryOptions) -> String {
+    summary(results, None, false, options)
+}
+
+/// Generate a summary report for a run that was cancelled part-way
+///
+/// Same as [`generate_summary`], with a notice under the title that the
+/// results are partial.
+pub fn generate_cancelled_summary(results: &[BenchmarkResult]) -> String {
+    summary(results, None, true, &SummaryOptions::default())
+}
+
+/// Generate a summary report for a run file, noting how a sharded run was split
+pub fn generate_run_summary(run: &RunFile) -> String {
+    summary(&run.results, Some(run), false, &SummaryOptions::default())
+}
+
+/// Directory of the target pages, next to the summary
+pub const TARGET_PAGES_DIR: &str = "targets";
+
+/// File name of each target's page, by target id
+///
+/// Names are the id with every character other than an ASCII letter, digit,
+/// `-`, `_` or inner `.` replaced by `-`, plus `.md`. Ids that would share a
+/// name, ignoring case, get `-2`, `-3` and so on in the order they first
+/// appear in `results`.
+pub fn target_page_names(results: &[BenchmarkResult]) -> BTreeMap<String, String> {
+    let mut names = BTreeMap::new();
+    let mut taken = BTreeSet::new();
+    for result in results {
+        if names.contains_key(&result.target_id) {
+            continue;
+        }
+        let mut stem: String = result
+            .target_id
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let safe = c.is_ascii_alphanumeric() || c == '-' || c == '_' || (c == '.' && i > 0);
+                if safe {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        if stem.is_empty() {
+            stem = "target".to_string();
+        }
+        let mut name = format!("{}.md", stem);
+        let mut copy = 1;
+        while !taken.insert(name.to_lowercase()) {
+            copy += 1;
+            name = format!("{}-{}.md", stem, copy);
+        }
+        names.insert(result.target_id.clone(), name);
+    }
+    names
+}
+
+/// `summary` with its links to the target pages pointing at their `.html` renderings
+///
+/// For a summary written with [`SummaryOptions::with_target_pages`] that is
+/// published as HTML next to HTML target pages.
+pub fn link_html_target_pages(summary: &str, results: &[BenchmarkResult]) -> String {
+    let mut output = summary.to_string();
+    for name in target_page_names(results).values() {
+        let stem = name.strip_suffix(".md").unwrap_or(name);
+        output = output.replace(
+            &format!("]({}/{})", TARGET_PAGES_DIR, name),
+            &format!("]({}/{}.html)", TARGET_PAGES_DIR, stem),
+        );
+    }
+    output
+}
+
+/// Page of one target for [`TARGET_PAGES_DIR`]: its detailed metrics as the summary renders them, and its history
+///
+/// The history table covers the target's metrics in `options.trends` over
+/// the last `trend_runs` runs, and is left out without trends.
+pub fn generate_target_page(result: &BenchmarkResult, options: &SummaryOptions) -> String {
+    let mut page = detail_section(result, "#", options);
+    let trends: Vec<&MetricTrend> = options
+        .trends
+        .iter()
+        .filter(|trend| trend.target_id == result.target_id)
+        .collect();
+    if !trends.is_empty() && options.trend_runs > 0 {
+        page.push_str("## History\n\n");
+        page.push_str(&trend_table(&trends, options.trend_runs));
+    }
+    page
+}
+
+/// Detailed metrics of one result, under a heading of `level`, e.g. `###`
+fn detail_section(result: &BenchmarkResult, level: &str, options: &SummaryOptions) -> String {
+    let mut output = format!("{} {}\n\n", level, result.target_id);
+    output.push_str(&format!(
+        "**Timestamp:** {}\n\n",
+        result.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
+    ));
+    if result.parallel {
+        output.push_str("**Run mode:** parallel (timings may include contention)\n\n");
+    }
+    if let Some(table) = latency_table(&result.metrics) {
+        output.push_str("**Latency (ms):**\n\n");
+        output.push_str(&table);
+        output.push('\n');
+    }
+    if let Some(table) = scaling_table(&result.metrics) {
+        output.push_str("**Scaling:**\n\n");
+        output.push_str(&table);
+        output.push('\n');
+    }
+    if let Some(line) = memory_line(&result.metrics) {
+        output.push_str(&line);
+    }
+    let json = serde_json::to_string_pretty(&result.metrics).unwrap_or_else(|_| "{}".to_string());
+    if options.collapsible {
+        // GitHub only renders markdown inside <details> after a blank line
+        output.push_str(&format!(
+            "<details><summary>{}</summary>\n\n",
+            crate::report::escape(&result.target_id)
+        ));
+        output.push_str(&format!("```json\n{}\n```\n\n", json));
+        output.push_str("</details>\n\n");
+    } else {
+        output.push_str("**Metrics:**\n\n");
+        output.push_str(&format!("```json\n{}\n```\n\n", json));
+    }
+    output
+}
+
+fn summary(results: &[BenchmarkResult], run: Option<&RunFile>, cancelled: bool, options: &SummaryOptions) -> String {
+    summary_sections(results, run, cancelled, options).concat()
+}
+
+/// Parts of the markdown summary, in order, each complete with its trailing newlines
+///
+/// Templates get them as `sections` to reuse the built-in layout piecemeal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct SummarySections {
+    /// Title, partial-run warning, generation time, run and environment
+    pub(crate) header: String,
+    /// Links to the sections and every target's details, empty unless [`SummaryOptions::toc`]
+    pub(crate) contents: String,
+    /// Results table and per-format breakdown
+    pub(crate) results: String,
+    /// Trend charts, empty without history
+    pub(crate) trends: String,
+    /// Detailed metrics of every target
+    pub(crate) details: String,
+}
+
+impl SummarySections {
+    pub(crate) fn concat(&self) -> String {
+        [&self.header, &self.contents, &self.results, &self.trends, &self.details]
+            .into_iter()
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+pub(crate) fn summary_header(results: &[BenchmarkResult], run: Option<&RunFile>, cancelled: bool) -> String {
     let mut output = String::new();
 
     // Title
     output.push_str("# Schema Registry Benchmark Summary\n\n");
+    if cancelled {
+        output.push_str(
+            "> **PARTIAL RUN:** cancelled before every target ran. Only targets listed below completed; compare with care.\n\n",
+        );
+    }
+
+    // Metadata
+    output.push_str(&format!(
+        "**Generated:** {}\n\n",
+        Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+    ));
+    output.push_str(&format!("**Total Benchmarks:** {}\n\n", results.len()));
+    if let Some(run) = run {
+        output.push_str(&run_section(run));
+    }
+    if let Some(section) = environment_section(results) {
+        output.push_str(&section);
+    }
+    output
+}
+
+/// Results table of the summary, highlighting regressions if `options` has a comparison
+fn summary_table(results: &[BenchmarkResult], options: &SummaryOptions) -> String {
+    if results.is_empty() {
+        return generate_table(results);
+    }
+    column_table(results, &TableOptions::default(), Some(options))
+}
+
+pub(crate) fn summary_sections(
+    results: &[BenchmarkResult],
+    run: Option<&RunFile>,
+    cancelled: bool,
+    options: &SummaryOptions,
+) -> SummarySections {
+    let sorted = sorted_results(results, options.sort);
+    let groups = options
+        .group
+        .map(|group| grouped_results(&sorted, group, options))
+        .filter(|groups| !groups.is_empty());
+
+    // Results table
+    let mut table = String::from("## Benchmark Results\n\n");
+    match &groups {
+        Some(groups) => {
+            for (name, members) in groups {
+                table.push_str(&format!("### {}\n\n", name));
+                table.push_str(&summary_table(members, options));
+                table.push('\n');
+            }
+        }
+        None => table.push_str(&summary_table(&sorted, options)),
+    }
+    if let Some(section) = format_section(&sorted) {
+        table.push('\n');
+        table.push_str(section.trim_end());
+        table.push('\n');
+    }
+
+    let trends = trends_section(&sorted, options)
+        .map(|section| format!("\n{}\n", section.trim_end()))
+        .unwrap_or_default();
+
+    // Detailed results, or links to the target pages holding them
+    let pages = options.target_pages.then(|| target_page_names(results));
+    let detail = |result: &BenchmarkResult, level: &str| match &pages {
+        Some(pages) => format!(
+            "{} {}\n\n[Detailed metrics]({}/{})\n\n",
+            level, result.target_id, TARGET_PAGES_DIR, pages[&result.target_id]
+        ),
+        None => detail_section(result, level, options),
+    };
+    let mut details = String::from("\n## Detailed Metrics\n\n");
+    match &groups {
+        Some(groups) => {
+            for (name, members) in groups {
+                details.push_str(&format!("### {}\n\n", name));
+                for result in members {
+                    details.push_str(&detail(result, "####"));
+                }
+            }
+        }
+        None => {
+            for result in sorted.iter() {
+                details.push_str(&detail(result, "###"));
+            }
+        }
+    }
+
+    let mut sections = SummarySections {
+        header: summary_header(results, run, cancelled),
+        contents: String::new(),
+        results: table,
+        trends,
+        details,
+    };
+    if options.toc {
+        sections.contents = contents_section(&sections);
+    }
+    sections
+}
+
+/// GitHub's anchor of a heading: lowercase, spaces as dashes, punctuation dropped
+///
+/// Letters and digits of any script are kept, as are `-` and `_`, so
+/// `Storage Operations` becomes `storage-operations` and `storage_operations`
+/// stays as it is.
+pub fn heading_slug(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Level, text and anchor of every heading in `markdown` outside code blocks
+///
+/// Repeated slugs get `-1`, `-2` and so on, as GitHub numbers them.
+fn heading_anchors(markdown: &str) -> Vec<(usize, &str, String)> {
+    let mut seen: BTreeMap<String, usize> = BTreeMap::new();
+    let mut in_code = false;
+    let mut headings = Vec::new();
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        let level = line.chars().take_while(|&c| c == '#').count();
+        let Some(text) = line[level..].strip_prefix(' ') else {
+            continue;
+        };
+        if in_code || level == 0 {
+            continue;
+        }
+        headings.push((level, text, numbered_anchor(&mut seen, heading_slug(text))));
+    }
+    headings
+}
+
+/// `slug`, or `slug-1`, `slug-2` and so on once `seen` has it
+fn numbered_anchor(seen: &mut BTreeMap<String, usize>, slug: String) -> String {
+    match seen.get_mut(&slug) {
+        Some(count) => {
+            *count += 1;
+            format!("{}-{}", slug, count)
+        }
+        None => {
+            seen.insert(slug.clone(), 0);
+            slug
+        }
+    }
+}
+
+/// "Contents" section linking the sections after it, and the targets under Detailed Metrics
+fn contents_section(sections: &SummarySections) -> String {
+    const HEADING: &str = "## Contents\n\n";
+    let document = format!(
+        "{}{}{}{}{}",
+        sections.header, HEADING, sections.results, sections.trends, sections.details
+    );
+
+    // Anchors are numbered across the whole document, so scan it all and skip up to this heading
+    let before = heading_anchors(&format!("{}{}", sections.header, HEADING)).len();
+    let mut contents = String::from(HEADING);
+    let mut in_details = false;
+    for (level, text, anchor) in heading_anchors(&document).into_iter().skip(before) {
+        if level <= 2 {
+            in_details = text == "Detailed Metrics";
+        } else if !in_details {
+            continue;
+        }
+        let link = text.replace('[', "\\[").replace(']', "\\]");
+        contents.push_str(&format!("{}- [{}](#{})\n", "  ".repeat(level.saturating_sub(2)), link, anchor));
+    }
+    contents.push('\n');
+    contents
+}
+
+/// Most bars in a [`sparkline`]; longer series are averaged down to this width
+pub const SPARKLINE_WIDTH: usize = 24;
+
+const SPARK_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Unicode sparkline of `samples` in recorded order, e.g. `▁▂▃▅▇`
+///
+/// Bars are scaled between the lowest and highest sample, so an equal series
+/// is drawn flat at mid height. Series longer than [`SPARKLINE_WIDTH`] are
+/// split into that many consecutive runs, each drawn at its mean. Non-finite
+/// samples are ignored; empty if none are left.
+pub fn sparkline(samples: &[f64]) -> String {
+    let finite: Vec<f64> = samples.iter().copied().filter(|sample| sample.is_finite()).collect();
+    let width = finite.len().min(SPARKLINE_WIDTH);
+    let bars: Vec<f64> = (0..width)
+        .map(|bar| {
+            let run = &finite[bar * finite.len() / width..(bar + 1) * finite.len() / width];
+            run.iter().sum::<f64>() / run.len() as f64
+        })
+        .collect();
+    let low = bars.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = bars.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    bars.iter()
+        .map(|&value| {
+            if high > low {
+                let top = (SPARK_BARS.len() - 1) as f64;
+                SPARK_BARS[((value - low) / (high - low) * top).round() as usize]
+            } else {
+                SPARK_BARS[SPARK_BARS.len() / 2 - 1]
+            }
+        })
+        .collect()
+}
+
+/// Sparkline of one series with its range, e.g. `▁▃▇ 0.100–0.500 ms (3 samples)`
+///
+/// Raw samples are timings in milliseconds. `None` if the series has no
+/// finite samples.
+pub fn sample_sparkline(samples: &[f64]) -> Option<String> {
+    let finite: Vec<f64> = samples.iter().copied().filter(|sample| sample.is_finite()).collect();
+    let low = finite.iter().copied().reduce(f64::min)?;
+    let high = finite.iter().copied().fold(low, f64::max);
+    let plural = if finite.len() == 1 { "" } else { "s" };
+    Some(format!(
+        "{} {}–{} ms ({} sample{})",
+        sparkline(&finite),
+        format_number(low),
+        format_number(high),
+        finite.len(),
+        plural
+    ))
+}
+
+/// One metric for the compact summary, e.g. `write avg 0.123ms`
+fn headline_text(path: &str, measurement: &Measurement) -> String {
+    let (operation, leaf) = match path.rsplit_once('.') {
+        Some((operation, leaf)) => (Some(operation), leaf),
+        None => (None, path),
+    };
+    let text = match measurement.in_unit(Unit::Milliseconds) {
+        Some(ms) => format!("{} {:.3}ms", leaf.strip_suffix("_ms").unwrap_or(leaf), ms),
+        None => format!("{} {}", leaf, measurement.display()),
+    };
+    match operation {
+        Some(operation) => format!("{} {}", operation, text),
+        None => text,
+    }
+}
+
+/// The metric named by the result's [`HEADLINE_KEY`], if it names one that exists
+fn headline(metrics: &Value, leaves: &BTreeMap<String, Measurement>) -> Option<String> {
+    let path = metrics.get(HEADLINE_KEY)?.as_str()?;
+    leaves.get(path).map(|measurement| headline_text(path, measurement))
+}
+
+/// Generate a compact summary for quick reference
+///
+/// Each target gets one line with the first of: the metric its
+/// [`HEADLINE_KEY`] names, its throughput, its `duration_ms`, its p95 per
+/// operation, or its first `avg_ms`. Results carrying
+/// [`raw_samples`](BenchmarkResult::raw_samples) also list a
+/// [`sample_sparkline`] per operation beneath it.
+pub fn generate_compact_summary(results: &[BenchmarkResult]) -> String {
+    let mut output = String::new();
+
+    output.push_str("# Benchmark Summary\n\n");
+    output.push_str(&format!("Total: {} | ", results.len()));
+    output.push_str(&format!("Generated: {}\n\n", Utc::now().format("%Y-%m-%d %H:%M:%S")));
+
+    for result in results {
+        output.push_str(&format!("- **{}**: ", result.target_id));
+        let leaves = numeric_leaves(&result.metrics);
+        let p95 = p95_cell(&result.metrics);
+        if let Some(headline) = headline(&result.metrics, &leaves) {
+            output.push_str(&headline);
+        } else if let Some(throughput) = throughput_cell(&result.metrics) {
+            output.push_str(&throughput);
+        } else if let Some(duration) = result
+            .metrics
+            .get("duration_ms")
+            .and_then(|value| Measurement::read("duration_ms", value)?.in_unit(Unit::Milliseconds))
+        {
+            output.push_str(&format!("{}ms", format_number(duration)));
+        } else if p95 != "-" {
+            output.push_str(&format!("p95 {}", p95));
+        } else if let Some((path, avg)) = leaves
+            .iter()
+            .find(|(path, _)| path.rsplit('.').next() == Some(metric_names::BENCH_AVG_MS.name))
+        {
+            output.push_str(&headline_text(path, avg));
+        } else {
+            output.push_str("see details");
+        }
+        output.push('\n');
+        for (label, samples) in &result.raw_samples {
+            if let Some(line) = sample_sparkline(samples) {
+                output.push_str(&format!("  - {}: {}\n", label, line));
+            }
+        }
+    }
+
+    output
+}
+
+/// Signed number for a delta column, `+` on increases
+fn signed_number(value: f64) -> String {
+    if value > 0.0 {
+        format!("+{}", format_number(value))
+    } else {
+        format_number(value)
+    }
+}
+
+/// Percent change with an arrow for its direction, e.g. `▲ +50.0%`
+fn percent_change(percent: f64) -> String {
+    if percent > 0.0 {
+        format!("▲ {:+.1}%", percent)
+    } else if percent < 0.0 {
+        format!("▼ {:+.1}%", percent)
+    } else {
+        "0.0%".to_string()
+    }
+}
+
+/// Comparison row of one metric, with regressions in bold
+fn comparison_row(metric: &MetricComparison) -> String {
+    let value = |value: Option<f64>| value.map_or_else(|| "-".to_string(), format_number);
+    let bold = |cell: String| match metric.status {
+        MetricStatus::Regressed => format!("**{}**", cell),
+        _ => cell,
+    };
+    format!(
+        "| {} | {} | {} | {} | {} |\n",
+        metric.path,
+        value(metric.baseline),
+        value(metric.current),
+        bold(metric.delta.map_or_else(|| "-".to_string(), signed_number)),
+        bold(metric.delta_percent.map_or_else(|| "-".to_string(), percent_change))
+    )
+}
+
+/// Markdown report of a comparison against a baseline
+///
+/// Each target gets a table of its metrics with the baseline and current
+/// values, the change and the change in percent. Arrows show which way a
+/// value moved; changes the report classifies as regressions are bold.
+/// Targets present on one side only are listed as added or removed.
+pub fn generate_comparison(report: &RegressionReport) -> String {
+    let mut output = String::from("# Benchmark Comparison\n\n");
+    let counts = report.counts();
+    let count = |status| counts.get(status_name(status)).copied().unwrap_or(0);
+    output.push_str(&format!(
+        "**Regressed:** {} | **Improved:** {} | **Unchanged:** {} | **Missing:** {} (threshold {}%)\n\n",
+        count(MetricStatus::Regressed),
+        count(MetricStatus::Improved),
+        count(MetricStatus::Unchanged),
+        count(MetricStatus::Missing),
+        format_number(report.threshold_percent)
+    ));
+    if !report.current_only.is_empty() {
+        output.push_str("## Added Targets\n\n");
+        for target in &report.current_only {
+            output.push_str(&format!("- {}\n", target));
+        }
+        output.push('\n');
+    }
+    if !report.baseline_only.is_empty() {
+        output.push_str("## Removed Targets\n\n");
+        for target in &report.baseline_only {
+            output.push_str(&format!("- {}\n", target));
+        }
+        output.push('\n');
+    }
+
+    let mut targets: Vec<(&str, Vec<&MetricComparison>)> = Vec::new();
+    for metric in &report.metrics {
+        let added_or_removed = report.current_only.contains(&metric.target_id)
+            || report.baseline_only.contains(&metric.target_id);
+        if added_or_removed {
+            continue;
+        }
+        match targets.last_mut() {
+            Some((target, metrics)) if *target == metric.target_id => metrics.push(metric),
+            _ => targets.push((&metric.target_id, vec![metric])),
+        }
+    }
+    if targets.is_empty() {
+        output.push_str("No targets in common with the baseline.\n");
+        return output;
+    }
+
+    for (target, metrics) in targets {
+        output.push_str(&format!("## {}\n\n", target));
+        if metrics.iter().all(|metric| metric.status == MetricStatus::Unchanged) {
+            output.push_str(&format!(
+                "All {} metric(s) within {}% of the baseline.\n\n",
+                metrics.len(),
+                format_number(report.threshold_percent)
+            ));
+            continue;
+        }
+        output.push_str("| Metric | Baseline | Current | Change | Change % |\n");
+        output.push_str("|--------|----------|---------|--------|----------|\n");
+        for metric in metrics {
+            output.push_str(&comparison_row(metric));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Colour bounds of a [`Badge`], in the unit the results table shows the metric in
+///
+/// For metrics that are better lower, values up to `green` are green, up to
+/// `yellow` yellow and above that red. For metrics better higher, such as
+/// throughput, values from `green` up are green and from `yellow` up yellow.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BadgeThresholds {
+    pub green: f64,
+    pub yellow: f64,
+}
+
+/// A shields.io badge of one metric
+///
+/// [`write_results`](crate::io::write_results) writes each badge of its
+/// layout to `<root>/badges/<name>.json`, for a README badge pointing at
+/// `https://img.shields.io/endpoint?url=...`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Badge {
+    /// File name of the badge, without `.json`
+    pub name: String,
+    pub label: String,
+    /// `<target_id>/<metric path>`, e.g. `validation_operations/json_schema.p95_ms`
+    pub metric_path: String,
+    /// Colour bounds; without them the badge is blue
+    pub thresholds: Option<BadgeThresholds>,
+}
+
+impl Badge {
+    pub fn new(name: impl Into<String>, label: impl Into<String>, metric_path: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            metric_path: metric_path.into(),
+            thresholds: None,
+        }
+    }
+
+    pub fn with_thresholds(mut self, green: f64, yellow: f64) -> Self {
+        self.thresholds = Some(BadgeThresholds { green, yellow });
+        self
+    }
+
+    /// Shields.io endpoint JSON of the badge for `results`
+    pub fn render(&self, results: &[BenchmarkResult]) -> String {
+        let Some((leaf, measurement)) = badge_metric(results, &self.metric_path) else {
+            return badge_json(&self.label, "unknown", "grey");
+        };
+        let color = match self.thresholds {
+            Some(thresholds) => {
+                let value = table_value(&measurement);
+                let within = |bound: f64| match Direction::of(&leaf) {
+                    Some(Direction::HigherIsBetter) => value >= bound,
+                    _ => value <= bound,
+                };
+                if within(thresholds.green) {
+                    "green"
+                } else if within(thresholds.yellow) {
+                    "yellow"
+                } else {
+                    "red"
+                }
+            }
+            None => "blue",
+        };
+        badge_json(&self.label, &measurement.display(), color)
+    }
+}
+
+/// Leaf key and value of `<target_id>/<metric path>` in the last result of that target
+fn badge_metric(results: &[BenchmarkResult], metric_path: &str) -> Option<(String, Measurement)> {
+    let (target_id, path) = metric_path.split_once('/')?;
+    let result = results.iter().rev().find(|result| result.target_id == target_id)?;
+    let measurement = numeric_leaves(&result.metrics).remove(path)?;
+    let leaf = path.rsplit('.').next().unwrap_or(path);
+    Some((leaf.to_string(), measurement))
+}
+
+fn badge_json(label: &str, message: &str, color: &str) -> String {
+    serde_json::json!({
+        "schemaVersion": 1,
+        "label": label,
+        "message": message,
+        "color": color,
+    })
+    .to_string()
+}
+
+/// Shields.io endpoint JSON showing the metric at `metric_path`, e.g. `validation_operations/json_schema.p95_ms`
+///
+/// The badge is blue; use [`Badge::with_thresholds`] for one coloured by
+/// value. A metric that isn't in `results` gives a grey `unknown` badge.
+pub fn generate_badge(results: &[BenchmarkResult], metric_path: &str, label: &str) -> String {
+    Badge::new("", label, metric_path).render(results)
+}
+
+/// Mermaid `xychart-beta` block of the last `last_n` points of the metric at
+/// `metric_path`, e.g. `storage_operations/write.p95_ms`
+///
+/// Runs that didn't record the metric have no point in its trend, so they
+/// are left out rather than drawn as zero. `None` if `trends` has no points
+/// for the metric.
+pub fn generate_trend_chart(trends: &[MetricTrend], metric_path: &str, last_n: usize) -> Option<String> {
+    let (target_id, path) = metric_path.split_once('/')?;
+    let trend = trends.iter().find(|trend| trend.target_id == target_id && trend.path == path)?;
+    trend_chart(trend, last_n)
+}
+
+fn trend_chart(trend: &MetricTrend, last_n: usize) -> Option<String> {
+    let points = trend.last(last_n);
+    if points.is_empty() {
+        return None;
+    }
+
+    let labels: Vec<String> = points
+        .iter()
+        .map(|point| format!("\"{}\"", point.timestamp.format("%m-%d %H:%M")))
+        .collect();
+    let values: Vec<String> = points.iter().map(|point| format_number(point.value)).collect();
+    let mut chart = String::from("```mermaid\nxychart-beta\n");
+    let title = format!("{} {}", trend.target_id, trend.path).replace('"', "'");
+    chart.push_str(&format!("    title \"{}\"\n", title));
+    chart.push_str(&format!("    x-axis [{}]\n", labels.join(", ")));
+    if !trend.unit.symbol().is_empty() {
+        chart.push_str(&format!("    y-axis \"{}\"\n", trend.unit.symbol()));
+    }
+    chart.push_str(&format!("    line [{}]\n", values.join(", ")));
+    chart.push_str("```\n");
+    Some(chart)
+}
+
+/// Trend chart of each target's first `avg_ms` metric with history, `None` if there are none
+///
+/// Only metrics recorded in at least two runs are charted.
+fn trends_section(results: &[BenchmarkResult], options: &SummaryOptions) -> Option<String> {
+    let mut charted = BTreeSet::new();
+    let mut section = String::new();
+    for result in results {
+        if !charted.insert(result.target_id.as_str()) {
+            continue;
+        }
+        let trend = options.trends.iter().find(|trend| {
+            trend.target_id == result.target_id
+                && trend.path.rsplit('.').next() == Some(metric_names::BENCH_AVG_MS.name)
+                && trend.last(options.trend_runs).len() > 1
+        });
+        if let Some(chart) = trend.and_then(|trend| trend_chart(trend, options.trend_runs)) {
+            section.push_str(&chart);
+            section.push('\n');
+        }
+    }
+    (!section.is_empty()).then(|| format!("## Trends\n\n{}", section))
+}
+
+/// Generate a table of the last `last_n` runs of every metric, per target
+///
+/// Columns are the target's most recent runs, oldest first; metrics missing
+/// from a run show `-`. The last column is each metric's rolling average at
+/// its newest run (see [`trends`](crate::trends::trends)).
+pub fn generate_trend_report(trends: &[MetricTrend], last_n: usize) -> String {
+    let mut output = String::from("# Benchmark Trends\n\n");
+    if trends.is_empty() {
+        output.push_str("No archived runs found.\n");
+        return output;
+    }
+
+    let mut by_target: BTreeMap<&str, Vec<&MetricTrend>> = BTreeMap::new();
+    for trend in trends {
+        by_target.entry(trend.target_id.as_str()).or_default().push(trend);
+    }
+
+    for (target_id, trends) in by_target {
+        output.push_str(&format!("## {}\n\n", target_id));
+        output.push_str(&trend_table(&trends, last_n));
+        output.push('\n');
+    }
 
-    // Metadata
-    output.push_str(&format!(
-        "**Generated:** {}\n\n",
-        Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
-    ));
-    output.push_str(&format!("**Total Benchmarks:** {}\n\n", results.len()));
+    output
+}
 
-    // Results table
-    output.push_str("## Benchmark Results\n\n");
-    output.push_str(&generate_table(results));
+/// Table of the last `last_n` runs of one target's `trends`, with each metric's rolling average
+fn trend_table(trends: &[&MetricTrend], last_n: usize) -> String {
+    let runs: BTreeSet<DateTime<Utc>> = trends
+        .iter()
+        .flat_map(|trend| trend.last(last_n).iter().map(|point| point.timestamp))
+        .collect();
+    let runs: Vec<_> = runs.into_iter().rev().take(last_n).rev().collect();
 
-    // Detailed results
-    output.push_str("\n## Detailed Metrics\n\n");
-    for result in results {
-        output.push_str(&format!("### {}\n\n", result.target_id));
-        output.push_str(&format!(
-            "**Timestamp:** {}\n\n",
-            result.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
-        ));
-        output.push_str("**Metrics:**\n\n");
-        output.push_str("```json\n");
-        output.push_str(&serde_json::to_string_pretty(&result.metrics).unwrap_or_else(|_| "{}".to_string()));
-        output.push_str("\n```\n\n");
+    let mut output = String::from("| Metric |");
+    for run in &runs {
+        output.push_str(&format!(" {} |", run.format("%Y-%m-%d %H:%M")));
     }
+    output.push_str(" Rolling avg |\n|--------|");
+    output.push_str(&"---|".repeat(runs.len()));
+    output.push_str("-------------|\n");
 
+    for trend in trends {
+        let metric = match trend.unit.symbol() {
+            "" => trend.path.clone(),
+            symbol => format!("{} ({})", trend.path, symbol),
+        };
+        output.push_str(&format!("| {} |", metric));
+        for run in &runs {
+            let cell = trend
+                .points
+                .iter()
+                .find(|point| point.timestamp == *run)
+                .map_or_else(|| "-".to_string(), |point| format_number(point.value));
+            output.push_str(&format!(" {} |", cell));
+        }
+        let average = trend.latest().map_or_else(|| "-".to_string(), |point| format_number(point.rolling_avg));
+        output.push_str(&format!(" {} |\n", average));
+    }
     output
 }
 
-/// Generate a compact summary for quick reference
-pub fn generate_compact_summary(results: &[BenchmarkResult]) -> String {
-    let mut output = String::new();
+/// `markdown` rendered as an HTML fragment
+///
+/// Needs the `markdown-html` feature. Tables become `<table>` elements with
+/// their column alignment, and fenced code blocks `<pre><code>` with a
+/// `language-*` class, so the JSON metrics stay monospace.
+#[cfg(feature = "markdown-html")]
+pub fn to_html(markdown: &str) -> String {
+    use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd};
 
-    output.push_str("# Benchmark Summary\n\n");
-    output.push_str(&format!("Total: {} | ", results.len()));
-    output.push_str(&format!("Generated: {}\n\n", Utc::now().format("%Y-%m-%d %H:%M:%S")));
+    let mut events: Vec<Event> =
+        Parser::new_ext(markdown, Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH).collect();
 
-    for result in results {
-        output.push_str(&format!("- **{}**: ", result.target_id));
-        if let Some(duration) = result.metrics.get("duration_ms") {
-            output.push_str(&format!("{}ms", duration));
-        } else {
-            output.push_str("see details");
+    // Give headings the anchors the "Contents" section links, from their rendered text
+    let mut seen = BTreeMap::new();
+    let mut anchors = Vec::new();
+    let mut heading: Option<String> = None;
+    for event in &events {
+        match event {
+            Event::Start(Tag::Heading { .. }) => heading = Some(String::new()),
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(heading) = &mut heading {
+                    heading.push_str(text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some(text) = heading.take() {
+                    anchors.push(numbered_anchor(&mut seen, heading_slug(&text)));
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut anchors = anchors.into_iter();
+    for event in &mut events {
+        if let Event::Start(Tag::Heading { id, .. }) = event {
+            *id = anchors.next().map(Into::into);
         }
-        output.push('\n');
     }
 
+    let mut output = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut output, events.into_iter());
     output
 }
 
+/// `markdown` rendered as a standalone HTML page titled `title`, ready to publish
+#[cfg(feature = "markdown-html")]
+pub fn to_html_document(markdown: &str, title: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+<style>\ntable {{ border-collapse: collapse; }}\nth, td {{ border: 1px solid #d0d7de; padding: 4px 8px; }}\n\
+pre {{ background: #f6f8fa; padding: 8px; overflow-x: auto; }}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        crate::report::escape(title),
+        to_html(markdown)
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
     use serde_json::json;
 
+    fn raw_table(results: &[BenchmarkResult]) -> String {
+        generate_table_with(results, &TableOptions::default().with_style(TableStyle::RawJson))
+    }
+
     fn create_test_result(id: &str) -> BenchmarkResult {
         BenchmarkResult::new(
             id.to_string(),
@@ -103,6 +1687,75 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_generate_comparison_tables_each_target() {
+        let baseline = [
+            BenchmarkResult::new("storage".to_string(), json!({"read": {"p95_ms": 2.0}, "write": {"p95_ms": 2.0, "avg_ms": 1.0}})),
+            BenchmarkResult::new("validation".to_string(), json!({"check": {"p95_ms": 1.0}})),
+            BenchmarkResult::new("retired".to_string(), json!({"iterations": 5})),
+        ];
+        let current = [
+            BenchmarkResult::new("storage".to_string(), json!({"read": {"p95_ms": 1.0}, "write": {"p95_ms": 3.0, "avg_ms": 1.0}})),
+            BenchmarkResult::new("validation".to_string(), json!({"check": {"p95_ms": 1.02}})),
+            BenchmarkResult::new("fresh".to_string(), json!({"iterations": 5})),
+        ];
+        let report = generate_comparison(&crate::compare::compare_results(&baseline, &current));
+
+        assert!(report.contains("# Benchmark Comparison"));
+        assert!(report.contains("**Regressed:** 1 | **Improved:** 1 | **Unchanged:** 2 | **Missing:** 2"));
+        assert!(report.contains("## Added Targets\n\n- fresh\n"));
+        assert!(report.contains("## Removed Targets\n\n- retired\n"));
+        assert!(!report.contains("## fresh") && !report.contains("## retired"));
+
+        let storage = &report[report.find("## storage").unwrap()..report.find("## validation").unwrap()];
+        assert_eq!(
+            storage,
+            "## storage\n\n\
+             | Metric | Baseline | Current | Change | Change % |\n\
+             |--------|----------|---------|--------|----------|\n\
+             | read.p95_ms | 2 | 1 | -1 | ▼ -50.0% |\n\
+             | write.avg_ms | 1 | 1 | 0 | 0.0% |\n\
+             | write.p95_ms | 2 | 3 | **+1** | **▲ +50.0%** |\n\n"
+        );
+        assert!(report.contains("## validation\n\nAll 1 metric(s) within 5% of the baseline.\n"));
+    }
+
+    #[test]
+    fn test_generate_comparison_without_common_targets() {
+        let baseline = [BenchmarkResult::new("old".to_string(), json!({"write": {"p95_ms": 1.0}}))];
+        let current = [BenchmarkResult::new("new".to_string(), json!({"write": {"p95_ms": 1.0}}))];
+        let report = generate_comparison(&crate::compare::compare_results(&baseline, &current));
+
+        assert!(report.contains("No targets in common with the baseline."));
+        assert!(!report.contains("| Metric |"));
+    }
+
+    #[test]
+    fn test_badges_show_metric_with_threshold_colour() {
+        let results = [timed_result(), BenchmarkResult::new("validation".to_string(), json!({"ops_per_sec": 1500.0}))];
+        let badge = |metric_path: &str| serde_json::from_str::<Value>(&generate_badge(&results, metric_path, "p95")).unwrap();
+
+        assert_eq!(
+            badge("storage/write.p95_ms"),
+            json!({"schemaVersion": 1, "label": "p95", "message": "2.500 ms", "color": "blue"})
+        );
+        assert_eq!(badge("storage/write.p999_ms")["message"], "unknown");
+        assert_eq!(badge("storage/write.p999_ms")["color"], "grey");
+        assert_eq!(badge("absent/write.p95_ms")["color"], "grey");
+        assert_eq!(badge("no-separator")["color"], "grey");
+
+        let colour = |badge: Badge| serde_json::from_str::<Value>(&badge.render(&results)).unwrap()["color"].clone();
+        let latency = |green, yellow| Badge::new("p95", "p95", "storage/write.p95_ms").with_thresholds(green, yellow);
+        assert_eq!(colour(latency(3.0, 5.0)), "green");
+        assert_eq!(colour(latency(2.0, 5.0)), "yellow");
+        assert_eq!(colour(latency(1.0, 2.0)), "red");
+        // Throughput is better higher, so the bounds are minimums
+        let throughput = |green, yellow| Badge::new("ops", "ops", "validation/ops_per_sec").with_thresholds(green, yellow);
+        assert_eq!(colour(throughput(1000.0, 500.0)), "green");
+        assert_eq!(colour(throughput(2000.0, 1000.0)), "yellow");
+        assert_eq!(colour(throughput(4000.0, 2000.0)), "red");
+    }
+
     #[test]
     fn test_generate_table_empty() {
         let results = vec![];
@@ -116,7 +1769,7 @@ mod tests {
         let table = generate_table(&results);
 
         assert!(table.contains("Target ID"));
-        assert!(table.contains("Metrics"));
+        assert!(table.contains("duration_ms"));
         assert!(table.contains("Timestamp"));
         assert!(table.contains("test_bench"));
     }
@@ -146,6 +1799,157 @@ mod tests {
         assert!(summary.contains("## Detailed Metrics"));
     }
 
+    #[test]
+    fn test_cancelled_summary_is_marked_partial() {
+        let results = vec![create_test_result("test")];
+
+        assert!(generate_cancelled_summary(&results).contains("**PARTIAL RUN:**"));
+        assert!(!generate_summary(&results).contains("PARTIAL RUN"));
+    }
+
+    /// Headings of the detailed metrics sections, in order
+    #[test]
+    fn test_target_page_names_are_safe_and_unique() {
+        let ids = ["storage_operations", "schema size/large", ".hidden", "Größe", "", "a b", "a-b", "A-B", "a b"];
+        let results: Vec<BenchmarkResult> = ids.iter().map(|id| create_test_result(id)).collect();
+        let names = target_page_names(&results);
+
+        assert_eq!(names.len(), 8);
+        assert_eq!(names["storage_operations"], "storage_operations.md");
+        assert_eq!(names["schema size/large"], "schema-size-large.md");
+        assert_eq!(names[".hidden"], "-hidden.md");
+        assert_eq!(names["Größe"], "Gr--e.md");
+        assert_eq!(names[""], "target.md");
+        assert_eq!(names["a b"], "a-b.md");
+        assert_eq!(names["a-b"], "a-b-2.md");
+        assert_eq!(names["A-B"], "A-B-3.md");
+    }
+
+    #[test]
+    fn test_summary_links_target_pages() {
+        let mut options = SummaryOptions::default().with_target_pages(true);
+        let results = [create_test_result("storage_operations"), create_test_result("schema size")];
+        let summary = generate_summary_with(&results, &options);
+
+        assert!(summary.contains("### storage_operations\n\n[Detailed metrics](targets/storage_operations.md)\n\n"));
+        assert!(summary.contains("### schema size\n\n[Detailed metrics](targets/schema-size.md)\n\n"));
+        assert!(!summary.contains("**Metrics:**"));
+
+        // Pages hold what the summary used to inline, plus the history when given
+        options.trends = crate::trends::trends(&[(Utc::now(), results.to_vec())], 2);
+        options.trend_runs = 5;
+        let page = generate_target_page(&results[1], &options);
+        assert!(page.starts_with(&detail_section(&results[1], "#", &SummaryOptions::default())));
+        assert!(page.contains("## History\n\n| Metric |"));
+        assert!(page.contains("| duration_ms (ms) | 100 |"));
+        assert!(!generate_target_page(&results[1], &SummaryOptions::default()).contains("## History"));
+    }
+
+    #[test]
+    fn test_heading_slug_follows_github() {
+        assert_eq!(heading_slug("storage_operations"), "storage_operations");
+        assert_eq!(heading_slug("Storage Operations"), "storage-operations");
+        assert_eq!(heading_slug("compression.large.gzip"), "compressionlargegzip");
+        assert_eq!(heading_slug("schéma_v2 (beta)"), "schéma_v2-beta");
+        assert_eq!(heading_slug("Größe 日本語"), "größe-日本語");
+        assert_eq!(heading_slug("⚡ fast-path"), "-fast-path");
+    }
+
+    #[test]
+    fn test_summary_toc_links_every_target() {
+        let results = [create_test_result("storage_operations"), create_test_result("Schema Size")];
+        let mut options = SummaryOptions::default().with_group(GroupBy::Tag);
+        options.tags.insert("storage_operations".to_string(), vec!["fast".to_string()]);
+        assert!(!generate_summary_with(&results, &options).contains("## Contents"));
+
+        let summary = generate_summary_with(&results, &options.with_toc(true));
+        // Group headings repeat under Detailed Metrics, where GitHub numbers them
+        let contents = "## Contents\n\n\
+            - [Benchmark Results](#benchmark-results)\n\
+            - [Detailed Metrics](#detailed-metrics)\n  \
+            - [fast](#fast-1)\n    \
+            - [storage_operations](#storage_operations)\n  \
+            - [Untagged](#untagged-1)\n    \
+            - [Schema Size](#schema-size)\n\n\
+            ## Benchmark Results\n";
+        assert!(summary.contains(contents), "{}", summary);
+
+        let anchors: Vec<String> = heading_anchors(&summary).into_iter().map(|(_, _, anchor)| anchor).collect();
+        assert!(["contents", "fast", "fast-1", "untagged-1", "schema-size"]
+            .iter()
+            .all(|anchor| anchors.iter().any(|a| a == anchor)));
+    }
+
+    fn detail_headings(summary: &str) -> Vec<&str> {
+        let details = &summary[summary.find("## Detailed Metrics").unwrap()..];
+        details.lines().filter(|line| line.starts_with("### ") || line.starts_with("#### ")).collect()
+    }
+
+    #[test]
+    fn test_summary_sorts_table_and_details() {
+        let mut slow = BenchmarkResult::new("slow".to_string(), json!({"read": {"avg_ms": 0.1}, "write": {"avg_ms": 2.0}}));
+        let mut fast = BenchmarkResult::new("fast".to_string(), json!({"write": {"avg_ms": {"value": 500.0, "unit": "microseconds"}}}));
+        let mut untimed = create_test_result("untimed");
+        untimed.timestamp = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+        fast.timestamp = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 1).unwrap();
+        slow.timestamp = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 2).unwrap();
+        let results = [fast, untimed, slow];
+        let order = |sort| generate_summary_with(&results, &SummaryOptions::default().with_sort(sort));
+
+        assert_eq!(detail_headings(&order(SortBy::Insertion)), ["### fast", "### untimed", "### slow"]);
+        assert_eq!(detail_headings(&order(SortBy::TargetId)), ["### fast", "### slow", "### untimed"]);
+        assert_eq!(detail_headings(&order(SortBy::Timestamp)), ["### untimed", "### fast", "### slow"]);
+        let slowest = order(SortBy::SlowestFirst);
+        assert_eq!(detail_headings(&slowest), ["### slow", "### fast", "### untimed"]);
+        assert!(slowest.find("| slow ").unwrap() < slowest.find("| fast ").unwrap());
+    }
+
+    #[test]
+    fn test_summary_groups_by_format_or_tag() {
+        let validation = BenchmarkResult::new(
+            "validation".to_string(),
+            json!({"by_format": {"avro": {"avg_ms": 0.25, "p95_ms": 0.5}}}),
+        );
+        let results = [create_test_result("storage"), validation, create_test_result("compatibility")];
+
+        let by_format = generate_summary_with(&results, &SummaryOptions::default().with_group(GroupBy::Format));
+        assert_eq!(
+            detail_headings(&by_format),
+            ["### Avro", "#### validation", "### Other", "#### storage", "#### compatibility"]
+        );
+        let table = &by_format[by_format.find("## Benchmark Results").unwrap()..by_format.find("## Results by Format").unwrap()];
+        assert!(table.find("### Avro").unwrap() < table.find("| validation").unwrap());
+        assert!(table.find("### Other").unwrap() < table.find("| storage").unwrap());
+
+        let mut options = SummaryOptions::default().with_group(GroupBy::Tag).with_sort(SortBy::TargetId);
+        options.tags.insert("storage".to_string(), vec!["io".to_string(), "slow".to_string()]);
+        options.tags.insert("compatibility".to_string(), vec!["schema".to_string()]);
+        let by_tag = generate_summary_with(&results, &options);
+        assert_eq!(
+            detail_headings(&by_tag),
+            ["### schema", "#### compatibility", "### io", "#### storage", "### Untagged", "#### validation"]
+        );
+
+        let empty = generate_summary_with(&[], &SummaryOptions::default().with_group(GroupBy::Tag));
+        assert!(empty.contains("No benchmark results available"));
+    }
+
+    #[test]
+    fn test_collapsible_summary_folds_metrics_json() {
+        let results = vec![create_test_result("a<b>"), create_test_result("test")];
+        let flat = generate_summary(&results);
+        assert!(!flat.contains("<details>"));
+        assert_eq!(flat.matches("**Metrics:**\n\n```json\n").count(), 2);
+
+        let folded = generate_summary_with(&results, &SummaryOptions::default().with_collapsible(true));
+        assert_eq!(folded.matches("<details>").count(), 2);
+        assert!(!folded.contains("**Metrics:**"));
+        assert!(folded.contains("<details><summary>a&lt;b&gt;</summary>\n\n```json\n{\n"));
+        assert!(folded.contains("\n```\n\n</details>\n\n"));
+        // The table is still outside any details element
+        assert!(folded.find("## Benchmark Results").unwrap() < folded.find("<details>").unwrap());
+    }
+
     #[test]
     fn test_generate_summary_includes_json() {
         let results = vec![create_test_result("test")];
@@ -167,18 +1971,532 @@ mod tests {
         assert!(summary.contains("100ms"));
     }
 
+    #[test]
+    fn test_sparkline_of_constant_samples_is_flat() {
+        assert_eq!(sparkline(&[2.0, 2.0, 2.0]), "▄▄▄");
+    }
+
+    #[test]
+    fn test_sparkline_scales_increasing_samples() {
+        let samples: Vec<f64> = (0..8).map(f64::from).collect();
+        assert_eq!(sparkline(&samples), "▁▂▃▄▅▆▇█");
+        assert_eq!(sparkline(&[10.0, 20.0, 30.0]), "▁▅█");
+
+        // 48 samples are averaged in pairs down to 24 bars
+        let long: Vec<f64> = (0..48).map(f64::from).collect();
+        let line = sparkline(&long);
+        assert_eq!(line.chars().count(), SPARKLINE_WIDTH);
+        assert!(line.starts_with('▁') && line.ends_with('█'));
+    }
+
+    #[test]
+    fn test_sparkline_of_single_sample() {
+        assert_eq!(sparkline(&[0.25]), "▄");
+        assert_eq!(sample_sparkline(&[0.25]).unwrap(), "▄ 0.250–0.250 ms (1 sample)");
+    }
+
+    #[test]
+    fn test_sparkline_ignores_non_finite_samples() {
+        assert_eq!(sparkline(&[1.0, f64::NAN, 3.0]), "▁█");
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sample_sparkline(&[f64::INFINITY]), None);
+    }
+
+    #[test]
+    fn test_compact_summary_draws_raw_samples() {
+        let mut result = create_test_result("storage");
+        assert!(!generate_compact_summary(&[result.clone()]).contains("  - "));
+
+        result.raw_samples.insert("write".to_string(), vec![0.1, 0.2, 0.5]);
+        result.raw_samples.insert("read".to_string(), Vec::new());
+        let summary = generate_compact_summary(&[result]);
+        assert!(summary.contains("- **storage**: 100ms\n  - write: ▁▃█ 0.100–0.500 ms (3 samples)\n"));
+        assert!(!summary.contains("read"));
+    }
+
     #[test]
     fn test_table_escapes_pipe_characters() {
         let result = BenchmarkResult::new(
             "test".to_string(),
             json!({"key": "value|with|pipes"}),
         );
-        let table = generate_table(&[result]);
+        let table = raw_table(&[result]);
 
         // Should escape pipes in JSON values
         assert!(table.contains("\\|"));
     }
 
+    #[test]
+    fn test_summary_flags_parallel_results() {
+        let mut parallel = create_test_result("contended");
+        parallel.parallel = true;
+        let summary = generate_summary(&[parallel, create_test_result("isolated")]);
+
+        assert_eq!(summary.matches("**Run mode:** parallel").count(), 1);
+    }
+
+    fn timed_result() -> BenchmarkResult {
+        BenchmarkResult::new(
+            "storage".to_string(),
+            json!({
+                "iterations": 10,
+                "write": {"avg_ms": 1.0, "p50_ms": 0.9, "p95_ms": 2.5, "p99_ms": 3.0, "max_ms": 3.1,
+                          "std_dev_ms": 0.05, "cv_percent": 5.0},
+                "read": {"avg_ms": 0.5, "p95_ms": 1.5, "std_dev_ms": 0.25, "cv_percent": 50.0},
+                "compression": {"large": {"gzip": {"encode": {"avg_ms": 0.1, "p95_ms": 0.2}}}}
+            }),
+        )
+    }
+
+    #[test]
+    fn test_table_surfaces_p95() {
+        let table = raw_table(&[timed_result(), create_test_result("untimed")]);
+
+        assert!(table.contains("p95 (ms)"));
+        let storage_row = table.lines().find(|line| line.starts_with("| storage |")).unwrap();
+        assert!(storage_row.contains("write: 2.500"));
+        assert!(!storage_row.contains("write: 2.500 (unstable)"));
+        assert!(storage_row.contains("read: 1.500 (unstable)"));
+        assert!(storage_row.contains("compression.large.gzip.encode: 0.200"));
+        assert!(table.contains("| untimed | - |"));
+    }
+
+    fn column_results() -> Vec<BenchmarkResult> {
+        let mut results = vec![
+            BenchmarkResult::new(
+                "storage".to_string(),
+                json!({"read": {"avg_ms": 0.5, "p95_ms": 1.5}, "write": {"avg_ms": 1.25, "p95_ms": 2.5}}),
+            ),
+            BenchmarkResult::new(
+                "validation".to_string(),
+                json!({
+                    "avro": {"avg_ms": {"value": 250.0, "unit": "microseconds"}, "p95_ms": 0.3},
+                    "json_schema": {"avg_ms": 0.75, "p95_ms": 0.9}
+                }),
+            ),
+            create_test_result("flat"),
+        ];
+        for result in &mut results {
+            result.timestamp = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+        }
+        results
+    }
+
+    #[test]
+    fn test_table_has_a_column_per_average() {
+        assert_eq!(generate_table(&column_results()), include_str!("testdata/table.md"));
+    }
+
+    #[test]
+    fn test_table_columns_are_bounded_or_chosen() {
+        let bounded = generate_table_with(&column_results(), &TableOptions::default().with_max_columns(2));
+        let header = bounded.lines().next().unwrap();
+        assert!(header.contains("read.avg_ms") && header.contains("write.avg_ms"));
+        assert!(!header.contains("avro.avg_ms"));
+        assert!(bounded.contains("2 more metric column(s) not shown"));
+
+        let chosen = generate_table_with(
+            &column_results(),
+            &TableOptions::default().with_columns(["write.p95_ms", "missing"]),
+        );
+        assert!(chosen.starts_with("| Target ID  | write.p95_ms | missing |"));
+        assert!(chosen.contains("| storage    |        2.500 |       — |"));
+        assert!(!chosen.contains("not shown"));
+
+        // Without averages, every numeric leaf is a candidate
+        let flat = generate_table(&[create_test_result("flat")]);
+        assert!(flat.starts_with("| Target ID | duration_ms | throughput |"));
+    }
+
+    #[test]
+    fn test_summary_includes_latency_table() {
+        let summary = generate_summary(&[timed_result()]);
+
+        assert!(summary.contains("| Operation | avg | p50 | p95 | p99 | max | std dev | CV % |"));
+        assert!(summary.contains("| write | 1.000 | 0.900 | 2.500 | 3.000 | 3.100 | 0.050 | 5.0 |"));
+        assert!(summary.contains("| compression.large.gzip.encode | 0.100 | - | 0.200 | - | - | - | - |"));
+    }
+
+    #[test]
+    fn test_summary_flags_unstable_operations() {
+        let summary = generate_summary(&[timed_result()]);
+
+        assert!(summary.contains("| read | 0.500 | - | 1.500 | - | - | 0.250 | 50.0 ⚠ unstable |"));
+        assert!(summary.contains("1 operation(s) have CV > 10%"));
+    }
+
+    #[test]
+    fn test_numbers_are_formatted_at_render_time() {
+        let result = BenchmarkResult::new(
+            "storage".to_string(),
+            json!({"write": {"avg_ms": 0.123456, "p95_ms": 0.2, "cv_percent": 7.24}, "duration_ms": 12.34567}),
+        );
+        let table = raw_table(&[result.clone()]);
+        assert!(table.contains("write: 0.200"));
+        assert!(table.contains("\"avg_ms\":0.123"));
+
+        let summary = generate_summary(&[result.clone()]);
+        assert!(summary.contains("| write | 0.123 | - | 0.200 | - | - | - | 7.2 |"));
+        // The JSON block keeps the full-precision values
+        assert!(summary.contains("0.123456"));
+
+        assert!(generate_compact_summary(&[result]).contains("12.346ms"));
+    }
+
+    #[test]
+    fn test_summary_renders_scaling_table() {
+        let level = |tasks: usize, throughput: f64, p99: f64, factor: f64| {
+            json!({
+                "tasks": {"value": tasks, "unit": "count"},
+                "throughput_ops_per_sec": {"value": throughput, "unit": "ops_per_sec"},
+                "p99_ms": {"value": p99, "unit": "microseconds"},
+                "scalability_factor": {"value": factor, "unit": "ratio"},
+            })
+        };
+        let result = BenchmarkResult::new(
+            "concurrency_scaling".to_string(),
+            json!({"levels": [level(1, 1000.0, 50.0, 1.0), level(8, 5000.0, 400.0, 5.0)], "scalability_factor": 5.0}),
+        );
+        let summary = generate_summary(&[result, create_test_result("flat")]);
+
+        assert!(summary.contains("| Tasks | Throughput (ops/s) | p99 (ms) | Scalability |"));
+        assert!(summary.contains("| 1 | 1000.0 | 0.050 | 1.00x |"));
+        assert!(summary.contains("| 8 | 5000.0 | 0.400 | 5.00x |"));
+        assert_eq!(summary.matches("**Scaling:**").count(), 1);
+    }
+
+    #[test]
+    fn test_summary_groups_results_by_format() {
+        let operation = |p95: f64| json!({"avg_ms": p95 / 2.0, "p95_ms": p95, "p99_ms": p95});
+        let validation = BenchmarkResult::new(
+            "validation_operations".to_string(),
+            json!({"by_format": {"avro": operation(0.25), "json_schema": operation(0.5)}}),
+        );
+        let compatibility = BenchmarkResult::new(
+            "compatibility_operations".to_string(),
+            json!({"backward": operation(2.0), "by_format": {"avro": {"backward": operation(1.0)}}}),
+        );
+        let summary = generate_summary(&[validation, compatibility, create_test_result("flat")]);
+
+        assert!(summary.contains("## Results by Format"));
+        assert!(summary.find("### JSON Schema").unwrap() < summary.find("### Avro").unwrap());
+        let avro = &summary[summary.find("### Avro").unwrap()..summary.find("## Detailed Metrics").unwrap()];
+        assert!(avro.contains("| validation_operations | - | 0.125 | 0.250 | 0.250 |"));
+        assert!(avro.contains("| compatibility_operations | backward | 0.500 | 1.000 | 1.000 |"));
+        assert!(!summary.contains("### Protobuf"));
+        assert!(!avro.contains("flat"));
+
+        assert!(!generate_summary(&[create_test_result("flat")]).contains("Results by Format"));
+    }
+
+    #[test]
+    fn test_summary_renders_memory() {
+        let measured = BenchmarkResult::new(
+            "validation".to_string(),
+            json!({"memory": {"rss_before_bytes": 10485760, "rss_after_bytes": 11534336,
+                              "rss_peak_bytes": 13107200, "allocated_bytes": 2048}}),
+        );
+        let rss_only = BenchmarkResult::new("storage".to_string(), json!({"memory": {"allocated_bytes": 512}}));
+        let summary = generate_summary(&[measured, rss_only, create_test_result("untracked")]);
+
+        assert!(summary.contains("**Memory:** peak RSS 12.5 MiB (start 10.0 MiB, end 11.0 MiB); 2.0 KiB allocated"));
+        assert!(summary.contains("**Memory:** RSS not available on this platform; 512 B allocated"));
+        assert_eq!(summary.matches("**Memory:**").count(), 2);
+    }
+
+    #[test]
+    fn test_compact_summary_prefers_throughput() {
+        let result = BenchmarkResult::new(
+            "validation".to_string(),
+            json!({
+                "duration_ms": 5,
+                "json_schema": {"avg_ms": 0.5, "p95_ms": 0.6, "ops_per_sec": 2000.0},
+                "avro": {"avg_ms": 0.25, "p95_ms": 0.3, "ops_per_sec": 4000.0}
+            }),
+        );
+        let compact = generate_compact_summary(&[result]);
+
+        assert!(compact.contains("- **validation**: avro: 4000.0 ops/s, json_schema: 2000.0 ops/s\n"));
+        // Results without throughput keep the previous fallbacks
+        assert!(generate_compact_summary(&[timed_result()]).contains("p95 write: 2.500"));
+    }
+
+    #[test]
+    fn test_compact_summary_of_adapter_metrics() {
+        use crate::metrics::{
+            CompatibilityMetrics, IterationCounts, OperationMetrics, StorageMetrics, ValidationFormatMetrics,
+            ValidationMetrics,
+        };
+
+        let op = OperationMetrics::from_samples(&[0.1, 0.2, 0.3]);
+        let counts = IterationCounts {
+            warmup_iterations: 2,
+            iterations: 3,
+            sample_time_ms: None,
+        };
+        let storage = StorageMetrics {
+            counts,
+            write: op,
+            read: op,
+            read_cold: op,
+            read_warm: op,
+            cache_hit_ratio: Some(1.0),
+            update: op,
+            compression: BTreeMap::new(),
+            adaptive_ttl: BTreeMap::new(),
+            many_subjects: BTreeMap::new(),
+        };
+        let validation = ValidationMetrics {
+            counts,
+            invalid_schemas: 0,
+            by_format: [("avro".to_string(), ValidationFormatMetrics { invalid_schemas: 0, latency: op })].into(),
+        };
+        let compatibility = CompatibilityMetrics {
+            counts,
+            checked_pairs: 4,
+            compatible_pairs: 4,
+            backward: op,
+            forward: op,
+            full: op,
+            transitive: op,
+            by_format: BTreeMap::new(),
+        };
+        let mut results = vec![
+            BenchmarkResult::new("storage_operations".to_string(), serde_json::to_value(storage).unwrap()),
+            BenchmarkResult::new("validation_operations".to_string(), serde_json::to_value(validation).unwrap()),
+            BenchmarkResult::new("compatibility_operations".to_string(), serde_json::to_value(compatibility).unwrap()),
+        ];
+
+        let compact = generate_compact_summary(&results);
+        assert!(!compact.contains("see details"));
+        assert!(compact.contains("- **storage_operations**: read: 5000.0 ops/s, "));
+        assert!(compact.contains("- **validation_operations**: by_format.avro.latency: 5000.0 ops/s\n"));
+        assert!(compact.contains("- **compatibility_operations**: backward: 5000.0 ops/s, "));
+
+        // A headline replaces the throughput list
+        results[0].metrics[HEADLINE_KEY] = json!("write.avg_ms");
+        let compact = generate_compact_summary(&results);
+        assert!(compact.contains("- **storage_operations**: write avg 0.200ms\n"));
+    }
+
+    #[test]
+    fn test_compact_summary_falls_back_to_avg() {
+        let avg_only = BenchmarkResult::new("codec".to_string(), json!({"large": {"encode": {"avg_ms": 0.123}}}));
+        assert!(generate_compact_summary(&[avg_only]).contains("- **codec**: large.encode avg 0.123ms\n"));
+
+        // A headline naming a missing metric is ignored
+        let result = BenchmarkResult::new(
+            "storage".to_string(),
+            json!({"headline": "update.avg_ms", "ratio": 0.5, "duration_ms": 10}),
+        );
+        assert!(generate_compact_summary(&[result.clone()]).contains("- **storage**: 10ms\n"));
+        let mut ratio = result;
+        ratio.metrics[HEADLINE_KEY] = json!("ratio");
+        assert!(generate_compact_summary(&[ratio]).contains("- **storage**: ratio 0.500\n"));
+    }
+
+    #[test]
+    fn test_legacy_string_statistics_render_like_numbers() {
+        let legacy = BenchmarkResult::new("old".to_string(), json!({"write": {"avg_ms": "1.5", "p95_ms": "2.000"}}));
+
+        assert!(raw_table(&[legacy.clone()]).contains("write: 2.000"));
+        assert!(generate_table(&[legacy.clone()]).contains("|        1.500 |"));
+        assert!(generate_compact_summary(&[legacy]).contains("p95 write: 2.000"));
+    }
+
+    #[test]
+    fn test_measurements_render_in_table_units() {
+        let result = BenchmarkResult::new(
+            "storage".to_string(),
+            json!({
+                "write": {
+                    "avg_ms": {"value": 850.0, "unit": "microseconds"},
+                    "p95_ms": {"value": 0.0012, "unit": "seconds"},
+                    "cv_percent": {"value": 7.24, "unit": "percent"}
+                },
+                "duration_ms": {"value": 2.5, "unit": "seconds"}
+            }),
+        );
+
+        assert!(raw_table(&[result.clone()]).contains("write: 1.200"));
+        let summary = generate_summary(&[result.clone()]);
+        assert!(summary.contains("| write | 0.850 | - | 1.200 | - | - | - | 7.2 |"));
+        assert!(generate_compact_summary(&[result]).contains("2500ms"));
+    }
+
+    /// Trends of three daily runs; storage's `write.avg_ms` is missing from the second
+    fn trend_history() -> Vec<MetricTrend> {
+        let runs = [
+            json!({"write": {"avg_ms": 1.5}}),
+            json!({"read": {"avg_ms": 0.5}}),
+            json!({"write": {"avg_ms": 4.0}}),
+        ];
+        let history: Vec<_> = runs
+            .into_iter()
+            .enumerate()
+            .map(|(day, metrics)| {
+                let timestamp = Utc.with_ymd_and_hms(2025, 6, day as u32 + 1, 12, 0, 0).unwrap();
+                let validation = BenchmarkResult::new("validation".to_string(), json!({"check": {"avg_ms": day}}));
+                (timestamp, vec![BenchmarkResult::new("storage".to_string(), metrics), validation])
+            })
+            .collect();
+        crate::trends::trends(&history, 2)
+    }
+
+    #[test]
+    fn test_trend_chart_skips_runs_without_the_metric() {
+        let trends = trend_history();
+
+        assert_eq!(
+            generate_trend_chart(&trends, "storage/write.avg_ms", 10).unwrap(),
+            include_str!("testdata/trend_chart.md")
+        );
+        let last = generate_trend_chart(&trends, "validation/check.avg_ms", 2).unwrap();
+        assert!(last.contains("    x-axis [\"06-02 12:00\", \"06-03 12:00\"]\n    y-axis \"ms\"\n    line [1, 2]\n"));
+        assert_eq!(generate_trend_chart(&trends, "storage/write.p95_ms", 10), None);
+        assert_eq!(generate_trend_chart(&trends, "storage", 10), None);
+    }
+
+    #[test]
+    fn test_summary_highlights_regressions() {
+        let baseline = [
+            BenchmarkResult::new("storage".to_string(), json!({"write": {"avg_ms": 1.0}, "read": {"avg_ms": 1.0}})),
+            BenchmarkResult::new("validation".to_string(), json!({"check": {"avg_ms": 1.0}})),
+        ];
+        let current = [
+            BenchmarkResult::new(
+                "storage".to_string(),
+                json!({"write": {"avg_ms": 1.5}, "read": {"avg_ms": 0.5}, "encode": {"avg_ms": 2.0}}),
+            ),
+            BenchmarkResult::new("validation".to_string(), json!({"check": {"avg_ms": 1.08}})),
+        ];
+        let report = crate::compare::compare_results(&baseline, &current);
+        let options = SummaryOptions::default().with_comparison(report, &RegressionThresholds::new(10.0));
+        let summary = generate_summary_with(&current, &options);
+        let table = &summary[..summary.find("## Detailed Metrics").unwrap()];
+        let row = |target: &str| table.lines().find(|line| line.starts_with(&format!("| {} ", target))).unwrap();
+
+        // Regressed past the allowed 10%
+        assert!(row("storage").contains("| **1.500** (+50.0%) ⚠️ |"));
+        // Improved, and not in the baseline: shown as usual
+        assert!(row("storage").contains(" 0.500 |"));
+        assert!(row("storage").contains(" 2.000 |"));
+        assert!(!row("storage").contains("**0.500**") && !row("storage").contains("**2.000**"));
+        // Regressed past the 5% noise threshold, but within the allowed 10%
+        assert!(row("validation").contains("**1.080** (+8.0%) |"));
+        assert!(!row("validation").contains("⚠️"));
+
+        assert!(!generate_summary(&current).contains("**1.500**"));
+    }
+
+    #[test]
+    fn test_summary_charts_trends_when_history_is_given() {
+        let results = [create_test_result("storage"), create_test_result("validation")];
+        assert!(!generate_summary(&results).contains("## Trends"));
+
+        let summary = generate_summary_with(&results, &SummaryOptions::default().with_trends(trend_history(), 10));
+        let trends = &summary[summary.find("## Trends").unwrap()..summary.find("## Detailed Metrics").unwrap()];
+        // storage's first avg_ms with history is read.avg_ms, recorded once, so write.avg_ms is charted
+        assert!(trends.contains("title \"storage write.avg_ms\""));
+        assert!(trends.contains("title \"validation check.avg_ms\""));
+        assert_eq!(trends.matches("```mermaid").count(), 2);
+    }
+
+    #[test]
+    fn test_trend_report_shows_last_runs() {
+        let history: Vec<_> = [1.0, 2.0, 4.0]
+            .iter()
+            .enumerate()
+            .map(|(day, p95)| {
+                let timestamp = format!("2025-06-0{}T12:00:00Z", day + 1).parse().unwrap();
+                let metrics = json!({"write": {"p95_ms": p95}, "iterations": 10});
+                (timestamp, vec![BenchmarkResult::new("storage".to_string(), metrics)])
+            })
+            .collect();
+        let report = generate_trend_report(&crate::trends::trends(&history, 2), 2);
+
+        assert!(report.contains("## storage"));
+        assert!(report.contains("| Metric | 2025-06-02 12:00 | 2025-06-03 12:00 | Rolling avg |"));
+        assert!(report.contains("| write.p95_ms (ms) | 2 | 4 | 3 |"));
+        assert!(report.contains("| iterations | 10 | 10 | 10 |"));
+        assert!(!report.contains("2025-06-01"));
+
+        assert!(generate_trend_report(&[], 5).contains("No archived runs found."));
+    }
+
+    fn environment(host: &str) -> RunEnvironment {
+        RunEnvironment {
+            git_commit: Some("0123456789abcdef".to_string()),
+            git_branch: Some("main".to_string()),
+            hostname: Some(host.to_string()),
+            cpu_model: Some("Test CPU".to_string()),
+            cpu_cores: Some(8),
+            os: "linux x86_64".to_string(),
+            rustc_version: None,
+        }
+    }
+
+    #[test]
+    fn test_summary_renders_environment() {
+        let mut result = create_test_result("test");
+        result.environment = Some(environment("bench-01"));
+        let summary = generate_summary(&[result]);
+
+        assert!(summary.contains("## Environment"));
+        assert!(summary.contains("- **Commit:** `0123456789abcdef` (main)"));
+        assert!(summary.contains("- **Host:** bench-01"));
+        assert!(summary.contains("- **CPU:** Test CPU (8 cores)"));
+        assert!(summary.contains("- **rustc:** unknown"));
+        assert!(!generate_summary(&[create_test_result("old")]).contains("## Environment"));
+    }
+
+    #[test]
+    fn test_environment_fields_one_line_each() {
+        let expected = "- **Commit:** `0123456789abcdef` (main)\n\
+            - **Host:** bench-01\n\
+            - **CPU:** Test CPU (8 cores)\n\
+            - **OS:** linux x86_64\n\
+            - **rustc:** unknown\n";
+        assert_eq!(environment_fields(&environment("bench-01")), expected);
+    }
+
+    #[test]
+    fn test_summary_separates_mixed_environments() {
+        let mut results = vec![create_test_result("a"), create_test_result("b"), create_test_result("c")];
+        results[0].environment = Some(environment("ci-1"));
+        results[1].environment = Some(environment("ci-2"));
+        results[2].environment = Some(environment("ci-1"));
+        let summary = generate_summary(&results);
+
+        assert!(summary.contains("Results come from 2 different environments"));
+        assert!(summary.contains("**Targets:** a, c"));
+        assert!(summary.contains("**Targets:** b"));
+    }
+
+    #[test]
+    fn test_run_summary_notes_shard_layout() {
+        let run: RunFile = serde_json::from_value(json!({
+            "run_id": "ci-42",
+            "profile": "default",
+            "seed": 7,
+            "shards": [
+                {"shard": {"index": 1, "count": 3}, "targets": ["alpha", "beta"]},
+                {"shard": {"index": 3, "count": 3}, "targets": ["gamma"]}
+            ],
+            "results": [],
+            "failed": ["gamma"]
+        }))
+        .unwrap();
+        let summary = generate_run_summary(&run);
+
+        assert!(summary.contains("**Run:** `ci-42` (profile `default`, seed 7)"));
+        assert!(summary.contains("**Sharding:** 2 of 3 shards"));
+        assert!(summary.contains("| 1/3 | alpha, beta |"));
+        assert!(summary.contains("**Missing shards:** 2/3"));
+        assert!(summary.contains("**Failed targets:** gamma"));
+        assert!(!generate_summary(&[]).contains("**Run:**"));
+    }
+
     #[test]
     fn test_summary_handles_empty_results() {
         let results = vec![];
@@ -187,4 +2505,31 @@ mod tests {
         assert!(summary.contains("**Total Benchmarks:** 0"));
         assert!(summary.contains("No benchmark results available"));
     }
+
+    #[cfg(feature = "markdown-html")]
+    #[test]
+    fn test_summary_converts_to_html() {
+        let html = to_html(include_str!("testdata/summary_small.md"));
+        assert_eq!(html, include_str!("testdata/summary_small.html"));
+
+        let page = to_html_document("# Q&A\n", "Q&A");
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("<title>Q&amp;A</title>"));
+        assert!(page.contains("<body>\n<h1 id=\"qa\">Q&amp;A</h1>\n</body>"));
+    }
+
+    #[cfg(feature = "markdown-html")]
+    #[test]
+    fn test_html_headings_carry_the_contents_anchors() {
+        let results = [create_test_result("storage_operations"), create_test_result("Schema Size")];
+        let mut options = SummaryOptions::default().with_group(GroupBy::Tag).with_toc(true);
+        options.tags.insert("storage_operations".to_string(), vec!["fast".to_string()]);
+        let html = to_html(&generate_summary_with(&results, &options));
+
+        let links: Vec<&str> = html.split("<a href=\"#").skip(1).map(|rest| &rest[..rest.find('"').unwrap()]).collect();
+        assert!(links.contains(&"fast-1") && links.contains(&"untagged-1"), "{}", html);
+        for anchor in links {
+            assert!(html.contains(&format!(" id=\"{}\">", anchor)), "no heading with id {}", anchor);
+        }
+    }
 }
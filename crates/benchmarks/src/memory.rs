@@ -0,0 +1,204 @@
+//! Memory usage of benchmark targets
+//!
+//! With the `mem-metrics` feature the runner wraps each target's run in
+//! [`track`], which samples the process resident set size (RSS) before and
+//! after the run and, from a background thread, every [`RSS_SAMPLE_INTERVAL`]
+//! during it. The numbers land in the result's metrics as a `memory` object
+//! (see [`MemoryMetrics`]).
+//!
+//! RSS is read from `/proc/self/status`, so it is only sampled on Linux;
+//! elsewhere the RSS fields are left out. `allocated_bytes` is counted by
+//! [`CountingAllocator`] and is left out unless the binary installs it:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//! ```
+//!
+//! Both are process-wide, so in parallel runs they include every target
+//! running at the same time.
+
+use crate::metrics::MemoryMetrics;
+use crate::BenchmarkResult;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// How often RSS is sampled while a target runs
+pub const RSS_SAMPLE_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Bytes requested through [`CountingAllocator`] since the process started
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+
+/// Global allocator counting the bytes requested from the system allocator
+///
+/// Only growth is counted: a `realloc` to a larger size adds the difference,
+/// and frees are not subtracted.
+pub struct CountingAllocator;
+
+// SAFETY: every call is forwarded unchanged to the system allocator
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATED.fetch_add(new_size.saturating_sub(layout.size()) as u64, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Bytes allocated so far, `None` unless [`CountingAllocator`] is installed
+///
+/// Any process allocates before it benchmarks, so a zero count means the
+/// allocator isn't in use.
+pub fn allocated_bytes() -> Option<u64> {
+    let total = ALLOCATED.load(Ordering::Relaxed);
+    (total > 0).then_some(total)
+}
+
+/// Current resident set size of the process, `None` where it can't be read
+pub fn rss_bytes() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        parse_vm_rss(&std::fs::read_to_string("/proc/self/status").ok()?)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// The `VmRSS` line of a `/proc/<pid>/status` file, in bytes
+fn parse_vm_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Background thread tracking the highest RSS seen
+struct PeakSampler {
+    peak: Arc<AtomicU64>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl PeakSampler {
+    fn start(initial: u64) -> Self {
+        let peak = Arc::new(AtomicU64::new(initial));
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = thread::Builder::new()
+            .name("bench-rss-sampler".to_string())
+            .spawn({
+                let peak = peak.clone();
+                let stop = stop.clone();
+                move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        if let Some(rss) = rss_bytes() {
+                            peak.fetch_max(rss, Ordering::Relaxed);
+                        }
+                        thread::park_timeout(RSS_SAMPLE_INTERVAL);
+                    }
+                }
+            })
+            .ok();
+        Self { peak, stop, handle }
+    }
+
+    /// Stop sampling and return the peak
+    fn finish(mut self) -> u64 {
+        self.shutdown();
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for PeakSampler {
+    /// Stops the thread when the run is abandoned, e.g. on timeout
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Await `run`, recording the memory it used
+pub async fn track<F: Future>(run: F) -> (F::Output, MemoryMetrics) {
+    let rss_before = rss_bytes();
+    let allocated_before = allocated_bytes();
+    let sampler = rss_before.map(PeakSampler::start);
+
+    let output = run.await;
+
+    let rss_after = rss_bytes();
+    let allocated_after = allocated_bytes();
+    let rss_peak = sampler.map(PeakSampler::finish);
+    let memory = MemoryMetrics {
+        rss_before_bytes: rss_before,
+        rss_after_bytes: rss_after,
+        rss_peak_bytes: rss_peak.map(|peak| rss_after.map_or(peak, |after| peak.max(after))),
+        allocated_bytes: allocated_before
+            .zip(allocated_after)
+            .map(|(before, after)| after.saturating_sub(before)),
+    };
+    (output, memory)
+}
+
+/// Await a target's run, adding its memory usage to the result's metrics
+pub async fn with_memory_metrics(
+    run: impl Future<Output = anyhow::Result<BenchmarkResult>>,
+) -> anyhow::Result<BenchmarkResult> {
+    let (result, memory) = track(run).await;
+    Ok(result?.with_memory(memory))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_vm_rss() {
+        let status = "Name:\tbench\nVmPeak:\t  20000 kB\nVmRSS:\t   1536 kB\nThreads:\t4\n";
+        assert_eq!(parse_vm_rss(status), Some(1536 * 1024));
+        assert_eq!(parse_vm_rss("Name:\tbench\n"), None);
+    }
+
+    #[tokio::test]
+    async fn test_track_samples_rss_where_available() {
+        let (value, memory) = track(async {
+            let buffer = vec![1u8; 8 << 20];
+            tokio::time::sleep(RSS_SAMPLE_INTERVAL * 4).await;
+            buffer.iter().map(|&b| b as usize).sum::<usize>()
+        })
+        .await;
+        assert_eq!(value, 8 << 20);
+
+        if cfg!(target_os = "linux") {
+            let peak = memory.rss_peak_bytes.unwrap();
+            assert!(peak >= memory.rss_before_bytes.unwrap());
+            assert!(peak >= memory.rss_after_bytes.unwrap());
+        } else {
+            assert_eq!(memory.rss_peak_bytes, None);
+        }
+        // The library's test binary doesn't install the counting allocator
+        assert_eq!(memory.allocated_bytes, None);
+    }
+}
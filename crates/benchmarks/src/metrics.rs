@@ -0,0 +1,517 @@
+//! Typed benchmark metrics
+//!
+//! Targets build these structs and serialize them into
+//! [`BenchmarkResult::metrics`](crate::BenchmarkResult::metrics), so every
+//! statistic reaches the JSON output as a number, wrapped with the unit its
+//! field declares (see [`crate::units`]). Rounding for display happens only
+//! when reports are rendered.
+//!
+//! Result files written before the metrics were typed hold statistics as
+//! strings (`"avg_ms": "0.105"`); [`migrate_metrics`] converts them when a
+//! result is deserialized, so old files still load and compare.
+
+use crate::stats::Summary;
+use crate::units;
+use schema_registry_core::metric_names;
+use schema_registry_core::SerializationFormat;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Key of the [`MemoryMetrics`] object in a result's metrics
+pub const MEMORY_KEY: &str = "memory";
+
+/// Optional key naming a result's most telling metric by dotted path, e.g. `"write.avg_ms"`
+///
+/// The compact summary shows that metric for the target instead of its
+/// throughput or p95.
+pub const HEADLINE_KEY: &str = "headline";
+
+/// Key of the [`ResourceUsageMetrics`] object in a result's metrics
+pub const RESOURCE_USAGE_KEY: &str = "resource_usage";
+
+/// Key of the per-format metrics in a result's metrics
+///
+/// Targets that declare [`formats`](crate::adapters::BenchTarget::formats)
+/// put each format's metrics in an object under this key, keyed by
+/// [`format_key`].
+pub const BY_FORMAT_KEY: &str = "by_format";
+
+/// Every schema format, in the order reports list them
+pub const SCHEMA_FORMATS: [SerializationFormat; 3] = [
+    SerializationFormat::JsonSchema,
+    SerializationFormat::Avro,
+    SerializationFormat::Protobuf,
+];
+
+/// Canonical name of `format` in metrics keys
+pub fn format_key(format: SerializationFormat) -> &'static str {
+    match format {
+        SerializationFormat::JsonSchema => "json_schema",
+        SerializationFormat::Avro => "avro",
+        SerializationFormat::Protobuf => "protobuf",
+    }
+}
+
+/// Statistics of one timed operation, in milliseconds
+///
+/// Field names are the catalogued statistic names in
+/// [`metric_names`](schema_registry_core::metric_names).
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OperationMetrics {
+    #[serde(with = "units::milliseconds")]
+    pub avg_ms: f64,
+    #[serde(with = "units::milliseconds")]
+    pub min_ms: f64,
+    #[serde(with = "units::milliseconds")]
+    pub max_ms: f64,
+    #[serde(with = "units::milliseconds")]
+    pub p50_ms: f64,
+    #[serde(with = "units::milliseconds")]
+    pub p90_ms: f64,
+    #[serde(with = "units::milliseconds")]
+    pub p95_ms: f64,
+    #[serde(with = "units::milliseconds")]
+    pub p99_ms: f64,
+    #[serde(with = "units::milliseconds")]
+    pub std_dev_ms: f64,
+    #[serde(with = "units::percent")]
+    pub cv_percent: f64,
+    /// Samples left out of the statistics above by the outlier policy
+    #[serde(with = "units::count")]
+    pub outliers_rejected: usize,
+    /// Operations per second of measured time; absent (0) in older result files
+    #[serde(with = "units::ops_per_sec")]
+    pub ops_per_sec: f64,
+}
+
+impl OperationMetrics {
+    /// Metrics of a set of timing samples
+    pub fn from_samples(samples: &[f64]) -> Self {
+        Summary::from_samples(samples).into()
+    }
+}
+
+impl From<Summary> for OperationMetrics {
+    fn from(summary: Summary) -> Self {
+        Self {
+            avg_ms: summary.avg,
+            min_ms: summary.min,
+            max_ms: summary.max,
+            p50_ms: summary.p50,
+            p90_ms: summary.p90,
+            p95_ms: summary.p95,
+            p99_ms: summary.p99,
+            std_dev_ms: summary.std_dev,
+            cv_percent: summary.cv_percent,
+            outliers_rejected: summary.outliers_rejected,
+            ops_per_sec: summary.ops_per_sec,
+        }
+    }
+}
+
+/// Iteration counts every built-in target reports
+///
+/// Built from a finished [`SampleLoop`](crate::adapters::SampleLoop), so
+/// `iterations` is the number of samples actually collected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IterationCounts {
+    #[serde(with = "units::count")]
+    pub warmup_iterations: usize,
+    #[serde(with = "units::count")]
+    pub iterations: usize,
+    /// Sampling budget per measured loop, when sampling by time
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "units::milliseconds")]
+    pub sample_time_ms: Option<u64>,
+}
+
+/// Metrics of the `storage_operations` target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageMetrics {
+    #[serde(flatten)]
+    pub counts: IterationCounts,
+    pub write: OperationMetrics,
+    /// Same as `read_warm`, kept for readers of older result files
+    pub read: OperationMetrics,
+    /// Reads with the read cache cleared first; absent from older result files
+    #[serde(default)]
+    pub read_cold: OperationMetrics,
+    /// Reads of the schema the cold read just cached; absent from older result files
+    #[serde(default)]
+    pub read_warm: OperationMetrics,
+    /// Share of warm reads answered from the read cache
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "units::ratio")]
+    pub cache_hit_ratio: Option<f64>,
+    pub update: OperationMetrics,
+    /// Codec results by size bucket, then codec name
+    pub compression: BTreeMap<String, BTreeMap<String, CodecMetrics>>,
+    /// Adaptive cache TTLs by workload class; absent from older result files
+    #[serde(default)]
+    pub adaptive_ttl: BTreeMap<String, CacheTtlMetrics>,
+    /// Blob store listing and lookup with many subjects, by directory layout;
+    /// absent from older result files
+    #[serde(default)]
+    pub many_subjects: BTreeMap<String, SubjectLayoutMetrics>,
+}
+
+/// Blob store operations on many subjects in one directory layout
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SubjectLayoutMetrics {
+    /// Fetching the first page of keys
+    pub list: OperationMetrics,
+    /// Reading one subject's blob
+    pub read: OperationMetrics,
+}
+
+/// Where the adaptive cache TTL of one workload class converged
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CacheTtlMetrics {
+    #[serde(with = "units::seconds")]
+    pub ttl_seconds: f64,
+}
+
+/// Metrics of one blob codec on one schema size
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CodecMetrics {
+    /// Encoded size divided by the original size
+    #[serde(with = "units::ratio")]
+    pub compression_ratio: f64,
+    pub encode: OperationMetrics,
+    pub decode: OperationMetrics,
+}
+
+/// Metrics of the `validation_operations` target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationMetrics {
+    #[serde(flatten)]
+    pub counts: IterationCounts,
+    /// Validations the engine rejected, over all measured iterations; absent from older result files
+    #[serde(default, with = "units::count")]
+    pub invalid_schemas: usize,
+    /// Results by [`format_key`]
+    pub by_format: BTreeMap<String, ValidationFormatMetrics>,
+}
+
+/// Validation of one format's schema
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ValidationFormatMetrics {
+    /// Validations the engine rejected, over all measured iterations
+    #[serde(with = "units::count")]
+    pub invalid_schemas: usize,
+    #[serde(flatten)]
+    pub latency: OperationMetrics,
+}
+
+/// Metrics of the `compatibility_operations` target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompatibilityMetrics {
+    #[serde(flatten)]
+    pub counts: IterationCounts,
+    /// Schema pairs one pass over the workload checks; absent from older result files
+    #[serde(default, with = "units::count")]
+    pub checked_pairs: usize,
+    /// How many of `checked_pairs` the checker found compatible; absent from older result files
+    #[serde(default, with = "units::count")]
+    pub compatible_pairs: usize,
+    pub backward: OperationMetrics,
+    pub forward: OperationMetrics,
+    pub full: OperationMetrics,
+    pub transitive: OperationMetrics,
+    /// The same metrics by [`format_key`]; absent from older result files
+    #[serde(default)]
+    pub by_format: BTreeMap<String, CompatibilityFormatMetrics>,
+}
+
+/// Compatibility checks of one format's share of the workload
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CompatibilityFormatMetrics {
+    #[serde(with = "units::count")]
+    pub checked_pairs: usize,
+    #[serde(with = "units::count")]
+    pub compatible_pairs: usize,
+    pub backward: OperationMetrics,
+    pub forward: OperationMetrics,
+    pub full: OperationMetrics,
+    pub transitive: OperationMetrics,
+}
+
+/// Metrics of the `registration_log_overhead` target
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RegistrationLogMetrics {
+    #[serde(flatten)]
+    pub counts: IterationCounts,
+    /// Registrations without a registration log
+    pub unlogged: OperationMetrics,
+    /// Registrations appended to a registration log
+    pub logged: OperationMetrics,
+    /// Mean `logged` time over mean `unlogged` time, minus 100%
+    #[serde(with = "units::percent")]
+    pub overhead_percent: f64,
+}
+
+/// Metrics of the `report_generation` target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportingMetrics {
+    #[serde(flatten)]
+    pub counts: IterationCounts,
+    /// Timings by the number of results in the synthetic result set
+    pub results: BTreeMap<String, ReportingOperationMetrics>,
+}
+
+/// Timings of the reporting operations over one result set
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReportingOperationMetrics {
+    /// [`markdown::generate_summary`](crate::markdown::generate_summary)
+    pub generate_summary: OperationMetrics,
+    /// [`markdown::generate_table`](crate::markdown::generate_table)
+    pub generate_table: OperationMetrics,
+    /// [`io::write_json`](crate::io::write_json) to a temporary file
+    pub write_json: OperationMetrics,
+    /// [`io::read_json`](crate::io::read_json) of the file just written
+    pub read_json: OperationMetrics,
+}
+
+/// Metrics of the `concurrency_scaling` target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConcurrencyMetrics {
+    #[serde(flatten)]
+    pub counts: IterationCounts,
+    /// Bound the concurrency levels were clamped to
+    #[serde(with = "units::count")]
+    pub max_tasks: usize,
+    /// One entry per concurrency level, fewest tasks first
+    pub levels: Vec<ConcurrencyLevelMetrics>,
+    /// Scalability factor of the highest level
+    #[serde(with = "units::ratio")]
+    pub scalability_factor: f64,
+}
+
+/// Throughput and latency at one concurrency level
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConcurrencyLevelMetrics {
+    /// Concurrent tasks
+    #[serde(with = "units::count")]
+    pub tasks: usize,
+    /// Operations measured, over all measured iterations
+    #[serde(with = "units::count")]
+    pub operations: usize,
+    /// `operations` over the wall time of the level
+    #[serde(with = "units::ops_per_sec")]
+    pub throughput_ops_per_sec: f64,
+    /// Throughput over the throughput of the lowest level; equals the task
+    /// ratio when the operation scales perfectly
+    #[serde(with = "units::ratio")]
+    pub scalability_factor: f64,
+    /// Latency of single operations
+    #[serde(flatten)]
+    pub latency: OperationMetrics,
+}
+
+/// Metrics of the `schema_size` and `schema_size_large` targets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaSizeMetrics {
+    #[serde(flatten)]
+    pub counts: IterationCounts,
+    /// Timings by size bucket (`1kb`, `100kb`, `1mb`), then by format
+    pub sizes: BTreeMap<String, BTreeMap<String, SchemaSizeFormatMetrics>>,
+}
+
+/// Validation of one generated schema
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SchemaSizeFormatMetrics {
+    /// Size of the schema text, at most the bucket size
+    #[serde(with = "units::bytes")]
+    pub schema_bytes: usize,
+    /// `schema_bytes` over the mean validation latency; falls as schemas grow
+    /// when validation is super-linear in their size
+    #[serde(with = "units::bytes_per_sec")]
+    pub bytes_per_sec: f64,
+    #[serde(flatten)]
+    pub latency: OperationMetrics,
+}
+
+/// Metrics of a `workload_<name>` target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkloadMetrics {
+    #[serde(flatten)]
+    pub counts: IterationCounts,
+    /// Seed the plan's operations were generated from
+    pub seed: u64,
+    /// Results by step kind (`register`, `read`, `update`, `compat-check`)
+    pub operations: BTreeMap<String, WorkloadOperationMetrics>,
+}
+
+/// Latency and throughput of one step kind of a workload
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorkloadOperationMetrics {
+    #[serde(flatten)]
+    pub latency: OperationMetrics,
+    /// Operations measured, over all measured passes
+    #[serde(with = "units::count")]
+    pub operations: usize,
+    /// `operations` over the wall time of the steps that performed them
+    #[serde(with = "units::ops_per_sec")]
+    pub throughput_ops_per_sec: f64,
+}
+
+/// Memory used by one target's run, reported under the `memory` key
+///
+/// Collected with the `mem-metrics` feature (see [`crate::memory`]). Fields
+/// that couldn't be measured on the platform are left out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct MemoryMetrics {
+    /// Resident set size when the run started
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "units::bytes")]
+    pub rss_before_bytes: Option<u64>,
+    /// Resident set size when the run finished
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "units::bytes")]
+    pub rss_after_bytes: Option<u64>,
+    /// Highest resident set size sampled during the run
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "units::bytes")]
+    pub rss_peak_bytes: Option<u64>,
+    /// Bytes requested from the allocator during the run
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "units::bytes")]
+    pub allocated_bytes: Option<u64>,
+}
+
+/// CPU time and context switches of one target's run, reported under the `resource_usage` key
+///
+/// Collected with the `rusage-metrics` feature from `getrusage` deltas (see
+/// [`crate::resource_usage`]). Where `getrusage` isn't available the whole
+/// object is left out.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct ResourceUsageMetrics {
+    /// CPU time spent in user mode during the run
+    #[serde(with = "units::milliseconds")]
+    pub user_cpu_ms: f64,
+    /// CPU time spent in the kernel during the run
+    #[serde(with = "units::milliseconds")]
+    pub system_cpu_ms: f64,
+    /// Times the process gave up the CPU, e.g. to wait for I/O
+    #[serde(with = "units::count")]
+    pub voluntary_context_switches: u64,
+    /// Times the process was preempted
+    #[serde(with = "units::count")]
+    pub involuntary_context_switches: u64,
+}
+
+impl ResourceUsageMetrics {
+    /// User and system CPU time together
+    pub fn cpu_ms(&self) -> f64 {
+        self.user_cpu_ms + self.system_cpu_ms
+    }
+}
+
+/// Deserialize a number that older result files wrote as a string
+pub fn number_or_string<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Number(f64),
+        Text(String),
+    }
+
+    match Raw::deserialize(deserializer)? {
+        Raw::Number(n) => Ok(n),
+        Raw::Text(s) => s
+            .trim()
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("expected a number, found '{}'", s))),
+    }
+}
+
+/// Deserialize a metrics object, converting string-valued statistics to numbers
+pub fn deserialize_metrics<'de, D>(deserializer: D) -> Result<Value, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut metrics = Value::deserialize(deserializer)?;
+    migrate_metrics(&mut metrics);
+    Ok(metrics)
+}
+
+/// Convert catalogued statistics stored as numeric strings to numbers, in place
+///
+/// Values that are not catalogued statistics, or don't parse, are left alone.
+pub fn migrate_metrics(metrics: &mut Value) {
+    match metrics {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let number = match value {
+                    Value::String(s) if metric_names::is_catalogued(key) => {
+                        s.trim().parse::<f64>().ok().and_then(serde_json::Number::from_f64)
+                    }
+                    _ => None,
+                };
+                match number {
+                    Some(number) => *value = Value::Number(number),
+                    None => migrate_metrics(value),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(migrate_metrics),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_operation_metrics_serialize_as_catalogued_measurements() {
+        let metrics = serde_json::to_value(OperationMetrics::from_samples(&[1.0, 2.0, 6.0])).unwrap();
+
+        assert_eq!(metrics["avg_ms"], json!({"value": 3.0, "unit": "milliseconds"}));
+        assert_eq!(metrics["p50_ms"]["value"], 2.0);
+        assert!((metrics["p95_ms"]["value"].as_f64().unwrap() - 5.6).abs() < 1e-9);
+        assert!((metrics["std_dev_ms"]["value"].as_f64().unwrap() - 7f64.sqrt()).abs() < 1e-9);
+        assert_eq!(metrics["cv_percent"]["unit"], "percent");
+        assert_eq!(metrics["outliers_rejected"], json!({"value": 0, "unit": "count"}));
+        // 3 operations in 9ms
+        assert_eq!(metrics["ops_per_sec"]["unit"], "ops_per_sec");
+        assert!((metrics["ops_per_sec"]["value"].as_f64().unwrap() - 1000.0 / 3.0).abs() < 1e-9);
+        for (key, value) in metrics.as_object().unwrap() {
+            assert!(metric_names::is_catalogued(key), "{key}");
+            assert!(crate::units::is_measurement(value), "{key}");
+        }
+
+        let round_trip: OperationMetrics = serde_json::from_value(metrics).unwrap();
+        assert_eq!(round_trip, OperationMetrics::from_samples(&[1.0, 2.0, 6.0]));
+    }
+
+    #[test]
+    fn test_operation_metrics_accept_legacy_strings() {
+        let legacy = json!({"avg_ms": "0.105", "min_ms": "0.100", "max_ms": "0.115", "p95_ms": 0.113});
+        let metrics: OperationMetrics = serde_json::from_value(legacy).unwrap();
+
+        assert_eq!(metrics.avg_ms, 0.105);
+        assert_eq!(metrics.p95_ms, 0.113);
+        // Statistics added after the file was written default to zero
+        assert_eq!(metrics.cv_percent, 0.0);
+
+        assert!(serde_json::from_value::<OperationMetrics>(json!({"avg_ms": "fast"})).is_err());
+    }
+
+    #[test]
+    fn test_migrate_metrics_converts_nested_statistics_only() {
+        let mut metrics = json!({
+            "iterations": 10,
+            "label": "1.5",
+            "write": {"avg_ms": "1.250", "p95_ms": "2.000"},
+            "compression": {"small": {"gzip": {"compression_ratio": "0.410", "encode": {"avg_ms": "0.010"}}}}
+        });
+        migrate_metrics(&mut metrics);
+
+        assert_eq!(metrics["write"]["avg_ms"], 1.25);
+        assert_eq!(metrics["compression"]["small"]["gzip"]["compression_ratio"], 0.41);
+        assert_eq!(metrics["compression"]["small"]["gzip"]["encode"]["avg_ms"], 0.01);
+        assert_eq!(metrics["label"], "1.5");
+        assert_eq!(metrics["iterations"], 10);
+    }
+}
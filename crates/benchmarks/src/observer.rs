@@ -0,0 +1,465 @@
+//! Structured progress events for embedding UIs
+//!
+//! A [`ProgressObserver`] is registered on a [`Runner`](crate::runner::Runner)
+//! through its builder and called at fixed points of a run: once when the run
+//! starts, when each target starts, after sampled iterations, when each target
+//! finishes or fails, and once when the run ends.
+//!
+//! Iterations are reported by the targets themselves through
+//! [`record_iteration`], which is a no-op outside a runner. Iteration events
+//! are throttled per target to the runner's configured interval, so a target
+//! with thousands of fast iterations produces a bounded number of events.
+//!
+//! Observers are isolated from the run and from each other: an observer that
+//! panics is disabled for the rest of the run and the panic goes no further.
+//!
+//! Built-in observers: [`NdjsonObserver`] writes one JSON event per line, and
+//! [`StatusObserver`] keeps a [`RunStatus`] snapshot a status endpoint can
+//! serve. The CLI's progress bar lives in `schema-cli`.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+tokio::task_local! {
+    static ITERATIONS: IterationReporter;
+}
+
+/// Timing of one iteration, as reported by the target
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct IterationSample {
+    /// Zero-based iteration, warmup included
+    pub iteration: usize,
+    /// Whether the iteration's samples are discarded
+    pub warmup: bool,
+    /// Time the iteration's measured operations took
+    pub elapsed_ms: f64,
+}
+
+/// How a target ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum TargetOutcome {
+    /// The target completed; `duration` includes warmup
+    Finished { duration: Duration },
+    /// The target returned an error or timed out
+    Failed { error: String },
+}
+
+/// Totals reported when a run ends
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RunSummary {
+    pub completed: usize,
+    pub failed: usize,
+    /// Whether the run was cancelled before every target was launched
+    pub cancelled: bool,
+    pub duration_ms: f64,
+}
+
+/// Receiver of a run's progress
+///
+/// Every method defaults to doing nothing. Methods are called from the task
+/// driving the run (in parallel mode, from several targets' futures), so they
+/// should return quickly.
+pub trait ProgressObserver: Send + Sync {
+    /// The run is about to launch `targets`, in scheduling order
+    fn on_run_started(&self, targets: &[String]) {
+        let _ = targets;
+    }
+
+    /// `target_id` began its warmup iterations
+    fn on_target_started(&self, target_id: &str) {
+        let _ = target_id;
+    }
+
+    /// `target_id` completed an iteration; sampled, not every iteration
+    fn on_iteration_completed(&self, target_id: &str, sample: IterationSample) {
+        let _ = (target_id, sample);
+    }
+
+    /// `target_id` completed or failed
+    fn on_target_finished(&self, target_id: &str, outcome: &TargetOutcome) {
+        let _ = (target_id, outcome);
+    }
+
+    /// The run ended
+    fn on_run_finished(&self, summary: &RunSummary) {
+        let _ = summary;
+    }
+}
+
+impl<T: ProgressObserver + ?Sized> ProgressObserver for Arc<T> {
+    fn on_run_started(&self, targets: &[String]) {
+        (**self).on_run_started(targets)
+    }
+
+    fn on_target_started(&self, target_id: &str) {
+        (**self).on_target_started(target_id)
+    }
+
+    fn on_iteration_completed(&self, target_id: &str, sample: IterationSample) {
+        (**self).on_iteration_completed(target_id, sample)
+    }
+
+    fn on_target_finished(&self, target_id: &str, outcome: &TargetOutcome) {
+        (**self).on_target_finished(target_id, outcome)
+    }
+
+    fn on_run_finished(&self, summary: &RunSummary) {
+        (**self).on_run_finished(summary)
+    }
+}
+
+/// Observers of one run, each disabled after its first panic
+#[derive(Default)]
+pub(crate) struct ObserverSet {
+    observers: Vec<(Box<dyn ProgressObserver>, AtomicBool)>,
+}
+
+impl ObserverSet {
+    pub(crate) fn push(&mut self, observer: Box<dyn ProgressObserver>) {
+        self.observers.push((observer, AtomicBool::new(false)));
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+
+    /// Call `event` on every observer that hasn't panicked
+    ///
+    /// `target_id` is the target the event is about, if any, named in the
+    /// warning logged when an observer panics.
+    pub(crate) fn notify(&self, target_id: Option<&str>, event: impl Fn(&dyn ProgressObserver)) {
+        for (index, (observer, panicked)) in self.observers.iter().enumerate() {
+            if panicked.load(Ordering::Relaxed) {
+                continue;
+            }
+            if let Err(payload) = catch_unwind(AssertUnwindSafe(|| event(observer.as_ref()))) {
+                panicked.store(true, Ordering::Relaxed);
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                    .unwrap_or("non-string panic payload");
+                match target_id {
+                    Some(target_id) => tracing::warn!(
+                        "Progress observer {} panicked on target {}: {}; it receives no further events",
+                        index + 1,
+                        target_id,
+                        message
+                    ),
+                    None => tracing::warn!(
+                        "Progress observer {} panicked: {}; it receives no further events",
+                        index + 1,
+                        message
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// Forwards one target's iterations to the observers, at most once per interval
+#[derive(Clone)]
+pub(crate) struct IterationReporter {
+    target_id: Arc<str>,
+    observers: Arc<ObserverSet>,
+    min_interval: Duration,
+    last_emitted: Arc<Mutex<Option<Instant>>>,
+}
+
+impl IterationReporter {
+    pub(crate) fn new(target_id: &str, observers: Arc<ObserverSet>, min_interval: Duration) -> Self {
+        Self {
+            target_id: target_id.into(),
+            observers,
+            min_interval,
+            last_emitted: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Run `fut` with iterations it records reported through `self`
+    pub(crate) async fn scope<F: std::future::Future>(self, fut: F) -> F::Output {
+        ITERATIONS.scope(self, fut).await
+    }
+
+    fn report(&self, sample: IterationSample) {
+        {
+            let mut last = self.last_emitted.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            if matches!(*last, Some(at) if now.duration_since(at) < self.min_interval) {
+                return;
+            }
+            *last = Some(now);
+        }
+        self.observers
+            .notify(Some(&*self.target_id), |observer| observer.on_iteration_completed(&self.target_id, sample));
+    }
+}
+
+/// Report a completed iteration of the running target
+///
+/// Called by targets from their iteration loop. Does nothing unless the
+/// target is run by a [`Runner`](crate::runner::Runner) with observers, and
+/// drops samples that arrive faster than the runner's iteration interval.
+pub fn record_iteration(iteration: usize, warmup: bool, elapsed_ms: f64) {
+    let _ = ITERATIONS.try_with(|reporter| {
+        reporter.report(IterationSample {
+            iteration,
+            warmup,
+            elapsed_ms,
+        })
+    });
+}
+
+/// A progress event as one self-describing value
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    RunStarted {
+        targets: Vec<String>,
+    },
+    TargetStarted {
+        target_id: String,
+    },
+    IterationCompleted {
+        target_id: String,
+        #[serde(flatten)]
+        sample: IterationSample,
+    },
+    TargetFinished {
+        target_id: String,
+        duration_ms: f64,
+    },
+    TargetFailed {
+        target_id: String,
+        error: String,
+    },
+    RunFinished {
+        #[serde(flatten)]
+        summary: RunSummary,
+    },
+}
+
+impl ProgressEvent {
+    fn target_finished(target_id: &str, outcome: &TargetOutcome) -> Self {
+        match outcome {
+            TargetOutcome::Finished { duration } => ProgressEvent::TargetFinished {
+                target_id: target_id.to_string(),
+                duration_ms: duration.as_secs_f64() * 1000.0,
+            },
+            TargetOutcome::Failed { error } => ProgressEvent::TargetFailed {
+                target_id: target_id.to_string(),
+                error: error.clone(),
+            },
+        }
+    }
+}
+
+/// Writes every event as one line of JSON
+///
+/// Write errors are ignored so a closed pipe doesn't fail the run.
+pub struct NdjsonObserver<W> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> NdjsonObserver<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    /// The wrapped writer
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn emit(&self, event: ProgressEvent) {
+        let Ok(line) = serde_json::to_string(&event) else {
+            return;
+        };
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = writeln!(writer, "{}", line).and_then(|_| writer.flush());
+    }
+}
+
+impl<W: Write + Send> ProgressObserver for NdjsonObserver<W> {
+    fn on_run_started(&self, targets: &[String]) {
+        self.emit(ProgressEvent::RunStarted {
+            targets: targets.to_vec(),
+        });
+    }
+
+    fn on_target_started(&self, target_id: &str) {
+        self.emit(ProgressEvent::TargetStarted {
+            target_id: target_id.to_string(),
+        });
+    }
+
+    fn on_iteration_completed(&self, target_id: &str, sample: IterationSample) {
+        self.emit(ProgressEvent::IterationCompleted {
+            target_id: target_id.to_string(),
+            sample,
+        });
+    }
+
+    fn on_target_finished(&self, target_id: &str, outcome: &TargetOutcome) {
+        self.emit(ProgressEvent::target_finished(target_id, outcome));
+    }
+
+    fn on_run_finished(&self, summary: &RunSummary) {
+        self.emit(ProgressEvent::RunFinished { summary: *summary });
+    }
+}
+
+/// Phase of a run as seen by a [`StatusObserver`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunState {
+    #[default]
+    Idle,
+    Running,
+    Finished,
+}
+
+/// Snapshot of a run for a status endpoint
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct RunStatus {
+    pub state: RunState,
+    /// Targets scheduled, in order
+    pub targets: Vec<String>,
+    /// Targets currently running
+    pub running: Vec<String>,
+    pub completed: Vec<String>,
+    pub failed: Vec<String>,
+    /// Most recent sampled iteration of each running target
+    pub last_iteration: BTreeMap<String, IterationSample>,
+    /// Set once the run has finished
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<RunSummary>,
+}
+
+/// Keeps a [`RunStatus`] up to date for a status endpoint to serve
+///
+/// Clone the observer before registering it; every clone shares the status.
+#[derive(Clone, Default)]
+pub struct StatusObserver {
+    status: Arc<RwLock<RunStatus>>,
+}
+
+impl StatusObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current status of the observed run
+    pub fn snapshot(&self) -> RunStatus {
+        self.status.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn update(&self, change: impl FnOnce(&mut RunStatus)) {
+        change(&mut self.status.write().unwrap_or_else(|e| e.into_inner()));
+    }
+}
+
+impl ProgressObserver for StatusObserver {
+    fn on_run_started(&self, targets: &[String]) {
+        self.update(|status| {
+            *status = RunStatus {
+                state: RunState::Running,
+                targets: targets.to_vec(),
+                ..RunStatus::default()
+            }
+        });
+    }
+
+    fn on_target_started(&self, target_id: &str) {
+        self.update(|status| status.running.push(target_id.to_string()));
+    }
+
+    fn on_iteration_completed(&self, target_id: &str, sample: IterationSample) {
+        self.update(|status| {
+            status.last_iteration.insert(target_id.to_string(), sample);
+        });
+    }
+
+    fn on_target_finished(&self, target_id: &str, outcome: &TargetOutcome) {
+        self.update(|status| {
+            status.running.retain(|id| id != target_id);
+            status.last_iteration.remove(target_id);
+            match outcome {
+                TargetOutcome::Finished { .. } => status.completed.push(target_id.to_string()),
+                TargetOutcome::Failed { .. } => status.failed.push(target_id.to_string()),
+            }
+        });
+    }
+
+    fn on_run_finished(&self, summary: &RunSummary) {
+        self.update(|status| {
+            status.state = RunState::Finished;
+            status.summary = Some(*summary);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn test_ndjson_observer_writes_one_event_per_line() {
+        let observer = NdjsonObserver::new(Vec::new());
+        observer.on_run_started(&["a".to_string()]);
+        observer.on_iteration_completed(
+            "a",
+            IterationSample {
+                iteration: 0,
+                warmup: true,
+                elapsed_ms: 1.5,
+            },
+        );
+        observer.on_target_finished("a", &TargetOutcome::Failed { error: "boom".to_string() });
+
+        let output = String::from_utf8(observer.into_inner()).unwrap();
+        let events: Vec<Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["event"], "run_started");
+        assert_eq!(events[1]["event"], "iteration_completed");
+        assert_eq!(events[1]["elapsed_ms"], 1.5);
+        assert_eq!(events[2]["event"], "target_failed");
+        assert_eq!(events[2]["error"], "boom");
+    }
+
+    #[test]
+    fn test_status_observer_tracks_targets() {
+        let observer = StatusObserver::new();
+        observer.on_run_started(&["a".to_string(), "b".to_string()]);
+        observer.on_target_started("a");
+        assert_eq!(observer.snapshot().running, vec!["a"]);
+
+        observer.on_target_finished(
+            "a",
+            &TargetOutcome::Finished {
+                duration: Duration::from_millis(5),
+            },
+        );
+        observer.on_target_started("b");
+        observer.on_target_finished("b", &TargetOutcome::Failed { error: "boom".to_string() });
+
+        let status = observer.snapshot();
+        assert_eq!(status.state, RunState::Running);
+        assert!(status.running.is_empty());
+        assert_eq!(status.completed, vec!["a"]);
+        assert_eq!(status.failed, vec!["b"]);
+    }
+
+    #[test]
+    fn test_record_iteration_outside_a_runner_is_a_no_op() {
+        record_iteration(0, false, 1.0);
+    }
+}
@@ -0,0 +1,184 @@
+//! Registry of benchmark targets
+//!
+//! [`BenchRegistry::builtin`] starts from the targets in
+//! [`adapters::all_targets`](crate::adapters::all_targets); downstream crates
+//! [`register`](BenchRegistry::register) their own on top, or start from
+//! [`BenchRegistry::new`] to run only their own targets.
+
+use crate::adapters::{self, BenchConfig, BenchTarget};
+use crate::BenchmarkResult;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Error raised when registering a benchmark target
+#[derive(Debug, Error)]
+pub enum BenchRegistryError {
+    /// Another target already uses the id
+    #[error("benchmark target {id} is already registered")]
+    DuplicateId { id: String },
+}
+
+/// Set of benchmark targets with unique ids, in registration order
+#[derive(Clone, Default)]
+pub struct BenchRegistry {
+    targets: Vec<Arc<dyn BenchTarget>>,
+}
+
+impl BenchRegistry {
+    /// Registry with no targets
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry holding the built-in targets
+    pub fn builtin() -> Self {
+        Self {
+            targets: adapters::all_targets().into_iter().map(Arc::from).collect(),
+        }
+    }
+
+    /// Add `target`, rejecting an id that is already registered
+    pub fn register(&mut self, target: Box<dyn BenchTarget>) -> Result<(), BenchRegistryError> {
+        if self.contains(target.id()) {
+            return Err(BenchRegistryError::DuplicateId {
+                id: target.id().to_string(),
+            });
+        }
+        self.targets.push(Arc::from(target));
+        Ok(())
+    }
+
+    /// Add `target` and return the registry, for chaining
+    pub fn with_target(mut self, target: Box<dyn BenchTarget>) -> Result<Self, BenchRegistryError> {
+        self.register(target)?;
+        Ok(self)
+    }
+
+    /// Whether a target with `id` is registered
+    pub fn contains(&self, id: &str) -> bool {
+        self.targets.iter().any(|target| target.id() == id)
+    }
+
+    /// Ids of the registered targets, in registration order
+    pub fn ids(&self) -> Vec<&str> {
+        self.targets.iter().map(|target| target.id()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Every registered target, ready to hand to a runner
+    ///
+    /// The registry keeps its targets, so it can produce the set again.
+    pub fn targets(&self) -> Vec<Box<dyn BenchTarget>> {
+        self.targets
+            .iter()
+            .map(|target| Box::new(Arc::clone(target)) as Box<dyn BenchTarget>)
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for BenchRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BenchRegistry").field("targets", &self.ids()).finish()
+    }
+}
+
+#[async_trait]
+impl<T: BenchTarget + ?Sized> BenchTarget for Arc<T> {
+    fn id(&self) -> &str {
+        (**self).id()
+    }
+
+    fn description(&self) -> &str {
+        (**self).description()
+    }
+
+    async fn setup(&self) -> Result<()> {
+        (**self).setup().await
+    }
+
+    async fn teardown(&self) -> Result<()> {
+        (**self).teardown().await
+    }
+
+    async fn run(&self) -> Result<BenchmarkResult> {
+        (**self).run().await
+    }
+
+    async fn run_with_config(&self, cfg: &BenchConfig) -> Result<BenchmarkResult> {
+        (**self).run_with_config(cfg).await
+    }
+
+    fn run_after(&self) -> &[&str] {
+        (**self).run_after()
+    }
+
+    fn tags(&self) -> &[&str] {
+        (**self).tags()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{self, RunMode};
+    use serde_json::json;
+
+    struct MockTarget;
+
+    #[async_trait]
+    impl BenchTarget for MockTarget {
+        fn id(&self) -> &str {
+            "downstream_mock"
+        }
+
+        fn description(&self) -> &str {
+            "registered by a downstream crate"
+        }
+
+        async fn run(&self) -> Result<BenchmarkResult> {
+            Ok(BenchmarkResult::new(self.id().to_string(), json!({"avg_ms": 1.0})))
+        }
+    }
+
+    #[test]
+    fn test_register_rejects_duplicate_ids() {
+        let mut registry = BenchRegistry::builtin();
+        registry.register(Box::new(MockTarget)).unwrap();
+
+        let err = registry.register(Box::new(MockTarget)).unwrap_err();
+        assert_eq!(err.to_string(), "benchmark target downstream_mock is already registered");
+
+        let builtin = adapters::all_targets().into_iter().next().unwrap();
+        assert!(registry.register(builtin).is_err());
+        assert_eq!(registry.len(), adapters::all_targets().len() + 1);
+    }
+
+    #[test]
+    fn test_empty_registry_holds_only_user_targets() {
+        let registry = BenchRegistry::new().with_target(Box::new(MockTarget)).unwrap();
+        assert_eq!(registry.ids(), vec!["downstream_mock"]);
+    }
+
+    #[tokio::test]
+    async fn test_registered_target_runs_alongside_builtins() {
+        let registry = BenchRegistry::builtin().with_target(Box::new(MockTarget)).unwrap();
+
+        let run = runner::run_targets(registry.targets(), RunMode::Sequential).await;
+        assert!(run.is_success(), "failed targets: {:?}", run.failed_ids());
+        let ids: Vec<&str> = run.results.iter().map(|result| result.target_id.as_str()).collect();
+        assert_eq!(ids, registry.ids());
+        assert_eq!(ids.last(), Some(&"downstream_mock"));
+
+        // The registry still holds its targets after a run
+        assert_eq!(registry.targets().len(), registry.len());
+    }
+}
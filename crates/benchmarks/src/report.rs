@@ -0,0 +1,307 @@
+//! Standalone HTML reports of benchmark results
+//!
+//! [`generate_html`] renders the same content as the markdown summary: a
+//! results table with one row per timed operation, then a section per target
+//! with its latency table and raw metrics. The page has no external assets;
+//! styling and a small script that sorts tables by a clicked column header
+//! are inlined.
+
+use crate::markdown::{is_unstable, stat, stat_value, timed_operations};
+use crate::BenchmarkResult;
+use chrono::Utc;
+use schema_registry_core::metric_names::{self, MetricName};
+use serde_json::{Map, Value};
+
+/// Statistics shown per operation, with their column headings
+const COLUMNS: [(MetricName, &str); 7] = [
+    (metric_names::BENCH_AVG_MS, "avg (ms)"),
+    (metric_names::BENCH_P50_MS, "p50 (ms)"),
+    (metric_names::BENCH_P95_MS, "p95 (ms)"),
+    (metric_names::BENCH_P99_MS, "p99 (ms)"),
+    (metric_names::BENCH_MAX_MS, "max (ms)"),
+    (metric_names::BENCH_STD_DEV_MS, "std dev (ms)"),
+    (metric_names::BENCH_CV_PERCENT, "CV %"),
+];
+
+const STYLE: &str = "body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 72rem; padding: 0 1rem; color: #1f2328; }
+table { border-collapse: collapse; margin: 1rem 0; }
+th, td { border: 1px solid #d0d7de; padding: 0.3rem 0.6rem; }
+td.number { text-align: right; font-variant-numeric: tabular-nums; }
+table.sortable th { cursor: pointer; background: #f6f8fa; }
+th[aria-sort=ascending]::after { content: \" \\25B2\"; }
+th[aria-sort=descending]::after { content: \" \\25BC\"; }
+.unstable { color: #9a6700; }
+.partial { border-left: 4px solid #cf222e; padding: 0.5rem 1rem; background: #fff5f5; }
+pre { background: #f6f8fa; padding: 1rem; overflow-x: auto; }
+";
+
+// Sorts by a cell's data-value when it has one, by its text otherwise
+const SCRIPT: &str = "document.querySelectorAll(\"table.sortable th\").forEach(function (th) {
+  th.addEventListener(\"click\", function () {
+    var table = th.closest(\"table\");
+    var body = table.tBodies[0];
+    var index = Array.prototype.indexOf.call(th.parentNode.children, th);
+    var ascending = th.getAttribute(\"aria-sort\") !== \"ascending\";
+    table.querySelectorAll(\"th\").forEach(function (other) { other.removeAttribute(\"aria-sort\"); });
+    th.setAttribute(\"aria-sort\", ascending ? \"ascending\" : \"descending\");
+    var key = function (row) {
+      var cell = row.children[index];
+      var value = cell.getAttribute(\"data-value\");
+      return value === null ? cell.textContent : parseFloat(value);
+    };
+    var rows = Array.prototype.slice.call(body.rows);
+    rows.sort(function (a, b) {
+      var x = key(a), y = key(b);
+      var order = typeof x === \"number\" && typeof y === \"number\" ? x - y : String(x).localeCompare(String(y));
+      return ascending ? order : -order;
+    });
+    rows.forEach(function (row) { body.appendChild(row); });
+  });
+});
+";
+
+/// Options for [`generate_html`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlOptions {
+    /// Page title and top-level heading
+    pub title: String,
+    /// Mark the results as coming from a run cancelled part-way
+    pub partial: bool,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        Self {
+            title: "Schema Registry Benchmark Summary".to_string(),
+            partial: false,
+        }
+    }
+}
+
+impl HtmlOptions {
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Show a notice that not every target ran
+    pub fn with_partial(mut self, partial: bool) -> Self {
+        self.partial = partial;
+        self
+    }
+}
+
+/// `text` with HTML special characters escaped, safe in text and attribute values
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Id of a target's detail section: its id with anything but letters, digits, `-` and `_` replaced
+pub fn target_anchor(target_id: &str) -> String {
+    let slug: String = target_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect();
+    format!("target-{}", slug)
+}
+
+/// Numeric table cell, sortable by its value; `-` when the statistic is missing
+fn stat_cell(stats: &Map<String, Value>, name: &str) -> String {
+    match stat_value(stats, name) {
+        Some(value) => format!("<td class=\"number\" data-value=\"{}\">{}</td>", value, escape(&stat(stats, name))),
+        None => "<td class=\"number\">-</td>".to_string(),
+    }
+}
+
+fn header_row(leading: &[&str]) -> String {
+    let headings = leading.iter().copied().chain(COLUMNS.iter().map(|(_, heading)| *heading));
+    let cells: String = headings.map(|heading| format!("<th>{}</th>", escape(heading))).collect();
+    format!("<thead><tr>{}</tr></thead>\n", cells)
+}
+
+/// `<tr>` of an operation's statistics, after the given leading cells
+fn operation_row(leading: &[String], stats: &Map<String, Value>) -> String {
+    let class = if is_unstable(stats) { " class=\"unstable\"" } else { "" };
+    let mut row = format!("<tr{}>", class);
+    for cell in leading {
+        row.push_str(cell);
+    }
+    for (metric, _) in COLUMNS {
+        row.push_str(&stat_cell(stats, metric.name));
+    }
+    row.push_str("</tr>\n");
+    row
+}
+
+/// Results table: one row per timed operation, targets without any get an empty row
+fn results_table(results: &[BenchmarkResult]) -> String {
+    let mut table = String::from("<table class=\"sortable\" id=\"results\">\n");
+    table.push_str(&header_row(&["Target", "Operation"]));
+    table.push_str("<tbody>\n");
+    for result in results {
+        let target = format!(
+            "<td><a href=\"#{}\">{}</a></td>",
+            target_anchor(&result.target_id),
+            escape(&result.target_id)
+        );
+        let operations = timed_operations(&result.metrics);
+        if operations.is_empty() {
+            table.push_str(&format!("<tr>{}<td>-</td>{}</tr>\n", target, "<td class=\"number\">-</td>".repeat(COLUMNS.len())));
+        }
+        for (path, stats) in operations {
+            let operation = format!("<td>{}</td>", escape(if path.is_empty() { "-" } else { path.as_str() }));
+            table.push_str(&operation_row(&[target.clone(), operation], stats));
+        }
+    }
+    table.push_str("</tbody>\n</table>\n");
+    table
+}
+
+/// Detail section of one target
+fn target_section(result: &BenchmarkResult) -> String {
+    let mut section = format!(
+        "<section id=\"{}\">\n<h3>{}</h3>\n",
+        target_anchor(&result.target_id),
+        escape(&result.target_id)
+    );
+    section.push_str(&format!(
+        "<p><strong>Timestamp:</strong> {}</p>\n",
+        result.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
+    ));
+    if result.parallel {
+        section.push_str("<p><strong>Run mode:</strong> parallel (timings may include contention)</p>\n");
+    }
+
+    let operations = timed_operations(&result.metrics);
+    if !operations.is_empty() {
+        section.push_str("<table class=\"sortable\">\n");
+        section.push_str(&header_row(&["Operation"]));
+        section.push_str("<tbody>\n");
+        for (path, stats) in operations {
+            let operation = format!("<td>{}</td>", escape(if path.is_empty() { "-" } else { path.as_str() }));
+            section.push_str(&operation_row(&[operation], stats));
+        }
+        section.push_str("</tbody>\n</table>\n");
+    }
+
+    let metrics = serde_json::to_string_pretty(&result.metrics).unwrap_or_else(|_| "{}".to_string());
+    section.push_str(&format!("<pre><code>{}</code></pre>\n</section>\n", escape(&metrics)));
+    section
+}
+
+/// Self-contained HTML page summarizing `results`
+pub fn generate_html(results: &[BenchmarkResult], options: &HtmlOptions) -> String {
+    let title = escape(&options.title);
+    let mut html = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n", title, STYLE));
+    html.push_str(&format!("<h1>{}</h1>\n", title));
+    if options.partial {
+        html.push_str(
+            "<p class=\"partial\"><strong>PARTIAL RUN:</strong> cancelled before every target ran. Only targets listed below completed; compare with care.</p>\n",
+        );
+    }
+    html.push_str(&format!(
+        "<p><strong>Generated:</strong> {} &middot; <strong>Total Benchmarks:</strong> {}</p>\n",
+        Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
+        results.len()
+    ));
+
+    html.push_str("<h2>Benchmark Results</h2>\n");
+    if results.is_empty() {
+        html.push_str("<p>No benchmark results available.</p>\n");
+    } else {
+        html.push_str(&results_table(results));
+        html.push_str("<h2>Detailed Metrics</h2>\n");
+        for result in results {
+            html.push_str(&target_section(result));
+        }
+    }
+
+    html.push_str(&format!("<script>\n{}</script>\n</body>\n</html>\n", SCRIPT));
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Panics unless every opened tag in `html` is closed in order
+    fn assert_balanced(html: &str) {
+        const VOID: [&str; 2] = ["meta", "br"];
+        let mut open: Vec<String> = Vec::new();
+        let mut rest = html;
+        while let Some(start) = rest.find('<') {
+            let end = rest[start..].find('>').expect("unterminated tag") + start;
+            let tag = &rest[start + 1..end];
+            rest = &rest[end + 1..];
+            if tag.starts_with('!') {
+                continue;
+            }
+            let name: String = tag
+                .trim_start_matches('/')
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .collect();
+            if let Some(closing) = tag.strip_prefix('/') {
+                assert_eq!(open.pop().as_deref(), Some(closing), "mismatched </{closing}>");
+            } else if !VOID.contains(&name.as_str()) {
+                open.push(name);
+            }
+        }
+        assert!(open.is_empty(), "unclosed tags: {open:?}");
+    }
+
+    fn results() -> Vec<BenchmarkResult> {
+        vec![
+            BenchmarkResult::new(
+                "storage".to_string(),
+                json!({
+                    "write": {"avg_ms": 1.0, "p95_ms": 2.5, "cv_percent": 5.0},
+                    "read": {"avg_ms": {"value": 500.0, "unit": "microseconds"}, "p95_ms": 1.5, "cv_percent": 50.0}
+                }),
+            ),
+            BenchmarkResult::new("<b>odd & \"quoted\"</b>".to_string(), json!({"note": "</pre><script>"})),
+        ]
+    }
+
+    #[test]
+    fn test_html_is_well_formed_and_escaped() {
+        let html = generate_html(&results(), &HtmlOptions::default().with_title("Nightly <run>"));
+
+        assert_balanced(&html);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Nightly &lt;run&gt;</title>"));
+        assert!(html.contains("&lt;b&gt;odd &amp; &quot;quoted&quot;&lt;/b&gt;"));
+        assert!(html.contains("&lt;/pre&gt;&lt;script&gt;"));
+        // Self-contained: no external stylesheets, scripts or images
+        assert!(!html.contains("src=") && !html.contains("<link"));
+
+        assert_balanced(&generate_html(&[], &HtmlOptions::default().with_partial(true)));
+    }
+
+    #[test]
+    fn test_tables_are_sortable_by_value() {
+        let html = generate_html(&results(), &HtmlOptions::default());
+
+        assert!(html.contains("<table class=\"sortable\" id=\"results\">"));
+        assert!(html.contains("<td class=\"number\" data-value=\"2.5\">2.500</td>"));
+        // Measurements sort by their value in the column's unit
+        assert!(html.contains("<td class=\"number\" data-value=\"0.5\">0.500</td>"));
+        assert_eq!(html.matches("<tr class=\"unstable\">").count(), 2);
+        assert!(html.contains("<a href=\"#target-storage\">storage</a>"));
+        assert!(html.contains("<section id=\"target-storage\">"));
+        assert!(!html.contains("PARTIAL RUN"));
+    }
+}
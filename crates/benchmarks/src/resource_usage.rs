@@ -0,0 +1,124 @@
+//! CPU time and context switches of benchmark targets
+//!
+//! With the `rusage-metrics` feature the runner wraps each target's run in
+//! [`track`], which reads `getrusage(RUSAGE_SELF)` before and after the run.
+//! The deltas land in the result's metrics as a `resource_usage` object (see
+//! [`ResourceUsageMetrics`]).
+//!
+//! `getrusage` is only read on unix; elsewhere the object is left out. The
+//! counters are process-wide, so in parallel runs they include every target
+//! running at the same time, and on a multi-threaded runtime the CPU time of
+//! all worker threads adds up.
+
+use crate::metrics::ResourceUsageMetrics;
+use crate::BenchmarkResult;
+use std::future::Future;
+
+/// Cumulative resource usage of the process at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Snapshot {
+    pub user_cpu_ms: f64,
+    pub system_cpu_ms: f64,
+    pub voluntary_context_switches: u64,
+    pub involuntary_context_switches: u64,
+}
+
+impl Snapshot {
+    /// Usage between `self` and the later snapshot `after`
+    pub fn delta(&self, after: &Snapshot) -> ResourceUsageMetrics {
+        ResourceUsageMetrics {
+            user_cpu_ms: (after.user_cpu_ms - self.user_cpu_ms).max(0.0),
+            system_cpu_ms: (after.system_cpu_ms - self.system_cpu_ms).max(0.0),
+            voluntary_context_switches: after
+                .voluntary_context_switches
+                .saturating_sub(self.voluntary_context_switches),
+            involuntary_context_switches: after
+                .involuntary_context_switches
+                .saturating_sub(self.involuntary_context_switches),
+        }
+    }
+}
+
+/// Current resource usage of the process, `None` where `getrusage` isn't available
+pub fn snapshot() -> Option<Snapshot> {
+    #[cfg(unix)]
+    {
+        let mut usage = std::mem::MaybeUninit::<libc::rusage>::zeroed();
+        // SAFETY: `usage` is valid for writes and only read after getrusage succeeded
+        let usage = unsafe {
+            if libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) != 0 {
+                return None;
+            }
+            usage.assume_init()
+        };
+        let ms = |time: libc::timeval| time.tv_sec as f64 * 1000.0 + time.tv_usec as f64 / 1000.0;
+        Some(Snapshot {
+            user_cpu_ms: ms(usage.ru_utime),
+            system_cpu_ms: ms(usage.ru_stime),
+            voluntary_context_switches: u64::try_from(usage.ru_nvcsw).unwrap_or(0),
+            involuntary_context_switches: u64::try_from(usage.ru_nivcsw).unwrap_or(0),
+        })
+    }
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+/// Await `run`, recording the resources it used where they can be read
+pub async fn track<F: Future>(run: F) -> (F::Output, Option<ResourceUsageMetrics>) {
+    let before = snapshot();
+    let output = run.await;
+    let usage = before.zip(snapshot()).map(|(before, after)| before.delta(&after));
+    (output, usage)
+}
+
+/// Await a target's run, adding its resource usage to the result's metrics
+pub async fn with_resource_usage_metrics(
+    run: impl Future<Output = anyhow::Result<BenchmarkResult>>,
+) -> anyhow::Result<BenchmarkResult> {
+    let (result, usage) = track(run).await;
+    let result = result?;
+    Ok(match usage {
+        Some(usage) => result.with_resource_usage(usage),
+        None => result,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delta_never_goes_negative() {
+        let before = Snapshot {
+            user_cpu_ms: 10.0,
+            system_cpu_ms: 5.0,
+            voluntary_context_switches: 3,
+            involuntary_context_switches: 7,
+        };
+        let after = Snapshot {
+            user_cpu_ms: 25.5,
+            system_cpu_ms: 4.0,
+            voluntary_context_switches: 8,
+            involuntary_context_switches: 7,
+        };
+        let usage = before.delta(&after);
+        assert_eq!(usage.user_cpu_ms, 15.5);
+        assert_eq!(usage.system_cpu_ms, 0.0);
+        assert_eq!(usage.voluntary_context_switches, 5);
+        assert_eq!(usage.involuntary_context_switches, 0);
+    }
+
+    #[tokio::test]
+    async fn test_track_measures_cpu_where_available() {
+        let (sum, usage) = track(async { (0..5_000_000u64).map(std::hint::black_box).sum::<u64>() }).await;
+        assert!(sum > 0);
+
+        if cfg!(unix) {
+            assert!(usage.unwrap().cpu_ms() >= 0.0);
+        } else {
+            assert_eq!(usage, None);
+        }
+    }
+}
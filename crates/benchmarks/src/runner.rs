@@ -0,0 +1,1062 @@
+//! Benchmark execution
+//!
+//! Runs benchmark targets and keeps failures alongside the successful results
+//! so callers can tell a failed target from a missing one.
+//!
+//! Targets run sequentially by default, which keeps measurements free of
+//! contention. [`RunMode::Parallel`] trades that accuracy for wall-clock time;
+//! its results are flagged with [`BenchmarkResult::parallel`].
+//!
+//! Every target is bounded by [`BenchmarkRunConfig::target_timeout`]; a target
+//! that overruns is dropped, reported as [`BenchmarkRunError::TimedOut`], and
+//! the run continues with the remaining targets.
+//!
+//! Each target's [`setup`](BenchTarget::setup) runs before it and its
+//! [`teardown`](BenchTarget::teardown) after it, even when the run fails or
+//! times out. Setup time is reported as [`BenchmarkResult::setup_ms`] rather
+//! than in the metrics.
+//!
+//! The [`RunEnvironment`] is detected once, before any target starts, and
+//! stamped on every result of the run.
+//!
+//! Callers that want feedback while a run is in progress pass a callback to
+//! [`run_targets_with_progress`]; it receives a [`BenchProgress`] event when
+//! each target starts and when it finishes or fails.
+//!
+//! [`Runner`] bundles these options behind a builder and adds
+//! [`ProgressObserver`]s, which see the whole run: its start and end, each
+//! target, and sampled iterations (see [`crate::observer`]).
+//!
+//! [`run_targets_cancellable`] takes a [`CancellationToken`]. Cancelling it
+//! stops new targets from being launched; targets already running finish,
+//! and the run returns what it gathered with [`BenchmarkRun::cancelled`] set.
+
+use crate::adapters::storage_backend::StorageBackend;
+use crate::adapters::{BenchConfig, BenchTarget, SamplingMode};
+use crate::environment::RunEnvironment;
+use crate::sink::{self, ResultSink, SinkOutcome};
+use crate::stats::OutlierPolicy;
+use crate::observer::{IterationReporter, ObserverSet, ProgressObserver, RunSummary, TargetOutcome};
+use crate::BenchmarkResult;
+use futures::future;
+use futures::stream::{self, Stream, StreamExt};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+pub use tokio_util::sync::CancellationToken;
+
+/// Default bound on a single target's run
+pub const DEFAULT_TARGET_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Default minimum time between two iteration events of one target
+pub const DEFAULT_ITERATION_EVENT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How a set of targets is executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RunMode {
+    /// One target at a time, in registration order
+    #[default]
+    Sequential,
+    /// Up to `max_concurrency` targets at once; results are ordered by target id
+    Parallel { max_concurrency: usize },
+}
+
+/// Settings for a benchmark run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchmarkRunConfig {
+    /// How targets are scheduled
+    pub mode: RunMode,
+    /// Longest a single target may run before it is abandoned
+    pub target_timeout: Duration,
+    /// Warmup and measured iteration counts passed to every target
+    pub bench: BenchConfig,
+}
+
+impl BenchmarkRunConfig {
+    /// Set the execution mode
+    pub fn with_mode(mut self, mode: RunMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the per-target timeout
+    pub fn with_target_timeout(mut self, target_timeout: Duration) -> Self {
+        self.target_timeout = target_timeout;
+        self
+    }
+
+    /// Set the iteration counts passed to every target
+    pub fn with_bench_config(mut self, bench: BenchConfig) -> Self {
+        self.bench = bench;
+        self
+    }
+
+    /// Set how every target decides when it has collected enough samples
+    pub fn with_sampling(mut self, sampling: SamplingMode) -> Self {
+        self.bench.sampling = sampling;
+        self
+    }
+
+    /// Set the outlier policy every target applies before computing statistics
+    pub fn with_outliers(mut self, outliers: OutlierPolicy) -> Self {
+        self.bench.outliers = outliers;
+        self
+    }
+
+    /// Set where the storage benchmark persists schemas
+    pub fn with_storage_backend(mut self, backend: StorageBackend) -> Self {
+        self.bench.storage_backend = backend;
+        self
+    }
+}
+
+impl Default for BenchmarkRunConfig {
+    fn default() -> Self {
+        Self {
+            mode: RunMode::default(),
+            target_timeout: DEFAULT_TARGET_TIMEOUT,
+            bench: BenchConfig::default(),
+        }
+    }
+}
+
+impl From<RunMode> for BenchmarkRunConfig {
+    fn from(mode: RunMode) -> Self {
+        Self::default().with_mode(mode)
+    }
+}
+
+/// Error raised while running a single benchmark target
+#[derive(Debug, Error)]
+pub enum BenchmarkRunError {
+    /// The target's `run` returned an error
+    #[error("benchmark {target_id} failed: {source}")]
+    TargetFailed {
+        target_id: String,
+        source: anyhow::Error,
+    },
+
+    /// The target did not finish within the per-target timeout
+    #[error("benchmark {target_id} timed out after {elapsed:.1?}")]
+    TimedOut { target_id: String, elapsed: Duration },
+}
+
+impl BenchmarkRunError {
+    /// Id of the target that failed
+    pub fn target_id(&self) -> &str {
+        match self {
+            BenchmarkRunError::TargetFailed { target_id, .. }
+            | BenchmarkRunError::TimedOut { target_id, .. } => target_id,
+        }
+    }
+}
+
+/// Progress of a run, reported as each target starts and completes
+///
+/// In parallel mode events of different targets interleave; each target
+/// still reports `TargetStarted` before its `TargetFinished` or `TargetFailed`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BenchProgress {
+    /// The target began its warmup iterations
+    TargetStarted { id: String },
+    /// The target completed; `duration` includes warmup
+    TargetFinished { id: String, duration: Duration },
+    /// The target returned an error or timed out
+    TargetFailed { id: String, error: String },
+}
+
+/// Outcome of running a set of benchmark targets
+#[derive(Debug, Default)]
+pub struct BenchmarkRun {
+    /// Results of targets that completed
+    pub results: Vec<BenchmarkResult>,
+    /// Targets that failed, in execution order
+    pub failures: Vec<BenchmarkRunError>,
+    /// Whether the run was cancelled before every target was launched
+    pub cancelled: bool,
+}
+
+impl BenchmarkRun {
+    /// Whether every target completed
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Ids of the failed targets
+    pub fn failed_ids(&self) -> Vec<&str> {
+        self.failures.iter().map(|failure| failure.target_id()).collect()
+    }
+
+    /// Discard failures and keep only the successful results
+    pub fn into_results(self) -> Vec<BenchmarkResult> {
+        self.results
+    }
+
+    /// Order results and failures by target id
+    pub fn sort_by_id(&mut self) {
+        self.results.sort_by(|a, b| a.target_id.cmp(&b.target_id));
+        self.failures.sort_by(|a, b| a.target_id().cmp(b.target_id()));
+    }
+
+    /// Publish the results to every sink, returning an outcome per sink, see [`sink::publish_all`]
+    pub async fn publish(&self, sinks: &[&dyn ResultSink]) -> Vec<SinkOutcome> {
+        sink::publish_all(&self.results, sinks).await
+    }
+}
+
+/// Collects a stream of outcomes such as [`run_targets_stream`]
+impl Extend<Result<BenchmarkResult, BenchmarkRunError>> for BenchmarkRun {
+    fn extend<I: IntoIterator<Item = Result<BenchmarkResult, BenchmarkRunError>>>(&mut self, outcomes: I) {
+        for outcome in outcomes {
+            match outcome {
+                Ok(result) => self.results.push(result),
+                Err(failure) => self.failures.push(failure),
+            }
+        }
+    }
+}
+
+/// Observers that receive iteration events, and how often
+#[derive(Clone, Copy)]
+struct Observed<'a> {
+    observers: &'a Arc<ObserverSet>,
+    iteration_interval: Duration,
+}
+
+/// Run one target with the configured iterations, bounded by the target timeout
+///
+/// The target's setup runs first and its teardown afterwards, whatever the
+/// outcome; each phase is bounded by the target timeout on its own.
+async fn run_target<F>(
+    target: &dyn BenchTarget,
+    config: &BenchmarkRunConfig,
+    on_event: &F,
+    observed: Option<Observed<'_>>,
+) -> Result<BenchmarkResult, BenchmarkRunError>
+where
+    F: Fn(BenchProgress) + Sync,
+{
+    let id = target.id().to_string();
+    on_event(BenchProgress::TargetStarted { id: id.clone() });
+
+    let started = Instant::now();
+    let run = async {
+        target
+            .setup()
+            .await
+            .map_err(|err| anyhow::anyhow!("setup failed: {:#}", err))?;
+        let setup_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+        let run = target.run_with_config(&config.bench);
+        #[cfg(feature = "mem-metrics")]
+        let run = crate::memory::with_memory_metrics(run);
+        #[cfg(feature = "rusage-metrics")]
+        let run = crate::resource_usage::with_resource_usage_metrics(run);
+        let mut result = match observed {
+            Some(observed) => {
+                IterationReporter::new(&id, observed.observers.clone(), observed.iteration_interval)
+                    .scope(run)
+                    .await
+            }
+            None => run.await,
+        }?;
+        result.setup_ms = Some(setup_ms);
+        result.duration_ms = Some(started.elapsed().as_secs_f64() * 1000.0);
+        result.target_version = Some(target.version());
+        result.shape_fingerprint = Some(crate::shape::fingerprint(&result.metrics));
+        result.seed.get_or_insert(config.bench.seed);
+        Ok::<_, anyhow::Error>(result)
+    };
+    let outcome = tokio::time::timeout(config.target_timeout, run).await;
+    let elapsed = started.elapsed();
+    let teardown = match tokio::time::timeout(config.target_timeout, target.teardown()).await {
+        Ok(teardown) => teardown,
+        Err(_) => Err(anyhow::anyhow!("teardown timed out after {:.1?}", config.target_timeout)),
+    };
+
+    let outcome = match (outcome, teardown) {
+        (Ok(Ok(result)), Ok(())) => Ok(result),
+        (Ok(Ok(_)), Err(source)) => Err(BenchmarkRunError::TargetFailed {
+            target_id: id.clone(),
+            source: anyhow::anyhow!("teardown failed: {:#}", source),
+        }),
+        // A failed run is the error worth reporting; teardown failing too is likely a consequence
+        (Ok(Err(source)), _) => Err(BenchmarkRunError::TargetFailed {
+            target_id: id.clone(),
+            source,
+        }),
+        (Err(_), _) => Err(BenchmarkRunError::TimedOut {
+            target_id: id.clone(),
+            elapsed,
+        }),
+    };
+
+    on_event(match &outcome {
+        Ok(_) => BenchProgress::TargetFinished {
+            id,
+            duration: started.elapsed(),
+        },
+        Err(failure) => BenchProgress::TargetFailed {
+            id,
+            error: failure.to_string(),
+        },
+    });
+    outcome
+}
+
+/// Run `targets` with the given mode or config, collecting successes and failures
+pub async fn run_targets(targets: Vec<Box<dyn BenchTarget>>, config: impl Into<BenchmarkRunConfig>) -> BenchmarkRun {
+    run_targets_with_progress(targets, config, |_| {}).await
+}
+
+/// Like [`run_targets`], reporting each target's progress to `on_event`
+///
+/// `on_event` is called from the task driving the run, so it should return
+/// quickly.
+pub async fn run_targets_with_progress<F>(
+    targets: Vec<Box<dyn BenchTarget>>,
+    config: impl Into<BenchmarkRunConfig>,
+    on_event: F,
+) -> BenchmarkRun
+where
+    F: Fn(BenchProgress) + Sync,
+{
+    execute(targets, config.into(), &on_event, None, None).await
+}
+
+/// Like [`run_targets_with_progress`], launching no new targets once `cancel` fires
+///
+/// Targets already running when the token is cancelled run to completion
+/// (or their timeout). The returned run holds everything gathered so far and
+/// has [`BenchmarkRun::cancelled`] set if any target was skipped.
+pub async fn run_targets_cancellable<F>(
+    targets: Vec<Box<dyn BenchTarget>>,
+    config: impl Into<BenchmarkRunConfig>,
+    cancel: &CancellationToken,
+    on_event: F,
+) -> BenchmarkRun
+where
+    F: Fn(BenchProgress) + Sync,
+{
+    execute(targets, config.into(), &on_event, Some(cancel), None).await
+}
+
+/// Run `targets` with the given mode or config, yielding each outcome as its target finishes
+///
+/// Sequential runs yield in target order; parallel runs yield in completion
+/// order. Collecting the stream gives the same [`BenchmarkRun`] as
+/// [`run_targets`], except that parallel results are not sorted by id.
+pub fn run_targets_stream(
+    targets: Vec<Box<dyn BenchTarget>>,
+    config: impl Into<BenchmarkRunConfig>,
+) -> impl Stream<Item = Result<BenchmarkResult, BenchmarkRunError>> {
+    launch(targets, config.into(), |_: BenchProgress| {}, None, None)
+}
+
+/// Outcomes of `targets` as they finish, stamped with the run environment
+fn launch<'a, F>(
+    targets: Vec<Box<dyn BenchTarget>>,
+    config: BenchmarkRunConfig,
+    on_event: F,
+    cancel: Option<&'a CancellationToken>,
+    observed: Option<Observed<'a>>,
+) -> impl Stream<Item = Result<BenchmarkResult, BenchmarkRunError>> + 'a
+where
+    F: Fn(BenchProgress) + Sync + Copy + 'a,
+{
+    let (parallel, concurrency) = match config.mode {
+        RunMode::Sequential => (false, 1),
+        RunMode::Parallel { max_concurrency } => (true, max_concurrency.max(1)),
+    };
+
+    // Detection runs git, rustc and hostname, so it runs on the blocking pool,
+    // and finishes before the first target starts so it never stalls a timed one
+    let detected = async {
+        tokio::task::spawn_blocking(RunEnvironment::detect)
+            .await
+            .unwrap_or_default()
+    };
+
+    stream::once(async move {
+        let environment = detected.await;
+        // The buffer pulls the next target only when a slot frees up, so the
+        // cancellation check runs at launch time rather than up front
+        stream::iter(targets)
+            .take_while(move |_| future::ready(!cancel.is_some_and(CancellationToken::is_cancelled)))
+            .map(move |target| async move {
+                run_target(target.as_ref(), &config, &on_event, observed).await
+            })
+            .buffer_unordered(concurrency)
+            .map(move |outcome| {
+                outcome.map(|mut result| {
+                    result.parallel = parallel;
+                    result.environment = Some(environment.clone());
+                    result
+                })
+            })
+    })
+    .flatten()
+}
+
+async fn execute<F>(
+    targets: Vec<Box<dyn BenchTarget>>,
+    config: BenchmarkRunConfig,
+    on_event: &F,
+    cancel: Option<&CancellationToken>,
+    observed: Option<Observed<'_>>,
+) -> BenchmarkRun
+where
+    F: Fn(BenchProgress) + Sync,
+{
+    let total = targets.len();
+    let mut run: BenchmarkRun = launch(targets, config, on_event, cancel, observed).collect().await;
+    // Every launched target yields exactly one outcome
+    run.cancelled = run.results.len() + run.failures.len() < total;
+    if let RunMode::Parallel { .. } = config.mode {
+        // Completion order is arbitrary; report in a stable order
+        run.sort_by_id();
+    }
+    run
+}
+
+/// Benchmark runner with progress observers, built by [`Runner::builder`]
+pub struct Runner {
+    config: BenchmarkRunConfig,
+    observers: Arc<ObserverSet>,
+    iteration_interval: Duration,
+    cancel: Option<CancellationToken>,
+}
+
+/// Builder for [`Runner`]
+pub struct RunnerBuilder {
+    config: BenchmarkRunConfig,
+    observers: ObserverSet,
+    iteration_interval: Duration,
+    cancel: Option<CancellationToken>,
+}
+
+impl RunnerBuilder {
+    /// Set the mode, timeout and iteration counts
+    pub fn config(mut self, config: impl Into<BenchmarkRunConfig>) -> Self {
+        self.config = config.into();
+        self
+    }
+
+    /// Add an observer; observers are called in the order they were added
+    pub fn observer(mut self, observer: impl ProgressObserver + 'static) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Report at most one iteration per target every `interval`
+    ///
+    /// [`Duration::ZERO`] reports every iteration.
+    pub fn iteration_event_interval(mut self, interval: Duration) -> Self {
+        self.iteration_interval = interval;
+        self
+    }
+
+    /// Stop launching targets once `cancel` fires
+    pub fn cancel_token(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    pub fn build(self) -> Runner {
+        Runner {
+            config: self.config,
+            observers: Arc::new(self.observers),
+            iteration_interval: self.iteration_interval,
+            cancel: self.cancel,
+        }
+    }
+}
+
+impl Runner {
+    /// Builder with the default config, no observers and no cancellation
+    pub fn builder() -> RunnerBuilder {
+        RunnerBuilder {
+            config: BenchmarkRunConfig::default(),
+            observers: ObserverSet::default(),
+            iteration_interval: DEFAULT_ITERATION_EVENT_INTERVAL,
+            cancel: None,
+        }
+    }
+
+    /// Run `targets`, reporting to the observers
+    pub async fn run(&self, targets: Vec<Box<dyn BenchTarget>>) -> BenchmarkRun {
+        let started = Instant::now();
+        self.notify_started(&targets);
+        let forward = |event| self.forward(event);
+        let run = execute(targets, self.config, &forward, self.cancel.as_ref(), self.observed()).await;
+
+        self.notify_finished(RunSummary {
+            completed: run.results.len(),
+            failed: run.failures.len(),
+            cancelled: run.cancelled,
+            duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+        });
+        run
+    }
+
+    /// Run `targets`, yielding each outcome as its target finishes
+    ///
+    /// Like [`run`](Self::run), but parallel outcomes arrive in completion
+    /// order rather than sorted by id. Observers hear of the run's start when
+    /// the stream is created and of its end when the stream is exhausted.
+    pub fn run_stream(
+        &self,
+        targets: Vec<Box<dyn BenchTarget>>,
+    ) -> impl Stream<Item = Result<BenchmarkResult, BenchmarkRunError>> + '_ {
+        let started = Instant::now();
+        let total = targets.len();
+        self.notify_started(&targets);
+        let forward = move |event| self.forward(event);
+        let outcomes = Box::pin(launch(targets, self.config, forward, self.cancel.as_ref(), self.observed()));
+
+        stream::unfold((outcomes, 0, 0), move |(mut outcomes, completed, failed)| async move {
+            match outcomes.next().await {
+                Some(Ok(result)) => Some((Ok(result), (outcomes, completed + 1, failed))),
+                Some(Err(failure)) => Some((Err(failure), (outcomes, completed, failed + 1))),
+                None => {
+                    self.notify_finished(RunSummary {
+                        completed,
+                        failed,
+                        cancelled: completed + failed < total,
+                        duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+                    });
+                    None
+                }
+            }
+        })
+    }
+
+    fn notify_started(&self, targets: &[Box<dyn BenchTarget>]) {
+        let ids: Vec<String> = targets.iter().map(|target| target.id().to_string()).collect();
+        self.observers.notify(None, |observer| observer.on_run_started(&ids));
+    }
+
+    fn notify_finished(&self, summary: RunSummary) {
+        self.observers.notify(None, |observer| observer.on_run_finished(&summary));
+    }
+
+    /// Pass a progress event on to the observers
+    fn forward(&self, event: BenchProgress) {
+        match event {
+            BenchProgress::TargetStarted { id } => {
+                self.observers.notify(Some(&id), |observer| observer.on_target_started(&id))
+            }
+            BenchProgress::TargetFinished { id, duration } => {
+                let outcome = TargetOutcome::Finished { duration };
+                self.observers.notify(Some(&id), |observer| observer.on_target_finished(&id, &outcome))
+            }
+            BenchProgress::TargetFailed { id, error } => {
+                let outcome = TargetOutcome::Failed { error };
+                self.observers.notify(Some(&id), |observer| observer.on_target_finished(&id, &outcome))
+            }
+        }
+    }
+
+    /// Iteration reporting, when anyone is listening
+    fn observed(&self) -> Option<Observed<'_>> {
+        (!self.observers.is_empty()).then_some(Observed {
+            observers: &self.observers,
+            iteration_interval: self.iteration_interval,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{bail, Result};
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    struct FixedTarget {
+        id: &'static str,
+        fail: bool,
+        delay_ms: u64,
+    }
+
+    fn target(id: &'static str, fail: bool, delay_ms: u64) -> Box<dyn BenchTarget> {
+        Box::new(FixedTarget { id, fail, delay_ms })
+    }
+
+    #[async_trait]
+    impl BenchTarget for FixedTarget {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn description(&self) -> &str {
+            "fixed outcome"
+        }
+
+        async fn run(&self) -> Result<BenchmarkResult> {
+            tokio::time::sleep(std::time::Duration::from_millis(self.delay_ms)).await;
+            if self.fail {
+                bail!("fixture drift");
+            }
+            Ok(BenchmarkResult::new(self.id.to_string(), json!({})))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_targets_keeps_failures() {
+        let run = run_targets(vec![target("ok", false, 0), target("broken", true, 0)], RunMode::Sequential).await;
+
+        assert!(!run.is_success());
+        assert_eq!(run.results.len(), 1);
+        assert!(!run.results[0].parallel);
+        assert_eq!(run.failed_ids(), vec!["broken"]);
+        assert_eq!(run.failures[0].to_string(), "benchmark broken failed: fixture drift");
+        assert_eq!(run.results[0].environment.as_ref().map(|env| env.os.is_empty()), Some(false));
+    }
+
+    #[tokio::test]
+    async fn test_parallel_results_are_ordered_by_id() {
+        // Slowest first, so completion order differs from id order
+        let targets = vec![
+            target("c_slow", false, 200),
+            target("a_medium", false, 100),
+            target("d_broken", true, 50),
+            target("b_fast", false, 0),
+        ];
+
+        let started = std::time::Instant::now();
+        let run = run_targets(targets, RunMode::Parallel { max_concurrency: 4 }).await;
+        // Bounded by the slowest target (200ms), not the sum (350ms)
+        assert!(started.elapsed() < std::time::Duration::from_millis(300));
+
+        let ids: Vec<_> = run.results.iter().map(|r| r.target_id.as_str()).collect();
+        assert_eq!(ids, vec!["a_medium", "b_fast", "c_slow"]);
+        assert!(run.results.iter().all(|r| r.parallel));
+        assert_eq!(run.failed_ids(), vec!["d_broken"]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_yields_each_outcome_as_it_finishes() {
+        let targets = vec![target("slow", false, 200), target("broken", true, 0), target("fast", false, 0)];
+        let started = std::time::Instant::now();
+        let mut outcomes = Box::pin(run_targets_stream(targets, RunMode::Parallel { max_concurrency: 3 }));
+
+        // The fast targets arrive well before the slow one finishes
+        let first = outcomes.next().await.unwrap();
+        assert!(started.elapsed() < std::time::Duration::from_millis(150));
+        assert_ne!(first.as_ref().map(|result| result.target_id.as_str()).ok(), Some("slow"));
+
+        let rest: Vec<_> = outcomes.collect().await;
+        assert_eq!(rest.len(), 2);
+        let last = rest[1].as_ref().unwrap();
+        assert_eq!(last.target_id, "slow");
+        assert!(last.parallel);
+        assert!(last.environment.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_collected_stream_matches_run_targets() {
+        let targets = || vec![target("ok", false, 0), target("broken", true, 0)];
+        let streamed: BenchmarkRun = run_targets_stream(targets(), RunMode::Sequential).collect().await;
+        let run = run_targets(targets(), RunMode::Sequential).await;
+
+        assert_eq!(streamed.results.len(), run.results.len());
+        assert_eq!(streamed.failed_ids(), run.failed_ids());
+        assert!(!streamed.cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_target_does_not_block_the_run() {
+        let config = BenchmarkRunConfig::default().with_target_timeout(Duration::from_millis(50));
+        let targets = vec![target("hangs", false, 60_000), target("ok", false, 0)];
+
+        let run = run_targets(targets, config).await;
+
+        assert_eq!(run.results.len(), 1);
+        assert_eq!(run.results[0].target_id, "ok");
+        assert_eq!(run.failed_ids(), vec!["hangs"]);
+        match &run.failures[0] {
+            BenchmarkRunError::TimedOut { elapsed, .. } => {
+                assert!(*elapsed >= Duration::from_millis(50));
+                assert!(*elapsed < Duration::from_secs(5));
+            }
+            other => panic!("expected timeout, got {other}"),
+        }
+    }
+
+    struct EchoConfigTarget;
+
+    #[async_trait]
+    impl BenchTarget for EchoConfigTarget {
+        fn id(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "reports the iteration counts it was given"
+        }
+
+        async fn run(&self) -> Result<BenchmarkResult> {
+            self.run_with_config(&BenchConfig::default()).await
+        }
+
+        async fn run_with_config(&self, cfg: &BenchConfig) -> Result<BenchmarkResult> {
+            let metrics = json!({"warmup_iterations": cfg.warmup_iterations, "iterations": cfg.measured_iterations});
+            Ok(BenchmarkResult::new("echo".to_string(), metrics))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bench_config_reaches_targets() {
+        let config = BenchmarkRunConfig::default().with_bench_config(BenchConfig::new(0, 25).with_seed(9));
+        let run = run_targets(vec![Box::new(EchoConfigTarget) as Box<dyn BenchTarget>], config).await;
+
+        assert_eq!(run.results[0].metrics["warmup_iterations"], 0);
+        assert_eq!(run.results[0].metrics["iterations"], 25);
+        assert_eq!(run.results[0].seed, Some(9));
+    }
+
+    /// Target logging its hook calls; fails the phase named in `fail`
+    struct HookedTarget {
+        fail: Option<&'static str>,
+        run_ms: u64,
+        calls: Arc<std::sync::Mutex<Vec<&'static str>>>,
+    }
+
+    impl HookedTarget {
+        fn phase(&self, name: &'static str) -> Result<()> {
+            self.calls.lock().unwrap().push(name);
+            if self.fail == Some(name) {
+                bail!("{} broke", name);
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl BenchTarget for HookedTarget {
+        fn id(&self) -> &str {
+            "hooked"
+        }
+
+        fn description(&self) -> &str {
+            "logs setup, run and teardown"
+        }
+
+        async fn setup(&self) -> Result<()> {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.phase("setup")
+        }
+
+        async fn teardown(&self) -> Result<()> {
+            self.phase("teardown")
+        }
+
+        async fn run(&self) -> Result<BenchmarkResult> {
+            self.phase("run")?;
+            tokio::time::sleep(Duration::from_millis(self.run_ms)).await;
+            Ok(BenchmarkResult::new("hooked".to_string(), json!({})))
+        }
+    }
+
+    async fn run_hooked(fail: Option<&'static str>, run_ms: u64) -> (BenchmarkRun, Vec<&'static str>) {
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let target = HookedTarget {
+            fail,
+            run_ms,
+            calls: calls.clone(),
+        };
+        let config = BenchmarkRunConfig::default().with_target_timeout(Duration::from_millis(200));
+        let run = run_targets(vec![Box::new(target) as Box<dyn BenchTarget>], config).await;
+        let calls = calls.lock().unwrap().clone();
+        (run, calls)
+    }
+
+    #[tokio::test]
+    async fn test_setup_and_teardown_bracket_every_run() {
+        let (run, calls) = run_hooked(None, 0).await;
+        assert_eq!(calls, vec!["setup", "run", "teardown"]);
+        assert!(run.results[0].setup_ms.unwrap() >= 20.0);
+        assert!(run.results[0].duration_ms.unwrap() >= run.results[0].setup_ms.unwrap());
+
+        let (run, calls) = run_hooked(Some("run"), 0).await;
+        assert_eq!(calls, vec!["setup", "run", "teardown"]);
+        assert_eq!(run.failures[0].to_string(), "benchmark hooked failed: run broke");
+
+        let (run, calls) = run_hooked(Some("setup"), 0).await;
+        assert_eq!(calls, vec!["setup", "teardown"]);
+        assert_eq!(run.failures[0].to_string(), "benchmark hooked failed: setup failed: setup broke");
+
+        let (run, calls) = run_hooked(Some("teardown"), 0).await;
+        assert_eq!(calls, vec!["setup", "run", "teardown"]);
+        assert_eq!(run.failures[0].to_string(), "benchmark hooked failed: teardown failed: teardown broke");
+
+        let (run, calls) = run_hooked(None, 60_000).await;
+        assert_eq!(calls, vec!["setup", "run", "teardown"]);
+        assert!(matches!(run.failures[0], BenchmarkRunError::TimedOut { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_progress_events_bracket_each_target() {
+        let events = std::sync::Mutex::new(Vec::new());
+        let targets = vec![target("ok", false, 0), target("broken", true, 0)];
+
+        let run = run_targets_with_progress(targets, RunMode::Sequential, |event| events.lock().unwrap().push(event)).await;
+
+        assert_eq!(run.results.len(), 1);
+        let events = events.into_inner().unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0], BenchProgress::TargetStarted { id: "ok".to_string() });
+        assert!(matches!(&events[1], BenchProgress::TargetFinished { id, .. } if id == "ok"));
+        assert_eq!(events[2], BenchProgress::TargetStarted { id: "broken".to_string() });
+        assert_eq!(
+            events[3],
+            BenchProgress::TargetFailed {
+                id: "broken".to_string(),
+                error: "benchmark broken failed: fixture drift".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parallel_progress_reports_every_target() {
+        let events = std::sync::Mutex::new(Vec::new());
+        let targets = vec![target("slow", false, 50), target("fast", false, 0)];
+
+        run_targets_with_progress(targets, RunMode::Parallel { max_concurrency: 2 }, |event| {
+            events.lock().unwrap().push(event)
+        })
+        .await;
+
+        let events = events.into_inner().unwrap();
+        let finished: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                BenchProgress::TargetFinished { id, .. } => Some(id.as_str()),
+                _ => None,
+            })
+            .collect();
+        // Reported in completion order, not id order
+        assert_eq!(finished, vec!["fast", "slow"]);
+        assert_eq!(events.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_run_keeps_finished_results() {
+        let cancel = CancellationToken::new();
+        let targets = vec![target("first", false, 0), target("second", false, 0), target("third", false, 0)];
+
+        // Cancel while the first target is reported finished
+        let run = run_targets_cancellable(targets, RunMode::Sequential, &cancel, |event| {
+            if let BenchProgress::TargetFinished { .. } = event {
+                cancel.cancel();
+            }
+        })
+        .await;
+
+        assert!(run.cancelled);
+        assert_eq!(run.results.len(), 1);
+        assert_eq!(run.results[0].target_id, "first");
+        assert!(run.is_success());
+    }
+
+    #[tokio::test]
+    async fn test_parallel_cancellation_lets_running_targets_finish() {
+        let cancel = CancellationToken::new();
+        let targets = vec![target("a", false, 50), target("b", false, 50), target("c", false, 0)];
+
+        let run = run_targets_cancellable(targets, RunMode::Parallel { max_concurrency: 2 }, &cancel, |event| {
+            if let BenchProgress::TargetStarted { id } = event {
+                if id == "b" {
+                    cancel.cancel();
+                }
+            }
+        })
+        .await;
+
+        assert!(run.cancelled);
+        let ids: Vec<_> = run.results.iter().map(|r| r.target_id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn test_uncancelled_run_is_not_marked() {
+        let cancel = CancellationToken::new();
+        let run = run_targets_cancellable(vec![target("ok", false, 0)], RunMode::Sequential, &cancel, |_| {}).await;
+        assert!(!run.cancelled);
+
+        cancel.cancel();
+        let run = run_targets_cancellable(vec![target("ok", false, 0)], RunMode::Sequential, &cancel, |_| {}).await;
+        assert!(run.cancelled);
+        assert!(run.results.is_empty());
+    }
+
+    /// Target reporting `iterations` iterations, failing after `fail_after` if set
+    struct IteratingTarget {
+        id: &'static str,
+        iterations: usize,
+        fail_after: Option<usize>,
+    }
+
+    #[async_trait]
+    impl BenchTarget for IteratingTarget {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn description(&self) -> &str {
+            "reports its iterations"
+        }
+
+        async fn run(&self) -> Result<BenchmarkResult> {
+            for i in 0..self.iterations {
+                if self.fail_after == Some(i) {
+                    bail!("fixture drift");
+                }
+                crate::observer::record_iteration(i, i == 0, 1.0);
+            }
+            Ok(BenchmarkResult::new(self.id.to_string(), json!({})))
+        }
+    }
+
+    /// Observer recording every event as a line of text
+    #[derive(Default)]
+    struct Recorder(std::sync::Mutex<Vec<String>>);
+
+    impl Recorder {
+        fn push(&self, event: String) {
+            self.0.lock().unwrap().push(event);
+        }
+
+        fn events(&self) -> Vec<String> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    impl ProgressObserver for Recorder {
+        fn on_run_started(&self, targets: &[String]) {
+            self.push(format!("run_started {}", targets.join(",")));
+        }
+
+        fn on_target_started(&self, target_id: &str) {
+            self.push(format!("target_started {}", target_id));
+        }
+
+        fn on_iteration_completed(&self, target_id: &str, sample: crate::observer::IterationSample) {
+            self.push(format!("iteration {} {} warmup={}", target_id, sample.iteration, sample.warmup));
+        }
+
+        fn on_target_finished(&self, target_id: &str, outcome: &TargetOutcome) {
+            match outcome {
+                TargetOutcome::Finished { .. } => self.push(format!("target_finished {}", target_id)),
+                TargetOutcome::Failed { error } => self.push(format!("target_failed {}: {}", target_id, error)),
+            }
+        }
+
+        fn on_run_finished(&self, summary: &RunSummary) {
+            self.push(format!(
+                "run_finished completed={} failed={} cancelled={}",
+                summary.completed, summary.failed, summary.cancelled
+            ));
+        }
+    }
+
+    fn iterating(id: &'static str, iterations: usize, fail_after: Option<usize>) -> Box<dyn BenchTarget> {
+        Box::new(IteratingTarget {
+            id,
+            iterations,
+            fail_after,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_observer_sees_exact_event_sequence() {
+        let recorder = Arc::new(Recorder::default());
+        let runner = Runner::builder()
+            .observer(recorder.clone())
+            .iteration_event_interval(Duration::ZERO)
+            .build();
+
+        let run = runner.run(vec![iterating("ok", 2, None), iterating("broken", 3, Some(1))]).await;
+
+        assert_eq!(run.results.len(), 1);
+        assert_eq!(
+            recorder.events(),
+            vec![
+                "run_started ok,broken",
+                "target_started ok",
+                "iteration ok 0 warmup=true",
+                "iteration ok 1 warmup=false",
+                "target_finished ok",
+                "target_started broken",
+                "iteration broken 0 warmup=true",
+                "target_failed broken: benchmark broken failed: fixture drift",
+                "run_finished completed=1 failed=1 cancelled=false",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_stream_reports_to_observers_when_exhausted() {
+        let recorder = Arc::new(Recorder::default());
+        let runner = Runner::builder().observer(recorder.clone()).build();
+
+        let mut outcomes = Box::pin(runner.run_stream(vec![target("ok", false, 0), target("broken", true, 0)]));
+        assert_eq!(outcomes.next().await.unwrap().unwrap().target_id, "ok");
+        assert!(!recorder.events().iter().any(|event| event.starts_with("run_finished")));
+
+        assert_eq!(outcomes.next().await.unwrap().unwrap_err().target_id(), "broken");
+        assert!(outcomes.next().await.is_none());
+        assert_eq!(
+            recorder.events().last().map(String::as_str),
+            Some("run_finished completed=1 failed=1 cancelled=false")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_iteration_events_are_throttled() {
+        let recorder = Arc::new(Recorder::default());
+        let runner = Runner::builder()
+            .observer(recorder.clone())
+            .iteration_event_interval(Duration::from_secs(3600))
+            .build();
+
+        runner.run(vec![iterating("many", 1000, None)]).await;
+
+        let iterations = recorder.events().iter().filter(|e| e.starts_with("iteration")).count();
+        assert_eq!(iterations, 1);
+    }
+
+    struct PanickingObserver;
+
+    impl ProgressObserver for PanickingObserver {
+        fn on_target_started(&self, _target_id: &str) {
+            panic!("observer bug");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_panicking_observer_is_isolated() {
+        let recorder = Arc::new(Recorder::default());
+        let runner = Runner::builder()
+            .observer(PanickingObserver)
+            .observer(recorder.clone())
+            .build();
+
+        let run = runner.run(vec![target("a", false, 0), target("b", false, 0)]).await;
+
+        assert!(run.is_success());
+        assert_eq!(run.results.len(), 2);
+        let events = recorder.events();
+        assert_eq!(events.first().map(String::as_str), Some("run_started a,b"));
+        assert_eq!(events.iter().filter(|e| e.starts_with("target_finished")).count(), 2);
+    }
+
+    #[test]
+    fn test_run_config_from_mode_keeps_default_timeout() {
+        let config = BenchmarkRunConfig::from(RunMode::Parallel { max_concurrency: 2 });
+        assert_eq!(config.mode, RunMode::Parallel { max_concurrency: 2 });
+        assert_eq!(config.target_timeout, DEFAULT_TARGET_TIMEOUT);
+    }
+}
@@ -0,0 +1,370 @@
+//! Allocation-free sample recording for target iteration loops
+//!
+//! Bookkeeping around a timed operation must not allocate, or a `Vec`
+//! doubling its capacity or a label `String` built per iteration ends up in
+//! the measurements themselves. Targets therefore record through a
+//! [`SamplePool`]:
+//!
+//! - series are looked up by label once, before the loop, and then referred
+//!   to by [`SeriesId`]; a label seen in an earlier run is matched against the
+//!   pool's copy instead of being stored again;
+//! - [`begin_run`](SamplePool::begin_run) reserves every buffer for the run's
+//!   measured iterations, and buffers keep the capacity of the largest
+//!   previous run;
+//! - summaries sort into one scratch buffer owned by the pool, after the
+//!   run's [`OutlierPolicy`] drops extreme samples.
+//!
+//! Invariant: once `begin_run` has returned and the run's series are looked
+//! up, [`SamplePool::record`] and
+//! [`record_iteration`](crate::observer::record_iteration) without observers
+//! perform no heap allocation. Debug builds assert it on every `record`, and
+//! a test with a counting global allocator records a full run of each
+//! built-in adapter's series, by their `SERIES` labels, through one pool.
+//! The adapters' own loops are not run under the counter: the operations
+//! they time allocate, and so may building an iteration's input before its
+//! timer starts, but labels and keys are prepared before the loop.
+
+use crate::metrics::OperationMetrics;
+use crate::stats::{OutlierPolicy, Summary};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError};
+
+/// Handle of a series in a [`SamplePool`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeriesId(usize);
+
+#[derive(Debug)]
+struct Series {
+    label: Box<str>,
+    values: Vec<f64>,
+}
+
+/// Reusable sample buffers of one target
+#[derive(Debug, Default)]
+pub struct SamplePool {
+    series: Vec<Series>,
+    /// Samples each buffer can hold without growing in the current run
+    capacity: usize,
+    /// Sorted copy used when summarising
+    scratch: Vec<f64>,
+    /// Samples [`metrics`](Self::metrics) leaves out
+    outliers: OutlierPolicy,
+}
+
+impl SamplePool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clear every series and reserve room for `measured_iterations` samples
+    pub fn begin_run(&mut self, measured_iterations: usize) {
+        let largest = self.series.iter().map(|series| series.values.len()).max().unwrap_or(0);
+        self.capacity = measured_iterations.max(largest);
+        for series in &mut self.series {
+            series.values.clear();
+            series.values.reserve(self.capacity);
+        }
+        self.scratch.reserve(self.capacity);
+    }
+
+    /// Leave out the samples `outliers` rejects from [`metrics`](Self::metrics)
+    ///
+    /// Recorded values are kept as they are; see [`values`](Self::values).
+    pub fn set_outlier_policy(&mut self, outliers: OutlierPolicy) {
+        self.outliers = outliers;
+    }
+
+    /// Make room for `additional` more samples in every series
+    ///
+    /// For targets that only learn how many samples a run takes as it goes,
+    /// e.g. one pass at a time. Call between measurement loops, not inside one.
+    pub fn reserve(&mut self, additional: usize) {
+        for series in &mut self.series {
+            series.values.reserve(additional);
+        }
+    }
+
+    /// Series named `label`, added on first use
+    ///
+    /// Call before the iteration loop: adding a series allocates.
+    pub fn series(&mut self, label: &str) -> SeriesId {
+        self.find_or_add(|existing| existing == label, || label.into())
+    }
+
+    /// Series labelled with `parts` joined by `/`, e.g. `small/zstd/encode`
+    ///
+    /// Finding an existing series doesn't build the label.
+    pub fn series_path(&mut self, parts: &[&str]) -> SeriesId {
+        self.find_or_add(|existing| path_matches(existing, parts), || parts.join("/").into())
+    }
+
+    fn find_or_add(&mut self, matches: impl Fn(&str) -> bool, label: impl FnOnce() -> Box<str>) -> SeriesId {
+        if let Some(index) = self.series.iter().position(|series| matches(&series.label)) {
+            return SeriesId(index);
+        }
+        self.series.push(Series {
+            label: label(),
+            values: Vec::with_capacity(self.capacity),
+        });
+        SeriesId(self.series.len() - 1)
+    }
+
+    /// Append a sample to `id`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if the series is full, i.e. the run records more
+    /// samples than [`begin_run`](Self::begin_run) reserved.
+    pub fn record(&mut self, id: SeriesId, value_ms: f64) {
+        let series = &mut self.series[id.0];
+        debug_assert!(
+            series.values.len() < series.values.capacity(),
+            "sample series '{}' is full ({} samples); recording would allocate inside the measurement loop",
+            series.label,
+            series.values.capacity()
+        );
+        series.values.push(value_ms);
+    }
+
+    /// Samples recorded for `id` in the current run
+    pub fn values(&self, id: SeriesId) -> &[f64] {
+        &self.series[id.0].values
+    }
+
+    /// Label of `id`
+    pub fn label(&self, id: SeriesId) -> &str {
+        &self.series[id.0].label
+    }
+
+    /// Metrics of the samples recorded for `id`, after outlier rejection
+    pub fn metrics(&mut self, id: SeriesId) -> OperationMetrics {
+        Summary::from_samples_with(&self.series[id.0].values, self.outliers, &mut self.scratch).into()
+    }
+
+    /// Samples recorded in the current run by series label, skipping empty series
+    pub fn raw_samples(&self) -> BTreeMap<String, Vec<f64>> {
+        self.series
+            .iter()
+            .filter(|series| !series.values.is_empty())
+            .map(|series| (series.label.to_string(), series.values.clone()))
+            .collect()
+    }
+}
+
+fn path_matches(label: &str, parts: &[&str]) -> bool {
+    let mut rest = label;
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            match rest.strip_prefix('/') {
+                Some(after) => rest = after,
+                None => return false,
+            }
+        }
+        match rest.strip_prefix(*part) {
+            Some(after) => rest = after,
+            None => return false,
+        }
+    }
+    rest.is_empty()
+}
+
+/// A target's [`SamplePool`], kept between runs
+///
+/// Targets run through `&self`, so the pool is taken for the duration of a
+/// run and put back afterwards. A run that fails before putting it back only
+/// costs the next run its warm buffers.
+#[derive(Debug, Default)]
+pub struct PoolSlot(Mutex<SamplePool>);
+
+impl PoolSlot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take the pool, leaving an empty one
+    pub fn take(&self) -> SamplePool {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Keep `pool` for the next run
+    pub fn restore(&self, pool: SamplePool) {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner) = pool;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{compatibility, registration_log, storage, validation, BenchConfig};
+    use crate::observer::record_iteration;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    /// Counts allocations made by the current thread
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn count_allocation() {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+    }
+
+    // SAFETY: every call is forwarded unchanged to the system allocator
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            count_allocation();
+            System.alloc(layout)
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            count_allocation();
+            System.alloc_zeroed(layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            count_allocation();
+            System.realloc(ptr, layout, new_size)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn allocations_during(f: impl FnOnce()) -> usize {
+        let before = ALLOCATIONS.with(Cell::get);
+        f();
+        ALLOCATIONS.with(Cell::get) - before
+    }
+
+    /// Run the bookkeeping of a target's measurement loop with `series` labels
+    fn measurement_loop_allocations(pool: &mut SamplePool, series: &[&str], cfg: BenchConfig) -> usize {
+        pool.begin_run(cfg.measured_iterations);
+        let ids: Vec<SeriesId> = series.iter().map(|label| pool.series(label)).collect();
+        allocations_during(|| {
+            for i in 0..cfg.total_iterations() {
+                record_iteration(i, cfg.is_warmup(i), 1.0);
+                if cfg.is_warmup(i) {
+                    continue;
+                }
+                for (n, id) in ids.iter().enumerate() {
+                    pool.record(*id, (i + n) as f64);
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_steady_state_recording_does_not_allocate() {
+        let cfg = BenchConfig::new(5, 500);
+        for series in [
+            &storage::SERIES[..],
+            &validation::SERIES,
+            &compatibility::SERIES,
+            &registration_log::SERIES,
+        ] {
+            let mut pool = SamplePool::new();
+            assert_eq!(measurement_loop_allocations(&mut pool, series, cfg), 0, "series {:?}", series);
+            // A second, longer run reuses the same pool
+            let longer = BenchConfig::new(5, 1000);
+            assert_eq!(measurement_loop_allocations(&mut pool, series, longer), 0, "series {:?}", series);
+        }
+
+        let mut pool = SamplePool::new();
+        pool.begin_run(cfg.measured_iterations);
+        let codec_series = [pool.series_path(&["small", "zstd", "encode"]), pool.series_path(&["small", "zstd", "decode"])];
+        let allocations = allocations_during(|| {
+            for i in 0..cfg.measured_iterations {
+                for id in codec_series {
+                    pool.record(id, i as f64);
+                }
+            }
+        });
+        assert_eq!(allocations, 0);
+    }
+
+    #[test]
+    fn test_buffers_keep_capacity_and_labels_between_runs() {
+        let mut pool = SamplePool::new();
+        pool.begin_run(100);
+        let read = pool.series("read");
+        let encode = pool.series_path(&["large", "gzip", "encode"]);
+        for i in 0..100 {
+            pool.record(read, i as f64);
+        }
+
+        pool.begin_run(10);
+        assert!(pool.values(read).is_empty());
+        let allocations = allocations_during(|| {
+            assert_eq!(pool.series("read"), read);
+            assert_eq!(pool.series_path(&["large", "gzip", "encode"]), encode);
+            for i in 0..100 {
+                pool.record(read, i as f64);
+            }
+        });
+        assert_eq!(allocations, 0);
+        assert_eq!(pool.label(encode), "large/gzip/encode");
+        assert_ne!(pool.series_path(&["large", "gzip"]), encode);
+    }
+
+    #[test]
+    fn test_metrics_match_unpooled_summary() {
+        let samples = [3.0, 1.0, 100.0, 2.0, 4.0];
+        let mut pool = SamplePool::new();
+        pool.begin_run(samples.len());
+        let id = pool.series("write");
+        for sample in samples {
+            pool.record(id, sample);
+        }
+        assert_eq!(pool.metrics(id), OperationMetrics::from_samples(&samples));
+    }
+
+    #[test]
+    fn test_outlier_policy_applies_to_metrics_not_values() {
+        let samples = [1.0, 1.1, 0.9, 1.0, 100.0, 1.05, 0.95, 1.0];
+        let mut pool = SamplePool::new();
+        pool.begin_run(samples.len());
+        pool.set_outlier_policy(OutlierPolicy::Iqr { k: 1.5 });
+        let id = pool.series("read");
+        for sample in samples {
+            pool.record(id, sample);
+        }
+
+        let metrics = pool.metrics(id);
+        assert_eq!(metrics.outliers_rejected, 1);
+        assert!(metrics.avg_ms < 1.1);
+        assert_eq!(pool.values(id), &samples);
+        assert_eq!(pool.raw_samples()["read"], samples);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "sample series 'write' is full")]
+    fn test_recording_past_reserved_capacity_panics_in_debug() {
+        let mut pool = SamplePool::new();
+        pool.begin_run(1);
+        let id = pool.series("write");
+        let capacity = pool.series[id.0].values.capacity();
+        for i in 0..=capacity {
+            pool.record(id, i as f64);
+        }
+    }
+
+    #[test]
+    fn test_pool_slot_keeps_pool_between_runs() {
+        let slot = PoolSlot::new();
+        let mut pool = slot.take();
+        pool.begin_run(8);
+        let id = pool.series("read");
+        slot.restore(pool);
+
+        let mut pool = slot.take();
+        assert_eq!(pool.series("read"), id);
+        assert!(slot.take().series.is_empty());
+    }
+}
@@ -0,0 +1,339 @@
+//! Metric shape fingerprints
+//!
+//! The shape of a result's metrics is every leaf key path (`write.p95_ms`,
+//! `memory.rss_peak_bytes`, ...) with the JSON type found there; values are
+//! ignored. The runner stores a fingerprint of the shape in
+//! [`BenchmarkResult::shape_fingerprint`], alongside the target's
+//! [`version`](crate::adapters::BenchTarget::version).
+//!
+//! Dashboards and comparisons key on metric paths, so a renamed or retyped
+//! key silently breaks them. [`compare`] lists, per target, the paths added,
+//! removed or retyped between a baseline and a current run, and flags drift
+//! the target didn't announce by bumping its version.
+//!
+//! Some keys depend on the run configuration rather than the target, e.g.
+//! `sample_time_ms` in time-based sampling or `memory` with the `mem-metrics`
+//! feature, so compare runs made with the same options.
+
+use crate::shard::fnv1a;
+use crate::units;
+use crate::BenchmarkResult;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// JSON type of a metric leaf
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueKind {
+    Null,
+    Bool,
+    /// A number, bare or as a `{value, unit}` measurement
+    ///
+    /// Units are left out so fingerprints stay the same when a file is
+    /// upgraded to recorded units; comparisons convert between units anyway.
+    Number,
+    String,
+    Array,
+    /// An empty object; non-empty objects are walked into
+    Object,
+}
+
+impl ValueKind {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(_) => Self::Bool,
+            Value::Number(_) => Self::Number,
+            Value::String(_) => Self::String,
+            Value::Array(_) => Self::Array,
+            Value::Object(_) => Self::Object,
+        }
+    }
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Null => "null",
+            Self::Bool => "bool",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+        })
+    }
+}
+
+/// Leaf key paths of a metrics value and their types
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetricsShape {
+    paths: BTreeMap<String, ValueKind>,
+}
+
+impl MetricsShape {
+    /// Shape of `metrics`, with nested keys joined by `.`
+    ///
+    /// A metrics value that isn't an object is a single leaf at the empty path.
+    pub fn of(metrics: &Value) -> Self {
+        let mut shape = Self::default();
+        shape.walk(String::new(), metrics);
+        shape
+    }
+
+    fn walk(&mut self, path: String, value: &Value) {
+        match value {
+            // A measurement is a number with its unit; see `ValueKind::Number`
+            _ if units::is_measurement(value) => {
+                self.paths.insert(path, ValueKind::Number);
+            }
+            Value::Object(map) if !map.is_empty() => {
+                for (key, value) in map {
+                    let child = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    self.walk(child, value);
+                }
+            }
+            _ => {
+                self.paths.insert(path, ValueKind::of(value));
+            }
+        }
+    }
+
+    /// Leaf paths in sorted order with their types
+    pub fn paths(&self) -> impl Iterator<Item = (&str, ValueKind)> {
+        self.paths.iter().map(|(path, kind)| (path.as_str(), *kind))
+    }
+
+    /// Stable hex fingerprint of the sorted `path:type` lines
+    ///
+    /// Uses FNV-1a so the fingerprint is the same on every platform and Rust
+    /// version. It detects change; it is not a cryptographic digest.
+    pub fn fingerprint(&self) -> String {
+        let lines: String = self.paths().map(|(path, kind)| format!("{path}:{kind}\n")).collect();
+        format!("{:016x}", fnv1a(lines.as_bytes()))
+    }
+}
+
+/// Fingerprint of the shape of `metrics`; see [`MetricsShape::fingerprint`]
+pub fn fingerprint(metrics: &Value) -> String {
+    MetricsShape::of(metrics).fingerprint()
+}
+
+/// A path whose type changed between runs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Retyped {
+    pub path: String,
+    pub baseline: ValueKind,
+    pub current: ValueKind,
+}
+
+/// How one target's metric shape differs between a baseline and a current run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShapeDrift {
+    pub target_id: String,
+    /// Paths only in the current run
+    pub added: Vec<String>,
+    /// Paths only in the baseline
+    pub removed: Vec<String>,
+    pub retyped: Vec<Retyped>,
+    /// Target version recorded in the baseline, absent in older files
+    pub baseline_version: Option<u32>,
+    /// Target version recorded in the current run
+    pub current_version: Option<u32>,
+}
+
+impl ShapeDrift {
+    /// Whether the target bumped its version along with the shape change
+    ///
+    /// Drift between results that don't both record a version is unannounced.
+    pub fn is_announced(&self) -> bool {
+        matches!(
+            (self.baseline_version, self.current_version),
+            (Some(baseline), Some(current)) if baseline != current
+        )
+    }
+}
+
+impl fmt::Display for ShapeDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.target_id)?;
+        for path in &self.added {
+            write!(f, "\n  + {path}")?;
+        }
+        for path in &self.removed {
+            write!(f, "\n  - {path}")?;
+        }
+        for retyped in &self.retyped {
+            write!(f, "\n  ~ {} ({} -> {})", retyped.path, retyped.baseline, retyped.current)?;
+        }
+        Ok(())
+    }
+}
+
+/// Shape of `result`, taking the fingerprint from the result when it has one
+fn shape_of(result: &BenchmarkResult) -> (MetricsShape, String) {
+    let shape = MetricsShape::of(&result.metrics);
+    let fingerprint = result.shape_fingerprint.clone().unwrap_or_else(|| shape.fingerprint());
+    (shape, fingerprint)
+}
+
+/// Targets in both `baseline` and `current` whose metric shape changed, in current order
+///
+/// Targets missing from either side are not reported. Results written before
+/// fingerprints were recorded are fingerprinted from their metrics.
+pub fn compare(baseline: &[BenchmarkResult], current: &[BenchmarkResult]) -> Vec<ShapeDrift> {
+    let baseline: HashMap<&str, &BenchmarkResult> = baseline.iter().map(|r| (r.target_id.as_str(), r)).collect();
+
+    current
+        .iter()
+        .filter_map(|current| {
+            let baseline = baseline.get(current.target_id.as_str())?;
+            let (old, old_fingerprint) = shape_of(baseline);
+            let (new, new_fingerprint) = shape_of(current);
+            if old_fingerprint == new_fingerprint {
+                return None;
+            }
+
+            let added = new.paths.keys().filter(|p| !old.paths.contains_key(*p)).cloned().collect();
+            let removed = old.paths.keys().filter(|p| !new.paths.contains_key(*p)).cloned().collect();
+            let retyped = old
+                .paths()
+                .filter_map(|(path, was)| {
+                    let now = *new.paths.get(path)?;
+                    (now != was).then(|| Retyped {
+                        path: path.to_string(),
+                        baseline: was,
+                        current: now,
+                    })
+                })
+                .collect();
+            Some(ShapeDrift {
+                target_id: current.target_id.clone(),
+                added,
+                removed,
+                retyped,
+                baseline_version: baseline.target_version,
+                current_version: current.target_version,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::BenchTarget;
+    use crate::runner::{run_targets, RunMode};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    struct MockTarget {
+        latency_key: &'static str,
+        version: u32,
+    }
+
+    #[async_trait]
+    impl BenchTarget for MockTarget {
+        fn id(&self) -> &str {
+            "mock"
+        }
+
+        fn description(&self) -> &str {
+            "fixed metrics"
+        }
+
+        fn version(&self) -> u32 {
+            self.version
+        }
+
+        async fn run(&self) -> Result<BenchmarkResult> {
+            let mut write = serde_json::Map::new();
+            write.insert(self.latency_key.to_string(), json!(1.5));
+            write.insert("count".to_string(), json!(10));
+            Ok(BenchmarkResult::new("mock".to_string(), json!({ "write": write })))
+        }
+    }
+
+    async fn run_mock(latency_key: &'static str, version: u32) -> Vec<BenchmarkResult> {
+        let run = run_targets(vec![Box::new(MockTarget { latency_key, version })], RunMode::Sequential).await;
+        assert!(run.is_success());
+        run.results
+    }
+
+    #[test]
+    fn test_shape_ignores_values() {
+        let a = json!({"write": {"avg_ms": 1.0, "count": 10}, "label": "a"});
+        let b = json!({"label": "b", "write": {"count": 99, "avg_ms": 7.5}});
+        assert_eq!(fingerprint(&a), fingerprint(&b));
+
+        let shape = MetricsShape::of(&a);
+        let paths: Vec<_> = shape.paths().collect();
+        assert_eq!(
+            paths,
+            vec![
+                ("label", ValueKind::String),
+                ("write.avg_ms", ValueKind::Number),
+                ("write.count", ValueKind::Number),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_measurements_are_number_leaves() {
+        let bare = json!({"write": {"avg_ms": 1.0}});
+        let measured = json!({"write": {"avg_ms": {"value": 1.0, "unit": "milliseconds"}}});
+        assert_eq!(fingerprint(&bare), fingerprint(&measured));
+    }
+
+    #[test]
+    fn test_retyped_path_changes_fingerprint() {
+        let a = json!({"write": {"avg_ms": 1.0}});
+        let b = json!({"write": {"avg_ms": "1.0"}});
+        assert_ne!(fingerprint(&a), fingerprint(&b));
+
+        let drift = compare(
+            &[BenchmarkResult::new("t".to_string(), a)],
+            &[BenchmarkResult::new("t".to_string(), b)],
+        );
+        assert_eq!(
+            drift[0].retyped,
+            vec![Retyped {
+                path: "write.avg_ms".to_string(),
+                baseline: ValueKind::Number,
+                current: ValueKind::String,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_runner_records_fingerprint_and_version() {
+        let results = run_mock("avg_ms", 3).await;
+        assert_eq!(results[0].target_version, Some(3));
+        assert_eq!(results[0].shape_fingerprint.as_deref(), Some(fingerprint(&results[0].metrics).as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_renamed_key_is_unannounced_drift() {
+        let baseline = run_mock("avg_ms", 1).await;
+        let current = run_mock("mean_ms", 1).await;
+        assert_ne!(baseline[0].shape_fingerprint, current[0].shape_fingerprint);
+
+        let drift = compare(&baseline, &current);
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].target_id, "mock");
+        assert_eq!(drift[0].added, vec!["write.mean_ms"]);
+        assert_eq!(drift[0].removed, vec!["write.avg_ms"]);
+        assert!(drift[0].retyped.is_empty());
+        assert!(!drift[0].is_announced());
+
+        let bumped = run_mock("mean_ms", 2).await;
+        assert!(compare(&baseline, &bumped)[0].is_announced());
+        assert!(compare(&baseline, &run_mock("avg_ms", 1).await).is_empty());
+    }
+}
@@ -0,0 +1,503 @@
+//! Sharded benchmark runs
+//!
+//! CI splits the benchmark targets across parallel jobs with
+//! `--shard <index>/<count>`. Assignment is deterministic: a target goes to
+//! `fnv1a(key) % count + 1`, where the key is the smallest id among the
+//! targets it is connected to through [`BenchTarget::run_after`]. Targets
+//! that must run in order therefore always share a shard, and
+//! [`Shard::select`] orders them behind their dependencies.
+//!
+//! Each shard writes a [`RunFile`] whose results are tagged with the shard
+//! index; [`merge`] combines the shard files into one run, checking that they
+//! belong to the same run id, profile and seed and that no target was run
+//! twice.
+
+use crate::adapters::BenchTarget;
+use crate::filter::BenchmarkFilterError;
+use crate::runner::BenchmarkRun;
+use crate::BenchmarkResult;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Error raised while sharding targets or merging shard outputs
+#[derive(Debug, Error)]
+pub enum ShardError {
+    /// A `--shard` value that is not `<index>/<count>` with `1 <= index <= count`
+    #[error("invalid shard '{spec}': {reason}")]
+    InvalidSpec { spec: String, reason: String },
+
+    /// Targets whose `run_after` constraints form a cycle
+    #[error("run_after cycle between benchmark targets: {}", targets.join(", "))]
+    DependencyCycle { targets: Vec<String> },
+
+    /// `merge` was given no inputs
+    #[error("no shard outputs to merge")]
+    NoInputs,
+
+    /// Shard outputs come from different runs
+    #[error("shard outputs disagree on {field}: '{expected}' vs '{found}'")]
+    Mismatch {
+        field: &'static str,
+        expected: String,
+        found: String,
+    },
+
+    /// An input to `merge` was not produced by a sharded run
+    #[error("input {input} of run {run_id} is not a shard output")]
+    NotSharded { input: usize, run_id: String },
+
+    /// Shard outputs were split into different shard counts
+    #[error("shard outputs disagree on the shard count: {expected} vs {found}")]
+    ShardCountMismatch { expected: usize, found: usize },
+
+    /// The same shard appears in more than one input
+    #[error("shard {shard} appears more than once")]
+    DuplicateShard { shard: Shard },
+
+    /// The same target was reported by more than one shard
+    #[error("benchmark {target_id} appears in more than one shard")]
+    DuplicateTarget { target_id: String },
+
+    /// Target selection failed before sharding
+    #[error(transparent)]
+    Filter(#[from] BenchmarkFilterError),
+}
+
+/// One of `count` shards, numbered from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Shard {
+    pub index: usize,
+    pub count: usize,
+}
+
+impl Shard {
+    /// Shard `index` of `count`, rejecting `index` outside `1..=count`
+    pub fn new(index: usize, count: usize) -> Result<Self, ShardError> {
+        check_bounds(index, count).map_err(|reason| ShardError::InvalidSpec {
+            spec: format!("{}/{}", index, count),
+            reason: reason.to_string(),
+        })?;
+        Ok(Self { index, count })
+    }
+
+    /// Keep the targets assigned to this shard, ordered so every target runs
+    /// after the targets named in its `run_after`
+    ///
+    /// Dependencies on targets outside `targets` (e.g. filtered out) are ignored.
+    pub fn select(&self, targets: Vec<Box<dyn BenchTarget>>) -> Result<Vec<Box<dyn BenchTarget>>, ShardError> {
+        let keys = group_keys(&targets);
+        let selected = targets
+            .into_iter()
+            .zip(keys)
+            .filter(|(_, key)| shard_index(key, self.count) == self.index)
+            .map(|(target, _)| target)
+            .collect();
+        order_by_dependencies(selected)
+    }
+}
+
+impl fmt::Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
+}
+
+impl FromStr for Shard {
+    type Err = ShardError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| ShardError::InvalidSpec {
+            spec: spec.to_string(),
+            reason: reason.to_string(),
+        };
+        let (index, count) = spec
+            .trim()
+            .split_once('/')
+            .ok_or_else(|| invalid("expected <index>/<count>, e.g. 2/4"))?;
+        let index = index.parse().map_err(|_| invalid("shard index is not a number"))?;
+        let count = count.parse().map_err(|_| invalid("shard count is not a number"))?;
+        check_bounds(index, count).map_err(invalid)?;
+        Ok(Self { index, count })
+    }
+}
+
+fn check_bounds(index: usize, count: usize) -> Result<(), &'static str> {
+    if count == 0 {
+        return Err("shard count must be at least 1");
+    }
+    if index == 0 || index > count {
+        return Err("shard index must be between 1 and the shard count");
+    }
+    Ok(())
+}
+
+/// Shard (1-based) that `key` is assigned to among `count` shards
+///
+/// Uses FNV-1a so the assignment is the same on every platform and Rust
+/// version, unlike `std`'s default hasher.
+pub fn shard_index(key: &str, count: usize) -> usize {
+    (fnv1a(key.as_bytes()) % count.max(1) as u64) as usize + 1
+}
+
+/// 64-bit FNV-1a hash of `bytes`
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes
+        .iter()
+        .fold(OFFSET_BASIS, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME))
+}
+
+/// Sharding key of each target: the smallest id in its `run_after` group
+fn group_keys(targets: &[Box<dyn BenchTarget>]) -> Vec<String> {
+    let position: HashMap<&str, usize> = targets.iter().enumerate().map(|(i, t)| (t.id(), i)).collect();
+    let mut parent: Vec<usize> = (0..targets.len()).collect();
+
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for (i, target) in targets.iter().enumerate() {
+        for dependency in target.run_after() {
+            if let Some(&j) = position.get(dependency) {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                parent[a] = b;
+            }
+        }
+    }
+
+    let mut smallest: HashMap<usize, &str> = HashMap::new();
+    for (i, target) in targets.iter().enumerate() {
+        let key = smallest.entry(root(&mut parent, i)).or_insert(target.id());
+        if target.id() < *key {
+            *key = target.id();
+        }
+    }
+    (0..targets.len())
+        .map(|i| smallest[&root(&mut parent, i)].to_string())
+        .collect()
+}
+
+/// Order `targets` behind their `run_after` dependencies, otherwise keeping
+/// registration order
+fn order_by_dependencies(targets: Vec<Box<dyn BenchTarget>>) -> Result<Vec<Box<dyn BenchTarget>>, ShardError> {
+    let ids: Vec<String> = targets.iter().map(|t| t.id().to_string()).collect();
+    let dependencies: Vec<Vec<usize>> = targets
+        .iter()
+        .map(|t| t.run_after().iter().filter_map(|d| ids.iter().position(|id| id == d)).collect())
+        .collect();
+
+    let mut slots: Vec<Option<Box<dyn BenchTarget>>> = targets.into_iter().map(Some).collect();
+    let mut done = vec![false; slots.len()];
+    let mut ordered = Vec::with_capacity(slots.len());
+    while ordered.len() < slots.len() {
+        let ready = (0..slots.len()).find(|&i| !done[i] && dependencies[i].iter().all(|&d| done[d]));
+        let Some(i) = ready else {
+            return Err(ShardError::DependencyCycle {
+                targets: (0..ids.len()).filter(|&i| !done[i]).map(|i| ids[i].clone()).collect(),
+            });
+        };
+        done[i] = true;
+        ordered.extend(slots[i].take());
+    }
+    Ok(ordered)
+}
+
+/// Targets one shard ran
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardLayout {
+    pub shard: Shard,
+    /// Ids of the targets assigned to the shard, including failed ones
+    pub targets: Vec<String>,
+}
+
+/// A benchmark run as written to disk by sharded runs and `benchmark merge`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunFile {
+    /// Identifier shared by every shard of one CI run
+    pub run_id: String,
+    /// Name of the benchmark profile the run used
+    pub profile: String,
+    /// Seed the run was started with
+    pub seed: u64,
+    /// One layout for a shard's output, one per merged shard for a merged
+    /// run, none for an unsharded run
+    #[serde(default)]
+    pub shards: Vec<ShardLayout>,
+    pub results: Vec<BenchmarkResult>,
+    /// Ids of targets that failed or timed out
+    #[serde(default)]
+    pub failed: Vec<String>,
+}
+
+impl RunFile {
+    /// An empty run
+    pub fn new(run_id: impl Into<String>, profile: impl Into<String>, seed: u64) -> Self {
+        Self {
+            run_id: run_id.into(),
+            profile: profile.into(),
+            seed,
+            shards: Vec::new(),
+            results: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+
+    /// Add the outcome of `run`, tagging its results with `shard`
+    pub fn record(mut self, shard: Option<Shard>, run: BenchmarkRun) -> Self {
+        let failed: Vec<String> = run.failed_ids().into_iter().map(str::to_string).collect();
+        if let Some(shard) = shard {
+            let targets: BTreeSet<String> = run
+                .results
+                .iter()
+                .map(|result| result.target_id.clone())
+                .chain(failed.iter().cloned())
+                .collect();
+            self.shards.push(ShardLayout {
+                shard,
+                targets: targets.into_iter().collect(),
+            });
+        }
+        self.results.extend(run.results.into_iter().map(|mut result| {
+            result.shard = shard.map(|shard| shard.index);
+            result
+        }));
+        self.failed.extend(failed);
+        self
+    }
+
+    /// Shard count of a sharded run
+    pub fn shard_count(&self) -> Option<usize> {
+        self.shards.first().map(|layout| layout.shard.count)
+    }
+
+    /// Shards of the split that are not part of this run
+    pub fn missing_shards(&self) -> Vec<Shard> {
+        let Some(count) = self.shard_count() else {
+            return Vec::new();
+        };
+        (1..=count)
+            .filter(|index| !self.shards.iter().any(|layout| layout.shard.index == *index))
+            .map(|index| Shard { index, count })
+            .collect()
+    }
+}
+
+/// Combine shard outputs of one run into a single run
+///
+/// Inputs may be individual shard outputs or earlier merges. Missing shards
+/// are allowed (a CI job may have failed) and show up in
+/// [`RunFile::missing_shards`].
+pub fn merge(inputs: Vec<RunFile>) -> Result<RunFile, ShardError> {
+    let first = inputs.first().ok_or(ShardError::NoInputs)?;
+    let mut merged = RunFile::new(first.run_id.clone(), first.profile.clone(), first.seed);
+    let mut seen_targets = HashSet::new();
+
+    for (input_index, input) in inputs.into_iter().enumerate() {
+        check_same("run_id", &merged.run_id, &input.run_id)?;
+        check_same("profile", &merged.profile, &input.profile)?;
+        check_same("seed", &merged.seed, &input.seed)?;
+        if input.shards.is_empty() {
+            return Err(ShardError::NotSharded {
+                input: input_index + 1,
+                run_id: input.run_id,
+            });
+        }
+
+        for layout in input.shards {
+            if let Some(expected) = merged.shard_count() {
+                if layout.shard.count != expected {
+                    return Err(ShardError::ShardCountMismatch {
+                        expected,
+                        found: layout.shard.count,
+                    });
+                }
+            }
+            if merged.shards.iter().any(|seen| seen.shard == layout.shard) {
+                return Err(ShardError::DuplicateShard { shard: layout.shard });
+            }
+            merged.shards.push(layout);
+        }
+
+        let target_ids = input.results.iter().map(|result| &result.target_id).chain(&input.failed);
+        for target_id in target_ids {
+            if !seen_targets.insert(target_id.clone()) {
+                return Err(ShardError::DuplicateTarget {
+                    target_id: target_id.clone(),
+                });
+            }
+        }
+        merged.results.extend(input.results);
+        merged.failed.extend(input.failed);
+    }
+
+    merged.shards.sort_by_key(|layout| layout.shard);
+    // Stable, so each shard keeps its execution order
+    merged.results.sort_by_key(|result| result.shard);
+    Ok(merged)
+}
+
+fn check_same<T: PartialEq + fmt::Display>(field: &'static str, expected: &T, found: &T) -> Result<(), ShardError> {
+    if expected == found {
+        return Ok(());
+    }
+    Err(ShardError::Mismatch {
+        field,
+        expected: expected.to_string(),
+        found: found.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::all_targets;
+    use crate::runner::BenchmarkRunError;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    struct Ordered {
+        id: &'static str,
+        after: &'static [&'static str],
+    }
+
+    #[async_trait]
+    impl BenchTarget for Ordered {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn description(&self) -> &str {
+            "ordering fixture"
+        }
+
+        async fn run(&self) -> Result<BenchmarkResult> {
+            Ok(BenchmarkResult::new(self.id.to_string(), json!({})))
+        }
+
+        fn run_after(&self) -> &[&str] {
+            self.after
+        }
+    }
+
+    fn ordered(id: &'static str, after: &'static [&'static str]) -> Box<dyn BenchTarget> {
+        Box::new(Ordered { id, after })
+    }
+
+    fn fixture() -> Vec<Box<dyn BenchTarget>> {
+        vec![
+            ordered("cache_read", &["cache_fill"]),
+            ordered("cache_fill", &[]),
+            ordered("alpha", &[]),
+            ordered("beta", &[]),
+            ordered("gamma", &[]),
+            ordered("cache_evict", &["cache_read"]),
+        ]
+    }
+
+    fn ids(targets: &[Box<dyn BenchTarget>]) -> Vec<&str> {
+        targets.iter().map(|t| t.id()).collect()
+    }
+
+    #[test]
+    fn test_parse_shard_spec() {
+        assert_eq!("2/4".parse::<Shard>().unwrap(), Shard { index: 2, count: 4 });
+        assert_eq!(Shard::new(2, 4).unwrap().to_string(), "2/4");
+        for bad in ["0/4", "5/4", "1/0", "2", "a/4", "2/b"] {
+            assert!(bad.parse::<Shard>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_shards_partition_targets_deterministically() {
+        let mut seen = Vec::new();
+        for index in 1..=4 {
+            let shard = Shard::new(index, 4).unwrap();
+            let first = ids(&shard.select(fixture()).unwrap()).join(",");
+            let again = ids(&shard.select(fixture()).unwrap()).join(",");
+            assert_eq!(first, again);
+            seen.extend(shard.select(fixture()).unwrap().iter().map(|t| t.id().to_string()));
+        }
+        seen.sort();
+        assert_eq!(seen, ["alpha", "beta", "cache_evict", "cache_fill", "cache_read", "gamma"]);
+
+        let builtin: usize = (1..=4)
+            .map(|index| Shard::new(index, 4).unwrap().select(all_targets()).unwrap().len())
+            .sum();
+        assert_eq!(builtin, all_targets().len());
+    }
+
+    #[test]
+    fn test_run_after_groups_share_a_shard_in_order() {
+        // The group is keyed by its smallest id
+        let shard = Shard::new(shard_index("cache_evict", 4), 4).unwrap();
+        let selected = shard.select(fixture()).unwrap();
+        let cache: Vec<_> = ids(&selected).into_iter().filter(|id| id.starts_with("cache_")).collect();
+
+        assert_eq!(cache, ["cache_fill", "cache_read", "cache_evict"]);
+    }
+
+    #[test]
+    fn test_run_after_cycle_is_an_error() {
+        let targets = vec![ordered("a", &["b"]), ordered("b", &["a"])];
+        let err = Shard::new(1, 1).unwrap().select(targets).err().unwrap();
+        assert_eq!(err.to_string(), "run_after cycle between benchmark targets: a, b");
+    }
+
+    fn shard_file(index: usize, targets: &[&str]) -> RunFile {
+        let run = BenchmarkRun {
+            results: targets
+                .iter()
+                .map(|id| BenchmarkResult::new(id.to_string(), json!({})))
+                .collect(),
+            failures: Vec::new(),
+            cancelled: false,
+        };
+        RunFile::new("ci-42", "default", 7).record(Some(Shard::new(index, 3).unwrap()), run)
+    }
+
+    #[test]
+    fn test_merge_combines_shards() {
+        let mut failing = BenchmarkRun::default();
+        failing.failures.push(BenchmarkRunError::TargetFailed {
+            target_id: "gamma".to_string(),
+            source: anyhow::anyhow!("boom"),
+        });
+        let third = RunFile::new("ci-42", "default", 7).record(Some(Shard::new(3, 3).unwrap()), failing);
+
+        let merged = merge(vec![third, shard_file(1, &["alpha", "beta"])]).unwrap();
+
+        let shards: Vec<_> = merged.shards.iter().map(|layout| layout.shard.index).collect();
+        assert_eq!(shards, [1, 3]);
+        assert_eq!(merged.missing_shards(), [Shard { index: 2, count: 3 }]);
+        assert_eq!(merged.failed, ["gamma"]);
+        assert!(merged.results.iter().all(|result| result.shard == Some(1)));
+    }
+
+    #[test]
+    fn test_merge_rejects_inconsistent_inputs() {
+        let mut other_seed = shard_file(2, &["gamma"]);
+        other_seed.seed = 8;
+        let err = merge(vec![shard_file(1, &["alpha"]), other_seed]).unwrap_err();
+        assert_eq!(err.to_string(), "shard outputs disagree on seed: '7' vs '8'");
+
+        let err = merge(vec![shard_file(1, &["alpha"]), shard_file(2, &["alpha"])]).unwrap_err();
+        assert!(matches!(err, ShardError::DuplicateTarget { target_id } if target_id == "alpha"));
+
+        let err = merge(vec![shard_file(1, &["alpha"]), shard_file(1, &["beta"])]).unwrap_err();
+        assert!(matches!(err, ShardError::DuplicateShard { .. }));
+
+        let unsharded = RunFile::new("ci-42", "default", 7);
+        assert!(matches!(merge(vec![unsharded]).unwrap_err(), ShardError::NotSharded { input: 1, .. }));
+        assert!(matches!(merge(Vec::new()).unwrap_err(), ShardError::NoInputs));
+    }
+}
@@ -0,0 +1,116 @@
+//! Destinations for benchmark results
+//!
+//! A [`ResultSink`] publishes the results of a run somewhere: [`FileSink`]
+//! writes them to an [`OutputLayout`], and with the `http-push` feature
+//! [`HttpSink`](crate::exporters::HttpSink) uploads them to an HTTP endpoint,
+//! and with `object-store` [`ObjectStoreSink`](crate::archive::ObjectStoreSink)
+//! archives them to a bucket.
+//! [`publish_all`] hands the results to several sinks and reports each
+//! sink's outcome, so one unreachable dashboard doesn't lose the files.
+
+use crate::io::{self, OutputLayout};
+use crate::{markdown, BenchmarkResult};
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// A destination for benchmark results
+#[async_trait]
+pub trait ResultSink: Send + Sync {
+    /// Name of the sink in reports, such as a directory or URL
+    fn name(&self) -> String;
+
+    /// Publish `results`
+    async fn publish(&self, results: &[BenchmarkResult]) -> Result<()>;
+}
+
+/// Writes results with [`io::write_results_async`]
+#[derive(Debug, Clone)]
+pub struct FileSink {
+    layout: OutputLayout,
+}
+
+impl FileSink {
+    pub fn new(layout: OutputLayout) -> Self {
+        Self { layout }
+    }
+}
+
+#[async_trait]
+impl ResultSink for FileSink {
+    fn name(&self) -> String {
+        self.layout.root.display().to_string()
+    }
+
+    async fn publish(&self, results: &[BenchmarkResult]) -> Result<()> {
+        io::write_results_async(results.to_vec(), markdown::generate_summary(results), self.layout.clone()).await
+    }
+}
+
+/// Outcome of publishing to one sink
+#[derive(Debug)]
+pub struct SinkOutcome {
+    /// [`ResultSink::name`] of the sink
+    pub sink: String,
+    pub result: Result<()>,
+}
+
+impl SinkOutcome {
+    pub fn is_success(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Publish `results` to every sink, returning an outcome per sink in order
+///
+/// Sinks publish concurrently; a failing sink doesn't stop the others.
+pub async fn publish_all(results: &[BenchmarkResult], sinks: &[&dyn ResultSink]) -> Vec<SinkOutcome> {
+    futures::future::join_all(sinks.iter().map(|sink| async move {
+        SinkOutcome {
+            sink: sink.name(),
+            result: sink.publish(results).await,
+        }
+    }))
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::BenchmarkRun;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    struct Unreachable;
+
+    #[async_trait]
+    impl ResultSink for Unreachable {
+        fn name(&self) -> String {
+            "unreachable".to_string()
+        }
+
+        async fn publish(&self, _results: &[BenchmarkResult]) -> Result<()> {
+            anyhow::bail!("connection refused")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_outcome_per_sink() {
+        let dir = TempDir::new().unwrap();
+        let layout = OutputLayout::new(dir.path());
+        let files = FileSink::new(layout.clone());
+        let run = BenchmarkRun {
+            results: vec![BenchmarkResult::new("storage".to_string(), json!({"write": {"p95_ms": 2.0}}))],
+            ..Default::default()
+        };
+
+        let outcomes = run.publish(&[&Unreachable, &files]).await;
+
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].sink, "unreachable");
+        assert!(outcomes[0].result.as_ref().unwrap_err().to_string().contains("connection refused"));
+        assert_eq!(outcomes[1].sink, dir.path().display().to_string());
+        assert!(outcomes[1].is_success());
+        assert_eq!(io::read_json(&layout.latest_file()).unwrap().len(), 1);
+        assert!(layout.summary_file.exists());
+    }
+}
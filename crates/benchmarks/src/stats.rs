@@ -0,0 +1,403 @@
+//! Summary statistics for benchmark samples
+//!
+//! Averages hide tail latency, so every timed operation is reported with
+//! p50/p90/p95/p99 next to avg/min/max. Percentiles use linear interpolation
+//! between the closest ranks (the same definition as numpy's default), so
+//! small sample sets still give stable, monotonic values.
+//!
+//! Spread is reported as the sample standard deviation and the coefficient of
+//! variation (std dev as a percentage of the mean), so comparison tools can
+//! judge whether a delta between two runs is larger than the noise.
+//!
+//! A single scheduler hiccup can dominate the max and the mean of a short
+//! run, so an [`OutlierPolicy`] may drop extreme samples first. Dropped
+//! samples are counted in [`Summary::outliers_rejected`]; nothing disappears
+//! silently.
+//!
+//! Throughput is derived from the same samples: [`Summary::ops_per_sec`] is
+//! the number of samples over the total time they took, so it is the
+//! reciprocal of the mean for one operation per sample.
+//!
+//! Targets report a [`Summary`] as [`OperationMetrics`](crate::metrics::OperationMetrics).
+
+use crate::units::Unit;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// Coefficient of variation above which a measurement is considered unstable
+pub const UNSTABLE_CV_PERCENT: f64 = 10.0;
+
+/// `k` of [`OutlierPolicy::Iqr`] when none is given (Tukey's fences)
+pub const DEFAULT_IQR_K: f64 = 1.5;
+
+/// Threshold of [`OutlierPolicy::Mad`] when none is given (Iglewicz and Hoaglin)
+pub const DEFAULT_MAD_THRESHOLD: f64 = 3.5;
+
+/// Scales a median absolute deviation to the standard deviation of a normal distribution
+const MAD_SCALE: f64 = 0.6745;
+
+/// Which samples are dropped before summary statistics are computed
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OutlierPolicy {
+    /// Keep every sample
+    #[default]
+    None,
+    /// Drop samples outside `[q1 - k * IQR, q3 + k * IQR]`
+    Iqr { k: f64 },
+    /// Drop samples whose modified z-score `0.6745 * |x - median| / MAD`
+    /// exceeds `threshold`
+    ///
+    /// When more than half the samples are equal the MAD is 0 and nothing is
+    /// dropped.
+    Mad { threshold: f64 },
+}
+
+impl OutlierPolicy {
+    /// Range of ascending `sorted` the policy keeps
+    ///
+    /// The median is always kept, so the range is empty only for an empty slice.
+    pub fn kept(&self, sorted: &[f64]) -> Range<usize> {
+        let everything = 0..sorted.len();
+        if sorted.is_empty() {
+            return everything;
+        }
+        let (low, high) = match *self {
+            OutlierPolicy::None => return everything,
+            OutlierPolicy::Iqr { k } => {
+                let q1 = percentile(sorted, 25.0);
+                let q3 = percentile(sorted, 75.0);
+                let fence = k * (q3 - q1);
+                (q1 - fence, q3 + fence)
+            }
+            OutlierPolicy::Mad { threshold } => {
+                let median = percentile(sorted, 50.0);
+                let mut deviations: Vec<f64> = sorted.iter().map(|x| (x - median).abs()).collect();
+                deviations.sort_by(f64::total_cmp);
+                let mad = percentile(&deviations, 50.0);
+                if mad == 0.0 {
+                    return everything;
+                }
+                let reach = threshold * mad / MAD_SCALE;
+                (median - reach, median + reach)
+            }
+        };
+        sorted.partition_point(|x| *x < low)..sorted.partition_point(|x| *x <= high)
+    }
+}
+
+impl fmt::Display for OutlierPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutlierPolicy::None => write!(f, "none"),
+            OutlierPolicy::Iqr { k } => write!(f, "iqr:{}", k),
+            OutlierPolicy::Mad { threshold } => write!(f, "mad:{}", threshold),
+        }
+    }
+}
+
+impl FromStr for OutlierPolicy {
+    type Err = String;
+
+    /// Parse `none`, `iqr[:K]` or `mad[:THRESHOLD]`
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let (name, given) = match raw.split_once(':') {
+            Some((name, param)) => (name, Some(param)),
+            None => (raw, None),
+        };
+        let param = |default: f64| -> Result<f64, String> {
+            let Some(param) = given else {
+                return Ok(default);
+            };
+            match param.trim().parse::<f64>() {
+                Ok(value) if value.is_finite() && value > 0.0 => Ok(value),
+                _ => Err(format!("outlier policy parameter must be a positive number, got '{}'", param)),
+            }
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "none" if given.is_none() => Ok(OutlierPolicy::None),
+            "iqr" => Ok(OutlierPolicy::Iqr { k: param(DEFAULT_IQR_K)? }),
+            "mad" => Ok(OutlierPolicy::Mad {
+                threshold: param(DEFAULT_MAD_THRESHOLD)?,
+            }),
+            _ => Err(format!("unknown outlier policy '{}' (expected none, iqr[:K] or mad[:THRESHOLD])", raw)),
+        }
+    }
+}
+
+/// Summary of a set of timing samples, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Summary {
+    /// Number of samples summarised, after outlier rejection
+    pub count: usize,
+    /// Samples the [`OutlierPolicy`] dropped
+    pub outliers_rejected: usize,
+    pub avg: f64,
+    pub min: f64,
+    pub max: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p95: f64,
+    pub p99: f64,
+    /// Sample standard deviation (n - 1 denominator); 0 for fewer than two samples
+    pub std_dev: f64,
+    /// `std_dev` as a percentage of `avg`; 0 when the mean is 0
+    pub cv_percent: f64,
+    /// Samples per second of total measured time; 0 when no time was measured
+    pub ops_per_sec: f64,
+}
+
+impl Summary {
+    /// Summarise `samples`; an empty slice gives an all-zero summary
+    pub fn from_samples(samples: &[f64]) -> Self {
+        Self::from_samples_in(samples, &mut Vec::new())
+    }
+
+    /// [`from_samples`](Self::from_samples), sorting a copy in `scratch`
+    ///
+    /// Reusing one scratch buffer across summaries avoids allocating a sorted
+    /// copy per summary.
+    pub fn from_samples_in(samples: &[f64], scratch: &mut Vec<f64>) -> Self {
+        Self::from_samples_with(samples, OutlierPolicy::None, scratch)
+    }
+
+    /// [`from_samples_in`](Self::from_samples_in), after dropping the samples `outliers` rejects
+    pub fn from_samples_with(samples: &[f64], outliers: OutlierPolicy, scratch: &mut Vec<f64>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        scratch.clear();
+        scratch.extend_from_slice(samples);
+        scratch.sort_by(f64::total_cmp);
+        let kept = outliers.kept(scratch);
+        let sorted = &scratch[kept];
+
+        let count = sorted.len();
+        let total_ms = sorted.iter().sum::<f64>();
+        let avg = total_ms / count as f64;
+        let std_dev = if count < 2 {
+            0.0
+        } else {
+            let sum_sq: f64 = sorted.iter().map(|x| (x - avg).powi(2)).sum();
+            (sum_sq / (count - 1) as f64).sqrt()
+        };
+        let cv_percent = if avg == 0.0 { 0.0 } else { std_dev / avg.abs() * 100.0 };
+        let ops_per_sec = if total_ms > 0.0 { count as f64 * 1000.0 / total_ms } else { 0.0 };
+
+        Self {
+            count,
+            outliers_rejected: samples.len() - count,
+            avg,
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            p50: percentile(sorted, 50.0),
+            p90: percentile(sorted, 90.0),
+            p95: percentile(sorted, 95.0),
+            p99: percentile(sorted, 99.0),
+            std_dev,
+            cv_percent,
+            ops_per_sec,
+        }
+    }
+
+    /// Whether the spread is too large for the mean to be compared across runs
+    pub fn is_unstable(&self) -> bool {
+        self.cv_percent > UNSTABLE_CV_PERCENT
+    }
+
+    /// This summary of samples timed in `unit`, restated in milliseconds
+    ///
+    /// Statistics are computed as if samples were milliseconds, so a target
+    /// timing in microseconds converts its summary before reporting it as
+    /// [`OperationMetrics`](crate::metrics::OperationMetrics); `ops_per_sec`
+    /// is corrected with the same factor. `None` for units that aren't times.
+    pub fn in_milliseconds(self, unit: Unit) -> Option<Self> {
+        let factor = unit.convert(1.0, Unit::Milliseconds)?;
+        Some(Self {
+            avg: self.avg * factor,
+            min: self.min * factor,
+            max: self.max * factor,
+            p50: self.p50 * factor,
+            p90: self.p90 * factor,
+            p95: self.p95 * factor,
+            p99: self.p99 * factor,
+            std_dev: self.std_dev * factor,
+            ops_per_sec: self.ops_per_sec / factor,
+            ..self
+        })
+    }
+}
+
+/// The `p`th percentile (0-100) of ascending `sorted` samples
+///
+/// Interpolates linearly between the samples at ranks `floor(r)` and
+/// `ceil(r)`, where `r = p / 100 * (n - 1)`.
+///
+/// # Panics
+///
+/// If `sorted` is empty.
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    assert!(!sorted.is_empty(), "percentile of an empty sample set");
+    let rank = (p / 100.0).clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "expected {expected}, got {actual}");
+    }
+
+    #[test]
+    fn test_percentiles_of_one_to_ten() {
+        let samples: Vec<f64> = (1..=10).map(f64::from).collect();
+        let summary = Summary::from_samples(&samples);
+
+        assert_eq!(summary.count, 10);
+        assert_close(summary.avg, 5.5);
+        assert_close(summary.p50, 5.5);
+        assert_close(summary.p90, 9.1);
+        assert_close(summary.p95, 9.55);
+        assert_close(summary.p99, 9.91);
+    }
+
+    #[test]
+    fn test_unsorted_input_with_outlier() {
+        let summary = Summary::from_samples(&[3.0, 1.0, 100.0, 2.0, 4.0]);
+
+        assert_close(summary.min, 1.0);
+        assert_close(summary.max, 100.0);
+        assert_close(summary.p50, 3.0);
+        assert_close(summary.p90, 61.6);
+        assert_close(summary.p95, 80.8);
+        assert_close(summary.p99, 96.16);
+    }
+
+    #[test]
+    fn test_summary_in_microseconds_restated_in_milliseconds() {
+        let micros = Summary::from_samples(&[500.0, 1500.0]);
+        let millis = micros.in_milliseconds(Unit::Microseconds).unwrap();
+
+        assert_eq!(millis.avg, 1.0);
+        assert_eq!(millis.max, 1.5);
+        assert_eq!(millis.cv_percent, micros.cv_percent);
+        // Two operations in 2ms
+        assert!((millis.ops_per_sec - 1000.0).abs() < 1e-9);
+        assert_eq!(micros.in_milliseconds(Unit::Milliseconds), Some(micros));
+        assert_eq!(micros.in_milliseconds(Unit::Bytes), None);
+    }
+
+    #[test]
+    fn test_single_and_empty_samples() {
+        let one = Summary::from_samples(&[7.0]);
+        assert_close(one.p50, 7.0);
+        assert_close(one.p99, 7.0);
+
+        assert_eq!(Summary::from_samples(&[]), Summary::default());
+    }
+
+    #[test]
+    fn test_percentile_bounds() {
+        let sorted = [10.0, 20.0, 30.0];
+        assert_close(percentile(&sorted, 0.0), 10.0);
+        assert_close(percentile(&sorted, 100.0), 30.0);
+        assert_close(percentile(&sorted, 25.0), 15.0);
+    }
+
+    #[test]
+    fn test_std_dev_of_known_vectors() {
+        // Classic example: mean 5, sum of squared deviations 32
+        let summary = Summary::from_samples(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert_close(summary.std_dev, (32.0f64 / 7.0).sqrt());
+        assert_close(summary.cv_percent, (32.0f64 / 7.0).sqrt() / 5.0 * 100.0);
+        assert!(summary.is_unstable());
+
+        // Mean 10, deviations of +-0.5 give std dev sqrt(1 / 3) and CV under 10%
+        let steady = Summary::from_samples(&[9.5, 10.5, 9.5, 10.5]);
+        assert_close(steady.std_dev, (1.0f64 / 3.0).sqrt());
+        assert!(!steady.is_unstable());
+    }
+
+    /// 20 samples around 1ms and one 100x hiccup
+    fn samples_with_hiccup() -> Vec<f64> {
+        let mut samples: Vec<f64> = (0..20).map(|i| 1.0 + (i % 5) as f64 * 0.01).collect();
+        samples.insert(7, 100.0);
+        samples
+    }
+
+    #[test]
+    fn test_outlier_dominates_avg_without_rejection() {
+        let summary = Summary::from_samples(&samples_with_hiccup());
+        assert!(summary.avg > 5.0);
+        assert_close(summary.max, 100.0);
+        assert_eq!(summary.outliers_rejected, 0);
+    }
+
+    #[test]
+    fn test_iqr_and_mad_reject_single_outlier() {
+        let samples = samples_with_hiccup();
+        let clean = Summary::from_samples(&samples[..7].iter().chain(&samples[8..]).copied().collect::<Vec<_>>());
+
+        for policy in [OutlierPolicy::Iqr { k: DEFAULT_IQR_K }, OutlierPolicy::Mad { threshold: DEFAULT_MAD_THRESHOLD }] {
+            let summary = Summary::from_samples_with(&samples, policy, &mut Vec::new());
+            assert_eq!(summary.outliers_rejected, 1, "{}", policy);
+            assert_eq!(summary.count, 20, "{}", policy);
+            assert_close(summary.avg, clean.avg);
+            assert_close(summary.max, clean.max);
+        }
+    }
+
+    #[test]
+    fn test_outlier_policy_keeps_constant_samples() {
+        let constant = [2.0; 8];
+        for policy in [OutlierPolicy::Iqr { k: 1.5 }, OutlierPolicy::Mad { threshold: 3.5 }] {
+            assert_eq!(policy.kept(&constant), 0..8);
+        }
+        assert_eq!(OutlierPolicy::None.kept(&[1.0, 1000.0]), 0..2);
+        assert_eq!(OutlierPolicy::Iqr { k: 1.5 }.kept(&[]), 0..0);
+    }
+
+    #[test]
+    fn test_parse_outlier_policy() {
+        assert_eq!("none".parse::<OutlierPolicy>().unwrap(), OutlierPolicy::None);
+        assert_eq!("iqr".parse::<OutlierPolicy>().unwrap(), OutlierPolicy::Iqr { k: DEFAULT_IQR_K });
+        assert_eq!("IQR:3".parse::<OutlierPolicy>().unwrap(), OutlierPolicy::Iqr { k: 3.0 });
+        assert_eq!("mad:2.5".parse::<OutlierPolicy>().unwrap(), OutlierPolicy::Mad { threshold: 2.5 });
+        assert!("mad:0".parse::<OutlierPolicy>().is_err());
+        assert!("none:1".parse::<OutlierPolicy>().is_err());
+        assert!("trim".parse::<OutlierPolicy>().is_err());
+
+        let policy = OutlierPolicy::Mad { threshold: 2.5 };
+        assert_eq!(policy.to_string().parse::<OutlierPolicy>().unwrap(), policy);
+    }
+
+    #[test]
+    fn test_ops_per_sec_from_count_and_total_time() {
+        // 4 samples taking 2ms in total
+        assert_close(Summary::from_samples(&[0.25, 0.5, 0.5, 0.75]).ops_per_sec, 2000.0);
+        assert_close(Summary::from_samples(&[10.0; 3]).ops_per_sec, 100.0);
+        assert_close(Summary::from_samples(&[0.0, 0.0]).ops_per_sec, 0.0);
+        assert_close(Summary::from_samples(&[]).ops_per_sec, 0.0);
+
+        // Rejected samples count toward neither the operations nor the time
+        let policy = OutlierPolicy::Iqr { k: DEFAULT_IQR_K };
+        let summary = Summary::from_samples_with(&samples_with_hiccup(), policy, &mut Vec::new());
+        assert_close(summary.ops_per_sec, 1000.0 / summary.avg);
+    }
+
+    #[test]
+    fn test_std_dev_degenerate_inputs() {
+        let constant = Summary::from_samples(&[3.0; 5]);
+        assert_close(constant.std_dev, 0.0);
+        assert_close(constant.cv_percent, 0.0);
+
+        assert_close(Summary::from_samples(&[7.0]).std_dev, 0.0);
+        assert_close(Summary::from_samples(&[0.0, 0.0]).cv_percent, 0.0);
+    }
+}
@@ -0,0 +1,224 @@
+//! Markdown reports rendered from user templates
+//!
+//! Needs the `templates` feature. Templates use [minijinja] syntax, a
+//! subset of Jinja2, and see a [`TemplateContext`]:
+//!
+//! | Variable | Value |
+//! |----------|-------|
+//! | `generated` | Time the context was built, RFC 3339 |
+//! | `cancelled` | Whether the run was cancelled part-way |
+//! | `results` | Every [`BenchmarkResult`] as in the raw JSON |
+//! | `environments` | Distinct [`RunEnvironment`]s of the results |
+//! | `comparison` | [`RegressionReport`] against a baseline, if any |
+//! | `history` | [`MetricTrend`]s of earlier runs |
+//! | `sections` | The built-in summary's `header`, `contents`, `results`, `trends` and `details` as markdown |
+//!
+//! Besides the built-in filters, `number` formats a number as the summary
+//! does, as in `{{ result.metrics.write.avg_ms | number }}`; metrics are
+//! `{value, unit}` measurements, and `number` prints the value in its own
+//! unit. `sparkline` draws a list of samples, as in
+//! `{{ result.raw_samples.write | sparkline }}`.
+//!
+//! [`DEFAULT_TEMPLATE`] reproduces [`markdown::generate_summary`] from the
+//! sections, so a template can start from it and rearrange or replace them.
+//!
+//! [minijinja]: https://docs.rs/minijinja
+
+use crate::compare::RegressionReport;
+use crate::environment::RunEnvironment;
+use crate::markdown::{self, SummaryOptions, SummarySections};
+use crate::trends::MetricTrend;
+use crate::BenchmarkResult;
+use chrono::{DateTime, Utc};
+use minijinja::{AutoEscape, Environment};
+use serde::Serialize;
+
+/// Template laying out the built-in summary's sections in order
+pub const DEFAULT_TEMPLATE: &str =
+    "{{ sections.header }}{{ sections.contents }}{{ sections.results }}{{ sections.trends }}{{ sections.details }}";
+
+/// A template that couldn't be parsed or rendered
+#[derive(Debug, thiserror::Error)]
+pub enum TemplateError {
+    #[error("line {line}: {message}")]
+    AtLine { line: usize, message: String },
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<minijinja::Error> for TemplateError {
+    fn from(e: minijinja::Error) -> Self {
+        let message = e.detail().map(str::to_string).unwrap_or_else(|| e.kind().to_string());
+        match e.line() {
+            Some(line) => TemplateError::AtLine { line, message },
+            None => TemplateError::Other(message),
+        }
+    }
+}
+
+/// Everything a report template can refer to
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateContext {
+    generated: DateTime<Utc>,
+    cancelled: bool,
+    results: Vec<BenchmarkResult>,
+    environments: Vec<RunEnvironment>,
+    comparison: Option<RegressionReport>,
+    history: Vec<MetricTrend>,
+    sections: SummarySections,
+}
+
+impl TemplateContext {
+    /// Context of `results`, with `sections` laid out by `options`
+    ///
+    /// `history` and `comparison` are taken from the options' trends and
+    /// comparison, if any.
+    pub fn new(results: &[BenchmarkResult], options: &SummaryOptions) -> Self {
+        let mut environments: Vec<RunEnvironment> = Vec::new();
+        for env in results.iter().filter_map(|result| result.environment.as_ref()) {
+            if !environments.contains(env) {
+                environments.push(env.clone());
+            }
+        }
+        Self {
+            generated: Utc::now(),
+            cancelled: false,
+            results: results.to_vec(),
+            environments,
+            comparison: options.comparison.clone(),
+            history: options.trends.clone(),
+            sections: markdown::summary_sections(results, None, false, options),
+        }
+    }
+
+    /// Mark the run as cancelled part-way, adding the partial-run notice to the header section
+    pub fn with_cancelled(mut self, cancelled: bool) -> Self {
+        self.cancelled = cancelled;
+        self.sections.header = markdown::summary_header(&self.results, None, cancelled);
+        self
+    }
+
+    /// Expose a comparison against a baseline as `comparison`
+    pub fn with_comparison(mut self, report: RegressionReport) -> Self {
+        self.comparison = Some(report);
+        self
+    }
+}
+
+fn environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    env.set_auto_escape_callback(|_| AutoEscape::None);
+    env.set_keep_trailing_newline(true);
+    env.add_filter("number", number);
+    env.add_filter("sparkline", |samples: Vec<f64>| markdown::sparkline(&samples));
+    env
+}
+
+/// The `number` filter: a number, or a measurement's value, formatted as the summary does
+///
+/// Numeric strings, as in result files that predate typed metrics, are read too.
+fn number(value: minijinja::Value) -> Result<String, minijinja::Error> {
+    let number = match value.get_attr("value") {
+        Ok(inner) if !inner.is_undefined() => inner,
+        _ => value,
+    };
+    let parsed = match number.as_str() {
+        Some(text) => text.trim().parse::<f64>().ok(),
+        None => f64::try_from(number.clone()).ok(),
+    };
+    parsed.map(markdown::format_number).ok_or_else(|| {
+        minijinja::Error::new(
+            minijinja::ErrorKind::InvalidOperation,
+            format!("number expects a number or a {{value, unit}} measurement, got {}", number),
+        )
+    })
+}
+
+/// Check that `template` parses, without rendering it
+pub fn check_template(template: &str) -> Result<(), TemplateError> {
+    environment().template_from_str(template)?;
+    Ok(())
+}
+
+/// Render `template` with `ctx`
+///
+/// Errors carry the line of the template they occurred on where known.
+pub fn generate_from_template(template: &str, ctx: &TemplateContext) -> Result<String, TemplateError> {
+    Ok(environment().render_str(template, ctx)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compare::compare_results;
+    use crate::metrics::OperationMetrics;
+    use serde_json::json;
+
+    /// A result shaped like the adapters', with `{value, unit}` measurements
+    fn results() -> Vec<BenchmarkResult> {
+        let metrics = json!({"write": OperationMetrics::from_samples(&[1.0, 1.5, 2.0])});
+        let mut result = BenchmarkResult::new("storage".to_string(), metrics);
+        result.raw_samples.insert("write".to_string(), vec![1.0, 2.0, 3.0]);
+        vec![result]
+    }
+
+    fn without_generated(report: &str) -> String {
+        report.lines().filter(|line| !line.starts_with("**Generated:**")).collect::<Vec<_>>().join("\n")
+    }
+
+    #[test]
+    fn test_default_template_matches_summary() {
+        let results = results();
+        let options = SummaryOptions::default().with_collapsible(true).with_toc(true);
+        let ctx = TemplateContext::new(&results, &options);
+
+        let rendered = generate_from_template(DEFAULT_TEMPLATE, &ctx).unwrap();
+        assert_eq!(
+            without_generated(&rendered),
+            without_generated(&markdown::generate_summary_with(&results, &options))
+        );
+
+        let cancelled = generate_from_template(DEFAULT_TEMPLATE, &ctx.with_cancelled(true)).unwrap();
+        assert!(cancelled.contains("**PARTIAL RUN:**"));
+    }
+
+    #[test]
+    fn test_template_sees_results_and_comparison() {
+        let results = results();
+        let baseline = vec![BenchmarkResult::new("storage".to_string(), json!({"write": {"avg_ms": 1.0}}))];
+        let ctx = TemplateContext::new(&results, &SummaryOptions::default())
+            .with_comparison(compare_results(&baseline, &results));
+        let template = "\
+{% for result in results %}- {{ result.target_id }}: {{ result.metrics.write.avg_ms | number }} ms \
+{{ result.raw_samples.write | sparkline }}
+{% endfor %}{% for metric in comparison.metrics if metric.path == \"write.avg_ms\" %}{{ metric.path }} {{ metric.status }}
+{% endfor %}";
+
+        assert_eq!(
+            generate_from_template(template, &ctx).unwrap(),
+            "- storage: 1.500 ms ▁▅█\nwrite.avg_ms regressed\n"
+        );
+    }
+
+    #[test]
+    fn test_number_filter_reads_measurements_and_plain_numbers() {
+        let ctx = TemplateContext::new(&results(), &SummaryOptions::default());
+        let template = "{{ results[0].metrics.write.p95_ms | number }} {{ 2 | number }} {{ \"0.25\" | number }}";
+        assert_eq!(generate_from_template(template, &ctx).unwrap(), "1.950 2 0.250");
+
+        let err = generate_from_template("{{ results[0].metrics.write | number }}", &ctx).unwrap_err();
+        assert!(err.to_string().contains("number expects"), "{}", err);
+    }
+
+    #[test]
+    fn test_template_errors_name_the_line() {
+        let ctx = TemplateContext::new(&results(), &SummaryOptions::default());
+
+        let err = check_template("# Report\n\n{% if %}\n").unwrap_err();
+        assert!(matches!(err, TemplateError::AtLine { line: 3, .. }), "{}", err);
+
+        let err = generate_from_template("# Report\n{{ results | no_such_filter }}\n", &ctx).unwrap_err();
+        assert!(matches!(err, TemplateError::AtLine { line: 2, .. }), "{}", err);
+        assert!(err.to_string().starts_with("line 2: "));
+    }
+}
@@ -0,0 +1,141 @@
+//! Metric trends across archived runs
+//!
+//! [`trends`] turns a history of runs, as read by
+//! [`io::read_history`](crate::io::read_history), into one time series per
+//! target and metric path, with a rolling average to smooth out noisy runs.
+//! [`markdown::generate_trend_report`](crate::markdown::generate_trend_report)
+//! renders the most recent points as tables.
+
+use crate::compare::numeric_leaves;
+use crate::units::Unit;
+use crate::BenchmarkResult;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Runs averaged by [`trends`] unless told otherwise
+pub const DEFAULT_WINDOW: usize = 5;
+
+/// One run's value of a metric
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrendPoint {
+    pub timestamp: DateTime<Utc>,
+    pub value: f64,
+    /// Mean of this value and up to `window - 1` values before it
+    pub rolling_avg: f64,
+}
+
+/// Time series of one metric of one target, oldest point first
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricTrend {
+    pub target_id: String,
+    /// Dotted path to the metric, e.g. `write.p95_ms`
+    pub path: String,
+    /// Unit of every point, the one the metric was first recorded in
+    pub unit: Unit,
+    pub points: Vec<TrendPoint>,
+}
+
+impl MetricTrend {
+    /// The newest point
+    pub fn latest(&self) -> Option<&TrendPoint> {
+        self.points.last()
+    }
+
+    /// The newest `n` points, oldest first
+    pub fn last(&self, n: usize) -> &[TrendPoint] {
+        &self.points[self.points.len().saturating_sub(n)..]
+    }
+}
+
+/// Time series of every numeric metric in `history`, sorted by target and path
+///
+/// Values are converted into the unit each metric was first recorded in;
+/// runs recording it in a unit that doesn't convert are left out of its
+/// series. Rolling averages cover `window` runs (at least one).
+pub fn trends(history: &[(DateTime<Utc>, Vec<BenchmarkResult>)], window: usize) -> Vec<MetricTrend> {
+    let window = window.max(1);
+    type Values = Vec<(DateTime<Utc>, f64)>;
+    let mut series: BTreeMap<(String, String), (Unit, Values)> = BTreeMap::new();
+
+    for (timestamp, results) in history {
+        for result in results {
+            for (path, measurement) in numeric_leaves(&result.metrics) {
+                let (unit, values) = series
+                    .entry((result.target_id.clone(), path))
+                    .or_insert_with(|| (measurement.unit, Vec::new()));
+                if let Some(value) = measurement.in_unit(*unit) {
+                    values.push((*timestamp, value));
+                }
+            }
+        }
+    }
+
+    series
+        .into_iter()
+        .map(|((target_id, path), (unit, values))| {
+            let points = values
+                .iter()
+                .enumerate()
+                .map(|(i, &(timestamp, value))| {
+                    let recent = &values[(i + 1).saturating_sub(window)..=i];
+                    let rolling_avg = recent.iter().map(|(_, value)| value).sum::<f64>() / recent.len() as f64;
+                    TrendPoint {
+                        timestamp,
+                        value,
+                        rolling_avg,
+                    }
+                })
+                .collect();
+            MetricTrend {
+                target_id,
+                path,
+                unit,
+                points,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn run(day: u32, metrics: serde_json::Value) -> (DateTime<Utc>, Vec<BenchmarkResult>) {
+        let timestamp = format!("2025-06-{:02}T00:00:00Z", day).parse().unwrap();
+        (timestamp, vec![BenchmarkResult::new("storage".to_string(), metrics)])
+    }
+
+    #[test]
+    fn test_series_with_rolling_average() {
+        let history = vec![
+            run(1, json!({"write": {"p95_ms": 1.0}})),
+            run(2, json!({"write": {"p95_ms": 3.0}})),
+            run(3, json!({"write": {"p95_ms": {"value": 5000.0, "unit": "microseconds"}}, "label": "x"})),
+        ];
+        let trends = trends(&history, 2);
+
+        assert_eq!(trends.len(), 1);
+        let p95 = &trends[0];
+        assert_eq!((p95.target_id.as_str(), p95.path.as_str()), ("storage", "write.p95_ms"));
+        assert_eq!(p95.unit, Unit::Milliseconds);
+        let values: Vec<_> = p95.points.iter().map(|p| (p.value, p.rolling_avg)).collect();
+        assert_eq!(values, vec![(1.0, 1.0), (3.0, 2.0), (5.0, 4.0)]);
+        assert_eq!(p95.last(2).len(), 2);
+        assert_eq!(p95.latest().unwrap().value, 5.0);
+    }
+
+    #[test]
+    fn test_metrics_missing_from_some_runs() {
+        let history = vec![
+            run(1, json!({"read": {"p95_ms": 1.0}, "write": {"p95_ms": 2.0}})),
+            run(2, json!({"write": {"p95_ms": 2.0}})),
+            run(3, json!({"write": {"p95_ms": {"value": 2.0, "unit": "bytes"}}})),
+        ];
+        let trends = trends(&history, 0);
+
+        let paths: Vec<_> = trends.iter().map(|t| (t.path.as_str(), t.points.len())).collect();
+        assert_eq!(paths, vec![("read.p95_ms", 1), ("write.p95_ms", 2)]);
+    }
+}
@@ -0,0 +1,526 @@
+//! Units of benchmark metrics
+//!
+//! Since result format version 3 every measured value in a result's metrics
+//! is written as a [`Measurement`], `{"value": 0.25, "unit": "milliseconds"}`,
+//! so readers no longer infer the unit from the key's suffix. The typed
+//! metrics in [`crate::metrics`] declare their unit per field with the serde
+//! modules below ([`milliseconds`], [`ops_per_sec`], ...). Inputs echoed into
+//! the metrics, such as a workload's `seed`, stay plain numbers.
+//!
+//! Older files hold bare numbers. [`Measurement::read`] maps those onto units
+//! by key ([`Unit::from_key`]), and the v2 → v3
+//! [migration](crate::io::migrations) rewrites them with [`annotate`], so
+//! both shapes flow through the same readers.
+//!
+//! Exporters convert from the recorded unit rather than the key:
+//! [`Unit::prometheus`] to Prometheus base units (seconds, bytes, ratios)
+//! and [`Unit::otlp`] to OTLP/UCUM units.
+
+use schema_registry_core::metric_names::{self, MetricUnit};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Unit of a metric value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Unit {
+    Milliseconds,
+    Microseconds,
+    Seconds,
+    /// Operations per second
+    OpsPerSec,
+    Bytes,
+    /// Bytes processed per second
+    BytesPerSec,
+    /// Fraction, 1.0 being the whole
+    Ratio,
+    /// Fraction out of 100
+    Percent,
+    /// Dimensionless number of samples, iterations or items
+    Count,
+}
+
+/// Units convertible into each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dimension {
+    Time,
+    Fraction,
+}
+
+impl Unit {
+    /// Unit implied by a metric key, for values written before units were recorded
+    ///
+    /// Catalogued names use their catalogue unit; other keys go by suffix
+    /// (`_ms`, `_us`, `_seconds`, `bytes_per_sec`, `_per_sec`, `_bytes`, `_ratio`,
+    /// `_percent`)
+    /// and are counts otherwise.
+    pub fn from_key(key: &str) -> Self {
+        if let Some(name) = metric_names::lookup(key) {
+            return match name.unit {
+                MetricUnit::Seconds => Self::Seconds,
+                MetricUnit::Milliseconds => Self::Milliseconds,
+                MetricUnit::Bytes => Self::Bytes,
+                MetricUnit::Count => Self::Count,
+                MetricUnit::Ratio => Self::Ratio,
+                MetricUnit::Percent => Self::Percent,
+                MetricUnit::PerSecond => Self::OpsPerSec,
+                MetricUnit::BytesPerSecond => Self::BytesPerSec,
+            };
+        }
+        let ends = |suffixes: &[&str]| suffixes.iter().any(|suffix| key.ends_with(suffix));
+        if ends(&["_ms", "_millis"]) {
+            Self::Milliseconds
+        } else if ends(&["_us", "_micros"]) {
+            Self::Microseconds
+        } else if ends(&["_seconds", "_secs"]) {
+            Self::Seconds
+        } else if ends(&["bytes_per_sec", "bytes_per_second"]) {
+            Self::BytesPerSec
+        } else if ends(&["_per_sec", "_per_second"]) {
+            Self::OpsPerSec
+        } else if ends(&["_bytes"]) {
+            Self::Bytes
+        } else if key == "ratio" || ends(&["_ratio"]) {
+            Self::Ratio
+        } else if ends(&["_percent", "_pct"]) {
+            Self::Percent
+        } else {
+            Self::Count
+        }
+    }
+
+    /// Short symbol for labels, e.g. `ms` or `ops/s`; empty for ratios and counts
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Milliseconds => "ms",
+            Self::Microseconds => "µs",
+            Self::Seconds => "s",
+            Self::OpsPerSec => "ops/s",
+            Self::Bytes => "B",
+            Self::BytesPerSec => "B/s",
+            Self::Percent => "%",
+            Self::Ratio | Self::Count => "",
+        }
+    }
+
+    /// Dimension and size of one unit in that dimension's base unit
+    fn scale(self) -> Option<(Dimension, f64)> {
+        match self {
+            Self::Microseconds => Some((Dimension::Time, 1e-6)),
+            Self::Milliseconds => Some((Dimension::Time, 1e-3)),
+            Self::Seconds => Some((Dimension::Time, 1.0)),
+            Self::Ratio => Some((Dimension::Fraction, 1.0)),
+            Self::Percent => Some((Dimension::Fraction, 0.01)),
+            Self::OpsPerSec | Self::Bytes | Self::BytesPerSec | Self::Count => None,
+        }
+    }
+
+    /// `value` in this unit expressed in `to`, `None` between unrelated units
+    pub fn convert(self, value: f64, to: Unit) -> Option<f64> {
+        if self == to {
+            return Some(value);
+        }
+        match (self.scale(), to.scale()) {
+            (Some((from, a)), Some((into, b))) if from == into => Some(value * a / b),
+            _ => None,
+        }
+    }
+
+    /// `value` in Prometheus base units, with the metric name suffix for that unit
+    ///
+    /// Durations become seconds and percentages ratios. Counts have no suffix;
+    /// `_total` is for counters, and benchmark statistics are gauges.
+    pub fn prometheus(self, value: f64) -> (f64, &'static str) {
+        match self {
+            Self::Milliseconds | Self::Microseconds | Self::Seconds => {
+                (self.convert(value, Self::Seconds).unwrap_or(value), "_seconds")
+            }
+            Self::Ratio | Self::Percent => (self.convert(value, Self::Ratio).unwrap_or(value), "_ratio"),
+            Self::Bytes => (value, "_bytes"),
+            Self::BytesPerSec => (value, "_bytes_per_second"),
+            Self::OpsPerSec => (value, "_per_second"),
+            Self::Count => (value, ""),
+        }
+    }
+
+    /// `value` in the unit OTLP instruments report it in, with its UCUM code
+    ///
+    /// Follows the OpenTelemetry semantic conventions: durations in seconds
+    /// (`s`), sizes in bytes (`By`), fractions as `1`.
+    pub fn otlp(self, value: f64) -> (f64, &'static str) {
+        match self {
+            Self::Milliseconds | Self::Microseconds | Self::Seconds => {
+                (self.convert(value, Self::Seconds).unwrap_or(value), "s")
+            }
+            Self::Ratio | Self::Percent => (self.convert(value, Self::Ratio).unwrap_or(value), "1"),
+            Self::Bytes => (value, "By"),
+            Self::BytesPerSec => (value, "By/s"),
+            Self::OpsPerSec => (value, "{operation}/s"),
+            Self::Count => (value, "{count}"),
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Milliseconds => "milliseconds",
+            Self::Microseconds => "microseconds",
+            Self::Seconds => "seconds",
+            Self::OpsPerSec => "ops_per_sec",
+            Self::Bytes => "bytes",
+            Self::BytesPerSec => "bytes_per_sec",
+            Self::Ratio => "ratio",
+            Self::Percent => "percent",
+            Self::Count => "count",
+        })
+    }
+}
+
+/// A metric value with its unit
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub value: f64,
+    pub unit: Unit,
+}
+
+impl Measurement {
+    pub fn new(value: f64, unit: Unit) -> Self {
+        Self { value, unit }
+    }
+
+    /// Measurement stored under `key`
+    ///
+    /// Reads `{value, unit}` objects, and bare numbers or numeric strings from
+    /// older files with the unit implied by `key`. `None` for anything else.
+    pub fn read(key: &str, value: &Value) -> Option<Self> {
+        match value {
+            Value::Object(map) => measurement_of(map),
+            other => number(other).map(|value| Self::new(value, Unit::from_key(key))),
+        }
+    }
+
+    /// The value in `unit`, `None` if it can't be converted
+    pub fn in_unit(&self, unit: Unit) -> Option<f64> {
+        self.unit.convert(self.value, unit)
+    }
+
+    /// Value for reports, scaled to a readable unit: `850.000 µs`, `1.250 ms`, `12.5 MiB`
+    pub fn display(&self) -> String {
+        match self.unit {
+            Unit::Milliseconds | Unit::Microseconds | Unit::Seconds => {
+                let seconds = self.in_unit(Unit::Seconds).unwrap_or(self.value);
+                let unit = if seconds.abs() >= 1.0 {
+                    Unit::Seconds
+                } else if seconds.abs() >= 1e-3 || seconds == 0.0 {
+                    Unit::Milliseconds
+                } else {
+                    Unit::Microseconds
+                };
+                let value = Unit::Seconds.convert(seconds, unit).unwrap_or(seconds);
+                format!("{:.3} {}", value, unit.symbol())
+            }
+            Unit::Percent => format!("{:.1}%", self.value),
+            Unit::Ratio => format!("{:.3}", self.value),
+            Unit::OpsPerSec => format!("{:.1} ops/s", self.value),
+            Unit::Bytes if self.value >= 0.0 => format_bytes(self.value as u64),
+            Unit::BytesPerSec if self.value >= 0.0 => format!("{}/s", format_bytes(self.value as u64)),
+            Unit::Bytes | Unit::BytesPerSec | Unit::Count => {
+                if self.value.fract() == 0.0 && self.value.abs() < 1e15 {
+                    format!("{}", self.value as i64)
+                } else {
+                    format!("{:.3}", self.value)
+                }
+            }
+        }
+    }
+}
+
+/// Byte count with a binary unit, e.g. `12.5 MiB`
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// A `{value, unit}` object
+fn measurement_of(map: &Map<String, Value>) -> Option<Measurement> {
+    if map.len() != 2 {
+        return None;
+    }
+    let value = map.get("value")?.as_f64()?;
+    let unit = serde_json::from_value(map.get("unit")?.clone()).ok()?;
+    Some(Measurement::new(value, unit))
+}
+
+/// Whether `value` is a `{value, unit}` object
+pub fn is_measurement(value: &Value) -> bool {
+    value.as_object().and_then(measurement_of).is_some()
+}
+
+/// Numeric value of a metric leaf, whether a measurement, a number or a numeric string
+pub fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        Value::Object(map) => measurement_of(map).map(|m| m.value),
+        _ => None,
+    }
+}
+
+/// Keys whose numbers are inputs to a run rather than measurements
+fn is_plain(key: &str) -> bool {
+    key == metric_names::BENCH_SEED.name
+}
+
+/// Wrap every bare numeric leaf of `metrics` in a measurement, in place
+///
+/// Units come from [`Unit::from_key`]. Leaves that are already measurements,
+/// inputs such as `seed`, and arrays are left alone.
+pub fn annotate(metrics: &mut Value) {
+    let Value::Object(map) = metrics else {
+        return;
+    };
+    if measurement_of(map).is_some() {
+        return;
+    }
+    for (key, value) in map.iter_mut() {
+        match value {
+            Value::Number(n) if !is_plain(key) => {
+                *value = serde_json::json!({"value": n.clone(), "unit": Unit::from_key(key)});
+            }
+            Value::Object(_) => annotate(value),
+            _ => {}
+        }
+    }
+}
+
+/// A field type that can hold a measured value
+pub trait Quantity: Sized {
+    fn from_f64(value: f64) -> Option<Self>;
+}
+
+impl Quantity for f64 {
+    fn from_f64(value: f64) -> Option<Self> {
+        Some(value)
+    }
+}
+
+impl Quantity for u64 {
+    fn from_f64(value: f64) -> Option<Self> {
+        (value >= 0.0 && value.fract() == 0.0 && value <= u64::MAX as f64).then_some(value as u64)
+    }
+}
+
+impl Quantity for usize {
+    fn from_f64(value: f64) -> Option<Self> {
+        u64::from_f64(value).and_then(|value| usize::try_from(value).ok())
+    }
+}
+
+impl<T: Quantity> Quantity for Option<T> {
+    fn from_f64(value: f64) -> Option<Self> {
+        T::from_f64(value).map(Some)
+    }
+}
+
+/// Serialize `value` as `{value, unit}`
+///
+/// Generic so integer fields keep integer values in the output.
+fn serialize_in<T: Serialize, S: Serializer>(value: &T, unit: Unit, serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct Tagged<'a, T> {
+        value: &'a T,
+        unit: Unit,
+    }
+
+    Tagged { value, unit }.serialize(serializer)
+}
+
+/// Deserialize a value in `unit` from a measurement in a compatible unit, a bare number or a numeric string
+fn deserialize_in<'de, T: Quantity, D: Deserializer<'de>>(deserializer: D, unit: Unit) -> Result<T, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Measured { value: f64, unit: Unit },
+        Number(f64),
+        Text(String),
+    }
+
+    let value = match Raw::deserialize(deserializer)? {
+        Raw::Measured { value, unit: found } => found.convert(value, unit).ok_or_else(|| {
+            serde::de::Error::custom(format!("expected a value in {}, found one in {}", unit, found))
+        })?,
+        Raw::Number(value) => value,
+        Raw::Text(s) => s
+            .trim()
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("expected a number, found '{}'", s)))?,
+    };
+    T::from_f64(value).ok_or_else(|| serde::de::Error::custom(format!("{} is out of range for this field", value)))
+}
+
+macro_rules! unit_fields {
+    ($($(#[$doc:meta])* $module:ident => $unit:expr;)*) => {
+        $(
+            $(#[$doc])*
+            pub mod $module {
+                use super::{deserialize_in, serialize_in, Quantity, Unit};
+                use serde::{Deserializer, Serialize, Serializer};
+
+                pub fn serialize<T: Serialize, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+                    serialize_in(value, $unit, serializer)
+                }
+
+                pub fn deserialize<'de, T: Quantity, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+                    deserialize_in(deserializer, $unit)
+                }
+            }
+        )*
+    };
+}
+
+unit_fields! {
+    /// `#[serde(with)]` module for fields measured in milliseconds
+    milliseconds => Unit::Milliseconds;
+    /// `#[serde(with)]` module for fields measured in microseconds
+    microseconds => Unit::Microseconds;
+    /// `#[serde(with)]` module for fields measured in seconds
+    seconds => Unit::Seconds;
+    /// `#[serde(with)]` module for throughput fields
+    ops_per_sec => Unit::OpsPerSec;
+    /// `#[serde(with)]` module for sizes in bytes
+    bytes => Unit::Bytes;
+    /// `#[serde(with)]` module for byte throughput
+    bytes_per_sec => Unit::BytesPerSec;
+    /// `#[serde(with)]` module for fractions of 1
+    ratio => Unit::Ratio;
+    /// `#[serde(with)]` module for fractions of 100
+    percent => Unit::Percent;
+    /// `#[serde(with)]` module for counts
+    count => Unit::Count;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Timed {
+        #[serde(with = "milliseconds")]
+        avg_ms: f64,
+        #[serde(with = "count")]
+        iterations: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none", with = "bytes")]
+        peak_bytes: Option<u64>,
+    }
+
+    #[test]
+    fn test_fields_serialize_with_their_unit() {
+        let timed = Timed {
+            avg_ms: 1.5,
+            iterations: 10,
+            peak_bytes: None,
+        };
+        let value = serde_json::to_value(&timed).unwrap();
+        assert_eq!(
+            value,
+            json!({"avg_ms": {"value": 1.5, "unit": "milliseconds"}, "iterations": {"value": 10, "unit": "count"}})
+        );
+        assert_eq!(serde_json::from_value::<Timed>(value).unwrap(), timed);
+    }
+
+    #[test]
+    fn test_fields_read_legacy_and_converted_values() {
+        let legacy: Timed = serde_json::from_value(json!({"avg_ms": "1.5", "iterations": 10, "peak_bytes": 2048})).unwrap();
+        assert_eq!(legacy.avg_ms, 1.5);
+        assert_eq!(legacy.peak_bytes, Some(2048));
+
+        let micros: Timed = serde_json::from_value(json!({
+            "avg_ms": {"value": 250.0, "unit": "microseconds"},
+            "iterations": {"value": 3, "unit": "count"}
+        }))
+        .unwrap();
+        assert_eq!(micros.avg_ms, 0.25);
+
+        let wrong = serde_json::from_value::<Timed>(json!({"avg_ms": {"value": 1, "unit": "bytes"}, "iterations": 1}));
+        assert!(wrong.is_err());
+        assert!(serde_json::from_value::<Timed>(json!({"avg_ms": 1.0, "iterations": 1.5})).is_err());
+    }
+
+    #[test]
+    fn test_legacy_keys_map_onto_units() {
+        assert_eq!(Unit::from_key("p95_ms"), Unit::Milliseconds);
+        assert_eq!(Unit::from_key("ops_per_sec"), Unit::OpsPerSec);
+        assert_eq!(Unit::from_key("throughput_ops_per_sec"), Unit::OpsPerSec);
+        assert_eq!(Unit::from_key("rss_peak_bytes"), Unit::Bytes);
+        assert_eq!(Unit::from_key("bytes_per_sec"), Unit::BytesPerSec);
+        assert_eq!(Unit::from_key("read_bytes_per_sec"), Unit::BytesPerSec);
+        assert_eq!(Unit::from_key("compression_ratio"), Unit::Ratio);
+        assert_eq!(Unit::from_key("cv_percent"), Unit::Percent);
+        assert_eq!(Unit::from_key("ttl_seconds"), Unit::Seconds);
+        assert_eq!(Unit::from_key("lock_wait_us"), Unit::Microseconds);
+        assert_eq!(Unit::from_key("iterations"), Unit::Count);
+
+        let mut metrics = json!({"write": {"avg_ms": 0.5, "count": 3}, "seed": 42, "label": "x",
+                                 "memory": {"rss_peak_bytes": {"value": 10, "unit": "bytes"}}});
+        annotate(&mut metrics);
+        assert_eq!(metrics["write"]["avg_ms"], json!({"value": 0.5, "unit": "milliseconds"}));
+        assert_eq!(metrics["write"]["count"], json!({"value": 3, "unit": "count"}));
+        assert_eq!(metrics["seed"], 42);
+        assert_eq!(metrics["label"], "x");
+        assert_eq!(metrics["memory"]["rss_peak_bytes"], json!({"value": 10, "unit": "bytes"}));
+
+        assert_eq!(Measurement::read("p95_ms", &json!(2.0)), Some(Measurement::new(2.0, Unit::Milliseconds)));
+        assert_eq!(
+            Measurement::read("p95_ms", &metrics["write"]["avg_ms"]),
+            Some(Measurement::new(0.5, Unit::Milliseconds))
+        );
+        assert_eq!(Measurement::read("label", &json!("x")), None);
+    }
+
+    #[test]
+    fn test_prometheus_uses_base_units() {
+        assert_eq!(Unit::Milliseconds.prometheus(250.0), (0.25, "_seconds"));
+        assert_eq!(Unit::Microseconds.prometheus(1500.0), (0.0015, "_seconds"));
+        assert_eq!(Unit::Percent.prometheus(12.5), (0.125, "_ratio"));
+        assert_eq!(Unit::Bytes.prometheus(4096.0), (4096.0, "_bytes"));
+        assert_eq!(Unit::OpsPerSec.prometheus(900.0), (900.0, "_per_second"));
+        assert_eq!(Unit::BytesPerSec.prometheus(2048.0), (2048.0, "_bytes_per_second"));
+        assert_eq!(Unit::Count.prometheus(7.0), (7.0, ""));
+    }
+
+    #[test]
+    fn test_otlp_uses_ucum_units() {
+        assert_eq!(Unit::Milliseconds.otlp(1500.0), (1.5, "s"));
+        assert_eq!(Unit::Percent.otlp(50.0), (0.5, "1"));
+        assert_eq!(Unit::Ratio.otlp(0.4), (0.4, "1"));
+        assert_eq!(Unit::Bytes.otlp(10.0), (10.0, "By"));
+        assert_eq!(Unit::OpsPerSec.otlp(3.0), (3.0, "{operation}/s"));
+        assert_eq!(Unit::Count.otlp(3.0), (3.0, "{count}"));
+    }
+
+    #[test]
+    fn test_display_picks_a_readable_unit() {
+        assert_eq!(Measurement::new(0.25, Unit::Milliseconds).display(), "250.000 µs");
+        assert_eq!(Measurement::new(1.25, Unit::Milliseconds).display(), "1.250 ms");
+        assert_eq!(Measurement::new(2500.0, Unit::Microseconds).display(), "2.500 ms");
+        assert_eq!(Measurement::new(1500.0, Unit::Milliseconds).display(), "1.500 s");
+        assert_eq!(Measurement::new(7.24, Unit::Percent).display(), "7.2%");
+        assert_eq!(Measurement::new(13107200.0, Unit::Bytes).display(), "12.5 MiB");
+        assert_eq!(Measurement::new(2048.0, Unit::BytesPerSec).display(), "2.0 KiB/s");
+        assert_eq!(Measurement::new(10.0, Unit::Count).display(), "10");
+        assert_eq!(Unit::Percent.convert(5.0, Unit::Ratio), Some(0.05));
+        assert_eq!(Unit::Bytes.convert(5.0, Unit::Seconds), None);
+    }
+}
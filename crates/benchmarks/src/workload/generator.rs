@@ -0,0 +1,337 @@
+//! Deterministic schema inputs
+//!
+//! [`SchemaWorkloadGenerator`] produces JSON Schema, Avro and Protobuf schema
+//! text from a seed. Each schema is a record of [`fields`](SchemaWorkloadGenerator::with_fields)
+//! fields whose first field nests another record, down to the configured
+//! [`depth`](SchemaWorkloadGenerator::with_depth); field names, types and
+//! optionality are drawn from the seed. The same seed, settings and call
+//! sequence always produce byte-identical schemas, so a run can be replayed
+//! from the seed recorded in its results
+//! ([`BenchmarkResult::seed`](crate::BenchmarkResult::seed)).
+
+use super::SplitMix64;
+use schema_registry_core::SerializationFormat;
+use serde_json::{json, Map, Value};
+use std::fmt::Write;
+
+/// Fields per record unless configured otherwise
+pub const DEFAULT_FIELDS: usize = 8;
+
+/// Levels of nested records below the root unless configured otherwise
+pub const DEFAULT_DEPTH: usize = 2;
+
+/// Namespace (Avro) and package (Protobuf) of generated schemas
+pub const GENERATED_NAMESPACE: &str = "bench.generated";
+
+const WORDS: [&str; 12] = [
+    "id", "name", "amount", "status", "created", "owner", "region", "count", "label", "score", "currency", "source",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Primitive {
+    String,
+    Int,
+    Long,
+    Double,
+    Boolean,
+    Bytes,
+}
+
+const PRIMITIVES: [Primitive; 6] = [
+    Primitive::String,
+    Primitive::Int,
+    Primitive::Long,
+    Primitive::Double,
+    Primitive::Boolean,
+    Primitive::Bytes,
+];
+
+impl Primitive {
+    fn json_schema(self) -> Value {
+        match self {
+            Primitive::String => json!({"type": "string"}),
+            Primitive::Int | Primitive::Long => json!({"type": "integer"}),
+            Primitive::Double => json!({"type": "number"}),
+            Primitive::Boolean => json!({"type": "boolean"}),
+            Primitive::Bytes => json!({"type": "string", "contentEncoding": "base64"}),
+        }
+    }
+
+    fn avro(self) -> &'static str {
+        match self {
+            Primitive::String => "string",
+            Primitive::Int => "int",
+            Primitive::Long => "long",
+            Primitive::Double => "double",
+            Primitive::Boolean => "boolean",
+            Primitive::Bytes => "bytes",
+        }
+    }
+
+    fn protobuf(self) -> &'static str {
+        match self {
+            Primitive::String => "string",
+            Primitive::Int => "int32",
+            Primitive::Long => "int64",
+            Primitive::Double => "double",
+            Primitive::Boolean => "bool",
+            Primitive::Bytes => "bytes",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum FieldType {
+    Primitive(Primitive),
+    Record(Record),
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    name: String,
+    required: bool,
+    kind: FieldType,
+}
+
+#[derive(Debug, Clone)]
+struct Record {
+    name: String,
+    fields: Vec<Field>,
+}
+
+/// Seeded generator of schema text; see the [module docs](self)
+#[derive(Debug)]
+pub struct SchemaWorkloadGenerator {
+    seed: u64,
+    rng: SplitMix64,
+    fields: usize,
+    depth: usize,
+    generated: usize,
+}
+
+impl SchemaWorkloadGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: SplitMix64(seed),
+            fields: DEFAULT_FIELDS,
+            depth: DEFAULT_DEPTH,
+            generated: 0,
+        }
+    }
+
+    /// Put `fields` fields in every record (at least one)
+    pub fn with_fields(mut self, fields: usize) -> Self {
+        self.fields = fields.max(1);
+        self
+    }
+
+    /// Nest records `depth` levels below the root; 0 gives flat records
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// The seed the generator was created with
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The next schema, in `format`
+    pub fn generate(&mut self, format: SerializationFormat) -> String {
+        let root = format!("GeneratedRecord{}", self.generated);
+        self.generated += 1;
+        let record = self.record(root, self.depth);
+        match format {
+            SerializationFormat::JsonSchema => render_json_schema(&record),
+            SerializationFormat::Avro => render_avro(&record),
+            SerializationFormat::Protobuf => render_protobuf(&record),
+        }
+    }
+
+    /// The next schema, as JSON Schema
+    pub fn json_schema(&mut self) -> String {
+        self.generate(SerializationFormat::JsonSchema)
+    }
+
+    /// The next schema, as an Avro record
+    pub fn avro(&mut self) -> String {
+        self.generate(SerializationFormat::Avro)
+    }
+
+    /// The next schema, as a proto3 file
+    pub fn protobuf(&mut self) -> String {
+        self.generate(SerializationFormat::Protobuf)
+    }
+
+    fn pick<T: Copy>(&mut self, choices: &[T]) -> T {
+        let index = (self.rng.next_unit() * choices.len() as f64) as usize;
+        choices[index.min(choices.len() - 1)]
+    }
+
+    fn record(&mut self, name: String, depth: usize) -> Record {
+        let fields = (0..self.fields)
+            .map(|i| {
+                let field_name = format!("{}_{}", self.pick(&WORDS), i);
+                let required = self.rng.next_unit() < 0.5;
+                let kind = if i == 0 && depth > 0 {
+                    FieldType::Record(self.record(format!("{}_{}", name, field_name), depth - 1))
+                } else {
+                    FieldType::Primitive(self.pick(&PRIMITIVES))
+                };
+                Field {
+                    name: field_name,
+                    required,
+                    kind,
+                }
+            })
+            .collect();
+        Record { name, fields }
+    }
+}
+
+fn render_json_schema(record: &Record) -> String {
+    let mut schema = json_object(record);
+    schema["$schema"] = json!("http://json-schema.org/draft-07/schema#");
+    serde_json::to_string_pretty(&schema).expect("generated JSON Schema serializes")
+}
+
+fn json_object(record: &Record) -> Value {
+    let mut properties = Map::new();
+    for field in &record.fields {
+        let value = match &field.kind {
+            FieldType::Primitive(primitive) => primitive.json_schema(),
+            FieldType::Record(nested) => json_object(nested),
+        };
+        properties.insert(field.name.clone(), value);
+    }
+    let required: Vec<&str> = record
+        .fields
+        .iter()
+        .filter(|field| field.required)
+        .map(|field| field.name.as_str())
+        .collect();
+    json!({
+        "title": record.name,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+fn render_avro(record: &Record) -> String {
+    let mut schema = avro_record(record);
+    schema["namespace"] = json!(GENERATED_NAMESPACE);
+    serde_json::to_string_pretty(&schema).expect("generated Avro schema serializes")
+}
+
+fn avro_record(record: &Record) -> Value {
+    let fields: Vec<Value> = record
+        .fields
+        .iter()
+        .map(|field| {
+            let kind = match &field.kind {
+                FieldType::Primitive(primitive) => json!(primitive.avro()),
+                FieldType::Record(nested) => avro_record(nested),
+            };
+            if field.required {
+                json!({"name": field.name, "type": kind})
+            } else {
+                json!({"name": field.name, "type": ["null", kind], "default": null})
+            }
+        })
+        .collect();
+    json!({"type": "record", "name": record.name, "fields": fields})
+}
+
+fn render_protobuf(record: &Record) -> String {
+    let mut out = format!("syntax = \"proto3\";\n\npackage {};\n\n", GENERATED_NAMESPACE);
+    protobuf_message(record, 0, &mut out);
+    out
+}
+
+fn protobuf_message(record: &Record, indent: usize, out: &mut String) {
+    let pad = "  ".repeat(indent);
+    let _ = writeln!(out, "{}message {} {{", pad, record.name);
+    for (number, field) in record.fields.iter().enumerate() {
+        let kind = match &field.kind {
+            FieldType::Primitive(primitive) => primitive.protobuf(),
+            FieldType::Record(nested) => {
+                protobuf_message(nested, indent + 1, out);
+                nested.name.as_str()
+            }
+        };
+        let label = if field.required { "" } else { "optional " };
+        let _ = writeln!(out, "{}  {}{} {} = {};", pad, label, kind, field.name, number + 1);
+    }
+    let _ = writeln!(out, "{}}}", pad);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMATS: [SerializationFormat; 3] = [
+        SerializationFormat::JsonSchema,
+        SerializationFormat::Avro,
+        SerializationFormat::Protobuf,
+    ];
+
+    fn sequence(seed: u64, fields: usize, depth: usize) -> Vec<String> {
+        let mut generator = SchemaWorkloadGenerator::new(seed).with_fields(fields).with_depth(depth);
+        (0..3).flat_map(|_| FORMATS.map(|format| generator.generate(format))).collect()
+    }
+
+    /// Levels of `properties` below the root object
+    fn json_depth(schema: &Value) -> usize {
+        schema["properties"]
+            .as_object()
+            .into_iter()
+            .flat_map(|properties| properties.values())
+            .filter(|property| property.get("properties").is_some())
+            .map(|property| 1 + json_depth(property))
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_same_seed_gives_identical_schemas() {
+        for seed in (0..64).chain([u64::MAX, 0x5EED]) {
+            for (fields, depth) in [(1, 0), (3, 1), (DEFAULT_FIELDS, DEFAULT_DEPTH), (20, 5)] {
+                assert_eq!(
+                    sequence(seed, fields, depth),
+                    sequence(seed, fields, depth),
+                    "seed {seed}, {fields} fields, depth {depth}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_different_seeds_give_different_schemas() {
+        let distinct: std::collections::BTreeSet<_> = (0..32).map(|seed| sequence(seed, 8, 2)).collect();
+        assert_eq!(distinct.len(), 32);
+    }
+
+    #[test]
+    fn test_size_and_depth_are_honoured() {
+        for depth in 0..4 {
+            let mut generator = SchemaWorkloadGenerator::new(7).with_fields(5).with_depth(depth);
+            let json: Value = serde_json::from_str(&generator.json_schema()).unwrap();
+            assert_eq!(json_depth(&json), depth);
+            assert_eq!(json["properties"].as_object().unwrap().len(), 5);
+
+            let avro: Value = serde_json::from_str(&generator.avro()).unwrap();
+            assert_eq!(avro["type"], "record");
+            assert_eq!(avro["fields"].as_array().unwrap().len(), 5);
+
+            let proto = generator.protobuf();
+            assert!(proto.starts_with("syntax = \"proto3\";"));
+            assert_eq!(proto.matches("message ").count(), depth + 1);
+        }
+
+        let small = SchemaWorkloadGenerator::new(1).with_fields(2).json_schema();
+        let large = SchemaWorkloadGenerator::new(1).with_fields(200).json_schema();
+        assert!(large.len() > 20 * small.len());
+    }
+}
@@ -0,0 +1,709 @@
+//! User-defined benchmark workloads
+//!
+//! A workload file describes a sequence of registry operations in YAML, so
+//! performance engineers can model their own traffic without writing Rust:
+//!
+//! ```yaml
+//! version: 1
+//! name: hot_reads
+//! seed: 42            # default 0
+//! concurrency: 4      # default 1; steps may override it
+//! steps:
+//!   - register: { count: 100, size: 10KB }
+//!   - read: { count: 1000, keys: { zipfian: { exponent: 1.2 } } }
+//!   - update: { count: 20 }
+//!   - compat-check: { count: 50, mode: backward }
+//!   - sleep: { duration: 100ms }
+//! ```
+//!
+//! `read`, `update` and `compat-check` pick among the schemas registered by
+//! earlier steps, by `keys`: `uniform` (the default) or `zipfian` with an
+//! exponent, where a few subjects take most of the traffic. Sizes are bytes,
+//! or a number with a `B`, `KB` or `MB` suffix (powers of 1024).
+//!
+//! [`WorkloadPlan::parse`] checks the whole plan up front; an invalid plan
+//! fails with a [`WorkloadError::InvalidStep`] naming the step index and
+//! field. [`WorkloadPlan::phases`] expands the plan into concrete operations
+//! from its seed, so two runs of the same plan and seed perform the same
+//! operations in the same order. The plan is run by
+//! [`CustomWorkloadBenchmark`](crate::adapters::custom_workload::CustomWorkloadBenchmark).
+//!
+//! Schema content itself comes from [`SchemaWorkloadGenerator`], which turns
+//! a seed into reproducible JSON Schema, Avro and Protobuf schemas.
+
+pub mod generator;
+
+use schema_registry_core::CompatibilityMode;
+use serde_yaml::{Mapping, Value};
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+use thiserror::Error;
+
+pub use generator::SchemaWorkloadGenerator;
+
+/// Newest workload format version this crate reads
+pub const WORKLOAD_FORMAT_VERSION: u32 = 1;
+
+/// Error loading or checking a workload plan
+#[derive(Debug, Error, PartialEq)]
+pub enum WorkloadError {
+    #[error("cannot read workload: {0}")]
+    Io(String),
+
+    #[error("invalid workload: {0}")]
+    Parse(String),
+
+    #[error("workload format version {found} is not supported (this runner supports up to {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error("invalid workload: `{field}` {message}")]
+    InvalidPlan { field: String, message: String },
+
+    #[error("invalid workload: steps[{index}].{field} {message}")]
+    InvalidStep { index: usize, field: String, message: String },
+}
+
+/// Kind of operation a step performs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StepKind {
+    Register,
+    Read,
+    Update,
+    CompatCheck,
+    Sleep,
+}
+
+impl StepKind {
+    /// Kinds whose latency is reported, in report order
+    pub const MEASURED: [StepKind; 4] = [StepKind::Register, StepKind::Read, StepKind::Update, StepKind::CompatCheck];
+
+    /// Name used in workload files and reports
+    pub fn name(self) -> &'static str {
+        match self {
+            StepKind::Register => "register",
+            StepKind::Read => "read",
+            StepKind::Update => "update",
+            StepKind::CompatCheck => "compat-check",
+            StepKind::Sleep => "sleep",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            StepKind::Register,
+            StepKind::Read,
+            StepKind::Update,
+            StepKind::CompatCheck,
+            StepKind::Sleep,
+        ]
+        .into_iter()
+        .find(|kind| kind.name() == name)
+    }
+}
+
+impl fmt::Display for StepKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How a step picks among the registered subjects
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum KeyDistribution {
+    /// Every subject equally likely
+    #[default]
+    Uniform,
+    /// Subject `k` (0-based, in registration order) weighted `1 / (k + 1)^exponent`
+    Zipfian { exponent: f64 },
+}
+
+/// One step of a plan
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+    Register {
+        count: usize,
+        size: usize,
+        concurrency: Option<usize>,
+    },
+    Read {
+        count: usize,
+        keys: KeyDistribution,
+        concurrency: Option<usize>,
+    },
+    Update {
+        count: usize,
+        keys: KeyDistribution,
+        concurrency: Option<usize>,
+    },
+    CompatCheck {
+        count: usize,
+        keys: KeyDistribution,
+        mode: CompatibilityMode,
+        concurrency: Option<usize>,
+    },
+    Sleep {
+        duration: Duration,
+    },
+}
+
+impl Step {
+    pub fn kind(&self) -> StepKind {
+        match self {
+            Step::Register { .. } => StepKind::Register,
+            Step::Read { .. } => StepKind::Read,
+            Step::Update { .. } => StepKind::Update,
+            Step::CompatCheck { .. } => StepKind::CompatCheck,
+            Step::Sleep { .. } => StepKind::Sleep,
+        }
+    }
+}
+
+/// A parsed and checked workload
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkloadPlan {
+    /// Name, used in the target id; letters, digits, `_` and `-`
+    pub name: String,
+    /// Seed for key selection
+    pub seed: u64,
+    /// Operations in flight at once, unless a step sets its own
+    pub concurrency: usize,
+    pub steps: Vec<Step>,
+}
+
+/// A single operation of an expanded plan
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+    /// Register subject number `subject` with content of about `size` bytes
+    Register { subject: usize, size: usize },
+    Read { subject: usize },
+    Update { subject: usize },
+    CompatCheck { subject: usize, mode: CompatibilityMode },
+    Sleep(Duration),
+}
+
+/// The operations of one step, with how many run at once
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phase {
+    pub kind: StepKind,
+    pub concurrency: usize,
+    pub operations: Vec<Operation>,
+}
+
+impl WorkloadPlan {
+    /// Read and check the plan in `path`
+    pub fn load(path: &Path) -> Result<Self, WorkloadError> {
+        let text = std::fs::read_to_string(path).map_err(|e| WorkloadError::Io(e.to_string()))?;
+        Self::parse(&text)
+    }
+
+    /// Parse and check a YAML workload
+    pub fn parse(text: &str) -> Result<Self, WorkloadError> {
+        let root: Mapping = serde_yaml::from_str(text).map_err(|e| WorkloadError::Parse(e.to_string()))?;
+        let mut fields = Fields::new(&root, |field: &str, message: String| WorkloadError::InvalidPlan {
+            field: field.to_string(),
+            message,
+        });
+
+        let version = fields.required("version", values::count)?;
+        if version as u32 > WORKLOAD_FORMAT_VERSION {
+            return Err(WorkloadError::UnsupportedVersion {
+                found: version as u32,
+                supported: WORKLOAD_FORMAT_VERSION,
+            });
+        }
+        let name = fields.required("name", values::name)?;
+        let seed = fields.optional("seed", values::seed)?.unwrap_or(0);
+        let concurrency = fields.optional("concurrency", values::count)?.unwrap_or(1);
+        let steps = fields.required("steps", |value: &Value| {
+            value.as_sequence().cloned().ok_or_else(|| "must be a list of steps".to_string())
+        })?;
+        fields.reject_unknown()?;
+
+        if steps.is_empty() {
+            return Err(WorkloadError::InvalidPlan {
+                field: "steps".to_string(),
+                message: "must list at least one step".to_string(),
+            });
+        }
+
+        let mut registered = 0;
+        let steps = steps
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let step = parse_step(index, value, registered)?;
+                if let Step::Register { count, .. } = step {
+                    registered += count;
+                }
+                Ok(step)
+            })
+            .collect::<Result<Vec<_>, WorkloadError>>()?;
+
+        Ok(Self {
+            name,
+            seed,
+            concurrency,
+            steps,
+        })
+    }
+
+    /// Use `seed` instead of the plan's own
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Number of operations each step kind performs in one pass of the plan
+    pub fn operation_count(&self, kind: StepKind) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| step.kind() == kind)
+            .map(|step| match step {
+                Step::Register { count, .. }
+                | Step::Read { count, .. }
+                | Step::Update { count, .. }
+                | Step::CompatCheck { count, .. } => *count,
+                Step::Sleep { .. } => 1,
+            })
+            .sum()
+    }
+
+    /// Expand the plan into concrete operations, choosing keys from the seed
+    pub fn phases(&self) -> Vec<Phase> {
+        let mut rng = SplitMix64(self.seed);
+        let mut registered = 0;
+        self.steps
+            .iter()
+            .map(|step| {
+                let (concurrency, operations) = match *step {
+                    Step::Register { count, size, concurrency } => {
+                        let operations = (registered..registered + count)
+                            .map(|subject| Operation::Register { subject, size })
+                            .collect();
+                        registered += count;
+                        (concurrency, operations)
+                    }
+                    Step::Read { count, keys, concurrency } => {
+                        let sampler = KeySampler::new(keys, registered);
+                        let operations = (0..count)
+                            .map(|_| Operation::Read {
+                                subject: sampler.sample(&mut rng),
+                            })
+                            .collect();
+                        (concurrency, operations)
+                    }
+                    Step::Update { count, keys, concurrency } => {
+                        let sampler = KeySampler::new(keys, registered);
+                        let operations = (0..count)
+                            .map(|_| Operation::Update {
+                                subject: sampler.sample(&mut rng),
+                            })
+                            .collect();
+                        (concurrency, operations)
+                    }
+                    Step::CompatCheck {
+                        count,
+                        keys,
+                        mode,
+                        concurrency,
+                    } => {
+                        let sampler = KeySampler::new(keys, registered);
+                        let operations = (0..count)
+                            .map(|_| Operation::CompatCheck {
+                                subject: sampler.sample(&mut rng),
+                                mode,
+                            })
+                            .collect();
+                        (concurrency, operations)
+                    }
+                    Step::Sleep { duration } => (Some(1), vec![Operation::Sleep(duration)]),
+                };
+                Phase {
+                    kind: step.kind(),
+                    concurrency: concurrency.unwrap_or(self.concurrency),
+                    operations,
+                }
+            })
+            .collect()
+    }
+}
+
+fn parse_step(index: usize, value: &Value, registered: usize) -> Result<Step, WorkloadError> {
+    let invalid = |field: &str, message: String| WorkloadError::InvalidStep {
+        index,
+        field: field.to_string(),
+        message,
+    };
+    let (kind, body) = match value.as_mapping() {
+        Some(step) if step.len() == 1 => step.iter().next().expect("one entry"),
+        _ => {
+            return Err(invalid(
+                "kind",
+                "must be a single-key map such as `read: { count: 10 }`".to_string(),
+            ))
+        }
+    };
+    let name = kind.as_str().unwrap_or_default();
+    let kind = StepKind::from_name(name).ok_or_else(|| {
+        invalid(
+            "kind",
+            format!("`{}` is not a step; expected register, read, update, compat-check or sleep", name),
+        )
+    })?;
+
+    let empty = Mapping::new();
+    let body = match body {
+        Value::Mapping(body) => body,
+        Value::Null => &empty,
+        _ => return Err(invalid(kind.name(), "must be a map of step fields".to_string())),
+    };
+    let mut fields = Fields::new(body, |field: &str, message: String| {
+        invalid(&format!("{}.{}", kind.name(), field), message)
+    });
+
+    if kind == StepKind::Sleep {
+        let duration = fields.required("duration", values::duration)?;
+        fields.reject_unknown()?;
+        return Ok(Step::Sleep { duration });
+    }
+
+    let count = fields.required("count", values::count)?;
+    let concurrency = fields.optional("concurrency", values::count)?;
+    let step = match kind {
+        StepKind::Register => Step::Register {
+            count,
+            size: fields.optional("size", values::size)?.unwrap_or(1024),
+            concurrency,
+        },
+        _ => {
+            let keys = fields.optional("keys", values::keys)?.unwrap_or_default();
+            if registered == 0 {
+                return Err(invalid(
+                    &format!("{}.keys", kind.name()),
+                    "has no schemas to pick from; add a register step before it".to_string(),
+                ));
+            }
+            match kind {
+                StepKind::Read => Step::Read {
+                    count,
+                    keys,
+                    concurrency,
+                },
+                StepKind::Update => Step::Update {
+                    count,
+                    keys,
+                    concurrency,
+                },
+                _ => Step::CompatCheck {
+                    count,
+                    keys,
+                    mode: fields.optional("mode", values::mode)?.unwrap_or(CompatibilityMode::Backward),
+                    concurrency,
+                },
+            }
+        }
+    };
+    fields.reject_unknown()?;
+    Ok(step)
+}
+
+/// Reads the fields of one map, tracking which were consumed
+struct Fields<'a, E> {
+    map: &'a Mapping,
+    seen: Vec<&'static str>,
+    error: E,
+}
+
+impl<'a, E> Fields<'a, E>
+where
+    E: Fn(&str, String) -> WorkloadError,
+{
+    fn new(map: &'a Mapping, error: E) -> Self {
+        Self {
+            map,
+            seen: Vec::new(),
+            error,
+        }
+    }
+
+    fn optional<T>(
+        &mut self,
+        field: &'static str,
+        parse: impl FnOnce(&Value) -> Result<T, String>,
+    ) -> Result<Option<T>, WorkloadError> {
+        self.seen.push(field);
+        match self.map.get(field) {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => parse(value).map(Some).map_err(|message| (self.error)(field, message)),
+        }
+    }
+
+    fn required<T>(
+        &mut self,
+        field: &'static str,
+        parse: impl FnOnce(&Value) -> Result<T, String>,
+    ) -> Result<T, WorkloadError> {
+        self.optional(field, parse)?
+            .ok_or_else(|| (self.error)(field, "is required".to_string()))
+    }
+
+    fn reject_unknown(&self) -> Result<(), WorkloadError> {
+        for key in self.map.keys() {
+            let key = key.as_str().unwrap_or_default();
+            if !self.seen.iter().any(|seen| *seen == key) {
+                return Err((self.error)(key, format!("is not a known field (expected one of {})", self.seen.join(", "))));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parsers of single field values, returning the message of a field error
+mod values {
+    use super::*;
+
+    pub(super) fn count(value: &Value) -> Result<usize, String> {
+        match value.as_u64() {
+            Some(n) if n >= 1 => Ok(n as usize),
+            _ => Err("must be a whole number of at least 1".to_string()),
+        }
+    }
+
+    pub(super) fn seed(value: &Value) -> Result<u64, String> {
+        value.as_u64().ok_or_else(|| "must be a non-negative whole number".to_string())
+    }
+
+    pub(super) fn name(value: &Value) -> Result<String, String> {
+        match value.as_str() {
+            Some(name)
+                if !name.is_empty()
+                    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') =>
+            {
+                Ok(name.to_string())
+            }
+            _ => Err("must be non-empty and use only letters, digits, `_` and `-`".to_string()),
+        }
+    }
+
+    pub(super) fn size(value: &Value) -> Result<usize, String> {
+        let bytes = match value {
+            Value::Number(n) => n.as_u64(),
+            Value::String(text) => parse_size(text),
+            _ => None,
+        };
+        match bytes {
+            Some(bytes) if bytes >= 1 => Ok(bytes as usize),
+            _ => Err("must be a size such as 512, 512B, 10KB or 1MB".to_string()),
+        }
+    }
+
+    pub(super) fn duration(value: &Value) -> Result<Duration, String> {
+        value
+            .as_str()
+            .and_then(parse_duration)
+            .ok_or_else(|| "must be a duration such as 500ms, 2s or 1m".to_string())
+    }
+
+    pub(super) fn mode(value: &Value) -> Result<CompatibilityMode, String> {
+        value
+            .as_str()
+            .and_then(|mode| serde_yaml::from_value(Value::String(mode.to_ascii_uppercase())).ok())
+            .ok_or_else(|| "must be a compatibility mode such as backward, forward or full_transitive".to_string())
+    }
+
+    pub(super) fn keys(value: &Value) -> Result<KeyDistribution, String> {
+        const EXPECTED: &str = "must be `uniform` or `{ zipfian: { exponent: <number> } }`";
+        if value.as_str() == Some("uniform") {
+            return Ok(KeyDistribution::Uniform);
+        }
+        let exponent = value
+            .get("zipfian")
+            .and_then(|zipfian| zipfian.get("exponent"))
+            .and_then(Value::as_f64)
+            .ok_or_else(|| EXPECTED.to_string())?;
+        if !(exponent > 0.0 && exponent.is_finite()) {
+            return Err("zipfian exponent must be greater than 0".to_string());
+        }
+        Ok(KeyDistribution::Zipfian { exponent })
+    }
+}
+
+/// Parse `512`, `512B`, `10KB` or `1MB` (powers of 1024)
+fn parse_size(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (digits, unit) = text.split_at(split);
+    let multiplier = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "KB" | "KIB" => 1024,
+        "MB" | "MIB" => 1024 * 1024,
+        _ => return None,
+    };
+    digits.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+/// Parse `500ms`, `2s`, `1m` (bare numbers are seconds)
+fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (digits, unit) = text.split_at(split);
+    let value: u64 = digits.parse().ok()?;
+    match unit {
+        "ms" => Some(Duration::from_millis(value)),
+        "" | "s" => Some(Duration::from_secs(value)),
+        "m" => Some(Duration::from_secs(value * 60)),
+        _ => None,
+    }
+}
+
+/// Picks subjects `0..subjects` by a [`KeyDistribution`]
+struct KeySampler {
+    subjects: usize,
+    /// Cumulative weights for zipfian sampling, normalised to end at 1
+    cdf: Option<Vec<f64>>,
+}
+
+impl KeySampler {
+    fn new(keys: KeyDistribution, subjects: usize) -> Self {
+        let cdf = match keys {
+            KeyDistribution::Uniform => None,
+            KeyDistribution::Zipfian { exponent } => {
+                let mut total = 0.0;
+                let mut cdf: Vec<f64> = (0..subjects)
+                    .map(|k| {
+                        total += 1.0 / ((k + 1) as f64).powf(exponent);
+                        total
+                    })
+                    .collect();
+                for weight in &mut cdf {
+                    *weight /= total;
+                }
+                Some(cdf)
+            }
+        };
+        Self { subjects, cdf }
+    }
+
+    fn sample(&self, rng: &mut SplitMix64) -> usize {
+        let unit = rng.next_unit();
+        match &self.cdf {
+            None => ((unit * self.subjects as f64) as usize).min(self.subjects - 1),
+            Some(cdf) => cdf.partition_point(|&weight| weight <= unit).min(self.subjects - 1),
+        }
+    }
+}
+
+/// SplitMix64, a small seedable generator with a fixed output sequence
+#[derive(Debug, Clone)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`
+    fn next_unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN: &str = r#"
+version: 1
+name: hot_reads
+seed: 7
+concurrency: 4
+steps:
+  - register: { count: 100, size: 10KB }
+  - read: { count: 1000, keys: { zipfian: { exponent: 1.2 } } }
+  - update: { count: 20, concurrency: 1 }
+  - compat-check: { count: 50, mode: full_transitive }
+  - sleep: { duration: 5ms }
+"#;
+
+    #[test]
+    fn test_parse_plan() {
+        let plan = WorkloadPlan::parse(PLAN).unwrap();
+        assert_eq!(plan.name, "hot_reads");
+        assert_eq!(plan.seed, 7);
+        assert_eq!(
+            plan.steps[0],
+            Step::Register {
+                count: 100,
+                size: 10 * 1024,
+                concurrency: None
+            }
+        );
+        assert_eq!(
+            plan.steps[3],
+            Step::CompatCheck {
+                count: 50,
+                keys: KeyDistribution::Uniform,
+                mode: CompatibilityMode::FullTransitive,
+                concurrency: None
+            }
+        );
+        assert_eq!(plan.steps[4], Step::Sleep { duration: Duration::from_millis(5) });
+        assert_eq!(plan.operation_count(StepKind::Read), 1000);
+
+        let phases = plan.phases();
+        assert_eq!(phases[1].concurrency, 4);
+        assert_eq!(phases[2].concurrency, 1);
+    }
+
+    #[test]
+    fn test_same_seed_gives_same_operations() {
+        let plan = WorkloadPlan::parse(PLAN).unwrap();
+        assert_eq!(plan.phases(), plan.phases());
+        assert_ne!(plan.phases(), plan.clone().with_seed(8).phases());
+    }
+
+    #[test]
+    fn test_zipfian_keys_favour_the_first_subjects() {
+        let plan = WorkloadPlan::parse(PLAN).unwrap();
+        let reads = &plan.phases()[1].operations;
+        let hot = reads
+            .iter()
+            .filter(|op| matches!(op, Operation::Read { subject } if *subject < 10))
+            .count();
+        // Exponent 1.2 over 100 subjects puts about 70% of reads on the first 10
+        assert!(hot > 600, "{} of {} reads hit the first 10 subjects", hot, reads.len());
+        assert!(reads.iter().all(|op| matches!(op, Operation::Read { subject } if *subject < 100)));
+    }
+
+    #[test]
+    fn test_invalid_steps_name_index_and_field() {
+        let cases = [
+            ("- read: { count: 5 }", "steps[0].read.keys has no schemas to pick from"),
+            ("- register: { count: 0 }", "steps[0].register.count must be a whole number of at least 1"),
+            ("- register: { count: 1 }\n  - read: { count: 1, keys: { zipfian: { exponent: -1 } } }", "steps[1].read.keys zipfian exponent must be greater than 0"),
+            ("- register: { count: 1, size: 10XB }", "steps[0].register.size must be a size"),
+            ("- register: { count: 1, colour: red }", "steps[0].register.colour is not a known field"),
+            ("- sleep: {}", "steps[0].sleep.duration is required"),
+            ("- scan: { count: 1 }", "steps[0].kind `scan` is not a step"),
+        ];
+        for (steps, expected) in cases {
+            let text = format!("version: 1\nname: broken\nsteps:\n  {}\n", steps);
+            let err = WorkloadPlan::parse(&text).unwrap_err().to_string();
+            assert!(err.contains(expected), "{:?}: {}", steps, err);
+        }
+    }
+
+    #[test]
+    fn test_plan_level_errors() {
+        let err = WorkloadPlan::parse("version: 2\nname: x\nsteps: []").unwrap_err();
+        assert_eq!(err, WorkloadError::UnsupportedVersion { found: 2, supported: 1 });
+
+        let err = WorkloadPlan::parse("version: 1\nname: has spaces\nsteps: []").unwrap_err();
+        assert!(err.to_string().contains("`name` must be non-empty"), "{}", err);
+    }
+}
@@ -0,0 +1,137 @@
+//! Error codes returned in HTTP error bodies
+//!
+//! Every `code` the REST layer puts in an error response has an entry here, so
+//! the generated error reference covers the HTTP surface as well as the core
+//! library errors.
+
+use schema_registry_core::error_catalog::{ErrorCategory::*, ErrorEntry};
+
+/// Whether `code` has an entry in [`API_ERRORS`]
+pub fn is_api_error_code(code: &str) -> bool {
+    API_ERRORS.iter().any(|entry| entry.code == code)
+}
+
+/// Catalog entries for every code returned in an HTTP error body
+pub const API_ERRORS: &[ErrorEntry] = &[
+    ErrorEntry {
+        code: "BAD_REQUEST",
+        category: Validation,
+        retryable: false,
+        description: "The request was malformed or a parameter was invalid.",
+        remediation: "Fix the request as described in the message.",
+    },
+    ErrorEntry {
+        code: "NOT_FOUND",
+        category: NotFound,
+        retryable: false,
+        description: "The requested schema or resource does not exist.",
+        remediation: "Check the id or subject in the request path.",
+    },
+    ErrorEntry {
+        code: "CONFLICT",
+        category: Conflict,
+        retryable: false,
+        description: "The request conflicts with the current state of the resource.",
+        remediation: "Fetch the current state and retry with an updated request.",
+    },
+    ErrorEntry {
+        code: "UNAUTHORIZED",
+        category: Security,
+        retryable: false,
+        description: "The request has no valid credentials.",
+        remediation: "Send a valid API key or bearer token.",
+    },
+    ErrorEntry {
+        code: "FORBIDDEN",
+        category: Security,
+        retryable: false,
+        description: "The credentials do not allow this operation.",
+        remediation: "Ask an administrator for the required permission.",
+    },
+    ErrorEntry {
+        code: "INTERNAL_ERROR",
+        category: Internal,
+        retryable: false,
+        description: "The server failed unexpectedly while handling the request.",
+        remediation: "Report it with the request id; server logs have the cause.",
+    },
+    ErrorEntry {
+        code: "SERVICE_UNAVAILABLE",
+        category: Availability,
+        retryable: true,
+        description: "The server or one of its dependencies is temporarily unavailable.",
+        remediation: "Retry with backoff; check /health for the failing component.",
+    },
+    ErrorEntry {
+        code: "INCOMPATIBLE_SCHEMA",
+        category: Compatibility,
+        retryable: false,
+        description: "The schema breaks compatibility with earlier versions of the subject.",
+        remediation: "Review the reported violations or change the subject's compatibility mode.",
+    },
+    ErrorEntry {
+        code: "INVALID_SCHEMA",
+        category: Validation,
+        retryable: false,
+        description: "The schema content is not valid for its declared format.",
+        remediation: "Validate the schema locally with `schema validate`.",
+    },
+    ErrorEntry {
+        code: "VALIDATION_FAILED",
+        category: Validation,
+        retryable: false,
+        description: "The data did not validate against the schema.",
+        remediation: "Check the listed errors against the schema.",
+    },
+    ErrorEntry {
+        code: "RATE_LIMIT_EXCEEDED",
+        category: Availability,
+        retryable: true,
+        description: "The client sent more requests than its rate limit allows.",
+        remediation: "Back off and retry after the interval in the Retry-After header.",
+    },
+    ErrorEntry {
+        code: "PAYLOAD_TOO_LARGE",
+        category: Validation,
+        retryable: false,
+        description: "The request body exceeded the server's schema size limit.",
+        remediation: "Split the schema or raise MAX_SCHEMA_SIZE_BYTES on the server.",
+    },
+    ErrorEntry {
+        code: "DATABASE_ERROR",
+        category: Storage,
+        retryable: true,
+        description: "The server could not read or write the schema database.",
+        remediation: "Check PostgreSQL health; transient failures succeed on retry.",
+    },
+    ErrorEntry {
+        code: "CACHE_ERROR",
+        category: Storage,
+        retryable: true,
+        description: "The server could not reach its Redis cache.",
+        remediation: "Check Redis health; transient failures succeed on retry.",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schema_registry_core::error_catalog::{ErrorCatalog, CORE_ERRORS};
+
+    #[test]
+    fn test_api_codes_do_not_clash_with_core_codes() {
+        let catalog = ErrorCatalog::new()
+            .register(CORE_ERRORS)
+            .register(API_ERRORS);
+        assert_eq!(
+            catalog.entries().len(),
+            CORE_ERRORS.len() + API_ERRORS.len()
+        );
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert!(is_api_error_code("PAYLOAD_TOO_LARGE"));
+        assert!(!is_api_error_code("SCHEMA_NOT_FOUND"));
+    }
+}
@@ -1,4 +1,5 @@
 //! API layer: REST (Axum) and gRPC (Tonic)
+pub mod error_codes;
 pub mod rest;
 pub mod grpc;
 
@@ -0,0 +1,52 @@
+//! MSRV and minimal-versions smoke binary
+//!
+//! Exercises the public API surface of core, validation, and benchmarks so
+//! that building this binary with the MSRV toolchain (`cargo +1.82 build`) and
+//! with `-Z minimal-versions` dependency resolution catches accidental reliance
+//! on newer language features or newer dependency APIs. See `make msrv-check`.
+
+use schema_registry_benchmarks::{adapters, markdown, BenchmarkResult};
+use schema_registry_core::buildinfo;
+use schema_registry_core::examples::{self, ExampleOptions};
+use schema_registry_core::{CompatibilityMode, RegisteredSchema, SemanticVersion, SerializationFormat};
+use schema_registry_validation::engine::ValidationEngine;
+use schema_registry_validation::types::SchemaFormat;
+
+const SCHEMA: &str = r#"{"type": "object", "required": ["id"], "properties": {"id": {"type": "string"}}}"#;
+
+#[tokio::main]
+async fn main() {
+    let info = buildinfo::build_info();
+    println!(
+        "schema-registry {} (msrv {}, built with rustc {})",
+        info.version, info.msrv, info.rustc_version
+    );
+
+    // Core
+    let version: SemanticVersion = "1.2.3".parse().expect("valid semantic version");
+    assert_eq!(version.to_string(), "1.2.3");
+    assert!(CompatibilityMode::FullTransitive.is_transitive());
+    assert_eq!(RegisteredSchema::calculate_content_hash(SCHEMA).len(), 64);
+    let example = examples::generate(SCHEMA, SerializationFormat::JsonSchema, &ExampleOptions::with_seed(1))
+        .expect("example generation");
+    assert!(example.value.get("id").is_some());
+
+    // Validation
+    let engine = ValidationEngine::new();
+    let result = engine
+        .validate(SCHEMA, SchemaFormat::JsonSchema)
+        .await
+        .expect("validation runs");
+    assert!(result.is_valid);
+
+    // Benchmarks
+    let targets = adapters::all_targets();
+    assert!(!targets.is_empty());
+    let results = vec![BenchmarkResult::new(
+        "msrv_check".to_string(),
+        serde_json::json!({"duration_ms": 1}),
+    )];
+    assert!(markdown::generate_summary(&results).contains("msrv_check"));
+
+    println!("public API smoke check passed");
+}
@@ -1,62 +1,1138 @@
 //! Benchmark command implementation
 
 use crate::config::Config;
-use crate::error::Result;
-use crate::output::OutputFormat;
-use clap::Subcommand;
+use crate::error::{CliError, Result};
+use crate::output::{self, OutputFormat};
+use clap::{Subcommand, ValueEnum};
 use colored::Colorize;
-use schema_registry_benchmarks::{io, markdown, run_all_benchmarks};
+use futures::StreamExt;
+use indicatif::{ProgressBar, ProgressStyle};
+use schema_registry_benchmarks::adapters::custom_workload::CustomWorkloadBenchmark;
+use schema_registry_benchmarks::adapters::{self, DEFAULT_MEASURED_ITERATIONS, DEFAULT_WARMUP_ITERATIONS};
+use schema_registry_benchmarks::baseline::{default_baselines_dir, read_results, Baseline, BaselineStore};
+use schema_registry_benchmarks::compare::{
+    compare_results, compare_results_with_threshold, GateResult, GateViolation, RegressionReport, RegressionThresholds,
+    DEFAULT_MAX_REGRESSION_PERCENT, DEFAULT_THRESHOLD_PERCENT,
+};
+use schema_registry_benchmarks::io::{DuplicatePolicy, FilenameTemplate, JunitFailure, OutputLayout, RetentionPolicy};
+use schema_registry_benchmarks::markdown::SummaryOptions;
+use schema_registry_benchmarks::report::{self, HtmlOptions};
+use schema_registry_benchmarks::observer::{IterationSample, RunSummary, TargetOutcome};
+use schema_registry_benchmarks::runner::DEFAULT_TARGET_TIMEOUT;
+use schema_registry_benchmarks::{
+    io, markdown, run_shard_with_progress, shape, shard, trends, BenchConfig, BenchProgress, BenchRegistry, BenchmarkFilter,
+    BenchmarkResult, BenchmarkRun, BenchmarkRunConfig, CancellationToken, NdjsonObserver, OutlierPolicy, ProgressObserver, ResultSink, RunFile, RunMode, Runner, Shard,
+    StorageBackend, WorkloadPlan,
+};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Pushgateway job of `--push-gateway` unless `--push-job` names another
+const DEFAULT_PUSH_JOB: &str = "schema-registry-benchmarks";
+
+/// Runs shown in the summary's trend charts, this one included
+const TREND_CHART_RUNS: usize = 10;
+
+/// How `benchmark run` reports progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProgressMode {
+    /// Bar when stderr is a terminal, otherwise lines
+    Auto,
+    /// Progress bar on stderr
+    Bar,
+    /// One status line per target
+    Lines,
+    /// One JSON event per line on stderr
+    Ndjson,
+}
+
+/// Extra result files `benchmark run` writes next to the summary and raw JSON
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// results.csv, one row per target with a column per metric
+    Csv,
+    /// report.html, a standalone page with sortable tables
+    Html,
+}
 
 #[derive(Subcommand)]
 pub enum BenchmarkCommand {
     /// Run all benchmarks and generate reports
     Run {
         /// Output directory for benchmark results
-        #[arg(short, long, default_value = "benchmarks/output")]
+        #[arg(short, long, env = "BENCHMARK_OUTPUT_DIR", default_value = "benchmarks/output")]
         output_dir: String,
 
         /// Skip writing to disk (dry run)
         #[arg(long)]
         dry_run: bool,
+
+        /// Also write the results in these formats (comma-separated)
+        #[arg(long = "format", value_name = "FORMAT", value_delimiter = ',', conflicts_with = "shard")]
+        formats: Vec<ExportFormat>,
+
+        /// Also write OUTPUT_DIR/report.html; same as --format html
+        #[arg(long, conflicts_with = "shard")]
+        html: bool,
+
+        /// Also append the results to OUTPUT_DIR/history.jsonl, one result per line
+        #[arg(long, conflicts_with = "shard")]
+        history: bool,
+
+        /// Write the timestamped raw results as .json.gz (needs the gzip feature); latest.json stays plain
+        #[arg(long, conflicts_with = "shard")]
+        gzip: bool,
+
+        /// Write each target's detailed metrics and history to OUTPUT_DIR/targets/<id>.md and link them from the summary
+        #[arg(long, conflicts_with = "shard")]
+        target_pages: bool,
+
+        /// Also write summary.md converted to HTML as OUTPUT_DIR/summary.html, and the target pages too (needs the markdown-html feature)
+        #[arg(long, conflicts_with = "shard")]
+        summary_html: bool,
+
+        /// Target ids or glob patterns to run (comma-separated, prefix with ! to exclude)
+        #[arg(long, value_delimiter = ',')]
+        targets: Vec<String>,
+
+        /// Run only targets with one of these tags (e.g. fast)
+        #[arg(long = "tag", value_delimiter = ',')]
+        tags: Vec<String>,
+
+        /// Skip targets with any of these tags (e.g. slow)
+        #[arg(long = "exclude-tag", value_delimiter = ',')]
+        exclude_tags: Vec<String>,
+
+        /// Run up to N targets concurrently (timings may include contention)
+        #[arg(long, value_name = "N")]
+        parallel: Option<usize>,
+
+        /// Abandon a target that runs longer than this (e.g. 90s, 5m, 500ms)
+        #[arg(long, value_name = "DURATION", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+
+        /// Iterations run per target before measuring; their samples are discarded
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_WARMUP_ITERATIONS)]
+        warmup: usize,
+
+        /// Measured iterations per target
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_MEASURED_ITERATIONS)]
+        iterations: usize,
+
+        /// Sample each operation for this long instead of a fixed --iterations (e.g. 2s, 500ms)
+        ///
+        /// Warmup iterations still run first and don't count against the budget.
+        #[arg(long, value_name = "DURATION", value_parser = parse_timeout, conflicts_with = "iterations")]
+        sample_time: Option<Duration>,
+
+        /// Drop outlying samples before computing statistics: none, iqr[:K] or mad[:THRESHOLD]
+        ///
+        /// The number of dropped samples is reported as outliers_rejected.
+        #[arg(long, value_name = "POLICY", default_value = "none")]
+        outliers: OutlierPolicy,
+
+        /// Run only shard INDEX of COUNT and write OUTPUT_DIR/shard<INDEX>.json
+        #[arg(long, value_name = "INDEX/COUNT")]
+        shard: Option<Shard>,
+
+        /// Run id shared by all shards of one CI run (defaults to a random id)
+        #[arg(long)]
+        run_id: Option<String>,
+
+        /// Benchmark profile name recorded with sharded results
+        #[arg(long, default_value = "default")]
+        profile: String,
+
+        /// Seed for generated inputs, recorded with every result; shards of one run must agree
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Where storage_operations persists schemas: memory, disk (a temporary
+        /// directory) or simulated (fixed sleeps, for smoke runs)
+        #[arg(long, value_name = "BACKEND", env = "BENCH_STORAGE_BACKEND", default_value = "memory")]
+        storage_backend: StorageBackend,
+
+        /// Progress reporting (sharded runs always print lines)
+        #[arg(long, value_enum, default_value = "auto")]
+        progress: ProgressMode,
+
+        /// Also run the workload plan in FILE as target `workload_<name>` (repeatable)
+        #[arg(long = "workload", value_name = "FILE", conflicts_with = "shard")]
+        workloads: Vec<PathBuf>,
+
+        /// Compare the results against a results file (e.g. latest.json from main) or a saved baseline
+        #[arg(long, value_name = "FILE", conflicts_with = "shard")]
+        baseline: Option<PathBuf>,
+
+        /// Also write a JUnit XML report to FILE: a testcase per target, failed targets and --baseline regressions as failures
+        #[arg(long, value_name = "FILE", conflicts_with = "shard")]
+        junit: Option<PathBuf>,
+
+        /// Push the results as gauges to the Prometheus Pushgateway at URL (needs the http-push feature)
+        #[arg(long, value_name = "URL", conflicts_with = "shard")]
+        push_gateway: Option<String>,
+
+        /// Pushgateway job the results are grouped under
+        #[arg(long, value_name = "JOB", default_value = DEFAULT_PUSH_JOB, requires = "push_gateway")]
+        push_job: String,
+
+        /// Extra Pushgateway grouping label, e.g. `branch=main` (repeatable or comma-separated)
+        #[arg(long = "push-label", value_name = "NAME=VALUE", value_delimiter = ',', value_parser = parse_label, requires = "push_gateway")]
+        push_labels: Vec<(String, String)>,
+
+        /// User name for the Pushgateway's basic auth
+        #[arg(long, value_name = "USER", env = "PUSHGATEWAY_USER")]
+        push_user: Option<String>,
+
+        /// Password for the Pushgateway's basic auth
+        #[arg(long, value_name = "PASSWORD", env = "PUSHGATEWAY_PASSWORD", hide_env_values = true)]
+        push_password: Option<String>,
+
+        /// Give up on the push after DURATION, e.g. `30s`
+        #[arg(long, value_name = "DURATION", value_parser = parse_timeout, requires = "push_gateway")]
+        push_timeout: Option<Duration>,
+
+        /// Name of the timestamped results file, with `{prefix}`, `{timestamp}`, `{git_sha}`, `{branch}` and `{hostname}` placeholders
+        #[arg(long, value_name = "TEMPLATE", conflicts_with = "shard")]
+        filename_template: Option<FilenameTemplate>,
+
+        /// How long to wait for another run writing to the same output directory
+        #[arg(long, value_name = "DURATION", value_parser = parse_timeout)]
+        lock_timeout: Option<Duration>,
+
+        /// Archive the raw results to object storage, e.g. `s3://bucket/prefix` (needs the object-store feature)
+        #[arg(long, value_name = "URL", conflicts_with = "shard")]
+        archive_url: Option<String>,
+
+        /// Key of the archived results under the URL's prefix, with `{date}`, `{timestamp}`, `{git_sha}` and `{branch}` placeholders
+        #[arg(long, value_name = "TEMPLATE", requires = "archive_url")]
+        archive_key: Option<String>,
+
+        /// Also archive the markdown summary next to the results
+        #[arg(long, requires = "archive_url")]
+        archive_summary: bool,
+
+        /// Render summary.md from this minijinja template instead of the built-in layout (needs the templates feature)
+        #[arg(long, value_name = "FILE", conflicts_with = "shard")]
+        report_template: Option<PathBuf>,
+
+        /// Largest regression of any metric against --baseline, in percent
+        #[arg(long, value_name = "PERCENT", default_value_t = DEFAULT_MAX_REGRESSION_PERCENT)]
+        threshold: f64,
+
+        /// Exit with an error when a metric regressed by more than --threshold
+        #[arg(long, requires = "baseline")]
+        fail_on_regression: bool,
+
+        /// Compare CPU time instead of wall time for targets with `resource_usage` on both sides
+        ///
+        /// Results only carry CPU time when built with the rusage-metrics feature.
+        #[arg(long, requires = "baseline")]
+        prefer_cpu_time: bool,
+    },
+
+    /// Merge shard outputs, or results files from several machines, into one run
+    ///
+    /// With --inputs, shard run files are merged into a run file. With
+    /// results files as arguments (e.g. each machine's latest.json), their
+    /// results are concatenated and the markdown summary is regenerated.
+    Merge {
+        /// Results files to combine
+        #[arg(value_name = "FILE", conflicts_with = "inputs")]
+        files: Vec<PathBuf>,
+
+        /// Shard output files (e.g. shard*.json)
+        #[arg(long, num_args = 1.., required_unless_present = "files")]
+        inputs: Vec<String>,
+
+        /// Merged run or results file to write
+        #[arg(long)]
+        out: String,
+
+        /// Also write a markdown summary of the merged run; for results files it defaults to OUT with an .md extension
+        #[arg(long)]
+        summary: Option<String>,
+
+        /// Target ids found in several results files: `error`, or `suffix` to rename each copy `<id>@<file stem>`
+        #[arg(long, value_name = "POLICY", default_value = "error")]
+        on_duplicate: DuplicatePolicy,
     },
 
+    /// Delete old timestamped results from OUTPUT_DIR/raw
+    ///
+    /// Only benchmarks_<timestamp>.json(.gz) files are deleted, never
+    /// latest.json, baselines or other files. A file is kept if --keep or
+    /// --keep-newer-than keeps it.
+    Prune {
+        /// Output directory the results were written to
+        #[arg(short, long, env = "BENCHMARK_OUTPUT_DIR", default_value = "benchmarks/output")]
+        output_dir: String,
+
+        /// Keep the newest N runs
+        #[arg(long, value_name = "N", required_unless_present = "keep_newer_than")]
+        keep: Option<usize>,
+
+        /// Keep runs younger than AGE (e.g. 30d, 12h)
+        #[arg(long, value_name = "AGE", value_parser = parse_age)]
+        keep_newer_than: Option<Duration>,
+
+        /// Only list the files that would be deleted
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Delete without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Check the files listed in DIR/SHA256SUMS against their recorded checksums
+    Verify {
+        /// Output directory the results were written to
+        #[arg(long, env = "BENCHMARK_OUTPUT_DIR", default_value = "benchmarks/output")]
+        dir: PathBuf,
+    },
+
+    /// List recent runs from OUTPUT_DIR/manifest.json, newest first
+    History {
+        /// Output directory the results were written to
+        #[arg(short, long, env = "BENCHMARK_OUTPUT_DIR", default_value = "benchmarks/output")]
+        output_dir: String,
+
+        /// Number of runs to list
+        #[arg(short = 'n', long, value_name = "N", default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Compare two results files metric by metric
+    ///
+    /// Prints the markdown comparison, or the report as JSON or YAML with
+    /// --output. Unlike --baseline on `benchmark run`, regressions don't fail
+    /// the command.
+    Compare {
+        /// Baseline results (e.g. latest.json from main) or a saved baseline
+        baseline: PathBuf,
+
+        /// Results to compare against the baseline
+        current: PathBuf,
+
+        /// Write the markdown comparison to FILE instead of printing it
+        #[arg(long, value_name = "FILE")]
+        markdown: Option<PathBuf>,
+
+        /// Changes smaller than this percentage count as unchanged
+        #[arg(long, value_name = "PERCENT", default_value_t = DEFAULT_THRESHOLD_PERCENT)]
+        threshold: f64,
+    },
+
+    /// List metric paths added, removed or retyped per target between two result files
+    ///
+    /// Exits with an error when a target's metrics changed shape without a
+    /// bump of its version.
+    SchemaDrift {
+        /// Baseline results (e.g. a previous latest.json)
+        #[arg(long)]
+        baseline: String,
+
+        /// Results to check against the baseline
+        #[arg(long)]
+        current: String,
+    },
+
+    /// Manage named baselines under benchmarks/output/baselines
+    #[command(subcommand)]
+    Baseline(BaselineCommand),
+
+    /// Convert a results file for other tools
+    #[command(subcommand)]
+    Export(ExportCommand),
+
     /// List available benchmark targets
     List,
 }
 
+/// `benchmark export` subcommands
+#[derive(Subcommand)]
+pub enum ExportCommand {
+    /// Write the metrics as Prometheus gauges in the OpenMetrics text format
+    Prometheus {
+        /// Results to export (a results file or a saved baseline)
+        #[arg(long, default_value = "benchmarks/output/raw/latest.json")]
+        results: PathBuf,
+
+        /// File to write, e.g. for the node exporter textfile collector
+        #[arg(long)]
+        out: PathBuf,
+    },
+}
+
+/// `benchmark baseline` subcommands
+#[derive(Subcommand)]
+pub enum BaselineCommand {
+    /// Save a results file as a named baseline
+    Save {
+        /// Baseline name (letters, digits, '-', '_' and '.')
+        name: String,
+
+        /// Results to save
+        #[arg(long, default_value = "benchmarks/output/raw/latest.json")]
+        results: PathBuf,
+
+        /// Note stored with the baseline
+        #[arg(long)]
+        note: Option<String>,
+
+        /// Baselines directory
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// List saved baselines
+    List {
+        /// Baselines directory
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Show a baseline's metadata and results
+    Show {
+        name: String,
+
+        /// Baselines directory
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Promote a run's results file to a named baseline, recording where it came from
+    Promote {
+        /// Results file of the run (e.g. benchmarks/output/raw/benchmarks_20250601_120000.json)
+        from_run: PathBuf,
+
+        /// Baseline to create or replace
+        to_name: String,
+
+        /// Note stored with the baseline
+        #[arg(long)]
+        note: Option<String>,
+
+        /// Baselines directory
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
 pub async fn execute(cmd: BenchmarkCommand, _config: &Config, output: OutputFormat) -> Result<()> {
     match cmd {
-        BenchmarkCommand::Run { output_dir, dry_run } => {
-            run_benchmarks(&output_dir, dry_run, output).await
+        BenchmarkCommand::Run {
+            output_dir,
+            dry_run,
+            mut formats,
+            html,
+            history,
+            gzip,
+            target_pages,
+            summary_html,
+            targets,
+            tags,
+            exclude_tags,
+            parallel,
+            timeout,
+            warmup,
+            iterations,
+            sample_time,
+            outliers,
+            shard,
+            run_id,
+            profile,
+            seed,
+            storage_backend,
+            progress,
+            workloads,
+            baseline,
+            junit,
+            push_gateway,
+            push_job,
+            push_labels,
+            push_user,
+            push_password,
+            push_timeout,
+            filename_template,
+            lock_timeout,
+            archive_url,
+            archive_key,
+            archive_summary,
+            report_template,
+            threshold,
+            fail_on_regression,
+            prefer_cpu_time,
+        } => {
+            let filter = tags.into_iter().fold(BenchmarkFilter::from_specs(&targets), BenchmarkFilter::with_tag);
+            let filter = exclude_tags.into_iter().fold(filter, BenchmarkFilter::with_exclude_tag);
+            let config = BenchmarkRunConfig::default()
+                .with_mode(run_mode(parallel)?)
+                .with_target_timeout(timeout.unwrap_or(DEFAULT_TARGET_TIMEOUT))
+                .with_bench_config(
+                    bench_config(warmup, iterations, sample_time)?
+                        .with_outliers(outliers)
+                        .with_seed(seed)
+                        .with_storage_backend(storage_backend),
+                );
+            let registry = registry_with_workloads(&workloads)?;
+            match shard {
+                Some(shard) => {
+                    let run_id = run_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+                    let run_file = RunFile::new(run_id, profile, seed);
+                    run_sharded(&output_dir, dry_run, &registry, &filter, shard, run_file, config, output).await
+                }
+                None => {
+                    let report_template = report_template.as_deref().map(ReportTemplate::load).transpose()?;
+                    let gate = baseline.map(|path| BaselineGate {
+                        path,
+                        thresholds: RegressionThresholds::new(threshold).with_prefer_cpu_time(prefer_cpu_time),
+                        fail_on_regression,
+                    });
+                    let push = push_gateway.map(|url| GatewayPush {
+                        url,
+                        job: push_job,
+                        labels: push_labels,
+                        basic_auth: push_user.map(|user| (user, push_password.unwrap_or_default())),
+                        timeout: push_timeout,
+                    });
+                    let sinks = archive_url
+                        .map(|url| archive_sink(&url, archive_key, archive_summary))
+                        .transpose()?
+                        .into_iter()
+                        .collect::<Vec<_>>();
+                    if html && !formats.contains(&ExportFormat::Html) {
+                        formats.push(ExportFormat::Html);
+                    }
+                    let layout = OutputLayout::new(&output_dir)
+                        .with_history(history)
+                        .with_compressed_raw(gzip)
+                        .with_target_pages(target_pages)
+                        .with_summary_html(summary_html);
+                    let layout = match lock_timeout {
+                        Some(timeout) => layout.with_lock_timeout(timeout),
+                        None => layout,
+                    };
+                    let layout = match filename_template {
+                        Some(template) => layout.with_filename_template(template),
+                        None => layout,
+                    };
+                    run_selected(
+                        &layout,
+                        dry_run,
+                        &formats,
+                        junit.as_deref(),
+                        report_template.as_ref(),
+                        push.as_ref(),
+                        &sinks,
+                        &filter,
+                        &registry,
+                        config,
+                        progress,
+                        gate,
+                        output,
+                    )
+                    .await
+                }
+            }
+        }
+        BenchmarkCommand::Merge {
+            files,
+            inputs,
+            out,
+            summary,
+            on_duplicate,
+        } => match files.is_empty() {
+            true => merge_shards(&inputs, &out, summary.as_deref()),
+            false => merge_result_files(&files, &out, summary.as_deref(), on_duplicate),
+        },
+        BenchmarkCommand::Prune {
+            output_dir,
+            keep,
+            keep_newer_than,
+            dry_run,
+            yes,
+        } => {
+            let policy = RetentionPolicy {
+                keep_last: keep,
+                keep_newer_than,
+                dry_run,
+            };
+            prune_results(&OutputLayout::new(&output_dir), policy, yes, output)
         }
+        BenchmarkCommand::Verify { dir } => verify_output(&dir, output),
+        BenchmarkCommand::History { output_dir, limit } => show_history(&OutputLayout::new(&output_dir), limit, output),
+        BenchmarkCommand::Compare {
+            baseline,
+            current,
+            markdown,
+            threshold,
+        } => compare_files(&baseline, &current, markdown.as_deref(), threshold, output),
+        BenchmarkCommand::SchemaDrift { baseline, current } => schema_drift(&baseline, &current, output),
+        BenchmarkCommand::Baseline(cmd) => manage_baselines(cmd, output),
+        BenchmarkCommand::Export(cmd) => export_results(cmd),
         BenchmarkCommand::List => list_benchmarks(output).await,
     }
 }
 
-async fn run_benchmarks(output_dir: &str, dry_run: bool, output: OutputFormat) -> Result<()> {
+/// Built-in targets plus a [`CustomWorkloadBenchmark`] for each workload file
+fn registry_with_workloads(workloads: &[PathBuf]) -> Result<BenchRegistry> {
+    workloads.iter().try_fold(BenchRegistry::builtin(), |registry, path| {
+        let plan = WorkloadPlan::load(path)
+            .map_err(|e| CliError::ValidationError(format!("{}: {}", path.display(), e)))?;
+        registry
+            .with_target(Box::new(CustomWorkloadBenchmark::new(plan)))
+            .map_err(|e| CliError::ValidationError(format!("{}: {}", path.display(), e)))
+    })
+}
+
+fn run_mode(parallel: Option<usize>) -> Result<RunMode> {
+    match parallel {
+        None => Ok(RunMode::Sequential),
+        Some(0) => Err(CliError::ValidationError("--parallel must be at least 1".to_string())),
+        Some(max_concurrency) => Ok(RunMode::Parallel { max_concurrency }),
+    }
+}
+
+fn bench_config(warmup: usize, iterations: usize, sample_time: Option<Duration>) -> Result<BenchConfig> {
+    if iterations == 0 {
+        return Err(CliError::ValidationError("--iterations must be at least 1".to_string()));
+    }
+    let config = BenchConfig::new(warmup, iterations);
+    Ok(match sample_time {
+        Some(budget) => config.with_sample_time(budget),
+        None => config,
+    })
+}
+
+/// Parse `--keep-newer-than`: like `--timeout`, or a number of days with a `d` suffix
+pub(crate) fn parse_age(raw: &str) -> std::result::Result<Duration, String> {
+    match raw.trim().strip_suffix('d') {
+        Some(days) => days
+            .parse::<u64>()
+            .ok()
+            .filter(|&days| days > 0)
+            .and_then(|days| days.checked_mul(24 * 3600))
+            .map(Duration::from_secs)
+            .ok_or_else(|| format!("invalid age '{}': expected e.g. 30d or 12h", raw)),
+        None => parse_timeout(raw),
+    }
+}
+
+/// Parse `--timeout`: a number with an `ms`, `s`, `m` or `h` suffix (bare numbers are seconds)
+pub(crate) fn parse_timeout(raw: &str) -> std::result::Result<Duration, String> {
+    let raw = raw.trim();
+    let split = raw.find(|c: char| !c.is_ascii_digit()).unwrap_or(raw.len());
+    let (digits, unit) = raw.split_at(split);
+    let invalid = || format!("invalid duration '{}': expected e.g. 60s or 5m", raw);
+    let value: u64 = digits.parse().map_err(|_| invalid())?;
+
+    let timeout = match unit {
+        "ms" => Duration::from_millis(value),
+        "" | "s" => Duration::from_secs(value),
+        "m" => Duration::from_secs(value.checked_mul(60).ok_or_else(invalid)?),
+        "h" => Duration::from_secs(value.checked_mul(60 * 60).ok_or_else(invalid)?),
+        _ => return Err(format!("invalid duration unit '{}': use ms, s, m or h", unit)),
+    };
+    if timeout.is_zero() {
+        return Err("timeout must be greater than zero".to_string());
+    }
+    Ok(timeout)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_selected(
+    layout: &OutputLayout,
+    dry_run: bool,
+    formats: &[ExportFormat],
+    junit: Option<&Path>,
+    report_template: Option<&ReportTemplate>,
+    push: Option<&GatewayPush>,
+    sinks: &[Box<dyn ResultSink>],
+    filter: &BenchmarkFilter,
+    registry: &BenchRegistry,
+    config: BenchmarkRunConfig,
+    progress: ProgressMode,
+    gate: Option<BaselineGate>,
+    output: OutputFormat,
+) -> Result<()> {
     println!("{}", "Running benchmarks...".cyan().bold());
     println!();
 
-    // Run all benchmarks
-    let results = run_all_benchmarks().await;
+    // Run the selected benchmarks
+    if let RunMode::Parallel { max_concurrency } = config.mode {
+        println!("{}", format!("Parallel mode: up to {} targets at once", max_concurrency).yellow());
+        println!();
+    }
 
+    let targets = filter
+        .apply(adapters::with_extended(registry.targets(), &filter))
+        .map_err(|e| CliError::ValidationError(e.to_string()))?;
+    let cancel = CancellationToken::new();
+    let builder = Runner::builder().config(config).cancel_token(cancel.clone());
+    let runner = match progress.resolve() {
+        ProgressMode::Bar => builder.observer(BarObserver::new(targets.len())),
+        ProgressMode::Ndjson => builder.observer(NdjsonObserver::new(std::io::stderr())),
+        _ => builder.observer(LineObserver),
+    }
+    .build();
+
+    // Table output prints each result as its target finishes
+    let progressive = matches!(output, OutputFormat::Table | OutputFormat::Plain);
+    if progressive {
+        print_results_header();
+    }
+
+    let interrupt = cancel_on_ctrl_c(cancel);
+    let total = targets.len();
+    let mut run = BenchmarkRun::default();
+    let mut outcomes = std::pin::pin!(runner.run_stream(targets));
+    while let Some(outcome) = outcomes.next().await {
+        if let (true, Ok(result)) = (progressive, &outcome) {
+            print_result(result)?;
+        }
+        run.extend([outcome]);
+    }
+    interrupt.abort();
+    run.cancelled = run.results.len() + run.failures.len() < total;
+    if let RunMode::Parallel { .. } = config.mode {
+        run.sort_by_id();
+    }
+    println!();
+
+    let results = &run.results;
     if results.is_empty() {
         println!("{}", "No benchmarks were executed.".yellow());
-        return Ok(());
+        if let (Some(path), false) = (junit, dry_run) {
+            write_junit_report(path, &run, &[])?;
+        }
+        return run_outcome(run.cancelled, &run.failed_ids());
     }
 
     println!("{}", format!("Completed {} benchmarks", results.len()).green());
+    if !run.is_success() {
+        println!("{}", format!("{} benchmarks failed", run.failures.len()).red());
+    }
+    if run.cancelled {
+        println!("{}", "Run cancelled - remaining targets were skipped".yellow());
+    }
+    println!();
+
+    if !progressive {
+        print_results(results, output)?;
+    }
+    let gate = gate.map(|gate| {
+        let evaluated = gate.evaluate(results);
+        (gate, evaluated)
+    });
+
+    // Write results to disk unless dry run
+    if !dry_run {
+        // Generate markdown summary
+        let options = if run.cancelled {
+            SummaryOptions::default()
+        } else {
+            let mut history = io::read_history(layout).unwrap_or_else(|e| {
+                output::print_warning(&format!("Trend charts skipped: {:#}", e));
+                Vec::new()
+            });
+            history.push((chrono::Utc::now(), results.to_vec()));
+            let trends = trends::trends(&history, trends::DEFAULT_WINDOW);
+            SummaryOptions::default().with_trends(trends, TREND_CHART_RUNS)
+        };
+        let options = options.with_target_pages(layout.target_pages);
+        // Highlight regressions against --baseline; a baseline that can't be read is reported by the gate
+        let options = match gate.as_ref().map(|(gate, _)| (gate.report(results), &gate.thresholds)) {
+            Some((Ok(report), thresholds)) => options.with_comparison(report, thresholds),
+            _ => options,
+        };
+        let summary = match report_template {
+            Some(template) => template.render(results, run.cancelled, &options)?,
+            None if run.cancelled => markdown::generate_cancelled_summary(results),
+            None => markdown::generate_summary_with(results, &options),
+        };
+
+        // Write results
+        io::write_results_async(results.to_vec(), summary, layout.clone()).await?;
+
+        println!();
+        println!("{}", "Results written to:".green().bold());
+        println!("  Summary: {}", layout.summary_file.display());
+        println!("  Raw JSON: {}", layout.latest_file().display());
+        if let Some(run) = io::read_manifest(&layout.manifest_file())?.last() {
+            println!("  Timestamped: {}", layout.raw_dir.join(&run.file).display());
+        }
+        if layout.append_history {
+            println!("  History: {}", layout.history_file.display());
+        }
+        if layout.target_pages {
+            println!("  Target pages: {}", layout.targets_dir().display());
+        }
+        if layout.summary_html {
+            println!("  Summary HTML: {}", layout.summary_html_file().display());
+        }
+        for format in formats {
+            match format {
+                ExportFormat::Csv => {
+                    io::write_csv(results, &layout.csv_file())?;
+                    println!("  CSV: {}", layout.csv_file().display());
+                }
+                ExportFormat::Html => {
+                    let options = HtmlOptions::default().with_partial(run.cancelled);
+                    io::write_html(&report::generate_html(results, &options), &layout.html_file())?;
+                    println!("  HTML: {}", layout.html_file().display());
+                }
+            }
+        }
+        if let Some(path) = junit {
+            let violations = gate
+                .as_ref()
+                .and_then(|(_, evaluated)| evaluated.as_ref().ok())
+                .map_or(&[][..], |evaluated| evaluated.violations.as_slice());
+            write_junit_report(path, &run, violations)?;
+        }
+        if let Some(push) = push {
+            push.push(results).await?;
+            println!("  Pushgateway: {}", push.url);
+        }
+        let sinks: Vec<&dyn ResultSink> = sinks.iter().map(|sink| sink.as_ref()).collect();
+        for outcome in run.publish(&sinks).await {
+            outcome
+                .result
+                .map_err(|e| e.context(format!("Failed to archive results to {}", outcome.sink)))?;
+            println!("  Archive: {}", outcome.sink);
+        }
+    } else {
+        println!();
+        println!("{}", "Dry run - results not written to disk".yellow());
+    }
+
+    let gated = gate.map_or(Ok(()), |(gate, evaluated)| gate.check(&evaluated?));
+    run_outcome(run.cancelled, &run.failed_ids())?;
+    gated
+}
+
+/// Write the `--junit` report: failed targets and gate violations are failures
+fn write_junit_report(path: &Path, run: &BenchmarkRun, violations: &[GateViolation]) -> Result<()> {
+    let failures: Vec<JunitFailure> = run
+        .failures
+        .iter()
+        .map(JunitFailure::from)
+        .chain(violations.iter().map(JunitFailure::from))
+        .collect();
+    io::write_junit(&run.results, &failures, path)?;
+    println!("  JUnit: {}", path.display());
+    Ok(())
+}
+
+/// Where `--push-gateway` sends a run's results
+struct GatewayPush {
+    url: String,
+    job: String,
+    labels: Vec<(String, String)>,
+    basic_auth: Option<(String, String)>,
+    timeout: Option<Duration>,
+}
+
+impl GatewayPush {
+    #[cfg(feature = "http-push")]
+    async fn push(&self, results: &[BenchmarkResult]) -> Result<()> {
+        use schema_registry_benchmarks::exporters::{push_to_gateway, PushOptions};
+
+        let mut options = PushOptions::new();
+        if let Some((user, password)) = &self.basic_auth {
+            options = options.with_basic_auth(user, password);
+        }
+        if let Some(timeout) = self.timeout {
+            options = options.with_timeout(timeout);
+        }
+        let labels: Vec<(&str, &str)> = self.labels.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+        push_to_gateway(results, &self.url, &self.job, &labels, &options).await?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "http-push"))]
+    async fn push(&self, _results: &[BenchmarkResult]) -> Result<()> {
+        Err(CliError::ValidationError(
+            "--push-gateway needs schema-cli built with the http-push feature".to_string(),
+        ))
+    }
+}
+
+/// Sink for `--archive-url`
+#[cfg(feature = "object-store")]
+fn archive_sink(url: &str, key_template: Option<String>, summary: bool) -> Result<Box<dyn ResultSink>> {
+    use schema_registry_benchmarks::archive::ObjectStoreSink;
+
+    let sink = ObjectStoreSink::from_url(url)?.with_summary(summary);
+    Ok(Box::new(match key_template {
+        Some(template) => sink.with_key_template(template),
+        None => sink,
+    }))
+}
+
+#[cfg(not(feature = "object-store"))]
+fn archive_sink(_url: &str, _key_template: Option<String>, _summary: bool) -> Result<Box<dyn ResultSink>> {
+    Err(CliError::ValidationError(
+        "--archive-url needs schema-cli built with the object-store feature".to_string(),
+    ))
+}
+
+/// Parse `--push-label`: `NAME=VALUE` with a valid Prometheus label name
+fn parse_label(arg: &str) -> std::result::Result<(String, String), String> {
+    arg.split_once('=')
+        .filter(|(name, _)| {
+            name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected NAME=VALUE with a label name like `branch`, got '{}'", arg))
+}
+
+/// `--report-template`, read and checked before the run so a typo doesn't waste it
+#[cfg(feature = "templates")]
+struct ReportTemplate {
+    path: PathBuf,
+    source: String,
+}
+
+#[cfg(feature = "templates")]
+impl ReportTemplate {
+    fn load(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| CliError::ValidationError(format!("{}: {}", path.display(), e)))?;
+        schema_registry_benchmarks::templates::check_template(&source)
+            .map_err(|e| CliError::ValidationError(format!("{}: {}", path.display(), e)))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    fn render(&self, results: &[BenchmarkResult], cancelled: bool, options: &SummaryOptions) -> Result<String> {
+        use schema_registry_benchmarks::templates::{generate_from_template, TemplateContext};
+
+        let ctx = TemplateContext::new(results, options).with_cancelled(cancelled);
+        generate_from_template(&self.source, &ctx)
+            .map_err(|e| CliError::ValidationError(format!("{}: {}", self.path.display(), e)))
+    }
+}
+
+/// Never constructed: [`ReportTemplate::load`] fails without the templates feature
+#[cfg(not(feature = "templates"))]
+enum ReportTemplate {}
+
+#[cfg(not(feature = "templates"))]
+impl ReportTemplate {
+    fn load(_path: &Path) -> Result<Self> {
+        Err(CliError::ValidationError(
+            "--report-template needs schema-cli built with the templates feature".to_string(),
+        ))
+    }
+
+    fn render(&self, _results: &[BenchmarkResult], _cancelled: bool, _options: &SummaryOptions) -> Result<String> {
+        match *self {}
+    }
+}
+
+/// Comparison of a run against `--baseline`
+struct BaselineGate {
+    path: PathBuf,
+    thresholds: RegressionThresholds,
+    fail_on_regression: bool,
+}
+
+impl BaselineGate {
+    /// Every metric of `results` compared with the baseline
+    fn report(&self, results: &[BenchmarkResult]) -> Result<RegressionReport> {
+        let baseline = read_results(&self.path)?;
+        Ok(compare_results(&baseline, results))
+    }
+
+    /// Metrics of `results` over the threshold against the baseline
+    fn evaluate(&self, results: &[BenchmarkResult]) -> Result<GateResult> {
+        Ok(self.report(results)?.evaluate(&self.thresholds))
+    }
+
+    /// Print the metrics over the threshold; an error if there are any and the gate is enforced
+    fn check(&self, gate: &GateResult) -> Result<()> {
+
+        println!();
+        if gate.passed() {
+            output::print_success(&format!(
+                "No metric regressed by more than {}% against {}",
+                self.thresholds.max_regression_percent,
+                self.path.display()
+            ));
+            return Ok(());
+        }
+        output::print_warning(&format!(
+            "{} metric(s) regressed by more than allowed against {}:",
+            gate.violations.len(),
+            self.path.display()
+        ));
+        for violation in &gate.violations {
+            println!("  {}", violation);
+        }
+
+        if !self.fail_on_regression {
+            return Ok(());
+        }
+        Err(CliError::Other(format!(
+            "benchmark regression gate failed: {} metric(s) over the threshold",
+            gate.violations.len()
+        )))
+    }
+}
+
+/// Cancel `token` on the first Ctrl-C; a second Ctrl-C exits immediately
+fn cancel_on_ctrl_c(token: CancellationToken) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_err() {
+            return;
+        }
+        eprintln!(
+            "{}",
+            "Interrupted: finishing the running target, then writing partial results (Ctrl-C again to abort)".yellow()
+        );
+        token.cancel();
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(130);
+        }
+    })
+}
+
+/// Exit status of a run: cancellation first, then failed targets
+fn run_outcome(cancelled: bool, failed: &[&str]) -> Result<()> {
+    if cancelled {
+        return Err(CliError::Other("benchmark run was cancelled; results are partial".to_string()));
+    }
+    failed_targets_error(failed)
+}
+
+/// Print one status line per target as the run progresses
+fn print_progress(event: BenchProgress) {
+    match event {
+        BenchProgress::TargetStarted { id } => println!("{} {}", "RUN".cyan().bold(), id),
+        BenchProgress::TargetFinished { id, duration } => {
+            println!("{} {} ({:.2?})", "OK".green().bold(), id, duration)
+        }
+        BenchProgress::TargetFailed { error, .. } => eprintln!("{} {}", "FAILED".red().bold(), error.red()),
+    }
+}
+
+impl ProgressMode {
+    /// Replace `Auto` with the mode to use
+    fn resolve(self) -> Self {
+        match self {
+            ProgressMode::Auto if std::io::stderr().is_terminal() => ProgressMode::Bar,
+            ProgressMode::Auto => ProgressMode::Lines,
+            mode => mode,
+        }
+    }
+}
+
+/// Prints the same status lines as [`print_progress`]
+struct LineObserver;
+
+impl ProgressObserver for LineObserver {
+    fn on_target_started(&self, target_id: &str) {
+        print_progress(BenchProgress::TargetStarted {
+            id: target_id.to_string(),
+        });
+    }
+
+    fn on_target_finished(&self, target_id: &str, outcome: &TargetOutcome) {
+        let event = match outcome {
+            TargetOutcome::Finished { duration } => BenchProgress::TargetFinished {
+                id: target_id.to_string(),
+                duration: *duration,
+            },
+            TargetOutcome::Failed { error } => BenchProgress::TargetFailed {
+                id: target_id.to_string(),
+                error: error.clone(),
+            },
+        };
+        print_progress(event);
+    }
+}
+
+/// Progress bar over the targets, showing the latest iteration
+struct BarObserver {
+    bar: ProgressBar,
+}
+
+impl BarObserver {
+    fn new(targets: usize) -> Self {
+        let bar = ProgressBar::new(targets as u64);
+        bar.set_style(
+            ProgressStyle::with_template("{spinner:.cyan} {bar:40.cyan/blue} {pos}/{len} targets {msg}")
+                .unwrap_or_else(|_| ProgressStyle::default_bar()),
+        );
+        bar.enable_steady_tick(Duration::from_millis(100));
+        Self { bar }
+    }
+}
+
+impl ProgressObserver for BarObserver {
+    fn on_target_started(&self, target_id: &str) {
+        self.bar.set_message(target_id.to_string());
+    }
+
+    fn on_iteration_completed(&self, target_id: &str, sample: IterationSample) {
+        let phase = if sample.warmup { "warmup" } else { "iteration" };
+        self.bar
+            .set_message(format!("{} ({} {})", target_id, phase, sample.iteration + 1));
+    }
+
+    fn on_target_finished(&self, target_id: &str, outcome: &TargetOutcome) {
+        match outcome {
+            TargetOutcome::Finished { duration } => {
+                self.bar
+                    .println(format!("{} {} ({:.2?})", "OK".green().bold(), target_id, duration))
+            }
+            TargetOutcome::Failed { error } => self.bar.println(format!("{} {}", "FAILED".red().bold(), error.red())),
+        }
+        self.bar.inc(1);
+    }
+
+    fn on_run_finished(&self, _summary: &RunSummary) {
+        self.bar.finish_and_clear();
+    }
+}
+
+/// Heading printed above table-format results
+fn print_results_header() {
+    println!("{}", "Benchmark Results".bold());
+    println!("{}", "=".repeat(80));
+}
+
+/// Print one result in table format
+fn print_result(result: &BenchmarkResult) -> Result<()> {
+    let mode = if result.parallel { " (parallel)" } else { "" };
+    println!("{}{}: {}", result.target_id.cyan(), mode.yellow(), result.timestamp);
+    println!("  Metrics: {}", serde_json::to_string_pretty(&result.metrics)?);
+    if !result.raw_samples.is_empty() {
+        println!("  Samples:");
+        for (label, samples) in &result.raw_samples {
+            if let Some(line) = markdown::sample_sparkline(samples) {
+                println!("    {}: {}", label, line);
+            }
+        }
+    }
     println!();
+    Ok(())
+}
 
-    // Display results based on output format
+/// Display results based on output format
+fn print_results(results: &[BenchmarkResult], output: OutputFormat) -> Result<()> {
     match output {
-        OutputFormat::Table => {
-            println!("{}", "Benchmark Results".bold());
-            println!("{}", "=".repeat(80));
-            for result in &results {
-                println!("{}: {}", result.target_id.cyan(), result.timestamp);
-                println!("  Metrics: {}", serde_json::to_string_pretty(&result.metrics)?);
-                println!();
+        OutputFormat::Table | OutputFormat::Plain => {
+            print_results_header();
+            for result in results {
+                print_result(result)?;
             }
         }
         OutputFormat::Json => {
@@ -66,55 +1142,425 @@ async fn run_benchmarks(output_dir: &str, dry_run: bool, output: OutputFormat) -
             println!("{}", serde_yaml::to_string(&results)?);
         }
     }
+    Ok(())
+}
 
-    // Write results to disk unless dry run
-    if !dry_run {
-        // Update output directory paths to use the provided output_dir
-        std::env::set_var("BENCHMARK_OUTPUT_DIR", output_dir);
+/// Run one CI shard and write its results, tagged with the shard, for `benchmark merge`
+#[allow(clippy::too_many_arguments)]
+async fn run_sharded(
+    output_dir: &str,
+    dry_run: bool,
+    registry: &BenchRegistry,
+    filter: &BenchmarkFilter,
+    shard: Shard,
+    run_file: RunFile,
+    config: BenchmarkRunConfig,
+    output: OutputFormat,
+) -> Result<()> {
+    println!("{}", format!("Running benchmark shard {} (run {})...", shard, run_file.run_id).cyan().bold());
+    println!();
 
-        // Generate markdown summary
-        let summary = markdown::generate_summary(&results);
+    let run = run_shard_with_progress(Some(registry), filter, shard, config, print_progress)
+        .await
+        .map_err(|e| CliError::ValidationError(e.to_string()))?;
+    println!();
 
-        // Write results
-        io::write_results(&results, &summary)?;
+    let run_file = run_file.record(Some(shard), run);
+    if run_file.results.is_empty() && run_file.failed.is_empty() {
+        println!("{}", format!("No targets are assigned to shard {}.", shard).yellow());
+    }
+    print_results(&run_file.results, output)?;
 
-        println!();
-        println!("{}", "Results written to:".green().bold());
-        println!("  Summary: {}/summary.md", output_dir);
-        println!("  Raw JSON: {}/raw/latest.json", output_dir);
-        println!("  Timestamped: {}/raw/benchmarks_*.json", output_dir);
-    } else {
-        println!();
+    if dry_run {
         println!("{}", "Dry run - results not written to disk".yellow());
+    } else {
+        let path = Path::new(output_dir).join(format!("shard{}.json", shard.index));
+        io::write_run_file(&run_file, &path)?;
+        println!("{} {}", "Shard results written to:".green().bold(), path.display());
+    }
+
+    let failed: Vec<&str> = run_file.failed.iter().map(String::as_str).collect();
+    failed_targets_error(&failed)
+}
+
+/// Combine shard outputs into one run file, and optionally a markdown summary
+fn merge_shards(inputs: &[String], out: &str, summary: Option<&str>) -> Result<()> {
+    let shards = inputs
+        .iter()
+        .map(|input| io::read_run_file(Path::new(input)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let merged = shard::merge(shards).map_err(|e| CliError::ValidationError(e.to_string()))?;
+
+    io::write_run_file(&merged, Path::new(out))?;
+    output::print_success(&format!(
+        "Merged {} shard(s) with {} results into {}",
+        merged.shards.len(),
+        merged.results.len(),
+        out
+    ));
+    for missing in merged.missing_shards() {
+        output::print_warning(&format!("Shard {} is missing from the merged run", missing));
     }
 
+    if let Some(path) = summary {
+        io::write_markdown(&markdown::generate_run_summary(&merged), Path::new(path))?;
+        output::print_success(&format!("Wrote summary to {}", path));
+    }
+    Ok(())
+}
+
+/// Concatenate results files into `out` and regenerate the markdown summary
+fn merge_result_files(files: &[PathBuf], out: &str, summary: Option<&str>, policy: DuplicatePolicy) -> Result<()> {
+    let merged = io::merge_results(files, policy)?;
+
+    io::write_json(&merged, Path::new(out))?;
+    output::print_success(&format!(
+        "Merged {} file(s) with {} results into {}",
+        files.len(),
+        merged.len(),
+        out
+    ));
+
+    let summary = summary.map_or_else(|| Path::new(out).with_extension("md"), PathBuf::from);
+    io::write_markdown(&markdown::generate_summary(&merged), &summary)?;
+    output::print_success(&format!("Wrote summary to {}", summary.display()));
+    Ok(())
+}
+
+/// Report files in `dir` that don't match their recorded checksums, failing if any
+fn verify_output(dir: &Path, output: OutputFormat) -> Result<()> {
+    let mismatches = io::verify_checksums(dir)?;
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            for mismatch in &mismatches {
+                match &mismatch.actual {
+                    Some(actual) => println!(
+                        "{} {} (expected {}, found {})",
+                        "MODIFIED".red().bold(),
+                        mismatch.path.display(),
+                        mismatch.expected,
+                        actual
+                    ),
+                    None => println!("{} {}", "MISSING".red().bold(), mismatch.path.display()),
+                }
+            }
+        }
+        _ => output::print(&mismatches, output)?,
+    }
+
+    if !mismatches.is_empty() {
+        return Err(CliError::ValidationError(format!(
+            "{} file(s) in {} don't match their checksums",
+            mismatches.len(),
+            dir.display()
+        )));
+    }
+    output::print_success(&format!("Checksums in {} match", dir.join(io::checksums::CHECKSUMS_FILE).display()));
     Ok(())
 }
 
+/// List the newest `limit` runs of the layout's manifest
+fn show_history(layout: &OutputLayout, limit: usize, output: OutputFormat) -> Result<()> {
+    let mut runs = io::read_manifest(&layout.manifest_file())?;
+    runs.reverse();
+    runs.truncate(limit);
+
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if runs.is_empty() {
+                println!("{}", format!("No runs recorded in {}", layout.manifest_file().display()).yellow());
+                return Ok(());
+            }
+            let header = format!("{:<20} {:<34} {:>7}  {:<12} {}", "Timestamp", "File", "Results", "Commit", "Targets");
+            println!("{}", header.bold());
+            for run in &runs {
+                let file = match layout.raw_dir.join(&run.file).exists() {
+                    true => run.file.clone(),
+                    false => format!("{} (pruned)", run.file),
+                };
+                let commit = run.git_sha.as_deref().map_or("-", |sha| &sha[..sha.len().min(12)]);
+                println!(
+                    "{:<20} {:<34} {:>7}  {:<12} {}",
+                    run.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                    file,
+                    run.result_count,
+                    commit,
+                    run.target_ids.join(", ")
+                );
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&runs)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&runs)?),
+    }
+    Ok(())
+}
+
+/// Delete the timestamped results `policy` doesn't keep, after confirming unless `yes`
+fn prune_results(layout: &OutputLayout, policy: RetentionPolicy, yes: bool, output: OutputFormat) -> Result<()> {
+    let planned = io::prune_raw_results(&layout.raw_dir, &policy.with_dry_run(true))?;
+    let report = if policy.dry_run || planned.deleted.is_empty() {
+        planned
+    } else {
+        if !yes {
+            for path in &planned.deleted {
+                println!("  {}", path.display());
+            }
+            let question = format!(
+                "Delete {} of {} timestamped results in {}?",
+                planned.deleted.len(),
+                planned.deleted.len() + planned.retained.len(),
+                layout.raw_dir.display()
+            );
+            if !confirm(&question)? {
+                output::print_info("Nothing deleted");
+                return Ok(());
+            }
+        }
+        let report = io::prune_raw_results(&layout.raw_dir, &policy)?;
+        io::forget_checksums(&layout.root, &report.deleted)?;
+        report
+    };
+
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if report.dry_run {
+                for path in &report.deleted {
+                    println!("  would delete {}", path.display());
+                }
+            }
+            let verb = if report.dry_run { "Would delete" } else { "Deleted" };
+            output::print_success(&format!(
+                "{} {} file(s), kept {}",
+                verb,
+                report.deleted.len(),
+                report.retained.len()
+            ));
+        }
+        _ => output::print(&report, output)?,
+    }
+    Ok(())
+}
+
+/// Ask `question` on the terminal; anything but y or yes declines
+fn confirm(question: &str) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        return Err(CliError::ValidationError(
+            "Refusing to delete without confirmation: pass --yes".to_string(),
+        ));
+    }
+    eprint!("{} [y/N] ", question);
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Compare two results files, writing the markdown report to `out` if given
+fn compare_files(
+    baseline: &Path,
+    current: &Path,
+    out: Option<&Path>,
+    threshold: f64,
+    output: OutputFormat,
+) -> Result<()> {
+    let report = compare_results_with_threshold(&read_results(baseline)?, &read_results(current)?, threshold);
+
+    if let Some(path) = out {
+        io::write_markdown(&markdown::generate_comparison(&report), path)?;
+        output::print_success(&format!(
+            "Wrote comparison of {} metric(s) to {}",
+            report.metrics.len(),
+            path.display()
+        ));
+        return Ok(());
+    }
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => print!("{}", markdown::generate_comparison(&report)),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&report)?),
+    }
+    Ok(())
+}
+
+/// Report metric shape drift between two result files
+fn schema_drift(baseline: &str, current: &str, output: OutputFormat) -> Result<()> {
+    let drift = shape::compare(&io::read_json(Path::new(baseline))?, &io::read_json(Path::new(current))?);
+
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if drift.is_empty() {
+                output::print_success("No metric shape drift");
+            }
+            for target in &drift {
+                if target.is_announced() {
+                    output::print_info(&format!(
+                        "Metric shape of {} changed with version {} -> {}",
+                        target.target_id,
+                        target.baseline_version.unwrap_or_default(),
+                        target.current_version.unwrap_or_default()
+                    ));
+                } else {
+                    output::print_warning(&format!(
+                        "Metric shape of {} changed without a version bump",
+                        target.target_id
+                    ));
+                }
+                println!("{}", target);
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&drift)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&drift)?),
+    }
+
+    let unannounced: Vec<&str> = drift
+        .iter()
+        .filter(|target| !target.is_announced())
+        .map(|target| target.target_id.as_str())
+        .collect();
+    if unannounced.is_empty() {
+        return Ok(());
+    }
+    Err(CliError::Other(format!(
+        "metric shape drifted without a version bump: {}",
+        unannounced.join(", ")
+    )))
+}
+
+/// Run a `benchmark baseline` subcommand
+fn export_results(cmd: ExportCommand) -> Result<()> {
+    match cmd {
+        ExportCommand::Prometheus { results, out } => {
+            let results = read_results(&results)?;
+            io::write_prometheus(&results, &out)?;
+            output::print_success(&format!("Wrote metrics of {} target(s) to {}", results.len(), out.display()));
+            Ok(())
+        }
+    }
+}
+
+fn manage_baselines(cmd: BaselineCommand, output: OutputFormat) -> Result<()> {
+    let store = |dir: Option<PathBuf>| BaselineStore::new(dir.unwrap_or_else(default_baselines_dir));
+
+    match cmd {
+        BaselineCommand::Save { name, results, note, dir } => {
+            let saved = store(dir).save(&io::read_json(&results)?, &name, note.as_deref())?;
+            print_saved_baseline(&saved);
+            Ok(())
+        }
+        BaselineCommand::Promote { from_run, to_name, note, dir } => {
+            let saved = store(dir).promote(&from_run, &to_name, note.as_deref())?;
+            print_saved_baseline(&saved);
+            Ok(())
+        }
+        BaselineCommand::List { dir } => {
+            let store = store(dir);
+            let baselines = store.list()?;
+            match output {
+                OutputFormat::Table | OutputFormat::Plain => {
+                    if baselines.is_empty() {
+                        output::print_info(&format!("No baselines in {}", store.dir().display()));
+                        return Ok(());
+                    }
+                    let rows = baselines
+                        .iter()
+                        .map(|b| {
+                            vec![
+                                b.name.clone(),
+                                b.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                                b.git_sha.as_deref().map_or("-", |sha| &sha[..sha.len().min(12)]).to_string(),
+                                b.note.clone().unwrap_or_default(),
+                            ]
+                        })
+                        .collect();
+                    output::print_table(vec!["Name", "Created", "Commit", "Note"], rows);
+                }
+                _ => output::print(&baselines, output)?,
+            }
+            Ok(())
+        }
+        BaselineCommand::Show { name, dir } => {
+            let baseline = store(dir).load(&name)?;
+            match output {
+                OutputFormat::Table | OutputFormat::Plain => {
+                    let metadata = &baseline.metadata;
+                    println!("{} {}", "Baseline".bold(), metadata.name.cyan().bold());
+                    println!("  Created: {}", metadata.created_at);
+                    println!("  Commit: {}", metadata.git_sha.as_deref().unwrap_or("unknown"));
+                    if let Some(note) = &metadata.note {
+                        println!("  Note: {}", note);
+                    }
+                    if let Some(from) = &metadata.promoted_from {
+                        println!("  Promoted from: {}", from);
+                    }
+                    println!();
+                    print_results(&baseline.results, output)
+                }
+                _ => output::print(&baseline, output),
+            }
+        }
+    }
+}
+
+fn print_saved_baseline(baseline: &Baseline) {
+    output::print_success(&format!(
+        "Saved baseline '{}' with {} results",
+        baseline.metadata.name,
+        baseline.results.len()
+    ));
+}
+
+/// Turn failed target ids into the command's exit status
+fn failed_targets_error(failed: &[&str]) -> Result<()> {
+    if failed.is_empty() {
+        return Ok(());
+    }
+    Err(CliError::Other(format!(
+        "{} benchmark target(s) failed: {}",
+        failed.len(),
+        failed.join(", ")
+    )))
+}
+
 async fn list_benchmarks(output: OutputFormat) -> Result<()> {
-    let targets = schema_registry_benchmarks::adapters::all_targets();
+    let targets = BenchRegistry::builtin().targets();
+    let extended = adapters::extended_targets();
 
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Plain => {
             println!("{}", "Available Benchmarks".bold());
             println!("{}", "=".repeat(80));
             println!();
 
-            for target in targets {
+            for target in &targets {
                 println!("{}", target.id().cyan().bold());
                 println!("  {}", target.description());
+                if !target.tags().is_empty() {
+                    println!("  Tags: {}", target.tags().join(", ").yellow());
+                }
+                println!();
+            }
+            for target in &extended {
+                println!("{}", target.id().cyan().bold());
+                println!("  {}", target.description());
+                if !target.tags().is_empty() {
+                    println!("  Tags: {}", target.tags().join(", ").yellow());
+                }
+                println!("  {}", "Runs only when selected by --targets or --tag".dimmed());
                 println!();
             }
 
-            println!("Total: {} benchmark targets", targets.len());
+            println!("Total: {} benchmark targets", targets.len() + extended.len());
         }
         OutputFormat::Json => {
             let targets_info: Vec<_> = targets
                 .iter()
-                .map(|t| {
+                .map(|t| (t, true))
+                .chain(extended.iter().map(|t| (t, false)))
+                .map(|(t, default)| {
                     serde_json::json!({
                         "id": t.id(),
-                        "description": t.description()
+                        "description": t.description(),
+                        "tags": t.tags(),
+                        "default": default
                     })
                 })
                 .collect();
@@ -123,10 +1569,14 @@ async fn list_benchmarks(output: OutputFormat) -> Result<()> {
         OutputFormat::Yaml => {
             let targets_info: Vec<_> = targets
                 .iter()
-                .map(|t| {
+                .map(|t| (t, true))
+                .chain(extended.iter().map(|t| (t, false)))
+                .map(|(t, default)| {
                     serde_json::json!({
                         "id": t.id(),
-                        "description": t.description()
+                        "description": t.description(),
+                        "tags": t.tags(),
+                        "default": default
                     })
                 })
                 .collect();
@@ -140,6 +1590,7 @@ async fn list_benchmarks(output: OutputFormat) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use schema_registry_benchmarks::SamplingMode;
 
     #[test]
     fn test_benchmark_command_variants() {
@@ -147,7 +1598,410 @@ mod tests {
         let _run = BenchmarkCommand::Run {
             output_dir: "test".to_string(),
             dry_run: false,
+            formats: vec![ExportFormat::Csv],
+            html: true,
+            history: true,
+            gzip: false,
+            target_pages: true,
+            summary_html: false,
+            targets: vec!["storage_*".to_string()],
+            tags: vec!["fast".to_string()],
+            exclude_tags: vec!["slow".to_string()],
+            parallel: Some(2),
+            timeout: Some(Duration::from_secs(60)),
+            warmup: 2,
+            iterations: 10,
+            sample_time: None,
+            outliers: OutlierPolicy::Iqr { k: 1.5 },
+            shard: Some(Shard::new(2, 4).unwrap()),
+            run_id: Some("ci-42".to_string()),
+            profile: "default".to_string(),
+            seed: 0,
+            storage_backend: StorageBackend::Disk,
+            progress: ProgressMode::Lines,
+            workloads: Vec::new(),
+            baseline: Some(PathBuf::from("main/latest.json")),
+            junit: Some(PathBuf::from("junit.xml")),
+            push_gateway: Some("http://localhost:9091".to_string()),
+            push_job: DEFAULT_PUSH_JOB.to_string(),
+            push_labels: vec![("branch".to_string(), "main".to_string())],
+            push_user: Some("ci".to_string()),
+            push_password: None,
+            push_timeout: Some(Duration::from_secs(5)),
+            filename_template: Some("{prefix}_{timestamp}_{git_sha}".parse().unwrap()),
+            lock_timeout: Some(Duration::from_secs(120)),
+            archive_url: Some("s3://bench-archive/ci".to_string()),
+            archive_key: Some("{date}/{git_sha}.json".to_string()),
+            archive_summary: true,
+            report_template: Some(PathBuf::from("report.md.tera")),
+            threshold: 10.0,
+            fail_on_regression: true,
+            prefer_cpu_time: true,
+        };
+        let _prune = BenchmarkCommand::Prune {
+            output_dir: "test".to_string(),
+            keep: Some(50),
+            keep_newer_than: None,
+            dry_run: true,
+            yes: false,
         };
+        let _verify = BenchmarkCommand::Verify {
+            dir: PathBuf::from("benchmarks/output"),
+        };
+        let _history = BenchmarkCommand::History {
+            output_dir: "test".to_string(),
+            limit: 5,
+        };
+        let _merge = BenchmarkCommand::Merge {
+            files: Vec::new(),
+            inputs: vec!["shard1.json".to_string()],
+            out: "merged.json".to_string(),
+            summary: None,
+            on_duplicate: DuplicatePolicy::Error,
+        };
+        let _compare = BenchmarkCommand::Compare {
+            baseline: PathBuf::from("old.json"),
+            current: PathBuf::from("new.json"),
+            markdown: Some(PathBuf::from("comparison.md")),
+            threshold: DEFAULT_THRESHOLD_PERCENT,
+        };
+        let _drift = BenchmarkCommand::SchemaDrift {
+            baseline: "old.json".to_string(),
+            current: "new.json".to_string(),
+        };
+        let _baseline = BenchmarkCommand::Baseline(BaselineCommand::Show {
+            name: "main".to_string(),
+            dir: None,
+        });
+        let _export = BenchmarkCommand::Export(ExportCommand::Prometheus {
+            results: PathBuf::from("latest.json"),
+            out: PathBuf::from("metrics.prom"),
+        });
         let _list = BenchmarkCommand::List;
     }
+
+    #[test]
+    fn test_export_prometheus() {
+        let dir = tempfile::tempdir().unwrap();
+        let results = dir.path().join("latest.json");
+        let out = dir.path().join("metrics.prom");
+        let result = BenchmarkResult::new("alpha".to_string(), serde_json::json!({"write": {"p95_ms": 2.0}}));
+        io::write_json(&[result], &results).unwrap();
+
+        export_results(ExportCommand::Prometheus { results, out: out.clone() }).unwrap();
+        let text = std::fs::read_to_string(out).unwrap();
+        assert!(text.contains("schema_registry_benchmark_p95_seconds{target=\"alpha\",operation=\"write\"} 0.002\n"));
+        assert!(text.ends_with("# EOF\n"));
+    }
+
+    #[tokio::test]
+    async fn test_run_writes_into_output_dir() {
+        #[derive(clap::Parser)]
+        struct Cli {
+            #[command(subcommand)]
+            cmd: BenchmarkCommand,
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let output_dir = dir.path().join("results");
+        let cli = <Cli as clap::Parser>::try_parse_from([
+            "benchmark",
+            "run",
+            "--output-dir",
+            output_dir.to_str().unwrap(),
+            "--targets",
+            "validation_operations",
+            "--warmup",
+            "0",
+            "--iterations",
+            "1",
+            "--progress",
+            "lines",
+            "--format",
+            "csv",
+            "--html",
+            "--junit",
+            output_dir.join("junit.xml").to_str().unwrap(),
+        ])
+        .unwrap();
+        execute(cli.cmd, &Config::default(), OutputFormat::Plain).await.unwrap();
+
+        assert!(output_dir.join("summary.md").is_file());
+        let results = io::read_json(&output_dir.join("raw/latest.json")).unwrap();
+        assert_eq!(results[0].target_id, "validation_operations");
+        let csv = std::fs::read_to_string(output_dir.join("results.csv")).unwrap();
+        assert!(csv.starts_with("target_id,timestamp,"));
+        assert!(csv.lines().nth(1).unwrap().starts_with("validation_operations,"));
+        let html = std::fs::read_to_string(output_dir.join("report.html")).unwrap();
+        assert!(html.contains("<section id=\"target-validation_operations\">"));
+        let junit = std::fs::read_to_string(output_dir.join("junit.xml")).unwrap();
+        assert!(junit.contains("<testcase name=\"validation_operations\""));
+    }
+
+    #[test]
+    fn test_run_mode_from_parallel_flag() {
+        assert_eq!(run_mode(None).unwrap(), RunMode::Sequential);
+        assert_eq!(run_mode(Some(4)).unwrap(), RunMode::Parallel { max_concurrency: 4 });
+        assert!(run_mode(Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_bench_config_from_flags() {
+        assert_eq!(bench_config(3, 20, None).unwrap(), BenchConfig::new(3, 20));
+        assert_eq!(bench_config(0, 1, None).unwrap(), BenchConfig::new(0, 1));
+        assert!(bench_config(2, 0, None).is_err());
+
+        let timed = bench_config(2, DEFAULT_MEASURED_ITERATIONS, Some(Duration::from_secs(2))).unwrap();
+        assert_eq!(timed.sampling, SamplingMode::Time(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_parse_label() {
+        assert_eq!(parse_label("branch=feature/x").unwrap(), ("branch".to_string(), "feature/x".to_string()));
+        assert_eq!(parse_label("host=").unwrap(), ("host".to_string(), String::new()));
+        assert!(parse_label("branch").is_err());
+        assert!(parse_label("=main").is_err());
+        assert!(parse_label("git-branch=main").is_err());
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout("60s").unwrap(), Duration::from_secs(60));
+        assert_eq!(parse_timeout("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_timeout("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_timeout("90").unwrap(), Duration::from_secs(90));
+        assert!(parse_timeout("0s").is_err());
+        assert!(parse_timeout("1d").is_err());
+        assert!(parse_timeout("s").is_err());
+        assert!(parse_timeout("999999999999999999h").unwrap_err().starts_with("invalid duration"));
+        assert!(parse_timeout("999999999999999999m").is_err());
+    }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("30d").unwrap(), Duration::from_secs(30 * 24 * 3600));
+        assert_eq!(parse_age("12h").unwrap(), Duration::from_secs(12 * 3600));
+        assert!(parse_age("0d").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("999999999999999999d").is_err());
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let layout = OutputLayout::new(dir.path());
+        let result = BenchmarkResult::new("alpha".to_string(), serde_json::json!({}));
+        for day in 1..=4 {
+            io::write_json(&[result.clone()], &layout.raw_dir.join(format!("benchmarks_2025010{}_000000.json", day)))
+                .unwrap();
+        }
+        io::write_json(&[result], &layout.latest_file()).unwrap();
+        let policy = RetentionPolicy::new().with_keep_last(1);
+
+        prune_results(&layout, policy.with_dry_run(true), false, OutputFormat::Json).unwrap();
+        assert_eq!(std::fs::read_dir(&layout.raw_dir).unwrap().count(), 5);
+
+        prune_results(&layout, policy, true, OutputFormat::Plain).unwrap();
+        let mut left: Vec<_> = std::fs::read_dir(&layout.raw_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        left.sort();
+        assert_eq!(left, vec!["benchmarks_20250104_000000.json", "latest.json"]);
+    }
+
+    #[test]
+    fn test_merge_shards_writes_run_and_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+        for (index, target) in [(1, "alpha"), (2, "beta")] {
+            let run = schema_registry_benchmarks::BenchmarkRun {
+                results: vec![BenchmarkResult::new(target.to_string(), serde_json::json!({}))],
+                failures: Vec::new(),
+                cancelled: false,
+            };
+            let file = RunFile::new("ci-42", "default", 0).record(Some(Shard::new(index, 2).unwrap()), run);
+            io::write_run_file(&file, Path::new(&path(&format!("shard{}.json", index)))).unwrap();
+        }
+
+        merge_shards(&[path("shard1.json"), path("shard2.json")], &path("merged.json"), Some(&path("merged.md")))
+            .unwrap();
+
+        let merged = io::read_run_file(Path::new(&path("merged.json"))).unwrap();
+        assert_eq!(merged.results.len(), 2);
+        assert!(merged.missing_shards().is_empty());
+        let summary = std::fs::read_to_string(path("merged.md")).unwrap();
+        assert!(summary.contains("**Sharding:** 2 of 2 shards"));
+
+        let err = merge_shards(&[path("shard1.json"), path("shard1.json")], &path("again.json"), None).unwrap_err();
+        assert!(err.to_string().contains("appears more than once"));
+    }
+
+    #[test]
+    fn test_verify_output_reports_modified_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let layout = OutputLayout::new(dir.path());
+        let results = vec![BenchmarkResult::new("alpha".to_string(), serde_json::json!({}))];
+        io::write_results(&results, "# Summary\n", &layout).unwrap();
+        verify_output(dir.path(), OutputFormat::Table).unwrap();
+
+        std::fs::write(&layout.summary_file, "# Edited\n").unwrap();
+        let err = verify_output(dir.path(), OutputFormat::Json).unwrap_err();
+        assert!(err.to_string().contains("1 file(s)"));
+    }
+
+    #[test]
+    fn test_show_history_lists_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let layout = OutputLayout::new(dir.path());
+        show_history(&layout, 5, OutputFormat::Table).unwrap();
+
+        let results = vec![BenchmarkResult::new("alpha".to_string(), serde_json::json!({}))];
+        io::write_results(&results, "# Summary\n", &layout).unwrap();
+        std::fs::remove_dir_all(&layout.raw_dir).unwrap();
+        show_history(&layout, 5, OutputFormat::Table).unwrap();
+        show_history(&layout, 5, OutputFormat::Json).unwrap();
+
+        std::fs::write(layout.manifest_file(), "not json").unwrap();
+        assert!(show_history(&layout, 5, OutputFormat::Table).is_err());
+    }
+
+    #[test]
+    fn test_merge_result_files_regenerates_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        let files: Vec<PathBuf> = [("linux.json", ["storage", "validation"]), ("macos.json", ["storage", "compatibility"])]
+            .into_iter()
+            .map(|(name, targets)| {
+                let path = dir.path().join(name);
+                let results: Vec<BenchmarkResult> = targets
+                    .iter()
+                    .map(|target| BenchmarkResult::new(target.to_string(), serde_json::json!({})))
+                    .collect();
+                io::write_json(&results, &path).unwrap();
+                path
+            })
+            .collect();
+        let out = dir.path().join("merged.json").to_string_lossy().into_owned();
+
+        let err = merge_result_files(&files, &out, None, DuplicatePolicy::Error).unwrap_err();
+        assert!(err.to_string().contains("Target storage appears in more than one input"));
+
+        merge_result_files(&files, &out, None, DuplicatePolicy::Suffix).unwrap();
+        let merged = io::read_json(Path::new(&out)).unwrap();
+        assert_eq!(merged.len(), 4);
+        let summary = std::fs::read_to_string(dir.path().join("merged.md")).unwrap();
+        assert!(summary.contains("storage@linux") && summary.contains("storage@macos"));
+    }
+
+    #[test]
+    fn test_schema_drift_fails_on_unannounced_rename() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+        let write = |name: &str, key: &str, version: u32| {
+            let mut result = BenchmarkResult::new("alpha".to_string(), serde_json::json!({ "write": { key: 1.0 } }));
+            result.target_version = Some(version);
+            io::write_json(&[result], Path::new(&path(name))).unwrap();
+        };
+        write("old.json", "avg_ms", 1);
+        write("same.json", "avg_ms", 1);
+        write("renamed.json", "mean_ms", 1);
+        write("bumped.json", "mean_ms", 2);
+
+        schema_drift(&path("old.json"), &path("same.json"), OutputFormat::Plain).unwrap();
+        schema_drift(&path("old.json"), &path("bumped.json"), OutputFormat::Plain).unwrap();
+        let err = schema_drift(&path("old.json"), &path("renamed.json"), OutputFormat::Plain).unwrap_err();
+        assert!(err.to_string().contains("alpha"));
+    }
+
+    #[test]
+    fn test_baseline_gate_fails_only_when_enforced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("latest.json");
+        let result = |p95: f64| BenchmarkResult::new("alpha".to_string(), serde_json::json!({"write": {"p95_ms": p95}}));
+        io::write_json(&[result(10.0)], &path).unwrap();
+
+        let gate = |fail_on_regression| BaselineGate {
+            path: path.clone(),
+            thresholds: RegressionThresholds::new(10.0),
+            fail_on_regression,
+        };
+        let check = |gate: BaselineGate, current: f64| gate.check(&gate.evaluate(&[result(current)])?);
+        check(gate(true), 10.5).unwrap();
+        check(gate(true), 2.0).unwrap();
+        check(gate(false), 12.0).unwrap();
+        let err = check(gate(true), 12.0).unwrap_err();
+        assert!(err.to_string().contains("regression gate failed"));
+    }
+
+    #[test]
+    fn test_baseline_promote_and_show() {
+        let dir = tempfile::tempdir().unwrap();
+        let run = dir.path().join("latest.json");
+        io::write_json(&[BenchmarkResult::new("alpha".to_string(), serde_json::json!({}))], &run).unwrap();
+        let baselines = Some(dir.path().join("baselines"));
+
+        manage_baselines(
+            BaselineCommand::Promote {
+                from_run: run,
+                to_name: "main".to_string(),
+                note: Some("release".to_string()),
+                dir: baselines.clone(),
+            },
+            OutputFormat::Plain,
+        )
+        .unwrap();
+        manage_baselines(BaselineCommand::List { dir: baselines.clone() }, OutputFormat::Json).unwrap();
+        manage_baselines(
+            BaselineCommand::Show {
+                name: "main".to_string(),
+                dir: baselines.clone(),
+            },
+            OutputFormat::Plain,
+        )
+        .unwrap();
+
+        let err = manage_baselines(
+            BaselineCommand::Show {
+                name: "missing".to_string(),
+                dir: baselines,
+            },
+            OutputFormat::Plain,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("No baseline named 'missing'"));
+    }
+
+    #[test]
+    fn test_workload_files_add_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = dir.path().join("hot.yaml");
+        std::fs::write(&plan, "version: 1\nname: hot\nsteps:\n  - register: { count: 2 }\n").unwrap();
+
+        let registry = registry_with_workloads(&[plan.clone()]).unwrap();
+        assert!(registry.contains("workload_hot"));
+        assert_eq!(registry.len(), BenchRegistry::builtin().len() + 1);
+
+        let err = registry_with_workloads(&[plan.clone(), plan]).unwrap_err();
+        assert!(err.to_string().contains("already registered"), "{}", err);
+
+        let broken = dir.path().join("broken.yaml");
+        std::fs::write(&broken, "version: 1\nname: broken\nsteps:\n  - read: { count: 2 }\n").unwrap();
+        let err = registry_with_workloads(&[broken]).unwrap_err();
+        assert!(err.to_string().contains("steps[0].read.keys"), "{}", err);
+    }
+
+    #[test]
+    fn test_run_outcome_reports_cancellation() {
+        assert!(run_outcome(false, &[]).is_ok());
+        assert!(run_outcome(true, &[]).unwrap_err().to_string().contains("cancelled"));
+        assert!(run_outcome(false, &["storage_operations"]).unwrap_err().to_string().contains("failed"));
+    }
+
+    #[test]
+    fn test_failed_targets_error() {
+        assert!(failed_targets_error(&[]).is_ok());
+
+        let err = failed_targets_error(&["storage_operations", "compatibility_checks"]).unwrap_err();
+        assert!(err.to_string().contains("2 benchmark target(s) failed: storage_operations, compatibility_checks"));
+    }
 }
@@ -0,0 +1,121 @@
+//! Compatibility matrix commands
+
+use clap::Subcommand;
+use schema_registry_core::compat_matrix::{self, MatrixLevel, MatrixView};
+use std::path::PathBuf;
+
+use crate::{
+    config::Config,
+    error::{CliError, Result},
+    output,
+};
+
+#[derive(Subcommand)]
+pub enum CompatCommand {
+    /// Show which versions of a subject are compatible with each other
+    ///
+    /// Reads the matrix kept by the compatibility matrix maintainer. Each row
+    /// compares a version with every earlier one.
+    Matrix {
+        /// Subject (namespace.name)
+        subject: String,
+
+        /// Compatibility level (backward, forward, full)
+        #[arg(long, default_value = "full")]
+        level: String,
+
+        /// Matrix file written by the maintainer
+        #[arg(long, default_value = "./data/compat-matrix.json")]
+        matrix_file: PathBuf,
+    },
+}
+
+pub async fn execute(cmd: CompatCommand, _config: &Config, format: output::OutputFormat) -> Result<()> {
+    match cmd {
+        CompatCommand::Matrix {
+            subject,
+            level,
+            matrix_file,
+        } => {
+            let level: MatrixLevel = level.parse()?;
+            let subjects = compat_matrix::load_matrix_file(&matrix_file)
+                .map_err(|e| CliError::ConfigError(format!("{}: {}", matrix_file.display(), e)))?;
+            let matrix = subjects.get(&subject).ok_or_else(|| {
+                CliError::NotFound(format!(
+                    "No compatibility matrix for {} in {}",
+                    subject,
+                    matrix_file.display()
+                ))
+            })?;
+            let view = MatrixView {
+                subject,
+                level,
+                versions: matrix.versions.clone(),
+                rows: matrix.rows(level).to_vec(),
+            };
+
+            if matches!(format, output::OutputFormat::Table) {
+                let (headers, rows) = triangle(&view);
+                output::print_table(headers.iter().map(String::as_str).collect(), rows);
+                Ok(())
+            } else {
+                output::print(&view, format)
+            }
+        }
+    }
+}
+
+/// Header and rows of the lower triangle: ✓/✗ below the diagonal, — on it
+fn triangle(view: &MatrixView) -> (Vec<String>, Vec<Vec<String>>) {
+    let mut headers = vec![format!("{} ({})", view.subject, view.level)];
+    headers.extend(view.versions.iter().map(ToString::to_string));
+
+    let rows = view
+        .versions
+        .iter()
+        .enumerate()
+        .map(|(i, version)| {
+            let mut row = vec![version.to_string()];
+            for j in 0..view.versions.len() {
+                let cell = match j.cmp(&i) {
+                    std::cmp::Ordering::Less => match view.rows.get(i).and_then(|row| row.get(j)) {
+                        Some(true) => "✓",
+                        Some(false) => "✗",
+                        None => "?",
+                    },
+                    std::cmp::Ordering::Equal => "—",
+                    std::cmp::Ordering::Greater => "",
+                };
+                row.push(cell.to_string());
+            }
+            row
+        })
+        .collect();
+    (headers, rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schema_registry_core::SemanticVersion;
+
+    #[test]
+    fn test_triangle_marks_lower_half_only() {
+        let view = MatrixView {
+            subject: "com.example.orders".to_string(),
+            level: MatrixLevel::Full,
+            versions: vec![
+                SemanticVersion::new(1, 0, 0),
+                SemanticVersion::new(1, 1, 0),
+                SemanticVersion::new(2, 0, 0),
+            ],
+            rows: vec![vec![], vec![true], vec![false, true]],
+        };
+
+        let (headers, rows) = triangle(&view);
+        assert_eq!(headers, vec!["com.example.orders (full)", "1.0.0", "1.1.0", "2.0.0"]);
+        assert_eq!(rows[0], vec!["1.0.0", "—", "", ""]);
+        assert_eq!(rows[1], vec!["1.1.0", "✓", "—", ""]);
+        assert_eq!(rows[2], vec!["2.0.0", "✗", "✓", "—"]);
+    }
+}
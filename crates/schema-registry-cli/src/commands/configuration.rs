@@ -0,0 +1,266 @@
+//! Configuration commands
+
+use clap::Subcommand;
+use regex::Regex;
+use serde::Serialize;
+
+use schema_registry_core::config_manager_adapter::{create_adapter_for, GlobalConfig, SchemaPolicies};
+use schema_registry_core::config_refresh::{ConfigChange, ConfigDiff};
+
+use crate::{
+    config::{Config, IgnoreRule},
+    error::{CliError, Result},
+    output,
+};
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Compare configuration and policies between two environments
+    DiffEnv {
+        /// Environment promoted from
+        #[arg(long)]
+        base: String,
+
+        /// Environment promoted to
+        #[arg(long)]
+        target: String,
+
+        /// Config Manager storage directory
+        #[arg(long, default_value = "./config")]
+        config_path: String,
+
+        /// Additional promotion-critical path prefixes (merged with the CLI config file)
+        #[arg(long = "critical")]
+        critical: Vec<String>,
+
+        /// Exit non-zero when a promotion-critical field differs
+        #[arg(long)]
+        fail_on_critical: bool,
+
+        /// Render a markdown table instead of the selected output format
+        #[arg(long)]
+        markdown: bool,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct EnvDiffRow {
+    #[serde(flatten)]
+    change: ConfigChange,
+    critical: bool,
+}
+
+pub async fn execute(cmd: ConfigCommand, config: &Config, format: output::OutputFormat) -> Result<()> {
+    match cmd {
+        ConfigCommand::DiffEnv { base, target, config_path, critical, fail_on_critical, markdown } => {
+            diff_env(config, &base, &target, &config_path, critical, fail_on_critical, markdown, format).await
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn diff_env(
+    config: &Config,
+    base: &str,
+    target: &str,
+    config_path: &str,
+    extra_critical: Vec<String>,
+    fail_on_critical: bool,
+    markdown: bool,
+    format: output::OutputFormat,
+) -> Result<()> {
+    let load = |environment: &str| -> Result<(GlobalConfig, SchemaPolicies)> {
+        let adapter = create_adapter_for(config_path, environment)
+            .map_err(|e| CliError::ConfigError(e.to_string()))?;
+        let global = adapter
+            .load_global_config()
+            .map_err(|e| CliError::ConfigError(e.to_string()))?;
+        let policies = adapter
+            .load_schema_policies()
+            .map_err(|e| CliError::ConfigError(e.to_string()))?;
+        Ok((global, policies))
+    };
+
+    let (base_config, base_policies) = load(base)?;
+    let (target_config, target_policies) = load(target)?;
+
+    let diff = ConfigDiff::compute(&base_config, &base_policies, &target_config, &target_policies)
+        .map_err(|e| CliError::ConfigError(e.to_string()))?
+        .redacted();
+
+    let ignore = compile_ignore_rules(&config.config_diff.ignore)?;
+    let critical: Vec<String> = config
+        .config_diff
+        .promotion_critical
+        .iter()
+        .cloned()
+        .chain(extra_critical)
+        .collect();
+
+    let rows: Vec<EnvDiffRow> = diff
+        .changes
+        .into_iter()
+        .filter(|change| !ignore.iter().any(|rule| rule.suppresses(change)))
+        .map(|change| EnvDiffRow {
+            critical: critical.iter().any(|prefix| path_under(&change.path, prefix)),
+            change,
+        })
+        .collect();
+
+    if markdown {
+        print!("{}", render_markdown(base, target, &rows));
+    } else {
+        match format {
+            output::OutputFormat::Table | output::OutputFormat::Plain => {
+                if rows.is_empty() {
+                    output::print_success(&format!("No differences between {} and {}", base, target));
+                } else {
+                    let table_rows = rows
+                        .iter()
+                        .map(|row| {
+                            vec![
+                                row.change.path.clone(),
+                                display_value(&row.change.base),
+                                display_value(&row.change.target),
+                                if row.critical { "yes".to_string() } else { String::new() },
+                            ]
+                        })
+                        .collect();
+                    output::print_table(vec!["Path", base, target, "Critical"], table_rows);
+                }
+            }
+            _ => output::print(&rows, format)?,
+        }
+    }
+
+    let critical_count = rows.iter().filter(|row| row.critical).count();
+    if fail_on_critical && critical_count > 0 {
+        return Err(CliError::ValidationError(format!(
+            "{} promotion-critical field(s) differ between {} and {}",
+            critical_count, base, target
+        )));
+    }
+
+    Ok(())
+}
+
+struct CompiledIgnoreRule<'a> {
+    path: &'a str,
+    value_pattern: Option<Regex>,
+}
+
+impl CompiledIgnoreRule<'_> {
+    /// Suppress when the path matches and, if a pattern is set, both sides match it
+    fn suppresses(&self, change: &ConfigChange) -> bool {
+        if !path_under(&change.path, self.path) {
+            return false;
+        }
+        match &self.value_pattern {
+            Some(pattern) => [&change.base, &change.target].iter().all(|value| {
+                value
+                    .as_ref()
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|s| pattern.is_match(s))
+            }),
+            None => true,
+        }
+    }
+}
+
+fn compile_ignore_rules(rules: &[IgnoreRule]) -> Result<Vec<CompiledIgnoreRule<'_>>> {
+    rules
+        .iter()
+        .map(|rule| {
+            let value_pattern = rule
+                .value_pattern
+                .as_deref()
+                .map(Regex::new)
+                .transpose()
+                .map_err(|e| CliError::ConfigError(format!("Invalid ignore pattern for {}: {}", rule.path, e)))?;
+            Ok(CompiledIgnoreRule { path: &rule.path, value_pattern })
+        })
+        .collect()
+}
+
+/// Whether `path` equals `prefix` or lies below it
+fn path_under(path: &str, prefix: &str) -> bool {
+    path == prefix
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with(['.', '[']))
+}
+
+fn display_value(value: &Option<serde_json::Value>) -> String {
+    match value {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => "(absent)".to_string(),
+    }
+}
+
+fn render_markdown(base: &str, target: &str, rows: &[EnvDiffRow]) -> String {
+    let mut output = format!("## Configuration diff: {} → {}\n\n", base, target);
+    if rows.is_empty() {
+        output.push_str("No differences.\n");
+        return output;
+    }
+
+    output.push_str(&format!("| Path | {} | {} | Critical |\n", base, target));
+    output.push_str("|------|------|------|----------|\n");
+    for row in rows {
+        output.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            row.change.path,
+            display_value(&row.change.base).replace('|', "\\|"),
+            display_value(&row.change.target).replace('|', "\\|"),
+            if row.critical { "**yes**" } else { "" }
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn change(path: &str, base: &str, target: &str) -> ConfigChange {
+        ConfigChange {
+            path: path.to_string(),
+            base: Some(json!(base)),
+            target: Some(json!(target)),
+        }
+    }
+
+    #[test]
+    fn test_path_under() {
+        assert!(path_under("config.server.host", "config.server"));
+        assert!(path_under("config.server", "config.server"));
+        assert!(path_under("policies.custom_rules[0].name", "policies.custom_rules"));
+        assert!(!path_under("config.server_extra", "config.server"));
+    }
+
+    #[test]
+    fn test_ignore_rule_with_value_pattern() {
+        let rules = vec![IgnoreRule {
+            path: "config.server.host".to_string(),
+            value_pattern: Some(r"^[a-z0-9-]+\.internal$".to_string()),
+        }];
+        let compiled = compile_ignore_rules(&rules).unwrap();
+
+        assert!(compiled[0].suppresses(&change("config.server.host", "stg-1.internal", "prod-1.internal")));
+        assert!(!compiled[0].suppresses(&change("config.server.host", "stg-1.internal", "10.0.0.1")));
+        assert!(!compiled[0].suppresses(&change("config.server.port", "a.internal", "b.internal")));
+    }
+
+    #[test]
+    fn test_render_markdown_marks_critical() {
+        let rows = vec![EnvDiffRow {
+            change: change("config.security.enable_tls", "false", "true"),
+            critical: true,
+        }];
+        let markdown = render_markdown("staging", "production", &rows);
+        assert!(markdown.contains("| Path | staging | production | Critical |"));
+        assert!(markdown.contains("| `config.security.enable_tls` | false | true | **yes** |"));
+    }
+}
@@ -0,0 +1,198 @@
+//! Setup diagnostics command
+
+use clap::Args;
+use schema_registry_core::doctor::{
+    checks, CheckOutcome, CheckStatus, DiagnosticCheck, DiagnosticContext, Doctor, DoctorReport,
+};
+use std::path::PathBuf;
+
+use crate::{
+    config,
+    error::{CliError, Result},
+    output,
+};
+
+#[derive(Args, Debug)]
+pub struct DoctorArgs {
+    /// Skip the check with this id (repeatable)
+    #[arg(long, value_name = "ID")]
+    skip: Vec<String>,
+
+    /// List the available checks without running them
+    #[arg(long)]
+    list: bool,
+
+    /// Config Manager storage directory
+    #[arg(long, default_value = "./config")]
+    config_path: PathBuf,
+
+    /// Config Manager environment
+    #[arg(long, default_value = "dev")]
+    environment: String,
+
+    /// Schema storage directory
+    #[arg(long, default_value = "./data")]
+    storage_dir: PathBuf,
+
+    /// Benchmark output directory
+    #[arg(long, default_value = "benchmarks/output")]
+    benchmark_output: PathBuf,
+}
+
+/// The CLI configuration file exists and parses
+///
+/// Runs before the configuration is loaded, so a broken file is reported here
+/// instead of aborting the command.
+struct CliConfigCheck {
+    path: Option<String>,
+}
+
+impl DiagnosticCheck for CliConfigCheck {
+    fn id(&self) -> &str {
+        "cli-config"
+    }
+
+    fn description(&self) -> &str {
+        "CLI configuration file is readable and valid"
+    }
+
+    fn run(&self, _ctx: &DiagnosticContext) -> CheckOutcome {
+        let shown = match &self.path {
+            Some(path) => path.clone(),
+            None => match config::config_path() {
+                Ok(path) if path.exists() => path.display().to_string(),
+                _ => return CheckOutcome::pass("no config file; using defaults"),
+            },
+        };
+        let loaded = config::load_config(self.path.as_deref());
+        match loaded.map(|config| url::Url::parse(&config.registry_url).map(|_| config)) {
+            Ok(Ok(config)) => CheckOutcome::pass(format!("{} (registry {})", shown, config.registry_url)),
+            Ok(Err(e)) => CheckOutcome::fail(
+                format!("{}: registry_url is not a valid URL: {}", shown, e),
+                "Set registry_url to a full URL such as http://localhost:8080",
+            ),
+            Err(e) => CheckOutcome::fail(
+                format!("{}: {}", shown, e),
+                "Fix the file, point --config / SCHEMA_REGISTRY_CONFIG at the right path, or run 'schema-cli init'",
+            ),
+        }
+    }
+}
+
+/// The CLI's check, the built-in checks, then any registered by downstream crates
+fn doctor(config_path: Option<&str>) -> Doctor {
+    let cli_check: Box<dyn DiagnosticCheck> = Box::new(CliConfigCheck {
+        path: config_path.map(str::to_string),
+    });
+    checks::builtin_checks()
+        .into_iter()
+        .fold(Doctor::new().with_check(cli_check), Doctor::with_check)
+        .with_registered()
+}
+
+pub async fn execute(args: DoctorArgs, config_path: Option<&str>, format: output::OutputFormat) -> Result<()> {
+    let doctor = doctor(config_path).with_skipped(args.skip.iter().cloned());
+
+    if args.list {
+        let rows = doctor.check_ids().into_iter().map(|id| vec![id.to_string()]).collect();
+        output::print_table(vec!["Check"], rows);
+        return Ok(());
+    }
+
+    let unknown = doctor.unknown_skips();
+    if !unknown.is_empty() {
+        return Err(CliError::ValidationError(format!(
+            "unknown check id(s) for --skip: {} (available: {})",
+            unknown.join(", "),
+            doctor.check_ids().join(", ")
+        )));
+    }
+
+    let ctx = DiagnosticContext {
+        config_storage_path: args.config_path,
+        environment: args.environment,
+        storage_dir: args.storage_dir,
+        benchmark_output_dir: args.benchmark_output,
+    };
+    let report = doctor.run(&ctx);
+
+    match format {
+        output::OutputFormat::Table | output::OutputFormat::Plain => print_report(&report),
+        _ => output::print(&report, format)?,
+    }
+
+    let failed = report.count(CheckStatus::Fail);
+    if failed > 0 {
+        return Err(CliError::ValidationError(format!("{} doctor check(s) failed", failed)));
+    }
+    Ok(())
+}
+
+fn print_report(report: &DoctorReport) {
+    let rows = report
+        .checks
+        .iter()
+        .map(|check| {
+            vec![
+                check.id.clone(),
+                check.outcome.status.to_string().to_uppercase(),
+                check.outcome.message.clone(),
+                check.outcome.remediation.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    output::print_table(vec!["Check", "Status", "Details", "Remediation"], rows);
+
+    let summary = format!(
+        "{} passed, {} warned, {} failed, {} skipped",
+        report.count(CheckStatus::Pass),
+        report.count(CheckStatus::Warn),
+        report.count(CheckStatus::Fail),
+        report.skipped.len()
+    );
+    if report.has_failures() {
+        output::print_error_msg(&summary);
+    } else if report.count(CheckStatus::Warn) > 0 {
+        output::print_warning(&summary);
+    } else {
+        output::print_success(&summary);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_config_check_reports_broken_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        let check = |contents: &str| {
+            std::fs::write(&path, contents).unwrap();
+            CliConfigCheck {
+                path: Some(path.to_string_lossy().into_owned()),
+            }
+            .run(&DiagnosticContext::default())
+            .status
+        };
+
+        assert_eq!(check("registry_url: http://localhost:8080\n"), CheckStatus::Pass);
+        assert_eq!(check("registry_url: localhost\n"), CheckStatus::Fail);
+        assert_eq!(check("registry_url: [unterminated\n"), CheckStatus::Fail);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_skip_is_rejected() {
+        let args = DoctorArgs {
+            skip: vec!["no-such-check".to_string()],
+            list: false,
+            config_path: PathBuf::from("./config"),
+            environment: "dev".to_string(),
+            storage_dir: PathBuf::from("./data"),
+            benchmark_output: PathBuf::from("benchmarks/output"),
+        };
+        let err = execute(args, None, output::OutputFormat::Json).await.unwrap_err();
+        assert!(err.to_string().contains("no-such-check"));
+        assert!(doctor(None).check_ids().starts_with(&["cli-config", "config-source"]));
+    }
+}
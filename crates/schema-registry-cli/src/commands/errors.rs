@@ -0,0 +1,82 @@
+//! Error catalog commands
+
+use clap::Subcommand;
+use schema_registry_core::error_catalog::ErrorCatalog;
+
+use crate::{config::Config, error, error::Result, output};
+
+#[derive(Subcommand)]
+pub enum ErrorsCommand {
+    /// Generate the error reference from the error catalog
+    Docs {
+        /// Write the Markdown reference to this file instead of stdout
+        #[arg(long)]
+        out: Option<String>,
+
+        /// Also write the catalog as JSON to this file
+        #[arg(long)]
+        json: Option<String>,
+    },
+}
+
+pub async fn execute(cmd: ErrorsCommand, _config: &Config, format: output::OutputFormat) -> Result<()> {
+    match cmd {
+        ErrorsCommand::Docs { out, json } => generate_docs(&error::catalog(), out, json, format),
+    }
+}
+
+fn generate_docs(
+    catalog: &ErrorCatalog,
+    out: Option<String>,
+    json: Option<String>,
+    format: output::OutputFormat,
+) -> Result<()> {
+    if let Some(path) = &json {
+        std::fs::write(path, serde_json::to_string_pretty(catalog)?)?;
+        output::print_success(&format!("Wrote {} error codes to {}", catalog.entries().len(), path));
+    }
+
+    match out {
+        Some(path) => {
+            std::fs::write(&path, catalog.to_markdown())?;
+            output::print_success(&format!("Wrote error reference to {}", path));
+        }
+        None if json.is_none() => match format {
+            output::OutputFormat::Table | output::OutputFormat::Plain => print!("{}", catalog.to_markdown()),
+            output::OutputFormat::Json | output::OutputFormat::Yaml => output::print(catalog, format)?,
+        },
+        None => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_docs_written_to_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let md = dir.path().join("errors.md");
+        let json = dir.path().join("errors.json");
+
+        generate_docs(
+            &error::catalog(),
+            Some(md.to_string_lossy().into_owned()),
+            Some(json.to_string_lossy().into_owned()),
+            output::OutputFormat::Table,
+        )
+        .unwrap();
+
+        let markdown = std::fs::read_to_string(&md).unwrap();
+        assert!(markdown.starts_with("# Error Reference"));
+        assert!(markdown.contains("`CLI_CONFIG_ERROR`"));
+        assert!(markdown.contains("`STORAGE_BUSY`"));
+
+        let entries: Vec<serde_json::Value> =
+            serde_json::from_str(&std::fs::read_to_string(&json).unwrap()).unwrap();
+        assert_eq!(entries.len(), error::catalog().entries().len());
+        assert_eq!(entries[0]["code"], "SCHEMA_NOT_FOUND");
+        assert_eq!(entries[0]["category"], "not_found");
+    }
+}
@@ -0,0 +1,156 @@
+//! Governance reporting commands
+
+use clap::{Subcommand, ValueEnum};
+use indicatif::{ProgressBar, ProgressStyle};
+use schema_registry_core::config_manager_adapter::create_adapter_for;
+use schema_registry_core::embedded::InMemoryStorage;
+use schema_registry_validation::governance::{GovernanceReport, GovernanceScanner};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::{
+    commands::{benchmark::parse_timeout, storage},
+    config::Config,
+    error::{CliError, Result},
+    output,
+};
+
+#[derive(Subcommand)]
+pub enum GovernanceCommand {
+    /// Policy findings, ownership, deprecation and quality across the registry
+    ///
+    /// Runs the current policies against every subject's latest version
+    /// without enforcing them, and rolls the results up per owning team.
+    Report {
+        /// Blob store directory holding the registry's schemas
+        #[arg(long)]
+        dir: PathBuf,
+
+        /// Report file; its extension is replaced for each format
+        #[arg(long, default_value = "governance.md")]
+        out: PathBuf,
+
+        /// Formats to write, comma-separated
+        #[arg(long, value_delimiter = ',', default_value = "markdown")]
+        format: Vec<ReportFormat>,
+
+        /// Scan for at most this long (e.g. 10m), sampling subjects if the
+        /// registry is too large; the report states the coverage
+        #[arg(long, value_name = "DURATION", value_parser = parse_timeout)]
+        time_budget: Option<Duration>,
+
+        /// Environment whose policies to apply
+        #[arg(long, default_value = "dev")]
+        environment: String,
+
+        /// Config Manager storage directory
+        #[arg(long, default_value = "./config")]
+        config_path: String,
+    },
+}
+
+/// File format of a governance report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Summary tables for reading
+    Markdown,
+    /// The full report for loading into a data warehouse
+    Json,
+}
+
+impl ReportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Json => "json",
+        }
+    }
+}
+
+pub async fn execute(cmd: GovernanceCommand, _config: &Config, _format: output::OutputFormat) -> Result<()> {
+    match cmd {
+        GovernanceCommand::Report { dir, out, format, time_budget, environment, config_path } => {
+            let policies = create_adapter_for(&config_path, &environment)
+                .and_then(|adapter| adapter.load_schema_policies())
+                .map_err(|e| CliError::ConfigError(e.to_string()))?;
+
+            let store = storage::open_store(&dir).await?;
+            let registry = InMemoryStorage::new();
+            storage::load_schemas(&store, &registry).await?;
+
+            let mut scanner = GovernanceScanner::new(policies);
+            if let Some(budget) = time_budget {
+                scanner = scanner.with_time_budget(budget);
+            }
+            let progress = ProgressBar::new(0);
+            progress.set_style(
+                ProgressStyle::with_template("{bar:40.cyan/blue} {pos}/{len} subjects ({msg})")
+                    .unwrap_or_else(|_| ProgressStyle::default_bar()),
+            );
+            let report = scanner
+                .scan_with_progress(&registry, |step| {
+                    progress.set_length(step.listed as u64);
+                    progress.set_position(step.position as u64);
+                    progress.set_message(if step.sampling {
+                        format!("{} scanned, sampling", step.scanned)
+                    } else {
+                        format!("{} scanned", step.scanned)
+                    });
+                })
+                .await?;
+            progress.finish_and_clear();
+
+            for path in write_report(&report, &out, &format)? {
+                output::print_success(&format!("Wrote {}", path.display()));
+            }
+            if let Some(note) = report.coverage.describe() {
+                output::print_warning(&note);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Write `report` in each of `formats` next to `out`; returns the files written
+fn write_report(report: &GovernanceReport, out: &Path, formats: &[ReportFormat]) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for format in formats {
+        let path = out.with_extension(format.extension());
+        let content = match format {
+            ReportFormat::Markdown => report.to_markdown(),
+            ReportFormat::Json => serde_json::to_string_pretty(report)?,
+        };
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, content)?;
+        if !written.contains(&path) {
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schema_registry_core::config_manager_adapter::SchemaPolicies;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_write_report_in_each_format() {
+        let report = GovernanceScanner::new(SchemaPolicies::default())
+            .scan(&InMemoryStorage::new())
+            .await
+            .unwrap();
+        let dir = TempDir::new().unwrap();
+        let out = dir.path().join("reports").join("governance.md");
+
+        let written = write_report(&report, &out, &[ReportFormat::Markdown, ReportFormat::Json]).unwrap();
+        assert_eq!(written, vec![out.clone(), out.with_extension("json")]);
+        assert!(std::fs::read_to_string(&out).unwrap().starts_with("# Schema Governance Report"));
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(out.with_extension("json")).unwrap()).unwrap();
+        assert_eq!(json["totals"]["subjects"], 0);
+    }
+}
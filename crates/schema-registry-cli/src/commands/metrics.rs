@@ -0,0 +1,62 @@
+//! Metric catalog commands
+
+use clap::Subcommand;
+use schema_registry_core::metric_names::{self, MetricName};
+
+use crate::{config::Config, error::Result, output};
+
+#[derive(Subcommand)]
+pub enum MetricsCommand {
+    /// List every metric name the registry and benchmarks emit
+    Catalog,
+}
+
+pub async fn execute(cmd: MetricsCommand, _config: &Config, format: output::OutputFormat) -> Result<()> {
+    match cmd {
+        MetricsCommand::Catalog => show_catalog(metric_names::catalog(), format),
+    }
+}
+
+fn show_catalog(catalog: &[MetricName], format: output::OutputFormat) -> Result<()> {
+    match format {
+        output::OutputFormat::Table => {
+            output::print_table(vec!["Name", "Kind", "Unit", "Description"], catalog_rows(catalog));
+            println!("Total: {} metrics", catalog.len());
+        }
+        output::OutputFormat::Plain => {
+            for metric in catalog {
+                println!("{}", metric);
+            }
+        }
+        output::OutputFormat::Json | output::OutputFormat::Yaml => output::print(&catalog, format)?,
+    }
+    Ok(())
+}
+
+fn catalog_rows(catalog: &[MetricName]) -> Vec<Vec<String>> {
+    catalog
+        .iter()
+        .map(|metric| {
+            vec![
+                metric.name.to_string(),
+                metric.kind.to_string(),
+                metric.unit.to_string(),
+                metric.description.to_string(),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_rows_cover_catalog() {
+        let rows = catalog_rows(metric_names::catalog());
+        assert_eq!(rows.len(), metric_names::catalog().len());
+        assert!(rows
+            .iter()
+            .any(|row| row[0] == "avg_ms" && row[1] == "statistic" && row[2] == "milliseconds"));
+    }
+}
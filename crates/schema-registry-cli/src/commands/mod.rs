@@ -3,6 +3,91 @@
 pub mod admin;
 pub mod analytics;
 pub mod benchmark;
+pub mod compat;
+pub mod configuration;
+pub mod doctor;
+pub mod errors;
+pub mod governance;
 pub mod lineage;
+pub mod metrics;
 pub mod migration;
+pub mod policy;
+pub mod scenario;
 pub mod schema;
+pub mod storage;
+
+use clap::Args;
+use schema_registry_core::pagination::{self, Page, PageRequest};
+use std::future::Future;
+
+use crate::error::Result;
+
+/// Cursor flags shared by listing commands
+#[derive(Args, Debug, Clone, Default)]
+pub struct PageArgs {
+    /// Continue from the cursor printed after a previous page
+    #[arg(long, conflicts_with = "all")]
+    pub cursor: Option<String>,
+
+    /// Follow cursors and fetch every page
+    #[arg(long)]
+    pub all: bool,
+}
+
+impl PageArgs {
+    /// Request for the first page this invocation should fetch
+    pub fn request(&self, limit: usize, pattern: Option<String>) -> PageRequest {
+        PageRequest {
+            cursor: self.cursor.clone(),
+            limit,
+            pattern,
+        }
+    }
+
+    /// Fetch one page, or every page with `--all`
+    pub async fn fetch<T, F, Fut>(&self, first: PageRequest, mut fetch: F) -> Result<Page<T>>
+    where
+        F: FnMut(PageRequest) -> Fut,
+        Fut: Future<Output = schema_registry_core::Result<Page<T>>>,
+    {
+        let page = if self.all {
+            Page {
+                items: pagination::collect_all(first, fetch).await?,
+                next_cursor: None,
+            }
+        } else {
+            fetch(first).await?
+        };
+        Ok(page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn numbers(request: PageRequest) -> schema_registry_core::Result<Page<String>> {
+        let all: Vec<String> = (0..7).map(|n| format!("subject-{}", n)).collect();
+        pagination::paginate(all, &request, |s| s.as_str())
+    }
+
+    #[tokio::test]
+    async fn test_page_args_single_page_and_all() {
+        let one = PageArgs::default();
+        let page = one.fetch(one.request(3, None), numbers).await.unwrap();
+        assert_eq!(page.items.len(), 3);
+        assert!(page.next_cursor.is_some());
+
+        let next = PageArgs {
+            cursor: page.next_cursor,
+            all: false,
+        };
+        let page = next.fetch(next.request(3, None), numbers).await.unwrap();
+        assert_eq!(page.items, vec!["subject-3", "subject-4", "subject-5"]);
+
+        let all = PageArgs { cursor: None, all: true };
+        let page = all.fetch(all.request(3, None), numbers).await.unwrap();
+        assert_eq!(page.items.len(), 7);
+        assert!(page.is_last());
+    }
+}
@@ -0,0 +1,130 @@
+//! Schema policy commands
+
+use chrono::{NaiveDate, Utc};
+use clap::Subcommand;
+use serde::Serialize;
+
+use schema_registry_core::config_manager_adapter::{create_adapter_for, PolicyWaiver};
+use schema_registry_core::doctor::checks::lint_waivers;
+
+use crate::{
+    config::Config,
+    error::{CliError, Result},
+    output,
+};
+
+#[derive(Subcommand)]
+pub enum PolicyCommand {
+    /// Policy waiver commands
+    #[command(subcommand)]
+    Waivers(WaiverCommand),
+}
+
+#[derive(Subcommand)]
+pub enum WaiverCommand {
+    /// List waivers from the schema policies
+    List {
+        /// Only waivers that still apply
+        #[arg(long, conflicts_with = "expired")]
+        active: bool,
+
+        /// Only waivers past their expiry date
+        #[arg(long)]
+        expired: bool,
+
+        /// Environment whose policies to read
+        #[arg(long, default_value = "dev")]
+        environment: String,
+
+        /// Config Manager storage directory
+        #[arg(long, default_value = "./config")]
+        config_path: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct WaiverRow<'a> {
+    #[serde(flatten)]
+    waiver: &'a PolicyWaiver,
+    active: bool,
+}
+
+pub async fn execute(cmd: PolicyCommand, _config: &Config, format: output::OutputFormat) -> Result<()> {
+    match cmd {
+        PolicyCommand::Waivers(WaiverCommand::List { active, expired, environment, config_path }) => {
+            let policies = create_adapter_for(&config_path, &environment)
+                .and_then(|adapter| adapter.load_schema_policies())
+                .map_err(|e| CliError::ConfigError(e.to_string()))?;
+            let today = Utc::now().date_naive();
+
+            let rows = select_waivers(&policies.waivers, today, active, expired);
+            print_waivers(&rows, format)?;
+            for warning in lint_waivers(&policies) {
+                output::print_warning(&warning);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Waivers to list: all, or only the active or expired ones
+fn select_waivers(waivers: &[PolicyWaiver], today: NaiveDate, active: bool, expired: bool) -> Vec<WaiverRow<'_>> {
+    waivers
+        .iter()
+        .map(|waiver| WaiverRow {
+            waiver,
+            active: waiver.is_active(today),
+        })
+        .filter(|row| !(active && !row.active) && !(expired && row.active))
+        .collect()
+}
+
+fn print_waivers(rows: &[WaiverRow<'_>], format: output::OutputFormat) -> Result<()> {
+    match format {
+        output::OutputFormat::Table | output::OutputFormat::Plain => {
+            if rows.is_empty() {
+                output::print_info("No waivers");
+                return Ok(());
+            }
+            let table_rows = rows
+                .iter()
+                .map(|row| {
+                    vec![
+                        row.waiver.subject.clone(),
+                        row.waiver.rules.join(", "),
+                        row.waiver.expires.to_string(),
+                        if row.active { "active" } else { "expired" }.to_string(),
+                        row.waiver.approver.clone(),
+                        row.waiver.reason.clone(),
+                    ]
+                })
+                .collect();
+            output::print_table(vec!["Subject", "Rules", "Expires", "Status", "Approver", "Reason"], table_rows);
+        }
+        _ => output::print(&rows, format)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_waivers_by_status() {
+        let waiver = |subject: &str, day| PolicyWaiver {
+            subject: subject.to_string(),
+            rules: vec!["field-naming-policy".to_string()],
+            expires: NaiveDate::from_ymd_opt(2025, 3, day).unwrap(),
+            approver: "governance".to_string(),
+            reason: "legacy producer".to_string(),
+        };
+        let waivers = vec![waiver("payments-value", 1), waiver("orders-*", 20)];
+        let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let subjects = |rows: Vec<WaiverRow<'_>>| rows.iter().map(|row| row.waiver.subject.clone()).collect::<Vec<_>>();
+
+        assert_eq!(subjects(select_waivers(&waivers, today, false, false)).len(), 2);
+        assert_eq!(subjects(select_waivers(&waivers, today, true, false)), vec!["orders-*"]);
+        assert_eq!(subjects(select_waivers(&waivers, today, false, true)), vec!["payments-value"]);
+    }
+}
@@ -0,0 +1,139 @@
+//! Scenario command implementation
+
+use crate::config::Config;
+use crate::error::{CliError, Result};
+use crate::output::{self, OutputFormat};
+use crate::scenarios::{self, Backend, Scenario, ScenarioReport, StepStatus};
+use clap::{Subcommand, ValueEnum};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Where scenario steps are executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BackendKind {
+    /// In-process embedded registry, empty at the start of the run
+    Embedded,
+    /// Running registry server at --url (or the configured registry URL)
+    Http,
+}
+
+#[derive(Subcommand)]
+pub enum ScenarioCommand {
+    /// Run a scenario file and report each step
+    Run {
+        /// Scenario YAML file
+        file: PathBuf,
+
+        /// Backend to run the steps against
+        #[arg(long, value_enum, default_value = "embedded")]
+        backend: BackendKind,
+
+        /// Set or override a scenario variable
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var)]
+        vars: Vec<(String, String)>,
+
+        /// Write a JUnit XML report to this path
+        #[arg(long, value_name = "PATH")]
+        junit: Option<PathBuf>,
+
+        /// Write a markdown report to this path
+        #[arg(long, value_name = "PATH")]
+        report: Option<PathBuf>,
+    },
+}
+
+pub async fn execute(cmd: ScenarioCommand, config: &Config, format: OutputFormat) -> Result<()> {
+    match cmd {
+        ScenarioCommand::Run {
+            file,
+            backend,
+            vars,
+            junit,
+            report,
+        } => {
+            let scenario = Scenario::parse(&std::fs::read_to_string(&file)?)
+                .map_err(|e| CliError::ValidationError(format!("{}: {}", file.display(), e)))?;
+            let mut backend = match backend {
+                BackendKind::Embedded => Backend::embedded(),
+                BackendKind::Http => Backend::http(&config.registry_url)
+                    .map_err(|e| CliError::ConfigError(e.message))?,
+            };
+            let base_dir = file.parent().unwrap_or(Path::new("."));
+            let overrides: BTreeMap<String, String> = vars.into_iter().collect();
+
+            let result = scenarios::run_scenario(&scenario, base_dir, &overrides, &mut backend).await;
+
+            if let Some(path) = junit {
+                std::fs::write(&path, result.to_junit_xml())?;
+                output::print_info(&format!("JUnit report written to {}", path.display()));
+            }
+            if let Some(path) = report {
+                std::fs::write(&path, result.to_markdown())?;
+                output::print_info(&format!("Markdown report written to {}", path.display()));
+            }
+            print_report(&result, format)?;
+
+            if result.is_success() {
+                Ok(())
+            } else {
+                Err(CliError::ValidationError(format!(
+                    "{} of {} scenario steps failed",
+                    result.count(StepStatus::Failed),
+                    result.steps.len()
+                )))
+            }
+        }
+    }
+}
+
+fn print_report(report: &ScenarioReport, format: OutputFormat) -> Result<()> {
+    if !matches!(format, OutputFormat::Table) {
+        return output::print(report, format);
+    }
+
+    let rows: Vec<Vec<String>> = report
+        .steps
+        .iter()
+        .map(|step| {
+            let status = match step.status {
+                StepStatus::Passed => "passed",
+                StepStatus::Failed => "FAILED",
+                StepStatus::Skipped => "skipped",
+            };
+            let detail = match &step.error {
+                Some(error) => format!("{}: {}", error.code, error.message),
+                None => step.detail.clone().unwrap_or_default(),
+            };
+            vec![
+                (step.index + 1).to_string(),
+                step.name.clone(),
+                step.action.to_string(),
+                status.to_string(),
+                detail,
+            ]
+        })
+        .collect();
+    output::print_table(vec!["#", "Step", "Action", "Status", "Detail"], rows);
+
+    let summary = format!(
+        "{}: {} passed, {} failed, {} skipped on {} backend",
+        report.scenario,
+        report.count(StepStatus::Passed),
+        report.count(StepStatus::Failed),
+        report.count(StepStatus::Skipped),
+        report.backend
+    );
+    if report.is_success() {
+        output::print_success(&summary);
+    } else {
+        output::print_warning(&summary);
+    }
+    Ok(())
+}
+
+fn parse_var(arg: &str) -> std::result::Result<(String, String), String> {
+    arg.split_once('=')
+        .filter(|(key, _)| !key.is_empty())
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", arg))
+}
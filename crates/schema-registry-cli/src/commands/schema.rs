@@ -4,13 +4,21 @@ use clap::Subcommand;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{config::Config, error::Result, output};
+use schema_registry_core::annotations::{self, AnnotationFilter};
+use schema_registry_core::examples::{self, ExampleOptions};
+use schema_registry_core::flatten::{self, FlattenOptions, DEFAULT_MAX_OUTPUT_BYTES};
+use schema_registry_core::ingest;
+use schema_registry_core::pagination::{self, Page, PageRequest};
+use schema_registry_core::types::{CompatibilityMode, SerializationFormat};
+use schema_registry_compatibility::report;
+
+use crate::{commands::PageArgs, config::Config, error::{CliError, Result}, output};
 
 #[derive(Subcommand)]
 pub enum SchemaCommand {
     /// List all schemas
     List {
-        /// Filter by subject
+        /// Filter by subject (`*` matches any characters)
         #[arg(short, long)]
         subject: Option<String>,
 
@@ -18,9 +26,12 @@ pub enum SchemaCommand {
         #[arg(short, long)]
         schema_type: Option<String>,
 
-        /// Limit number of results
+        /// Results per page (at most 1000)
         #[arg(short, long, default_value = "100")]
         limit: usize,
+
+        #[command(flatten)]
+        page: PageArgs,
     },
 
     /// Get schema by ID
@@ -39,7 +50,7 @@ pub enum SchemaCommand {
         #[arg(short, long)]
         subject: String,
 
-        /// Schema content (file path or JSON)
+        /// Schema content (file path, - for stdin, or JSON)
         #[arg(short, long)]
         content: String,
 
@@ -54,7 +65,7 @@ pub enum SchemaCommand {
 
     /// Validate schema content
     Validate {
-        /// Schema content (file path or JSON)
+        /// Schema content (file path, - for stdin, or JSON)
         content: String,
 
         /// Schema type
@@ -64,15 +75,19 @@ pub enum SchemaCommand {
 
     /// Check compatibility between schemas
     Compatible {
-        /// Old schema ID
+        /// Old schema file (- for stdin)
         old: String,
 
-        /// New schema ID or content
+        /// New schema file (- for stdin)
         new: String,
 
         /// Compatibility mode (BACKWARD, FORWARD, FULL, NONE)
         #[arg(short, long, default_value = "BACKWARD")]
         mode: String,
+
+        /// Schema type (JSON, AVRO, PROTOBUF); inferred from the new file's extension if omitted
+        #[arg(short = 't', long)]
+        schema_type: Option<String>,
     },
 
     /// Get schema versions
@@ -96,9 +111,77 @@ pub enum SchemaCommand {
         /// Search query
         query: String,
 
-        /// Limit results
+        /// Results per page (at most 1000)
         #[arg(short, long, default_value = "20")]
         limit: usize,
+
+        #[command(flatten)]
+        page: PageArgs,
+    },
+
+    /// Generate example payloads conforming to a schema
+    Example {
+        /// Schema file
+        #[arg(short, long)]
+        file: String,
+
+        /// Schema type (JSON, AVRO); inferred from the file extension if omitted
+        #[arg(short = 't', long)]
+        schema_type: Option<String>,
+
+        /// Seed for deterministic generation
+        #[arg(long, default_value = "0")]
+        seed: u64,
+
+        /// Number of examples to generate
+        #[arg(long, default_value = "1")]
+        count: usize,
+
+        /// Probability of including optional fields (0.0 - 1.0)
+        #[arg(long, default_value = "0.5")]
+        optional_probability: f64,
+    },
+
+    /// Extract custom annotations (x-* keywords, Avro properties, Protobuf options)
+    Annotations {
+        /// Schema file
+        #[arg(short, long)]
+        file: String,
+
+        /// Schema type (JSON, AVRO, PROTOBUF); inferred from the file extension if omitted
+        #[arg(short = 't', long)]
+        schema_type: Option<String>,
+
+        /// Only annotations whose key starts with this prefix (e.g. x-)
+        #[arg(long)]
+        prefix: Option<String>,
+
+        /// Only annotations at or below this path (e.g. $.address)
+        #[arg(long)]
+        path: Option<String>,
+    },
+
+    /// Inline all resolvable references into one self-contained schema
+    Flatten {
+        /// Root schema file
+        #[arg(short, long)]
+        file: String,
+
+        /// Directory searched for referenced files and Avro named types (repeatable)
+        #[arg(short = 'I', long = "include-dir")]
+        include_dirs: Vec<String>,
+
+        /// Write the flattened schema here instead of stdout
+        #[arg(long)]
+        out: Option<String>,
+
+        /// Schema type (JSON, AVRO); inferred from the file extension if omitted
+        #[arg(short = 't', long)]
+        schema_type: Option<String>,
+
+        /// Fail if the flattened schema would exceed this many bytes
+        #[arg(long, default_value_t = DEFAULT_MAX_OUTPUT_BYTES)]
+        max_output_bytes: usize,
     },
 }
 
@@ -113,8 +196,8 @@ pub struct SchemaListItem {
 
 pub async fn execute(cmd: SchemaCommand, config: &Config, format: output::OutputFormat) -> Result<()> {
     match cmd {
-        SchemaCommand::List { subject, schema_type, limit } => {
-            list_schemas(config, subject.as_deref(), schema_type.as_deref(), limit, format).await
+        SchemaCommand::List { subject, schema_type, limit, page } => {
+            list_schemas(config, subject, schema_type.as_deref(), limit, &page, format).await
         }
         SchemaCommand::Get { id, full } => {
             get_schema(config, &id, full, format).await
@@ -125,8 +208,8 @@ pub async fn execute(cmd: SchemaCommand, config: &Config, format: output::Output
         SchemaCommand::Validate { content, schema_type } => {
             validate_schema(config, &content, &schema_type, format).await
         }
-        SchemaCommand::Compatible { old, new, mode } => {
-            check_compatibility(config, &old, &new, &mode, format).await
+        SchemaCommand::Compatible { old, new, mode, schema_type } => {
+            check_compatibility(config, &old, &new, &mode, schema_type.as_deref(), format).await
         }
         SchemaCommand::Versions { subject } => {
             list_versions(config, &subject, format).await
@@ -134,36 +217,39 @@ pub async fn execute(cmd: SchemaCommand, config: &Config, format: output::Output
         SchemaCommand::Delete { id, confirm } => {
             delete_schema(config, &id, confirm, format).await
         }
-        SchemaCommand::Search { query, limit } => {
-            search_schemas(config, &query, limit, format).await
+        SchemaCommand::Search { query, limit, page } => {
+            search_schemas(config, &query, limit, &page, format).await
+        }
+        SchemaCommand::Example { file, schema_type, seed, count, optional_probability } => {
+            generate_examples(config, &file, schema_type.as_deref(), seed, count, optional_probability, format).await
+        }
+        SchemaCommand::Annotations { file, schema_type, prefix, path } => {
+            show_annotations(config, &file, schema_type.as_deref(), prefix, path, format).await
+        }
+        SchemaCommand::Flatten { file, include_dirs, out, schema_type, max_output_bytes } => {
+            flatten_schema(&file, &include_dirs, out.as_deref(), schema_type.as_deref(), max_output_bytes, format).await
         }
     }
 }
 
 async fn list_schemas(
-    _config: &Config,
-    subject: Option<&str>,
+    config: &Config,
+    subject: Option<String>,
     schema_type: Option<&str>,
     limit: usize,
+    page_args: &PageArgs,
     format: output::OutputFormat,
 ) -> Result<()> {
-    // TODO: Implement actual API call
     output::print_info(&format!(
         "Listing schemas (subject: {:?}, type: {:?}, limit: {})",
         subject, schema_type, limit
     ));
 
-    // Mock data for now
-    let schemas = vec![
-        SchemaListItem {
-            id: Uuid::new_v4(),
-            subject: "com.example.User".to_string(),
-            version: "1.0.0".to_string(),
-            schema_type: "JSON".to_string(),
-            created_at: "2024-01-15T10:30:00Z".to_string(),
-        },
-    ];
+    let first = page_args.request(limit, subject);
+    let page = page_args.fetch(first, |request| fetch_schema_page(config, request)).await?;
+    let schemas = &page.items;
 
+    // JSON/YAML print the whole page so scripts can read next_cursor
     match format {
         output::OutputFormat::Table => {
             output::print_table(
@@ -176,15 +262,39 @@ async fn list_schemas(
                     s.created_at.clone(),
                 ]).collect(),
             );
+            print_next_cursor(&page);
         }
         _ => {
-            output::print(&schemas, format)?;
+            output::print(&page, format)?;
         }
     }
 
     Ok(())
 }
 
+/// One page of schemas, keyed by subject
+async fn fetch_schema_page(_config: &Config, request: PageRequest) -> schema_registry_core::Result<Page<SchemaListItem>> {
+    // TODO: Implement actual API call
+    // Mock data for now
+    let schemas = vec![
+        SchemaListItem {
+            id: Uuid::new_v4(),
+            subject: "com.example.User".to_string(),
+            version: "1.0.0".to_string(),
+            schema_type: "JSON".to_string(),
+            created_at: "2024-01-15T10:30:00Z".to_string(),
+        },
+    ];
+    pagination::paginate(schemas, &request, |s| s.subject.as_str())
+}
+
+/// Tell the user how to fetch the page after `page`
+fn print_next_cursor<T>(page: &Page<T>) {
+    if let Some(cursor) = &page.next_cursor {
+        output::print_info(&format!("More results available: --cursor {} (or --all)", cursor));
+    }
+}
+
 async fn get_schema(_config: &Config, id: &str, _full: bool, format: output::OutputFormat) -> Result<()> {
     output::print_info(&format!("Getting schema: {}", id));
 
@@ -208,7 +318,7 @@ async fn get_schema(_config: &Config, id: &str, _full: bool, format: output::Out
 }
 
 async fn register_schema(
-    _config: &Config,
+    config: &Config,
     subject: &str,
     content: &str,
     schema_type: &str,
@@ -221,11 +331,7 @@ async fn register_schema(
     ));
 
     // TODO: Implement actual registration
-    let _content = if std::path::Path::new(content).exists() {
-        std::fs::read_to_string(content)?
-    } else {
-        content.to_string()
-    };
+    let _content = read_schema_arg(content, config.max_schema_size)?;
 
     let schema_id = Uuid::new_v4();
     output::print_success(&format!("Schema registered with ID: {}", schema_id));
@@ -234,7 +340,7 @@ async fn register_schema(
 }
 
 async fn validate_schema(
-    _config: &Config,
+    config: &Config,
     content: &str,
     schema_type: &str,
     _format: output::OutputFormat,
@@ -242,31 +348,59 @@ async fn validate_schema(
     output::print_info(&format!("Validating {} schema", schema_type));
 
     // TODO: Implement actual validation
-    let _content = if std::path::Path::new(content).exists() {
-        std::fs::read_to_string(content)?
-    } else {
-        content.to_string()
-    };
+    let _content = read_schema_arg(content, config.max_schema_size)?;
 
     output::print_success("Schema is valid");
     Ok(())
 }
 
 async fn check_compatibility(
-    _config: &Config,
+    config: &Config,
     old: &str,
     new: &str,
     mode: &str,
-    _format: output::OutputFormat,
+    schema_type: Option<&str>,
+    format: output::OutputFormat,
 ) -> Result<()> {
     output::print_info(&format!(
         "Checking compatibility: {} -> {} (mode: {})",
         old, new, mode
     ));
 
-    // TODO: Implement actual compatibility check
-    output::print_success("Schemas are compatible");
-    Ok(())
+    let compatibility_mode: CompatibilityMode =
+        serde_json::from_value(serde_json::Value::String(mode.to_uppercase()))
+            .map_err(|_| CliError::ValidationError(format!("Unsupported compatibility mode: {}", mode)))?;
+    let schema_format = resolve_format(schema_type, new)?;
+    if old == "-" && new == "-" {
+        return Err(CliError::ValidationError("Only one schema can be read from stdin".to_string()));
+    }
+    let old_content = read_schema_file(old, config.max_schema_size)?;
+    let new_content = read_schema_file(new, config.max_schema_size)?;
+
+    let violations = schema_registry_compatibility::check_content(
+        &new_content,
+        &old_content,
+        schema_format,
+        compatibility_mode,
+    )
+    .map_err(|e| CliError::ValidationError(e.to_string()))?;
+
+    match format {
+        output::OutputFormat::Table | output::OutputFormat::Plain => {
+            print!("{}", report::render_text(&violations));
+        }
+        _ => output::print(&violations, format)?,
+    }
+
+    if violations.is_empty() {
+        output::print_success("Schemas are compatible");
+        Ok(())
+    } else {
+        Err(CliError::ValidationError(format!(
+            "{} compatibility violation(s) found",
+            violations.len()
+        )))
+    }
 }
 
 async fn list_versions(_config: &Config, subject: &str, format: output::OutputFormat) -> Result<()> {
@@ -301,9 +435,34 @@ async fn delete_schema(_config: &Config, id: &str, confirm: bool, _format: outpu
     Ok(())
 }
 
-async fn search_schemas(_config: &Config, query: &str, limit: usize, format: output::OutputFormat) -> Result<()> {
+async fn search_schemas(
+    config: &Config,
+    query: &str,
+    limit: usize,
+    page_args: &PageArgs,
+    format: output::OutputFormat,
+) -> Result<()> {
     output::print_info(&format!("Searching schemas: {} (limit: {})", query, limit));
 
+    let first = page_args.request(limit, Some(format!("*{}*", query)));
+    let page = page_args.fetch(first, |request| fetch_search_page(config, query, request)).await?;
+
+    match format {
+        output::OutputFormat::Table | output::OutputFormat::Plain => {
+            output::print(&page.items, format)?;
+            print_next_cursor(&page);
+        }
+        _ => output::print(&page, format)?,
+    }
+    Ok(())
+}
+
+/// One page of search results, keyed by subject
+async fn fetch_search_page(
+    _config: &Config,
+    query: &str,
+    request: PageRequest,
+) -> schema_registry_core::Result<Page<SchemaListItem>> {
     // Mock results
     let results = vec![
         SchemaListItem {
@@ -314,7 +473,217 @@ async fn search_schemas(_config: &Config, query: &str, limit: usize, format: out
             created_at: "2024-01-15T10:30:00Z".to_string(),
         },
     ];
+    pagination::paginate(results, &request, |s| s.subject.as_str())
+}
+
+/// Read a schema from a file, or stdin for `-`, without buffering past `limit` bytes
+fn read_schema_file(path: &str, limit: usize) -> Result<String> {
+    let input = if path == "-" {
+        ingest::read_capped(std::io::stdin().lock(), limit)
+    } else {
+        ingest::read_capped(std::fs::File::open(path)?, limit)
+    };
+    input.and_then(|input| input.into_string()).map_err(ingest_error)
+}
+
+/// Read a schema given as a file path, `-` for stdin, or inline content
+fn read_schema_arg(content: &str, limit: usize) -> Result<String> {
+    if content == "-" || std::path::Path::new(content).exists() {
+        return read_schema_file(content, limit);
+    }
+    if content.len() > limit {
+        return Err(ingest_error(schema_registry_core::Error::SizeExceeded {
+            limit,
+            seen: content.len() as u64,
+        }));
+    }
+    Ok(content.to_string())
+}
+
+fn ingest_error(error: schema_registry_core::Error) -> CliError {
+    match error {
+        schema_registry_core::Error::IoError(e) => CliError::IoError(e),
+        other => CliError::ValidationError(other.to_string()),
+    }
+}
+
+/// Resolve a schema format from an explicit type or the file extension
+fn resolve_format(schema_type: Option<&str>, file: &str) -> Result<SerializationFormat> {
+    let schema_type = match schema_type {
+        Some(t) => t.to_uppercase(),
+        None => match std::path::Path::new(file).extension().and_then(|e| e.to_str()) {
+            Some("avsc") => "AVRO".to_string(),
+            Some("proto") => "PROTOBUF".to_string(),
+            _ => "JSON".to_string(),
+        },
+    };
+
+    match schema_type.as_str() {
+        "JSON" | "JSON_SCHEMA" => Ok(SerializationFormat::JsonSchema),
+        "AVRO" => Ok(SerializationFormat::Avro),
+        "PROTOBUF" | "PROTO" => Ok(SerializationFormat::Protobuf),
+        other => Err(CliError::ValidationError(format!("Unsupported schema type: {}", other))),
+    }
+}
+
+async fn generate_examples(
+    config: &Config,
+    file: &str,
+    schema_type: Option<&str>,
+    seed: u64,
+    count: usize,
+    optional_probability: f64,
+    format: output::OutputFormat,
+) -> Result<()> {
+    if !(0.0..=1.0).contains(&optional_probability) {
+        return Err(CliError::ValidationError(
+            "--optional-probability must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+    if count == 0 {
+        return Err(CliError::ValidationError("--count must be at least 1".to_string()));
+    }
+
+    let content = read_schema_file(file, config.max_schema_size)?;
+    let schema_format = resolve_format(schema_type, file)?;
+    let options = ExampleOptions {
+        seed,
+        optional_field_probability: optional_probability,
+        ..ExampleOptions::default()
+    };
+
+    let generated = examples::generate_many(&content, schema_format, &options, count)
+        .map_err(|e| CliError::ValidationError(e.to_string()))?;
+
+    for (idx, example) in generated.iter().enumerate() {
+        for unsupported in &example.report.unsupported {
+            output::print_warning(&format!(
+                "example {}: {} ({}): {}",
+                idx + 1,
+                unsupported.path,
+                unsupported.construct,
+                unsupported.reason
+            ));
+        }
+    }
+
+    let values: Vec<&serde_json::Value> = generated.iter().map(|e| &e.value).collect();
+    match format {
+        output::OutputFormat::Table | output::OutputFormat::Plain => {
+            for value in values {
+                println!("{}", serde_json::to_string_pretty(value)?);
+            }
+        }
+        _ => {
+            output::print(&values, format)?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn show_annotations(
+    config: &Config,
+    file: &str,
+    schema_type: Option<&str>,
+    prefix: Option<String>,
+    path: Option<String>,
+    format: output::OutputFormat,
+) -> Result<()> {
+    let content = read_schema_file(file, config.max_schema_size)?;
+    let schema_format = resolve_format(schema_type, file)?;
+
+    let mut filter = AnnotationFilter::new();
+    if let Some(prefix) = prefix {
+        filter = filter.with_key_prefix(prefix);
+    }
+    if let Some(path) = path {
+        filter = filter.under_path(path);
+    }
+
+    let extracted = annotations::extract(&content, schema_format)
+        .map_err(|e| CliError::ValidationError(e.to_string()))?
+        .filter(&filter);
+
+    match format {
+        output::OutputFormat::Table => {
+            let rows = extracted
+                .iter()
+                .map(|(path, key, value)| vec![path.to_string(), key.to_string(), value.to_string()])
+                .collect();
+            output::print_table(vec!["Path", "Annotation", "Value"], rows);
+        }
+        output::OutputFormat::Plain => {
+            for (path, key, value) in extracted.iter() {
+                println!("{}\t{}\t{}", path, key, value);
+            }
+        }
+        _ => {
+            output::print(&extracted, format)?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn flatten_schema(
+    file: &str,
+    include_dirs: &[String],
+    out: Option<&str>,
+    schema_type: Option<&str>,
+    max_output_bytes: usize,
+    format: output::OutputFormat,
+) -> Result<()> {
+    let schema_format = resolve_format(schema_type, file)?;
+    let options = FlattenOptions {
+        include_dirs: include_dirs.iter().map(std::path::PathBuf::from).collect(),
+        max_output_bytes,
+    };
+
+    let flattened = flatten::flatten_file(std::path::Path::new(file), schema_format, &options)
+        .map_err(ingest_error)?;
+    let report = &flattened.report;
+    let document = serde_json::to_string_pretty(&flattened.value)?;
+
+    let Some(out) = out else {
+        // stdout carries the schema; the report goes to stderr
+        println!("{}", document);
+        for kept in &report.kept {
+            eprintln!("kept {} at {}: {}", kept.reference, kept.path, kept.reason.as_deref().unwrap_or(""));
+        }
+        eprintln!(
+            "{} inlined, {} kept, growth {:.2}x",
+            report.inlined.len(),
+            report.kept.len(),
+            report.growth_factor()
+        );
+        return Ok(());
+    };
+
+    std::fs::write(out, document)?;
+    match format {
+        output::OutputFormat::Table | output::OutputFormat::Plain => {
+            let rows = report
+                .inlined
+                .iter()
+                .map(|r| vec![r.path.clone(), r.reference.clone(), "inlined".to_string()])
+                .chain(report.kept.iter().map(|r| {
+                    vec![r.path.clone(), r.reference.clone(), r.reason.clone().unwrap_or_default()]
+                }))
+                .collect();
+            output::print_table(vec!["Path", "Reference", "Outcome"], rows);
+            output::print_success(&format!(
+                "Wrote {} ({} inlined, {} kept, {} -> {}, growth {:.2}x)",
+                out,
+                report.inlined.len(),
+                report.kept.len(),
+                output::format_size(report.input_bytes as u64),
+                output::format_size(report.output_bytes as u64),
+                report.growth_factor()
+            ));
+        }
+        _ => output::print(report, format)?,
+    }
 
-    output::print(&results, format)?;
     Ok(())
 }
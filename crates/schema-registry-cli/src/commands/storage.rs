@@ -0,0 +1,425 @@
+//! Storage maintenance commands
+
+use clap::Subcommand;
+use indicatif::{ProgressBar, ProgressStyle};
+use schema_registry_core::embedded::{EmbeddedRegistry, InMemoryStorage};
+use schema_registry_core::registration_log::{read_log, Checkpoint};
+use schema_registry_core::traits::SchemaStorage;
+use schema_registry_core::RegisteredSchema;
+use schema_registry_storage::blob::{recompress, BlobStore, FsBlobStore};
+use schema_registry_storage::compression::{
+    Codec, CompressionError, CompressionPolicy, DEFAULT_MIN_COMPRESS_SIZE,
+};
+use schema_registry_storage::fs_layout::{migrate_layout, read_layout, FsLayout, DEFAULT_PREFIX_LEN};
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    config::Config,
+    error::{CliError, Result},
+    output,
+};
+
+#[derive(Subcommand)]
+pub enum StorageCommand {
+    /// Rewrite stored schema blobs with a different compression codec
+    Recompress {
+        /// Blob store root directory
+        #[arg(long)]
+        dir: String,
+
+        /// Codec for blobs of at least --min-size bytes (none, gzip, zstd)
+        #[arg(long, default_value = "gzip")]
+        codec: String,
+
+        /// Blobs smaller than this many bytes are stored uncompressed
+        #[arg(long, default_value_t = DEFAULT_MIN_COMPRESS_SIZE)]
+        min_size: usize,
+    },
+
+    /// Rebuild schema storage by replaying a registration log onto a backup
+    ///
+    /// The schemas in --into (one JSON file per version, empty for a fresh
+    /// start) are the restored backup. Registrations logged after the
+    /// checkpoint matching that state are replayed and written back.
+    Replay {
+        /// Active segment of the registration log; rotated segments are read too
+        #[arg(long)]
+        log: PathBuf,
+
+        /// Directory holding the restored schemas, updated in place
+        #[arg(long)]
+        into: PathBuf,
+
+        /// Replay from this checkpoint sequence instead of the latest matching one
+        #[arg(long)]
+        checkpoint: Option<u64>,
+    },
+
+    /// Move a flat blob store into the sharded directory layout
+    ///
+    /// Without --apply, only reports how many blobs would move. The migration
+    /// can run against a live store and resumes where it stopped if interrupted.
+    MigrateLayout {
+        /// Blob store root directory
+        #[arg(long)]
+        dir: PathBuf,
+
+        /// Hex digits of the subject hash per directory level (1-4)
+        #[arg(long, default_value_t = DEFAULT_PREFIX_LEN)]
+        prefix_len: usize,
+
+        /// Move the blobs instead of reporting what would move
+        #[arg(long)]
+        apply: bool,
+    },
+}
+
+pub async fn execute(cmd: StorageCommand, _config: &Config, format: output::OutputFormat) -> Result<()> {
+    match cmd {
+        StorageCommand::Recompress { dir, codec, min_size } => recompress_blobs(&dir, &codec, min_size, format).await,
+        StorageCommand::Replay { log, into, checkpoint } => replay_log(&log, &into, checkpoint, format).await,
+        StorageCommand::MigrateLayout { dir, prefix_len, apply } => {
+            migrate_store_layout(&dir, prefix_len, apply, format).await
+        }
+    }
+}
+
+/// Blob store at `dir`, in the layout recorded there
+pub(crate) async fn open_store(dir: &Path) -> Result<FsBlobStore> {
+    FsBlobStore::open(dir)
+        .await
+        .map_err(|e| CliError::Other(format!("Cannot open blob store {}: {}", dir.display(), e)))
+}
+
+fn parse_codec(codec: &str) -> Result<Codec> {
+    let codec: Codec = codec
+        .parse()
+        .map_err(|e: CompressionError| CliError::ValidationError(e.to_string()))?;
+    if !codec.is_available() {
+        return Err(CliError::ValidationError(format!(
+            "codec {} is not available in this build (rebuild with --features zstd)",
+            codec
+        )));
+    }
+    Ok(codec)
+}
+
+async fn recompress_blobs(dir: &str, codec: &str, min_size: usize, format: output::OutputFormat) -> Result<()> {
+    let policy = CompressionPolicy::new(parse_codec(codec)?, min_size);
+    let store = open_store(Path::new(dir)).await?;
+
+    output::print_info(&format!(
+        "Recompressing blobs in {} with {} (min size {})",
+        dir,
+        policy.codec,
+        output::format_size(min_size as u64)
+    ));
+
+    let progress = ProgressBar::new(0);
+    progress.set_style(
+        ProgressStyle::with_template("{bar:40.cyan/blue} {pos}/{len} blobs ({msg})")
+            .unwrap_or_else(|_| ProgressStyle::default_bar()),
+    );
+
+    let report = recompress(&store, &policy, |report| {
+        progress.set_length(report.total as u64);
+        progress.set_position(report.scanned as u64);
+        progress.set_message(format!("{} rewritten", report.rewritten));
+    })
+    .await
+    .map_err(|e| CliError::Other(format!("Recompression failed: {}", e)))?;
+    progress.finish_and_clear();
+
+    match format {
+        output::OutputFormat::Table | output::OutputFormat::Plain => {
+            output::print_table(
+                vec!["Blobs", "Rewritten", "Unchanged", "Size Before", "Size After"],
+                vec![vec![
+                    report.scanned.to_string(),
+                    report.rewritten.to_string(),
+                    report.unchanged().to_string(),
+                    output::format_size(report.bytes_before),
+                    output::format_size(report.bytes_after),
+                ]],
+            );
+            output::print_success(&format!("{} blobs rewritten", report.rewritten));
+        }
+        output::OutputFormat::Json | output::OutputFormat::Yaml => {
+            let summary = serde_json::json!({
+                "codec": policy.codec,
+                "min_size": policy.min_size,
+                "total": report.total,
+                "rewritten": report.rewritten,
+                "unchanged": report.unchanged(),
+                "bytes_before": report.bytes_before,
+                "bytes_after": report.bytes_after,
+            });
+            output::print(&summary, format)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Key of a schema version in the recovered-data directory
+fn schema_key(schema: &RegisteredSchema) -> String {
+    format!("{}/{}/{}.json", schema.namespace, schema.name, schema.version)
+}
+
+/// Load every schema in `store` into `storage`; returns how many were loaded
+pub(crate) async fn load_schemas(store: &FsBlobStore, storage: &InMemoryStorage) -> Result<usize> {
+    if !store.root().exists() {
+        return Ok(0);
+    }
+    let keys = store
+        .keys()
+        .await
+        .map_err(|e| CliError::Other(format!("Cannot list {}: {}", store.root().display(), e)))?;
+    let mut loaded = 0;
+    for key in keys.iter().filter(|key| key.ends_with(".json")) {
+        let bytes = store
+            .read(key)
+            .await
+            .map_err(|e| CliError::Other(format!("Cannot read {}: {}", key, e)))?;
+        let schema: RegisteredSchema = serde_json::from_slice(&bytes)
+            .map_err(|e| CliError::SerializationError(format!("{}: {}", key, e)))?;
+        storage
+            .store(schema)
+            .await
+            .map_err(|e| CliError::Other(format!("Cannot load {}: {}", key, e)))?;
+        loaded += 1;
+    }
+    Ok(loaded)
+}
+
+/// The checkpoint to replay from: the requested one, or the latest matching `fingerprint`
+fn starting_checkpoint<'a>(
+    checkpoints: impl Iterator<Item = &'a Checkpoint>,
+    fingerprint: &str,
+    requested: Option<u64>,
+) -> Result<&'a Checkpoint> {
+    let checkpoint = match requested {
+        Some(sequence) => checkpoints
+            .filter(|checkpoint| checkpoint.sequence == sequence)
+            .last()
+            .ok_or_else(|| CliError::NotFound(format!("no checkpoint with sequence {} in the log", sequence)))?,
+        None => checkpoints
+            .filter(|checkpoint| checkpoint.fingerprint == fingerprint)
+            .last()
+            .ok_or_else(|| {
+                CliError::ValidationError(format!(
+                    "no checkpoint in the log matches the restored state (fingerprint {}); \
+                     restore the backup taken at a checkpoint, or start from an empty directory",
+                    fingerprint
+                ))
+            })?,
+    };
+    if checkpoint.fingerprint != fingerprint {
+        return Err(CliError::ValidationError(format!(
+            "checkpoint {} was taken with {} schemas (fingerprint {}), but the restored state has fingerprint {}",
+            checkpoint.sequence, checkpoint.schema_count, checkpoint.fingerprint, fingerprint
+        )));
+    }
+    Ok(checkpoint)
+}
+
+async fn replay_log(log: &Path, into: &Path, checkpoint: Option<u64>, format: output::OutputFormat) -> Result<()> {
+    let contents = read_log(log).map_err(|e| CliError::Other(format!("Cannot read registration log: {}", e)))?;
+    if contents.truncated_tail {
+        output::print_warning("The log ends with an incomplete frame from an interrupted write; it was ignored");
+    }
+
+    let store = open_store(into).await?;
+    let registry = EmbeddedRegistry::new();
+    let storage = registry.storage();
+    let restored = load_schemas(&store, &storage).await?;
+    let start = starting_checkpoint(contents.checkpoints(), &storage.fingerprint(), checkpoint)?;
+
+    output::print_info(&format!(
+        "Replaying {} from checkpoint {} onto {} restored schemas",
+        log.display(),
+        start.sequence,
+        restored
+    ));
+    let report = registry
+        .replay(contents.after(start.sequence))
+        .await
+        .map_err(|e| CliError::Other(format!("Replay failed: {}", e)))?;
+
+    for schema in storage.schemas() {
+        let json = serde_json::to_vec_pretty(&schema).map_err(|e| CliError::SerializationError(e.to_string()))?;
+        store
+            .write(&schema_key(&schema), &json)
+            .await
+            .map_err(|e| CliError::Other(format!("Cannot write {}: {}", schema_key(&schema), e)))?;
+    }
+
+    match format {
+        output::OutputFormat::Table | output::OutputFormat::Plain => {
+            output::print_table(
+                vec!["Checkpoint", "Segments", "Replayed", "Skipped Rejections", "Checkpoints Verified", "Schemas"],
+                vec![vec![
+                    start.sequence.to_string(),
+                    contents.segments.len().to_string(),
+                    report.replayed.to_string(),
+                    report.skipped_rejected.to_string(),
+                    report.checkpoints_verified.to_string(),
+                    storage.len().to_string(),
+                ]],
+            );
+            output::print_success(&format!("Recovered {} schemas into {}", storage.len(), into.display()));
+        }
+        output::OutputFormat::Json | output::OutputFormat::Yaml => {
+            let summary = serde_json::json!({
+                "checkpoint": start.sequence,
+                "segments": contents.segments.len(),
+                "truncated_tail": contents.truncated_tail,
+                "replayed": report.replayed,
+                "skipped_rejected": report.skipped_rejected,
+                "checkpoints_verified": report.checkpoints_verified,
+                "schemas": storage.len(),
+                "fingerprint": storage.fingerprint(),
+            });
+            output::print(&summary, format)?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn migrate_store_layout(dir: &Path, prefix_len: usize, apply: bool, format: output::OutputFormat) -> Result<()> {
+    let layout = FsLayout::sharded(prefix_len).map_err(|e| CliError::ValidationError(e.to_string()))?;
+    let current = read_layout(dir)
+        .await
+        .map_err(|e| CliError::Other(format!("Cannot read the layout of {}: {}", dir.display(), e)))?;
+    if current != FsLayout::Flat && current != layout {
+        return Err(CliError::ValidationError(format!(
+            "{} already uses {:?}; re-sharding is not supported",
+            dir.display(),
+            current
+        )));
+    }
+
+    if !apply {
+        let pending = FsBlobStore::new(dir)
+            .flat_keys()
+            .await
+            .map_err(|e| CliError::Other(format!("Cannot list {}: {}", dir.display(), e)))?
+            .len();
+        match format {
+            output::OutputFormat::Table | output::OutputFormat::Plain => output::print_info(&format!(
+                "{} blobs in {} would move to the sharded layout (prefix length {}); rerun with --apply",
+                pending,
+                dir.display(),
+                prefix_len
+            )),
+            output::OutputFormat::Json | output::OutputFormat::Yaml => output::print(
+                &serde_json::json!({"applied": false, "layout": layout, "pending": pending}),
+                format,
+            )?,
+        }
+        return Ok(());
+    }
+
+    let progress = ProgressBar::new(0);
+    progress.set_style(
+        ProgressStyle::with_template("{bar:40.cyan/blue} {pos}/{len} blobs ({msg})")
+            .unwrap_or_else(|_| ProgressStyle::default_bar()),
+    );
+    let report = migrate_layout(dir, layout, |report| {
+        progress.set_length(report.total as u64);
+        progress.set_position((report.moved + report.deduplicated) as u64);
+        progress.set_message(format!("{} moved", report.moved));
+    })
+    .await
+    .map_err(|e| CliError::Other(format!("Layout migration failed: {}", e)))?;
+    progress.finish_and_clear();
+
+    match format {
+        output::OutputFormat::Table | output::OutputFormat::Plain => {
+            output::print_table(
+                vec!["Blobs", "Moved", "Already Sharded"],
+                vec![vec![
+                    report.total.to_string(),
+                    report.moved.to_string(),
+                    report.deduplicated.to_string(),
+                ]],
+            );
+            output::print_success(&format!("{} now uses the sharded layout", dir.display()));
+        }
+        output::OutputFormat::Json | output::OutputFormat::Yaml => output::print(
+            &serde_json::json!({
+                "applied": true,
+                "layout": layout,
+                "total": report.total,
+                "moved": report.moved,
+                "deduplicated": report.deduplicated,
+            }),
+            format,
+        )?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_codec() {
+        assert_eq!(parse_codec("gzip").unwrap(), Codec::Gzip);
+        assert_eq!(parse_codec("none").unwrap(), Codec::None);
+        assert!(parse_codec("lz4").is_err());
+        assert_eq!(parse_codec("zstd").is_ok(), Codec::Zstd.is_available());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_layout_dry_run_then_apply() {
+        let dir = tempfile::tempdir().unwrap();
+        let flat = FsBlobStore::new(dir.path());
+        for name in ["user", "order", "invoice"] {
+            flat.write(&format!("com.example/{name}/1.0.0.json"), b"{}").await.unwrap();
+        }
+
+        migrate_store_layout(dir.path(), 2, false, output::OutputFormat::Json).await.unwrap();
+        assert_eq!(read_layout(dir.path()).await.unwrap(), FsLayout::Flat);
+
+        migrate_store_layout(dir.path(), 2, true, output::OutputFormat::Json).await.unwrap();
+        let store = open_store(dir.path()).await.unwrap();
+        assert_eq!(store.layout(), FsLayout::sharded(2).unwrap());
+        assert!(store.flat_keys().await.unwrap().is_empty());
+        assert_eq!(store.keys().await.unwrap().len(), 3);
+
+        assert!(migrate_store_layout(dir.path(), 3, true, output::OutputFormat::Json).await.is_err());
+        assert!(migrate_store_layout(dir.path(), 9, false, output::OutputFormat::Json).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replay_recovers_registrations_after_backup() {
+        use schema_registry_core::registration_log::{RegistrationLog, RegistrationLogConfig};
+        use schema_registry_core::{SchemaInput, SerializationFormat};
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().unwrap();
+        let wal = dir.path().join("reg.wal");
+        let recovered = dir.path().join("recovered-data");
+        let log = Arc::new(RegistrationLog::open(RegistrationLogConfig::new(&wal)).unwrap());
+        let registry = EmbeddedRegistry::builder().with_registration_log(log).build();
+        for name in ["user", "order"] {
+            let input = SchemaInput::new("com.example", name, SerializationFormat::JsonSchema, r#"{"type":"object"}"#);
+            registry.register(input).await.unwrap();
+        }
+
+        replay_log(&wal, &recovered, None, output::OutputFormat::Json).await.unwrap();
+        let store = FsBlobStore::new(&recovered);
+        assert_eq!(store.keys().await.unwrap().len(), 2);
+        let reloaded = InMemoryStorage::new();
+        load_schemas(&store, &reloaded).await.unwrap();
+        assert_eq!(reloaded.fingerprint(), registry.storage().fingerprint());
+
+        // Running it again finds the recovered state matches no checkpoint
+        let err = replay_log(&wal, &recovered, None, output::OutputFormat::Json).await.unwrap_err();
+        assert!(err.to_string().contains("no checkpoint"));
+    }
+}
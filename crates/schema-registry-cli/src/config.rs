@@ -5,6 +5,7 @@ use std::fs;
 use std::path::PathBuf;
 
 use crate::error::{CliError, Result};
+use schema_registry_core::ingest::DEFAULT_MAX_SCHEMA_SIZE;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -15,6 +16,34 @@ pub struct Config {
     pub timeout_seconds: u64,
     #[serde(default)]
     pub retry_attempts: u32,
+    #[serde(default)]
+    pub config_diff: ConfigDiffSettings,
+    /// Largest schema input (file, stdin or inline) read before aborting, in bytes
+    #[serde(default = "default_max_schema_size")]
+    pub max_schema_size: usize,
+}
+
+fn default_max_schema_size() -> usize {
+    DEFAULT_MAX_SCHEMA_SIZE
+}
+
+/// Settings for `config diff-env`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigDiffSettings {
+    /// Path prefixes whose differences block promotion (e.g. `config.security`)
+    #[serde(default)]
+    pub promotion_critical: Vec<String>,
+    /// Differences to suppress as environment-expected
+    #[serde(default)]
+    pub ignore: Vec<IgnoreRule>,
+}
+
+/// Suppresses differences under `path`, optionally only when both values match `value_pattern`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IgnoreRule {
+    pub path: String,
+    #[serde(default)]
+    pub value_pattern: Option<String>,
 }
 
 impl Default for Config {
@@ -24,6 +53,8 @@ impl Default for Config {
             api_key: None,
             timeout_seconds: 30,
             retry_attempts: 3,
+            config_diff: ConfigDiffSettings::default(),
+            max_schema_size: DEFAULT_MAX_SCHEMA_SIZE,
         }
     }
 }
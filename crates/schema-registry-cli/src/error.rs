@@ -1,6 +1,9 @@
 //! Error handling for the CLI
 
 use colored::Colorize;
+use schema_registry_core::error_catalog::{
+    ErrorCatalog, ErrorCategory::*, ErrorEntry, CORE_ERRORS,
+};
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, CliError>;
@@ -29,6 +32,77 @@ pub enum CliError {
     Other(String),
 }
 
+schema_registry_core::error_codes!(CliError {
+    ConfigError => "CLI_CONFIG_ERROR",
+    ApiError => "CLI_API_ERROR",
+    ValidationError => "CLI_VALIDATION_ERROR",
+    NotFound => "CLI_NOT_FOUND",
+    IoError => "CLI_IO_ERROR",
+    SerializationError => "CLI_SERIALIZATION_ERROR",
+    Other => "CLI_ERROR",
+});
+
+/// Entries for [`CliError`]
+pub const CLI_ERRORS: &[ErrorEntry] = &[
+    ErrorEntry {
+        code: "CLI_CONFIG_ERROR",
+        category: Configuration,
+        retryable: false,
+        description: "The CLI configuration file is missing or invalid.",
+        remediation: "Run 'schema-cli init --url <URL>' or fix the reported setting.",
+    },
+    ErrorEntry {
+        code: "CLI_API_ERROR",
+        category: Client,
+        retryable: true,
+        description: "The registry API request failed or could not be sent.",
+        remediation: "Check that the registry URL is correct and the server is running.",
+    },
+    ErrorEntry {
+        code: "CLI_VALIDATION_ERROR",
+        category: Validation,
+        retryable: false,
+        description: "A command argument or input file was invalid.",
+        remediation: "Fix the argument as described; --help lists accepted values.",
+    },
+    ErrorEntry {
+        code: "CLI_NOT_FOUND",
+        category: NotFound,
+        retryable: false,
+        description: "The requested schema, file or resource does not exist.",
+        remediation: "Check the name, id or path given on the command line.",
+    },
+    ErrorEntry {
+        code: "CLI_IO_ERROR",
+        category: Client,
+        retryable: false,
+        description: "A local file could not be read or written.",
+        remediation: "Check the path and its permissions.",
+    },
+    ErrorEntry {
+        code: "CLI_SERIALIZATION_ERROR",
+        category: Client,
+        retryable: false,
+        description: "Input or output could not be converted to or from JSON or YAML.",
+        remediation: "Check the syntax of the input file.",
+    },
+    ErrorEntry {
+        code: "CLI_ERROR",
+        category: Client,
+        retryable: false,
+        description: "A command failed without a more specific code.",
+        remediation: "See the message; rerun with -v for details.",
+    },
+];
+
+/// Every error code the CLI and the registry it talks to can report
+pub fn catalog() -> ErrorCatalog {
+    ErrorCatalog::new()
+        .register(CORE_ERRORS)
+        .register(llm_schema_api::error_codes::API_ERRORS)
+        .register(CLI_ERRORS)
+}
+
 impl From<serde_json::Error> for CliError {
     fn from(e: serde_json::Error) -> Self {
         CliError::SerializationError(e.to_string())
@@ -41,6 +115,18 @@ impl From<serde_yaml::Error> for CliError {
     }
 }
 
+impl From<schema_registry_core::Error> for CliError {
+    fn from(e: schema_registry_core::Error) -> Self {
+        if e.is_not_found() {
+            CliError::NotFound(e.to_string())
+        } else if e.is_validation_error() {
+            CliError::ValidationError(e.to_string())
+        } else {
+            CliError::Other(e.to_string())
+        }
+    }
+}
+
 impl From<anyhow::Error> for CliError {
     fn from(e: anyhow::Error) -> Self {
         CliError::Other(e.to_string())
@@ -48,6 +134,11 @@ impl From<anyhow::Error> for CliError {
 }
 
 pub fn print_error(error: &CliError) {
+    debug_assert!(
+        catalog().contains(error.code()),
+        "CLI error code {} is not catalogued",
+        error.code()
+    );
     eprintln!("{} {}", "Error:".red().bold(), error);
 
     // Print additional context for certain error types
@@ -63,3 +154,19 @@ pub fn print_error(error: &CliError) {
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_covers_every_error_code() {
+        let catalog = catalog();
+        for code in CliError::CODES
+            .iter()
+            .chain(schema_registry_core::Error::CODES)
+        {
+            assert!(catalog.contains(code), "{} has no catalog entry", code);
+        }
+    }
+}
@@ -7,12 +7,18 @@ mod commands;
 mod config;
 mod error;
 mod output;
+mod scenarios;
 
 use clap::{Parser, Subcommand};
-use commands::{admin, analytics, benchmark, lineage, migration, schema};
+use commands::{admin, analytics, benchmark, compat, configuration, doctor, errors, governance, lineage, metrics, migration, policy, scenario, schema, storage};
 use error::Result;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
+#[cfg(feature = "mem-metrics")]
+#[global_allocator]
+static ALLOCATOR: schema_registry_benchmarks::memory::CountingAllocator =
+    schema_registry_benchmarks::memory::CountingAllocator;
+
 #[derive(Parser)]
 #[command(name = "schema-cli")]
 #[command(version = env!("CARGO_PKG_VERSION"))]
@@ -70,6 +76,34 @@ enum Commands {
     #[command(subcommand)]
     Benchmark(benchmark::BenchmarkCommand),
 
+    /// Metric catalog commands
+    #[command(subcommand)]
+    Metrics(metrics::MetricsCommand),
+
+    /// Storage maintenance commands
+    #[command(subcommand)]
+    Storage(storage::StorageCommand),
+
+    /// Error catalog commands
+    #[command(subcommand)]
+    Errors(errors::ErrorsCommand),
+
+    /// Schema policy commands
+    #[command(subcommand)]
+    Policy(policy::PolicyCommand),
+
+    /// Governance reporting commands
+    #[command(subcommand)]
+    Governance(governance::GovernanceCommand),
+
+    /// Scripted end-to-end scenario commands
+    #[command(subcommand)]
+    Scenario(scenario::ScenarioCommand),
+
+    /// Compatibility matrix commands
+    #[command(subcommand)]
+    Compat(compat::CompatCommand),
+
     /// Initialize configuration
     Init {
         /// Registry URL
@@ -81,11 +115,17 @@ enum Commands {
         force: bool,
     },
 
-    /// Show configuration
-    Config,
+    /// Show configuration, or run a configuration subcommand
+    Config {
+        #[command(subcommand)]
+        command: Option<configuration::ConfigCommand>,
+    },
 
     /// Validate configuration
     Validate,
+
+    /// Diagnose common setup problems
+    Doctor(doctor::DoctorArgs),
 }
 
 #[tokio::main]
@@ -104,6 +144,12 @@ async fn main() {
 }
 
 async fn run(cli: Cli) -> Result<()> {
+    // Doctor reports a broken config file instead of failing to load it
+    let command = match cli.command {
+        Commands::Doctor(args) => return doctor::execute(args, cli.config.as_deref(), cli.output).await,
+        command => command,
+    };
+
     // Load configuration
     let config = config::load_config(cli.config.as_deref())?;
 
@@ -113,13 +159,20 @@ async fn run(cli: Cli) -> Result<()> {
         config.registry_url = url;
     }
 
-    match cli.command {
+    match command {
         Commands::Schema(cmd) => schema::execute(cmd, &config, cli.output).await,
         Commands::Lineage(cmd) => lineage::execute(cmd, &config, cli.output).await,
         Commands::Analytics(cmd) => analytics::execute(cmd, &config, cli.output).await,
         Commands::Migration(cmd) => migration::execute(cmd, &config, cli.output).await,
         Commands::Admin(cmd) => admin::execute(cmd, &config, cli.output).await,
         Commands::Benchmark(cmd) => benchmark::execute(cmd, &config, cli.output).await,
+        Commands::Metrics(cmd) => metrics::execute(cmd, &config, cli.output).await,
+        Commands::Storage(cmd) => storage::execute(cmd, &config, cli.output).await,
+        Commands::Errors(cmd) => errors::execute(cmd, &config, cli.output).await,
+        Commands::Policy(cmd) => policy::execute(cmd, &config, cli.output).await,
+        Commands::Governance(cmd) => governance::execute(cmd, &config, cli.output).await,
+        Commands::Scenario(cmd) => scenario::execute(cmd, &config, cli.output).await,
+        Commands::Compat(cmd) => compat::execute(cmd, &config, cli.output).await,
         Commands::Init { url, force } => {
             config::init_config(&url, force)?;
             println!("✓ Configuration initialized successfully");
@@ -127,7 +180,8 @@ async fn run(cli: Cli) -> Result<()> {
             println!("  Config file: {}", config::config_path()?.display());
             Ok(())
         }
-        Commands::Config => {
+        Commands::Config { command: Some(cmd) } => configuration::execute(cmd, &config, cli.output).await,
+        Commands::Config { command: None } => {
             println!("{}", serde_yaml::to_string(&config)?);
             Ok(())
         }
@@ -136,6 +190,7 @@ async fn run(cli: Cli) -> Result<()> {
             println!("  Registry URL: {}", config.registry_url);
             Ok(())
         }
+        Commands::Doctor(_) => unreachable!("handled before the configuration is loaded"),
     }
 }
 
@@ -0,0 +1,350 @@
+//! Registries a scenario can run against
+
+use schema_registry_compatibility::CompatibilityCheckerImpl;
+use schema_registry_core::config_manager_adapter::SchemaPolicies;
+use schema_registry_core::embedded::EmbeddedRegistry;
+use schema_registry_core::pagination::{self, PageRequest};
+use schema_registry_core::versioning::SemanticVersion;
+use schema_registry_core::{CompatibilityMode, SchemaInput, SerializationFormat};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Structured error reported by a failed step
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StepError {
+    /// Error catalog code (e.g. `SCHEMA_VALIDATION_FAILED`)
+    pub code: String,
+    pub message: String,
+    /// Extra fields returned by the backend, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+impl StepError {
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+            details: None,
+        }
+    }
+}
+
+impl From<schema_registry_core::Error> for StepError {
+    fn from(err: schema_registry_core::Error) -> Self {
+        StepError::new(err.code(), err.to_string())
+    }
+}
+
+/// A schema to register
+#[derive(Debug, Clone)]
+pub struct Registration {
+    pub subject: String,
+    pub format: SerializationFormat,
+    pub content: String,
+    pub version: Option<String>,
+    pub compatibility: Option<CompatibilityMode>,
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Subject and version counts
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Stats {
+    pub subjects: usize,
+    pub versions: BTreeMap<String, usize>,
+}
+
+/// Registry the scenario runs against
+pub enum Backend {
+    /// In-process [`EmbeddedRegistry`] with the real compatibility checker
+    Embedded(EmbeddedBackend),
+    /// A running registry reached over HTTP
+    Http(HttpBackend),
+}
+
+impl Backend {
+    pub fn embedded() -> Self {
+        Backend::Embedded(EmbeddedBackend::new())
+    }
+
+    /// HTTP backend for `base_url` (plain `http://` only)
+    pub fn http(base_url: &str) -> Result<Self, StepError> {
+        HttpBackend::new(base_url).map(Backend::Http)
+    }
+
+    /// Name shown in reports
+    pub fn name(&self) -> &'static str {
+        match self {
+            Backend::Embedded(_) => "embedded",
+            Backend::Http(_) => "http",
+        }
+    }
+
+    /// Register a schema, returning its version
+    pub async fn register(&mut self, registration: Registration) -> Result<String, StepError> {
+        match self {
+            Backend::Embedded(backend) => backend.register(registration).await,
+            Backend::Http(backend) => backend.register(registration).await,
+        }
+    }
+
+    pub async fn set_compat(&mut self, subject: &str, mode: CompatibilityMode) -> Result<(), StepError> {
+        match self {
+            Backend::Embedded(backend) => {
+                backend.compat.insert(subject.to_string(), mode);
+                Ok(())
+            }
+            Backend::Http(backend) => backend.set_compat(subject, mode).await,
+        }
+    }
+
+    pub async fn refresh_policies(&mut self, policies: SchemaPolicies) -> Result<(), StepError> {
+        match self {
+            Backend::Embedded(backend) => {
+                backend.registry.update_policies(policies);
+                Ok(())
+            }
+            Backend::Http(backend) => backend.refresh_policies(&policies).await,
+        }
+    }
+
+    /// Version counts for every subject
+    pub async fn stats(&mut self) -> Result<Stats, StepError> {
+        match self {
+            Backend::Embedded(backend) => backend.stats().await,
+            Backend::Http(backend) => backend.stats().await,
+        }
+    }
+}
+
+/// Split a `namespace.name` subject
+fn split_subject(subject: &str) -> Result<(&str, &str), StepError> {
+    match subject.rsplit_once('.') {
+        Some((namespace, name)) if !namespace.is_empty() && !name.is_empty() => Ok((namespace, name)),
+        _ => Err(StepError::new(
+            "SCENARIO_INVALID_STEP",
+            format!("subject '{}' must have the form namespace.name", subject),
+        )),
+    }
+}
+
+pub struct EmbeddedBackend {
+    registry: EmbeddedRegistry,
+    /// Modes set with `set-compat`, by subject
+    compat: HashMap<String, CompatibilityMode>,
+}
+
+impl EmbeddedBackend {
+    fn new() -> Self {
+        Self {
+            registry: EmbeddedRegistry::builder()
+                .with_compatibility_checker(Arc::new(CompatibilityCheckerImpl::new()))
+                .build(),
+            compat: HashMap::new(),
+        }
+    }
+
+    async fn register(&self, registration: Registration) -> Result<String, StepError> {
+        let (namespace, name) = split_subject(&registration.subject)?;
+        let mut input = SchemaInput::new(namespace, name, registration.format, registration.content);
+        if let Some(mode) = registration.compatibility.or_else(|| self.compat.get(&registration.subject).copied()) {
+            input.compatibility_mode = mode;
+        }
+        if let Some(version) = &registration.version {
+            input.version = Some(
+                version
+                    .parse::<SemanticVersion>()
+                    .map_err(|e| StepError::new("SCENARIO_INVALID_STEP", e.to_string()))?,
+            );
+        }
+        input.metadata = registration
+            .metadata
+            .into_iter()
+            .map(|(key, value)| (key, Value::String(value)))
+            .collect();
+
+        let schema = self.registry.register(input).await?;
+        Ok(schema.version.to_string())
+    }
+
+    async fn stats(&self) -> Result<Stats, StepError> {
+        let subjects =
+            pagination::collect_all(PageRequest::new(pagination::MAX_PAGE_LIMIT), |page| async move {
+                self.registry.subjects(&page).await
+            })
+            .await?;
+        let mut versions = BTreeMap::new();
+        for subject in &subjects {
+            let (namespace, name) = split_subject(subject)?;
+            versions.insert(subject.clone(), self.registry.versions(namespace, name).await?.len());
+        }
+        Ok(Stats {
+            subjects: subjects.len(),
+            versions,
+        })
+    }
+}
+
+/// Minimal JSON-over-HTTP/1.1 client for a running registry
+///
+/// Error responses are expected in the API's `{error_code, message, details}`
+/// shape; anything else is reported with an `HTTP_<status>` code.
+pub struct HttpBackend {
+    host: String,
+    port: u16,
+    base_path: String,
+}
+
+impl HttpBackend {
+    fn new(base_url: &str) -> Result<Self, StepError> {
+        let url = url::Url::parse(base_url).map_err(|e| StepError::new("SCENARIO_INVALID_BACKEND", e.to_string()))?;
+        if url.scheme() != "http" {
+            return Err(StepError::new(
+                "SCENARIO_INVALID_BACKEND",
+                format!("only http:// URLs are supported, got {}", base_url),
+            ));
+        }
+        Ok(Self {
+            host: url.host_str().unwrap_or("localhost").to_string(),
+            port: url.port_or_known_default().unwrap_or(80),
+            base_path: url.path().trim_end_matches('/').to_string(),
+        })
+    }
+
+    async fn register(&self, registration: Registration) -> Result<String, StepError> {
+        let schema = serde_json::from_str::<Value>(&registration.content).unwrap_or(Value::String(registration.content));
+        let body = serde_json::json!({
+            "subject": registration.subject,
+            "schema": schema,
+            "schema_type": match registration.format {
+                SerializationFormat::JsonSchema => "json",
+                SerializationFormat::Avro => "avro",
+                SerializationFormat::Protobuf => "protobuf",
+            },
+            "metadata": registration.metadata,
+            "compatibility_level": registration.compatibility,
+            "version": registration.version,
+        });
+        let response = self.request("POST", "/api/v1/schemas", Some(&body)).await?;
+        Ok(response["version"].as_str().unwrap_or_default().to_string())
+    }
+
+    async fn set_compat(&self, subject: &str, mode: CompatibilityMode) -> Result<(), StepError> {
+        let body = serde_json::json!({ "compatibility_level": mode });
+        let path = format!("/api/v1/subjects/{}/compatibility", subject);
+        self.request("PUT", &path, Some(&body)).await.map(|_| ())
+    }
+
+    async fn refresh_policies(&self, policies: &SchemaPolicies) -> Result<(), StepError> {
+        let body = serde_json::to_value(policies).map_err(|e| StepError::new("SERIALIZATION_ERROR", e.to_string()))?;
+        self.request("PUT", "/api/v1/policies", Some(&body)).await.map(|_| ())
+    }
+
+    async fn stats(&self) -> Result<Stats, StepError> {
+        let subjects: Vec<String> = serde_json::from_value(self.request("GET", "/api/v1/subjects", None).await?)
+            .map_err(|e| StepError::new("SERIALIZATION_ERROR", e.to_string()))?;
+        let mut versions = BTreeMap::new();
+        for subject in &subjects {
+            let path = format!("/api/v1/subjects/{}/versions", subject);
+            let count = self.request("GET", &path, None).await?.as_array().map_or(0, Vec::len);
+            versions.insert(subject.clone(), count);
+        }
+        Ok(Stats {
+            subjects: subjects.len(),
+            versions,
+        })
+    }
+
+    async fn request(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Value, StepError> {
+        let io = |e: std::io::Error| StepError::new("CLI_API_ERROR", format!("{}:{}: {}", self.host, self.port, e));
+        let body = body.map(Value::to_string).unwrap_or_default();
+        let request = format!(
+            "{method} {base}{path} HTTP/1.1\r\nHost: {host}\r\nAccept: application/json\r\nContent-Type: application/json\r\nContent-Length: {len}\r\nConnection: close\r\n\r\n{body}",
+            base = self.base_path,
+            host = self.host,
+            len = body.len(),
+        );
+
+        let mut stream = TcpStream::connect((self.host.as_str(), self.port)).await.map_err(io)?;
+        stream.write_all(request.as_bytes()).await.map_err(io)?;
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw).await.map_err(io)?;
+
+        let (status, payload) = parse_response(&raw)?;
+        let value = if payload.trim().is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(&payload).unwrap_or(Value::String(payload))
+        };
+        if (200..300).contains(&status) {
+            return Ok(value);
+        }
+        Err(match value.get("error_code").and_then(Value::as_str) {
+            Some(code) => StepError {
+                code: code.to_string(),
+                message: value["message"].as_str().unwrap_or_default().to_string(),
+                details: value.get("details").filter(|details| !details.is_null()).cloned(),
+            },
+            None => StepError {
+                code: format!("HTTP_{}", status),
+                message: format!("{} {} returned {}", method, path, status),
+                details: Some(value),
+            },
+        })
+    }
+}
+
+/// Status code and body of a raw HTTP/1.1 response, decoding chunked bodies
+fn parse_response(raw: &[u8]) -> Result<(u16, String), StepError> {
+    let malformed = || StepError::new("CLI_API_ERROR", "malformed HTTP response");
+    let text = String::from_utf8_lossy(raw);
+    let (head, body) = text.split_once("\r\n\r\n").ok_or_else(malformed)?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(malformed)?;
+    let chunked = head
+        .lines()
+        .any(|line| line.to_ascii_lowercase().starts_with("transfer-encoding:") && line.to_ascii_lowercase().contains("chunked"));
+    if !chunked {
+        return Ok((status, body.to_string()));
+    }
+
+    let mut decoded = String::new();
+    let mut rest = body;
+    loop {
+        let (size, after) = rest.split_once("\r\n").ok_or_else(malformed)?;
+        let size = usize::from_str_radix(size.split(';').next().unwrap_or("").trim(), 16).map_err(|_| malformed())?;
+        if size == 0 {
+            break;
+        }
+        decoded.push_str(after.get(..size).ok_or_else(malformed)?);
+        rest = after.get(size + 2..).ok_or_else(malformed)?;
+    }
+    Ok((status, decoded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response_plain_and_chunked() {
+        let plain = b"HTTP/1.1 409 Conflict\r\nContent-Length: 2\r\n\r\n{}";
+        assert_eq!(parse_response(plain).unwrap(), (409, "{}".to_string()));
+
+        let chunked = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n[\"a\"\r\n1\r\n]\r\n0\r\n\r\n";
+        assert_eq!(parse_response(chunked).unwrap(), (200, "[\"a\"]".to_string()));
+    }
+
+    #[test]
+    fn test_split_subject() {
+        assert_eq!(split_subject("com.example.user").unwrap(), ("com.example", "user"));
+        assert_eq!(split_subject("user").unwrap_err().code, "SCENARIO_INVALID_STEP");
+    }
+}
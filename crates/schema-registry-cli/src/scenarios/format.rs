@@ -0,0 +1,261 @@
+//! Scenario file format
+//!
+//! ```yaml
+//! version: 1
+//! name: payments evolution
+//! variables:
+//!   subject: com.example.payments
+//! steps:
+//!   - name: register v1
+//!     register:
+//!       subject: ${subject}
+//!       file: schemas/payments-v1.json
+//!   - refresh-policies:
+//!       file: policies/require-owner.yaml
+//!   - name: owner is now required
+//!     register:
+//!       subject: ${subject}
+//!       file: schemas/payments-v2.json
+//!     expect-error: SCHEMA_VALIDATION_FAILED
+//!   - assert-stats:
+//!       subjects: 1
+//!       versions:
+//!         ${subject}: 1
+//! ```
+//!
+//! `version` is required. Files with a newer version than
+//! [`CURRENT_SCENARIO_VERSION`] are rejected rather than half-understood;
+//! within a version, unknown step fields are ignored so older runners can
+//! read files that only add optional fields.
+
+use schema_registry_core::{CompatibilityMode, SerializationFormat};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::ScenarioError;
+
+/// Newest scenario format this runner understands
+pub const CURRENT_SCENARIO_VERSION: u32 = 1;
+
+/// A scripted sequence of registry operations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    /// Format version; see [`CURRENT_SCENARIO_VERSION`]
+    pub version: u32,
+    pub name: String,
+    /// Values substituted for `${name}` in step strings
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+    pub steps: Vec<Step>,
+}
+
+/// One step and how its outcome is judged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Step {
+    /// Label in reports; defaults to the action name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Error code the step must fail with
+    #[serde(default)]
+    pub expect_error: Option<String>,
+    /// Keep running later steps if this one fails
+    #[serde(default)]
+    pub continue_on_failure: bool,
+    #[serde(flatten)]
+    pub action: Action,
+}
+
+impl Step {
+    /// Label used in reports
+    pub fn label(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.action.kind().to_string())
+    }
+}
+
+/// What a step does
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    /// Register a schema under a `namespace.name` subject
+    Register(RegisterStep),
+    /// Set the compatibility mode used by later registrations of a subject
+    SetCompat(SetCompatStep),
+    /// Replace the schema policies (including waivers) from a file
+    RefreshPolicies(RefreshPoliciesStep),
+    /// Check subject and version counts
+    AssertStats(AssertStatsStep),
+}
+
+impl Action {
+    /// Name of the action as written in scenario files
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Action::Register(_) => "register",
+            Action::SetCompat(_) => "set-compat",
+            Action::RefreshPolicies(_) => "refresh-policies",
+            Action::AssertStats(_) => "assert-stats",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterStep {
+    pub subject: String,
+    #[serde(default = "default_format")]
+    pub format: SerializationFormat,
+    /// Inline schema content
+    #[serde(default)]
+    pub schema: Option<String>,
+    /// Schema file, relative to the scenario file
+    #[serde(default)]
+    pub file: Option<String>,
+    /// Explicit version (e.g. `2.0.0`); otherwise the next minor version
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Overrides the subject's mode set with `set-compat`
+    #[serde(default)]
+    pub compatibility: Option<CompatibilityMode>,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetCompatStep {
+    pub subject: String,
+    pub mode: CompatibilityMode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshPoliciesStep {
+    /// YAML or JSON `SchemaPolicies`, relative to the scenario file
+    pub file: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssertStatsStep {
+    /// Expected number of subjects
+    #[serde(default)]
+    pub subjects: Option<usize>,
+    /// Expected number of versions per subject
+    #[serde(default)]
+    pub versions: BTreeMap<String, usize>,
+}
+
+fn default_format() -> SerializationFormat {
+    SerializationFormat::JsonSchema
+}
+
+impl Scenario {
+    /// Parse a scenario, rejecting format versions this runner doesn't know
+    pub fn parse(yaml: &str) -> Result<Self, ScenarioError> {
+        #[derive(Deserialize)]
+        struct Versioned {
+            version: Option<u32>,
+        }
+
+        let versioned: Versioned = serde_yaml::from_str(yaml).map_err(|e| ScenarioError::Parse(e.to_string()))?;
+        match versioned.version {
+            None => return Err(ScenarioError::Parse("missing 'version'".to_string())),
+            Some(version) if version == 0 || version > CURRENT_SCENARIO_VERSION => {
+                return Err(ScenarioError::UnsupportedVersion {
+                    found: version,
+                    supported: CURRENT_SCENARIO_VERSION,
+                })
+            }
+            Some(_) => {}
+        }
+        serde_yaml::from_str(yaml).map_err(|e| ScenarioError::Parse(e.to_string()))
+    }
+}
+
+/// Substitutes `${name}` references with scenario variables
+#[derive(Debug, Clone, Default)]
+pub struct Variables(BTreeMap<String, String>);
+
+impl Variables {
+    pub fn new(values: BTreeMap<String, String>) -> Self {
+        Self(values)
+    }
+
+    /// Replace every `${name}` in `text`; unknown names are an error
+    pub fn expand(&self, text: &str) -> Result<String, ScenarioError> {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("${") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let end = after
+                .find('}')
+                .ok_or_else(|| ScenarioError::Parse(format!("unterminated variable in '{}'", text)))?;
+            let name = &after[..end];
+            let value = self
+                .0
+                .get(name)
+                .ok_or_else(|| ScenarioError::UndefinedVariable(name.to_string()))?;
+            out.push_str(value);
+            rest = &after[end + 1..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENARIO: &str = r#"
+version: 1
+name: evolve
+variables:
+  subject: com.example.payments
+steps:
+  - register:
+      subject: ${subject}
+      schema: '{"type": "object"}'
+  - name: must fail
+    set-compat:
+      subject: ${subject}
+      mode: FULL
+    expect-error: CONFIG_ERROR
+    continue-on-failure: true
+    added-in-a-later-minor: ignored
+  - assert-stats:
+      versions:
+        ${subject}: 1
+"#;
+
+    #[test]
+    fn test_parse_steps() {
+        let scenario = Scenario::parse(SCENARIO).unwrap();
+        assert_eq!(scenario.steps.len(), 3);
+        assert_eq!(scenario.steps[0].label(), "register");
+        assert_eq!(scenario.steps[1].label(), "must fail");
+        assert_eq!(scenario.steps[1].expect_error.as_deref(), Some("CONFIG_ERROR"));
+        assert!(scenario.steps[1].continue_on_failure);
+        match &scenario.steps[1].action {
+            Action::SetCompat(step) => assert_eq!(step.mode, CompatibilityMode::Full),
+            other => panic!("unexpected action {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_newer_or_missing_versions() {
+        let newer = SCENARIO.replace("version: 1", "version: 2");
+        assert!(matches!(
+            Scenario::parse(&newer),
+            Err(ScenarioError::UnsupportedVersion { found: 2, supported: 1 })
+        ));
+        let missing = SCENARIO.replace("version: 1\n", "");
+        assert!(matches!(Scenario::parse(&missing), Err(ScenarioError::Parse(_))));
+    }
+
+    #[test]
+    fn test_variables_expand() {
+        let vars = Variables::new(BTreeMap::from([("subject".to_string(), "com.example.a".to_string())]));
+        assert_eq!(vars.expand("${subject}-v1").unwrap(), "com.example.a-v1");
+        assert_eq!(vars.expand("plain").unwrap(), "plain");
+        assert!(matches!(vars.expand("${missing}"), Err(ScenarioError::UndefinedVariable(name)) if name == "missing"));
+    }
+}
@@ -0,0 +1,323 @@
+//! Scripted end-to-end scenarios
+//!
+//! A scenario file (see [`format`]) lists registry operations to run in
+//! order against a [`Backend`], each with an optional expected error code.
+//! [`run_scenario`] records every step in a [`ScenarioReport`], which renders
+//! as JUnit XML for CI and as markdown for people.
+
+pub mod backend;
+pub mod format;
+pub mod report;
+
+use schema_registry_core::config_manager_adapter::SchemaPolicies;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Instant;
+use thiserror::Error;
+
+pub use backend::{Backend, Registration, StepError};
+pub use format::{Action, Scenario, Variables};
+pub use report::{ScenarioReport, StepReport, StepStatus};
+
+/// Error code reported when a step cannot be prepared (bad variable, missing file)
+const INVALID_STEP: &str = "SCENARIO_INVALID_STEP";
+
+/// Error code reported when `assert-stats` sees different counts
+const ASSERTION_FAILED: &str = "SCENARIO_ASSERTION_FAILED";
+
+/// Error code reported when a step succeeds but was expected to fail
+const UNEXPECTED_SUCCESS: &str = "SCENARIO_UNEXPECTED_SUCCESS";
+
+/// Error loading or preparing a scenario
+#[derive(Debug, Error)]
+pub enum ScenarioError {
+    #[error("Invalid scenario: {0}")]
+    Parse(String),
+
+    #[error("Scenario format version {found} is not supported (this runner supports up to {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error("Undefined scenario variable: {0}")]
+    UndefinedVariable(String),
+
+    #[error("Cannot read {0}")]
+    Io(String),
+}
+
+impl From<ScenarioError> for StepError {
+    fn from(err: ScenarioError) -> Self {
+        StepError::new(INVALID_STEP, err.to_string())
+    }
+}
+
+/// Run every step of `scenario` against `backend`
+///
+/// `overrides` replace scenario variables of the same name. Files are read
+/// relative to `base_dir`. After a failed step the remaining steps are
+/// skipped unless the failed step sets `continue-on-failure`.
+pub async fn run_scenario(
+    scenario: &Scenario,
+    base_dir: &Path,
+    overrides: &BTreeMap<String, String>,
+    backend: &mut Backend,
+) -> ScenarioReport {
+    let mut values = scenario.variables.clone();
+    values.extend(overrides.clone());
+    let vars = Variables::new(values);
+
+    let started = Instant::now();
+    let mut steps = Vec::with_capacity(scenario.steps.len());
+    let mut stopped = false;
+
+    for (index, step) in scenario.steps.iter().enumerate() {
+        let mut report = StepReport {
+            index,
+            name: step.label(),
+            action: step.action.kind(),
+            status: StepStatus::Skipped,
+            duration_ms: 0.0,
+            detail: None,
+            error: None,
+        };
+        if stopped {
+            steps.push(report);
+            continue;
+        }
+
+        let step_started = Instant::now();
+        let outcome = run_step(&step.action, &vars, base_dir, backend).await;
+        report.duration_ms = step_started.elapsed().as_secs_f64() * 1000.0;
+
+        match (outcome, step.expect_error.as_deref()) {
+            (Ok(detail), None) => {
+                report.status = StepStatus::Passed;
+                report.detail = detail;
+            }
+            (Ok(_), Some(expected)) => {
+                report.status = StepStatus::Failed;
+                report.error = Some(StepError::new(
+                    UNEXPECTED_SUCCESS,
+                    format!("expected error {} but the step succeeded", expected),
+                ));
+            }
+            (Err(error), Some(expected)) if error.code == expected => {
+                report.status = StepStatus::Passed;
+                report.detail = Some(format!("failed as expected with {}", error.code));
+            }
+            (Err(error), expected) => {
+                report.status = StepStatus::Failed;
+                report.detail = expected.map(|expected| format!("expected error {}", expected));
+                report.error = Some(error);
+            }
+        }
+
+        if report.status == StepStatus::Failed && !step.continue_on_failure {
+            stopped = true;
+        }
+        steps.push(report);
+    }
+
+    ScenarioReport {
+        scenario: scenario.name.clone(),
+        backend: backend.name(),
+        duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+        steps,
+    }
+}
+
+/// Run one action, returning a short description of what it did
+async fn run_step(
+    action: &Action,
+    vars: &Variables,
+    base_dir: &Path,
+    backend: &mut Backend,
+) -> Result<Option<String>, StepError> {
+    match action {
+        Action::Register(step) => {
+            let content = match (&step.schema, &step.file) {
+                (Some(schema), None) => vars.expand(schema)?,
+                (None, Some(file)) => read_file(base_dir, &vars.expand(file)?)?,
+                _ => {
+                    return Err(StepError::new(
+                        INVALID_STEP,
+                        "register needs exactly one of 'schema' or 'file'",
+                    ))
+                }
+            };
+            let metadata = step
+                .metadata
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), vars.expand(value)?)))
+                .collect::<Result<BTreeMap<_, _>, ScenarioError>>()?;
+            let version = backend
+                .register(Registration {
+                    subject: vars.expand(&step.subject)?,
+                    format: step.format,
+                    content,
+                    version: step.version.as_deref().map(|v| vars.expand(v)).transpose()?,
+                    compatibility: step.compatibility,
+                    metadata,
+                })
+                .await?;
+            Ok(Some(format!("registered version {}", version)))
+        }
+        Action::SetCompat(step) => {
+            backend.set_compat(&vars.expand(&step.subject)?, step.mode).await?;
+            Ok(Some(format!("compatibility set to {:?}", step.mode)))
+        }
+        Action::RefreshPolicies(step) => {
+            let path = vars.expand(&step.file)?;
+            let text = read_file(base_dir, &path)?;
+            // YAML is a superset of JSON, so this reads both
+            let policies: SchemaPolicies = serde_yaml::from_str(&text)
+                .map_err(|e| StepError::new(INVALID_STEP, format!("invalid policies in {}: {}", path, e)))?;
+            backend.refresh_policies(policies).await?;
+            Ok(Some(format!("policies loaded from {}", path)))
+        }
+        Action::AssertStats(step) => {
+            let stats = backend.stats().await?;
+            let mut mismatches = Vec::new();
+            if let Some(expected) = step.subjects {
+                if stats.subjects != expected {
+                    mismatches.push(format!("subjects: expected {}, found {}", expected, stats.subjects));
+                }
+            }
+            for (subject, expected) in &step.versions {
+                let subject = vars.expand(subject)?;
+                let found = stats.versions.get(&subject).copied().unwrap_or(0);
+                if found != *expected {
+                    mismatches.push(format!("{} versions: expected {}, found {}", subject, expected, found));
+                }
+            }
+            if mismatches.is_empty() {
+                Ok(Some(format!("{} subjects", stats.subjects)))
+            } else {
+                let mut error = StepError::new(ASSERTION_FAILED, mismatches.join("; "));
+                error.details = serde_json::to_value(&stats).ok();
+                Err(error)
+            }
+        }
+    }
+}
+
+fn read_file(base_dir: &Path, path: &str) -> Result<String, ScenarioError> {
+    let full = base_dir.join(path);
+    std::fs::read_to_string(&full).map_err(|e| ScenarioError::Io(format!("{}: {}", full.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format::{AssertStatsStep, Step};
+    use super::*;
+
+    const POLICIES: &str = r#"
+field_naming:
+  convention: snake_case
+  enforce: false
+type_restrictions: []
+required_metadata: [owner]
+custom_rules: []
+"#;
+
+    const SCENARIO: &str = r#"
+version: 1
+name: owner becomes required
+variables:
+  subject: com.example.payments
+steps:
+  - name: register v1
+    register:
+      subject: ${subject}
+      file: payments-v1.json
+  - refresh-policies:
+      file: require-owner.yaml
+  - name: owner is now required
+    register:
+      subject: ${subject}
+      file: payments-v2.json
+      version: 1.1.0
+    expect-error: SCHEMA_VALIDATION_FAILED
+  - name: with an owner
+    register:
+      subject: ${subject}
+      file: payments-v2.json
+      version: 1.1.0
+      metadata:
+        owner: payments-team
+  - assert-stats:
+      subjects: 1
+      versions:
+        ${subject}: 2
+"#;
+
+    fn scenario_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("payments-v1.json"), r#"{"type": "object"}"#).unwrap();
+        std::fs::write(
+            dir.path().join("payments-v2.json"),
+            r#"{"type": "object", "properties": {"memo": {"type": "string"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("require-owner.yaml"), POLICIES).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_embedded_scenario_passes() {
+        let dir = scenario_dir();
+        let scenario = Scenario::parse(SCENARIO).unwrap();
+        let mut backend = Backend::embedded();
+
+        let report = run_scenario(&scenario, dir.path(), &BTreeMap::new(), &mut backend).await;
+        assert!(report.is_success(), "{}", report.to_markdown());
+        assert_eq!(report.count(StepStatus::Passed), 5);
+        assert_eq!(report.steps[2].detail.as_deref(), Some("failed as expected with SCHEMA_VALIDATION_FAILED"));
+    }
+
+    #[tokio::test]
+    async fn test_failure_skips_remaining_steps_unless_continued() {
+        let dir = scenario_dir();
+        // Without the policy refresh the expected error never happens
+        let mut scenario = Scenario::parse(SCENARIO).unwrap();
+        scenario.steps.remove(1);
+
+        let report = run_scenario(&scenario, dir.path(), &BTreeMap::new(), &mut Backend::embedded()).await;
+        let statuses: Vec<StepStatus> = report.steps.iter().map(|step| step.status).collect();
+        assert_eq!(
+            statuses,
+            vec![StepStatus::Passed, StepStatus::Failed, StepStatus::Skipped, StepStatus::Skipped]
+        );
+        assert_eq!(report.steps[1].error.as_ref().unwrap().code, UNEXPECTED_SUCCESS);
+
+        scenario.steps[1].continue_on_failure = true;
+        scenario.steps.push(Step {
+            name: None,
+            expect_error: None,
+            continue_on_failure: false,
+            action: Action::AssertStats(AssertStatsStep {
+                subjects: Some(3),
+                versions: BTreeMap::new(),
+            }),
+        });
+        let report = run_scenario(&scenario, dir.path(), &BTreeMap::new(), &mut Backend::embedded()).await;
+        assert_eq!(report.count(StepStatus::Failed), 2);
+        assert_eq!(report.count(StepStatus::Skipped), 0);
+        let assertion = report.steps[4].error.as_ref().unwrap();
+        assert_eq!(assertion.code, ASSERTION_FAILED);
+        assert!(assertion.details.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_variable_overrides_and_missing_files() {
+        let dir = scenario_dir();
+        let scenario = Scenario::parse(SCENARIO).unwrap();
+        let overrides = BTreeMap::from([("subject".to_string(), "com.example.orders".to_string())]);
+
+        let report = run_scenario(&scenario, dir.path(), &overrides, &mut Backend::embedded()).await;
+        assert!(report.is_success(), "{}", report.to_markdown());
+
+        let missing = tempfile::tempdir().unwrap();
+        let report = run_scenario(&scenario, missing.path(), &overrides, &mut Backend::embedded()).await;
+        assert_eq!(report.steps[0].error.as_ref().unwrap().code, INVALID_STEP);
+    }
+}
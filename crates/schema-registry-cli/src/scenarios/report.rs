@@ -0,0 +1,197 @@
+//! Scenario results as JUnit XML and markdown
+
+use serde::Serialize;
+use std::fmt::Write;
+
+use super::backend::StepError;
+
+/// How a step ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepStatus {
+    Passed,
+    Failed,
+    /// Not run because an earlier step failed without `continue-on-failure`
+    Skipped,
+}
+
+/// Outcome of one step
+#[derive(Debug, Clone, Serialize)]
+pub struct StepReport {
+    pub index: usize,
+    pub name: String,
+    pub action: &'static str,
+    pub status: StepStatus,
+    pub duration_ms: f64,
+    /// What happened, e.g. the registered version or the expected error seen
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Why the step failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<StepError>,
+}
+
+/// Outcome of a scenario run
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioReport {
+    pub scenario: String,
+    pub backend: &'static str,
+    pub duration_ms: f64,
+    pub steps: Vec<StepReport>,
+}
+
+impl ScenarioReport {
+    pub fn count(&self, status: StepStatus) -> usize {
+        self.steps.iter().filter(|step| step.status == status).count()
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.count(StepStatus::Failed) == 0
+    }
+
+    /// One `<testsuite>` with a `<testcase>` per step
+    pub fn to_junit_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+            escape_xml(&self.scenario),
+            self.steps.len(),
+            self.count(StepStatus::Failed),
+            self.count(StepStatus::Skipped),
+            self.duration_ms / 1000.0
+        );
+        let _ = writeln!(xml, "  <properties><property name=\"backend\" value=\"{}\"/></properties>", self.backend);
+        for step in &self.steps {
+            let _ = write!(
+                xml,
+                "  <testcase classname=\"{}\" name=\"{:02} {}\" time=\"{:.3}\"",
+                escape_xml(&self.scenario),
+                step.index + 1,
+                escape_xml(&step.name),
+                step.duration_ms / 1000.0
+            );
+            match (step.status, &step.error) {
+                (StepStatus::Passed, _) => xml.push_str("/>\n"),
+                (StepStatus::Skipped, _) => xml.push_str(">\n    <skipped/>\n  </testcase>\n"),
+                (StepStatus::Failed, error) => {
+                    let (code, message) = error
+                        .as_ref()
+                        .map_or(("", ""), |error| (error.code.as_str(), error.message.as_str()));
+                    let body = error
+                        .as_ref()
+                        .and_then(|error| serde_json::to_string_pretty(error).ok())
+                        .unwrap_or_default();
+                    let _ = write!(
+                        xml,
+                        ">\n    <failure type=\"{}\" message=\"{}\">{}</failure>\n  </testcase>\n",
+                        escape_xml(code),
+                        escape_xml(message),
+                        escape_xml(&body)
+                    );
+                }
+            }
+        }
+        xml.push_str("</testsuite>\n");
+        xml
+    }
+
+    /// Summary line and a table of steps, with failed steps' errors below
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("# Scenario: {}\n\n", self.scenario);
+        let _ = writeln!(
+            md,
+            "Backend: `{}` | {} passed, {} failed, {} skipped | {:.1} ms\n",
+            self.backend,
+            self.count(StepStatus::Passed),
+            self.count(StepStatus::Failed),
+            self.count(StepStatus::Skipped),
+            self.duration_ms
+        );
+        md.push_str("| # | Step | Action | Status | Detail |\n|---|------|--------|--------|--------|\n");
+        for step in &self.steps {
+            let detail = match &step.error {
+                Some(error) => format!("`{}` {}", error.code, error.message),
+                None => step.detail.clone().unwrap_or_default(),
+            };
+            let _ = writeln!(
+                md,
+                "| {} | {} | {} | {} | {} |",
+                step.index + 1,
+                step.name,
+                step.action,
+                match step.status {
+                    StepStatus::Passed => "✅ passed",
+                    StepStatus::Failed => "❌ failed",
+                    StepStatus::Skipped => "⏭ skipped",
+                },
+                detail.replace('|', "\\|").replace('\n', " ")
+            );
+        }
+
+        for step in self.steps.iter().filter(|step| step.status == StepStatus::Failed) {
+            if let Some(error) = &step.error {
+                let _ = write!(
+                    md,
+                    "\n## Step {}: {}\n\n```json\n{}\n```\n",
+                    step.index + 1,
+                    step.name,
+                    serde_json::to_string_pretty(error).unwrap_or_default()
+                );
+            }
+        }
+        md
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> ScenarioReport {
+        let step = |index, status, error: Option<StepError>| StepReport {
+            index,
+            name: format!("step <{}>", index),
+            action: "register",
+            status,
+            duration_ms: 1.5,
+            detail: None,
+            error,
+        };
+        ScenarioReport {
+            scenario: "evolve".to_string(),
+            backend: "embedded",
+            duration_ms: 4.5,
+            steps: vec![
+                step(0, StepStatus::Passed, None),
+                step(1, StepStatus::Failed, Some(StepError::new("COMPATIBILITY_CHECK_FAILED", "field \"id\" removed"))),
+                step(2, StepStatus::Skipped, None),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_junit_xml_counts_and_escapes() {
+        let xml = report().to_junit_xml();
+        assert!(xml.contains("tests=\"3\" failures=\"1\" skipped=\"1\""));
+        assert!(xml.contains("name=\"01 step &lt;0&gt;\" time=\"0.002\"/>"));
+        assert!(xml.contains("<failure type=\"COMPATIBILITY_CHECK_FAILED\" message=\"field &quot;id&quot; removed\">"));
+        assert!(xml.contains("<skipped/>"));
+    }
+
+    #[test]
+    fn test_markdown_lists_failed_step_errors() {
+        let md = report().to_markdown();
+        assert!(md.contains("1 passed, 1 failed, 1 skipped"));
+        assert!(md.contains("| 2 | step <1> | register | ❌ failed | `COMPATIBILITY_CHECK_FAILED`"));
+        assert!(md.contains("## Step 2: step <1>"));
+    }
+}
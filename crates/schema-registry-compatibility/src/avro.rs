@@ -0,0 +1,417 @@
+//! Avro schema resolution diff
+//!
+//! Applies the Avro specification's schema resolution rules to a reader and
+//! writer schema: primitive promotion, record fields matched by name or alias
+//! (missing reader fields need a default), enum symbols (unless the reader
+//! declares a default symbol), fixed sizes, arrays, maps and unions.
+
+use crate::Direction;
+use schema_registry_core::traits::CompatibilityViolation;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// Collect violations preventing `reader` from decoding data written with `writer`
+pub(crate) fn diff(reader: &Value, writer: &Value, direction: Direction) -> Vec<CompatibilityViolation> {
+    let mut resolver = Resolver {
+        reader_names: HashMap::new(),
+        writer_names: HashMap::new(),
+        visited: HashSet::new(),
+        direction,
+        out: Vec::new(),
+    };
+    collect_names(reader, None, &mut resolver.reader_names);
+    collect_names(writer, None, &mut resolver.writer_names);
+    resolver.compare(reader, writer, "$");
+    resolver.out
+}
+
+struct Resolver<'a> {
+    reader_names: HashMap<String, &'a Value>,
+    writer_names: HashMap<String, &'a Value>,
+    /// Named type pairs already compared; guards recursive types
+    visited: HashSet<(String, String)>,
+    direction: Direction,
+    out: Vec<CompatibilityViolation>,
+}
+
+impl<'a> Resolver<'a> {
+    fn compare(&mut self, reader: &'a Value, writer: &'a Value, path: &str) {
+        let reader = resolve(reader, &self.reader_names);
+        let writer = resolve(writer, &self.writer_names);
+
+        if let (Some(reader_name), Some(writer_name)) = (full_name(reader), full_name(writer)) {
+            if !self.visited.insert((reader_name, writer_name)) {
+                return;
+            }
+        }
+
+        match (reader, writer) {
+            (_, Value::Array(writer_branches)) => {
+                let unreadable = writer_branches.iter().any(|branch| !self.readable(reader, branch));
+                if unreadable {
+                    self.push_union_change(reader, writer, path);
+                    return;
+                }
+                if let Value::Array(reader_branches) = reader {
+                    for branch in writer_branches {
+                        if let Some(target) = self.matching_branch(reader_branches, branch) {
+                            self.compare(target, branch, path);
+                        }
+                    }
+                } else {
+                    for branch in writer_branches {
+                        self.compare(reader, branch, path);
+                    }
+                }
+            }
+            (Value::Array(reader_branches), _) => match self.matching_branch(reader_branches, writer) {
+                Some(target) => self.compare(target, writer, path),
+                None => self.push_union_change(reader, writer, path),
+            },
+            _ => self.compare_single(reader, writer, path),
+        }
+    }
+
+    fn compare_single(&mut self, reader: &'a Value, writer: &'a Value, path: &str) {
+        let reader_kind = kind(reader);
+        let writer_kind = kind(writer);
+
+        if reader_kind != writer_kind {
+            if promotes(writer_kind, reader_kind) {
+                return;
+            }
+            let violation = if promotes(reader_kind, writer_kind) {
+                self.direction.reader_type_narrower(label(reader), label(writer), path)
+            } else {
+                self.direction.type_changed(label(reader), label(writer), path)
+            };
+            self.out.push(violation);
+            return;
+        }
+
+        match reader_kind {
+            "record" | "error" => self.compare_records(reader, writer, path),
+            "enum" => self.compare_enums(reader, writer, path),
+            "fixed" => {
+                if reader.get("size") != writer.get("size") {
+                    let violation = self.direction.type_changed(label(reader), label(writer), path);
+                    self.out.push(violation);
+                }
+            }
+            "array" => {
+                if let (Some(r), Some(w)) = (reader.get("items"), writer.get("items")) {
+                    self.compare(r, w, &format!("{}[]", path));
+                }
+            }
+            "map" => {
+                if let (Some(r), Some(w)) = (reader.get("values"), writer.get("values")) {
+                    self.compare(r, w, &format!("{}{{}}", path));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn compare_records(&mut self, reader: &'a Value, writer: &'a Value, path: &str) {
+        let empty = Vec::new();
+        let reader_fields = reader.get("fields").and_then(Value::as_array).unwrap_or(&empty);
+        let writer_fields = writer.get("fields").and_then(Value::as_array).unwrap_or(&empty);
+
+        for reader_field in reader_fields {
+            let Some(name) = reader_field.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let aliases: Vec<&str> = reader_field
+                .get("aliases")
+                .and_then(Value::as_array)
+                .map(|a| a.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+
+            let writer_field = writer_fields.iter().find(|f| {
+                f.get("name")
+                    .and_then(Value::as_str)
+                    .is_some_and(|n| n == name || aliases.contains(&n))
+            });
+
+            match writer_field {
+                Some(writer_field) => {
+                    if let (Some(r), Some(w)) = (reader_field.get("type"), writer_field.get("type")) {
+                        self.compare(r, w, &format!("{}.{}", path, name));
+                    }
+                }
+                None if reader_field.get("default").is_some() => {}
+                None => {
+                    let violation = self.direction.reader_requires_field(name, false, path);
+                    self.out.push(violation);
+                }
+            }
+        }
+    }
+
+    fn compare_enums(&mut self, reader: &'a Value, writer: &'a Value, path: &str) {
+        if reader.get("default").is_some() {
+            return;
+        }
+        let symbols = |schema: &'a Value| -> Vec<&'a str> {
+            schema
+                .get("symbols")
+                .and_then(Value::as_array)
+                .map(|s| s.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default()
+        };
+        let reader_symbols = symbols(reader);
+        for symbol in symbols(writer).into_iter().filter(|s| !reader_symbols.contains(s)) {
+            let violation = self.direction.reader_missing_symbol(symbol.to_string(), path);
+            self.out.push(violation);
+        }
+    }
+
+    /// Whether `writer` resolves against `reader` at all, ignoring nested detail
+    fn readable(&self, reader: &'a Value, writer: &'a Value) -> bool {
+        let reader = resolve(reader, &self.reader_names);
+        let writer = resolve(writer, &self.writer_names);
+        match reader {
+            Value::Array(branches) => self.matching_branch(branches, writer).is_some(),
+            _ => shallow_match(reader, writer),
+        }
+    }
+
+    /// First reader branch the writer schema resolves to: exact kind (and name
+    /// for named types) first, then promotion
+    fn matching_branch(&self, reader_branches: &'a [Value], writer: &'a Value) -> Option<&'a Value> {
+        let writer = resolve(writer, &self.writer_names);
+        let resolved: Vec<&Value> = reader_branches.iter().map(|b| resolve(b, &self.reader_names)).collect();
+        resolved
+            .iter()
+            .find(|r| kind(r) == kind(writer) && shallow_match(r, writer))
+            .or_else(|| resolved.iter().find(|r| shallow_match(r, writer)))
+            .copied()
+    }
+
+    fn push_union_change(&mut self, reader: &Value, writer: &Value, path: &str) {
+        let reader_branches = branch_labels(reader, &self.reader_names);
+        let writer_branches = branch_labels(writer, &self.writer_names);
+        let violation = self.direction.union_change(reader_branches, writer_branches, path);
+        self.out.push(violation);
+    }
+}
+
+fn branch_labels<'a>(schema: &'a Value, names: &HashMap<String, &'a Value>) -> Vec<String> {
+    match schema {
+        Value::Array(branches) => branches.iter().map(|b| label(resolve(b, names))).collect(),
+        other => vec![label(resolve(other, names))],
+    }
+}
+
+/// Register every named type under its full name
+fn collect_names<'a>(schema: &'a Value, namespace: Option<&str>, names: &mut HashMap<String, &'a Value>) {
+    match schema {
+        Value::Array(branches) => {
+            for branch in branches {
+                collect_names(branch, namespace, names);
+            }
+        }
+        Value::Object(obj) => {
+            let namespace = obj.get("namespace").and_then(Value::as_str).or(namespace);
+            if let Some(name) = obj.get("name").and_then(Value::as_str) {
+                if matches!(kind(schema), "record" | "error" | "enum" | "fixed") {
+                    names.insert(qualify(name, namespace), schema);
+                    // Unqualified lookups are common in hand-written schemas
+                    names.entry(name.to_string()).or_insert(schema);
+                }
+            }
+            if let Some(fields) = obj.get("fields").and_then(Value::as_array) {
+                for field in fields {
+                    if let Some(ty) = field.get("type") {
+                        collect_names(ty, namespace, names);
+                    }
+                }
+            }
+            for key in ["items", "values"] {
+                if let Some(inner) = obj.get(key) {
+                    collect_names(inner, namespace, names);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn qualify(name: &str, namespace: Option<&str>) -> String {
+    match namespace {
+        Some(ns) if !name.contains('.') && !ns.is_empty() => format!("{}.{}", ns, name),
+        _ => name.to_string(),
+    }
+}
+
+/// Follow a by-name reference to its definition
+fn resolve<'a>(schema: &'a Value, names: &HashMap<String, &'a Value>) -> &'a Value {
+    if let Value::String(name) = schema {
+        if let Some(definition) = names.get(name) {
+            return definition;
+        }
+    }
+    if let Value::Object(obj) = schema {
+        // {"type": "Name"} wrapping a reference
+        if let Some(Value::String(name)) = obj.get("type") {
+            if let Some(definition) = names.get(name) {
+                return definition;
+            }
+        }
+    }
+    schema
+}
+
+fn kind(schema: &Value) -> &str {
+    match schema {
+        Value::String(ty) => ty,
+        Value::Object(obj) => match obj.get("type") {
+            Some(Value::String(ty)) => ty,
+            Some(inner) => kind(inner),
+            None => "",
+        },
+        Value::Array(_) => "union",
+        _ => "",
+    }
+}
+
+fn full_name(schema: &Value) -> Option<String> {
+    let name = schema.get("name")?.as_str()?;
+    Some(qualify(name, schema.get("namespace").and_then(Value::as_str)))
+}
+
+fn label(schema: &Value) -> String {
+    match kind(schema) {
+        "record" | "error" | "enum" => full_name(schema).unwrap_or_else(|| kind(schema).to_string()),
+        "fixed" => format!(
+            "fixed({})",
+            schema.get("size").and_then(Value::as_u64).unwrap_or_default()
+        ),
+        "array" => "array".to_string(),
+        "map" => "map".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Avro's permitted writer -> reader promotions
+fn promotes(writer: &str, reader: &str) -> bool {
+    matches!(
+        (writer, reader),
+        ("int", "long" | "float" | "double")
+            | ("long", "float" | "double")
+            | ("float", "double")
+            | ("string", "bytes")
+            | ("bytes", "string")
+    )
+}
+
+fn shallow_match(reader: &Value, writer: &Value) -> bool {
+    let (reader_kind, writer_kind) = (kind(reader), kind(writer));
+    if reader_kind != writer_kind {
+        return promotes(writer_kind, reader_kind);
+    }
+    match reader_kind {
+        "record" | "error" | "enum" | "fixed" => {
+            let unqualified = |s: &Value| s.get("name").and_then(Value::as_str).map(str::to_string);
+            unqualified(reader) == unqualified(writer)
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn codes(violations: &[CompatibilityViolation]) -> Vec<&'static str> {
+        violations.iter().map(|v| v.code()).collect()
+    }
+
+    fn user(fields: Value) -> Value {
+        json!({"type": "record", "name": "User", "namespace": "com.example", "fields": fields})
+    }
+
+    #[test]
+    fn test_added_field_needs_default() {
+        let old = user(json!([{"name": "id", "type": "string"}]));
+        let new = user(json!([
+            {"name": "id", "type": "string"},
+            {"name": "email", "type": "string"}
+        ]));
+
+        assert_eq!(codes(&diff(&new, &old, Direction::Backward)), vec!["REQUIRED_FIELD_ADDED"]);
+
+        let with_default = user(json!([
+            {"name": "id", "type": "string"},
+            {"name": "email", "type": "string", "default": ""}
+        ]));
+        assert!(diff(&with_default, &old, Direction::Backward).is_empty());
+    }
+
+    #[test]
+    fn test_removed_field_without_default_breaks_forward() {
+        let old = user(json!([
+            {"name": "id", "type": "string"},
+            {"name": "age", "type": "int"}
+        ]));
+        let new = user(json!([{"name": "id", "type": "string"}]));
+
+        let violations = diff(&old, &new, Direction::Forward);
+        assert_eq!(codes(&violations), vec!["FIELD_REMOVED_WITHOUT_DEFAULT"]);
+        assert!(diff(&new, &old, Direction::Backward).is_empty());
+    }
+
+    #[test]
+    fn test_type_promotion_and_narrowing() {
+        let old = user(json!([{"name": "count", "type": "long"}]));
+        let new = user(json!([{"name": "count", "type": "int"}]));
+
+        let violations = diff(&new, &old, Direction::Backward);
+        assert_eq!(codes(&violations), vec!["TYPE_NARROWED"]);
+        assert_eq!(violations[0].field_path, "$.count");
+
+        // int written, long read: promotion
+        assert!(diff(&old, &new, Direction::Forward).is_empty());
+    }
+
+    #[test]
+    fn test_enum_symbol_removed() {
+        let old = json!({"type": "enum", "name": "Color", "symbols": ["RED", "GREEN", "BLUE"]});
+        let new = json!({"type": "enum", "name": "Color", "symbols": ["RED", "GREEN"]});
+
+        let violations = diff(&new, &old, Direction::Backward);
+        assert_eq!(codes(&violations), vec!["ENUM_SYMBOL_REMOVED"]);
+
+        let with_default = json!({
+            "type": "enum", "name": "Color", "symbols": ["RED", "GREEN"], "default": "RED"
+        });
+        assert!(diff(&with_default, &old, Direction::Backward).is_empty());
+    }
+
+    #[test]
+    fn test_union_branch_removed() {
+        let old = user(json!([{"name": "nick", "type": ["null", "string", "int"]}]));
+        let new = user(json!([{"name": "nick", "type": ["null", "string"]}]));
+
+        let violations = diff(&new, &old, Direction::Backward);
+        assert_eq!(codes(&violations), vec!["INCOMPATIBLE_UNION_CHANGE"]);
+
+        // Widening a field into a union is fine for readers of the union
+        let plain = user(json!([{"name": "nick", "type": "string"}]));
+        assert!(diff(&new, &plain, Direction::Backward).is_empty());
+    }
+
+    #[test]
+    fn test_recursive_named_types_terminate() {
+        let node = json!({
+            "type": "record",
+            "name": "Node",
+            "fields": [
+                {"name": "value", "type": "int"},
+                {"name": "next", "type": ["null", "Node"]}
+            ]
+        });
+        assert!(diff(&node, &node, Direction::Backward).is_empty());
+    }
+}
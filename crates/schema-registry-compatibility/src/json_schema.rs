@@ -0,0 +1,306 @@
+//! JSON Schema structural diff
+//!
+//! Compares a reader schema against a writer schema: every document the
+//! writer accepts must also be accepted by the reader. Covers `type`, `enum`,
+//! numeric/length bounds, `pattern`, `required`, `properties`,
+//! `additionalProperties: false` and array `items`. Combinators (`oneOf`,
+//! `anyOf`, `allOf`) and `$ref` are not analysed.
+
+use crate::Direction;
+use schema_registry_core::traits::CompatibilityViolation;
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+/// Keywords where a larger reader value is stricter
+const LOWER_BOUNDS: &[&str] = &["minimum", "exclusiveMinimum", "minLength", "minItems", "minProperties"];
+
+/// Keywords where a smaller reader value is stricter
+const UPPER_BOUNDS: &[&str] = &["maximum", "exclusiveMaximum", "maxLength", "maxItems", "maxProperties"];
+
+/// Collect violations preventing `reader` from accepting documents valid under `writer`
+pub(crate) fn diff(reader: &Value, writer: &Value, direction: Direction) -> Vec<CompatibilityViolation> {
+    let mut violations = Vec::new();
+    diff_node(reader, writer, "$", direction, &mut violations);
+    violations
+}
+
+fn diff_node(
+    reader: &Value,
+    writer: &Value,
+    path: &str,
+    direction: Direction,
+    out: &mut Vec<CompatibilityViolation>,
+) {
+    let (Some(reader), Some(writer)) = (reader.as_object(), writer.as_object()) else {
+        return;
+    };
+
+    if !diff_types(reader, writer, path, direction, out) {
+        // Unrelated types: nested keywords are meaningless to compare
+        return;
+    }
+
+    diff_enum(reader, writer, path, direction, out);
+    diff_bounds(reader, writer, path, direction, out);
+
+    if let Some(pattern) = reader.get("pattern") {
+        if writer.get("pattern") != Some(pattern) {
+            out.push(direction.reader_constraint_stricter(
+                "pattern",
+                pattern.clone(),
+                writer.get("pattern").cloned().unwrap_or(Value::Null),
+                path,
+            ));
+        }
+    }
+
+    diff_object(reader, writer, path, direction, out);
+
+    if let (Some(reader_items), Some(writer_items)) = (reader.get("items"), writer.get("items")) {
+        diff_node(reader_items, writer_items, &format!("{}[]", path), direction, out);
+    }
+}
+
+/// Returns `false` when the types are unrelated
+fn diff_types(
+    reader: &Map<String, Value>,
+    writer: &Map<String, Value>,
+    path: &str,
+    direction: Direction,
+    out: &mut Vec<CompatibilityViolation>,
+) -> bool {
+    let reader_types = types_of(reader);
+    let writer_types = types_of(writer);
+
+    let Some(reader_set) = &reader_types else {
+        // Reader accepts any type
+        return true;
+    };
+
+    let accepts = |set: &BTreeSet<String>, ty: &str| {
+        set.contains(ty) || (ty == "integer" && set.contains("number"))
+    };
+
+    let writer_accepted = match &writer_types {
+        Some(writer_set) => writer_set.iter().all(|ty| accepts(reader_set, ty)),
+        None => false,
+    };
+    if writer_accepted {
+        return true;
+    }
+
+    let reader_label = type_label(&reader_types);
+    let writer_label = type_label(&writer_types);
+    let reader_is_subset = match &writer_types {
+        Some(writer_set) => reader_set.iter().all(|ty| accepts(writer_set, ty)),
+        None => true,
+    };
+
+    if reader_is_subset {
+        out.push(direction.reader_type_narrower(reader_label, writer_label, path));
+        true
+    } else {
+        out.push(direction.type_changed(reader_label, writer_label, path));
+        false
+    }
+}
+
+fn diff_enum(
+    reader: &Map<String, Value>,
+    writer: &Map<String, Value>,
+    path: &str,
+    direction: Direction,
+    out: &mut Vec<CompatibilityViolation>,
+) {
+    let Some(reader_values) = reader.get("enum").and_then(Value::as_array) else {
+        return;
+    };
+
+    match writer.get("enum").and_then(Value::as_array) {
+        Some(writer_values) => {
+            for value in writer_values.iter().filter(|v| !reader_values.contains(v)) {
+                let symbol = value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string());
+                out.push(direction.reader_missing_symbol(symbol, path));
+            }
+        }
+        None => out.push(direction.reader_constraint_stricter(
+            "enum",
+            Value::Array(reader_values.clone()),
+            Value::Null,
+            path,
+        )),
+    }
+}
+
+fn diff_bounds(
+    reader: &Map<String, Value>,
+    writer: &Map<String, Value>,
+    path: &str,
+    direction: Direction,
+    out: &mut Vec<CompatibilityViolation>,
+) {
+    let bounds = LOWER_BOUNDS
+        .iter()
+        .map(|k| (*k, true))
+        .chain(UPPER_BOUNDS.iter().map(|k| (*k, false)));
+
+    for (keyword, is_lower) in bounds {
+        let Some(reader_bound) = reader.get(keyword).and_then(Value::as_f64) else {
+            continue;
+        };
+        let tighter = match writer.get(keyword).and_then(Value::as_f64) {
+            Some(writer_bound) if is_lower => reader_bound > writer_bound,
+            Some(writer_bound) => reader_bound < writer_bound,
+            None => true,
+        };
+        if tighter {
+            out.push(direction.reader_constraint_stricter(
+                keyword,
+                reader[keyword].clone(),
+                writer.get(keyword).cloned().unwrap_or(Value::Null),
+                path,
+            ));
+        }
+    }
+}
+
+fn diff_object(
+    reader: &Map<String, Value>,
+    writer: &Map<String, Value>,
+    path: &str,
+    direction: Direction,
+    out: &mut Vec<CompatibilityViolation>,
+) {
+    let empty = Map::new();
+    let reader_props = reader.get("properties").and_then(Value::as_object).unwrap_or(&empty);
+    let writer_props = writer.get("properties").and_then(Value::as_object).unwrap_or(&empty);
+    let reader_required = required_of(reader);
+    let writer_required = required_of(writer);
+
+    for field in reader_required.difference(&writer_required) {
+        out.push(direction.reader_requires_field(field, writer_props.contains_key(field), path));
+    }
+
+    let closed = |schema: &Map<String, Value>| schema.get("additionalProperties") == Some(&Value::Bool(false));
+    if closed(reader) {
+        for field in writer_props.keys().filter(|f| !reader_props.contains_key(*f)) {
+            out.push(direction.reader_rejects_field(field, path));
+        }
+        if !closed(writer) {
+            out.push(direction.reader_constraint_stricter(
+                "additionalProperties",
+                Value::Bool(false),
+                writer.get("additionalProperties").cloned().unwrap_or(Value::Null),
+                path,
+            ));
+        }
+    }
+
+    for (name, reader_prop) in reader_props {
+        if let Some(writer_prop) = writer_props.get(name) {
+            diff_node(reader_prop, writer_prop, &format!("{}.{}", path, name), direction, out);
+        }
+    }
+}
+
+fn types_of(schema: &Map<String, Value>) -> Option<BTreeSet<String>> {
+    match schema.get("type")? {
+        Value::String(ty) => Some(BTreeSet::from([ty.clone()])),
+        Value::Array(types) => Some(types.iter().filter_map(Value::as_str).map(str::to_string).collect()),
+        _ => None,
+    }
+}
+
+fn required_of(schema: &Map<String, Value>) -> BTreeSet<String> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|fields| fields.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn type_label(types: &Option<BTreeSet<String>>) -> String {
+    match types {
+        Some(set) => set.iter().cloned().collect::<Vec<_>>().join("|"),
+        None => "any".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn codes(violations: &[CompatibilityViolation]) -> Vec<&'static str> {
+        violations.iter().map(|v| v.code()).collect()
+    }
+
+    #[test]
+    fn test_required_field_added_is_backward_violation() {
+        let old = json!({"type": "object", "properties": {"id": {"type": "string"}}});
+        let new = json!({
+            "type": "object",
+            "properties": {"id": {"type": "string"}, "email": {"type": "string"}},
+            "required": ["email"]
+        });
+
+        let violations = diff(&new, &old, Direction::Backward);
+        assert_eq!(codes(&violations), vec!["REQUIRED_FIELD_ADDED"]);
+        assert_eq!(violations[0].field_path, "$");
+
+        // Old readers happily ignore the new field
+        assert!(diff(&old, &new, Direction::Forward).is_empty());
+    }
+
+    #[test]
+    fn test_type_narrowing_and_widening() {
+        let old = json!({"type": "object", "properties": {"n": {"type": "number"}}});
+        let new = json!({"type": "object", "properties": {"n": {"type": "integer"}}});
+
+        let backward = diff(&new, &old, Direction::Backward);
+        assert_eq!(codes(&backward), vec!["TYPE_NARROWED"]);
+        assert_eq!(backward[0].field_path, "$.n");
+        assert!(diff(&old, &new, Direction::Forward).is_empty());
+
+        // Widening integer -> number is the mirror image: old readers reject new data
+        let (old, new) = (new, old);
+        let forward = diff(&old, &new, Direction::Forward);
+        assert_eq!(codes(&forward), vec!["TYPE_WIDENED"]);
+        assert!(diff(&new, &old, Direction::Backward).is_empty());
+    }
+
+    #[test]
+    fn test_type_changed_stops_descent() {
+        let old = json!({"type": "string", "maxLength": 10});
+        let new = json!({"type": "integer", "maximum": 5});
+
+        let violations = diff(&new, &old, Direction::Backward);
+        assert_eq!(codes(&violations), vec!["TYPE_CHANGED"]);
+    }
+
+    #[test]
+    fn test_enum_and_constraints() {
+        let old = json!({"type": "string", "enum": ["a", "b", "c"], "maxLength": 10});
+        let new = json!({"type": "string", "enum": ["a", "b"], "maxLength": 5});
+
+        let violations = diff(&new, &old, Direction::Backward);
+        assert_eq!(codes(&violations), vec!["ENUM_SYMBOL_REMOVED", "CONSTRAINT_TIGHTENED"]);
+    }
+
+    #[test]
+    fn test_closed_schema_field_removal() {
+        let old = json!({
+            "type": "object",
+            "properties": {"a": {"type": "string"}, "b": {"type": "string"}},
+            "additionalProperties": false
+        });
+        let new = json!({
+            "type": "object",
+            "properties": {"a": {"type": "string"}},
+            "additionalProperties": false
+        });
+
+        assert_eq!(codes(&diff(&new, &old, Direction::Backward)), vec!["FIELD_REMOVED"]);
+        assert!(diff(&old, &new, Direction::Forward).is_empty());
+    }
+}
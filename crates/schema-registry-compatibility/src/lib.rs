@@ -1,14 +1,153 @@
 //! # Schema Registry Compatibility
 //!
 //! Compatibility checking engine supporting 7 compatibility modes.
+//!
+//! Violations are reported as structured [`CompatViolation`] values located
+//! by path; see [`report`] for rendering them.
+
+mod avro;
+mod json_schema;
+pub mod report;
 
 use async_trait::async_trait;
 use schema_registry_core::{
-    error::Result,
+    error::{Error, Result},
     schema::RegisteredSchema,
-    traits::{CompatibilityChecker, CompatibilityResult},
-    types::CompatibilityMode,
+    traits::{CompatibilityChecker, CompatibilityResult, CompatibilityViolation},
+    types::{CompatViolation, CompatibilityMode, SerializationFormat},
 };
+use serde_json::Value;
+
+/// Which side of an upgrade is reading
+///
+/// The diff modules only know "reader" and "writer"; the direction maps a
+/// reader-side finding to the violation as seen from old -> new.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Direction {
+    /// New schema reads data written with the old one
+    Backward,
+    /// Old schema reads data written with the new one
+    Forward,
+}
+
+impl Direction {
+    /// Reader requires `field`, which the writer does not guarantee
+    fn reader_requires_field(self, field: &str, writer_has_field: bool, path: &str) -> CompatibilityViolation {
+        let field = field.to_string();
+        let violation = match self {
+            Direction::Backward => CompatViolation::RequiredFieldAdded { field },
+            Direction::Forward if writer_has_field => CompatViolation::FieldMadeOptional { field },
+            Direction::Forward => CompatViolation::FieldRemovedWithoutDefault { field },
+        };
+        CompatibilityViolation::new(violation, path)
+    }
+
+    /// Reader rejects unknown properties and the writer declares `field`
+    fn reader_rejects_field(self, field: &str, path: &str) -> CompatibilityViolation {
+        let field = field.to_string();
+        let violation = match self {
+            Direction::Backward => CompatViolation::FieldRemoved { field },
+            Direction::Forward => CompatViolation::FieldAdded { field },
+        };
+        CompatibilityViolation::new(violation, path)
+    }
+
+    /// Reader type is a strict subset of the writer type
+    fn reader_type_narrower(self, reader: String, writer: String, path: &str) -> CompatibilityViolation {
+        let violation = match self {
+            Direction::Backward => CompatViolation::TypeNarrowed { from: writer, to: reader },
+            Direction::Forward => CompatViolation::TypeWidened { from: reader, to: writer },
+        };
+        CompatibilityViolation::new(violation, path)
+    }
+
+    fn type_changed(self, reader: String, writer: String, path: &str) -> CompatibilityViolation {
+        let (from, to) = self.old_new(reader, writer);
+        CompatibilityViolation::new(CompatViolation::TypeChanged { from, to }, path)
+    }
+
+    /// Writer may produce `symbol`, which the reader does not know
+    fn reader_missing_symbol(self, symbol: String, path: &str) -> CompatibilityViolation {
+        let violation = match self {
+            Direction::Backward => CompatViolation::EnumSymbolRemoved { symbol },
+            Direction::Forward => CompatViolation::EnumSymbolAdded { symbol },
+        };
+        CompatibilityViolation::new(violation, path)
+    }
+
+    fn union_change(self, reader: Vec<String>, writer: Vec<String>, path: &str) -> CompatibilityViolation {
+        let (from, to) = self.old_new(reader, writer);
+        CompatibilityViolation::new(CompatViolation::IncompatibleUnionChange { from, to }, path)
+    }
+
+    /// Reader enforces `constraint` more strictly than the writer
+    fn reader_constraint_stricter(
+        self,
+        constraint: &str,
+        reader: Value,
+        writer: Value,
+        path: &str,
+    ) -> CompatibilityViolation {
+        let constraint = constraint.to_string();
+        let violation = match self {
+            Direction::Backward => CompatViolation::ConstraintTightened { constraint, from: writer, to: reader },
+            Direction::Forward => CompatViolation::ConstraintRelaxed { constraint, from: reader, to: writer },
+        };
+        CompatibilityViolation::new(violation, path)
+    }
+
+    fn old_new<T>(self, reader: T, writer: T) -> (T, T) {
+        match self {
+            Direction::Backward => (writer, reader),
+            Direction::Forward => (reader, writer),
+        }
+    }
+}
+
+/// Compare raw schema content under `mode`
+///
+/// Transitive modes compare just this pair; iterating over history is the
+/// caller's job (see [`CompatibilityChecker::check_transitive_compatibility`]).
+/// Protobuf content is not analysed yet and always reports no violations.
+pub fn check_content(
+    new_content: &str,
+    old_content: &str,
+    format: SerializationFormat,
+    mode: CompatibilityMode,
+) -> Result<Vec<CompatibilityViolation>> {
+    let (backward, forward) = match mode {
+        CompatibilityMode::None => return Ok(Vec::new()),
+        CompatibilityMode::Backward | CompatibilityMode::BackwardTransitive => (true, false),
+        CompatibilityMode::Forward | CompatibilityMode::ForwardTransitive => (false, true),
+        CompatibilityMode::Full | CompatibilityMode::FullTransitive => (true, true),
+    };
+
+    let diff: fn(&Value, &Value, Direction) -> Vec<CompatibilityViolation> = match format {
+        SerializationFormat::JsonSchema => json_schema::diff,
+        SerializationFormat::Avro => avro::diff,
+        SerializationFormat::Protobuf => {
+            tracing::debug!("Protobuf compatibility analysis not implemented; skipping");
+            return Ok(Vec::new());
+        }
+    };
+
+    let parse = |content: &str, which: &str| {
+        serde_json::from_str::<Value>(content).map_err(|e| {
+            Error::CompatibilityError(format!("failed to parse {} {} schema: {}", which, format, e))
+        })
+    };
+    let new_schema = parse(new_content, "new")?;
+    let old_schema = parse(old_content, "old")?;
+
+    let mut violations = Vec::new();
+    if backward {
+        violations.extend(diff(&new_schema, &old_schema, Direction::Backward));
+    }
+    if forward {
+        violations.extend(diff(&old_schema, &new_schema, Direction::Forward));
+    }
+    Ok(violations)
+}
 
 /// Compatibility checker
 pub struct CompatibilityCheckerImpl {}
@@ -43,11 +182,25 @@ impl CompatibilityChecker for CompatibilityCheckerImpl {
             });
         }
 
-        // Detailed compatibility check would go here
+        let violations = if new_schema.format != old_schema.format {
+            let violation = CompatViolation::FormatChanged {
+                from: old_schema.format,
+                to: new_schema.format,
+            };
+            vec![CompatibilityViolation::new(violation, "$")]
+        } else {
+            check_content(&new_schema.content, &old_schema.content, new_schema.format, mode)?
+        };
+
+        let violations: Vec<_> = violations
+            .into_iter()
+            .map(|v| v.with_versions(old_schema.version.clone(), new_schema.version.clone()))
+            .collect();
+
         Ok(CompatibilityResult {
-            is_compatible: true,
+            is_compatible: violations.is_empty(),
             mode,
-            violations: Vec::new(),
+            violations,
             checked_versions: vec![old_schema.version.clone()],
         })
     }
@@ -348,4 +501,47 @@ mod tests {
         assert!(compat.is_compatible);
         assert!(compat.violations.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_check_compatibility_reports_structured_violations() {
+        let checker = CompatibilityCheckerImpl::new();
+        let old_schema = create_test_schema(
+            SemanticVersion::new(1, 0, 0),
+            r#"{"type": "object", "properties": {"id": {"type": "string"}}}"#,
+            "hash1",
+        );
+        let new_schema = create_test_schema(
+            SemanticVersion::new(1, 1, 0),
+            r#"{"type": "object", "properties": {"id": {"type": "string"}}, "required": ["id"]}"#,
+            "hash2",
+        );
+
+        let compat = checker
+            .check_compatibility(&new_schema, &old_schema, CompatibilityMode::Backward)
+            .await
+            .unwrap();
+
+        assert!(!compat.is_compatible);
+        assert_eq!(compat.violations.len(), 1);
+        let violation = &compat.violations[0];
+        assert_eq!(
+            violation.violation,
+            CompatViolation::RequiredFieldAdded { field: "id".to_string() }
+        );
+        assert_eq!(violation.old_version, Some(SemanticVersion::new(1, 0, 0)));
+        assert_eq!(violation.new_version, Some(SemanticVersion::new(1, 1, 0)));
+
+        // Forward only: old readers don't care about the new requirement
+        let compat = checker
+            .check_compatibility(&new_schema, &old_schema, CompatibilityMode::Forward)
+            .await
+            .unwrap();
+        assert!(compat.is_compatible);
+    }
+
+    #[test]
+    fn test_check_content_rejects_unparseable_schema() {
+        let result = check_content("{not json", "{}", SerializationFormat::Avro, CompatibilityMode::Backward);
+        assert!(matches!(result, Err(Error::CompatibilityError(_))));
+    }
 }
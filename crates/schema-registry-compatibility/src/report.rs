@@ -0,0 +1,76 @@
+//! Rendering of compatibility violations
+//!
+//! Both renderers group violations by their stable kind so reviewers see
+//! "all removed enum symbols" together rather than in traversal order.
+
+use schema_registry_core::traits::CompatibilityViolation;
+use std::collections::BTreeMap;
+
+/// Group violations by [`CompatibilityViolation::code`], preserving order within a group
+pub fn group_by_kind(violations: &[CompatibilityViolation]) -> BTreeMap<&'static str, Vec<&CompatibilityViolation>> {
+    let mut groups: BTreeMap<&'static str, Vec<&CompatibilityViolation>> = BTreeMap::new();
+    for violation in violations {
+        groups.entry(violation.code()).or_default().push(violation);
+    }
+    groups
+}
+
+/// Render violations as a markdown section per kind
+pub fn render_markdown(violations: &[CompatibilityViolation]) -> String {
+    if violations.is_empty() {
+        return "No compatibility violations.\n".to_string();
+    }
+
+    let mut output = String::new();
+    for (kind, group) in group_by_kind(violations) {
+        output.push_str(&format!("### {} ({})\n\n", kind, group.len()));
+        for violation in group {
+            output.push_str(&format!("- `{}`: {}", violation.field_path, violation.description));
+            if let (Some(old), Some(new)) = (&violation.old_version, &violation.new_version) {
+                output.push_str(&format!(" ({} -> {})", old, new));
+            }
+            output.push('\n');
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Render violations as plain text, one indented line per violation
+pub fn render_text(violations: &[CompatibilityViolation]) -> String {
+    let mut output = String::new();
+    for (kind, group) in group_by_kind(violations) {
+        output.push_str(&format!("{} ({}):\n", kind, group.len()));
+        for violation in group {
+            output.push_str(&format!("  {}\n", violation));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schema_registry_core::CompatViolation;
+
+    #[test]
+    fn test_render_markdown_groups_by_kind() {
+        let violations = vec![
+            CompatibilityViolation::new(CompatViolation::EnumSymbolRemoved { symbol: "RED".to_string() }, "$.color"),
+            CompatibilityViolation::new(CompatViolation::RequiredFieldAdded { field: "id".to_string() }, "$"),
+            CompatibilityViolation::new(CompatViolation::EnumSymbolRemoved { symbol: "BLUE".to_string() }, "$.color"),
+        ];
+
+        let markdown = render_markdown(&violations);
+        assert!(markdown.contains("### ENUM_SYMBOL_REMOVED (2)"));
+        assert!(markdown.contains("### REQUIRED_FIELD_ADDED (1)"));
+        assert!(markdown.find("'RED'").unwrap() < markdown.find("'BLUE'").unwrap());
+        assert!(markdown.find("ENUM_SYMBOL_REMOVED").unwrap() < markdown.find("REQUIRED_FIELD_ADDED").unwrap());
+    }
+
+    #[test]
+    fn test_render_markdown_empty() {
+        assert_eq!(render_markdown(&[]), "No compatibility violations.\n");
+        assert!(render_text(&[]).is_empty());
+    }
+}
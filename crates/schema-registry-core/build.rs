@@ -0,0 +1,81 @@
+//! Build script for schema-registry-core
+//!
+//! Detects the rustc version compiling the crate and:
+//! - exposes it to `buildinfo` via `SCHEMA_REGISTRY_RUSTC_VERSION`
+//! - sets `schema_registry_below_msrv` when rustc is older than the declared
+//!   `rust-version`, which turns into a clear compile error in `buildinfo`
+//! - sets `schema_registry_rustc_1_XX` cfgs so newer stdlib conveniences can be
+//!   gated instead of raising the MSRV by accident
+//!
+//! To use an API stabilized after the MSRV, gate it on the release that
+//! stabilized it and keep a fallback for older toolchains:
+//!
+//! ```ignore
+//! #[cfg(schema_registry_rustc_1_84)]
+//! let root = n.isqrt();
+//! #[cfg(not(schema_registry_rustc_1_84))]
+//! let root = (n as f64).sqrt() as u64;
+//! ```
+//!
+//! Every cfg is declared with `rustc-check-cfg`, so a typo or a version
+//! missing from `VERSION_CFGS` trips `unexpected_cfgs`; add the release
+//! there first.
+
+use std::env;
+use std::process::Command;
+
+/// Minor versions newer than the MSRV that code may gate on
+const VERSION_CFGS: &[u32] = &[83, 84, 85, 86, 87, 88];
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=RUSTC");
+
+    println!("cargo:rustc-check-cfg=cfg(schema_registry_below_msrv)");
+    for minor in VERSION_CFGS {
+        println!("cargo:rustc-check-cfg=cfg(schema_registry_rustc_1_{})", minor);
+    }
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let version_output = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .unwrap_or_default();
+
+    let version = version_output
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or("unknown")
+        .to_string();
+    println!("cargo:rustc-env=SCHEMA_REGISTRY_RUSTC_VERSION={}", version);
+
+    // Deliberately written without newer syntax (e.g. let-else) so that old
+    // toolchains reach the MSRV compile error instead of failing here.
+    let declared = env::var("CARGO_PKG_RUST_VERSION").unwrap_or_default();
+    let (current, msrv) = match (parse_minor(&version), parse_minor(&declared)) {
+        (Some(current), Some(msrv)) => (current, msrv),
+        // Unknown toolchain version: assume it is recent enough
+        _ => return,
+    };
+
+    if current < msrv {
+        println!("cargo:rustc-cfg=schema_registry_below_msrv");
+    }
+
+    for minor in VERSION_CFGS {
+        if current >= *minor {
+            println!("cargo:rustc-cfg=schema_registry_rustc_1_{}", minor);
+        }
+    }
+}
+
+/// Extract the minor component of a `1.MINOR[.PATCH][-suffix]` version
+fn parse_minor(version: &str) -> Option<u32> {
+    let mut parts = version.split(|c: char| c == '.' || c == '-');
+    if parts.next()? != "1" {
+        return None;
+    }
+    parts.next()?.parse().ok()
+}
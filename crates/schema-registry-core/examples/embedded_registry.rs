@@ -0,0 +1,51 @@
+//! Exercising registration logic against an in-process registry
+//!
+//! Run with `cargo run -p schema-registry-core --example embedded_registry`.
+
+use chrono::{DateTime, Utc};
+use schema_registry_core::embedded::{EmbeddedRegistry, ManualClock};
+use schema_registry_core::{CompatibilityMode, SchemaInput, SerializationFormat};
+use std::sync::Arc;
+
+#[tokio::main]
+async fn main() -> schema_registry_core::Result<()> {
+    // A fixed seed and clock make ids and timestamps reproducible
+    let clock = Arc::new(ManualClock::new(DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap()));
+    let registry = EmbeddedRegistry::builder()
+        .with_seed(42)
+        .with_clock(clock.clone())
+        .build();
+
+    let v1 = SchemaInput::new(
+        "com.example",
+        "order",
+        SerializationFormat::JsonSchema,
+        r#"{"type":"object","properties":{"id":{"type":"string"}},"required":["id"]}"#,
+    );
+    let registered = registry.register(v1).await?;
+    println!("registered {} v{} at {}", registered.id, registered.version, registered.metadata.created_at);
+
+    // Fork the state before trying a change
+    let baseline = registry.snapshot();
+
+    clock.advance(chrono::Duration::minutes(5));
+    let v2 = SchemaInput::new(
+        "com.example",
+        "order",
+        SerializationFormat::JsonSchema,
+        r#"{"type":"object","properties":{"id":{"type":"string"},"total":{"type":"number"}},"required":["id"]}"#,
+    )
+    .with_compatibility_mode(CompatibilityMode::Backward);
+
+    let check = registry.check_compatibility(&v2).await?;
+    println!("v2 compatible: {}", check.is_compatible);
+
+    let next = registry.register(v2).await?;
+    println!("registered v{} at {}", next.version, next.metadata.created_at);
+
+    registry.restore(&baseline);
+    let versions = registry.versions("com.example", "order").await?;
+    println!("after restore: {} version(s)", versions.len());
+
+    Ok(())
+}
@@ -0,0 +1,451 @@
+//! Schema annotation extraction
+//!
+//! Collects custom annotations from a schema so code generators and policy
+//! rules can read them without format-specific parsing:
+//! - JSON Schema: `x-*` extension keywords, `description` as `doc`
+//! - Avro: non-reserved attributes and `doc`
+//! - Protobuf: parenthesized custom options (`option (x) = ...;` and
+//!   `[(x) = ...]` on fields), parsed line by line
+//!
+//! Annotations are keyed by a normalized field path: `$` is the schema root,
+//! `.name` a property or field, `[]` array items and `{}` map values. JSON
+//! Schema definitions are rooted at `$defs.<Name>`; Protobuf messages at
+//! `$.<Message>`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::error::{Error, Result};
+use crate::types::SerializationFormat;
+
+/// Key under which documentation strings are reported for every format
+pub const DOC_KEY: &str = "doc";
+
+/// Avro attributes defined by the specification
+const AVRO_RESERVED: &[&str] = &[
+    "type", "name", "namespace", "fields", "symbols", "items", "values", "size", "default",
+    "aliases", "order", "logicalType", "precision", "scale", "doc",
+];
+
+/// Annotations grouped by normalized path, then by key
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AnnotationMap {
+    entries: BTreeMap<String, BTreeMap<String, Value>>,
+}
+
+impl AnnotationMap {
+    /// Annotations at `path`
+    pub fn get(&self, path: &str) -> Option<&BTreeMap<String, Value>> {
+        self.entries.get(path)
+    }
+
+    /// Single annotation value
+    pub fn value(&self, path: &str, key: &str) -> Option<&Value> {
+        self.entries.get(path)?.get(key)
+    }
+
+    /// Iterate over `(path, key, value)` triples in path order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str, &Value)> {
+        self.entries.iter().flat_map(|(path, annotations)| {
+            annotations
+                .iter()
+                .map(move |(key, value)| (path.as_str(), key.as_str(), value))
+        })
+    }
+
+    /// Number of annotations across all paths
+    pub fn len(&self) -> usize {
+        self.entries.values().map(BTreeMap::len).sum()
+    }
+
+    /// Whether no annotations were found
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Subset matching `filter`
+    pub fn filter(&self, filter: &AnnotationFilter) -> AnnotationMap {
+        let mut filtered = AnnotationMap::default();
+        for (path, key, value) in self.iter().filter(|(path, key, _)| filter.matches(path, key)) {
+            filtered.insert(path, key, value.clone());
+        }
+        filtered
+    }
+
+    fn insert(&mut self, path: &str, key: &str, value: Value) {
+        self.entries
+            .entry(path.to_string())
+            .or_default()
+            .insert(key.to_string(), value);
+    }
+}
+
+/// Selects annotations by key prefix, exact key and path prefix
+///
+/// An empty filter matches everything; set criteria are combined with AND.
+#[derive(Debug, Clone, Default)]
+pub struct AnnotationFilter {
+    key_prefix: Option<String>,
+    keys: Vec<String>,
+    path_prefix: Option<String>,
+}
+
+impl AnnotationFilter {
+    /// Filter matching every annotation
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep keys starting with `prefix` (e.g. `x-`)
+    pub fn with_key_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.key_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Keep only `key`; may be given several times
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.keys.push(key.into());
+        self
+    }
+
+    /// Keep paths at or below `path`
+    pub fn under_path(mut self, path: impl Into<String>) -> Self {
+        self.path_prefix = Some(path.into());
+        self
+    }
+
+    /// Whether an annotation at `path` with `key` is selected
+    pub fn matches(&self, path: &str, key: &str) -> bool {
+        if let Some(prefix) = &self.key_prefix {
+            if !key.starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+        if !self.keys.is_empty() && !self.keys.iter().any(|k| k == key) {
+            return false;
+        }
+        match &self.path_prefix {
+            Some(prefix) => {
+                path == prefix
+                    || path
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with(['.', '[', '{']))
+            }
+            None => true,
+        }
+    }
+}
+
+/// Extract all annotations from `schema`
+pub fn extract(schema: &str, format: SerializationFormat) -> Result<AnnotationMap> {
+    let mut annotations = AnnotationMap::default();
+    match format {
+        SerializationFormat::JsonSchema => {
+            let value = parse_json(schema, format)?;
+            extract_json_schema(&value, "$", &mut annotations);
+            for defs_key in ["$defs", "definitions"] {
+                if let Some(defs) = value.get(defs_key).and_then(Value::as_object) {
+                    for (name, def) in defs {
+                        extract_json_schema(def, &format!("$defs.{}", name), &mut annotations);
+                    }
+                }
+            }
+        }
+        SerializationFormat::Avro => {
+            let value = parse_json(schema, format)?;
+            extract_avro(&value, "$", &mut HashSet::new(), &mut annotations);
+        }
+        SerializationFormat::Protobuf => extract_protobuf(schema, &mut annotations),
+    }
+    Ok(annotations)
+}
+
+fn parse_json(schema: &str, format: SerializationFormat) -> Result<Value> {
+    serde_json::from_str(schema)
+        .map_err(|e| Error::ParseError(format!("invalid {} schema: {}", format, e)))
+}
+
+fn extract_json_schema(schema: &Value, path: &str, out: &mut AnnotationMap) {
+    let Some(obj) = schema.as_object() else {
+        return;
+    };
+
+    for (key, value) in obj {
+        if key.starts_with("x-") {
+            out.insert(path, key, value.clone());
+        }
+    }
+    if let Some(description) = obj.get("description") {
+        out.insert(path, DOC_KEY, description.clone());
+    }
+
+    if let Some(properties) = obj.get("properties").and_then(Value::as_object) {
+        for (name, property) in properties {
+            extract_json_schema(property, &format!("{}.{}", path, name), out);
+        }
+    }
+    if let Some(items) = obj.get("items") {
+        extract_json_schema(items, &format!("{}[]", path), out);
+    }
+    if let Some(additional @ Value::Object(_)) = obj.get("additionalProperties") {
+        extract_json_schema(additional, &format!("{}{{}}", path), out);
+    }
+}
+
+fn extract_avro(schema: &Value, path: &str, seen: &mut HashSet<String>, out: &mut AnnotationMap) {
+    match schema {
+        Value::Array(branches) => {
+            for branch in branches {
+                extract_avro(branch, path, seen, out);
+            }
+        }
+        Value::Object(obj) => {
+            if let Some(name) = obj.get("name").and_then(Value::as_str) {
+                // Recursive or reused named types are annotated once
+                if !seen.insert(name.to_string()) {
+                    return;
+                }
+            }
+            collect_avro_attributes(obj, path, out);
+
+            if let Some(fields) = obj.get("fields").and_then(Value::as_array) {
+                for field in fields {
+                    let Some(field_obj) = field.as_object() else {
+                        continue;
+                    };
+                    let Some(name) = field_obj.get("name").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    let field_path = format!("{}.{}", path, name);
+                    collect_avro_attributes(field_obj, &field_path, out);
+                    if let Some(ty) = field_obj.get("type") {
+                        extract_avro(ty, &field_path, seen, out);
+                    }
+                }
+            }
+            if let Some(items) = obj.get("items") {
+                extract_avro(items, &format!("{}[]", path), seen, out);
+            }
+            if let Some(values) = obj.get("values") {
+                extract_avro(values, &format!("{}{{}}", path), seen, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_avro_attributes(obj: &Map<String, Value>, path: &str, out: &mut AnnotationMap) {
+    for (key, value) in obj {
+        if !AVRO_RESERVED.contains(&key.as_str()) {
+            out.insert(path, key, value.clone());
+        }
+    }
+    if let Some(doc) = obj.get("doc") {
+        out.insert(path, DOC_KEY, doc.clone());
+    }
+}
+
+fn extract_protobuf(schema: &str, out: &mut AnnotationMap) {
+    let mut scopes: Vec<String> = Vec::new();
+
+    for raw_line in schema.lines() {
+        let line = strip_line_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let path = match scopes.last() {
+            Some(scope) => scope.clone(),
+            None => "$".to_string(),
+        };
+
+        if let Some(rest) = line.strip_prefix("message ").or_else(|| line.strip_prefix("enum ")) {
+            let name = rest.trim_end_matches('{').trim();
+            scopes.push(format!("{}.{}", path, name));
+        } else if let Some(rest) = line.strip_prefix("option ") {
+            if let Some((key, value)) = parse_proto_option(rest.trim_end_matches(';')) {
+                out.insert(&path, &key, value);
+            }
+        } else if let (Some(open), Some(close)) = (line.find('['), line.rfind(']')) {
+            // Field declaration: `type name = N [opts];`
+            let name = line[..open]
+                .split('=')
+                .next()
+                .and_then(|decl| decl.split_whitespace().last());
+            if let Some(name) = name {
+                let field_path = format!("{}.{}", path, name);
+                for option in line[open + 1..close].split(',') {
+                    if let Some((key, value)) = parse_proto_option(option) {
+                        out.insert(&field_path, &key, value);
+                    }
+                }
+            }
+        }
+
+        let closes = line.matches('}').count().saturating_sub(line.matches('{').count());
+        for _ in 0..closes {
+            scopes.pop();
+        }
+    }
+}
+
+/// `line` up to its `//` comment, if any; `//` inside a string literal, as in a URL, is kept
+fn strip_line_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if line[i..].starts_with("//") => return &line[..i],
+            None => {}
+        }
+    }
+    line
+}
+
+/// Parse `(custom.option) = value`; built-in options without parentheses are skipped
+fn parse_proto_option(option: &str) -> Option<(String, Value)> {
+    let (key, value) = option.split_once('=')?;
+    let key = key.trim();
+    if !key.starts_with('(') {
+        return None;
+    }
+    let key = key.trim_start_matches('(').replacen(')', "", 1);
+    let value = value.trim();
+
+    let parsed = if let Some(s) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Value::String(s.to_string())
+    } else if let Ok(b) = value.parse::<bool>() {
+        Value::Bool(b)
+    } else if let Ok(n) = value.parse::<i64>() {
+        Value::from(n)
+    } else if let Ok(n) = value.parse::<f64>() {
+        Value::from(n)
+    } else {
+        Value::String(value.to_string())
+    };
+    Some((key, parsed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_schema_extensions_and_doc() {
+        let schema = r#"{
+            "type": "object",
+            "x-owner": "payments",
+            "description": "A payment",
+            "properties": {
+                "amount": {"type": "number", "x-go-type": "decimal.Decimal"},
+                "tags": {"type": "array", "items": {"type": "string", "x-max-tag": 5}}
+            },
+            "$defs": {"Money": {"type": "object", "x-go-type": "Money"}}
+        }"#;
+
+        let annotations = extract(schema, SerializationFormat::JsonSchema).unwrap();
+        assert_eq!(annotations.value("$", "x-owner"), Some(&json!("payments")));
+        assert_eq!(annotations.value("$", DOC_KEY), Some(&json!("A payment")));
+        assert_eq!(annotations.value("$.amount", "x-go-type"), Some(&json!("decimal.Decimal")));
+        assert_eq!(annotations.value("$.tags[]", "x-max-tag"), Some(&json!(5)));
+        assert_eq!(annotations.value("$defs.Money", "x-go-type"), Some(&json!("Money")));
+        assert_eq!(annotations.len(), 5);
+    }
+
+    #[test]
+    fn test_avro_custom_properties() {
+        let schema = r#"{
+            "type": "record",
+            "name": "User",
+            "doc": "A user",
+            "owner": "identity",
+            "fields": [
+                {"name": "id", "type": "string", "x-go-type": "uuid.UUID"},
+                {"name": "address", "type": {
+                    "type": "record", "name": "Address", "pii": true,
+                    "fields": [{"name": "zip", "type": "string", "doc": "Postal code"}]
+                }}
+            ]
+        }"#;
+
+        let annotations = extract(schema, SerializationFormat::Avro).unwrap();
+        assert_eq!(annotations.value("$", "owner"), Some(&json!("identity")));
+        assert_eq!(annotations.value("$", DOC_KEY), Some(&json!("A user")));
+        assert_eq!(annotations.value("$.id", "x-go-type"), Some(&json!("uuid.UUID")));
+        assert_eq!(annotations.value("$.address", "pii"), Some(&json!(true)));
+        assert_eq!(annotations.value("$.address.zip", DOC_KEY), Some(&json!("Postal code")));
+    }
+
+    #[test]
+    fn test_protobuf_custom_options() {
+        let schema = r#"
+            syntax = "proto3";
+            option (owner) = "billing";
+            message Invoice {
+                option (x.table) = "invoices";
+                string id = 1 [(x.go_type) = "InvoiceID", deprecated = true];
+                int64 total = 2; // no options
+            }
+        "#;
+
+        let annotations = extract(schema, SerializationFormat::Protobuf).unwrap();
+        assert_eq!(annotations.value("$", "owner"), Some(&json!("billing")));
+        assert_eq!(annotations.value("$.Invoice", "x.table"), Some(&json!("invoices")));
+        assert_eq!(annotations.value("$.Invoice.id", "x.go_type"), Some(&json!("InvoiceID")));
+        assert!(annotations.value("$.Invoice.id", "deprecated").is_none());
+        assert_eq!(annotations.len(), 3);
+    }
+
+    #[test]
+    fn test_protobuf_comment_markers_inside_strings() {
+        let schema = r#"
+            option (owner) = "https://example.com/teams/billing"; // team page
+            message Invoice {
+                string id = 1 [(x.doc) = "see http://docs/ids"]; // trailing
+                string note = 2 [(x.pattern) = "a\"//b"];
+            }
+        "#;
+
+        let annotations = extract(schema, SerializationFormat::Protobuf).unwrap();
+        assert_eq!(annotations.value("$", "owner"), Some(&json!("https://example.com/teams/billing")));
+        assert_eq!(annotations.value("$.Invoice.id", "x.doc"), Some(&json!("see http://docs/ids")));
+        assert_eq!(annotations.value("$.Invoice.note", "x.pattern"), Some(&json!("a\\\"//b")));
+    }
+
+    #[test]
+    fn test_filter_by_prefix_and_path() {
+        let schema = r#"{
+            "x-owner": "team",
+            "description": "root",
+            "properties": {
+                "a": {"x-go-type": "A", "properties": {"b": {"x-go-type": "B"}}},
+                "ab": {"x-go-type": "AB"}
+            }
+        }"#;
+        let annotations = extract(schema, SerializationFormat::JsonSchema).unwrap();
+
+        let extensions = annotations.filter(&AnnotationFilter::new().with_key_prefix("x-"));
+        assert_eq!(extensions.len(), 4);
+        assert!(extensions.value("$", DOC_KEY).is_none());
+
+        let under_a = annotations.filter(&AnnotationFilter::new().under_path("$.a"));
+        assert_eq!(under_a.len(), 2);
+        assert!(under_a.get("$.ab").is_none());
+
+        let json = serde_json::to_value(&under_a).unwrap();
+        assert_eq!(json["$.a.b"]["x-go-type"], "B");
+    }
+
+    #[test]
+    fn test_invalid_json_is_parse_error() {
+        let result = extract("{", SerializationFormat::Avro);
+        assert!(matches!(result, Err(Error::ParseError(_))));
+    }
+}
@@ -0,0 +1,61 @@
+//! Build information
+//!
+//! Exposes the crate version, the declared minimum supported Rust version
+//! (MSRV), and the rustc version the crate was compiled with. Building with a
+//! toolchain older than the MSRV fails with a clear error from this module.
+
+#[cfg(schema_registry_below_msrv)]
+compile_error!(concat!(
+    "schema-registry-core requires rustc ",
+    env!("CARGO_PKG_RUST_VERSION"),
+    " or newer (declared as `rust-version` in the workspace manifest)"
+));
+
+/// Crate version
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Declared minimum supported Rust version
+pub const MSRV: &str = env!("CARGO_PKG_RUST_VERSION");
+
+/// Version of rustc that compiled this crate ("unknown" if undetectable)
+pub const RUSTC_VERSION: &str = env!("SCHEMA_REGISTRY_RUSTC_VERSION");
+
+/// Snapshot of build information, suitable for serialization
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct BuildInfo {
+    /// Crate version
+    pub version: &'static str,
+    /// Declared minimum supported Rust version
+    pub msrv: &'static str,
+    /// Version of rustc used for the build
+    pub rustc_version: &'static str,
+}
+
+/// Get build information for this crate
+pub fn build_info() -> BuildInfo {
+    BuildInfo {
+        version: VERSION,
+        msrv: MSRV,
+        rustc_version: RUSTC_VERSION,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_msrv_is_declared() {
+        let mut parts = MSRV.split('.');
+        assert_eq!(parts.next(), Some("1"));
+        assert!(parts.next().and_then(|m| m.parse::<u32>().ok()).is_some());
+    }
+
+    #[test]
+    fn test_build_info() {
+        let info = build_info();
+        assert_eq!(info.version, VERSION);
+        assert_eq!(info.msrv, MSRV);
+        assert!(!info.rustc_version.is_empty());
+    }
+}
@@ -0,0 +1,738 @@
+//! Precomputed compatibility matrix
+//!
+//! For subjects under strict governance, UI and CI ask which historical
+//! versions are mutually compatible far more often than versions change. A
+//! [`CompatibilityMatrix`] answers from a precomputed table: for every
+//! subject and [`MatrixLevel`] it stores whether each version is compatible
+//! with every earlier one.
+//!
+//! The matrix follows registrations through [`matrix_events`]: give the
+//! publisher to
+//! [`EmbeddedRegistryBuilder::with_event_publisher`](crate::embedded::EmbeddedRegistryBuilder::with_event_publisher)
+//! and hand the receiving end to [`CompatibilityMatrix::spawn`]. The
+//! resulting [`MatrixMaintainer`] task adds a row per new version, checking
+//! it only against the existing versions. Checks are memoized by content
+//! hash, so recomputing a subject doesn't repeat checks of unchanged content.
+//!
+//! With a file path the matrix is saved after every update, with a
+//! fingerprint of each subject's versions. [`CompatibilityMatrix::open`]
+//! recomputes every subject whose saved fingerprint doesn't match storage,
+//! so a matrix left stale by a crash or an out-of-band change is repaired on
+//! startup. [`MatrixLagCheck`] reports events not yet applied.
+
+use async_trait::async_trait;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::doctor::{CheckOutcome, DiagnosticCheck, DiagnosticContext};
+use crate::error::{Error, Result};
+use crate::events::{EventPayload, SchemaEvent};
+use crate::pagination::{self, PageRequest};
+use crate::registration_log;
+use crate::schema::RegisteredSchema;
+use crate::traits::{CompatibilityChecker, EventPublisher, SchemaStorage};
+use crate::types::CompatibilityMode;
+use crate::versioning::SemanticVersion;
+
+/// Format version of the saved matrix file
+pub const MATRIX_FILE_VERSION: u32 = 1;
+
+/// Id of [`MatrixLagCheck`]
+pub const MATRIX_LAG_CHECK_ID: &str = "compat-matrix-lag";
+
+/// Compatibility level the matrix is kept for
+///
+/// Transitive modes need no level of their own: a version is transitively
+/// compatible when its whole row is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MatrixLevel {
+    Backward,
+    Forward,
+    Full,
+}
+
+impl MatrixLevel {
+    pub const ALL: [MatrixLevel; 3] = [MatrixLevel::Backward, MatrixLevel::Forward, MatrixLevel::Full];
+
+    /// Mode the checker is asked for
+    pub fn mode(self) -> CompatibilityMode {
+        match self {
+            MatrixLevel::Backward => CompatibilityMode::Backward,
+            MatrixLevel::Forward => CompatibilityMode::Forward,
+            MatrixLevel::Full => CompatibilityMode::Full,
+        }
+    }
+}
+
+impl fmt::Display for MatrixLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MatrixLevel::Backward => "backward",
+            MatrixLevel::Forward => "forward",
+            MatrixLevel::Full => "full",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for MatrixLevel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        MatrixLevel::ALL
+            .into_iter()
+            .find(|level| level.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                Error::ValidationError(format!(
+                    "unknown compatibility level '{}' (expected backward, forward or full)",
+                    s
+                ))
+            })
+    }
+}
+
+/// Compatibility of every pair of versions of one subject
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SubjectMatrix {
+    /// [`registration_log::fingerprint`] of the versions the matrix was computed from
+    pub fingerprint: String,
+    /// Versions, oldest first
+    pub versions: Vec<SemanticVersion>,
+    /// Content hash of each version, to tell which rows still apply after a change
+    content_hashes: Vec<String>,
+    /// Per level, row `i` holds whether version `i` is compatible with versions `0..i`
+    rows: BTreeMap<MatrixLevel, Vec<Vec<bool>>>,
+}
+
+impl SubjectMatrix {
+    /// Lower triangle at `level`: row `i` compares version `i` with each earlier version
+    pub fn rows(&self, level: MatrixLevel) -> &[Vec<bool>] {
+        self.rows.get(&level).map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether the later of `a` and `b` is compatible with the earlier one
+    ///
+    /// `None` if either version isn't in the matrix. A version is compatible
+    /// with itself.
+    pub fn is_compatible(&self, a: &SemanticVersion, b: &SemanticVersion, level: MatrixLevel) -> Option<bool> {
+        let a = self.versions.iter().position(|version| version == a)?;
+        let b = self.versions.iter().position(|version| version == b)?;
+        if a == b {
+            return Some(true);
+        }
+        let (newer, older) = (a.max(b), a.min(b));
+        self.rows(level).get(newer)?.get(older).copied()
+    }
+}
+
+/// One level of a subject's matrix, as returned by [`CompatibilityMatrix::matrix`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MatrixView {
+    pub subject: String,
+    pub level: MatrixLevel,
+    /// Versions, oldest first
+    pub versions: Vec<SemanticVersion>,
+    /// Row `i` holds whether version `i` is compatible with versions `0..i`
+    pub rows: Vec<Vec<bool>>,
+}
+
+impl MatrixView {
+    fn of(subject: &str, matrix: &SubjectMatrix, level: MatrixLevel) -> Self {
+        Self {
+            subject: subject.to_string(),
+            level,
+            versions: matrix.versions.clone(),
+            rows: matrix.rows(level).to_vec(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct MatrixFile {
+    format_version: u32,
+    subjects: BTreeMap<String, SubjectMatrix>,
+}
+
+/// Read a saved matrix file, keyed by subject
+pub fn load_matrix_file(path: &Path) -> Result<BTreeMap<String, SubjectMatrix>> {
+    let file: MatrixFile = serde_json::from_slice(&std::fs::read(path)?)?;
+    if file.format_version > MATRIX_FILE_VERSION {
+        return Err(Error::SerializationError(format!(
+            "{} has matrix format version {}, newer than the supported {}",
+            path.display(),
+            file.format_version,
+            MATRIX_FILE_VERSION
+        )));
+    }
+    Ok(file.subjects)
+}
+
+fn save_matrix_file(path: &Path, subjects: BTreeMap<String, SubjectMatrix>) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = MatrixFile {
+        format_version: MATRIX_FILE_VERSION,
+        subjects,
+    };
+    // Write then rename, so a crash never leaves a half-written matrix
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(&file)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Checker results cached by the content hashes of both schemas
+struct MemoizedChecker {
+    inner: Arc<dyn CompatibilityChecker>,
+    results: Mutex<HashMap<(String, String, MatrixLevel), bool>>,
+}
+
+impl MemoizedChecker {
+    async fn check(&self, newer: &RegisteredSchema, older: &RegisteredSchema, level: MatrixLevel) -> Result<bool> {
+        let key = (newer.content_hash.clone(), older.content_hash.clone(), level);
+        let cached = self.results.lock().get(&key).copied();
+        if let Some(compatible) = cached {
+            return Ok(compatible);
+        }
+        let compatible = self
+            .inner
+            .check_compatibility(newer, older, level.mode())
+            .await?
+            .is_compatible;
+        self.results.lock().insert(key, compatible);
+        Ok(compatible)
+    }
+}
+
+/// Subjects [`CompatibilityMatrix::repair`] recomputed or dropped
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepairReport {
+    /// Subjects whose saved matrix was missing or didn't match storage
+    pub repaired: Vec<String>,
+    /// Saved subjects no longer in storage
+    pub removed: Vec<String>,
+}
+
+/// Compatibility matrices of every subject in a storage
+///
+/// Cheap to clone; clones share the same matrices.
+#[derive(Clone)]
+pub struct CompatibilityMatrix {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    storage: Arc<dyn SchemaStorage>,
+    checker: MemoizedChecker,
+    subjects: RwLock<BTreeMap<String, SubjectMatrix>>,
+    path: Option<PathBuf>,
+    /// Held while a subject is recomputed, so updates apply in order
+    updates: tokio::sync::Mutex<()>,
+}
+
+impl CompatibilityMatrix {
+    /// Load the matrix saved at `path` (if any) and repair it against `storage`
+    ///
+    /// An unreadable matrix file is treated as empty and rebuilt.
+    pub async fn open(
+        storage: Arc<dyn SchemaStorage>,
+        checker: Arc<dyn CompatibilityChecker>,
+        path: Option<PathBuf>,
+    ) -> Result<(Self, RepairReport)> {
+        let saved = match &path {
+            Some(path) if path.exists() => load_matrix_file(path).unwrap_or_else(|err| {
+                tracing::warn!("rebuilding compatibility matrix: cannot read {}: {}", path.display(), err);
+                BTreeMap::new()
+            }),
+            _ => BTreeMap::new(),
+        };
+        let matrix = Self {
+            inner: Arc::new(Inner {
+                storage,
+                checker: MemoizedChecker {
+                    inner: checker,
+                    results: Mutex::new(HashMap::new()),
+                },
+                subjects: RwLock::new(saved),
+                path,
+                updates: tokio::sync::Mutex::new(()),
+            }),
+        };
+        let report = matrix.repair().await?;
+        Ok((matrix, report))
+    }
+
+    /// Recompute subjects whose fingerprint doesn't match storage and drop deleted ones
+    pub async fn repair(&self) -> Result<RepairReport> {
+        let _updates = self.inner.updates.lock().await;
+        let subjects = pagination::collect_all(PageRequest::new(pagination::MAX_PAGE_LIMIT), |page| async move {
+            self.inner.storage.list_subjects(&page).await
+        })
+        .await?;
+
+        let mut report = RepairReport::default();
+        for subject in &subjects {
+            let versions = self.versions_of(subject).await?;
+            if !self.is_current(subject, &versions) {
+                self.apply(subject, versions).await?;
+                report.repaired.push(subject.clone());
+            }
+        }
+        {
+            let mut saved = self.inner.subjects.write();
+            saved.retain(|subject, _| {
+                let keep = subjects.contains(subject);
+                if !keep {
+                    report.removed.push(subject.clone());
+                }
+                keep
+            });
+        }
+
+        if !report.repaired.is_empty() || !report.removed.is_empty() {
+            self.save()?;
+        }
+        Ok(report)
+    }
+
+    /// Bring `subject` up to date with storage
+    pub async fn update(&self, subject: &str) -> Result<()> {
+        let _updates = self.inner.updates.lock().await;
+        let versions = self.versions_of(subject).await?;
+        if self.is_current(subject, &versions) {
+            return Ok(());
+        }
+        if versions.is_empty() {
+            self.inner.subjects.write().remove(subject);
+        } else {
+            self.apply(subject, versions).await?;
+        }
+        self.save()
+    }
+
+    /// One level of `subject`'s matrix
+    pub fn matrix(&self, subject: &str, level: MatrixLevel) -> Option<MatrixView> {
+        self.inner
+            .subjects
+            .read()
+            .get(subject)
+            .map(|matrix| MatrixView::of(subject, matrix, level))
+    }
+
+    /// Whether the later of versions `a` and `b` of `subject` is compatible with the earlier one
+    pub fn is_compatible(
+        &self,
+        subject: &str,
+        a: &SemanticVersion,
+        b: &SemanticVersion,
+        level: MatrixLevel,
+    ) -> Option<bool> {
+        self.inner.subjects.read().get(subject)?.is_compatible(a, b, level)
+    }
+
+    /// Subjects with a matrix, in order
+    pub fn subjects(&self) -> Vec<String> {
+        self.inner.subjects.read().keys().cloned().collect()
+    }
+
+    /// Apply registration events from `events` in a background task
+    pub fn spawn(&self, events: MatrixEvents) -> MatrixMaintainer {
+        let MatrixEvents { mut receiver, pending } = events;
+        let matrix = self.clone();
+        let task_pending = Arc::clone(&pending);
+        let task = tokio::spawn(async move {
+            while let Some(subject) = receiver.recv().await {
+                if let Err(err) = matrix.update(&subject).await {
+                    tracing::warn!("failed to update compatibility matrix of {}: {}", subject, err);
+                }
+                task_pending.fetch_sub(1, Ordering::SeqCst);
+            }
+        });
+        MatrixMaintainer { pending, task }
+    }
+
+    fn is_current(&self, subject: &str, versions: &[RegisteredSchema]) -> bool {
+        let fingerprint = registration_log::fingerprint(versions);
+        self.inner
+            .subjects
+            .read()
+            .get(subject)
+            .is_some_and(|matrix| matrix.fingerprint == fingerprint)
+    }
+
+    async fn versions_of(&self, subject: &str) -> Result<Vec<RegisteredSchema>> {
+        let (namespace, name) = subject
+            .rsplit_once('.')
+            .ok_or_else(|| Error::ValidationError(format!("subject '{}' is not of the form namespace.name", subject)))?;
+        let mut versions = self.inner.storage.find_by_name(namespace, name).await?;
+        versions.sort_by(|a, b| a.version.cmp(&b.version));
+        Ok(versions)
+    }
+
+    /// Recompute `subject`, keeping rows of the unchanged leading versions
+    async fn apply(&self, subject: &str, versions: Vec<RegisteredSchema>) -> Result<()> {
+        let previous = self.inner.subjects.read().get(subject).cloned().unwrap_or_default();
+        let unchanged = previous
+            .versions
+            .iter()
+            .zip(&previous.content_hashes)
+            .zip(&versions)
+            .take_while(|((version, hash), schema)| **version == schema.version && **hash == schema.content_hash)
+            .count();
+
+        let mut rows = BTreeMap::new();
+        for level in MatrixLevel::ALL {
+            let mut level_rows: Vec<Vec<bool>> = previous
+                .rows(level)
+                .iter()
+                .take(unchanged)
+                .enumerate()
+                .take_while(|(i, row)| row.len() == *i)
+                .map(|(_, row)| row.clone())
+                .collect();
+            for i in level_rows.len()..versions.len() {
+                let mut row = Vec::with_capacity(i);
+                for older in &versions[..i] {
+                    row.push(self.inner.checker.check(&versions[i], older, level).await?);
+                }
+                level_rows.push(row);
+            }
+            rows.insert(level, level_rows);
+        }
+
+        let matrix = SubjectMatrix {
+            fingerprint: registration_log::fingerprint(&versions),
+            versions: versions.iter().map(|schema| schema.version.clone()).collect(),
+            content_hashes: versions.iter().map(|schema| schema.content_hash.clone()).collect(),
+            rows,
+        };
+        self.inner.subjects.write().insert(subject.to_string(), matrix);
+        Ok(())
+    }
+
+    fn save(&self) -> Result<()> {
+        match &self.inner.path {
+            Some(path) => save_matrix_file(path, self.inner.subjects.read().clone()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Publisher and receiver connecting a registry to a [`MatrixMaintainer`]
+pub fn matrix_events() -> (Arc<MatrixEventPublisher>, MatrixEvents) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let pending = Arc::new(AtomicUsize::new(0));
+    (
+        Arc::new(MatrixEventPublisher {
+            sender,
+            pending: Arc::clone(&pending),
+        }),
+        MatrixEvents { receiver, pending },
+    )
+}
+
+/// Queues the subject of every `SchemaRegistered` event for the maintainer
+pub struct MatrixEventPublisher {
+    sender: mpsc::UnboundedSender<String>,
+    pending: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl EventPublisher for MatrixEventPublisher {
+    async fn publish(&self, event: SchemaEvent) -> Result<()> {
+        let EventPayload::SchemaRegistered {
+            schema_name, namespace, ..
+        } = event.payload
+        else {
+            return Ok(());
+        };
+        self.pending.fetch_add(1, Ordering::SeqCst);
+        if self.sender.send(format!("{}.{}", namespace, schema_name)).is_err() {
+            self.pending.fetch_sub(1, Ordering::SeqCst);
+            return Err(Error::EventPublishError(
+                "compatibility matrix maintainer has stopped".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn publish_batch(&self, events: Vec<SchemaEvent>) -> Result<()> {
+        for event in events {
+            self.publish(event).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Receiving end of [`matrix_events`]
+pub struct MatrixEvents {
+    receiver: mpsc::UnboundedReceiver<String>,
+    pending: Arc<AtomicUsize>,
+}
+
+impl MatrixEvents {
+    /// Events published but not yet applied
+    pub fn pending(&self) -> usize {
+        self.pending.load(Ordering::SeqCst)
+    }
+
+    /// Health check reporting this channel's backlog
+    pub fn lag_check(&self, warn_after: usize) -> MatrixLagCheck {
+        MatrixLagCheck {
+            pending: Arc::clone(&self.pending),
+            warn_after,
+        }
+    }
+}
+
+/// Background task applying registration events to a [`CompatibilityMatrix`]
+pub struct MatrixMaintainer {
+    pending: Arc<AtomicUsize>,
+    task: JoinHandle<()>,
+}
+
+impl MatrixMaintainer {
+    /// Events published but not yet applied
+    pub fn pending(&self) -> usize {
+        self.pending.load(Ordering::SeqCst)
+    }
+
+    /// Health check warning when more than `warn_after` events are pending
+    pub fn lag_check(&self, warn_after: usize) -> MatrixLagCheck {
+        MatrixLagCheck {
+            pending: Arc::clone(&self.pending),
+            warn_after,
+        }
+    }
+
+    /// Wait until every event published so far has been applied
+    pub async fn settled(&self) {
+        while self.pending() > 0 && !self.task.is_finished() {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    }
+
+    /// Stop applying events; later publishes fail
+    pub async fn stop(self) {
+        self.task.abort();
+        let _ = self.task.await;
+    }
+}
+
+/// Doctor check reporting registration events the matrix hasn't applied yet
+pub struct MatrixLagCheck {
+    pending: Arc<AtomicUsize>,
+    warn_after: usize,
+}
+
+impl DiagnosticCheck for MatrixLagCheck {
+    fn id(&self) -> &str {
+        MATRIX_LAG_CHECK_ID
+    }
+
+    fn description(&self) -> &str {
+        "Compatibility matrix keeps up with registrations"
+    }
+
+    fn run(&self, _ctx: &DiagnosticContext) -> CheckOutcome {
+        match self.pending.load(Ordering::SeqCst) {
+            0 => CheckOutcome::pass("compatibility matrix is up to date"),
+            pending if pending <= self.warn_after => {
+                CheckOutcome::pass(format!("{} registration event(s) pending", pending))
+            }
+            pending => CheckOutcome::warn(
+                format!(
+                    "{} registration events pending in the compatibility matrix (more than {})",
+                    pending, self.warn_after
+                ),
+                "The matrix maintainer is falling behind; check for slow compatibility checks or a stopped maintainer task",
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doctor::CheckStatus;
+    use crate::embedded::EmbeddedRegistry;
+    use crate::traits::CompatibilityResult;
+    use crate::{SchemaInput, SerializationFormat};
+    use uuid::Uuid;
+
+    const SUBJECT: &str = "com.example.orders";
+
+    /// Backward-incompatible when the newer schema mentions "breaking"; counts calls
+    #[derive(Default)]
+    struct MarkerChecker {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl CompatibilityChecker for MarkerChecker {
+        async fn check_compatibility(
+            &self,
+            new_schema: &RegisteredSchema,
+            _old_schema: &RegisteredSchema,
+            mode: CompatibilityMode,
+        ) -> Result<CompatibilityResult> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let breaking = new_schema.content.contains("breaking");
+            Ok(CompatibilityResult {
+                is_compatible: !(breaking && matches!(mode, CompatibilityMode::Backward | CompatibilityMode::Full)),
+                mode,
+                violations: Vec::new(),
+                checked_versions: Vec::new(),
+            })
+        }
+
+        async fn check_transitive_compatibility(
+            &self,
+            new_schema: &RegisteredSchema,
+            previous_versions: &[RegisteredSchema],
+            mode: CompatibilityMode,
+        ) -> Result<CompatibilityResult> {
+            self.check_compatibility(new_schema, &previous_versions[0], mode).await
+        }
+    }
+
+    fn schema(description: &str) -> SchemaInput {
+        let (namespace, name) = SUBJECT.rsplit_once('.').unwrap();
+        SchemaInput::new(
+            namespace,
+            name,
+            SerializationFormat::JsonSchema,
+            format!(r#"{{"type": "object", "description": "{}"}}"#, description),
+        )
+    }
+
+    async fn version_list(registry: &EmbeddedRegistry) -> Vec<SemanticVersion> {
+        let (namespace, name) = SUBJECT.rsplit_once('.').unwrap();
+        let versions = registry.versions(namespace, name).await.unwrap();
+        versions.into_iter().map(|schema| schema.version).collect()
+    }
+
+    fn temp_matrix(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("compat-matrix-{}-{}", name, Uuid::new_v4()));
+        dir.join("matrix.json")
+    }
+
+    #[tokio::test]
+    async fn test_maintainer_adds_a_row_per_registration() {
+        let (publisher, events) = matrix_events();
+        let registry = EmbeddedRegistry::builder().with_event_publisher(publisher).build();
+        let checker = Arc::new(MarkerChecker::default());
+        let (matrix, report) = CompatibilityMatrix::open(registry.storage(), checker.clone(), None)
+            .await
+            .unwrap();
+        assert_eq!(report, RepairReport::default());
+        let maintainer = matrix.spawn(events);
+
+        for description in ["v1", "v2", "breaking v3"] {
+            registry.register(schema(description)).await.unwrap();
+        }
+        maintainer.settled().await;
+        assert_eq!(maintainer.pending(), 0);
+
+        let versions = version_list(&registry).await;
+        let backward = matrix.matrix(SUBJECT, MatrixLevel::Backward).unwrap();
+        assert_eq!(backward.versions, versions);
+        assert_eq!(backward.rows, vec![vec![], vec![true], vec![false, false]]);
+        assert_eq!(matrix.is_compatible(SUBJECT, &versions[0], &versions[2], MatrixLevel::Forward), Some(true));
+        assert_eq!(matrix.is_compatible(SUBJECT, &versions[2], &versions[0], MatrixLevel::Full), Some(false));
+        assert_eq!(matrix.is_compatible(SUBJECT, &versions[1], &versions[1], MatrixLevel::Full), Some(true));
+
+        // Each new version is only checked against the existing ones: 0 + 1 + 2 pairs per level
+        assert_eq!(checker.calls.load(Ordering::SeqCst), 3 * MatrixLevel::ALL.len());
+    }
+
+    #[tokio::test]
+    async fn test_open_repairs_a_stale_saved_matrix() {
+        let path = temp_matrix("repair");
+        let (publisher, events) = matrix_events();
+        let registry = EmbeddedRegistry::builder().with_event_publisher(publisher).build();
+        let checker = Arc::new(MarkerChecker::default());
+        let (matrix, _) = CompatibilityMatrix::open(registry.storage(), checker.clone(), Some(path.clone()))
+            .await
+            .unwrap();
+        let maintainer = matrix.spawn(events);
+        registry.register(schema("v1")).await.unwrap();
+        registry.register(schema("v2")).await.unwrap();
+        maintainer.settled().await;
+        maintainer.stop().await;
+
+        // Reopening an up-to-date matrix recomputes nothing
+        let (_, report) = CompatibilityMatrix::open(registry.storage(), checker.clone(), Some(path.clone()))
+            .await
+            .unwrap();
+        assert!(report.repaired.is_empty());
+
+        // A version stored while no maintainer was running leaves the saved matrix stale
+        registry.register(schema("breaking v3")).await.unwrap();
+        let calls_before = checker.calls.load(Ordering::SeqCst);
+        let (reopened, report) = CompatibilityMatrix::open(registry.storage(), checker.clone(), Some(path.clone()))
+            .await
+            .unwrap();
+        assert_eq!(report.repaired, vec![SUBJECT.to_string()]);
+        assert_eq!(checker.calls.load(Ordering::SeqCst) - calls_before, 2 * MatrixLevel::ALL.len());
+
+        let versions = version_list(&registry).await;
+        assert_eq!(
+            reopened.is_compatible(SUBJECT, &versions[2], &versions[1], MatrixLevel::Backward),
+            Some(false)
+        );
+        assert_eq!(load_matrix_file(&path).unwrap()[SUBJECT].versions, versions);
+
+        // An unreadable file is rebuilt from storage
+        std::fs::write(&path, "not json").unwrap();
+        let (_, report) = CompatibilityMatrix::open(registry.storage(), checker, Some(path.clone()))
+            .await
+            .unwrap();
+        assert_eq!(report.repaired, vec![SUBJECT.to_string()]);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_lag_check_reports_pending_events() {
+        let (publisher, events) = matrix_events();
+        let registry = EmbeddedRegistry::builder().with_event_publisher(publisher).build();
+        let check = events.lag_check(1);
+        let ctx = DiagnosticContext::default();
+        assert_eq!(check.run(&ctx).status, CheckStatus::Pass);
+
+        registry.register(schema("v1")).await.unwrap();
+        assert_eq!(events.pending(), 1);
+        assert_eq!(check.run(&ctx).status, CheckStatus::Pass);
+
+        registry.register(schema("v2")).await.unwrap();
+        let outcome = check.run(&ctx);
+        assert_eq!(outcome.status, CheckStatus::Warn);
+        assert!(outcome.message.starts_with("2 registration events pending"));
+
+        let (matrix, _) = CompatibilityMatrix::open(registry.storage(), Arc::new(MarkerChecker::default()), None)
+            .await
+            .unwrap();
+        let maintainer = matrix.spawn(events);
+        maintainer.settled().await;
+        assert_eq!(check.run(&ctx).status, CheckStatus::Pass);
+        assert_eq!(matrix.matrix(SUBJECT, MatrixLevel::Full).unwrap().rows.len(), 2);
+    }
+
+    #[test]
+    fn test_level_parsing() {
+        assert_eq!("FULL".parse::<MatrixLevel>().unwrap(), MatrixLevel::Full);
+        assert_eq!("backward".parse::<MatrixLevel>().unwrap(), MatrixLevel::Backward);
+        assert!("backward_transitive".parse::<MatrixLevel>().is_err());
+    }
+}
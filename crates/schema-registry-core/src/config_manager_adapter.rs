@@ -14,12 +14,34 @@
 //! 1. **Startup Configuration**: Load global settings at server initialization
 //! 2. **Schema Policies**: Ingest validation rules and policy definitions
 //! 3. **Runtime Refresh**: Optional hooks for live configuration updates
-
+//!
+//! On wasm32 only the configuration and policy types are available; the
+//! Config Manager itself, and with it `ConfigManagerAdapter`, only builds natively.
+//!
+//! # Sync and async consumers
+//!
+//! [`AsyncConfigConsumer`] is the primary interface; the refresh manager and
+//! startup loaders are generic over it. Existing [`ConfigConsumer`]
+//! implementations keep working through [`BlockingConsumer`], which runs them
+//! on the blocking thread pool.
+//!
+//! The sync trait is deprecated on the following timeline:
+//!
+//! - 0.1: documented as deprecated; both traits are supported
+//! - 0.2: marked `#[deprecated]`
+//! - 0.3: removed, together with [`BlockingConsumer`]
+
+use crate::pagination::glob_match;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+#[cfg(not(target_arch = "wasm32"))]
 use llm_config_core::{ConfigManager, Environment, ConfigValue, Result as ConfigResult};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
 use tracing::{info, debug};
 
 // ============================================================================
@@ -27,6 +49,11 @@ use tracing::{info, debug};
 // ============================================================================
 
 /// Trait for consuming configuration from an upstream config source
+///
+/// Deprecated in favour of [`AsyncConfigConsumer`], see the
+/// [module docs](self#sync-and-async-consumers) for the removal timeline.
+/// Wrap existing implementations in [`BlockingConsumer`] to use them where an
+/// async consumer is expected.
 pub trait ConfigConsumer: Send + Sync {
     /// Load global configuration for the schema registry
     fn load_global_config(&self) -> Result<GlobalConfig, ConfigError>;
@@ -38,6 +65,60 @@ pub trait ConfigConsumer: Send + Sync {
     fn refresh(&self) -> Result<(), ConfigError>;
 }
 
+/// Async trait for consuming configuration from an upstream config source
+#[async_trait]
+pub trait AsyncConfigConsumer: Send + Sync {
+    /// Load global configuration for the schema registry
+    async fn load_global_config(&self) -> Result<GlobalConfig, ConfigError>;
+
+    /// Load schema validation policies
+    async fn load_schema_policies(&self) -> Result<SchemaPolicies, ConfigError>;
+
+    /// Refresh configuration (for runtime updates)
+    async fn refresh(&self) -> Result<(), ConfigError>;
+}
+
+/// [`AsyncConfigConsumer`] over a synchronous [`ConfigConsumer`]
+///
+/// Each call runs on the blocking thread pool, so slow sync consumers don't
+/// stall the runtime.
+pub struct BlockingConsumer<C: ?Sized = dyn ConfigConsumer> {
+    inner: Arc<C>,
+}
+
+impl<C: ConfigConsumer + ?Sized + 'static> BlockingConsumer<C> {
+    pub fn new(inner: Arc<C>) -> Self {
+        Self { inner }
+    }
+
+    /// The wrapped sync consumer
+    pub fn inner(&self) -> &Arc<C> {
+        &self.inner
+    }
+
+    async fn run<T: Send + 'static>(&self, load: fn(&C) -> Result<T, ConfigError>) -> Result<T, ConfigError> {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || load(&inner))
+            .await
+            .map_err(|e| ConfigError::ConfigManager(format!("Config load task failed: {}", e)))?
+    }
+}
+
+#[async_trait]
+impl<C: ConfigConsumer + ?Sized + 'static> AsyncConfigConsumer for BlockingConsumer<C> {
+    async fn load_global_config(&self) -> Result<GlobalConfig, ConfigError> {
+        self.run(C::load_global_config).await
+    }
+
+    async fn load_schema_policies(&self) -> Result<SchemaPolicies, ConfigError> {
+        self.run(C::load_schema_policies).await
+    }
+
+    async fn refresh(&self) -> Result<(), ConfigError> {
+        self.run(C::refresh).await
+    }
+}
+
 /// Trait for receiving configuration update notifications
 pub trait ConfigUpdateListener: Send + Sync {
     /// Called when configuration is updated
@@ -45,6 +126,14 @@ pub trait ConfigUpdateListener: Send + Sync {
 
     /// Called when policies are updated
     fn on_policies_updated(&self, policies: &SchemaPolicies);
+
+    /// Called after a refresh applied some sections and kept the previous
+    /// values of others, in addition to the two calls above
+    ///
+    /// The default ignores it.
+    fn on_partial_refresh(&self, refresh: &crate::config_refresh::PartialRefresh) {
+        let _ = refresh;
+    }
 }
 
 // ============================================================================
@@ -109,6 +198,24 @@ impl Default for ServerConfig {
     }
 }
 
+impl ServerConfig {
+    /// Reject settings the server cannot start with
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.host.trim().is_empty() {
+            return Err(ConfigError::InvalidConfig("server.host must not be empty".to_string()));
+        }
+        if self.port == 0 {
+            return Err(ConfigError::InvalidConfig("server.port must not be 0".to_string()));
+        }
+        if self.max_request_size == 0 || self.timeout_seconds == 0 {
+            return Err(ConfigError::InvalidConfig(
+                "server.max_request_size and server.timeout_seconds must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Storage-specific configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageConfig {
@@ -120,6 +227,70 @@ pub struct StorageConfig {
 
     /// Enable compression
     pub enable_compression: bool,
+
+    /// Codec for compressed schema blobs (`none`, `gzip` or `zstd`)
+    #[serde(default = "default_compression_codec")]
+    pub compression_codec: String,
+
+    /// Schema blobs smaller than this many bytes are stored uncompressed
+    #[serde(default = "default_compression_min_size")]
+    pub compression_min_size: usize,
+
+    /// Derive each subject's cache TTL from how often it is written, within
+    /// `cache_min_ttl_seconds..=cache_max_ttl_seconds`, instead of using
+    /// `cache_ttl_seconds` for every entry
+    #[serde(default)]
+    pub adaptive_cache_ttl: bool,
+
+    /// Shortest TTL the adaptive cache assigns, in seconds
+    #[serde(default = "default_cache_min_ttl_seconds")]
+    pub cache_min_ttl_seconds: u64,
+
+    /// Longest TTL the adaptive cache assigns, in seconds
+    #[serde(default = "default_cache_max_ttl_seconds")]
+    pub cache_max_ttl_seconds: u64,
+}
+
+fn default_compression_codec() -> String {
+    "gzip".to_string()
+}
+
+fn default_compression_min_size() -> usize {
+    1024
+}
+
+fn default_cache_min_ttl_seconds() -> u64 {
+    5
+}
+
+fn default_cache_max_ttl_seconds() -> u64 {
+    3600
+}
+
+impl StorageConfig {
+    /// Codecs `compression_codec` may name
+    pub const CODECS: &'static [&'static str] = &["none", "gzip", "zstd"];
+
+    /// Reject settings storage cannot run with
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.pool_size == 0 {
+            return Err(ConfigError::InvalidConfig("storage.pool_size must be at least 1".to_string()));
+        }
+        if !Self::CODECS.contains(&self.compression_codec.as_str()) {
+            return Err(ConfigError::InvalidConfig(format!(
+                "storage.compression_codec `{}` is not one of {}",
+                self.compression_codec,
+                Self::CODECS.join(", ")
+            )));
+        }
+        if self.cache_min_ttl_seconds > self.cache_max_ttl_seconds {
+            return Err(ConfigError::InvalidConfig(format!(
+                "storage.cache_min_ttl_seconds ({}) exceeds storage.cache_max_ttl_seconds ({})",
+                self.cache_min_ttl_seconds, self.cache_max_ttl_seconds
+            )));
+        }
+        Ok(())
+    }
 }
 
 impl Default for StorageConfig {
@@ -128,6 +299,11 @@ impl Default for StorageConfig {
             pool_size: 10,
             cache_ttl_seconds: 300,
             enable_compression: true,
+            compression_codec: default_compression_codec(),
+            compression_min_size: default_compression_min_size(),
+            adaptive_cache_ttl: false,
+            cache_min_ttl_seconds: default_cache_min_ttl_seconds(),
+            cache_max_ttl_seconds: default_cache_max_ttl_seconds(),
         }
     }
 }
@@ -148,6 +324,16 @@ pub struct ValidationConfig {
     pub security_checks: bool,
 }
 
+impl ValidationConfig {
+    /// Reject settings that would refuse every schema
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.max_schema_size == 0 {
+            return Err(ConfigError::InvalidConfig("validation.max_schema_size must be greater than 0".to_string()));
+        }
+        Ok(())
+    }
+}
+
 impl Default for ValidationConfig {
     fn default() -> Self {
         Self {
@@ -172,6 +358,16 @@ pub struct SecurityConfig {
     pub rate_limit_rps: u32,
 }
 
+impl SecurityConfig {
+    /// Reject settings that would refuse every request
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.rate_limit_rps == 0 {
+            return Err(ConfigError::InvalidConfig("security.rate_limit_rps must be greater than 0".to_string()));
+        }
+        Ok(())
+    }
+}
+
 impl Default for SecurityConfig {
     fn default() -> Self {
         Self {
@@ -191,11 +387,76 @@ pub struct SchemaPolicies {
     /// Type restriction policies
     pub type_restrictions: Vec<String>,
 
-    /// Required metadata fields
+    /// Metadata keys every registered schema must carry in its registration metadata
     pub required_metadata: Vec<String>,
 
+    /// Annotations the schema root must carry, e.g. `x-owner`
+    #[serde(default)]
+    pub required_annotations: Vec<String>,
+
+    /// Declared JSON types of annotation values, keyed by annotation name
+    /// (`string`, `number`, `integer`, `boolean`, `array`, `object`)
+    #[serde(default)]
+    pub annotation_types: HashMap<String, String>,
+
+    /// Content policy for documentation strings
+    #[serde(default)]
+    pub documentation: DocumentationPolicy,
+
+    /// Style lint checks for descriptions, examples, titles and enums
+    #[serde(default)]
+    pub style: StylePolicy,
+
     /// Custom validation rules
     pub custom_rules: Vec<CustomPolicyRule>,
+
+    /// Time-limited exemptions of subjects from specific rules
+    #[serde(default)]
+    pub waivers: Vec<PolicyWaiver>,
+}
+
+/// Rule ids reported by the built-in policy checks
+///
+/// Custom rules report `custom-policy-<name>`.
+pub const POLICY_RULE_IDS: &[&str] = &[
+    "field-naming-policy",
+    "metadata-policy",
+    "documentation-policy",
+    "style-description",
+    "style-example",
+    "style-title-case",
+    "style-enum-docs",
+];
+
+/// Values `SchemaPolicies::annotation_types` may declare
+pub const ANNOTATION_TYPES: &[&str] = &["string", "number", "integer", "boolean", "array", "object"];
+
+impl SchemaPolicies {
+    /// Reject policies the checks cannot apply
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if let Some((name, declared)) = self
+            .annotation_types
+            .iter()
+            .find(|(_, declared)| !ANNOTATION_TYPES.contains(&declared.as_str()))
+        {
+            return Err(ConfigError::InvalidConfig(format!(
+                "policies.annotation_types.{} declares unknown type `{}`",
+                name, declared
+            )));
+        }
+        if let Some(index) = self.custom_rules.iter().position(|rule| rule.name.trim().is_empty()) {
+            return Err(ConfigError::InvalidConfig(format!("policies.custom_rules[{}].name must not be empty", index)));
+        }
+        Ok(())
+    }
+
+    /// Whether any built-in or custom rule reports `rule_id`
+    pub fn has_rule(&self, rule_id: &str) -> bool {
+        POLICY_RULE_IDS.contains(&rule_id)
+            || rule_id
+                .strip_prefix("custom-policy-")
+                .is_some_and(|name| self.custom_rules.iter().any(|rule| rule.name == name))
+    }
 }
 
 impl Default for SchemaPolicies {
@@ -204,11 +465,55 @@ impl Default for SchemaPolicies {
             field_naming: FieldNamingPolicy::default(),
             type_restrictions: Vec::new(),
             required_metadata: Vec::new(),
+            required_annotations: Vec::new(),
+            annotation_types: HashMap::new(),
+            documentation: DocumentationPolicy::default(),
+            style: StylePolicy::default(),
             custom_rules: Vec::new(),
+            waivers: Vec::new(),
         }
     }
 }
 
+/// Centrally tracked exemption of subjects from policy rules
+///
+/// Findings of a waived rule on a matching subject are downgraded to
+/// informational until the waiver expires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyWaiver {
+    /// Subject name or glob where `*` matches any run (e.g. `payments-*`)
+    pub subject: String,
+
+    /// Rule ids waived, as reported in findings; `*` waives every rule
+    pub rules: Vec<String>,
+
+    /// First day the waiver no longer applies
+    pub expires: NaiveDate,
+
+    /// Who approved the waiver
+    pub approver: String,
+
+    /// Why the subject is exempt
+    pub reason: String,
+}
+
+impl PolicyWaiver {
+    /// Whether the waiver still applies on `today`
+    pub fn is_active(&self, today: NaiveDate) -> bool {
+        today < self.expires
+    }
+
+    /// Whether the waiver's subject pattern matches `subject`
+    pub fn covers_subject(&self, subject: &str) -> bool {
+        glob_match(&self.subject, subject)
+    }
+
+    /// Whether the waiver lists `rule_id`
+    pub fn covers_rule(&self, rule_id: &str) -> bool {
+        self.rules.iter().any(|rule| rule == "*" || rule == rule_id)
+    }
+}
+
 /// Field naming policy
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FieldNamingPolicy {
@@ -228,6 +533,92 @@ impl Default for FieldNamingPolicy {
     }
 }
 
+/// Documentation content policy
+///
+/// Applies to `description` in JSON Schema and `doc` in Avro; Protobuf
+/// comments are not checked. All limits are off by default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DocumentationPolicy {
+    /// Maximum length of a single documentation string, in characters
+    pub max_doc_length: Option<usize>,
+
+    /// Maximum total size of all documentation strings in a schema, in bytes
+    pub max_total_doc_bytes: Option<usize>,
+
+    /// Regexes documentation must not match (e.g. internal hostnames)
+    pub forbidden_patterns: Vec<String>,
+
+    /// Strip oversized documentation at registration instead of rejecting it
+    pub strip_oversized: bool,
+}
+
+impl DocumentationPolicy {
+    /// Whether any limit or pattern is configured
+    pub fn is_enabled(&self) -> bool {
+        self.max_doc_length.is_some() || self.max_total_doc_bytes.is_some() || !self.forbidden_patterns.is_empty()
+    }
+}
+
+/// Severity of style lint findings
+///
+/// Style findings are their own class: they describe documentation debt, not
+/// invalid schemas, so they are informational unless a policy raises them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StyleSeverity {
+    #[default]
+    Info,
+    Warning,
+    Error,
+}
+
+/// Style lint checks, see [`style_lint`](crate::style_lint)
+///
+/// Every check is on by default; findings are informational unless
+/// `severity` is raised.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StylePolicy {
+    /// Severity every style finding is reported with
+    pub severity: StyleSeverity,
+
+    /// Require a description on every property (JSON Schema) or field (Avro)
+    pub descriptions: bool,
+
+    /// Shortest description, in characters, that counts as one
+    pub min_description_length: usize,
+
+    /// Require at least one entry in `examples` on a top-level JSON Schema
+    pub examples: bool,
+
+    /// Require titles in Title Case that are not placeholders like `object1`
+    pub title_case: bool,
+
+    /// Require every enum value to be documented
+    pub enum_docs: bool,
+}
+
+impl StylePolicy {
+    /// Whether any check is on
+    pub fn is_enabled(&self) -> bool {
+        self.descriptions || self.examples || self.title_case || self.enum_docs
+    }
+}
+
+impl Default for StylePolicy {
+    fn default() -> Self {
+        Self {
+            severity: StyleSeverity::Info,
+            descriptions: true,
+            min_description_length: 10,
+            examples: true,
+            title_case: true,
+            enum_docs: true,
+        }
+    }
+}
+
 /// Custom policy rule
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CustomPolicyRule {
@@ -269,12 +660,17 @@ pub enum ConfigError {
 // ============================================================================
 
 /// Adapter for consuming configuration from LLM Config Manager
+///
+/// Cloning is cheap; clones share the underlying manager.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone)]
 pub struct ConfigManagerAdapter {
     manager: Arc<ConfigManager>,
     environment: Environment,
     namespace: String,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl ConfigManagerAdapter {
     /// Create a new adapter with the specified storage path
     ///
@@ -305,6 +701,32 @@ impl ConfigManagerAdapter {
         })
     }
 
+    /// Create a new adapter without blocking the runtime
+    ///
+    /// Opening the config storage reads it from disk; this runs that on the
+    /// blocking thread pool. Otherwise equivalent to [`new`](Self::new).
+    pub async fn open(storage_path: impl Into<PathBuf>, environment: Environment) -> Result<Self, ConfigError> {
+        let storage_path = storage_path.into();
+        tokio::task::spawn_blocking(move || Self::new(storage_path, environment))
+            .await
+            .map_err(|e| ConfigError::ConfigManager(format!("Config Manager open task failed: {}", e)))?
+    }
+
+    /// Run `load` against a clone of this adapter on the blocking thread pool
+    ///
+    /// Config Manager reads are synchronous file IO; a whole load runs as one
+    /// blocking task rather than one per key.
+    pub async fn load_blocking<T, F>(&self, load: F) -> Result<T, ConfigError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Self) -> Result<T, ConfigError> + Send + 'static,
+    {
+        let adapter = self.clone();
+        tokio::task::spawn_blocking(move || load(&adapter))
+            .await
+            .map_err(|e| ConfigError::ConfigManager(format!("Config load task failed: {}", e)))?
+    }
+
     /// Create adapter with custom namespace
     pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
         self.namespace = namespace.into();
@@ -338,6 +760,7 @@ impl ConfigManagerAdapter {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl ConfigConsumer for ConfigManagerAdapter {
     fn load_global_config(&self) -> Result<GlobalConfig, ConfigError> {
         info!("Loading global configuration from Config Manager");
@@ -401,6 +824,21 @@ impl ConfigConsumer for ConfigManagerAdapter {
             }
         }
 
+        if let Ok(Some(value)) = self.get_config_value("policies/style") {
+            if let Ok(style) = self.parse_value::<StylePolicy>(&value) {
+                policies.style = style;
+                debug!("Loaded style lint policy from Config Manager");
+            }
+        }
+
+        // Waivers may be managed separately from the policies they relax
+        if let Ok(Some(value)) = self.get_config_value("policies/waivers") {
+            if let Ok(waivers) = self.parse_value::<Vec<PolicyWaiver>>(&value) {
+                debug!("Loaded {} policy waivers from Config Manager", waivers.len());
+                policies.waivers = waivers;
+            }
+        }
+
         info!("Schema policies loaded successfully");
         Ok(policies)
     }
@@ -423,22 +861,61 @@ impl ConfigConsumer for ConfigManagerAdapter {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl AsyncConfigConsumer for ConfigManagerAdapter {
+    async fn load_global_config(&self) -> Result<GlobalConfig, ConfigError> {
+        self.load_blocking(|adapter| ConfigConsumer::load_global_config(adapter)).await
+    }
+
+    async fn load_schema_policies(&self) -> Result<SchemaPolicies, ConfigError> {
+        self.load_blocking(|adapter| ConfigConsumer::load_schema_policies(adapter)).await
+    }
+
+    async fn refresh(&self) -> Result<(), ConfigError> {
+        self.load_blocking(|adapter| ConfigConsumer::refresh(adapter)).await
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
 /// Create a default adapter for development environment
+#[cfg(not(target_arch = "wasm32"))]
 pub fn create_dev_adapter(storage_path: impl AsRef<Path>) -> Result<Arc<dyn ConfigConsumer>, ConfigError> {
     let adapter = ConfigManagerAdapter::new(storage_path, Environment::Development)?;
     Ok(Arc::new(adapter))
 }
 
 /// Create a default adapter for production environment
+#[cfg(not(target_arch = "wasm32"))]
 pub fn create_prod_adapter(storage_path: impl AsRef<Path>) -> Result<Arc<dyn ConfigConsumer>, ConfigError> {
     let adapter = ConfigManagerAdapter::new(storage_path, Environment::Production)?;
     Ok(Arc::new(adapter))
 }
 
+/// Parse an environment name (`dev`, `development`, `staging`, `prod`, `production`)
+#[cfg(not(target_arch = "wasm32"))]
+pub fn parse_environment(name: &str) -> Result<Environment, ConfigError> {
+    match name.to_ascii_lowercase().as_str() {
+        "dev" | "development" => Ok(Environment::Development),
+        "stage" | "staging" => Ok(Environment::Staging),
+        "prod" | "production" => Ok(Environment::Production),
+        other => Err(ConfigError::InvalidConfig(format!("Unknown environment: {}", other))),
+    }
+}
+
+/// Create an adapter for a named environment
+#[cfg(not(target_arch = "wasm32"))]
+pub fn create_adapter_for(
+    storage_path: impl AsRef<Path>,
+    environment: &str,
+) -> Result<Arc<dyn ConfigConsumer>, ConfigError> {
+    let adapter = ConfigManagerAdapter::new(storage_path, parse_environment(environment)?)?;
+    Ok(Arc::new(adapter))
+}
+
 // ============================================================================
 // Phase 2B: Schema Sources Configuration Adapter
 // ============================================================================
@@ -974,6 +1451,7 @@ pub trait ConfigConsumerExt: ConfigConsumer {
     fn load_validation_settings(&self) -> Result<ValidationSettingsConfig, ConfigError>;
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl ConfigConsumerExt for ConfigManagerAdapter {
     fn load_schema_sources(&self) -> Result<SchemaSourcesConfig, ConfigError> {
         info!("Loading schema sources configuration from Config Manager");
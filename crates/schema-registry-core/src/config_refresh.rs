@@ -3,14 +3,35 @@
 //! Provides optional hooks for live configuration updates from Config Manager
 //! without requiring server restart. This enables dynamic policy updates and
 //! configuration changes in production environments.
+//!
+//! Each top-level section of a reload is validated on its own. By default
+//! valid sections are applied while invalid ones keep their previous values
+//! ([`RefreshOutcome::PartiallyApplied`]); [`RefreshStrictness::AllOrNothing`]
+//! rejects the whole reload instead.
 
 use crate::config_manager_adapter::{
-    ConfigConsumer, ConfigUpdateListener, GlobalConfig, SchemaPolicies, ConfigError,
+    AsyncConfigConsumer, BlockingConsumer, ConfigConsumer, ConfigUpdateListener, GlobalConfig, SchemaPolicies,
+    ConfigError,
 };
+#[cfg(not(target_arch = "wasm32"))]
+use crate::startup::StartupContext;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use tokio::time;
-use tracing::{info, warn, error};
+use tracing::{debug, info, warn, error};
+
+/// Key fragments marking a configuration value as sensitive
+pub const SENSITIVE_KEY_MARKERS: &[&str] = &["password", "secret", "token", "api_key", "credential", "private_key"];
+
+/// Placeholder replacing redacted values
+pub const REDACTED: &str = "***";
+
+/// Refresh outcomes kept by [`ConfigRefreshManager::audit_log`]
+pub const AUDIT_LOG_CAPACITY: usize = 256;
 
 /// Configuration refresh strategy
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -25,13 +46,158 @@ pub enum RefreshStrategy {
     EventDriven,
 }
 
+/// How a refresh treats a reload in which some sections are invalid
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefreshStrictness {
+    /// Apply the valid sections, keep previous values of the invalid ones
+    #[default]
+    PerSection,
+
+    /// Reject the whole reload if any section is invalid
+    AllOrNothing,
+}
+
+/// Top-level section of the configuration, validated and applied as a unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSection {
+    Server,
+    Storage,
+    Validation,
+    Security,
+    Metadata,
+    Policies,
+}
+
+impl ConfigSection {
+    /// Every section, in application order
+    pub const ALL: [ConfigSection; 6] = [
+        ConfigSection::Server,
+        ConfigSection::Storage,
+        ConfigSection::Validation,
+        ConfigSection::Security,
+        ConfigSection::Metadata,
+        ConfigSection::Policies,
+    ];
+
+    /// Prefix of the section's paths in a [`ConfigDiff`]
+    pub fn path(&self) -> &'static str {
+        match self {
+            ConfigSection::Server => "config.server",
+            ConfigSection::Storage => "config.storage",
+            ConfigSection::Validation => "config.validation",
+            ConfigSection::Security => "config.security",
+            ConfigSection::Metadata => "config.metadata",
+            ConfigSection::Policies => "policies",
+        }
+    }
+
+    /// Whether the diff path `path` lies in this section
+    pub fn contains(&self, path: &str) -> bool {
+        path.strip_prefix(self.path())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.') || rest.starts_with('['))
+    }
+
+    /// Validate this section of `config` and `policies`
+    pub fn validate(&self, config: &GlobalConfig, policies: &SchemaPolicies) -> Result<(), ConfigError> {
+        match self {
+            ConfigSection::Server => config.server.validate(),
+            ConfigSection::Storage => config.storage.validate(),
+            ConfigSection::Validation => config.validation.validate(),
+            ConfigSection::Security => config.security.validate(),
+            ConfigSection::Metadata => Ok(()),
+            ConfigSection::Policies => policies.validate(),
+        }
+    }
+
+    /// Copy this section from `source_*` into `config` and `policies`
+    pub fn apply(
+        &self,
+        source_config: &GlobalConfig,
+        source_policies: &SchemaPolicies,
+        config: &mut GlobalConfig,
+        policies: &mut SchemaPolicies,
+    ) {
+        match self {
+            ConfigSection::Server => config.server = source_config.server.clone(),
+            ConfigSection::Storage => config.storage = source_config.storage.clone(),
+            ConfigSection::Validation => config.validation = source_config.validation.clone(),
+            ConfigSection::Security => config.security = source_config.security.clone(),
+            ConfigSection::Metadata => config.metadata = source_config.metadata.clone(),
+            ConfigSection::Policies => *policies = source_policies.clone(),
+        }
+    }
+}
+
+/// A section a refresh kept at its previous value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SectionRejection {
+    pub section: ConfigSection,
+    /// Why the reloaded section failed validation
+    pub reason: String,
+}
+
+/// A refresh that applied some changed sections and rejected others
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartialRefresh {
+    pub applied: Vec<ConfigSection>,
+    pub rejected: Vec<SectionRejection>,
+    /// Changes in the applied sections only, redacted
+    pub diff: ConfigDiff,
+}
+
+/// Result of [`ConfigRefreshManager::refresh`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum RefreshOutcome {
+    /// Every changed section was valid and applied
+    Applied { diff: ConfigDiff },
+
+    /// Valid sections were applied, invalid ones kept their previous values
+    PartiallyApplied(PartialRefresh),
+
+    /// Nothing was applied
+    Rejected { rejected: Vec<SectionRejection> },
+}
+
+impl RefreshOutcome {
+    /// Short name used in logs and the audit trail
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RefreshOutcome::Applied { .. } => "applied",
+            RefreshOutcome::PartiallyApplied(_) => "partially_applied",
+            RefreshOutcome::Rejected { .. } => "rejected",
+        }
+    }
+
+    /// Sections that failed validation
+    pub fn rejected(&self) -> &[SectionRejection] {
+        match self {
+            RefreshOutcome::Applied { .. } => &[],
+            RefreshOutcome::PartiallyApplied(partial) => &partial.rejected,
+            RefreshOutcome::Rejected { rejected } => rejected,
+        }
+    }
+}
+
+/// One refresh recorded in the audit trail
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefreshAuditEntry {
+    pub at: DateTime<Utc>,
+    pub outcome: RefreshOutcome,
+}
+
 /// Configuration refresh manager
 ///
 /// Manages runtime configuration updates from Config Manager, providing
 /// hooks for automatic refresh and notification to listeners.
-pub struct ConfigRefreshManager {
+///
+/// Generic over the [`AsyncConfigConsumer`] it reloads from; the default
+/// erases it behind a trait object.
+pub struct ConfigRefreshManager<C: ?Sized = dyn AsyncConfigConsumer> {
     /// Config adapter
-    adapter: Arc<dyn ConfigConsumer>,
+    adapter: Arc<C>,
 
     /// Current global configuration
     global_config: Arc<RwLock<GlobalConfig>>,
@@ -44,15 +210,47 @@ pub struct ConfigRefreshManager {
 
     /// Refresh strategy
     strategy: RefreshStrategy,
+
+    /// Whether invalid sections reject the whole reload
+    strictness: RefreshStrictness,
+
+    /// Most recent refresh outcomes, oldest first
+    audit: RwLock<VecDeque<RefreshAuditEntry>>,
 }
 
 impl ConfigRefreshManager {
-    /// Create a new refresh manager
+    /// Create a new refresh manager over a sync consumer
+    ///
+    /// The consumer runs on the blocking thread pool, see [`BlockingConsumer`].
     pub fn new(
         adapter: Arc<dyn ConfigConsumer>,
         initial_config: GlobalConfig,
         initial_policies: SchemaPolicies,
         strategy: RefreshStrategy,
+    ) -> Self {
+        Self::with_consumer(Arc::new(BlockingConsumer::new(adapter)), initial_config, initial_policies, strategy)
+    }
+
+    /// Create a manager for the configuration loaded at startup
+    ///
+    /// Returns `None` when startup ran without a config adapter.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_startup(context: &StartupContext, strategy: RefreshStrategy) -> Option<Self> {
+        let adapter = context.config_adapter.clone()?;
+        Some(
+            Self::new(adapter, context.global_config.clone(), context.schema_policies.clone(), strategy)
+                .with_strictness(context.refresh_strictness),
+        )
+    }
+}
+
+impl<C: AsyncConfigConsumer + ?Sized> ConfigRefreshManager<C> {
+    /// Create a new refresh manager over an async consumer
+    pub fn with_consumer(
+        adapter: Arc<C>,
+        initial_config: GlobalConfig,
+        initial_policies: SchemaPolicies,
+        strategy: RefreshStrategy,
     ) -> Self {
         Self {
             adapter,
@@ -60,9 +258,26 @@ impl ConfigRefreshManager {
             schema_policies: Arc::new(RwLock::new(initial_policies)),
             listeners: Arc::new(RwLock::new(Vec::new())),
             strategy,
+            strictness: RefreshStrictness::default(),
+            audit: RwLock::new(VecDeque::new()),
         }
     }
 
+    /// Set how reloads with invalid sections are treated
+    pub fn with_strictness(mut self, strictness: RefreshStrictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    pub fn strictness(&self) -> RefreshStrictness {
+        self.strictness
+    }
+
+    /// Most recent refresh outcomes, oldest first
+    pub fn audit_log(&self) -> Vec<RefreshAuditEntry> {
+        self.audit.read().unwrap().iter().cloned().collect()
+    }
+
     /// Register a configuration update listener
     pub fn register_listener(&self, listener: Arc<dyn ConfigUpdateListener>) {
         let mut listeners = self.listeners.write().unwrap();
@@ -81,36 +296,81 @@ impl ConfigRefreshManager {
     }
 
     /// Manually trigger a configuration refresh
-    pub async fn refresh(&self) -> Result<(), ConfigError> {
+    ///
+    /// Only changed sections are validated. Errors are reserved for failing
+    /// to load the configuration; invalid sections are reported in the outcome.
+    pub async fn refresh(&self) -> Result<RefreshOutcome, ConfigError> {
         info!("Triggering manual configuration refresh");
 
         // Refresh via adapter
-        self.adapter.refresh()?;
+        self.adapter.refresh().await?;
 
         // Reload configuration
-        let new_config = self.adapter.load_global_config()?;
-        let new_policies = self.adapter.load_schema_policies()?;
-
-        // Update internal state
-        {
-            let mut config = self.global_config.write().unwrap();
-            *config = new_config.clone();
+        let new_config = self.adapter.load_global_config().await?;
+        let new_policies = self.adapter.load_schema_policies().await?;
+
+        let mut config = self.get_global_config();
+        let mut policies = self.get_schema_policies();
+        let diff = ConfigDiff::compute(&config, &policies, &new_config, &new_policies)?.redacted();
+        for change in &diff.changes {
+            debug!("Config change at {}: {:?} -> {:?}", change.path, change.base, change.target);
+        }
+        info!("Configuration refresh found {} changed field(s)", diff.changes.len());
+
+        // Validate each changed section on its own
+        let mut applied = Vec::new();
+        let mut rejected = Vec::new();
+        for section in ConfigSection::ALL {
+            if !diff.changes.iter().any(|change| section.contains(&change.path)) {
+                continue;
+            }
+            match section.validate(&new_config, &new_policies) {
+                Ok(()) => applied.push(section),
+                Err(e) => rejected.push(SectionRejection {
+                    section,
+                    reason: e.to_string(),
+                }),
+            }
+        }
+        if !rejected.is_empty() && self.strictness == RefreshStrictness::AllOrNothing {
+            applied.clear();
         }
 
-        {
-            let mut policies = self.schema_policies.write().unwrap();
-            *policies = new_policies.clone();
+        for section in &applied {
+            section.apply(&new_config, &new_policies, &mut config, &mut policies);
         }
+        let diff = diff.retain(|path| applied.iter().any(|section| section.contains(path)));
+
+        let outcome = if rejected.is_empty() {
+            RefreshOutcome::Applied { diff }
+        } else if applied.is_empty() {
+            RefreshOutcome::Rejected { rejected }
+        } else {
+            RefreshOutcome::PartiallyApplied(PartialRefresh { applied, rejected, diff })
+        };
+
+        if !matches!(outcome, RefreshOutcome::Rejected { .. }) {
+            // Update internal state
+            {
+                let mut current = self.global_config.write().unwrap();
+                *current = config.clone();
+            }
 
-        // Notify listeners
-        self.notify_listeners(&new_config, &new_policies).await;
+            {
+                let mut current = self.schema_policies.write().unwrap();
+                *current = policies.clone();
+            }
 
-        info!("Configuration refresh completed successfully");
-        Ok(())
+            // Notify listeners
+            self.notify_listeners(&config, &policies, &outcome).await;
+        }
+
+        self.audit(&outcome);
+        Ok(outcome)
     }
 
     /// Notify all registered listeners of config updates
-    async fn notify_listeners(&self, config: &GlobalConfig, policies: &SchemaPolicies) {
+    async fn notify_listeners(&self, config: &GlobalConfig, policies: &SchemaPolicies, outcome: &RefreshOutcome) {
         let listeners = self.listeners.read().unwrap().clone();
 
         info!("Notifying {} listeners of config update", listeners.len());
@@ -118,11 +378,62 @@ impl ConfigRefreshManager {
         for listener in listeners {
             listener.on_config_updated(config);
             listener.on_policies_updated(policies);
+            if let RefreshOutcome::PartiallyApplied(partial) = outcome {
+                listener.on_partial_refresh(partial);
+            }
         }
     }
 
+    /// Log `outcome` to the audit target and keep it in the audit trail
+    fn audit(&self, outcome: &RefreshOutcome) {
+        for rejection in outcome.rejected() {
+            warn!(
+                target: "audit",
+                event = "config_refresh.section_rejected",
+                section = rejection.section.path(),
+                reason = %rejection.reason,
+                "Kept previous configuration section"
+            );
+        }
+        match outcome {
+            RefreshOutcome::Applied { diff } => info!(
+                target: "audit",
+                event = "config_refresh.applied",
+                changes = diff.changes.len(),
+                "Configuration refresh applied"
+            ),
+            RefreshOutcome::PartiallyApplied(partial) => warn!(
+                target: "audit",
+                event = "config_refresh.partially_applied",
+                applied = ?partial.applied,
+                rejected = partial.rejected.len(),
+                changes = partial.diff.changes.len(),
+                "Configuration refresh partially applied"
+            ),
+            RefreshOutcome::Rejected { rejected } => warn!(
+                target: "audit",
+                event = "config_refresh.rejected",
+                rejected = rejected.len(),
+                strictness = ?self.strictness,
+                "Configuration refresh rejected"
+            ),
+        }
+
+        let mut audit = self.audit.write().unwrap();
+        if audit.len() == AUDIT_LOG_CAPACITY {
+            audit.pop_front();
+        }
+        audit.push_back(RefreshAuditEntry {
+            at: Utc::now(),
+            outcome: outcome.clone(),
+        });
+    }
+
     /// Start background refresh task (for periodic strategy)
-    pub async fn start_background_refresh(self: Arc<Self>) {
+    pub async fn start_background_refresh(self: Arc<Self>)
+    where
+        C: 'static,
+    {
         match self.strategy {
             RefreshStrategy::Manual => {
                 info!("Manual refresh strategy - no background task needed");
@@ -136,9 +447,16 @@ impl ConfigRefreshManager {
                         ticker.tick().await;
 
                         match self.refresh().await {
-                            Ok(()) => {
+                            Ok(RefreshOutcome::Applied { .. }) => {
                                 info!("Periodic configuration refresh succeeded");
                             }
+                            Ok(outcome) => {
+                                warn!(
+                                    "Periodic configuration refresh {}: {} section(s) kept previous values",
+                                    outcome.kind(),
+                                    outcome.rejected().len()
+                                );
+                            }
                             Err(e) => {
                                 error!("Periodic configuration refresh failed: {}", e);
                             }
@@ -156,6 +474,137 @@ impl ConfigRefreshManager {
     }
 }
 
+/// A single field that differs between two configurations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChange {
+    /// Dotted path of the field (`config.server.host`, `policies.custom_rules[0].name`)
+    pub path: String,
+    /// Value on the base side, `None` when absent
+    pub base: Option<Value>,
+    /// Value on the target side, `None` when absent
+    pub target: Option<Value>,
+}
+
+/// Structural difference between two configurations
+///
+/// Used by [`ConfigRefreshManager::refresh`] to report what a reload changed
+/// and by tooling comparing environments.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigDiff {
+    /// Changed fields, in path order
+    pub changes: Vec<ConfigChange>,
+}
+
+impl ConfigDiff {
+    /// Diff any two serializable values field by field
+    pub fn between<T: Serialize>(base: &T, target: &T) -> Result<Self, ConfigError> {
+        let mut diff = ConfigDiff::default();
+        diff_values("", &serde_json::to_value(base)?, &serde_json::to_value(target)?, &mut diff.changes);
+        Ok(diff)
+    }
+
+    /// Diff global config and policies, prefixing paths with `config.` and `policies.`
+    pub fn compute(
+        base_config: &GlobalConfig,
+        base_policies: &SchemaPolicies,
+        target_config: &GlobalConfig,
+        target_policies: &SchemaPolicies,
+    ) -> Result<Self, ConfigError> {
+        let mut changes = Vec::new();
+        diff_values(
+            "config",
+            &serde_json::to_value(base_config)?,
+            &serde_json::to_value(target_config)?,
+            &mut changes,
+        );
+        diff_values(
+            "policies",
+            &serde_json::to_value(base_policies)?,
+            &serde_json::to_value(target_policies)?,
+            &mut changes,
+        );
+        Ok(Self { changes })
+    }
+
+    /// Whether the configurations are identical
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Keep only changes whose path satisfies `keep`
+    pub fn retain(mut self, keep: impl Fn(&str) -> bool) -> Self {
+        self.changes.retain(|change| keep(&change.path));
+        self
+    }
+
+    /// Replace values of fields for which `is_sensitive(path)` holds
+    pub fn redact(mut self, is_sensitive: impl Fn(&str) -> bool) -> Self {
+        for change in &mut self.changes {
+            if is_sensitive(&change.path) {
+                let redacted = Some(Value::String(REDACTED.to_string()));
+                change.base = change.base.as_ref().and(redacted.clone());
+                change.target = change.target.as_ref().and(redacted);
+            }
+        }
+        self
+    }
+
+    /// Redact fields whose path contains one of [`SENSITIVE_KEY_MARKERS`]
+    pub fn redacted(self) -> Self {
+        self.redact(|path| {
+            let path = path.to_ascii_lowercase();
+            SENSITIVE_KEY_MARKERS.iter().any(|marker| path.contains(marker))
+        })
+    }
+}
+
+fn diff_values(path: &str, base: &Value, target: &Value, changes: &mut Vec<ConfigChange>) {
+    let join = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+
+    match (base, target) {
+        (Value::Object(b), Value::Object(t)) => {
+            let mut keys: Vec<&String> = b.keys().chain(t.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                match (b.get(key), t.get(key)) {
+                    (Some(bv), Some(tv)) => diff_values(&join(key), bv, tv, changes),
+                    (bv, tv) => changes.push(ConfigChange {
+                        path: join(key),
+                        base: bv.cloned(),
+                        target: tv.cloned(),
+                    }),
+                }
+            }
+        }
+        (Value::Array(b), Value::Array(t)) => {
+            for idx in 0..b.len().max(t.len()) {
+                let item_path = format!("{}[{}]", path, idx);
+                match (b.get(idx), t.get(idx)) {
+                    (Some(bv), Some(tv)) => diff_values(&item_path, bv, tv, changes),
+                    (bv, tv) => changes.push(ConfigChange {
+                        path: item_path,
+                        base: bv.cloned(),
+                        target: tv.cloned(),
+                    }),
+                }
+            }
+        }
+        (b, t) if b != t => changes.push(ConfigChange {
+            path: path.to_string(),
+            base: Some(b.clone()),
+            target: Some(t.clone()),
+        }),
+        _ => {}
+    }
+}
+
 /// Example listener that logs configuration changes
 pub struct LoggingConfigListener;
 
@@ -199,4 +648,162 @@ mod tests {
         listener.on_config_updated(&config);
         listener.on_policies_updated(&policies);
     }
+
+    #[test]
+    fn test_config_diff_reports_changed_paths() {
+        let base = GlobalConfig::default();
+        let mut target = GlobalConfig::default();
+        target.server.port = 9090;
+        target.metadata.insert("db_password".to_string(), "hunter2".to_string());
+
+        let mut target_policies = SchemaPolicies::default();
+        target_policies.required_metadata.push("owner".to_string());
+
+        let diff = ConfigDiff::compute(&base, &SchemaPolicies::default(), &target, &target_policies)
+            .unwrap()
+            .redacted();
+
+        let paths: Vec<&str> = diff.changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["config.metadata.db_password", "config.server.port", "policies.required_metadata[0]"]
+        );
+        assert_eq!(diff.changes[0].base, None);
+        assert_eq!(diff.changes[0].target, Some(Value::String(REDACTED.to_string())));
+        assert_eq!(diff.changes[1].target, Some(Value::from(9090)));
+    }
+
+    /// Consumer whose reload changes storage validly and validation invalidly
+    struct MixedConsumer;
+
+    impl ConfigConsumer for MixedConsumer {
+        fn load_global_config(&self) -> Result<GlobalConfig, ConfigError> {
+            let mut config = GlobalConfig::default();
+            config.storage.pool_size = 32;
+            config.validation.max_schema_size = 0;
+            Ok(config)
+        }
+
+        fn load_schema_policies(&self) -> Result<SchemaPolicies, ConfigError> {
+            Ok(SchemaPolicies::default())
+        }
+
+        fn refresh(&self) -> Result<(), ConfigError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingListener {
+        partial: RwLock<Vec<PartialRefresh>>,
+    }
+
+    impl ConfigUpdateListener for RecordingListener {
+        fn on_config_updated(&self, _config: &GlobalConfig) {}
+
+        fn on_policies_updated(&self, _policies: &SchemaPolicies) {}
+
+        fn on_partial_refresh(&self, refresh: &PartialRefresh) {
+            self.partial.write().unwrap().push(refresh.clone());
+        }
+    }
+
+    fn mixed_manager() -> ConfigRefreshManager {
+        ConfigRefreshManager::new(
+            Arc::new(MixedConsumer),
+            GlobalConfig::default(),
+            SchemaPolicies::default(),
+            RefreshStrategy::Manual,
+        )
+    }
+
+    #[test]
+    fn test_section_contains_only_its_paths() {
+        assert!(ConfigSection::Server.contains("config.server.port"));
+        assert!(ConfigSection::Policies.contains("policies.custom_rules[0].name"));
+        assert!(!ConfigSection::Server.contains("config.server_name"));
+        assert!(!ConfigSection::Storage.contains("config.server.port"));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_applies_valid_sections_only() {
+        let manager = mixed_manager();
+        let listener = Arc::new(RecordingListener::default());
+        manager.register_listener(listener.clone());
+
+        let outcome = manager.refresh().await.unwrap();
+        let RefreshOutcome::PartiallyApplied(partial) = &outcome else {
+            panic!("expected a partial refresh, got {:?}", outcome);
+        };
+        assert_eq!(partial.applied, vec![ConfigSection::Storage]);
+        assert_eq!(partial.rejected.len(), 1);
+        assert_eq!(partial.rejected[0].section, ConfigSection::Validation);
+        assert!(partial.rejected[0].reason.contains("max_schema_size"));
+        assert!(partial.diff.changes.iter().all(|c| ConfigSection::Storage.contains(&c.path)));
+
+        let config = manager.get_global_config();
+        assert_eq!(config.storage.pool_size, 32);
+        assert_eq!(config.validation.max_schema_size, GlobalConfig::default().validation.max_schema_size);
+
+        assert_eq!(listener.partial.read().unwrap().as_slice(), std::slice::from_ref(partial));
+
+        let audit = manager.audit_log();
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].outcome.kind(), "partially_applied");
+    }
+
+    #[tokio::test]
+    async fn test_all_or_nothing_rejects_whole_refresh() {
+        let manager = mixed_manager().with_strictness(RefreshStrictness::AllOrNothing);
+        let listener = Arc::new(RecordingListener::default());
+        manager.register_listener(listener.clone());
+
+        let outcome = manager.refresh().await.unwrap();
+        assert_eq!(outcome.kind(), "rejected");
+        assert_eq!(outcome.rejected().len(), 1);
+        assert_eq!(manager.get_global_config().storage.pool_size, GlobalConfig::default().storage.pool_size);
+        assert!(listener.partial.read().unwrap().is_empty());
+        assert_eq!(manager.audit_log()[0].outcome, outcome);
+    }
+
+    /// Native async consumer whose reload changes the server port
+    struct AsyncPortConsumer;
+
+    #[async_trait::async_trait]
+    impl AsyncConfigConsumer for AsyncPortConsumer {
+        async fn load_global_config(&self) -> Result<GlobalConfig, ConfigError> {
+            tokio::task::yield_now().await;
+            let mut config = GlobalConfig::default();
+            config.server.port = 9090;
+            Ok(config)
+        }
+
+        async fn load_schema_policies(&self) -> Result<SchemaPolicies, ConfigError> {
+            Ok(SchemaPolicies::default())
+        }
+
+        async fn refresh(&self) -> Result<(), ConfigError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refresh_with_async_consumer() {
+        let manager = ConfigRefreshManager::with_consumer(
+            Arc::new(AsyncPortConsumer),
+            GlobalConfig::default(),
+            SchemaPolicies::default(),
+            RefreshStrategy::Manual,
+        );
+
+        let outcome = manager.refresh().await.unwrap();
+        assert_eq!(outcome.kind(), "applied");
+        assert_eq!(manager.get_global_config().server.port, 9090);
+    }
+
+    #[test]
+    fn test_config_diff_identical() {
+        let diff = ConfigDiff::between(&GlobalConfig::default(), &GlobalConfig::default()).unwrap();
+        assert!(diff.is_empty());
+    }
 }
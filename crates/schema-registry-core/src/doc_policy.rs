@@ -0,0 +1,421 @@
+//! Documentation content policy
+//!
+//! Checks documentation strings against a [`DocumentationPolicy`]: the
+//! length of each string, the total documentation size of the schema, and a
+//! list of forbidden patterns. Documentation is `description` in JSON Schema
+//! and `doc` in Avro; Protobuf comments are not checked.
+//!
+//! Paths are JSON locations of the documented object (`$.properties.amount`,
+//! `$.fields[0]`), so a violation points at the exact string to fix.
+//!
+//! With [`DocumentationPolicy::strip_oversized`] set, [`apply`] removes
+//! oversized strings instead of reporting them and lists what it removed.
+//! Strings matching a forbidden pattern are always reported, never stripped.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+use crate::config_manager_adapter::DocumentationPolicy;
+use crate::error::{Error, Result};
+use crate::types::SerializationFormat;
+
+/// JSON Schema keywords whose values are instance data, not subschemas
+const JSON_SCHEMA_DATA_KEYWORDS: &[&str] = &["default", "const", "enum", "examples"];
+
+/// Avro attributes whose values are instance data
+const AVRO_DATA_ATTRIBUTES: &[&str] = &["default"];
+
+/// Key holding documentation in `format`, or `None` if it is not checked
+pub fn doc_key(format: SerializationFormat) -> Option<&'static str> {
+    match format {
+        SerializationFormat::JsonSchema => Some("description"),
+        SerializationFormat::Avro => Some("doc"),
+        SerializationFormat::Protobuf => None,
+    }
+}
+
+/// A documentation string breaking the policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DocViolation {
+    /// A single string is longer than `max_doc_length`
+    TooLong { path: String, length: usize, limit: usize },
+    /// All strings together are larger than `max_total_doc_bytes`
+    TotalTooLarge { total: usize, limit: usize },
+    /// A string matches a forbidden pattern
+    ForbiddenPattern { path: String, pattern: String },
+}
+
+impl DocViolation {
+    /// Location of the offending documentation
+    pub fn path(&self) -> &str {
+        match self {
+            DocViolation::TooLong { path, .. } | DocViolation::ForbiddenPattern { path, .. } => path,
+            DocViolation::TotalTooLarge { .. } => "$",
+        }
+    }
+}
+
+impl fmt::Display for DocViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DocViolation::TooLong { path, length, limit } => write!(
+                f,
+                "documentation at {} is {} characters, limit is {}",
+                path, length, limit
+            ),
+            DocViolation::TotalTooLarge { total, limit } => write!(
+                f,
+                "schema documentation totals {} bytes, limit is {}",
+                total, limit
+            ),
+            DocViolation::ForbiddenPattern { path, pattern } => write!(
+                f,
+                "documentation at {} matches forbidden pattern '{}'",
+                path, pattern
+            ),
+        }
+    }
+}
+
+/// Why a documentation string was stripped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StripReason {
+    /// The string was longer than `max_doc_length`
+    TooLong,
+    /// Removed to bring the schema under `max_total_doc_bytes`
+    TotalTooLarge,
+}
+
+/// A documentation string removed by [`apply`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StrippedDoc {
+    pub path: String,
+    /// Size of the removed string in bytes
+    pub bytes: usize,
+    pub reason: StripReason,
+}
+
+/// Result of enforcing a policy with [`apply`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocPolicyOutcome {
+    /// Schema content; unchanged (byte for byte) unless something was stripped
+    pub content: String,
+    /// Documentation removed from the content
+    pub stripped: Vec<StrippedDoc>,
+    /// Violations remaining in `content`
+    pub violations: Vec<DocViolation>,
+}
+
+/// Check the documentation in `schema` without changing it
+pub fn check(schema: &str, format: SerializationFormat, policy: &DocumentationPolicy) -> Result<Vec<DocViolation>> {
+    let Some(key) = doc_key(format).filter(|_| policy.is_enabled()) else {
+        return Ok(Vec::new());
+    };
+    let patterns = compile_patterns(policy)?;
+    let value = parse_json(schema, format)?;
+
+    let mut docs = Vec::new();
+    collect_docs(&value, "$", key, data_keys(format), &mut docs);
+    Ok(violations(&docs, policy, &patterns))
+}
+
+/// Enforce `policy`, stripping oversized documentation if the policy allows
+///
+/// Without `strip_oversized` this is [`check`] plus the unchanged content.
+pub fn apply(schema: &str, format: SerializationFormat, policy: &DocumentationPolicy) -> Result<DocPolicyOutcome> {
+    let unchanged = |violations| DocPolicyOutcome {
+        content: schema.to_string(),
+        stripped: Vec::new(),
+        violations,
+    };
+    let Some(key) = doc_key(format).filter(|_| policy.is_enabled()) else {
+        return Ok(unchanged(Vec::new()));
+    };
+    let patterns = compile_patterns(policy)?;
+    let mut value = parse_json(schema, format)?;
+    let data_keys = data_keys(format);
+
+    let mut docs = Vec::new();
+    collect_docs(&value, "$", key, data_keys, &mut docs);
+    if !policy.strip_oversized {
+        return Ok(unchanged(violations(&docs, policy, &patterns)));
+    }
+
+    let mut stripped = Vec::new();
+    if let Some(limit) = policy.max_doc_length {
+        for doc in docs.iter().filter(|doc| doc.text.chars().count() > limit) {
+            stripped.push(StrippedDoc {
+                path: doc.path.clone(),
+                bytes: doc.text.len(),
+                reason: StripReason::TooLong,
+            });
+        }
+    }
+    if let Some(limit) = policy.max_total_doc_bytes {
+        // Largest strings go first so as little documentation as possible is lost
+        let mut remaining: Vec<&Doc> = docs
+            .iter()
+            .filter(|doc| !stripped.iter().any(|s| s.path == doc.path))
+            .collect();
+        remaining.sort_by(|a, b| b.text.len().cmp(&a.text.len()).then_with(|| a.path.cmp(&b.path)));
+        let mut total: usize = remaining.iter().map(|doc| doc.text.len()).sum();
+        for doc in remaining {
+            if total <= limit {
+                break;
+            }
+            total -= doc.text.len();
+            stripped.push(StrippedDoc {
+                path: doc.path.clone(),
+                bytes: doc.text.len(),
+                reason: StripReason::TotalTooLarge,
+            });
+        }
+    }
+
+    if stripped.is_empty() {
+        return Ok(unchanged(violations(&docs, policy, &patterns)));
+    }
+
+    strip_docs(&mut value, "$", key, data_keys, &stripped);
+    docs.retain(|doc| !stripped.iter().any(|s| s.path == doc.path));
+    Ok(DocPolicyOutcome {
+        content: serde_json::to_string(&value)?,
+        stripped,
+        violations: violations(&docs, policy, &patterns),
+    })
+}
+
+/// A documentation string and the path of the object holding it
+struct Doc {
+    path: String,
+    text: String,
+}
+
+fn violations(docs: &[Doc], policy: &DocumentationPolicy, patterns: &[Regex]) -> Vec<DocViolation> {
+    let mut violations = Vec::new();
+
+    for doc in docs {
+        if let Some(limit) = policy.max_doc_length {
+            let length = doc.text.chars().count();
+            if length > limit {
+                violations.push(DocViolation::TooLong {
+                    path: doc.path.clone(),
+                    length,
+                    limit,
+                });
+            }
+        }
+        for pattern in patterns.iter().filter(|pattern| pattern.is_match(&doc.text)) {
+            violations.push(DocViolation::ForbiddenPattern {
+                path: doc.path.clone(),
+                pattern: pattern.as_str().to_string(),
+            });
+        }
+    }
+
+    if let Some(limit) = policy.max_total_doc_bytes {
+        let total = docs.iter().map(|doc| doc.text.len()).sum();
+        if total > limit {
+            violations.push(DocViolation::TotalTooLarge { total, limit });
+        }
+    }
+    violations
+}
+
+fn compile_patterns(policy: &DocumentationPolicy) -> Result<Vec<Regex>> {
+    policy
+        .forbidden_patterns
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern)
+                .map_err(|e| Error::ConfigError(format!("invalid forbidden documentation pattern '{}': {}", pattern, e)))
+        })
+        .collect()
+}
+
+pub(crate) fn parse_json(schema: &str, format: SerializationFormat) -> Result<Value> {
+    serde_json::from_str(schema).map_err(|e| Error::ParseError(format!("invalid {} schema: {}", format, e)))
+}
+
+pub(crate) fn data_keys(format: SerializationFormat) -> &'static [&'static str] {
+    match format {
+        SerializationFormat::Avro => AVRO_DATA_ATTRIBUTES,
+        _ => JSON_SCHEMA_DATA_KEYWORDS,
+    }
+}
+
+fn collect_docs(value: &Value, path: &str, key: &str, data_keys: &[&str], out: &mut Vec<Doc>) {
+    match value {
+        Value::Object(obj) => {
+            if let Some(text) = obj.get(key).and_then(Value::as_str) {
+                out.push(Doc {
+                    path: path.to_string(),
+                    text: text.to_string(),
+                });
+            }
+            for (name, child) in obj {
+                if name == key || data_keys.contains(&name.as_str()) {
+                    continue;
+                }
+                collect_docs(child, &format!("{}.{}", path, name), key, data_keys, out);
+            }
+        }
+        Value::Array(items) => {
+            for (idx, item) in items.iter().enumerate() {
+                collect_docs(item, &format!("{}[{}]", path, idx), key, data_keys, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn strip_docs(value: &mut Value, path: &str, key: &str, data_keys: &[&str], stripped: &[StrippedDoc]) {
+    match value {
+        Value::Object(obj) => {
+            if obj.get(key).is_some_and(Value::is_string) && stripped.iter().any(|s| s.path == path) {
+                obj.remove(key);
+            }
+            for (name, child) in obj.iter_mut() {
+                if name == key || data_keys.contains(&name.as_str()) {
+                    continue;
+                }
+                strip_docs(child, &format!("{}.{}", path, name), key, data_keys, stripped);
+            }
+        }
+        Value::Array(items) => {
+            for (idx, item) in items.iter_mut().enumerate() {
+                strip_docs(item, &format!("{}[{}]", path, idx), key, data_keys, stripped);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> DocumentationPolicy {
+        DocumentationPolicy {
+            max_doc_length: Some(20),
+            max_total_doc_bytes: None,
+            forbidden_patterns: vec![r"\.corp\.internal\b".to_string()],
+            strip_oversized: false,
+        }
+    }
+
+    const JSON_SCHEMA: &str = r#"{
+        "type": "object",
+        "description": "A payment",
+        "properties": {
+            "amount": {"type": "number", "description": "Amount charged, in the smallest currency unit"},
+            "callback": {"type": "string", "description": "pay.corp.internal"},
+            "note": {"type": "string", "default": {"description": "data, not documentation at all"}}
+        }
+    }"#;
+
+    #[test]
+    fn test_json_schema_violations() {
+        let violations = check(JSON_SCHEMA, SerializationFormat::JsonSchema, &policy()).unwrap();
+
+        assert_eq!(violations.len(), 3);
+        assert!(violations.contains(&DocViolation::TooLong {
+            path: "$.properties.amount".to_string(),
+            length: 45,
+            limit: 20,
+        }));
+        assert!(violations
+            .iter()
+            .any(|v| matches!(v, DocViolation::ForbiddenPattern { path, .. } if path == "$.properties.callback")));
+        assert!(violations.iter().all(|v| !v.path().contains("note")));
+    }
+
+    #[test]
+    fn test_avro_doc_and_total_limit() {
+        let schema = r#"{
+            "type": "record", "name": "Payment", "doc": "0123456789",
+            "fields": [
+                {"name": "amount", "type": "long", "doc": "0123456789"},
+                {"name": "currency", "type": "string", "doc": "01234"}
+            ]
+        }"#;
+        let policy = DocumentationPolicy {
+            max_total_doc_bytes: Some(20),
+            ..Default::default()
+        };
+
+        let violations = check(schema, SerializationFormat::Avro, &policy).unwrap();
+        assert_eq!(violations, vec![DocViolation::TotalTooLarge { total: 25, limit: 20 }]);
+    }
+
+    #[test]
+    fn test_strip_oversized() {
+        let policy = DocumentationPolicy {
+            strip_oversized: true,
+            ..policy()
+        };
+        let outcome = apply(JSON_SCHEMA, SerializationFormat::JsonSchema, &policy).unwrap();
+
+        assert_eq!(
+            outcome.stripped,
+            vec![StrippedDoc {
+                path: "$.properties.amount".to_string(),
+                bytes: 45,
+                reason: StripReason::TooLong,
+            }]
+        );
+        // Forbidden content is never stripped silently
+        assert_eq!(outcome.violations.len(), 1);
+        let value: Value = serde_json::from_str(&outcome.content).unwrap();
+        assert!(value["properties"]["amount"].get("description").is_none());
+        assert_eq!(value["description"], "A payment");
+    }
+
+    #[test]
+    fn test_strip_largest_first_to_fit_total() {
+        let schema = r#"{"type": "record", "name": "R", "doc": "0123456789", "fields": [
+            {"name": "a", "type": "long", "doc": "012345678901234"},
+            {"name": "b", "type": "long", "doc": "0123"}
+        ]}"#;
+        let policy = DocumentationPolicy {
+            max_total_doc_bytes: Some(16),
+            strip_oversized: true,
+            ..Default::default()
+        };
+        let outcome = apply(schema, SerializationFormat::Avro, &policy).unwrap();
+
+        let paths: Vec<_> = outcome.stripped.iter().map(|s| s.path.as_str()).collect();
+        assert_eq!(paths, ["$.fields[0]"]);
+        assert_eq!(outcome.stripped[0].reason, StripReason::TotalTooLarge);
+        assert!(outcome.violations.is_empty());
+    }
+
+    #[test]
+    fn test_compliant_content_is_untouched() {
+        let schema = "{\n  \"type\": \"string\",\n  \"description\": \"short\"\n}";
+        let policy = DocumentationPolicy {
+            strip_oversized: true,
+            ..policy()
+        };
+        let outcome = apply(schema, SerializationFormat::JsonSchema, &policy).unwrap();
+        assert_eq!(outcome.content, schema);
+        assert!(outcome.stripped.is_empty() && outcome.violations.is_empty());
+    }
+
+    #[test]
+    fn test_protobuf_and_invalid_patterns() {
+        let proto = "// internal: pay.corp.internal\nmessage Payment { int64 amount = 1; }";
+        assert!(check(proto, SerializationFormat::Protobuf, &policy()).unwrap().is_empty());
+
+        let bad = DocumentationPolicy {
+            forbidden_patterns: vec!["(".to_string()],
+            ..Default::default()
+        };
+        let err = check(JSON_SCHEMA, SerializationFormat::JsonSchema, &bad).unwrap_err();
+        assert!(matches!(err, Error::ConfigError(_)));
+    }
+}
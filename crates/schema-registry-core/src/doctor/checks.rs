@@ -0,0 +1,440 @@
+//! Built-in diagnostic checks
+//!
+//! Platform probes shell out to `df` and read `/proc/self/limits` rather than
+//! calling into libc; where those are unavailable the check warns that it
+//! could not tell instead of failing.
+
+use super::{CheckOutcome, DiagnosticCheck, DiagnosticContext};
+use crate::config_manager_adapter::{create_adapter_for, ConfigConsumer, SchemaPolicies, ANNOTATION_TYPES};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Free space below which `disk-space` fails, in bytes
+pub const MIN_FREE_DISK_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Free space below which `disk-space` warns, in bytes
+pub const LOW_FREE_DISK_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Open file soft limit below which `fd-limit` fails
+pub const MIN_OPEN_FILES: u64 = 256;
+
+/// Open file soft limit below which `fd-limit` warns
+pub const LOW_OPEN_FILES: u64 = 1024;
+
+/// Naming conventions the field naming rule enforces
+const NAMING_CONVENTIONS: &[&str] = &["snake_case", "camelCase", "PascalCase"];
+
+/// The checks every doctor run starts with
+pub fn builtin_checks() -> Vec<Box<dyn DiagnosticCheck>> {
+    vec![
+        Box::new(ConfigSourceCheck),
+        Box::new(PolicyLintCheck),
+        Box::new(StorageProbeCheck),
+        Box::new(DiskSpaceCheck),
+        Box::new(FileDescriptorCheck),
+        Box::new(BenchmarkOutputCheck),
+    ]
+}
+
+/// Config Manager storage exists and the configuration loads
+pub struct ConfigSourceCheck;
+
+impl DiagnosticCheck for ConfigSourceCheck {
+    fn id(&self) -> &str {
+        "config-source"
+    }
+
+    fn description(&self) -> &str {
+        "Config Manager storage is reachable and the global config loads"
+    }
+
+    fn run(&self, ctx: &DiagnosticContext) -> CheckOutcome {
+        let path = &ctx.config_storage_path;
+        if !path.is_dir() {
+            return CheckOutcome::warn(
+                format!("{} does not exist; built-in defaults will be used", path.display()),
+                "Pass --config-path pointing at the Config Manager storage directory",
+            );
+        }
+        let loaded = create_adapter_for(path, &ctx.environment).and_then(|adapter| adapter.load_global_config());
+        match loaded {
+            Ok(_) => CheckOutcome::pass(format!("loaded {} config from {}", ctx.environment, path.display())),
+            Err(e) => CheckOutcome::fail(
+                format!("cannot load {} config from {}: {}", ctx.environment, path.display(), e),
+                "Check the environment name (dev, staging, prod) and the files under the config path",
+            ),
+        }
+    }
+}
+
+/// Schema policies load and their patterns and settings are valid
+pub struct PolicyLintCheck;
+
+impl DiagnosticCheck for PolicyLintCheck {
+    fn id(&self) -> &str {
+        "policy-lint"
+    }
+
+    fn description(&self) -> &str {
+        "Schema policies have valid regexes, naming conventions and annotation types"
+    }
+
+    fn run(&self, ctx: &DiagnosticContext) -> CheckOutcome {
+        if !ctx.config_storage_path.is_dir() {
+            return CheckOutcome::pass("no Config Manager storage; default policies in effect");
+        }
+        let policies = match create_adapter_for(&ctx.config_storage_path, &ctx.environment)
+            .and_then(|adapter| adapter.load_schema_policies())
+        {
+            Ok(policies) => policies,
+            Err(e) => {
+                return CheckOutcome::fail(
+                    format!("cannot load schema policies: {}", e),
+                    "Fix the schema-policies entry in Config Manager",
+                )
+            }
+        };
+
+        let (errors, warnings) = lint_policies(&policies);
+        if !errors.is_empty() {
+            CheckOutcome::fail(errors.join("; "), "Fix the listed policy entries; invalid rules reject every schema")
+        } else if !warnings.is_empty() {
+            CheckOutcome::warn(warnings.join("; "), "Use one of the supported values listed in the policy docs")
+        } else {
+            CheckOutcome::pass("schema policies are valid")
+        }
+    }
+}
+
+/// Problems in `policies`, as (errors, warnings)
+pub fn lint_policies(policies: &SchemaPolicies) -> (Vec<String>, Vec<String>) {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    for pattern in &policies.documentation.forbidden_patterns {
+        if let Err(e) = Regex::new(pattern) {
+            errors.push(format!("documentation.forbidden_patterns '{}': {}", pattern, e));
+        }
+    }
+    for rule in &policies.custom_rules {
+        if let Some(pattern) = &rule.pattern {
+            if let Err(e) = Regex::new(pattern) {
+                errors.push(format!("custom rule '{}' pattern: {}", rule.name, e));
+            }
+        }
+    }
+    for (annotation, json_type) in &policies.annotation_types {
+        if !ANNOTATION_TYPES.contains(&json_type.as_str()) {
+            errors.push(format!("annotation_types.{}: unknown type '{}'", annotation, json_type));
+        }
+    }
+    warnings.extend(lint_waivers(policies));
+    if !NAMING_CONVENTIONS.contains(&policies.field_naming.convention.as_str()) {
+        warnings.push(format!(
+            "field_naming.convention '{}' is not enforced (expected one of {})",
+            policies.field_naming.convention,
+            NAMING_CONVENTIONS.join(", ")
+        ));
+    }
+
+    errors.sort();
+    (errors, warnings)
+}
+
+/// Waivers in `policies` that can never downgrade a finding
+pub fn lint_waivers(policies: &SchemaPolicies) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for waiver in &policies.waivers {
+        if waiver.rules.is_empty() {
+            warnings.push(format!("waiver for '{}' lists no rules", waiver.subject));
+        }
+        for rule in &waiver.rules {
+            if rule != "*" && !policies.has_rule(rule) {
+                warnings.push(format!("waiver for '{}' names unknown rule '{}'", waiver.subject, rule));
+            }
+        }
+    }
+    warnings
+}
+
+/// The schema storage directory can be written and read back
+pub struct StorageProbeCheck;
+
+impl DiagnosticCheck for StorageProbeCheck {
+    fn id(&self) -> &str {
+        "storage-probe"
+    }
+
+    fn description(&self) -> &str {
+        "Schema storage directory is readable and writable"
+    }
+
+    fn run(&self, ctx: &DiagnosticContext) -> CheckOutcome {
+        let dir = &ctx.storage_dir;
+        if !dir.exists() {
+            return CheckOutcome::warn(
+                format!("{} does not exist", dir.display()),
+                "Create the directory or pass --storage-dir",
+            );
+        }
+        match probe_dir(dir) {
+            Ok(()) => CheckOutcome::pass(format!("{} is readable and writable", dir.display())),
+            Err(e) => CheckOutcome::fail(
+                format!("cannot write and read back {}: {}", dir.display(), e),
+                "Grant the registry user read/write access or move storage off a read-only mount",
+            ),
+        }
+    }
+}
+
+/// Enough free space where schemas are stored
+pub struct DiskSpaceCheck;
+
+impl DiagnosticCheck for DiskSpaceCheck {
+    fn id(&self) -> &str {
+        "disk-space"
+    }
+
+    fn description(&self) -> &str {
+        "Free disk space on the schema storage volume"
+    }
+
+    fn run(&self, ctx: &DiagnosticContext) -> CheckOutcome {
+        let dir = existing_ancestor(&ctx.storage_dir);
+        let Some(free) = free_disk_bytes(&dir) else {
+            return CheckOutcome::warn(
+                format!("could not determine free space for {}", dir.display()),
+                "Check free space manually with df",
+            );
+        };
+        let message = format!("{} MiB free on {}", free / (1024 * 1024), dir.display());
+        if free < MIN_FREE_DISK_BYTES {
+            CheckOutcome::fail(message, "Free up space or move storage to a larger volume")
+        } else if free < LOW_FREE_DISK_BYTES {
+            CheckOutcome::warn(message, "Free up space before registering large schema sets")
+        } else {
+            CheckOutcome::pass(message)
+        }
+    }
+}
+
+/// Open file limit high enough for storage and connections
+pub struct FileDescriptorCheck;
+
+impl DiagnosticCheck for FileDescriptorCheck {
+    fn id(&self) -> &str {
+        "fd-limit"
+    }
+
+    fn description(&self) -> &str {
+        "Soft limit on open file descriptors"
+    }
+
+    fn run(&self, _ctx: &DiagnosticContext) -> CheckOutcome {
+        let remediation = format!("Raise the limit to at least {} (ulimit -n, or LimitNOFILE for systemd)", LOW_OPEN_FILES);
+        match open_files_limit() {
+            None => CheckOutcome::warn("could not determine the open file limit", remediation),
+            Some(OpenFilesLimit::Unlimited) => CheckOutcome::pass("open files: unlimited"),
+            Some(OpenFilesLimit::Limited(limit)) if limit < MIN_OPEN_FILES => {
+                CheckOutcome::fail(format!("open files limited to {}", limit), remediation)
+            }
+            Some(OpenFilesLimit::Limited(limit)) if limit < LOW_OPEN_FILES => {
+                CheckOutcome::warn(format!("open files limited to {}", limit), remediation)
+            }
+            Some(OpenFilesLimit::Limited(limit)) => CheckOutcome::pass(format!("open files limited to {}", limit)),
+        }
+    }
+}
+
+/// Benchmark results can be written to the output directory
+pub struct BenchmarkOutputCheck;
+
+impl DiagnosticCheck for BenchmarkOutputCheck {
+    fn id(&self) -> &str {
+        "benchmark-output"
+    }
+
+    fn description(&self) -> &str {
+        "Benchmark output directory is writable"
+    }
+
+    fn run(&self, ctx: &DiagnosticContext) -> CheckOutcome {
+        // The benchmark command creates missing directories, so probe the
+        // closest one that exists
+        let dir = existing_ancestor(&ctx.benchmark_output_dir);
+        match probe_dir(&dir) {
+            Ok(()) => CheckOutcome::pass(format!("{} is writable", ctx.benchmark_output_dir.display())),
+            Err(e) => CheckOutcome::fail(
+                format!("cannot write to {}: {}", dir.display(), e),
+                "Pass --output to a writable directory; CI checkouts are sometimes mounted read-only",
+            ),
+        }
+    }
+}
+
+/// Write a probe file to `dir`, read it back and remove it
+fn probe_dir(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(format!(".schema-registry-doctor-{}", std::process::id()));
+    let contents = b"schema-registry doctor probe";
+    let result = fs::write(&probe, contents).and_then(|()| fs::read(&probe)).and_then(|read| {
+        if read == contents {
+            Ok(())
+        } else {
+            Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "probe file read back differently"))
+        }
+    });
+    let _ = fs::remove_file(&probe);
+    result
+}
+
+/// `path` or its closest existing ancestor; `.` if none exists
+fn existing_ancestor(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find(|ancestor| !ancestor.as_os_str().is_empty() && ancestor.exists())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Available bytes on the filesystem holding `path`, from `df -Pk`
+fn free_disk_bytes(path: &Path) -> Option<u64> {
+    let output = Command::new("df").arg("-Pk").arg(path).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_df_available(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Available kilobytes in POSIX `df -Pk` output, in bytes
+fn parse_df_available(output: &str) -> Option<u64> {
+    let line = output.lines().nth(1)?;
+    let available_kb: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available_kb * 1024)
+}
+
+/// Soft limit on open files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OpenFilesLimit {
+    Unlimited,
+    Limited(u64),
+}
+
+fn open_files_limit() -> Option<OpenFilesLimit> {
+    if let Ok(limits) = fs::read_to_string("/proc/self/limits") {
+        return parse_proc_limits(&limits);
+    }
+    let output = Command::new("sh").args(["-c", "ulimit -n"]).output().ok()?;
+    parse_limit_value(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+/// Soft "Max open files" limit from `/proc/self/limits`
+fn parse_proc_limits(limits: &str) -> Option<OpenFilesLimit> {
+    let line = limits.lines().find(|line| line.starts_with("Max open files"))?;
+    parse_limit_value(line.trim_start_matches("Max open files").split_whitespace().next()?)
+}
+
+fn parse_limit_value(value: &str) -> Option<OpenFilesLimit> {
+    if value == "unlimited" {
+        return Some(OpenFilesLimit::Unlimited);
+    }
+    value.parse().ok().map(OpenFilesLimit::Limited)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_manager_adapter::{CustomPolicyRule, PolicyWaiver};
+    use crate::doctor::CheckStatus;
+
+    fn context_in(dir: &Path) -> DiagnosticContext {
+        DiagnosticContext {
+            config_storage_path: dir.join("missing-config"),
+            environment: "dev".to_string(),
+            storage_dir: dir.to_path_buf(),
+            benchmark_output_dir: dir.join("bench/output"),
+        }
+    }
+
+    #[test]
+    fn test_lint_policies_reports_invalid_regexes_and_types() {
+        let mut policies = SchemaPolicies::default();
+        policies.documentation.forbidden_patterns = vec!["(unclosed".to_string(), "ok".to_string()];
+        policies.custom_rules.push(CustomPolicyRule {
+            name: "ids".to_string(),
+            description: String::new(),
+            pattern: Some("[".to_string()),
+            mandatory: true,
+        });
+        policies.annotation_types.insert("x-owner".to_string(), "str".to_string());
+        policies.field_naming.convention = "kebab-case".to_string();
+
+        let (errors, warnings) = lint_policies(&policies);
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors.iter().any(|e| e.starts_with("custom rule 'ids'")));
+        assert!(errors.iter().any(|e| e.contains("unknown type 'str'")));
+        assert_eq!(warnings.len(), 1);
+
+        assert_eq!(lint_policies(&SchemaPolicies::default()), (vec![], vec![]));
+    }
+
+    #[test]
+    fn test_lint_waivers_flags_rules_that_match_nothing() {
+        let waiver = |rules: &[&str]| PolicyWaiver {
+            subject: "payments-*".to_string(),
+            rules: rules.iter().map(|rule| rule.to_string()).collect(),
+            expires: chrono::NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            approver: "governance".to_string(),
+            reason: "legacy producer".to_string(),
+        };
+        let mut policies = SchemaPolicies::default();
+        policies.custom_rules.push(CustomPolicyRule {
+            name: "ids".to_string(),
+            description: String::new(),
+            pattern: None,
+            mandatory: false,
+        });
+        policies.waivers = vec![
+            waiver(&["field-naming-policy", "custom-policy-ids"]),
+            waiver(&["*"]),
+            waiver(&["field-naming"]),
+            waiver(&[]),
+        ];
+
+        let warnings = lint_waivers(&policies);
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings[0].contains("unknown rule 'field-naming'"));
+        assert!(warnings[1].contains("lists no rules"));
+    }
+
+    #[test]
+    fn test_directory_probes() {
+        let dir = std::env::temp_dir().join(format!("doctor-checks-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let ctx = context_in(&dir);
+
+        assert_eq!(StorageProbeCheck.run(&ctx).status, CheckStatus::Pass);
+        // Missing output directories are created on write, so their parent is probed
+        assert_eq!(BenchmarkOutputCheck.run(&ctx).status, CheckStatus::Pass);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0, "probe files must be removed");
+
+        let missing = context_in(&dir.join("absent"));
+        assert_eq!(StorageProbeCheck.run(&missing).status, CheckStatus::Warn);
+        assert_eq!(ConfigSourceCheck.run(&missing).status, CheckStatus::Warn);
+        assert_eq!(PolicyLintCheck.run(&missing).status, CheckStatus::Pass);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_platform_output() {
+        let df = "Filesystem 1024-blocks Used Available Capacity Mounted on\n/dev/sda1 1000 400 600 40% /\n";
+        assert_eq!(parse_df_available(df), Some(600 * 1024));
+        assert_eq!(parse_df_available("garbage"), None);
+
+        let limits = "Limit                     Soft Limit           Hard Limit           Units\n\
+                      Max open files            1024                 4096                 files\n";
+        assert_eq!(parse_proc_limits(limits), Some(OpenFilesLimit::Limited(1024)));
+        assert_eq!(parse_limit_value("unlimited"), Some(OpenFilesLimit::Unlimited));
+    }
+}
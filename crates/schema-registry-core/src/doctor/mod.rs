@@ -0,0 +1,300 @@
+//! Setup diagnostics
+//!
+//! A [`Doctor`] runs a series of named [`DiagnosticCheck`]s against a
+//! [`DiagnosticContext`] and collects their outcomes into a [`DoctorReport`].
+//! Each check reports [`CheckStatus::Pass`], `Warn` or `Fail`, with
+//! remediation text for anything that isn't a pass.
+//!
+//! The built-in checks live in [`checks`]. Downstream crates add their own
+//! with [`register_check`]; [`Doctor::with_registered`] picks them up. A
+//! check registered under the id of an existing one replaces it.
+
+pub mod checks;
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// Result of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            CheckStatus::Pass => "pass",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "fail",
+        };
+        f.write_str(label)
+    }
+}
+
+/// What a check found, and how to fix it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckOutcome {
+    pub status: CheckStatus,
+    pub message: String,
+    /// How to resolve a warning or failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+}
+
+impl CheckOutcome {
+    pub fn pass(message: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Pass,
+            message: message.into(),
+            remediation: None,
+        }
+    }
+
+    pub fn warn(message: impl Into<String>, remediation: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Warn,
+            message: message.into(),
+            remediation: Some(remediation.into()),
+        }
+    }
+
+    pub fn fail(message: impl Into<String>, remediation: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Fail,
+            message: message.into(),
+            remediation: Some(remediation.into()),
+        }
+    }
+}
+
+/// Locations the checks inspect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticContext {
+    /// Config Manager storage directory
+    pub config_storage_path: PathBuf,
+    /// Config Manager environment name (`dev`, `staging`, `prod`)
+    pub environment: String,
+    /// Directory schema blobs are stored in
+    pub storage_dir: PathBuf,
+    /// Directory benchmark results are written to
+    pub benchmark_output_dir: PathBuf,
+}
+
+impl Default for DiagnosticContext {
+    fn default() -> Self {
+        Self {
+            config_storage_path: PathBuf::from("./config"),
+            environment: "dev".to_string(),
+            storage_dir: PathBuf::from("./data"),
+            benchmark_output_dir: PathBuf::from("benchmarks/output"),
+        }
+    }
+}
+
+/// A named diagnostic
+pub trait DiagnosticCheck: Send + Sync {
+    /// Stable identifier, used with `--skip`
+    fn id(&self) -> &str;
+
+    /// One-line description of what is checked
+    fn description(&self) -> &str;
+
+    /// Run the check
+    fn run(&self, ctx: &DiagnosticContext) -> CheckOutcome;
+}
+
+/// Constructor of a registered check
+pub type CheckFactory = fn() -> Box<dyn DiagnosticCheck>;
+
+static REGISTERED_CHECKS: RwLock<Vec<CheckFactory>> = RwLock::new(Vec::new());
+
+/// Register a check to be run by every [`Doctor::with_registered`]
+pub fn register_check(factory: CheckFactory) {
+    REGISTERED_CHECKS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .push(factory);
+}
+
+/// Instances of every check added with [`register_check`], in registration order
+pub fn registered_checks() -> Vec<Box<dyn DiagnosticCheck>> {
+    REGISTERED_CHECKS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .iter()
+        .map(|factory| factory())
+        .collect()
+}
+
+/// Outcome of one check in a report
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport {
+    pub id: String,
+    pub description: String,
+    #[serde(flatten)]
+    pub outcome: CheckOutcome,
+}
+
+/// Outcomes of a doctor run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DoctorReport {
+    /// Checks that ran, in order
+    pub checks: Vec<CheckReport>,
+    /// Ids of checks left out with `--skip`
+    pub skipped: Vec<String>,
+}
+
+impl DoctorReport {
+    /// Number of checks that ended with `status`
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|check| check.outcome.status == status).count()
+    }
+
+    /// Whether any check failed
+    pub fn has_failures(&self) -> bool {
+        self.count(CheckStatus::Fail) > 0
+    }
+}
+
+/// Ordered set of checks to run
+#[derive(Default)]
+pub struct Doctor {
+    checks: Vec<Box<dyn DiagnosticCheck>>,
+    skip: BTreeSet<String>,
+}
+
+impl Doctor {
+    /// A doctor with no checks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A doctor with the built-in checks followed by the registered ones
+    pub fn with_defaults() -> Self {
+        checks::builtin_checks()
+            .into_iter()
+            .fold(Self::new(), Self::with_check)
+            .with_registered()
+    }
+
+    /// Add a check, replacing any existing check with the same id
+    pub fn with_check(mut self, check: Box<dyn DiagnosticCheck>) -> Self {
+        match self.checks.iter_mut().find(|existing| existing.id() == check.id()) {
+            Some(existing) => *existing = check,
+            None => self.checks.push(check),
+        }
+        self
+    }
+
+    /// Add every check from [`register_check`]
+    pub fn with_registered(self) -> Self {
+        registered_checks().into_iter().fold(self, Self::with_check)
+    }
+
+    /// Leave out the checks with these ids
+    pub fn with_skipped<I, S>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.skip.extend(ids.into_iter().map(Into::into));
+        self
+    }
+
+    /// Ids of the checks, in run order
+    pub fn check_ids(&self) -> Vec<&str> {
+        self.checks.iter().map(|check| check.id()).collect()
+    }
+
+    /// Skipped ids that don't name any check
+    pub fn unknown_skips(&self) -> Vec<&str> {
+        self.skip
+            .iter()
+            .filter(|id| !self.checks.iter().any(|check| check.id() == id.as_str()))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Run every check that isn't skipped
+    pub fn run(&self, ctx: &DiagnosticContext) -> DoctorReport {
+        let mut report = DoctorReport::default();
+        for check in &self.checks {
+            if self.skip.contains(check.id()) {
+                report.skipped.push(check.id().to_string());
+                continue;
+            }
+            report.checks.push(CheckReport {
+                id: check.id().to_string(),
+                description: check.description().to_string(),
+                outcome: check.run(ctx),
+            });
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed(&'static str, CheckStatus);
+
+    impl DiagnosticCheck for Fixed {
+        fn id(&self) -> &str {
+            self.0
+        }
+
+        fn description(&self) -> &str {
+            "fixed outcome"
+        }
+
+        fn run(&self, _ctx: &DiagnosticContext) -> CheckOutcome {
+            match self.1 {
+                CheckStatus::Pass => CheckOutcome::pass("ok"),
+                CheckStatus::Warn => CheckOutcome::warn("hmm", "look"),
+                CheckStatus::Fail => CheckOutcome::fail("broken", "fix it"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_doctor_runs_checks_in_order_and_skips() {
+        let doctor = Doctor::new()
+            .with_check(Box::new(Fixed("a", CheckStatus::Pass)))
+            .with_check(Box::new(Fixed("b", CheckStatus::Fail)))
+            .with_check(Box::new(Fixed("c", CheckStatus::Warn)))
+            .with_skipped(["b", "nope"]);
+
+        assert_eq!(doctor.unknown_skips(), vec!["nope"]);
+        let report = doctor.run(&DiagnosticContext::default());
+        let ids: Vec<_> = report.checks.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "c"]);
+        assert_eq!(report.skipped, vec!["b"]);
+        assert_eq!(report.count(CheckStatus::Warn), 1);
+        assert!(!report.has_failures());
+    }
+
+    #[test]
+    fn test_registered_check_replaces_same_id() {
+        fn replacement() -> Box<dyn DiagnosticCheck> {
+            Box::new(Fixed("disk-space", CheckStatus::Fail))
+        }
+        register_check(replacement);
+
+        let doctor = Doctor::new()
+            .with_check(Box::new(Fixed("disk-space", CheckStatus::Pass)))
+            .with_check(Box::new(Fixed("other", CheckStatus::Pass)))
+            .with_registered();
+        assert_eq!(doctor.check_ids(), vec!["disk-space", "other"]);
+
+        let report = doctor.run(&DiagnosticContext::default());
+        assert_eq!(report.checks[0].outcome.message, "broken");
+        assert!(report.has_failures());
+    }
+}
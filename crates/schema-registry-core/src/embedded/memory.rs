@@ -0,0 +1,165 @@
+//! In-memory [`SchemaStorage`] used by the embedded registry
+
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::pagination::{paginate, Page, PageRequest};
+use crate::registration_log;
+use crate::schema::RegisteredSchema;
+use crate::traits::SchemaStorage;
+use crate::versioning::SemanticVersion;
+
+/// Point-in-time copy of an [`InMemoryStorage`]
+#[derive(Debug, Clone, Default)]
+pub struct MemorySnapshot {
+    schemas: BTreeMap<(Uuid, SemanticVersion), RegisteredSchema>,
+}
+
+impl MemorySnapshot {
+    /// Number of stored schema versions
+    pub fn len(&self) -> usize {
+        self.schemas.len()
+    }
+
+    /// Whether the snapshot holds no schemas
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty()
+    }
+}
+
+/// Schema storage held entirely in process memory
+///
+/// Versions are kept ordered, so listing and "latest version" lookups are
+/// deterministic.
+#[derive(Debug, Default)]
+pub struct InMemoryStorage {
+    schemas: RwLock<BTreeMap<(Uuid, SemanticVersion), RegisteredSchema>>,
+}
+
+impl InMemoryStorage {
+    /// Create empty storage
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy the current contents
+    pub fn snapshot(&self) -> MemorySnapshot {
+        MemorySnapshot {
+            schemas: self.schemas.read().clone(),
+        }
+    }
+
+    /// Replace the current contents with `snapshot`
+    pub fn restore(&self, snapshot: &MemorySnapshot) {
+        *self.schemas.write() = snapshot.schemas.clone();
+    }
+
+    /// Number of stored schema versions
+    pub fn len(&self) -> usize {
+        self.schemas.read().len()
+    }
+
+    /// Whether no schemas are stored
+    pub fn is_empty(&self) -> bool {
+        self.schemas.read().is_empty()
+    }
+
+    /// Every stored version, ordered by id then version
+    pub fn schemas(&self) -> Vec<RegisteredSchema> {
+        self.schemas.read().values().cloned().collect()
+    }
+
+    /// [`registration_log::fingerprint`] of the stored versions
+    pub fn fingerprint(&self) -> String {
+        registration_log::fingerprint(self.schemas.read().values())
+    }
+}
+
+#[async_trait]
+impl SchemaStorage for InMemoryStorage {
+    async fn store(&self, schema: RegisteredSchema) -> Result<()> {
+        let key = (schema.id, schema.version.clone());
+        let mut schemas = self.schemas.write();
+        if schemas.contains_key(&key) {
+            return Err(Error::SchemaAlreadyExists(format!("{}@{}", key.0, key.1)));
+        }
+        schemas.insert(key, schema);
+        Ok(())
+    }
+
+    async fn retrieve(&self, id: Uuid, version: Option<SemanticVersion>) -> Result<RegisteredSchema> {
+        let schemas = self.schemas.read();
+        let found = match version {
+            Some(version) => schemas.get(&(id, version)),
+            None => schemas
+                .iter()
+                .filter(|((schema_id, _), _)| *schema_id == id)
+                .map(|(_, schema)| schema)
+                .last(),
+        };
+        found
+            .cloned()
+            .ok_or_else(|| Error::SchemaNotFound(id.to_string()))
+    }
+
+    async fn retrieve_by_hash(&self, content_hash: &str) -> Result<Option<RegisteredSchema>> {
+        Ok(self
+            .schemas
+            .read()
+            .values()
+            .find(|schema| schema.content_hash == content_hash)
+            .cloned())
+    }
+
+    async fn update(&self, schema: RegisteredSchema) -> Result<()> {
+        let key = (schema.id, schema.version.clone());
+        match self.schemas.write().get_mut(&key) {
+            Some(existing) => {
+                *existing = schema;
+                Ok(())
+            }
+            None => Err(Error::SchemaNotFound(format!("{}@{}", key.0, key.1))),
+        }
+    }
+
+    async fn delete(&self, id: Uuid, version: SemanticVersion) -> Result<()> {
+        let key = (id, version);
+        match self.schemas.write().remove(&key) {
+            Some(_) => Ok(()),
+            None => Err(Error::SchemaNotFound(format!("{}@{}", key.0, key.1))),
+        }
+    }
+
+    async fn list_versions(&self, id: Uuid) -> Result<Vec<SemanticVersion>> {
+        Ok(self
+            .schemas
+            .read()
+            .keys()
+            .filter(|(schema_id, _)| *schema_id == id)
+            .map(|(_, version)| version.clone())
+            .collect())
+    }
+
+    async fn find_by_name(&self, namespace: &str, name: &str) -> Result<Vec<RegisteredSchema>> {
+        Ok(self
+            .schemas
+            .read()
+            .values()
+            .filter(|schema| schema.namespace == namespace && schema.name == name)
+            .cloned()
+            .collect())
+    }
+
+    async fn list_subjects(&self, page: &PageRequest) -> Result<Page<String>> {
+        let subjects: BTreeSet<String> = self
+            .schemas
+            .read()
+            .values()
+            .map(|schema| schema.fully_qualified_name())
+            .collect();
+        paginate(subjects, page, |subject| subject.as_str())
+    }
+}
@@ -0,0 +1,1201 @@
+//! Embedded, in-process registry
+//!
+//! [`EmbeddedRegistry`] runs the registration pipeline (validation, policy
+//! checks, compatibility, lifecycle transitions, storage) entirely in memory,
+//! so client services can unit-test their registration logic without files,
+//! databases or a server. Ids come from a seeded generator and timestamps
+//! from an injected [`Clock`], so runs are reproducible, and
+//! [`EmbeddedRegistry::snapshot`]/[`EmbeddedRegistry::restore`] let a test
+//! fork state cheaply.
+//!
+//! The registry only depends on the core traits: plug in the real validation
+//! engine and compatibility checker with
+//! [`EmbeddedRegistryBuilder::with_validator`] and
+//! [`EmbeddedRegistryBuilder::with_compatibility_checker`]. By default it uses
+//! [`StructuralValidator`] (the content must parse) and [`AcceptAllChecker`].
+//!
+//! Attach a [`RegistrationLog`] with
+//! [`EmbeddedRegistryBuilder::with_registration_log`] to record every
+//! registration for disaster recovery; [`EmbeddedRegistry::replay`] applies
+//! logged registrations on top of a restored backup.
+//!
+//! [`EmbeddedRegistryBuilder::with_event_publisher`] publishes a
+//! `SchemaRegistered` event for every newly stored version, e.g. to keep a
+//! [`CompatibilityMatrix`](crate::compat_matrix::CompatibilityMatrix) current.
+//!
+//! # Example
+//!
+//! ```
+//! use schema_registry_core::embedded::EmbeddedRegistry;
+//! use schema_registry_core::{SchemaInput, SerializationFormat};
+//!
+//! # async fn example() -> schema_registry_core::Result<()> {
+//! let registry = EmbeddedRegistry::builder().with_seed(42).build();
+//!
+//! let input = SchemaInput::new("com.example", "user", SerializationFormat::JsonSchema, r#"{"type":"object"}"#);
+//! let registered = registry.register(input).await?;
+//!
+//! let before = registry.snapshot();
+//! registry.delete(registered.id, registered.version.clone()).await?;
+//! registry.restore(&before);
+//!
+//! assert_eq!(registry.get(registered.id, None).await?.version, registered.version);
+//! # Ok(())
+//! # }
+//! ```
+
+mod memory;
+
+pub use memory::{InMemoryStorage, MemorySnapshot};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::config_manager_adapter::{ConfigUpdateListener, GlobalConfig, SchemaPolicies, ServerConfig};
+use crate::doc_policy::{self, StrippedDoc};
+use crate::error::{Error, Result};
+use crate::events::{EventPayload, EventType, SchemaEvent};
+use crate::pagination::{Page, PageRequest};
+use crate::registration_log::{LogEntry, LoggedOutcome, RegistrationLog};
+use crate::retry::{Deadline, RetryMetrics, RetryPolicy, RetryingStorage};
+use crate::rng::SplitMix64;
+use crate::schema::{RegisteredSchema, SchemaInput, SchemaMetadata};
+use crate::state::{SchemaLifecycle, SchemaState};
+use crate::traits::{
+    CompatibilityChecker, CompatibilityResult, EventPublisher, SchemaStorage, SchemaValidator,
+    ValidationError, ValidationResult,
+};
+use crate::types::{CompatibilityMode, SerializationFormat};
+use crate::versioning::SemanticVersion;
+
+/// Actor recorded on metadata and lifecycle transitions
+const EMBEDDED_ACTOR: &str = "embedded";
+
+// ============================================================================
+// Clock
+// ============================================================================
+
+/// Source of the current time
+pub trait Clock: Send + Sync {
+    /// Current time
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Wall-clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that only moves when told to
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl ManualClock {
+    /// Create a clock frozen at `start`
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self { now: Mutex::new(start) }
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, by: chrono::Duration) {
+        *self.now.lock() += by;
+    }
+
+    /// Set the clock to `now`
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock() = now;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new(DateTime::<Utc>::UNIX_EPOCH)
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock()
+    }
+}
+
+// ============================================================================
+// Default validation and compatibility
+// ============================================================================
+
+/// Validator that only checks the content parses in its declared format
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StructuralValidator;
+
+#[async_trait]
+impl SchemaValidator for StructuralValidator {
+    async fn validate(&self, input: &SchemaInput) -> Result<ValidationResult> {
+        self.validate_content(&input.content, input.format).await
+    }
+
+    async fn validate_content(&self, content: &str, format: SerializationFormat) -> Result<ValidationResult> {
+        let problem = match format {
+            SerializationFormat::JsonSchema => match serde_json::from_str::<serde_json::Value>(content) {
+                Ok(value) if value.is_object() || value.is_boolean() => None,
+                Ok(_) => Some("JSON Schema must be an object or a boolean".to_string()),
+                Err(e) => Some(format!("Invalid JSON: {}", e)),
+            },
+            SerializationFormat::Avro => apache_avro::Schema::parse_str(content)
+                .err()
+                .map(|e| format!("Invalid Avro schema: {}", e)),
+            SerializationFormat::Protobuf => {
+                let opens = content.matches('{').count();
+                if content.trim().is_empty() {
+                    Some("Protobuf schema is empty".to_string())
+                } else if opens != content.matches('}').count() {
+                    Some("Unbalanced braces in Protobuf schema".to_string())
+                } else {
+                    None
+                }
+            }
+        };
+
+        Ok(ValidationResult {
+            is_valid: problem.is_none(),
+            errors: problem
+                .into_iter()
+                .map(|message| ValidationError {
+                    message,
+                    field_path: None,
+                    code: "STRUCTURE".to_string(),
+                })
+                .collect(),
+            warnings: Vec::new(),
+            metadata: HashMap::new(),
+        })
+    }
+}
+
+/// Compatibility checker that accepts every change
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AcceptAllChecker;
+
+#[async_trait]
+impl CompatibilityChecker for AcceptAllChecker {
+    async fn check_compatibility(
+        &self,
+        _new_schema: &RegisteredSchema,
+        old_schema: &RegisteredSchema,
+        mode: CompatibilityMode,
+    ) -> Result<CompatibilityResult> {
+        Ok(CompatibilityResult {
+            is_compatible: true,
+            mode,
+            violations: Vec::new(),
+            checked_versions: vec![old_schema.version.clone()],
+        })
+    }
+
+    async fn check_transitive_compatibility(
+        &self,
+        _new_schema: &RegisteredSchema,
+        previous_versions: &[RegisteredSchema],
+        mode: CompatibilityMode,
+    ) -> Result<CompatibilityResult> {
+        Ok(CompatibilityResult {
+            is_compatible: true,
+            mode,
+            violations: Vec::new(),
+            checked_versions: previous_versions.iter().map(|s| s.version.clone()).collect(),
+        })
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// Point-in-time copy of an [`EmbeddedRegistry`], including its id generator
+#[derive(Debug, Clone)]
+pub struct RegistrySnapshot {
+    storage: MemorySnapshot,
+    rng: SplitMix64,
+}
+
+impl RegistrySnapshot {
+    /// Stored schema versions in the snapshot
+    pub fn storage(&self) -> &MemorySnapshot {
+        &self.storage
+    }
+}
+
+/// Builder for [`EmbeddedRegistry`]
+pub struct EmbeddedRegistryBuilder {
+    policies: SchemaPolicies,
+    validator: Arc<dyn SchemaValidator>,
+    checker: Arc<dyn CompatibilityChecker>,
+    clock: Arc<dyn Clock>,
+    seed: u64,
+    log: Option<Arc<RegistrationLog>>,
+    events: Option<Arc<dyn EventPublisher>>,
+    retry: RetryPolicy,
+}
+
+impl Default for EmbeddedRegistryBuilder {
+    fn default() -> Self {
+        Self {
+            policies: SchemaPolicies::default(),
+            validator: Arc::new(StructuralValidator),
+            checker: Arc::new(AcceptAllChecker),
+            clock: Arc::new(SystemClock),
+            seed: 0,
+            log: None,
+            events: None,
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+impl EmbeddedRegistryBuilder {
+    /// Enforce `policies` on registration
+    pub fn with_policies(mut self, policies: SchemaPolicies) -> Self {
+        self.policies = policies;
+        self
+    }
+
+    /// Validate inputs with `validator`
+    pub fn with_validator(mut self, validator: Arc<dyn SchemaValidator>) -> Self {
+        self.validator = validator;
+        self
+    }
+
+    /// Check compatibility with `checker`
+    pub fn with_compatibility_checker(mut self, checker: Arc<dyn CompatibilityChecker>) -> Self {
+        self.checker = checker;
+        self
+    }
+
+    /// Take timestamps from `clock`
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Seed the schema id generator
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Record every registration, accepted or rejected, in `log`
+    pub fn with_registration_log(mut self, log: Arc<RegistrationLog>) -> Self {
+        self.log = Some(log);
+        self
+    }
+
+    /// Publish a `SchemaRegistered` event to `publisher` for each new version
+    ///
+    /// Publishing happens after the version is stored; a failed publish is
+    /// logged and doesn't fail the registration.
+    pub fn with_event_publisher(mut self, publisher: Arc<dyn EventPublisher>) -> Self {
+        self.events = Some(publisher);
+        self
+    }
+
+    /// Bound each operation, storage retries included, by `config`'s request timeout
+    pub fn with_server_config(mut self, config: &ServerConfig) -> Self {
+        self.retry = RetryPolicy::from_server_config(config);
+        self
+    }
+
+    /// Retry transient storage failures with `policy`
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Build the registry
+    pub fn build(self) -> EmbeddedRegistry {
+        let storage = Arc::new(InMemoryStorage::new());
+        EmbeddedRegistry {
+            timeout: self.retry.default_timeout,
+            retrying: RetryingStorage::new(storage.clone(), self.retry),
+            storage,
+            policies: RwLock::new(self.policies),
+            validator: self.validator,
+            checker: self.checker,
+            clock: self.clock,
+            rng: Mutex::new(SplitMix64(self.seed)),
+            log: self.log,
+            events: self.events,
+            log_order: tokio::sync::Mutex::new(()),
+            pinned: Mutex::new(None),
+        }
+    }
+}
+
+/// Result of [`EmbeddedRegistry::register_with_outcome`]
+#[derive(Debug, Clone)]
+pub struct RegistrationOutcome {
+    /// The registered (or identical existing) version
+    pub schema: RegisteredSchema,
+    /// Documentation removed from the content before it was stored
+    pub stripped_docs: Vec<StrippedDoc>,
+}
+
+/// Result of [`EmbeddedRegistry::replay`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// Registrations re-applied
+    pub replayed: usize,
+    /// Logged rejections, which have nothing to re-apply
+    pub skipped_rejected: usize,
+    /// Checkpoints whose fingerprint matched the replayed state
+    pub checkpoints_verified: usize,
+}
+
+/// A validated registration that hasn't been stored yet
+struct Prepared {
+    outcome: RegistrationOutcome,
+    is_new: bool,
+}
+
+/// Id and timestamp a replayed registration must reuse
+#[derive(Debug, Clone, Copy)]
+struct Pinned {
+    id: Uuid,
+    at: DateTime<Utc>,
+}
+
+/// In-process schema registry backed by [`InMemoryStorage`]
+///
+/// Storage calls go through a [`RetryingStorage`], and each operation runs
+/// within a [`Deadline`] of the retry policy's timeout, unless the caller's
+/// deadline is earlier.
+pub struct EmbeddedRegistry {
+    storage: Arc<InMemoryStorage>,
+    retrying: RetryingStorage<Arc<InMemoryStorage>>,
+    timeout: Duration,
+    policies: RwLock<SchemaPolicies>,
+    validator: Arc<dyn SchemaValidator>,
+    checker: Arc<dyn CompatibilityChecker>,
+    clock: Arc<dyn Clock>,
+    rng: Mutex<SplitMix64>,
+    log: Option<Arc<RegistrationLog>>,
+    events: Option<Arc<dyn EventPublisher>>,
+    /// Held across a logged registration so log order matches storage order
+    log_order: tokio::sync::Mutex<()>,
+    pinned: Mutex<Option<Pinned>>,
+}
+
+impl Default for EmbeddedRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EmbeddedRegistry {
+    /// Create a registry with default policies, validator and checker
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    /// Start building a registry
+    pub fn builder() -> EmbeddedRegistryBuilder {
+        EmbeddedRegistryBuilder::default()
+    }
+
+    /// Underlying storage
+    pub fn storage(&self) -> Arc<InMemoryStorage> {
+        self.storage.clone()
+    }
+
+    /// Retry counters of the registry's storage
+    pub fn retry_metrics(&self) -> &RetryMetrics {
+        self.retrying.metrics()
+    }
+
+    /// Policies enforced on registration
+    pub fn policies(&self) -> SchemaPolicies {
+        self.policies.read().clone()
+    }
+
+    /// Replace the policies enforced on later registrations
+    pub fn update_policies(&self, policies: SchemaPolicies) {
+        *self.policies.write() = policies;
+    }
+
+    /// Validate, check and store a new schema version
+    ///
+    /// Registering content identical to an existing version of the same
+    /// subject returns that version unchanged.
+    pub async fn register(&self, input: SchemaInput) -> Result<RegisteredSchema> {
+        self.register_with_outcome(input).await.map(|outcome| outcome.schema)
+    }
+
+    /// [`register`](Self::register), also reporting documentation stripped by policy
+    pub async fn register_with_outcome(&self, input: SchemaInput) -> Result<RegistrationOutcome> {
+        // One budget for the whole registration, not one per storage call
+        Deadline::after(self.timeout).scope(self.register_logged(input)).await
+    }
+
+    async fn register_logged(&self, input: SchemaInput) -> Result<RegistrationOutcome> {
+        let Some(log) = &self.log else {
+            let prepared = self.prepare(input).await?;
+            return self.commit(prepared).await;
+        };
+
+        let _order = self.log_order.lock().await;
+        if log.needs_checkpoint() {
+            log.checkpoint(self.storage.fingerprint(), self.storage.len(), self.now())?;
+        }
+        let requested_at = self.now();
+        let request = input.clone();
+        let prepared = self.prepare(input).await;
+        let completed_at = self.now();
+        let committed = match prepared {
+            Ok(prepared) => self.commit(prepared).await,
+            Err(err) => Err(err),
+        };
+        let outcome = match &committed {
+            Ok(committed) => {
+                let schema = &committed.schema;
+                LoggedOutcome::Registered {
+                    id: schema.id,
+                    version: schema.version.clone(),
+                    content_hash: schema.content_hash.clone(),
+                    stored_content: (schema.content != request.content).then(|| schema.content.clone()),
+                }
+            }
+            Err(err) => LoggedOutcome::Rejected {
+                code: err.code().to_string(),
+                message: err.to_string(),
+            },
+        };
+        // Written once the version is stored, so a failed store is logged as
+        // rejected; a crash between storing and logging leaves it out of the log
+        log.record(requested_at, completed_at, request, outcome)?;
+        committed
+    }
+
+    /// Write a checkpoint of the current state to the registration log
+    ///
+    /// Call this when taking a storage backup so that replay can start from
+    /// it. Returns the checkpoint's sequence, or `None` without a log.
+    pub async fn checkpoint_log(&self) -> Result<Option<u64>> {
+        let Some(log) = &self.log else {
+            return Ok(None);
+        };
+        let _order = self.log_order.lock().await;
+        log.checkpoint(self.storage.fingerprint(), self.storage.len(), self.now())
+            .map(Some)
+    }
+
+    /// Re-apply logged registrations on top of the current state
+    ///
+    /// `entries` normally comes from [`LogContents::after`] the checkpoint
+    /// matching the restored backup. Each registration reuses its logged id
+    /// and completion time and must reproduce the logged version and content
+    /// hash; each checkpoint must match the replayed state's fingerprint.
+    /// Replay stops with [`Error::StorageError`] at the first divergence.
+    /// Replayed registrations are not written to this registry's own log.
+    ///
+    /// [`LogContents::after`]: crate::registration_log::LogContents::after
+    pub async fn replay(&self, entries: &[LogEntry]) -> Result<ReplayReport> {
+        let mut report = ReplayReport::default();
+        for entry in entries {
+            let record = match entry {
+                LogEntry::Checkpoint(checkpoint) => {
+                    let fingerprint = self.storage.fingerprint();
+                    if fingerprint != checkpoint.fingerprint {
+                        return Err(Error::StorageError(format!(
+                            "replay diverged at checkpoint {}: state fingerprint {} does not match logged {}",
+                            checkpoint.sequence, fingerprint, checkpoint.fingerprint
+                        )));
+                    }
+                    report.checkpoints_verified += 1;
+                    continue;
+                }
+                LogEntry::Registration(record) => record,
+            };
+            let LoggedOutcome::Registered {
+                id,
+                version,
+                content_hash,
+                stored_content,
+            } = &record.outcome
+            else {
+                report.skipped_rejected += 1;
+                continue;
+            };
+
+            let mut input = record.request.clone();
+            if let Some(content) = stored_content {
+                input.content = content.clone();
+            }
+            *self.pinned.lock() = Some(Pinned {
+                id: *id,
+                at: record.completed_at,
+            });
+            let replayed = match self.prepare(input).await {
+                Ok(prepared) => self.commit(prepared).await,
+                Err(err) => Err(err),
+            };
+            *self.pinned.lock() = None;
+
+            let schema = replayed.map_err(|err| {
+                Error::StorageError(format!("replay diverged at sequence {}: {}", record.sequence, err))
+            })?;
+            if schema.id != *id || &schema.version != version || &schema.content_hash != content_hash {
+                return Err(Error::StorageError(format!(
+                    "replay diverged at sequence {}: logged {}@{} ({}), replayed {}@{} ({})",
+                    record.sequence, id, version, content_hash, schema.id, schema.version, schema.content_hash
+                )));
+            }
+            report.replayed += 1;
+        }
+        Ok(report)
+    }
+
+    /// Run the pipeline up to, but not including, storage
+    async fn prepare(&self, input: SchemaInput) -> Result<Prepared> {
+        let validation = self.validator.validate(&input).await?;
+        if !validation.is_valid {
+            let messages: Vec<_> = validation.errors.iter().map(|e| e.message.as_str()).collect();
+            return Err(Error::ValidationError(messages.join("; ")));
+        }
+        let policies = self.policies();
+        self.check_policies(&input, &policies)?;
+        let (input, stripped_docs) = self.apply_documentation_policy(input, &policies)?;
+
+        let existing = self.versions(&input.namespace, &input.name).await?;
+        let content_hash = RegisteredSchema::calculate_content_hash(&input.content);
+        if let Some(same) = existing.iter().find(|schema| schema.content_hash == content_hash) {
+            return Ok(Prepared {
+                outcome: RegistrationOutcome {
+                    schema: same.clone(),
+                    stripped_docs,
+                },
+                is_new: false,
+            });
+        }
+
+        let mut candidate = self.candidate(&input, &existing)?;
+        self.transition(&mut candidate, SchemaState::Validating)?;
+        self.transition(&mut candidate, SchemaState::CompatibilityCheck)?;
+
+        let compatibility = self.check_against(&candidate, &existing).await?;
+        if !compatibility.is_compatible {
+            let violations: Vec<_> = compatibility.violations.iter().map(|v| v.to_string()).collect();
+            return Err(Error::CompatibilityError(violations.join("; ")));
+        }
+
+        self.transition(&mut candidate, SchemaState::Registered)?;
+        if input.auto_activate {
+            self.transition(&mut candidate, SchemaState::Active)?;
+            candidate.metadata.activated_at = Some(self.now());
+        }
+
+        Ok(Prepared {
+            outcome: RegistrationOutcome {
+                schema: candidate,
+                stripped_docs,
+            },
+            is_new: true,
+        })
+    }
+
+    /// Store a prepared registration unless it matched an existing version
+    async fn commit(&self, prepared: Prepared) -> Result<RegistrationOutcome> {
+        if prepared.is_new {
+            self.retrying.store(prepared.outcome.schema.clone()).await?;
+            self.publish_registered(&prepared.outcome.schema).await;
+        }
+        Ok(prepared.outcome)
+    }
+
+    async fn publish_registered(&self, schema: &RegisteredSchema) {
+        let Some(events) = &self.events else {
+            return;
+        };
+        let event = SchemaEvent::new(
+            EventType::SchemaRegistered,
+            schema.id,
+            schema.version.clone(),
+            EMBEDDED_ACTOR.to_string(),
+            EventPayload::SchemaRegistered {
+                schema_name: schema.name.clone(),
+                namespace: schema.namespace.clone(),
+                validation_result: None,
+                compatibility_result: None,
+            },
+        );
+        if let Err(err) = events.publish(event).await {
+            tracing::warn!("failed to publish registration of {}: {}", schema.fully_qualified_name(), err);
+        }
+    }
+
+    /// Fetch a schema version, or the latest version when `version` is `None`
+    pub async fn get(&self, id: Uuid, version: Option<SemanticVersion>) -> Result<RegisteredSchema> {
+        self.retrying.retrieve(id, version).await
+    }
+
+    /// All versions of a subject, oldest first
+    pub async fn versions(&self, namespace: &str, name: &str) -> Result<Vec<RegisteredSchema>> {
+        let mut versions = self.retrying.find_by_name(namespace, name).await?;
+        versions.sort_by(|a, b| a.version.cmp(&b.version));
+        Ok(versions)
+    }
+
+    /// One page of registered subjects, in ascending order
+    pub async fn subjects(&self, page: &PageRequest) -> Result<Page<String>> {
+        self.retrying.list_subjects(page).await
+    }
+
+    /// Check `input` against the registered versions of its subject without storing it
+    pub async fn check_compatibility(&self, input: &SchemaInput) -> Result<CompatibilityResult> {
+        let existing = self.versions(&input.namespace, &input.name).await?;
+        let candidate = self.candidate(input, &existing)?;
+        self.check_against(&candidate, &existing).await
+    }
+
+    /// Remove a schema version
+    pub async fn delete(&self, id: Uuid, version: SemanticVersion) -> Result<()> {
+        self.retrying.delete(id, version).await
+    }
+
+    /// Copy the current state, including the id generator
+    pub fn snapshot(&self) -> RegistrySnapshot {
+        RegistrySnapshot {
+            storage: self.storage.snapshot(),
+            rng: self.rng.lock().clone(),
+        }
+    }
+
+    /// Return to a previously taken snapshot
+    pub fn restore(&self, snapshot: &RegistrySnapshot) {
+        self.storage.restore(&snapshot.storage);
+        *self.rng.lock() = snapshot.rng.clone();
+    }
+
+    fn check_policies(&self, input: &SchemaInput, policies: &SchemaPolicies) -> Result<()> {
+        let missing: Vec<_> = policies
+            .required_metadata
+            .iter()
+            .filter(|key| !input.metadata.contains_key(*key))
+            .map(String::as_str)
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::ValidationError(format!(
+                "Missing required metadata: {}",
+                missing.join(", ")
+            )))
+        }
+    }
+
+    /// Enforce the documentation policy, stripping oversized docs if it allows
+    fn apply_documentation_policy(
+        &self,
+        mut input: SchemaInput,
+        policies: &SchemaPolicies,
+    ) -> Result<(SchemaInput, Vec<StrippedDoc>)> {
+        let outcome = doc_policy::apply(&input.content, input.format, &policies.documentation)?;
+        if !outcome.violations.is_empty() {
+            let messages: Vec<_> = outcome.violations.iter().map(ToString::to_string).collect();
+            return Err(Error::ValidationError(messages.join("; ")));
+        }
+        input.content = outcome.content;
+        Ok((input, outcome.stripped))
+    }
+
+    /// Build the draft schema that `input` would register as
+    fn candidate(&self, input: &SchemaInput, existing: &[RegisteredSchema]) -> Result<RegisteredSchema> {
+        let version = match (&input.version, existing.last()) {
+            (Some(version), _) => {
+                if existing.iter().any(|schema| &schema.version == version) {
+                    return Err(Error::SchemaAlreadyExists(format!(
+                        "{}.{}@{}",
+                        input.namespace, input.name, version
+                    )));
+                }
+                version.clone()
+            }
+            (None, Some(latest)) => {
+                let mut version = latest.version.clone();
+                version.increment_minor();
+                version
+            }
+            (None, None) => SemanticVersion::new(1, 0, 0),
+        };
+        let id = existing.first().map(|schema| schema.id).unwrap_or_else(|| self.next_id());
+
+        let now = self.now();
+        let mut lifecycle = SchemaLifecycle::new(id);
+        lifecycle.created_at = now;
+        lifecycle.updated_at = now;
+
+        Ok(RegisteredSchema {
+            id,
+            name: input.name.clone(),
+            namespace: input.namespace.clone(),
+            version,
+            format: input.format,
+            content: input.content.clone(),
+            content_hash: RegisteredSchema::calculate_content_hash(&input.content),
+            description: input.description.clone(),
+            compatibility_mode: input.compatibility_mode,
+            state: SchemaState::Draft,
+            metadata: SchemaMetadata {
+                created_at: now,
+                created_by: EMBEDDED_ACTOR.to_string(),
+                updated_at: now,
+                updated_by: EMBEDDED_ACTOR.to_string(),
+                activated_at: None,
+                deprecation: None,
+                deletion: None,
+                custom: input.metadata.clone(),
+            },
+            tags: input.tags.clone(),
+            examples: input.examples.clone(),
+            lifecycle,
+        })
+    }
+
+    async fn check_against(
+        &self,
+        candidate: &RegisteredSchema,
+        existing: &[RegisteredSchema],
+    ) -> Result<CompatibilityResult> {
+        let mode = candidate.compatibility_mode;
+        let previous = match existing.last() {
+            _ if mode == CompatibilityMode::None => &[][..],
+            Some(_) if !mode.is_transitive() => &existing[existing.len() - 1..],
+            _ => existing,
+        };
+        if previous.is_empty() {
+            return Ok(CompatibilityResult {
+                is_compatible: true,
+                mode,
+                violations: Vec::new(),
+                checked_versions: Vec::new(),
+            });
+        }
+        self.checker
+            .check_transitive_compatibility(candidate, previous, mode)
+            .await
+    }
+
+    /// Apply a lifecycle transition stamped with the injected clock
+    fn transition(&self, schema: &mut RegisteredSchema, to: SchemaState) -> Result<()> {
+        schema
+            .lifecycle
+            .transition(to, "registration".to_string(), EMBEDDED_ACTOR.to_string())?;
+        let now = self.now();
+        if let Some(last) = schema.lifecycle.state_history.last_mut() {
+            last.timestamp = now;
+        }
+        schema.lifecycle.updated_at = now;
+        schema.state = to;
+        Ok(())
+    }
+
+    /// The injected clock's time, or the pinned time during replay
+    fn now(&self) -> DateTime<Utc> {
+        match *self.pinned.lock() {
+            Some(pinned) => pinned.at,
+            None => self.clock.now(),
+        }
+    }
+
+    /// Next generated id, or the pinned id during replay
+    ///
+    /// The generator advances either way, so a replayed registry keeps
+    /// drawing the ids the original would have.
+    fn next_id(&self) -> Uuid {
+        let mut rng = self.rng.lock();
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&rng.next_u64().to_le_bytes());
+        bytes[8..].copy_from_slice(&rng.next_u64().to_le_bytes());
+        match *self.pinned.lock() {
+            Some(pinned) => pinned.id,
+            None => uuid::Builder::from_random_bytes(bytes).into_uuid(),
+        }
+    }
+}
+
+impl ConfigUpdateListener for EmbeddedRegistry {
+    fn on_config_updated(&self, _config: &GlobalConfig) {}
+
+    fn on_policies_updated(&self, policies: &SchemaPolicies) {
+        self.update_policies(policies.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::CompatibilityViolation;
+    use crate::types::CompatViolation;
+
+    const V1: &str = r#"{"type":"object","properties":{"id":{"type":"string"}}}"#;
+    const V2: &str = r#"{"type":"object","properties":{"id":{"type":"string"},"name":{"type":"string"}}}"#;
+
+    fn input(content: &str) -> SchemaInput {
+        SchemaInput::new("com.example", "user", SerializationFormat::JsonSchema, content)
+    }
+
+    /// Rejects any change that touches the `name` property
+    struct RejectNameChecker;
+
+    #[async_trait]
+    impl CompatibilityChecker for RejectNameChecker {
+        async fn check_compatibility(
+            &self,
+            new_schema: &RegisteredSchema,
+            old_schema: &RegisteredSchema,
+            mode: CompatibilityMode,
+        ) -> Result<CompatibilityResult> {
+            self.check_transitive_compatibility(new_schema, std::slice::from_ref(old_schema), mode)
+                .await
+        }
+
+        async fn check_transitive_compatibility(
+            &self,
+            new_schema: &RegisteredSchema,
+            _previous_versions: &[RegisteredSchema],
+            mode: CompatibilityMode,
+        ) -> Result<CompatibilityResult> {
+            let violations: Vec<_> = new_schema
+                .content
+                .contains("\"name\"")
+                .then(|| {
+                    let field = "name".to_string();
+                    CompatibilityViolation::new(CompatViolation::FieldAdded { field }, "$.name")
+                })
+                .into_iter()
+                .collect();
+            Ok(CompatibilityResult {
+                is_compatible: violations.is_empty(),
+                mode,
+                violations,
+                checked_versions: Vec::new(),
+            })
+        }
+    }
+
+    /// Accepts every change, but only after `delay`
+    struct SlowChecker {
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl CompatibilityChecker for SlowChecker {
+        async fn check_compatibility(
+            &self,
+            new_schema: &RegisteredSchema,
+            old_schema: &RegisteredSchema,
+            mode: CompatibilityMode,
+        ) -> Result<CompatibilityResult> {
+            self.check_transitive_compatibility(new_schema, std::slice::from_ref(old_schema), mode)
+                .await
+        }
+
+        async fn check_transitive_compatibility(
+            &self,
+            new_schema: &RegisteredSchema,
+            previous_versions: &[RegisteredSchema],
+            mode: CompatibilityMode,
+        ) -> Result<CompatibilityResult> {
+            tokio::time::sleep(self.delay).await;
+            AcceptAllChecker
+                .check_transitive_compatibility(new_schema, previous_versions, mode)
+                .await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_assigns_versions_and_dedupes() {
+        let registry = EmbeddedRegistry::new();
+
+        let v1 = registry.register(input(V1)).await.unwrap();
+        let v2 = registry.register(input(V2)).await.unwrap();
+        let again = registry.register(input(V1)).await.unwrap();
+
+        assert_eq!(v1.version, SemanticVersion::new(1, 0, 0));
+        assert_eq!(v2.version, SemanticVersion::new(1, 1, 0));
+        assert_eq!(v1.id, v2.id);
+        assert_eq!(again.version, v1.version);
+        assert_eq!(v2.state, SchemaState::Registered);
+        assert_eq!(registry.get(v1.id, None).await.unwrap().version, v2.version);
+    }
+
+    #[tokio::test]
+    async fn test_registration_is_bounded_by_the_request_deadline() {
+        let config = ServerConfig {
+            timeout_seconds: 0,
+            ..ServerConfig::default()
+        };
+        let registry = EmbeddedRegistry::builder().with_server_config(&config).build();
+
+        let err = registry.register(input(V1)).await.unwrap_err();
+        assert!(matches!(err, Error::DeadlineExceeded(_)));
+        assert_eq!(registry.retry_metrics().deadline_exhaustions(), 1);
+        assert!(registry.storage().is_empty());
+
+        // A caller's earlier deadline wins over the registry's own timeout
+        let registry = EmbeddedRegistry::new();
+        let err = Deadline::after(Duration::ZERO).scope(registry.register(input(V1))).await.unwrap_err();
+        assert!(matches!(err, Error::DeadlineExceeded(_)));
+        assert!(registry.storage().is_empty());
+
+        registry.register(input(V1)).await.unwrap();
+        assert_eq!(registry.storage().len(), 1);
+        assert_eq!(registry.retry_metrics().retries(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_register_rejects_invalid_and_incompatible() {
+        let registry = EmbeddedRegistry::builder()
+            .with_compatibility_checker(Arc::new(RejectNameChecker))
+            .build();
+
+        let err = registry.register(input("{not json")).await.unwrap_err();
+        assert!(err.is_validation_error());
+
+        registry.register(input(V1)).await.unwrap();
+        let check = registry.check_compatibility(&input(V2)).await.unwrap();
+        assert!(!check.is_compatible);
+        assert!(registry.register(input(V2)).await.unwrap_err().is_compatibility_error());
+
+        // No compatibility requirement skips the checker entirely
+        let unchecked = input(V2).with_compatibility_mode(CompatibilityMode::None);
+        assert!(registry.register(unchecked).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_required_metadata_policy() {
+        let mut policies = SchemaPolicies::default();
+        policies.required_metadata = vec!["owner".to_string()];
+        let registry = EmbeddedRegistry::builder().with_policies(policies).build();
+
+        assert!(registry.register(input(V1)).await.unwrap_err().is_validation_error());
+        assert!(registry
+            .register(input(V1).with_metadata("owner", serde_json::json!("team-a")))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_documentation_policy_rejects_then_strips_after_refresh() {
+        const DOCUMENTED: &str = r#"{"type":"object","description":"A user record kept by the accounts service"}"#;
+        let mut policies = SchemaPolicies::default();
+        policies.documentation.max_doc_length = Some(10);
+        let registry = EmbeddedRegistry::builder().with_policies(policies.clone()).build();
+
+        assert!(registry.register(input(DOCUMENTED)).await.unwrap_err().is_validation_error());
+
+        policies.documentation.strip_oversized = true;
+        registry.on_policies_updated(&policies);
+        let outcome = registry.register_with_outcome(input(DOCUMENTED)).await.unwrap();
+
+        assert_eq!(outcome.stripped_docs.len(), 1);
+        assert_eq!(outcome.stripped_docs[0].path, "$");
+        assert_eq!(outcome.schema.content, r#"{"type":"object"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_seed_and_clock_make_runs_deterministic() {
+        let start = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        let build = || {
+            EmbeddedRegistry::builder()
+                .with_seed(7)
+                .with_clock(Arc::new(ManualClock::new(start)))
+                .build()
+        };
+
+        let a = build().register(input(V1)).await.unwrap();
+        let b = build().register(input(V1)).await.unwrap();
+
+        assert_eq!(a.id, b.id);
+        assert_eq!(a.metadata.created_at, start);
+        assert!(a.lifecycle.state_history.iter().all(|t| t.timestamp == start));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_restore_forks_state() {
+        let registry = EmbeddedRegistry::builder().with_seed(1).build();
+        let base = registry.snapshot();
+
+        let first = registry.register(input(V1)).await.unwrap();
+        let after_first = registry.snapshot();
+        registry.register(input(V2)).await.unwrap();
+        assert_eq!(registry.versions("com.example", "user").await.unwrap().len(), 2);
+
+        registry.restore(&after_first);
+        assert_eq!(registry.versions("com.example", "user").await.unwrap().len(), 1);
+
+        // Restoring also rewinds the id generator
+        registry.restore(&base);
+        assert!(registry.storage().is_empty());
+        assert_eq!(registry.register(input(V1)).await.unwrap().id, first.id);
+    }
+
+    fn subject(name: &str) -> SchemaInput {
+        SchemaInput::new("com.example", name, SerializationFormat::JsonSchema, V1)
+    }
+
+    #[tokio::test]
+    async fn test_subject_pages_survive_concurrent_registrations() {
+        let registry = EmbeddedRegistry::builder().with_seed(3).build();
+        for name in ["b", "d", "f", "h"] {
+            registry.register(subject(name)).await.unwrap();
+        }
+
+        let first = registry.subjects(&PageRequest::new(2)).await.unwrap();
+        assert_eq!(first.items, vec!["com.example.b", "com.example.d"]);
+
+        // One registration lands before the cursor, one after it
+        registry.register(subject("a")).await.unwrap();
+        registry.register(subject("e")).await.unwrap();
+
+        let registry = &registry;
+        let rest = crate::pagination::collect_all(
+            PageRequest::new(2).with_cursor(first.next_cursor.unwrap()),
+            |page| async move { registry.subjects(&page).await },
+        )
+        .await
+        .unwrap();
+
+        // "a" is skipped, "e" is picked up, nothing is repeated
+        assert_eq!(rest, vec!["com.example.e", "com.example.f", "com.example.h"]);
+    }
+
+    #[tokio::test]
+    async fn test_subject_pages_survive_deletes() {
+        let registry = EmbeddedRegistry::builder().with_seed(4).build();
+        let mut registered = Vec::new();
+        for name in ["a", "b", "c", "d"] {
+            registered.push(registry.register(subject(name)).await.unwrap());
+        }
+
+        let first = registry.subjects(&PageRequest::new(2)).await.unwrap();
+        assert_eq!(first.items, vec!["com.example.a", "com.example.b"]);
+
+        // Deleting the cursor's own item must not invalidate the cursor
+        for schema in [&registered[1], &registered[2]] {
+            registry.delete(schema.id, schema.version.clone()).await.unwrap();
+        }
+
+        let next = registry
+            .subjects(&PageRequest::new(2).with_cursor(first.next_cursor.unwrap()))
+            .await
+            .unwrap();
+        assert_eq!(next.items, vec!["com.example.d"]);
+        assert!(next.is_last());
+    }
+
+    #[tokio::test]
+    async fn test_registration_log_replays_onto_backup() {
+        use crate::registration_log::{read_log, RegistrationLogConfig};
+
+        let dir = std::env::temp_dir().join(format!("embedded-wal-{}", Uuid::new_v4()));
+        let path = dir.join("reg.wal");
+        let clock = Arc::new(ManualClock::default());
+        let log = Arc::new(RegistrationLog::open(RegistrationLogConfig::new(&path)).unwrap());
+        let registry = EmbeddedRegistry::builder()
+            .with_seed(5)
+            .with_clock(clock.clone())
+            .with_registration_log(log)
+            .build();
+
+        registry.register(input(V1)).await.unwrap();
+        let backup = registry.snapshot();
+        registry.checkpoint_log().await.unwrap();
+        clock.advance(chrono::Duration::seconds(30));
+        registry.register(input(V2)).await.unwrap();
+        assert!(registry.register(input("{not json")).await.is_err());
+        let orders = registry.register(subject("orders")).await.unwrap();
+
+        let contents = read_log(&path).unwrap();
+        let recovered = EmbeddedRegistry::new();
+        recovered.restore(&backup);
+        let checkpoint = contents.checkpoint_matching(&recovered.storage().fingerprint()).unwrap();
+        let report = recovered.replay(contents.after(checkpoint.sequence)).await.unwrap();
+
+        assert_eq!(
+            report,
+            ReplayReport {
+                replayed: 2,
+                skipped_rejected: 1,
+                checkpoints_verified: 0,
+            }
+        );
+        assert_eq!(recovered.storage().fingerprint(), registry.storage().fingerprint());
+        let replayed = recovered.get(orders.id, None).await.unwrap();
+        assert_eq!(replayed.metadata.created_at, orders.metadata.created_at);
+
+        // Replaying onto the wrong starting state is caught
+        let err = EmbeddedRegistry::new()
+            .replay(contents.after(checkpoint.sequence))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("replay diverged"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_store_is_logged_as_rejected() {
+        use crate::registration_log::{read_log, RegistrationLogConfig};
+
+        let dir = std::env::temp_dir().join(format!("embedded-wal-{}", Uuid::new_v4()));
+        let path = dir.join("reg.wal");
+        let log = Arc::new(RegistrationLog::open(RegistrationLogConfig::new(&path)).unwrap());
+        // The checker outlasts the deadline, so the store after it fails
+        let policy = RetryPolicy {
+            default_timeout: Duration::from_millis(100),
+            ..RetryPolicy::default()
+        };
+        let registry = EmbeddedRegistry::builder()
+            .with_retry_policy(policy)
+            .with_compatibility_checker(Arc::new(SlowChecker {
+                delay: Duration::from_secs(1),
+            }))
+            .with_registration_log(log)
+            .build();
+
+        registry.register(input(V1)).await.unwrap();
+        let err = registry.register(input(V2)).await.unwrap_err();
+        assert!(matches!(err, Error::DeadlineExceeded(_)));
+        assert_eq!(registry.storage().len(), 1);
+
+        let contents = read_log(&path).unwrap();
+        let LogEntry::Registration(failed) = contents.entries.last().unwrap() else {
+            panic!("expected a registration record last");
+        };
+        assert!(matches!(&failed.outcome, LoggedOutcome::Rejected { code, .. } if code == "DEADLINE_EXCEEDED"));
+
+        let recovered = EmbeddedRegistry::new();
+        let report = recovered.replay(&contents.entries).await.unwrap();
+        assert_eq!(
+            report,
+            ReplayReport {
+                replayed: 1,
+                skipped_rejected: 1,
+                checkpoints_verified: 1,
+            }
+        );
+        assert_eq!(recovered.storage().fingerprint(), registry.storage().fingerprint());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -20,6 +20,10 @@ pub enum Error {
     #[error("Validation failed: {0}")]
     ValidationError(String),
 
+    /// Input larger than the configured schema size limit
+    #[error("Schema exceeds the {limit} byte size limit ({seen} bytes read before aborting)")]
+    SizeExceeded { limit: usize, seen: u64 },
+
     /// Compatibility error
     #[error("Compatibility check failed: {0}")]
     CompatibilityError(String),
@@ -52,6 +56,14 @@ pub enum Error {
     #[error("Storage error: {0}")]
     StorageError(String),
 
+    /// Transient storage error (lock contention, busy database); safe to retry
+    #[error("Storage busy: {0}")]
+    StorageBusy(String),
+
+    /// Request deadline exceeded
+    #[error("Deadline exceeded: {0}")]
+    DeadlineExceeded(String),
+
     /// Event publishing error
     #[error("Event publish error: {0}")]
     EventPublishError(String),
@@ -81,6 +93,30 @@ pub enum Error {
     Other(#[from] anyhow::Error),
 }
 
+crate::error_codes!(Error {
+    SchemaNotFound => "SCHEMA_NOT_FOUND",
+    SchemaAlreadyExists => "SCHEMA_ALREADY_EXISTS",
+    ValidationError => "SCHEMA_VALIDATION_FAILED",
+    SizeExceeded => "SCHEMA_TOO_LARGE",
+    CompatibilityError => "COMPATIBILITY_CHECK_FAILED",
+    StateTransitionError => "INVALID_STATE_TRANSITION",
+    RegistrationError => "REGISTRATION_FAILED",
+    DeprecationError => "DEPRECATION_FAILED",
+    RollbackError => "ROLLBACK_FAILED",
+    ParseError => "SCHEMA_PARSE_ERROR",
+    SerializationError => "SERIALIZATION_ERROR",
+    StorageError => "STORAGE_ERROR",
+    StorageBusy => "STORAGE_BUSY",
+    DeadlineExceeded => "DEADLINE_EXCEEDED",
+    EventPublishError => "EVENT_PUBLISH_FAILED",
+    ConfigError => "CONFIG_ERROR",
+    SecurityError => "SECURITY_ERROR",
+    InternalError => "REGISTRY_INTERNAL_ERROR",
+    IoError => "IO_ERROR",
+    JsonError => "JSON_ERROR",
+    Other => "UNCLASSIFIED_ERROR",
+});
+
 impl Error {
     /// Check if the error is a not found error
     pub fn is_not_found(&self) -> bool {
@@ -89,11 +125,25 @@ impl Error {
 
     /// Check if the error is a validation error
     pub fn is_validation_error(&self) -> bool {
-        matches!(self, Error::ValidationError(_))
+        matches!(self, Error::ValidationError(_) | Error::SizeExceeded { .. })
     }
 
     /// Check if the error is a compatibility error
     pub fn is_compatibility_error(&self) -> bool {
         matches!(self, Error::CompatibilityError(_))
     }
+
+    /// Check if the operation may succeed when retried unchanged
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::StorageBusy(_) => true,
+            Error::IoError(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::WouldBlock
+                    | std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::TimedOut
+            ),
+            _ => false,
+        }
+    }
 }
@@ -0,0 +1,373 @@
+//! Catalog of error codes
+//!
+//! Every error enum that can reach a user gets a stable code per variant via
+//! [`error_codes!`](crate::error_codes), and every crate registers an
+//! [`ErrorEntry`] for each of its codes. Entries carry what support needs:
+//! category, whether a retry can help, a user-facing description and a
+//! remediation hint. Binaries assemble an [`ErrorCatalog`] from the entries of
+//! the crates they link and render it with [`ErrorCatalog::to_markdown`] or as
+//! JSON.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Broad class of an error, used to group the rendered catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    NotFound,
+    Conflict,
+    Validation,
+    Compatibility,
+    Lifecycle,
+    Storage,
+    Availability,
+    Configuration,
+    Security,
+    Client,
+    Internal,
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ErrorCategory::NotFound => "Not found",
+            ErrorCategory::Conflict => "Conflict",
+            ErrorCategory::Validation => "Validation",
+            ErrorCategory::Compatibility => "Compatibility",
+            ErrorCategory::Lifecycle => "Lifecycle",
+            ErrorCategory::Storage => "Storage",
+            ErrorCategory::Availability => "Availability",
+            ErrorCategory::Configuration => "Configuration",
+            ErrorCategory::Security => "Security",
+            ErrorCategory::Client => "Client",
+            ErrorCategory::Internal => "Internal",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Documentation for a single error code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ErrorEntry {
+    /// Stable code, unique across the catalog
+    pub code: &'static str,
+    pub category: ErrorCategory,
+    /// Whether retrying the same request can succeed, for at least some causes
+    pub retryable: bool,
+    /// What happened, in user-facing terms
+    pub description: &'static str,
+    /// What to do about it
+    pub remediation: &'static str,
+}
+
+/// Error entries registered by one or more crates
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(transparent)]
+pub struct ErrorCatalog {
+    entries: Vec<ErrorEntry>,
+}
+
+impl ErrorCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a crate's entries
+    ///
+    /// # Panics
+    ///
+    /// If a code is already registered; codes must be unique across crates.
+    pub fn register(mut self, entries: &[ErrorEntry]) -> Self {
+        for entry in entries {
+            assert!(
+                !self.contains(entry.code),
+                "error code {} registered twice",
+                entry.code
+            );
+            self.entries.push(*entry);
+        }
+        self
+    }
+
+    /// All entries, in registration order
+    pub fn entries(&self) -> &[ErrorEntry] {
+        &self.entries
+    }
+
+    pub fn lookup(&self, code: &str) -> Option<&ErrorEntry> {
+        self.entries.iter().find(|entry| entry.code == code)
+    }
+
+    pub fn contains(&self, code: &str) -> bool {
+        self.lookup(code).is_some()
+    }
+
+    /// Entries grouped by category, codes sorted within each group
+    pub fn by_category(&self) -> BTreeMap<ErrorCategory, Vec<&ErrorEntry>> {
+        let mut groups: BTreeMap<ErrorCategory, Vec<&ErrorEntry>> = BTreeMap::new();
+        for entry in &self.entries {
+            groups.entry(entry.category).or_default().push(entry);
+        }
+        for entries in groups.values_mut() {
+            entries.sort_by_key(|entry| entry.code);
+        }
+        groups
+    }
+
+    /// Render the catalog as a Markdown reference, one table per category
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Error Reference\n\n");
+        out.push_str("Generated from the error catalog; do not edit by hand.\n");
+
+        for (category, entries) in self.by_category() {
+            out.push_str(&format!("\n## {}\n\n", category));
+            out.push_str("| Code | Retryable | Description | Remediation |\n");
+            out.push_str("|------|-----------|-------------|-------------|\n");
+            for entry in entries {
+                out.push_str(&format!(
+                    "| `{}` | {} | {} | {} |\n",
+                    entry.code,
+                    if entry.retryable { "yes" } else { "no" },
+                    entry.description.replace('|', "\\|"),
+                    entry.remediation.replace('|', "\\|"),
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// Give each variant of an error enum a stable code
+///
+/// Generates `code(&self)` and a `CODES` list of every variant's code. The
+/// match is exhaustive, so adding a variant without a code fails to compile,
+/// and `CODES` lets tests check that the catalog covers the whole enum.
+///
+/// ```ignore
+/// error_codes!(Error {
+///     SchemaNotFound => "SCHEMA_NOT_FOUND",
+///     StorageBusy => "STORAGE_BUSY",
+/// });
+/// ```
+#[macro_export]
+macro_rules! error_codes {
+    ($ty:ident { $($variant:ident => $code:literal),+ $(,)? }) => {
+        impl $ty {
+            /// Stable code of this error in the error catalog
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $($ty::$variant { .. } => $code,)+
+                }
+            }
+
+            /// Codes of every variant, in declaration order
+            pub const CODES: &'static [&'static str] = &[$($code),+];
+        }
+    };
+}
+
+use ErrorCategory::*;
+
+/// Entries for [`crate::Error`]
+pub const CORE_ERRORS: &[ErrorEntry] = &[
+    ErrorEntry {
+        code: "SCHEMA_NOT_FOUND",
+        category: NotFound,
+        retryable: false,
+        description: "No schema exists with the requested id, name or version.",
+        remediation: "Check the id or namespace/name and list the subject's versions.",
+    },
+    ErrorEntry {
+        code: "SCHEMA_ALREADY_EXISTS",
+        category: Conflict,
+        retryable: false,
+        description: "A schema with the same subject and version is already registered.",
+        remediation: "Bump the version or reuse the existing registration.",
+    },
+    ErrorEntry {
+        code: "SCHEMA_VALIDATION_FAILED",
+        category: Validation,
+        retryable: false,
+        description: "The schema content failed validation for its format.",
+        remediation: "Fix the reported problems; `schema validate` shows them locally.",
+    },
+    ErrorEntry {
+        code: "SCHEMA_TOO_LARGE",
+        category: Validation,
+        retryable: false,
+        description: "The schema input exceeded the configured size limit and was not read fully.",
+        remediation: "Split the schema or raise max_schema_size / MAX_SCHEMA_SIZE_BYTES.",
+    },
+    ErrorEntry {
+        code: "COMPATIBILITY_CHECK_FAILED",
+        category: Compatibility,
+        retryable: false,
+        description:
+            "The new schema is not compatible with earlier versions under the subject's mode.",
+        remediation:
+            "Review the violations with `schema compatible`, or change the compatibility mode.",
+    },
+    ErrorEntry {
+        code: "INVALID_STATE_TRANSITION",
+        category: Lifecycle,
+        retryable: false,
+        description:
+            "The requested lifecycle change is not allowed from the schema's current state.",
+        remediation: "Check the schema state; e.g. only active schemas can be deprecated.",
+    },
+    ErrorEntry {
+        code: "REGISTRATION_FAILED",
+        category: Lifecycle,
+        retryable: false,
+        description: "Registration could not be completed.",
+        remediation: "See the message for the failing step and correct the input.",
+    },
+    ErrorEntry {
+        code: "DEPRECATION_FAILED",
+        category: Lifecycle,
+        retryable: false,
+        description: "The schema could not be deprecated.",
+        remediation: "Check the schema state and the deprecation request.",
+    },
+    ErrorEntry {
+        code: "ROLLBACK_FAILED",
+        category: Lifecycle,
+        retryable: false,
+        description: "Rolling back to an earlier version failed.",
+        remediation: "Check that the target version exists and is not deleted.",
+    },
+    ErrorEntry {
+        code: "SCHEMA_PARSE_ERROR",
+        category: Validation,
+        retryable: false,
+        description: "The schema content could not be parsed.",
+        remediation: "Check the syntax for the declared format.",
+    },
+    ErrorEntry {
+        code: "SERIALIZATION_ERROR",
+        category: Internal,
+        retryable: false,
+        description: "A value could not be serialized or deserialized.",
+        remediation: "Report the message; it usually indicates a version mismatch.",
+    },
+    ErrorEntry {
+        code: "STORAGE_ERROR",
+        category: Storage,
+        retryable: false,
+        description: "The storage backend rejected or failed the operation.",
+        remediation: "Check backend health and logs.",
+    },
+    ErrorEntry {
+        code: "STORAGE_BUSY",
+        category: Storage,
+        retryable: true,
+        description: "The storage backend is temporarily busy (lock contention, pool exhausted).",
+        remediation: "Retry with backoff; the server already retries within the request deadline.",
+    },
+    ErrorEntry {
+        code: "DEADLINE_EXCEEDED",
+        category: Availability,
+        retryable: true,
+        description: "The request did not finish within its deadline.",
+        remediation: "Retry later or raise the request timeout.",
+    },
+    ErrorEntry {
+        code: "EVENT_PUBLISH_FAILED",
+        category: Availability,
+        retryable: true,
+        description: "A registry event could not be published.",
+        remediation: "Check the event broker connection.",
+    },
+    ErrorEntry {
+        code: "CONFIG_ERROR",
+        category: Configuration,
+        retryable: false,
+        description: "Configuration is missing or invalid.",
+        remediation: "Fix the reported setting and restart.",
+    },
+    ErrorEntry {
+        code: "SECURITY_ERROR",
+        category: Security,
+        retryable: false,
+        description: "The operation was rejected by a security check.",
+        remediation: "Check credentials and permissions for the subject.",
+    },
+    ErrorEntry {
+        code: "REGISTRY_INTERNAL_ERROR",
+        category: Internal,
+        retryable: false,
+        description: "An unexpected internal failure occurred.",
+        remediation: "Report it with the request id and logs.",
+    },
+    ErrorEntry {
+        code: "IO_ERROR",
+        category: Availability,
+        retryable: true,
+        description: "An I/O operation failed; timeouts and interruptions are retryable.",
+        remediation: "Check file paths, permissions and network connectivity.",
+    },
+    ErrorEntry {
+        code: "JSON_ERROR",
+        category: Validation,
+        retryable: false,
+        description: "Input was not valid JSON.",
+        remediation: "Check the JSON syntax at the reported line and column.",
+    },
+    ErrorEntry {
+        code: "UNCLASSIFIED_ERROR",
+        category: Internal,
+        retryable: false,
+        description: "An error without a more specific code.",
+        remediation: "Report it; it should be given its own code.",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn test_core_catalog_covers_every_error_variant() {
+        let catalog = ErrorCatalog::new().register(CORE_ERRORS);
+        for code in Error::CODES {
+            assert!(catalog.contains(code), "{} has no catalog entry", code);
+        }
+        assert_eq!(Error::CODES.len(), CORE_ERRORS.len());
+    }
+
+    #[test]
+    fn test_retryable_entries_match_error_classification() {
+        let catalog = ErrorCatalog::new().register(CORE_ERRORS);
+        let busy = Error::StorageBusy("locked".to_string());
+        assert_eq!(
+            catalog.lookup(busy.code()).unwrap().retryable,
+            busy.is_retryable()
+        );
+        let missing = Error::SchemaNotFound("x".to_string());
+        assert_eq!(
+            catalog.lookup(missing.code()).unwrap().retryable,
+            missing.is_retryable()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "registered twice")]
+    fn test_duplicate_codes_are_rejected() {
+        ErrorCatalog::new()
+            .register(CORE_ERRORS)
+            .register(&CORE_ERRORS[..1]);
+    }
+
+    #[test]
+    fn test_markdown_groups_by_category() {
+        let markdown = ErrorCatalog::new().register(CORE_ERRORS).to_markdown();
+        let storage = markdown.find("## Storage").unwrap();
+        let busy = markdown.find("`STORAGE_BUSY`").unwrap();
+        let lifecycle = markdown.find("## Lifecycle").unwrap();
+        assert!(lifecycle < storage && storage < busy);
+        assert!(markdown.contains("| `STORAGE_BUSY` | yes |"));
+    }
+}
@@ -0,0 +1,1429 @@
+//! Example payload generation
+//!
+//! Produces realistic sample documents that conform to a registered schema,
+//! for use in documentation and contract tests. Generation is deterministic
+//! for a given seed.
+//!
+//! Supported formats:
+//! - JSON Schema: types, `enum`/`const`, required and dependent fields,
+//!   property counts, bounded arrays with `prefixItems` and `contains`,
+//!   numeric bounds, string lengths, common `format`s and simple `pattern`s
+//! - Avro: field defaults are preferred, then type-driven values
+//!
+//! Constructs that cannot be honored are never silently ignored: each one is
+//! recorded per-path in the [`GenerationReport`] returned alongside the value.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+use crate::rng::SplitMix64;
+use crate::types::SerializationFormat;
+
+/// Options controlling example generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExampleOptions {
+    /// Seed for the deterministic random source
+    pub seed: u64,
+    /// Probability (0.0..=1.0) that an optional field is included
+    pub optional_field_probability: f64,
+    /// Upper bound on generated array and map sizes
+    pub max_array_items: usize,
+    /// Maximum nesting depth before recursion is cut off
+    pub max_depth: usize,
+}
+
+impl Default for ExampleOptions {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            optional_field_probability: 0.5,
+            max_array_items: 3,
+            max_depth: 16,
+        }
+    }
+}
+
+impl ExampleOptions {
+    /// Create options with the given seed and default settings
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            ..Self::default()
+        }
+    }
+}
+
+/// A construct that could not be honored during generation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsupportedConstruct {
+    /// Path of the schema node (e.g., "$.properties.name.pattern")
+    pub path: String,
+    /// Keyword or type that was not supported
+    pub construct: String,
+    /// Why the construct was not honored
+    pub reason: String,
+}
+
+/// Report describing how faithfully an example matches its schema
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GenerationReport {
+    /// Unsupported constructs encountered, keyed by schema path
+    pub unsupported: Vec<UnsupportedConstruct>,
+}
+
+impl GenerationReport {
+    /// Whether every construct in the schema was honored
+    pub fn is_complete(&self) -> bool {
+        self.unsupported.is_empty()
+    }
+
+    fn record(&mut self, path: &str, construct: &str, reason: impl Into<String>) {
+        self.unsupported.push(UnsupportedConstruct {
+            path: path.to_string(),
+            construct: construct.to_string(),
+            reason: reason.into(),
+        });
+    }
+}
+
+/// A generated example payload with its generation report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedExample {
+    /// The generated document
+    pub value: Value,
+    /// Constructs that could not be honored
+    pub report: GenerationReport,
+}
+
+/// Generate a single example payload for a schema
+pub fn generate(
+    schema: &str,
+    format: SerializationFormat,
+    options: &ExampleOptions,
+) -> Result<GeneratedExample> {
+    let mut examples = generate_many(schema, format, options, 1)?;
+    Ok(examples.remove(0))
+}
+
+/// Generate `count` example payloads from a single deterministic stream
+///
+/// The same schema, options, and count always yield the same examples. A
+/// `count` of zero still parses the schema and returns no examples.
+pub fn generate_many(
+    schema: &str,
+    format: SerializationFormat,
+    options: &ExampleOptions,
+    count: usize,
+) -> Result<Vec<GeneratedExample>> {
+    match format {
+        SerializationFormat::JsonSchema => {
+            let root: Value = serde_json::from_str(schema)
+                .map_err(|e| Error::ParseError(format!("Invalid JSON Schema: {}", e)))?;
+            let mut generator = JsonSchemaGenerator::new(&root, options);
+            Ok((0..count).map(|_| generator.generate()).collect())
+        }
+        SerializationFormat::Avro => {
+            let root: Value = serde_json::from_str(schema)
+                .map_err(|e| Error::ParseError(format!("Invalid Avro schema: {}", e)))?;
+            let mut generator = AvroGenerator::new(options);
+            Ok((0..count).map(|_| generator.generate(&root)).collect())
+        }
+        SerializationFormat::Protobuf => {
+            let mut report = GenerationReport::default();
+            report.record("$", "protobuf", "example generation is not supported for Protobuf schemas");
+            Ok((0..count)
+                .map(|_| GeneratedExample {
+                    value: Value::Null,
+                    report: report.clone(),
+                })
+                .collect())
+        }
+    }
+}
+
+/// Draws per item an array with `uniqueItems` gets to find distinct values
+const UNIQUE_ITEM_DRAWS: usize = 8;
+
+/// Most items, properties or characters generated for one value; larger minimums are clamped and reported
+const MAX_GENERATED_LEN: usize = 1024;
+
+/// The schema an absent `items` or `additionalProperties` stands for
+static ANY_SCHEMA: Value = Value::Bool(true);
+
+/// Strings drawn from a `pattern` looking for one within `minLength` and `maxLength`
+const PATTERN_DRAWS: usize = 8;
+
+const WORDS: &[&str] = &[
+    "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel",
+];
+
+// ============================================================================
+// JSON Schema
+// ============================================================================
+
+struct JsonSchemaGenerator<'a> {
+    root: &'a Value,
+    options: &'a ExampleOptions,
+    rng: SplitMix64,
+    report: GenerationReport,
+}
+
+impl<'a> JsonSchemaGenerator<'a> {
+    fn new(root: &'a Value, options: &'a ExampleOptions) -> Self {
+        Self {
+            root,
+            options,
+            rng: SplitMix64(options.seed),
+            report: GenerationReport::default(),
+        }
+    }
+
+    fn generate(&mut self) -> GeneratedExample {
+        self.report = GenerationReport::default();
+        let value = self.node(self.root, "$", 0);
+        GeneratedExample {
+            value,
+            report: std::mem::take(&mut self.report),
+        }
+    }
+
+    fn node(&mut self, schema: &'a Value, path: &str, depth: usize) -> Value {
+        if depth > self.options.max_depth {
+            self.report.record(path, "depth", "maximum nesting depth reached");
+            return Value::Null;
+        }
+
+        let obj = match schema {
+            Value::Bool(true) => return json!({}),
+            Value::Bool(false) => {
+                self.report.record(path, "false", "schema accepts no values");
+                return Value::Null;
+            }
+            Value::Object(obj) => obj,
+            _ => {
+                self.report.record(path, "schema", "schema node is not an object");
+                return Value::Null;
+            }
+        };
+
+        if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+            return match self.resolve_ref(reference) {
+                Some(target) => self.node(target, &format!("{}.$ref", path), depth + 1),
+                None => {
+                    self.report.record(path, "$ref", format!("cannot resolve reference '{}'", reference));
+                    Value::Null
+                }
+            };
+        }
+
+        for keyword in [
+            "not",
+            "if",
+            "patternProperties",
+            "propertyNames",
+            "dependencies",
+            "dependentSchemas",
+            "minContains",
+            "maxContains",
+        ] {
+            if obj.contains_key(keyword) {
+                self.report.record(path, keyword, "keyword is not honored by the example generator");
+            }
+        }
+
+        if let Some(value) = obj.get("const") {
+            return value.clone();
+        }
+        if let Some(values) = obj.get("enum").and_then(Value::as_array) {
+            if let Some(value) = self.rng.pick(values) {
+                return value.clone();
+            }
+        }
+        if let Some(value) = obj.get("default") {
+            return value.clone();
+        }
+        if let Some(value) = obj.get("examples").and_then(Value::as_array).and_then(|e| e.first()) {
+            return value.clone();
+        }
+
+        for keyword in ["oneOf", "anyOf"] {
+            if let Some(branches) = obj.get(keyword).and_then(Value::as_array) {
+                if let Some(first) = branches.first() {
+                    return self.node(first, &format!("{}.{}[0]", path, keyword), depth + 1);
+                }
+            }
+        }
+
+        if let Some(parts) = obj.get("allOf").and_then(Value::as_array) {
+            let mut merged = Map::new();
+            for (idx, part) in parts.iter().enumerate() {
+                match self.node(part, &format!("{}.allOf[{}]", path, idx), depth + 1) {
+                    Value::Object(fields) => merged.extend(fields),
+                    Value::Null => {}
+                    other => return other,
+                }
+            }
+            if obj.contains_key("properties") || obj.contains_key("required") {
+                if let Value::Object(fields) = self.object(obj, path, depth) {
+                    merged.extend(fields);
+                }
+            }
+            if obj.get("additionalProperties") == Some(&Value::Bool(false)) {
+                let declared = obj.get("properties").and_then(Value::as_object);
+                let extra: Vec<String> = merged
+                    .keys()
+                    .filter(|key| !declared.is_some_and(|d| d.contains_key(*key)))
+                    .cloned()
+                    .collect();
+                for key in extra {
+                    merged.remove(&key);
+                    self.report.record(
+                        path,
+                        "additionalProperties",
+                        format!("property '{}' from allOf is not declared here; dropped", key),
+                    );
+                }
+            }
+            return Value::Object(merged);
+        }
+
+        let ty = match obj.get("type") {
+            Some(Value::String(ty)) => ty.as_str(),
+            Some(Value::Array(types)) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|t| *t != "null")
+                .unwrap_or("null"),
+            _ if obj.contains_key("properties") => "object",
+            _ if obj.contains_key("items") => "array",
+            _ => "object",
+        };
+
+        match ty {
+            "object" => self.object(obj, path, depth),
+            "array" => self.array(obj, path, depth),
+            "string" => self.string(obj, path),
+            "integer" => self.integer(obj, path),
+            "number" => self.number(obj, path),
+            "boolean" => Value::Bool(self.rng.chance(0.5)),
+            "null" => Value::Null,
+            other => {
+                self.report.record(path, "type", format!("unknown type '{}'", other));
+                Value::Null
+            }
+        }
+    }
+
+    fn resolve_ref(&self, reference: &str) -> Option<&'a Value> {
+        let pointer = reference.strip_prefix('#')?;
+        if pointer.is_empty() {
+            return Some(self.root);
+        }
+        self.root.pointer(pointer)
+    }
+
+    fn object(&mut self, obj: &'a Map<String, Value>, path: &str, depth: usize) -> Value {
+        let required: Vec<&str> = obj
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let properties = obj.get("properties").and_then(Value::as_object);
+        let min_properties = obj.get("minProperties").and_then(Value::as_u64).map_or(0, saturating_usize);
+        let max_properties = obj.get("maxProperties").and_then(Value::as_u64).map(saturating_usize);
+        // Optional properties only fill the room maxProperties leaves after the required ones
+        let mut optional_room = max_properties.map_or(usize::MAX, |max| max.saturating_sub(required.len()));
+
+        let mut out = Map::new();
+        if let Some(properties) = properties {
+            for (name, property) in properties {
+                let include = required.contains(&name.as_str())
+                    || (self.rng.chance(self.options.optional_field_probability) && optional_room > 0);
+                if include {
+                    if !required.contains(&name.as_str()) {
+                        optional_room -= 1;
+                    }
+                    let child = format!("{}.properties.{}", path, name);
+                    out.insert(name.clone(), self.node(property, &child, depth + 1));
+                }
+            }
+        }
+
+        for name in &required {
+            if !out.contains_key(*name) {
+                let value = self.property(properties, name, path, "required", depth);
+                out.insert((*name).to_string(), value);
+            }
+        }
+
+        // Top up to minProperties with the optional properties left out, in declaration order
+        if let Some(properties) = properties {
+            for (name, property) in properties {
+                if out.len() >= min_properties || optional_room == 0 {
+                    break;
+                }
+                if !out.contains_key(name) {
+                    optional_room -= 1;
+                    let child = format!("{}.properties.{}", path, name);
+                    out.insert(name.clone(), self.node(property, &child, depth + 1));
+                }
+            }
+        }
+
+        // Each pulled-in property can depend on further ones, so repeat until nothing is missing
+        if let Some(dependent) = obj.get("dependentRequired").and_then(Value::as_object) {
+            loop {
+                let missing: Vec<&str> = dependent
+                    .iter()
+                    .filter(|(name, _)| out.contains_key(*name))
+                    .filter_map(|(_, names)| names.as_array())
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .filter(|name| !out.contains_key(*name))
+                    .collect();
+                if missing.is_empty() {
+                    break;
+                }
+                for name in missing {
+                    if !out.contains_key(name) {
+                        let value = self.property(properties, name, path, "dependentRequired", depth);
+                        out.insert(name.to_string(), value);
+                    }
+                }
+            }
+        }
+
+        let additional = obj.get("additionalProperties").unwrap_or(&ANY_SCHEMA);
+        if *additional != Value::Bool(false) {
+            let wanted = min_properties.min(max_properties.unwrap_or(usize::MAX)).min(MAX_GENERATED_LEN);
+            let child = format!("{}.additionalProperties", path);
+            let mut idx = 0;
+            while out.len() < wanted {
+                let key = format!("key{}", idx);
+                idx += 1;
+                if !out.contains_key(&key) && !properties.is_some_and(|p| p.contains_key(&key)) {
+                    let value = self.node(additional, &child, depth + 1);
+                    out.insert(key, value);
+                }
+            }
+        }
+
+        if out.len() < min_properties {
+            self.report.record(
+                path,
+                "minProperties",
+                format!("generated {} properties, fewer than the {} minProperties requires", out.len(), min_properties),
+            );
+        }
+        if let Some(max) = max_properties.filter(|max| out.len() > *max) {
+            self.report.record(
+                path,
+                "maxProperties",
+                format!("required and dependent properties exceed the {} maxProperties allows", max),
+            );
+        }
+
+        Value::Object(out)
+    }
+
+    /// Generate the declared property `name`, which `keyword` asks for, or null when it has no schema
+    fn property(
+        &mut self,
+        properties: Option<&'a Map<String, Value>>,
+        name: &str,
+        path: &str,
+        keyword: &str,
+        depth: usize,
+    ) -> Value {
+        match properties.and_then(|p| p.get(name)) {
+            Some(property) => self.node(property, &format!("{}.properties.{}", path, name), depth + 1),
+            None => {
+                self.report.record(
+                    &format!("{}.{}", path, keyword),
+                    keyword,
+                    format!("{} property '{}' has no schema; emitted null", keyword, name),
+                );
+                Value::Null
+            }
+        }
+    }
+
+    fn array(&mut self, obj: &'a Map<String, Value>, path: &str, depth: usize) -> Value {
+        let min_items = obj.get("minItems").and_then(Value::as_u64).map_or(0, saturating_usize);
+        let min = min_items.min(MAX_GENERATED_LEN);
+        let max_items = obj.get("maxItems").and_then(Value::as_u64).map(saturating_usize);
+        let max = max_items
+            .unwrap_or(self.options.max_array_items)
+            .min(self.options.max_array_items.max(min));
+        let mut len = self.rng.range(min as i64, max.max(min) as i64) as usize;
+
+        let items = match obj.get("items") {
+            Some(Value::Array(tuple)) => {
+                self.report.record(path, "items", "tuple-form items generated positionally");
+                return Value::Array(
+                    tuple
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, item)| self.node(item, &format!("{}.items[{}]", path, idx), depth + 1))
+                        .collect(),
+                );
+            }
+            Some(items) => items,
+            None => &ANY_SCHEMA,
+        };
+        let prefix = obj.get("prefixItems").and_then(Value::as_array).map_or(&[][..], Vec::as_slice);
+        // `items: false` closes the array after the prefix
+        let open = *items != Value::Bool(false);
+        if !open {
+            len = len.min(prefix.len());
+        }
+
+        // The item matching `contains` goes right after the whole prefix, if maxItems leaves room
+        let contains = obj.get("contains");
+        let contains_fits = open && max_items.is_none_or(|max| prefix.len() < max);
+        let prefix_len = match contains {
+            Some(_) if contains_fits => prefix.len(),
+            _ => len.min(prefix.len()),
+        };
+
+        let child = format!("{}.items", path);
+        let unique = obj.get("uniqueItems").and_then(Value::as_bool).unwrap_or(false);
+        let mut out: Vec<Value> = Vec::with_capacity(len.max(prefix_len + 1));
+        for (idx, item) in prefix.iter().take(prefix_len).enumerate() {
+            out.push(self.node(item, &format!("{}.prefixItems[{}]", path, idx), depth + 1));
+        }
+        if let Some(contains) = contains {
+            if contains_fits {
+                out.push(self.node(contains, &format!("{}.contains", path), depth + 1));
+            } else {
+                self.report.record(path, "contains", "no room after prefixItems for an item matching contains");
+            }
+        }
+
+        // Unique items get a few extra draws each to find values not already in the array
+        let remaining = len.saturating_sub(out.len());
+        let mut draws = if unique { remaining.saturating_mul(UNIQUE_ITEM_DRAWS) } else { remaining };
+        while out.len() < len && draws > 0 {
+            draws -= 1;
+            let reported = self.report.unsupported.len();
+            let item = self.node(items, &child, depth + 1);
+            if unique && out.contains(&item) {
+                // A discarded draw's report entries would repeat the kept ones
+                self.report.unsupported.truncate(reported);
+                continue;
+            }
+            out.push(item);
+        }
+        if out.len() < min {
+            let (keyword, reason) = if open {
+                ("uniqueItems", format!("found {} distinct items, fewer than the {} minItems requires", out.len(), min))
+            } else {
+                ("items", format!("items: false stops the array at {} items, fewer than the {} minItems requires", out.len(), min))
+            };
+            self.report.record(path, keyword, reason);
+        } else if out.len() < min_items {
+            self.report.record(
+                path,
+                "minItems",
+                format!("minItems {} exceeds the {}-item generation cap", min_items, MAX_GENERATED_LEN),
+            );
+        }
+        Value::Array(out)
+    }
+
+    fn string(&mut self, obj: &'a Map<String, Value>, path: &str) -> Value {
+        let min_len = obj.get("minLength").and_then(Value::as_u64).map_or(0, saturating_usize);
+        let max_len = obj.get("maxLength").and_then(Value::as_u64).map(saturating_usize);
+        if max_len.is_some_and(|max| max < min_len) {
+            return self.unsatisfiable(path, "maxLength", "maxLength is below minLength");
+        }
+        // The length keyword `s` breaks, if any
+        let outside = |s: &str| {
+            let len = s.chars().count();
+            if len < min_len {
+                Some("minLength")
+            } else if max_len.is_some_and(|max| len > max) {
+                Some("maxLength")
+            } else {
+                None
+            }
+        };
+
+        if let Some(pattern) = obj.get("pattern").and_then(Value::as_str) {
+            // Quantifiers vary the length, so a few draws look for one within the bounds
+            let mut drawn = generate_from_pattern(pattern, &mut self.rng);
+            for _ in 1..PATTERN_DRAWS {
+                match &drawn {
+                    Some(PatternDraw { value, clamped: false }) if outside(value).is_some() => {
+                        drawn = generate_from_pattern(pattern, &mut self.rng)
+                    }
+                    _ => break,
+                }
+            }
+            match drawn {
+                Some(PatternDraw { value: s, clamped }) => {
+                    if clamped {
+                        self.report.record(
+                            &format!("{}.pattern", path),
+                            "pattern",
+                            format!("a quantifier in pattern '{}' exceeds the {}-character generation cap", pattern, MAX_GENERATED_LEN),
+                        );
+                    } else if let Some(keyword) = outside(&s) {
+                        self.report.record(
+                            path,
+                            keyword,
+                            format!("no string drawn from pattern '{}' has a length within the bounds", pattern),
+                        );
+                    }
+                    return Value::String(s);
+                }
+                None => self.report.record(
+                    &format!("{}.pattern", path),
+                    "pattern",
+                    format!("pattern '{}' is too complex for the simple generator", pattern),
+                ),
+            }
+        }
+
+        if let Some(format) = obj.get("format").and_then(Value::as_str) {
+            let n = self.rng.next_u64();
+            let formatted = match format {
+                "date-time" => Some("2024-01-15T10:30:00Z".to_string()),
+                "date" => Some("2024-01-15".to_string()),
+                "time" => Some("10:30:00Z".to_string()),
+                "email" => Some(format!("user{}@example.com", n % 1000)),
+                "uri" | "url" => Some(format!("https://example.com/{}", n % 1000)),
+                "hostname" => Some(format!("host{}.example.com", n % 1000)),
+                "ipv4" => Some(format!("10.0.{}.{}", (n >> 8) % 256, n % 256)),
+                "uuid" => Some(format!(
+                    "{:08x}-{:04x}-4{:03x}-8{:03x}-{:012x}",
+                    n >> 32,
+                    (n >> 16) & 0xffff,
+                    n & 0xfff,
+                    (n >> 12) & 0xfff,
+                    n & 0xffff_ffff_ffff
+                )),
+                _ => None,
+            };
+            match formatted {
+                Some(s) => {
+                    if let Some(keyword) = outside(&s) {
+                        self.report.record(
+                            path,
+                            keyword,
+                            format!("string format '{}' gives {} characters", format, s.chars().count()),
+                        );
+                    }
+                    return Value::String(s);
+                }
+                None => self.report.record(
+                    &format!("{}.format", path),
+                    "format",
+                    format!("string format '{}' is not supported; emitted plain text", format),
+                ),
+            }
+        }
+
+        let mut s = self.rng.pick(WORDS).copied().unwrap_or("example").to_string();
+        if min_len > MAX_GENERATED_LEN {
+            self.report.record(
+                path,
+                "minLength",
+                format!("minLength {} exceeds the {}-character generation cap", min_len, MAX_GENERATED_LEN),
+            );
+        }
+        while s.len() < min_len.min(MAX_GENERATED_LEN) {
+            s.push('x');
+        }
+        if let Some(max) = max_len {
+            s.truncate(max);
+        }
+        Value::String(s)
+    }
+
+    fn integer(&mut self, obj: &'a Map<String, Value>, path: &str) -> Value {
+        // Fractional bounds round inward; a bound past the i64 range on its inner side leaves no integer
+        let mut lower = None;
+        for (key, exclusive) in [("minimum", false), ("exclusiveMinimum", true)] {
+            let Some(bound) = obj.get(key).filter(|b| b.is_number()) else {
+                continue;
+            };
+            match integer_at_least(bound, exclusive) {
+                Some(n) => lower = Some(lower.map_or(n, |lower: i64| lower.max(n))),
+                None => return self.unsatisfiable(path, key, format!("no integer satisfies {} {}", key, bound)),
+            }
+        }
+        let mut upper = None;
+        for (key, exclusive) in [("maximum", false), ("exclusiveMaximum", true)] {
+            let Some(bound) = obj.get(key).filter(|b| b.is_number()) else {
+                continue;
+            };
+            match integer_at_most(bound, exclusive) {
+                Some(n) => upper = Some(upper.map_or(n, |upper: i64| upper.min(n))),
+                None => return self.unsatisfiable(path, key, format!("no integer satisfies {} {}", key, bound)),
+            }
+        }
+        let (lo, hi) = match (lower, upper) {
+            (Some(lo), Some(hi)) => (lo, hi),
+            (Some(lo), None) => (lo, lo.saturating_add(100)),
+            (None, Some(hi)) if hi < 0 => (hi.saturating_sub(100), hi),
+            (None, Some(hi)) => (0, hi),
+            (None, None) => (0, 100),
+        };
+        if lo > hi {
+            return self.unsatisfiable(path, "maximum", "no integer lies between the minimum and maximum");
+        }
+
+        let step = match obj.get("multipleOf").and_then(Value::as_f64).filter(|s| s.is_finite() && *s > 0.0) {
+            Some(step) if step.fract() == 0.0 => step,
+            // Every integer is a multiple of 1/n
+            Some(step) if (1.0 / step).fract() == 0.0 => return json!(self.rng.range(lo, hi)),
+            Some(step) => {
+                self.report.record(
+                    path,
+                    "multipleOf",
+                    format!("fractional multipleOf {} is not supported for integers", step),
+                );
+                return json!(self.rng.range(lo, hi));
+            }
+            None => return json!(self.rng.range(lo, hi)),
+        };
+        // Multiples are counted in i128 so bounds near the i64 extremes can't overflow
+        let step = step as i128;
+        let first = (i128::from(lo) + step - 1).div_euclid(step);
+        let last = i128::from(hi).div_euclid(step);
+        if first > last {
+            return self.unsatisfiable(
+                path,
+                "multipleOf",
+                format!("no multiple of {} lies between the minimum and maximum", step),
+            );
+        }
+        let count = i64::try_from(last - first).unwrap_or(i64::MAX);
+        let n = (first + i128::from(self.rng.range(0, count))) * step;
+        json!(n as i64)
+    }
+
+    fn number(&mut self, obj: &'a Map<String, Value>, path: &str) -> Value {
+        let bound = |key: &str| obj.get(key).and_then(Value::as_f64).filter(|b| b.is_finite());
+        // The tighter bound on each side, and whether it is exclusive
+        let lower = match (bound("minimum"), bound("exclusiveMinimum")) {
+            (Some(min), Some(ex)) if ex >= min => Some((ex, true)),
+            (Some(min), _) => Some((min, false)),
+            (None, ex) => ex.map(|ex| (ex, true)),
+        };
+        let upper = match (bound("maximum"), bound("exclusiveMaximum")) {
+            (Some(max), Some(ex)) if ex <= max => Some((ex, true)),
+            (Some(max), _) => Some((max, false)),
+            (None, ex) => ex.map(|ex| (ex, true)),
+        };
+        let ((lo, lo_open), (hi, hi_open)) = match (lower, upper) {
+            (Some(lower), Some(upper)) => (lower, upper),
+            (Some(lower), None) => (lower, (lower.0 + 100.0, false)),
+            (None, Some(upper)) if upper.0 < 0.0 => ((upper.0 - 100.0, false), upper),
+            (None, Some(upper)) => ((0.0, false), upper),
+            (None, None) => ((0.0, false), (100.0, false)),
+        };
+        let fits = |n: f64| (n > lo || (!lo_open && n == lo)) && (n < hi || (!hi_open && n == hi));
+        if lo > hi || (lo == hi && (lo_open || hi_open)) {
+            return self.unsatisfiable(path, "maximum", "no number lies between the minimum and maximum");
+        }
+
+        if let Some(step) = obj.get("multipleOf").and_then(Value::as_f64).filter(|s| s.is_finite() && *s > 0.0) {
+            let mut first = (lo / step).ceil();
+            if !fits(first * step) {
+                first += 1.0;
+            }
+            let mut last = (hi / step).floor();
+            if !fits(last * step) {
+                last -= 1.0;
+            }
+            if !(first * step).is_finite() || !(last * step).is_finite() {
+                return self.unsatisfiable(path, "multipleOf", format!("multiples of {} are out of range", step));
+            }
+            if first > last {
+                return self.unsatisfiable(
+                    path,
+                    "multipleOf",
+                    format!("no multiple of {} lies between the minimum and maximum", step),
+                );
+            }
+            let count = (last - first).min(i64::MAX as f64) as i64;
+            return json!((first + self.rng.range(0, count) as f64) * step);
+        }
+
+        let r = self.rng.next_f64();
+        let mut n = (lo * (1.0 - r) + hi * r).clamp(lo, hi);
+        if !fits(n) {
+            n = lo / 2.0 + hi / 2.0;
+        }
+        let rounded = (n * 100.0).round() / 100.0;
+        json!(if fits(rounded) { rounded } else { n })
+    }
+
+    /// Record that no value at `path` satisfies `keyword`, and generate null in its place
+    fn unsatisfiable(&mut self, path: &str, keyword: &str, reason: impl Into<String>) -> Value {
+        self.report.record(path, keyword, reason);
+        Value::Null
+    }
+}
+
+/// Smallest integer at or above the numeric `bound`, or above it when `exclusive`; `None` past `i64::MAX`
+fn integer_at_least(bound: &Value, exclusive: bool) -> Option<i64> {
+    if let Some(n) = bound.as_i64() {
+        return if exclusive { n.checked_add(1) } else { Some(n) };
+    }
+    let bound = bound.as_f64()?;
+    let n = if exclusive { bound.floor() + 1.0 } else { bound.ceil() };
+    // `i64::MAX as f64` rounds up to 2^63, the first value past the range
+    (n < i64::MAX as f64).then(|| n.max(i64::MIN as f64) as i64)
+}
+
+/// Largest integer at or below the numeric `bound`, or below it when `exclusive`; `None` past `i64::MIN`
+fn integer_at_most(bound: &Value, exclusive: bool) -> Option<i64> {
+    if let Some(n) = bound.as_i64() {
+        return if exclusive { n.checked_sub(1) } else { Some(n) };
+    }
+    let bound = bound.as_f64()?;
+    let n = if exclusive { bound.ceil() - 1.0 } else { bound.floor() };
+    (n >= i64::MIN as f64).then(|| n.min(i64::MAX as f64) as i64)
+}
+
+/// A string drawn from a pattern
+struct PatternDraw {
+    value: String,
+    /// A quantifier's minimum was cut to [`MAX_GENERATED_LEN`], so `value` does not match
+    clamped: bool,
+}
+
+/// Generate a string matching a small regex subset
+///
+/// Supports literals, anchors, `.`, `\d`, `\w`, `\s`, character classes with
+/// ranges, and the quantifiers `?`, `*`, `+`, `{n}`, `{n,m}`. Returns `None`
+/// for anything else (groups, alternation, negated classes, lookarounds).
+fn generate_from_pattern(pattern: &str, rng: &mut SplitMix64) -> Option<PatternDraw> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut out = String::new();
+    let mut clamped = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let alphabet: Vec<char> = match chars[i] {
+            '^' | '$' if i == 0 || i == chars.len() - 1 => {
+                i += 1;
+                continue;
+            }
+            '(' | ')' | '|' | '^' | '$' => return None,
+            '.' => {
+                i += 1;
+                ('a'..='z').collect()
+            }
+            '\\' => {
+                let escaped = *chars.get(i + 1)?;
+                i += 2;
+                match escaped {
+                    'd' => ('0'..='9').collect(),
+                    'w' => ('a'..='z').chain('0'..='9').chain(std::iter::once('_')).collect(),
+                    's' => vec![' '],
+                    c if c.is_ascii_alphanumeric() => return None,
+                    c => vec![c],
+                }
+            }
+            '[' => {
+                let end = chars[i..].iter().position(|c| *c == ']')? + i;
+                let class = &chars[i + 1..end];
+                i = end + 1;
+                if class.first() == Some(&'^') {
+                    return None;
+                }
+                let mut set = Vec::new();
+                let mut j = 0;
+                while j < class.len() {
+                    if j + 2 < class.len() && class[j + 1] == '-' {
+                        set.extend(class[j]..=class[j + 2]);
+                        j += 3;
+                    } else if class[j] == '\\' && j + 1 < class.len() {
+                        match class[j + 1] {
+                            'd' => set.extend('0'..='9'),
+                            'w' => set.extend(('a'..='z').chain('0'..='9')),
+                            c => set.push(c),
+                        }
+                        j += 2;
+                    } else {
+                        set.push(class[j]);
+                        j += 1;
+                    }
+                }
+                set
+            }
+            c => {
+                i += 1;
+                vec![c]
+            }
+        };
+
+        if alphabet.is_empty() {
+            return None;
+        }
+
+        let (min, max) = match chars.get(i) {
+            Some('?') => {
+                i += 1;
+                (0, 1)
+            }
+            Some('*') => {
+                i += 1;
+                (0, 3)
+            }
+            Some('+') => {
+                i += 1;
+                (1, 3)
+            }
+            Some('{') => {
+                let end = chars[i..].iter().position(|c| *c == '}')? + i;
+                let spec: String = chars[i + 1..end].iter().collect();
+                i = end + 1;
+                let mut parts = spec.splitn(2, ',');
+                let min: usize = parts.next()?.trim().parse().ok()?;
+                let max = match parts.next() {
+                    None => min,
+                    Some(m) if m.trim().is_empty() => min.saturating_add(3),
+                    Some(m) => m.trim().parse().ok()?,
+                };
+                (min, max)
+            }
+            _ => (1, 1),
+        };
+
+        clamped |= min > MAX_GENERATED_LEN;
+        let count = rng.range(min.min(MAX_GENERATED_LEN) as i64, max.min(MAX_GENERATED_LEN) as i64) as usize;
+        for _ in 0..count {
+            out.push(*rng.pick(&alphabet)?);
+        }
+    }
+
+    Some(PatternDraw { value: out, clamped })
+}
+
+/// A JSON Schema count as a `usize`, saturating on targets where it does not fit
+fn saturating_usize(n: u64) -> usize {
+    usize::try_from(n).unwrap_or(usize::MAX)
+}
+
+// ============================================================================
+// Avro
+// ============================================================================
+
+struct AvroGenerator<'a> {
+    options: &'a ExampleOptions,
+    rng: SplitMix64,
+    named: HashMap<String, Value>,
+    report: GenerationReport,
+}
+
+impl<'a> AvroGenerator<'a> {
+    fn new(options: &'a ExampleOptions) -> Self {
+        Self {
+            options,
+            rng: SplitMix64(options.seed),
+            named: HashMap::new(),
+            report: GenerationReport::default(),
+        }
+    }
+
+    fn generate(&mut self, root: &Value) -> GeneratedExample {
+        self.report = GenerationReport::default();
+        let value = self.node(root, "$", 0);
+        GeneratedExample {
+            value,
+            report: std::mem::take(&mut self.report),
+        }
+    }
+
+    fn node(&mut self, schema: &Value, path: &str, depth: usize) -> Value {
+        if depth > self.options.max_depth {
+            self.report.record(path, "depth", "maximum nesting depth reached");
+            return Value::Null;
+        }
+
+        match schema {
+            Value::String(name) => self.named_or_primitive(name, path, depth),
+            Value::Array(branches) => {
+                // Prefer the first non-null branch so the example carries data
+                let branch = branches
+                    .iter()
+                    .position(|b| b.as_str() != Some("null"))
+                    .unwrap_or(0);
+                match branches.get(branch) {
+                    Some(b) => self.node(b, &format!("{}[{}]", path, branch), depth + 1),
+                    None => Value::Null,
+                }
+            }
+            Value::Object(obj) => {
+                let ty = obj.get("type").cloned().unwrap_or(Value::Null);
+                if let Some(name) = obj.get("name").and_then(Value::as_str) {
+                    let qualified = match obj.get("namespace").and_then(Value::as_str) {
+                        Some(ns) if !name.contains('.') => format!("{}.{}", ns, name),
+                        _ => name.to_string(),
+                    };
+                    self.named.insert(name.to_string(), schema.clone());
+                    self.named.insert(qualified, schema.clone());
+                }
+
+                match ty.as_str() {
+                    Some("record") | Some("error") => self.record(obj, path, depth),
+                    Some("enum") => {
+                        if let Some(default) = obj.get("default") {
+                            return default.clone();
+                        }
+                        let symbols = obj
+                            .get("symbols")
+                            .and_then(Value::as_array)
+                            .cloned()
+                            .unwrap_or_default();
+                        self.rng.pick(&symbols).cloned().unwrap_or(Value::Null)
+                    }
+                    Some("array") => {
+                        let len = self.rng.range(1, self.options.max_array_items.max(1) as i64) as usize;
+                        let items = obj.get("items").cloned().unwrap_or(Value::Null);
+                        let child = format!("{}.items", path);
+                        Value::Array((0..len).map(|_| self.node(&items, &child, depth + 1)).collect())
+                    }
+                    Some("map") => {
+                        let len = self.rng.range(1, self.options.max_array_items.max(1) as i64) as usize;
+                        let values = obj.get("values").cloned().unwrap_or(Value::Null);
+                        let child = format!("{}.values", path);
+                        let mut out = Map::new();
+                        for idx in 0..len {
+                            out.insert(format!("key{}", idx), self.node(&values, &child, depth + 1));
+                        }
+                        Value::Object(out)
+                    }
+                    Some("fixed") => {
+                        let size = obj.get("size").and_then(Value::as_u64).map_or(0, saturating_usize);
+                        if size > MAX_GENERATED_LEN {
+                            self.report.record(
+                                path,
+                                "size",
+                                format!("fixed size {} exceeds the {}-byte generation cap", size, MAX_GENERATED_LEN),
+                            );
+                        }
+                        Value::String("\u{0}".repeat(size.min(MAX_GENERATED_LEN)))
+                    }
+                    Some(primitive) => {
+                        if let Some(logical) = obj.get("logicalType").and_then(Value::as_str) {
+                            if let Some(value) = logical_value(logical) {
+                                return value;
+                            }
+                            self.report.record(
+                                path,
+                                "logicalType",
+                                format!("logical type '{}' generated from its underlying type", logical),
+                            );
+                        }
+                        self.named_or_primitive(primitive, path, depth)
+                    }
+                    None => self.node(&ty, &format!("{}.type", path), depth + 1),
+                }
+            }
+            _ => {
+                self.report.record(path, "schema", "unrecognized Avro schema node");
+                Value::Null
+            }
+        }
+    }
+
+    fn record(&mut self, obj: &Map<String, Value>, path: &str, depth: usize) -> Value {
+        let mut out = Map::new();
+        let fields = obj.get("fields").and_then(Value::as_array).cloned().unwrap_or_default();
+        for field in &fields {
+            let name = match field.get("name").and_then(Value::as_str) {
+                Some(name) => name,
+                None => {
+                    self.report.record(path, "field", "record field without a name");
+                    continue;
+                }
+            };
+            let value = match field.get("default") {
+                Some(default) => default.clone(),
+                None => {
+                    let ty = field.get("type").cloned().unwrap_or(Value::Null);
+                    self.node(&ty, &format!("{}.{}", path, name), depth + 1)
+                }
+            };
+            out.insert(name.to_string(), value);
+        }
+        Value::Object(out)
+    }
+
+    fn named_or_primitive(&mut self, name: &str, path: &str, depth: usize) -> Value {
+        match name {
+            "null" => Value::Null,
+            "boolean" => Value::Bool(self.rng.chance(0.5)),
+            "int" | "long" => json!(self.rng.range(0, 1000)),
+            "float" | "double" => json!((self.rng.next_f64() * 10000.0).round() / 100.0),
+            "bytes" => Value::String("bytes".to_string()),
+            "string" => Value::String(self.rng.pick(WORDS).copied().unwrap_or("example").to_string()),
+            other => match self.named.get(other).cloned() {
+                Some(schema) => {
+                    if depth >= self.options.max_depth {
+                        self.report.record(path, other, "recursive named type cut off at maximum depth");
+                        return Value::Null;
+                    }
+                    self.node(&schema, path, depth + 1)
+                }
+                None => {
+                    self.report.record(path, other, "named type is not defined in this schema");
+                    Value::Null
+                }
+            },
+        }
+    }
+}
+
+fn logical_value(logical: &str) -> Option<Value> {
+    match logical {
+        "timestamp-millis" => Some(json!(1_705_314_600_000i64)),
+        "timestamp-micros" => Some(json!(1_705_314_600_000_000i64)),
+        "date" => Some(json!(19_737)),
+        "time-millis" => Some(json!(37_800_000)),
+        "uuid" => Some(json!("00000000-0000-4000-8000-000000000000")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_SCHEMA: &str = r#"{
+        "type": "object",
+        "required": ["id", "status"],
+        "properties": {
+            "id": {"type": "string", "pattern": "^usr_[a-z0-9]{8}$"},
+            "status": {"enum": ["active", "suspended"]},
+            "age": {"type": "integer", "minimum": 18, "maximum": 99},
+            "tags": {"type": "array", "items": {"type": "string"}, "maxItems": 10}
+        }
+    }"#;
+
+    #[test]
+    fn test_json_schema_generation_is_deterministic() {
+        let options = ExampleOptions::with_seed(7);
+        let a = generate(USER_SCHEMA, SerializationFormat::JsonSchema, &options).unwrap();
+        let b = generate(USER_SCHEMA, SerializationFormat::JsonSchema, &options).unwrap();
+        assert_eq!(a.value, b.value);
+    }
+
+    #[test]
+    fn test_json_schema_required_fields_and_constraints() {
+        let options = ExampleOptions {
+            optional_field_probability: 1.0,
+            ..ExampleOptions::with_seed(3)
+        };
+        let example = generate(USER_SCHEMA, SerializationFormat::JsonSchema, &options).unwrap();
+        let value = example.value;
+
+        let id = value["id"].as_str().unwrap();
+        assert!(id.starts_with("usr_"));
+        assert_eq!(id.len(), 12);
+        assert!(["active", "suspended"].contains(&value["status"].as_str().unwrap()));
+        let age = value["age"].as_i64().unwrap();
+        assert!((18..=99).contains(&age));
+        assert!(value["tags"].as_array().unwrap().len() <= options.max_array_items);
+        assert!(example.report.is_complete());
+    }
+
+    #[test]
+    fn test_optional_fields_can_be_excluded() {
+        let options = ExampleOptions {
+            optional_field_probability: 0.0,
+            ..ExampleOptions::default()
+        };
+        let example = generate(USER_SCHEMA, SerializationFormat::JsonSchema, &options).unwrap();
+        let obj = example.value.as_object().unwrap();
+        assert_eq!(obj.len(), 2);
+        assert!(obj.contains_key("id"));
+        assert!(obj.contains_key("status"));
+    }
+
+    #[test]
+    fn test_generate_many_varies_examples() {
+        let options = ExampleOptions::with_seed(11);
+        let examples = generate_many(USER_SCHEMA, SerializationFormat::JsonSchema, &options, 3).unwrap();
+        assert_eq!(examples.len(), 3);
+        assert!(examples.iter().any(|e| e.value != examples[0].value));
+    }
+
+    #[test]
+    fn test_unsupported_constructs_are_reported_per_path() {
+        let schema = r#"{
+            "type": "object",
+            "required": ["code"],
+            "properties": {
+                "code": {"type": "string", "pattern": "^(foo|bar)$"},
+                "other": {"not": {"type": "string"}}
+            }
+        }"#;
+        let options = ExampleOptions {
+            optional_field_probability: 1.0,
+            ..ExampleOptions::default()
+        };
+        let example = generate(schema, SerializationFormat::JsonSchema, &options).unwrap();
+        let paths: Vec<&str> = example.report.unsupported.iter().map(|u| u.path.as_str()).collect();
+        assert!(paths.contains(&"$.properties.code.pattern"));
+        assert!(paths.contains(&"$.properties.other"));
+    }
+
+    #[test]
+    fn test_internal_refs_are_resolved() {
+        let schema = r##"{
+            "$defs": {"name": {"type": "string", "const": "Ada"}},
+            "type": "object",
+            "required": ["name"],
+            "properties": {"name": {"$ref": "#/$defs/name"}}
+        }"##;
+        let example = generate(schema, SerializationFormat::JsonSchema, &ExampleOptions::default()).unwrap();
+        assert_eq!(example.value["name"], "Ada");
+    }
+
+    #[test]
+    fn test_avro_prefers_defaults() {
+        let schema = r#"{
+            "type": "record",
+            "name": "User",
+            "fields": [
+                {"name": "name", "type": "string"},
+                {"name": "country", "type": "string", "default": "NZ"},
+                {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A", "B"]}},
+                {"name": "nickname", "type": ["null", "string"], "default": null}
+            ]
+        }"#;
+        let example = generate(schema, SerializationFormat::Avro, &ExampleOptions::default()).unwrap();
+        assert_eq!(example.value["country"], "NZ");
+        assert!(example.value["name"].is_string());
+        assert!(["A", "B"].contains(&example.value["kind"].as_str().unwrap()));
+        assert!(example.value["nickname"].is_null());
+        assert!(example.report.is_complete());
+    }
+
+    #[test]
+    fn test_avro_unknown_named_type_is_reported() {
+        let schema = r#"{"type": "record", "name": "R", "fields": [{"name": "x", "type": "Missing"}]}"#;
+        let example = generate(schema, SerializationFormat::Avro, &ExampleOptions::default()).unwrap();
+        assert_eq!(example.report.unsupported.len(), 1);
+        assert_eq!(example.report.unsupported[0].path, "$.x");
+    }
+
+    #[test]
+    fn test_protobuf_reports_unsupported() {
+        let example = generate("syntax = \"proto3\";", SerializationFormat::Protobuf, &ExampleOptions::default()).unwrap();
+        assert!(!example.report.is_complete());
+    }
+
+    #[test]
+    fn test_extreme_integer_bounds_do_not_overflow() {
+        let schemas = [
+            json!({"type": "integer", "exclusiveMinimum": i64::MAX}),
+            json!({"type": "integer", "exclusiveMaximum": i64::MIN}),
+            json!({"type": "integer", "minimum": i64::MIN, "maximum": i64::MAX}),
+            json!({"type": "integer", "minimum": i64::MAX - 1, "multipleOf": 7}),
+        ];
+        for schema in schemas {
+            generate(&schema.to_string(), SerializationFormat::JsonSchema, &ExampleOptions::default()).unwrap();
+        }
+    }
+
+    fn json_example(schema: &Value, seed: u64) -> GeneratedExample {
+        generate(&schema.to_string(), SerializationFormat::JsonSchema, &ExampleOptions::with_seed(seed)).unwrap()
+    }
+
+    #[test]
+    fn test_numeric_examples_conform_to_bounds() {
+        for seed in 0..20 {
+            let n = json_example(&json!({"type": "integer", "maximum": -5}), seed).value.as_i64().unwrap();
+            assert!(n <= -5, "{}", n);
+            let schema = json!({"type": "integer", "minimum": 1, "maximum": 10, "multipleOf": 4});
+            let n = json_example(&schema, seed).value.as_i64().unwrap();
+            assert!(n == 4 || n == 8, "{}", n);
+            let schema = json!({"type": "number", "exclusiveMinimum": 0, "exclusiveMaximum": 0.01});
+            let n = json_example(&schema, seed).value.as_f64().unwrap();
+            assert!(n > 0.0 && n < 0.01, "{}", n);
+            let schema = json!({"type": "number", "minimum": 0.5, "maximum": 2, "multipleOf": 0.25});
+            let n = json_example(&schema, seed).value.as_f64().unwrap();
+            assert!((0.5..=2.0).contains(&n) && (n / 0.25).fract() == 0.0, "{}", n);
+        }
+    }
+
+    #[test]
+    fn test_fractional_integer_keywords_are_honored() {
+        for seed in 0..20 {
+            let schema = json!({"type": "integer", "minimum": 1.5, "exclusiveMaximum": 4.5, "multipleOf": 0.5});
+            let example = json_example(&schema, seed);
+            let n = example.value.as_i64().unwrap();
+            assert!((2..=4).contains(&n), "{}", n);
+            assert!(example.report.is_complete());
+        }
+
+        let example = json_example(&json!({"type": "integer", "minimum": 0, "maximum": 10, "multipleOf": 1.5}), 0);
+        assert!(example.value.is_i64());
+        assert_eq!(example.report.unsupported[0].construct, "multipleOf");
+    }
+
+    #[test]
+    fn test_unsatisfiable_numbers_are_reported() {
+        let schemas = [
+            json!({"type": "integer", "minimum": 1, "maximum": 3, "multipleOf": 4}),
+            json!({"type": "integer", "minimum": 5, "maximum": 1}),
+            json!({"type": "integer", "exclusiveMinimum": i64::MAX}),
+            json!({"type": "integer", "minimum": 1.2, "maximum": 1.8}),
+            json!({"type": "number", "exclusiveMinimum": 1, "exclusiveMaximum": 1}),
+            json!({"type": "number", "minimum": 0.1, "maximum": 0.2, "multipleOf": 0.5}),
+        ];
+        for schema in &schemas {
+            let example = json_example(schema, 0);
+            assert_eq!(example.value, Value::Null, "{}", schema);
+            assert_eq!(example.report.unsupported.len(), 1, "{}", schema);
+            assert_eq!(example.report.unsupported[0].path, "$");
+        }
+    }
+
+    #[test]
+    fn test_unique_arrays_keep_distinct_items() {
+        for seed in 0..20 {
+            let schema = json!({"type": "array", "items": {"enum": [1, 2, 3]}, "minItems": 3, "uniqueItems": true});
+            let example = json_example(&schema, seed);
+            let mut items: Vec<i64> = example.value.as_array().unwrap().iter().map(|v| v.as_i64().unwrap()).collect();
+            items.sort_unstable();
+            assert_eq!(items, [1, 2, 3]);
+            assert!(example.report.is_complete());
+        }
+
+        let schema = json!({"type": "array", "items": {"const": "x"}, "minItems": 2, "uniqueItems": true});
+        let example = json_example(&schema, 0);
+        assert_eq!(example.value, json!(["x"]));
+        assert_eq!(example.report.unsupported.len(), 1);
+        assert_eq!(example.report.unsupported[0].construct, "uniqueItems");
+    }
+
+    #[test]
+    fn test_string_lengths_are_checked_or_reported() {
+        for seed in 0..20 {
+            let schema = json!({"type": "string", "pattern": "^[a-z]{2,6}$", "minLength": 5, "maxLength": 6});
+            let example = json_example(&schema, seed);
+            let len = example.value.as_str().unwrap().len();
+            assert!((5..=6).contains(&len) || example.report.unsupported[0].construct == "minLength", "{}", len);
+        }
+
+        let example = json_example(&json!({"type": "string", "pattern": "^a{3}$", "maxLength": 2}), 0);
+        assert_eq!(example.value, "aaa");
+        assert_eq!(example.report.unsupported[0].construct, "maxLength");
+
+        let example = json_example(&json!({"type": "string", "minLength": 4, "maxLength": 2}), 0);
+        assert_eq!(example.value, Value::Null);
+        assert_eq!(example.report.unsupported[0].construct, "maxLength");
+    }
+
+    #[test]
+    fn test_huge_minimums_are_clamped_and_reported() {
+        let cases = [
+            (json!({"type": "array", "items": {"type": "integer"}, "minItems": u64::MAX}), "minItems"),
+            (json!({"type": "array", "items": {"enum": [1, 2]}, "minItems": 1u64 << 40, "uniqueItems": true}), "uniqueItems"),
+            (json!({"type": "string", "minLength": u64::MAX}), "minLength"),
+            (json!({"type": "string", "pattern": "^a{99999999}$"}), "pattern"),
+            (json!({"type": "object", "minProperties": u64::MAX}), "minProperties"),
+        ];
+        for (schema, construct) in &cases {
+            let example = json_example(schema, 0);
+            assert_eq!(example.report.unsupported.len(), 1, "{}", schema);
+            assert_eq!(example.report.unsupported[0].construct, *construct, "{}", schema);
+        }
+
+        let schema = r#"{"type": "fixed", "name": "Blob", "size": 18446744073709551615}"#;
+        let example = generate(schema, SerializationFormat::Avro, &ExampleOptions::default()).unwrap();
+        assert_eq!(example.value.as_str().unwrap().len(), MAX_GENERATED_LEN);
+        assert_eq!(example.report.unsupported[0].construct, "size");
+    }
+
+    #[test]
+    fn test_object_keywords_are_honored() {
+        for seed in 0..20 {
+            let schema = json!({
+                "type": "object",
+                "properties": {
+                    "a": {"type": "string"},
+                    "b": {"type": "string"},
+                    "c": {"type": "string"},
+                    "d": {"type": "string"}
+                },
+                "minProperties": 2,
+                "maxProperties": 3,
+                "dependentRequired": {"a": ["b"]}
+            });
+            let example = json_example(&schema, seed);
+            let obj = example.value.as_object().unwrap();
+            assert!((2..=3).contains(&obj.len()), "{:?}", obj);
+            assert!(!obj.contains_key("a") || obj.contains_key("b"), "{:?}", obj);
+            assert!(example.report.is_complete());
+        }
+
+        let schema = json!({"type": "object", "additionalProperties": {"type": "integer"}, "minProperties": 2});
+        let example = json_example(&schema, 0);
+        assert_eq!(example.value.as_object().unwrap().len(), 2);
+        assert!(example.value.as_object().unwrap().values().all(Value::is_i64));
+
+        let schema = json!({
+            "allOf": [{"type": "object", "required": ["extra"], "properties": {"extra": {"const": 1}}}],
+            "properties": {"id": {"const": "x"}},
+            "required": ["id"],
+            "additionalProperties": false
+        });
+        let example = json_example(&schema, 0);
+        assert_eq!(example.value, json!({"id": "x"}));
+        assert_eq!(example.report.unsupported[0].construct, "additionalProperties");
+
+        let example = json_example(&json!({"type": "object", "propertyNames": {"maxLength": 3}}), 0);
+        assert_eq!(example.report.unsupported[0].construct, "propertyNames");
+    }
+
+    #[test]
+    fn test_prefix_items_and_contains_are_honored() {
+        for seed in 0..20 {
+            let schema = json!({
+                "type": "array",
+                "prefixItems": [{"const": "head"}],
+                "items": {"type": "integer"},
+                "contains": {"const": "marker"}
+            });
+            let example = json_example(&schema, seed);
+            let items = example.value.as_array().unwrap();
+            assert_eq!(items[0], "head");
+            assert_eq!(items[1], "marker");
+            assert!(items[2..].iter().all(Value::is_i64));
+            assert!(example.report.is_complete());
+        }
+
+        let schema = json!({"type": "array", "prefixItems": [{"const": 1}], "items": false, "minItems": 2});
+        let example = json_example(&schema, 0);
+        assert_eq!(example.value, json!([1]));
+        assert_eq!(example.report.unsupported[0].construct, "items");
+
+        let example = json_example(&json!({"type": "array", "contains": {"const": 1}, "maxItems": 0}), 0);
+        assert_eq!(example.value, json!([]));
+        assert_eq!(example.report.unsupported[0].construct, "contains");
+    }
+
+    #[test]
+    fn test_generate_many_with_zero_count_is_empty() {
+        let examples = generate_many(USER_SCHEMA, SerializationFormat::JsonSchema, &ExampleOptions::default(), 0).unwrap();
+        assert!(examples.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_json_is_a_parse_error() {
+        let err = generate("{not json", SerializationFormat::JsonSchema, &ExampleOptions::default()).unwrap_err();
+        assert!(matches!(err, Error::ParseError(_)));
+    }
+}
@@ -0,0 +1,714 @@
+//! Reference flattening
+//!
+//! Produces a single self-contained schema document for tools that cannot
+//! follow references:
+//! - JSON Schema: internal (`#/...`) and file (`other.json#/...`) `$ref`s are
+//!   inlined. Files are looked up next to the referencing document, then in
+//!   each include directory in order.
+//! - Avro: named types missing from the root are inlined from the `.avsc`
+//!   files of the include directories (the bundle).
+//!
+//! Reference cycles are never expanded indefinitely. A JSON Schema target
+//! that refers back to itself is moved to a `#/$defs` entry and referenced
+//! from there; an Avro type is defined at its first use and referenced by
+//! name afterwards. Every reference is recorded in the [`FlattenReport`] as
+//! inlined or kept, and [`FlattenOptions::max_output_bytes`] bounds the
+//! output so diamond-shaped reference graphs cannot blow up exponentially.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::types::SerializationFormat;
+
+/// Default cap on the flattened document size (16 MiB)
+pub const DEFAULT_MAX_OUTPUT_BYTES: usize = 16 * 1024 * 1024;
+
+/// Avro primitive type names, which never refer to a named type
+const AVRO_PRIMITIVES: &[&str] = &[
+    "null", "boolean", "int", "long", "float", "double", "bytes", "string",
+];
+
+/// Options controlling reference flattening
+#[derive(Debug, Clone)]
+pub struct FlattenOptions {
+    /// Directories searched for referenced files, in order
+    pub include_dirs: Vec<PathBuf>,
+    /// Abort once inlined content would exceed this many bytes
+    pub max_output_bytes: usize,
+}
+
+impl Default for FlattenOptions {
+    fn default() -> Self {
+        Self {
+            include_dirs: Vec::new(),
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+        }
+    }
+}
+
+/// A reference encountered while flattening
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceRecord {
+    /// Normalized path of the referencing node (`$` is the root)
+    pub path: String,
+    /// Reference as written in the schema
+    pub reference: String,
+    /// Why the reference was kept; `None` for inlined references
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// What flattening did to each reference
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FlattenReport {
+    /// References replaced by their target
+    pub inlined: Vec<ReferenceRecord>,
+    /// References left in the output (cycles, unresolvable targets)
+    pub kept: Vec<ReferenceRecord>,
+    /// Definitions added to hold cyclic targets
+    pub added_definitions: Vec<String>,
+    /// Size of the root document in bytes
+    pub input_bytes: usize,
+    /// Size of the flattened document in bytes
+    pub output_bytes: usize,
+}
+
+impl FlattenReport {
+    /// Output size relative to the root document
+    pub fn growth_factor(&self) -> f64 {
+        if self.input_bytes == 0 {
+            return 1.0;
+        }
+        self.output_bytes as f64 / self.input_bytes as f64
+    }
+
+    /// Whether every reference was inlined
+    pub fn is_complete(&self) -> bool {
+        self.kept.is_empty()
+    }
+
+    fn inline(&mut self, path: &str, reference: &str) {
+        self.inlined.push(ReferenceRecord {
+            path: path.to_string(),
+            reference: reference.to_string(),
+            reason: None,
+        });
+    }
+
+    fn keep(&mut self, path: &str, reference: &str, reason: impl Into<String>) {
+        self.kept.push(ReferenceRecord {
+            path: path.to_string(),
+            reference: reference.to_string(),
+            reason: Some(reason.into()),
+        });
+    }
+}
+
+/// A flattened schema with its report
+#[derive(Debug, Clone)]
+pub struct Flattened {
+    /// The self-contained schema
+    pub value: Value,
+    /// Inlined and kept references
+    pub report: FlattenReport,
+}
+
+/// Flatten the schema stored at `path`
+///
+/// Relative file references are resolved against the directory of `path`.
+pub fn flatten_file(path: &Path, format: SerializationFormat, options: &FlattenOptions) -> Result<Flattened> {
+    let content = std::fs::read_to_string(path)?;
+    flatten(&content, format, path.parent(), options)
+}
+
+/// Flatten `schema`, resolving relative file references against `base_dir`
+pub fn flatten(
+    schema: &str,
+    format: SerializationFormat,
+    base_dir: Option<&Path>,
+    options: &FlattenOptions,
+) -> Result<Flattened> {
+    if format == SerializationFormat::Protobuf {
+        return Err(Error::ValidationError(
+            "flattening is not supported for Protobuf schemas".to_string(),
+        ));
+    }
+    let root: Value = serde_json::from_str(schema)
+        .map_err(|e| Error::ParseError(format!("invalid {} schema: {}", format, e)))?;
+
+    let (value, mut report) = match format {
+        SerializationFormat::Avro => AvroFlattener::new(options)?.run(root)?,
+        _ => JsonFlattener::new(root, base_dir, options).run()?,
+    };
+
+    report.input_bytes = schema.len();
+    report.output_bytes = serialized_len(&value);
+    if report.output_bytes > options.max_output_bytes {
+        return Err(output_too_large(options.max_output_bytes, report.output_bytes));
+    }
+    Ok(Flattened { value, report })
+}
+
+fn serialized_len(value: &Value) -> usize {
+    serde_json::to_vec(value).map(|bytes| bytes.len()).unwrap_or(0)
+}
+
+fn output_too_large(limit: usize, seen: usize) -> Error {
+    Error::ValidationError(format!(
+        "flattened schema exceeds --max-output-bytes ({} > {} bytes)",
+        seen, limit
+    ))
+}
+
+/// Identifies a reference target: document index and JSON pointer
+type TargetKey = (usize, String);
+
+struct JsonFlattener<'a> {
+    options: &'a FlattenOptions,
+    /// Loaded documents; index 0 is the root
+    documents: Vec<Value>,
+    /// Directory each document's relative references resolve against
+    dirs: Vec<Option<PathBuf>>,
+    /// Document index per canonical file path
+    loaded: HashMap<PathBuf, usize>,
+    /// Targets currently being expanded
+    stack: Vec<TargetKey>,
+    /// Targets found to be part of a cycle, with their `$defs` name
+    cyclic: HashMap<TargetKey, String>,
+    /// Flattened value and size per finished target
+    memo: HashMap<TargetKey, (Value, usize)>,
+    /// Finished definitions for cyclic targets
+    defs: Map<String, Value>,
+    /// Bytes of inlined content so far
+    inlined_bytes: usize,
+    report: FlattenReport,
+}
+
+impl<'a> JsonFlattener<'a> {
+    fn new(root: Value, base_dir: Option<&Path>, options: &'a FlattenOptions) -> Self {
+        Self {
+            options,
+            documents: vec![root],
+            dirs: vec![base_dir.map(Path::to_path_buf)],
+            loaded: HashMap::new(),
+            stack: Vec::new(),
+            cyclic: HashMap::new(),
+            memo: HashMap::new(),
+            defs: Map::new(),
+            inlined_bytes: 0,
+            report: FlattenReport::default(),
+        }
+    }
+
+    fn run(mut self) -> Result<(Value, FlattenReport)> {
+        let root = self.documents[0].clone();
+        let mut value = self.node(&root, 0, "$")?;
+
+        if !self.defs.is_empty() {
+            let Some(obj) = value.as_object_mut() else {
+                return Err(Error::ValidationError(
+                    "cyclic references require an object root schema".to_string(),
+                ));
+            };
+            let defs = obj
+                .entry("$defs")
+                .or_insert_with(|| Value::Object(Map::new()));
+            if let Some(defs) = defs.as_object_mut() {
+                for (name, def) in std::mem::take(&mut self.defs) {
+                    defs.insert(name, def);
+                }
+            }
+        }
+        self.report.added_definitions.sort();
+        Ok((value, self.report))
+    }
+
+    fn node(&mut self, schema: &Value, doc: usize, path: &str) -> Result<Value> {
+        match schema {
+            Value::Object(obj) => {
+                if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+                    return self.reference(obj, reference, doc, path);
+                }
+                let mut out = Map::with_capacity(obj.len());
+                for (key, child) in obj {
+                    let child_path = format!("{}.{}", path, key);
+                    out.insert(key.clone(), self.node(child, doc, &child_path)?);
+                }
+                Ok(Value::Object(out))
+            }
+            Value::Array(items) => {
+                let mut out = Vec::with_capacity(items.len());
+                for (idx, item) in items.iter().enumerate() {
+                    out.push(self.node(item, doc, &format!("{}[{}]", path, idx))?);
+                }
+                Ok(Value::Array(out))
+            }
+            other => Ok(other.clone()),
+        }
+    }
+
+    fn reference(&mut self, obj: &Map<String, Value>, reference: &str, doc: usize, path: &str) -> Result<Value> {
+        let key = match self.resolve(reference, doc) {
+            Ok(key) => key,
+            Err(reason) => {
+                self.report.keep(path, reference, reason);
+                return Ok(Value::Object(obj.clone()));
+            }
+        };
+
+        let replacement = if self.stack.contains(&key) || self.cyclic.contains_key(&key) {
+            // Back-edge, or a target already moved to a definition
+            let name = self.def_name(&key);
+            self.report.keep(path, reference, format!("cycle; moved to #/$defs/{}", name));
+            def_ref(&name)
+        } else {
+            let (value, size) = self.target(key)?;
+            self.inlined_bytes += size;
+            if self.inlined_bytes > self.options.max_output_bytes {
+                return Err(output_too_large(self.options.max_output_bytes, self.inlined_bytes));
+            }
+            self.report.inline(path, reference);
+            value
+        };
+
+        // Keywords next to `$ref` still apply to the instance
+        let siblings: Map<String, Value> = obj
+            .iter()
+            .filter(|(k, _)| k.as_str() != "$ref")
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        if siblings.is_empty() {
+            return Ok(replacement);
+        }
+        let siblings = self.node(&Value::Object(siblings), doc, path)?;
+        Ok(serde_json::json!({ "allOf": [replacement, siblings] }))
+    }
+
+    /// Flattened value of a target, expanding it on first use
+    fn target(&mut self, key: TargetKey) -> Result<(Value, usize)> {
+        if let Some(done) = self.memo.get(&key) {
+            return Ok(done.clone());
+        }
+
+        let schema = self.documents[key.0]
+            .pointer(&key.1)
+            .cloned()
+            .unwrap_or(Value::Null);
+        self.stack.push(key.clone());
+        let expanded = self.node(&schema, key.0, &pointer_path(&key.1));
+        self.stack.pop();
+        let mut value = expanded?;
+
+        if let Some(name) = self.cyclic.get(&key).cloned() {
+            self.defs.insert(name.clone(), value);
+            value = def_ref(&name);
+        }
+        let size = serialized_len(&value);
+        self.memo.insert(key, (value.clone(), size));
+        Ok((value, size))
+    }
+
+    /// Resolve a reference from document `doc` to a target, or explain why not
+    fn resolve(&mut self, reference: &str, doc: usize) -> std::result::Result<TargetKey, String> {
+        let (location, fragment) = match reference.split_once('#') {
+            Some((location, fragment)) => (location, fragment),
+            None => (reference, ""),
+        };
+        if location.contains("://") || location.starts_with("urn:") {
+            return Err("remote references are not fetched".to_string());
+        }
+        if !fragment.is_empty() && !fragment.starts_with('/') {
+            return Err(format!("anchor '#{}' is not supported", fragment));
+        }
+
+        let target_doc = if location.is_empty() {
+            doc
+        } else {
+            self.load(location, doc)?
+        };
+        if self.documents[target_doc].pointer(fragment).is_none() {
+            return Err(format!("'#{}' does not exist in the target document", fragment));
+        }
+        Ok((target_doc, fragment.to_string()))
+    }
+
+    /// Load a referenced file, relative to `doc` then the include directories
+    fn load(&mut self, location: &str, doc: usize) -> std::result::Result<usize, String> {
+        let candidates = self.dirs[doc]
+            .iter()
+            .chain(self.options.include_dirs.iter())
+            .map(|dir| dir.join(location));
+        let Some(path) = candidates
+            .chain(std::iter::once(PathBuf::from(location)))
+            .find(|p| p.is_file())
+        else {
+            return Err(format!("file '{}' not found in the include directories", location));
+        };
+
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if let Some(&idx) = self.loaded.get(&canonical) {
+            return Ok(idx);
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("cannot read '{}': {}", path.display(), e))?;
+        let value: Value = serde_json::from_str(&content)
+            .map_err(|e| format!("'{}' is not valid JSON: {}", path.display(), e))?;
+
+        self.documents.push(value);
+        self.dirs.push(path.parent().map(Path::to_path_buf));
+        let idx = self.documents.len() - 1;
+        self.loaded.insert(canonical, idx);
+        Ok(idx)
+    }
+
+    /// `$defs` name for a cyclic target, allocated on first request
+    fn def_name(&mut self, key: &TargetKey) -> String {
+        if let Some(name) = self.cyclic.get(key) {
+            return name.clone();
+        }
+
+        let base = key
+            .1
+            .rsplit('/')
+            .next()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.replace("~1", "/").replace("~0", "~"))
+            .unwrap_or_else(|| format!("Document{}", key.0));
+        let existing = self.documents[0].get("$defs").and_then(Value::as_object);
+        // A root definition keeps its own name; anything else must not shadow one
+        let own_def = key.0 == 0 && key.1 == format!("/$defs/{}", base);
+        let taken = |name: &str| {
+            self.cyclic.values().any(|n| n == name)
+                || (!own_def && existing.is_some_and(|defs| defs.contains_key(name)))
+        };
+        let mut name = base.clone();
+        let mut n = 2;
+        while taken(&name) {
+            name = format!("{}{}", base, n);
+            n += 1;
+        }
+
+        self.cyclic.insert(key.clone(), name.clone());
+        self.report.added_definitions.push(name.clone());
+        name
+    }
+}
+
+fn def_ref(name: &str) -> Value {
+    serde_json::json!({ "$ref": format!("#/$defs/{}", name) })
+}
+
+/// Report path for a JSON pointer (`/properties/a` -> `$.properties.a`)
+fn pointer_path(pointer: &str) -> String {
+    let mut path = "$".to_string();
+    for segment in pointer.split('/').skip(1) {
+        path.push('.');
+        path.push_str(segment);
+    }
+    path
+}
+
+struct AvroFlattener<'a> {
+    options: &'a FlattenOptions,
+    /// Named types available from the bundle, by full name
+    bundle: HashMap<String, Value>,
+    /// Full names defined so far in the output
+    defined: HashSet<String>,
+    inlined_bytes: usize,
+    report: FlattenReport,
+}
+
+impl<'a> AvroFlattener<'a> {
+    fn new(options: &'a FlattenOptions) -> Result<Self> {
+        let mut bundle = HashMap::new();
+        for dir in &options.include_dirs {
+            let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("avsc"))
+                .collect();
+            files.sort();
+            for file in files {
+                let content = std::fs::read_to_string(&file)?;
+                let value: Value = serde_json::from_str(&content).map_err(|e| {
+                    Error::ParseError(format!("invalid Avro schema '{}': {}", file.display(), e))
+                })?;
+                collect_named_types(&value, "", &mut bundle);
+            }
+        }
+
+        Ok(Self {
+            options,
+            bundle,
+            defined: HashSet::new(),
+            inlined_bytes: 0,
+            report: FlattenReport::default(),
+        })
+    }
+
+    fn run(mut self, root: Value) -> Result<(Value, FlattenReport)> {
+        let value = self.node(&root, "", "$")?;
+        Ok((value, self.report))
+    }
+
+    fn node(&mut self, schema: &Value, namespace: &str, path: &str) -> Result<Value> {
+        match schema {
+            Value::String(name) if !AVRO_PRIMITIVES.contains(&name.as_str()) => {
+                self.named(name, namespace, path)
+            }
+            Value::Array(branches) => {
+                let mut out = Vec::with_capacity(branches.len());
+                for branch in branches {
+                    out.push(self.node(branch, namespace, path)?);
+                }
+                Ok(Value::Array(out))
+            }
+            Value::Object(obj) => self.complex(obj, namespace, path),
+            other => Ok(other.clone()),
+        }
+    }
+
+    fn complex(&mut self, obj: &Map<String, Value>, namespace: &str, path: &str) -> Result<Value> {
+        let mut out = obj.clone();
+        let mut namespace = namespace.to_string();
+
+        if let Some(name) = obj.get("name").and_then(Value::as_str) {
+            let ns = obj.get("namespace").and_then(Value::as_str).unwrap_or(&namespace);
+            let full = full_name(name, ns);
+            namespace = full.rsplit_once('.').map(|(ns, _)| ns.to_string()).unwrap_or_default();
+            self.defined.insert(full);
+        }
+
+        if let Some(fields) = obj.get("fields").and_then(Value::as_array) {
+            let mut flattened = Vec::with_capacity(fields.len());
+            for field in fields {
+                let mut field = field.clone();
+                let field_path = match field.get("name").and_then(Value::as_str) {
+                    Some(name) => format!("{}.{}", path, name),
+                    None => path.to_string(),
+                };
+                if let Some(ty) = field.get("type").cloned() {
+                    field["type"] = self.node(&ty, &namespace, &field_path)?;
+                }
+                flattened.push(field);
+            }
+            out.insert("fields".to_string(), Value::Array(flattened));
+        }
+        if let Some(items) = obj.get("items") {
+            out.insert("items".to_string(), self.node(items, &namespace, &format!("{}[]", path))?);
+        }
+        if let Some(values) = obj.get("values") {
+            out.insert("values".to_string(), self.node(values, &namespace, &format!("{}{{}}", path))?);
+        }
+        Ok(Value::Object(out))
+    }
+
+    fn named(&mut self, name: &str, namespace: &str, path: &str) -> Result<Value> {
+        let full = full_name(name, namespace);
+        if self.defined.contains(&full) || self.defined.contains(name) {
+            // Defined earlier in the output: a name reference is valid Avro
+            self.report.keep(path, name, "defined earlier in the document");
+            return Ok(Value::String(name.to_string()));
+        }
+
+        let Some(definition) = self.bundle.get(&full).or_else(|| self.bundle.get(name)).cloned() else {
+            self.report.keep(path, name, "named type not found in the bundle");
+            return Ok(Value::String(name.to_string()));
+        };
+
+        // Keep the type's own namespace so nested short names still resolve
+        let mut definition = definition;
+        if let (Some(obj), Some((ns, _))) = (definition.as_object_mut(), full.rsplit_once('.')) {
+            obj.entry("namespace").or_insert_with(|| Value::String(ns.to_string()));
+        }
+
+        self.report.inline(path, name);
+        let value = self.node(&definition, namespace, path)?;
+        self.inlined_bytes += serialized_len(&value);
+        if self.inlined_bytes > self.options.max_output_bytes {
+            return Err(output_too_large(self.options.max_output_bytes, self.inlined_bytes));
+        }
+        Ok(value)
+    }
+}
+
+/// Full name of an Avro type declared as `name` inside `namespace`
+fn full_name(name: &str, namespace: &str) -> String {
+    if name.contains('.') || namespace.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", namespace, name)
+    }
+}
+
+/// Index every named type declared in `schema` by full name
+fn collect_named_types(schema: &Value, namespace: &str, out: &mut HashMap<String, Value>) {
+    match schema {
+        Value::Array(branches) => {
+            for branch in branches {
+                collect_named_types(branch, namespace, out);
+            }
+        }
+        Value::Object(obj) => {
+            let mut namespace = namespace.to_string();
+            if let Some(name) = obj.get("name").and_then(Value::as_str) {
+                let ns = obj.get("namespace").and_then(Value::as_str).unwrap_or(&namespace);
+                let full = full_name(name, ns);
+                namespace = full.rsplit_once('.').map(|(ns, _)| ns.to_string()).unwrap_or_default();
+                out.entry(full).or_insert_with(|| schema.clone());
+            }
+            if let Some(fields) = obj.get("fields").and_then(Value::as_array) {
+                for ty in fields.iter().filter_map(|f| f.get("type")) {
+                    collect_named_types(ty, &namespace, out);
+                }
+            }
+            for key in ["items", "values"] {
+                if let Some(inner) = obj.get(key) {
+                    collect_named_types(inner, &namespace, out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn write(dir: &Path, name: &str, value: Value) {
+        std::fs::write(dir.join(name), serde_json::to_string(&value).unwrap()).unwrap();
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("flatten-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_internal_and_file_refs_are_inlined() {
+        let dir = scratch_dir("files");
+        write(&dir, "address.json", json!({
+            "type": "object",
+            "properties": {"zip": {"$ref": "#/$defs/zip"}},
+            "$defs": {"zip": {"type": "string", "pattern": "^[0-9]{5}$"}}
+        }));
+        let root = json!({
+            "type": "object",
+            "properties": {
+                "name": {"$ref": "#/$defs/name"},
+                "address": {"$ref": "address.json"}
+            },
+            "$defs": {"name": {"type": "string"}}
+        });
+
+        let options = FlattenOptions { include_dirs: vec![dir.clone()], ..FlattenOptions::default() };
+        let flat = flatten(&root.to_string(), SerializationFormat::JsonSchema, None, &options).unwrap();
+
+        assert_eq!(flat.value["properties"]["name"], json!({"type": "string"}));
+        assert_eq!(flat.value["properties"]["address"]["properties"]["zip"]["pattern"], "^[0-9]{5}$");
+        assert!(flat.report.is_complete());
+        assert_eq!(flat.report.inlined.len(), 3);
+        assert!(flat.report.growth_factor() > 1.0);
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_cycles_become_definitions() {
+        let root = json!({
+            "type": "object",
+            "properties": {"root": {"$ref": "#/$defs/node"}},
+            "$defs": {
+                "node": {
+                    "type": "object",
+                    "properties": {"children": {"type": "array", "items": {"$ref": "#/$defs/node"}}}
+                }
+            }
+        });
+
+        let flat = flatten(&root.to_string(), SerializationFormat::JsonSchema, None, &FlattenOptions::default()).unwrap();
+
+        assert_eq!(flat.value["properties"]["root"], json!({"$ref": "#/$defs/node"}));
+        assert_eq!(
+            flat.value["$defs"]["node"]["properties"]["children"]["items"],
+            json!({"$ref": "#/$defs/node"})
+        );
+        assert_eq!(flat.report.added_definitions, vec!["node".to_string()]);
+        assert!(flat.report.kept.iter().any(|r| r.reason.as_deref().unwrap().starts_with("cycle")));
+    }
+
+    #[test]
+    fn test_unresolvable_refs_are_kept() {
+        let root = json!({
+            "properties": {
+                "a": {"$ref": "missing.json"},
+                "b": {"$ref": "https://example.com/schema.json"}
+            }
+        });
+
+        let flat = flatten(&root.to_string(), SerializationFormat::JsonSchema, None, &FlattenOptions::default()).unwrap();
+
+        assert_eq!(flat.value, root);
+        assert_eq!(flat.report.kept.len(), 2);
+        assert!(flat.report.inlined.is_empty());
+    }
+
+    #[test]
+    fn test_diamond_graph_hits_output_guard() {
+        // Each level references the next twice: 2^20 copies of the leaf
+        let mut defs = Map::new();
+        for level in 0..20 {
+            defs.insert(format!("l{}", level), json!({
+                "type": "object",
+                "properties": {
+                    "left": {"$ref": format!("#/$defs/l{}", level + 1)},
+                    "right": {"$ref": format!("#/$defs/l{}", level + 1)}
+                }
+            }));
+        }
+        defs.insert("l20".to_string(), json!({"type": "string"}));
+        let root = json!({"$ref": "#/$defs/l0", "$defs": defs});
+
+        let options = FlattenOptions { max_output_bytes: 64 * 1024, ..FlattenOptions::default() };
+        let result = flatten(&root.to_string(), SerializationFormat::JsonSchema, None, &options);
+        assert!(matches!(result, Err(Error::ValidationError(msg)) if msg.contains("max-output-bytes")));
+    }
+
+    #[test]
+    fn test_avro_named_types_from_bundle() {
+        let dir = scratch_dir("avro");
+        write(&dir, "address.avsc", json!({
+            "type": "record", "name": "Address", "namespace": "com.example",
+            "fields": [{"name": "zip", "type": "string"}]
+        }));
+        let root = json!({
+            "type": "record", "name": "User", "namespace": "com.example",
+            "fields": [
+                {"name": "home", "type": "Address"},
+                {"name": "work", "type": ["null", "com.example.Address"]},
+                {"name": "manager", "type": ["null", "User"]}
+            ]
+        });
+
+        let options = FlattenOptions { include_dirs: vec![dir.clone()], ..FlattenOptions::default() };
+        let flat = flatten(&root.to_string(), SerializationFormat::Avro, None, &options).unwrap();
+
+        assert_eq!(flat.value["fields"][0]["type"]["name"], "Address");
+        assert_eq!(flat.value["fields"][1]["type"][1], "com.example.Address");
+        assert_eq!(flat.value["fields"][2]["type"][1], "User");
+        assert_eq!(flat.report.inlined.len(), 1);
+        assert_eq!(flat.report.kept.len(), 2);
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_protobuf_is_rejected() {
+        let result = flatten("syntax = \"proto3\";", SerializationFormat::Protobuf, None, &FlattenOptions::default());
+        assert!(matches!(result, Err(Error::ValidationError(_))));
+    }
+}
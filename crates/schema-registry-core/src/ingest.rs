@@ -0,0 +1,177 @@
+//! Size-capped schema ingestion
+//!
+//! Schema content arriving from files, stdin or request bodies is read
+//! through a [`CappedCollector`], which aborts with [`Error::SizeExceeded`]
+//! as soon as the configured limit is passed instead of buffering the whole
+//! input first. The SHA-256 content hash is computed while reading, so
+//! accepted inputs need no second pass.
+
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::error::{Error, Result};
+
+/// Default schema size limit (1 MiB), matching the validation engine
+pub const DEFAULT_MAX_SCHEMA_SIZE: usize = 1024 * 1024;
+
+/// Size of each read from the underlying source
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Input accepted under the size limit
+#[derive(Debug, Clone)]
+pub struct CappedInput {
+    /// Raw content
+    pub bytes: Vec<u8>,
+    /// Hex SHA-256 of `bytes`, as produced by `RegisteredSchema::calculate_content_hash`
+    pub content_hash: String,
+}
+
+impl CappedInput {
+    /// Content length in bytes
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether the content is empty
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Decode the content as UTF-8
+    pub fn into_string(self) -> Result<String> {
+        String::from_utf8(self.bytes)
+            .map_err(|e| Error::ValidationError(format!("Schema is not valid UTF-8: {}", e)))
+    }
+}
+
+/// Accumulates chunks up to a byte limit while hashing them
+#[derive(Debug)]
+pub struct CappedCollector {
+    limit: usize,
+    seen: u64,
+    bytes: Vec<u8>,
+    hasher: Sha256,
+}
+
+impl CappedCollector {
+    /// Create a collector accepting at most `limit` bytes
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            seen: 0,
+            bytes: Vec::new(),
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Bytes offered so far, including any rejected chunk
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    /// Append a chunk, failing once the total passes the limit
+    ///
+    /// A chunk that crosses the limit is not buffered.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<()> {
+        self.seen += chunk.len() as u64;
+        if self.seen > self.limit as u64 {
+            return Err(Error::SizeExceeded {
+                limit: self.limit,
+                seen: self.seen,
+            });
+        }
+        self.hasher.update(chunk);
+        self.bytes.extend_from_slice(chunk);
+        Ok(())
+    }
+
+    /// Finish collecting
+    pub fn finish(self) -> CappedInput {
+        CappedInput {
+            bytes: self.bytes,
+            content_hash: hex::encode(self.hasher.finalize()),
+        }
+    }
+}
+
+/// Read `reader` to the end, aborting once more than `limit` bytes arrive
+pub fn read_capped<R: Read>(mut reader: R, limit: usize) -> Result<CappedInput> {
+    let mut collector = CappedCollector::new(limit);
+    let mut chunk = [0u8; CHUNK_SIZE];
+    loop {
+        match reader.read(&mut chunk) {
+            Ok(0) => return Ok(collector.finish()),
+            Ok(n) => collector.push(&chunk[..n])?,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Async counterpart of [`read_capped`]
+pub async fn read_capped_async<R: AsyncRead + Unpin>(mut reader: R, limit: usize) -> Result<CappedInput> {
+    let mut collector = CappedCollector::new(limit);
+    let mut chunk = vec![0u8; CHUNK_SIZE];
+    loop {
+        let n = reader.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(collector.finish());
+        }
+        collector.push(&chunk[..n])?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::RegisteredSchema;
+
+    /// Reader that never reaches end of input
+    struct Endless;
+
+    impl Read for Endless {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            buf.fill(b'x');
+            Ok(buf.len())
+        }
+    }
+
+    #[test]
+    fn test_accepts_input_under_limit_and_hashes_it() {
+        let content = r#"{"type":"object"}"#;
+        let input = read_capped(content.as_bytes(), 1024).unwrap();
+
+        assert_eq!(input.len(), content.len());
+        assert_eq!(input.content_hash, RegisteredSchema::calculate_content_hash(content));
+        assert_eq!(input.into_string().unwrap(), content);
+    }
+
+    #[test]
+    fn test_endless_reader_is_cut_off_near_the_limit() {
+        let limit = 64 * 1024;
+        match read_capped(Endless, limit) {
+            Err(Error::SizeExceeded { limit: reported, seen }) => {
+                assert_eq!(reported, limit);
+                // Aborted within one chunk of the limit
+                assert!(seen > limit as u64 && seen <= (limit + CHUNK_SIZE) as u64);
+            }
+            other => panic!("expected SizeExceeded, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_exact_limit_is_accepted() {
+        let content = vec![b'a'; 100];
+        assert_eq!(read_capped(&content[..], 100).unwrap().len(), 100);
+        assert!(read_capped(&content[..], 99).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_async_reader_is_capped() {
+        let content = vec![b'a'; 3 * CHUNK_SIZE];
+        let err = read_capped_async(&content[..], CHUNK_SIZE).await.unwrap_err();
+        assert!(matches!(err, Error::SizeExceeded { seen, .. } if seen == 2 * CHUNK_SIZE as u64));
+        assert!(err.is_validation_error());
+    }
+}
@@ -11,8 +11,24 @@
 //! - Error types
 //! - Event system
 
+pub mod annotations;
+pub mod buildinfo;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod compat_matrix;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod doctor;
+pub mod embedded;
 pub mod error;
+pub mod error_catalog;
 pub mod events;
+pub mod examples;
+pub mod flatten;
+pub mod ingest;
+pub mod metric_names;
+pub mod pagination;
+pub mod registration_log;
+pub mod retry;
+mod rng;
 pub mod schema;
 pub mod state;
 pub mod traits;
@@ -21,12 +37,15 @@ pub mod versioning;
 
 // Config Manager integration adapter (Phase 2B)
 pub mod config_manager_adapter;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod startup;
 pub mod config_refresh;
+pub mod doc_policy;
+pub mod style_lint;
 
 // Re-export commonly used types
 pub use error::{Error, Result};
 pub use schema::{RegisteredSchema, SchemaInput, SchemaMetadata};
 pub use state::{SchemaState, StateTransition, SchemaLifecycle};
-pub use types::{CompatibilityMode, SerializationFormat};
+pub use types::{CompatViolation, CompatibilityMode, SerializationFormat};
 pub use versioning::SemanticVersion;
@@ -0,0 +1,874 @@
+//! Canonical metric names
+//!
+//! Every metric identifier emitted by the registry's Prometheus exporter and
+//! by the benchmark suite is defined here once, with its kind, unit and
+//! description. Exporters take names from these constants rather than string
+//! literals, so the set of names dashboards depend on is the [`catalog`].
+//!
+//! Names are part of the public interface: renaming or removing an entry is a
+//! breaking change for anyone scraping or parsing the output.
+
+use serde::Serialize;
+use std::fmt;
+
+/// How a metric's value evolves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricKind {
+    /// Monotonically increasing total
+    Counter,
+    /// Point-in-time value that can go up or down
+    Gauge,
+    /// Bucketed distribution of observations
+    Histogram,
+    /// Summary statistic reported by a benchmark run
+    Statistic,
+}
+
+impl fmt::Display for MetricKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetricKind::Counter => write!(f, "counter"),
+            MetricKind::Gauge => write!(f, "gauge"),
+            MetricKind::Histogram => write!(f, "histogram"),
+            MetricKind::Statistic => write!(f, "statistic"),
+        }
+    }
+}
+
+/// Unit of a metric's value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricUnit {
+    Seconds,
+    Milliseconds,
+    Bytes,
+    /// Dimensionless number of events or items
+    Count,
+    /// Fraction between 0.0 and 1.0
+    Ratio,
+    /// Fraction expressed out of 100
+    Percent,
+    /// Events or items per second
+    PerSecond,
+    /// Bytes processed per second
+    BytesPerSecond,
+}
+
+impl fmt::Display for MetricUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetricUnit::Seconds => write!(f, "seconds"),
+            MetricUnit::Milliseconds => write!(f, "milliseconds"),
+            MetricUnit::Bytes => write!(f, "bytes"),
+            MetricUnit::Count => write!(f, "count"),
+            MetricUnit::Ratio => write!(f, "ratio"),
+            MetricUnit::Percent => write!(f, "percent"),
+            MetricUnit::PerSecond => write!(f, "per_second"),
+            MetricUnit::BytesPerSecond => write!(f, "bytes_per_second"),
+        }
+    }
+}
+
+/// A catalogued metric identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MetricName {
+    pub name: &'static str,
+    pub kind: MetricKind,
+    pub unit: MetricUnit,
+    pub description: &'static str,
+}
+
+impl MetricName {
+    const fn new(
+        name: &'static str,
+        kind: MetricKind,
+        unit: MetricUnit,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            kind,
+            unit,
+            description,
+        }
+    }
+}
+
+impl fmt::Display for MetricName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+use MetricKind::{Counter, Gauge, Histogram, Statistic};
+use MetricUnit::{Bytes, BytesPerSecond, Count, Milliseconds, PerSecond, Percent, Ratio, Seconds};
+
+// HTTP
+
+pub const HTTP_REQUESTS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_http_requests_total",
+    Counter,
+    Count,
+    "Total HTTP requests by method, path, and status",
+);
+
+pub const HTTP_REQUEST_DURATION_SECONDS: MetricName = MetricName::new(
+    "schema_registry_http_request_duration_seconds",
+    Histogram,
+    Seconds,
+    "HTTP request duration in seconds",
+);
+
+pub const HTTP_REQUESTS_IN_FLIGHT: MetricName = MetricName::new(
+    "schema_registry_http_requests_in_flight",
+    Gauge,
+    Count,
+    "Current number of HTTP requests being processed",
+);
+
+pub const HTTP_REQUEST_SIZE_BYTES: MetricName = MetricName::new(
+    "schema_registry_http_request_size_bytes",
+    Histogram,
+    Bytes,
+    "HTTP request size in bytes",
+);
+
+pub const HTTP_RESPONSE_SIZE_BYTES: MetricName = MetricName::new(
+    "schema_registry_http_response_size_bytes",
+    Histogram,
+    Bytes,
+    "HTTP response size in bytes",
+);
+
+// gRPC
+
+pub const GRPC_REQUESTS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_grpc_requests_total",
+    Counter,
+    Count,
+    "Total gRPC requests by service, method, and status",
+);
+
+pub const GRPC_REQUEST_DURATION_SECONDS: MetricName = MetricName::new(
+    "schema_registry_grpc_request_duration_seconds",
+    Histogram,
+    Seconds,
+    "gRPC request duration in seconds",
+);
+
+pub const GRPC_REQUESTS_IN_FLIGHT: MetricName = MetricName::new(
+    "schema_registry_grpc_requests_in_flight",
+    Gauge,
+    Count,
+    "Current number of gRPC requests being processed",
+);
+
+// Schemas
+
+pub const SCHEMAS_REGISTERED_TOTAL: MetricName = MetricName::new(
+    "schema_registry_schemas_registered_total",
+    Counter,
+    Count,
+    "Total schemas registered by format and state",
+);
+
+pub const SCHEMAS_ACTIVE_TOTAL: MetricName = MetricName::new(
+    "schema_registry_schemas_active_total",
+    Gauge,
+    Count,
+    "Total active schemas by format",
+);
+
+pub const SCHEMAS_DEPRECATED_TOTAL: MetricName = MetricName::new(
+    "schema_registry_schemas_deprecated_total",
+    Gauge,
+    Count,
+    "Total deprecated schemas by format",
+);
+
+pub const SCHEMAS_DELETED_TOTAL: MetricName = MetricName::new(
+    "schema_registry_schemas_deleted_total",
+    Gauge,
+    Count,
+    "Total deleted schemas by format",
+);
+
+pub const SCHEMA_VERSIONS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_schema_versions_total",
+    Gauge,
+    Count,
+    "Total schema versions by subject",
+);
+
+pub const SCHEMA_SIZE_BYTES: MetricName = MetricName::new(
+    "schema_registry_schema_size_bytes",
+    Histogram,
+    Bytes,
+    "Schema size in bytes",
+);
+
+// Validation
+
+pub const VALIDATIONS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_validations_total",
+    Counter,
+    Count,
+    "Total validations by format and result",
+);
+
+pub const VALIDATION_DURATION_SECONDS: MetricName = MetricName::new(
+    "schema_registry_validation_duration_seconds",
+    Histogram,
+    Seconds,
+    "Validation duration in seconds",
+);
+
+pub const VALIDATION_ERRORS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_validation_errors_total",
+    Counter,
+    Count,
+    "Total validation errors by format and error type",
+);
+
+// Compatibility
+
+pub const COMPATIBILITY_CHECKS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_compatibility_checks_total",
+    Counter,
+    Count,
+    "Total compatibility checks by mode and result",
+);
+
+pub const COMPATIBILITY_CHECK_DURATION_SECONDS: MetricName = MetricName::new(
+    "schema_registry_compatibility_check_duration_seconds",
+    Histogram,
+    Seconds,
+    "Compatibility check duration in seconds",
+);
+
+pub const COMPATIBILITY_VIOLATIONS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_compatibility_violations_total",
+    Counter,
+    Count,
+    "Total compatibility violations by mode and type",
+);
+
+// Cache
+
+pub const CACHE_OPERATIONS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_cache_operations_total",
+    Counter,
+    Count,
+    "Total cache operations by operation, tier, and result",
+);
+
+pub const CACHE_HIT_RATE: MetricName = MetricName::new(
+    "schema_registry_cache_hit_rate",
+    Gauge,
+    Ratio,
+    "Cache hit rate by tier (0.0 to 1.0)",
+);
+
+pub const CACHE_SIZE_BYTES: MetricName = MetricName::new(
+    "schema_registry_cache_size_bytes",
+    Gauge,
+    Bytes,
+    "Cache size in bytes by tier",
+);
+
+pub const CACHE_ITEMS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_cache_items_total",
+    Gauge,
+    Count,
+    "Total items in cache by tier",
+);
+
+pub const CACHE_EVICTIONS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_cache_evictions_total",
+    Counter,
+    Count,
+    "Total cache evictions by tier and reason",
+);
+
+// Database
+
+pub const DB_CONNECTIONS_ACTIVE: MetricName = MetricName::new(
+    "schema_registry_db_connections_active",
+    Gauge,
+    Count,
+    "Active database connections by pool",
+);
+
+pub const DB_CONNECTIONS_IDLE: MetricName = MetricName::new(
+    "schema_registry_db_connections_idle",
+    Gauge,
+    Count,
+    "Idle database connections by pool",
+);
+
+pub const DB_CONNECTIONS_MAX: MetricName = MetricName::new(
+    "schema_registry_db_connections_max",
+    Gauge,
+    Count,
+    "Maximum database connections by pool",
+);
+
+pub const DB_QUERY_DURATION_SECONDS: MetricName = MetricName::new(
+    "schema_registry_db_query_duration_seconds",
+    Histogram,
+    Seconds,
+    "Database query duration in seconds",
+);
+
+pub const DB_QUERIES_TOTAL: MetricName = MetricName::new(
+    "schema_registry_db_queries_total",
+    Counter,
+    Count,
+    "Total database queries by query and result",
+);
+
+pub const DB_ERRORS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_db_errors_total",
+    Counter,
+    Count,
+    "Total database errors by type",
+);
+
+pub const DB_POOL_WAIT_DURATION_SECONDS: MetricName = MetricName::new(
+    "schema_registry_db_pool_wait_duration_seconds",
+    Histogram,
+    Seconds,
+    "Time waiting for database connection from pool",
+);
+
+// Redis
+
+pub const REDIS_OPERATIONS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_redis_operations_total",
+    Counter,
+    Count,
+    "Total Redis operations by command and result",
+);
+
+pub const REDIS_OPERATION_DURATION_SECONDS: MetricName = MetricName::new(
+    "schema_registry_redis_operation_duration_seconds",
+    Histogram,
+    Seconds,
+    "Redis operation duration in seconds",
+);
+
+pub const REDIS_ERRORS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_redis_errors_total",
+    Counter,
+    Count,
+    "Total Redis errors by type",
+);
+
+pub const REDIS_CONNECTIONS_ACTIVE: MetricName = MetricName::new(
+    "schema_registry_redis_connections_active",
+    Gauge,
+    Count,
+    "Active Redis connections",
+);
+
+// S3
+
+pub const S3_OPERATIONS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_s3_operations_total",
+    Counter,
+    Count,
+    "Total S3 operations by operation and result",
+);
+
+pub const S3_OPERATION_DURATION_SECONDS: MetricName = MetricName::new(
+    "schema_registry_s3_operation_duration_seconds",
+    Histogram,
+    Seconds,
+    "S3 operation duration in seconds",
+);
+
+pub const S3_ERRORS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_s3_errors_total",
+    Counter,
+    Count,
+    "Total S3 errors by type",
+);
+
+pub const S3_BYTES_TRANSFERRED_TOTAL: MetricName = MetricName::new(
+    "schema_registry_s3_bytes_transferred_total",
+    Counter,
+    Bytes,
+    "Total bytes transferred to/from S3",
+);
+
+// Storage retries
+
+pub const STORAGE_RETRIES_TOTAL: MetricName = MetricName::new(
+    "schema_registry_storage_retries_total",
+    Counter,
+    Count,
+    "Total storage calls retried after a transient error",
+);
+
+pub const STORAGE_DEADLINE_EXHAUSTIONS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_storage_deadline_exhaustions_total",
+    Counter,
+    Count,
+    "Total storage calls abandoned because the request deadline left no room for another attempt",
+);
+
+// Process and runtime
+
+pub const PROCESS_CPU_SECONDS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_process_cpu_seconds_total",
+    Counter,
+    Seconds,
+    "Total CPU time consumed by the process",
+);
+
+pub const PROCESS_MEMORY_BYTES: MetricName = MetricName::new(
+    "schema_registry_process_memory_bytes",
+    Gauge,
+    Bytes,
+    "Process memory usage in bytes",
+);
+
+pub const PROCESS_OPEN_FDS: MetricName = MetricName::new(
+    "schema_registry_process_open_fds",
+    Gauge,
+    Count,
+    "Number of open file descriptors",
+);
+
+pub const PROCESS_THREADS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_process_threads_total",
+    Gauge,
+    Count,
+    "Total number of threads",
+);
+
+pub const TOKIO_TASKS_TOTAL: MetricName = MetricName::new(
+    "schema_registry_tokio_tasks_total",
+    Gauge,
+    Count,
+    "Total number of Tokio tasks",
+);
+
+pub const TOKIO_TASKS_ACTIVE: MetricName = MetricName::new(
+    "schema_registry_tokio_tasks_active",
+    Gauge,
+    Count,
+    "Number of active Tokio tasks",
+);
+
+// Benchmark statistics (keys in `BenchmarkResult::metrics`)
+
+pub const BENCH_ITERATIONS: MetricName = MetricName::new(
+    "iterations",
+    Statistic,
+    Count,
+    "Number of iterations each benchmark operation was run",
+);
+
+pub const BENCH_WARMUP_ITERATIONS: MetricName = MetricName::new(
+    "warmup_iterations",
+    Statistic,
+    Count,
+    "Iterations run before measuring and left out of the statistics",
+);
+
+pub const BENCH_SAMPLE_TIME_MS: MetricName = MetricName::new(
+    "sample_time_ms",
+    Statistic,
+    Milliseconds,
+    "Time budget each measured loop sampled for, when sampling by time rather than iteration count",
+);
+
+pub const BENCH_AVG_MS: MetricName = MetricName::new(
+    "avg_ms",
+    Statistic,
+    Milliseconds,
+    "Mean operation time across iterations",
+);
+
+pub const BENCH_MIN_MS: MetricName = MetricName::new(
+    "min_ms",
+    Statistic,
+    Milliseconds,
+    "Fastest operation time across iterations",
+);
+
+pub const BENCH_MAX_MS: MetricName = MetricName::new(
+    "max_ms",
+    Statistic,
+    Milliseconds,
+    "Slowest operation time across iterations",
+);
+
+pub const BENCH_P50_MS: MetricName = MetricName::new(
+    "p50_ms",
+    Statistic,
+    Milliseconds,
+    "Median operation time across iterations",
+);
+
+pub const BENCH_P90_MS: MetricName = MetricName::new(
+    "p90_ms",
+    Statistic,
+    Milliseconds,
+    "90th percentile operation time across iterations",
+);
+
+pub const BENCH_P95_MS: MetricName = MetricName::new(
+    "p95_ms",
+    Statistic,
+    Milliseconds,
+    "95th percentile operation time across iterations",
+);
+
+pub const BENCH_P99_MS: MetricName = MetricName::new(
+    "p99_ms",
+    Statistic,
+    Milliseconds,
+    "99th percentile operation time across iterations",
+);
+
+pub const BENCH_STD_DEV_MS: MetricName = MetricName::new(
+    "std_dev_ms",
+    Statistic,
+    Milliseconds,
+    "Sample standard deviation of operation time across iterations",
+);
+
+pub const BENCH_CV_PERCENT: MetricName = MetricName::new(
+    "cv_percent",
+    Statistic,
+    Percent,
+    "Coefficient of variation: standard deviation as a percentage of the mean",
+);
+
+pub const BENCH_OUTLIERS_REJECTED: MetricName = MetricName::new(
+    "outliers_rejected",
+    Statistic,
+    Count,
+    "Samples dropped by the outlier policy before the other statistics were computed",
+);
+
+pub const BENCH_OPS_PER_SEC: MetricName = MetricName::new(
+    "ops_per_sec",
+    Statistic,
+    PerSecond,
+    "Operations completed per second of measured time: sample count over total sample time",
+);
+
+pub const BENCH_COMPRESSION_RATIO: MetricName = MetricName::new(
+    "compression_ratio",
+    Statistic,
+    Ratio,
+    "Encoded blob size divided by original schema size",
+);
+
+pub const BENCH_OVERHEAD_PERCENT: MetricName = MetricName::new(
+    "overhead_percent",
+    Statistic,
+    Percent,
+    "Increase of the mean time over the baseline operation, as a percentage",
+);
+
+pub const BENCH_CACHE_HIT_RATIO: MetricName = MetricName::new(
+    "cache_hit_ratio",
+    Statistic,
+    Ratio,
+    "Share of a benchmark's warm reads answered from the read cache",
+);
+
+pub const BENCH_TTL_SECONDS: MetricName = MetricName::new(
+    "ttl_seconds",
+    Statistic,
+    Seconds,
+    "Cache TTL the adaptive policy settled on for a workload class",
+);
+
+pub const BENCH_OPERATIONS: MetricName = MetricName::new(
+    "operations",
+    Statistic,
+    Count,
+    "Operations of one kind performed across the measured passes of a workload",
+);
+
+pub const BENCH_THROUGHPUT_OPS_PER_SEC: MetricName = MetricName::new(
+    "throughput_ops_per_sec",
+    Statistic,
+    PerSecond,
+    "Operations of one kind completed per second of the workload steps performing them",
+);
+
+pub const BENCH_TASKS: MetricName = MetricName::new(
+    "tasks",
+    Statistic,
+    Count,
+    "Concurrent tasks at one level of a concurrency benchmark",
+);
+
+pub const BENCH_MAX_TASKS: MetricName = MetricName::new(
+    "max_tasks",
+    Statistic,
+    Count,
+    "Bound on the concurrent tasks of a concurrency benchmark, by default the available cores",
+);
+
+pub const BENCH_SCALABILITY_FACTOR: MetricName = MetricName::new(
+    "scalability_factor",
+    Statistic,
+    Ratio,
+    "Throughput at a concurrency level over the throughput at the lowest level",
+);
+
+pub const BENCH_SCHEMA_BYTES: MetricName = MetricName::new(
+    "schema_bytes",
+    Statistic,
+    Bytes,
+    "Size of the schema text a size-parameterized benchmark processed",
+);
+
+pub const BENCH_BYTES_PER_SEC: MetricName = MetricName::new(
+    "bytes_per_sec",
+    Statistic,
+    BytesPerSecond,
+    "Schema bytes processed per second of mean operation latency",
+);
+
+pub const BENCH_INVALID_SCHEMAS: MetricName = MetricName::new(
+    "invalid_schemas",
+    Statistic,
+    Count,
+    "Schemas a validation benchmark's engine rejected across the measured iterations",
+);
+
+pub const BENCH_CHECKED_PAIRS: MetricName = MetricName::new(
+    "checked_pairs",
+    Statistic,
+    Count,
+    "Schema pairs one pass of a compatibility benchmark's workload checks",
+);
+
+pub const BENCH_COMPATIBLE_PAIRS: MetricName = MetricName::new(
+    "compatible_pairs",
+    Statistic,
+    Count,
+    "Checked schema pairs the compatibility checker found compatible",
+);
+
+pub const BENCH_SEED: MetricName = MetricName::new(
+    "seed",
+    Statistic,
+    Count,
+    "Seed the workload's key selection was generated from",
+);
+
+pub const BENCH_RSS_BEFORE_BYTES: MetricName = MetricName::new(
+    "rss_before_bytes",
+    Statistic,
+    Bytes,
+    "Process resident set size when a benchmark target started",
+);
+
+pub const BENCH_RSS_AFTER_BYTES: MetricName = MetricName::new(
+    "rss_after_bytes",
+    Statistic,
+    Bytes,
+    "Process resident set size when a benchmark target finished",
+);
+
+pub const BENCH_RSS_PEAK_BYTES: MetricName = MetricName::new(
+    "rss_peak_bytes",
+    Statistic,
+    Bytes,
+    "Highest process resident set size sampled while a benchmark target ran",
+);
+
+pub const BENCH_ALLOCATED_BYTES: MetricName = MetricName::new(
+    "allocated_bytes",
+    Statistic,
+    Bytes,
+    "Bytes requested from the allocator while a benchmark target ran",
+);
+
+pub const BENCH_USER_CPU_MS: MetricName = MetricName::new(
+    "user_cpu_ms",
+    Statistic,
+    Milliseconds,
+    "CPU time the process spent in user mode while a benchmark target ran",
+);
+
+pub const BENCH_SYSTEM_CPU_MS: MetricName = MetricName::new(
+    "system_cpu_ms",
+    Statistic,
+    Milliseconds,
+    "CPU time the process spent in the kernel while a benchmark target ran",
+);
+
+pub const BENCH_VOLUNTARY_CONTEXT_SWITCHES: MetricName = MetricName::new(
+    "voluntary_context_switches",
+    Statistic,
+    Count,
+    "Voluntary context switches of the process while a benchmark target ran",
+);
+
+pub const BENCH_INVOLUNTARY_CONTEXT_SWITCHES: MetricName = MetricName::new(
+    "involuntary_context_switches",
+    Statistic,
+    Count,
+    "Involuntary context switches of the process while a benchmark target ran",
+);
+
+const CATALOG: &[MetricName] = &[
+    HTTP_REQUESTS_TOTAL,
+    HTTP_REQUEST_DURATION_SECONDS,
+    HTTP_REQUESTS_IN_FLIGHT,
+    HTTP_REQUEST_SIZE_BYTES,
+    HTTP_RESPONSE_SIZE_BYTES,
+    GRPC_REQUESTS_TOTAL,
+    GRPC_REQUEST_DURATION_SECONDS,
+    GRPC_REQUESTS_IN_FLIGHT,
+    SCHEMAS_REGISTERED_TOTAL,
+    SCHEMAS_ACTIVE_TOTAL,
+    SCHEMAS_DEPRECATED_TOTAL,
+    SCHEMAS_DELETED_TOTAL,
+    SCHEMA_VERSIONS_TOTAL,
+    SCHEMA_SIZE_BYTES,
+    VALIDATIONS_TOTAL,
+    VALIDATION_DURATION_SECONDS,
+    VALIDATION_ERRORS_TOTAL,
+    COMPATIBILITY_CHECKS_TOTAL,
+    COMPATIBILITY_CHECK_DURATION_SECONDS,
+    COMPATIBILITY_VIOLATIONS_TOTAL,
+    CACHE_OPERATIONS_TOTAL,
+    CACHE_HIT_RATE,
+    CACHE_SIZE_BYTES,
+    CACHE_ITEMS_TOTAL,
+    CACHE_EVICTIONS_TOTAL,
+    DB_CONNECTIONS_ACTIVE,
+    DB_CONNECTIONS_IDLE,
+    DB_CONNECTIONS_MAX,
+    DB_QUERY_DURATION_SECONDS,
+    DB_QUERIES_TOTAL,
+    DB_ERRORS_TOTAL,
+    DB_POOL_WAIT_DURATION_SECONDS,
+    REDIS_OPERATIONS_TOTAL,
+    REDIS_OPERATION_DURATION_SECONDS,
+    REDIS_ERRORS_TOTAL,
+    REDIS_CONNECTIONS_ACTIVE,
+    S3_OPERATIONS_TOTAL,
+    S3_OPERATION_DURATION_SECONDS,
+    S3_ERRORS_TOTAL,
+    S3_BYTES_TRANSFERRED_TOTAL,
+    STORAGE_RETRIES_TOTAL,
+    STORAGE_DEADLINE_EXHAUSTIONS_TOTAL,
+    PROCESS_CPU_SECONDS_TOTAL,
+    PROCESS_MEMORY_BYTES,
+    PROCESS_OPEN_FDS,
+    PROCESS_THREADS_TOTAL,
+    TOKIO_TASKS_TOTAL,
+    TOKIO_TASKS_ACTIVE,
+    BENCH_ITERATIONS,
+    BENCH_WARMUP_ITERATIONS,
+    BENCH_SAMPLE_TIME_MS,
+    BENCH_AVG_MS,
+    BENCH_MIN_MS,
+    BENCH_MAX_MS,
+    BENCH_P50_MS,
+    BENCH_P90_MS,
+    BENCH_P95_MS,
+    BENCH_P99_MS,
+    BENCH_STD_DEV_MS,
+    BENCH_CV_PERCENT,
+    BENCH_OUTLIERS_REJECTED,
+    BENCH_OPS_PER_SEC,
+    BENCH_COMPRESSION_RATIO,
+    BENCH_OVERHEAD_PERCENT,
+    BENCH_CACHE_HIT_RATIO,
+    BENCH_TTL_SECONDS,
+    BENCH_OPERATIONS,
+    BENCH_THROUGHPUT_OPS_PER_SEC,
+    BENCH_TASKS,
+    BENCH_MAX_TASKS,
+    BENCH_SCALABILITY_FACTOR,
+    BENCH_SCHEMA_BYTES,
+    BENCH_BYTES_PER_SEC,
+    BENCH_INVALID_SCHEMAS,
+    BENCH_CHECKED_PAIRS,
+    BENCH_COMPATIBLE_PAIRS,
+    BENCH_SEED,
+    BENCH_RSS_BEFORE_BYTES,
+    BENCH_RSS_AFTER_BYTES,
+    BENCH_RSS_PEAK_BYTES,
+    BENCH_ALLOCATED_BYTES,
+    BENCH_USER_CPU_MS,
+    BENCH_SYSTEM_CPU_MS,
+    BENCH_VOLUNTARY_CONTEXT_SWITCHES,
+    BENCH_INVOLUNTARY_CONTEXT_SWITCHES,
+];
+
+/// Every catalogued metric, registry metrics first, then benchmark statistics
+pub fn catalog() -> &'static [MetricName] {
+    CATALOG
+}
+
+/// Look up a catalogued metric by name
+pub fn lookup(name: &str) -> Option<&'static MetricName> {
+    CATALOG.iter().find(|metric| metric.name == name)
+}
+
+/// Whether `name` is in the catalog
+pub fn is_catalogued(name: &str) -> bool {
+    lookup(name).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_names_are_unique() {
+        let mut seen = HashSet::new();
+        for metric in catalog() {
+            assert!(
+                seen.insert(metric.name),
+                "duplicate metric name {}",
+                metric.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_registry_names_follow_prometheus_conventions() {
+        for metric in catalog().iter().filter(|m| m.kind != MetricKind::Statistic) {
+            assert!(
+                metric.name.starts_with("schema_registry_"),
+                "{}",
+                metric.name
+            );
+            match metric.unit {
+                MetricUnit::Seconds => assert!(metric.name.contains("_seconds"), "{}", metric.name),
+                MetricUnit::Bytes => assert!(metric.name.contains("bytes"), "{}", metric.name),
+                _ => {}
+            }
+            if metric.kind == MetricKind::Counter {
+                assert!(metric.name.ends_with("_total"), "{}", metric.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(lookup("avg_ms"), Some(&BENCH_AVG_MS));
+        assert_eq!(
+            lookup("schema_registry_http_requests_total").map(|m| m.kind),
+            Some(MetricKind::Counter)
+        );
+        assert!(!is_catalogued("schema_registry_unknown"));
+    }
+}
@@ -0,0 +1,275 @@
+//! Cursor pagination for list operations
+//!
+//! Lists are paged by key (keyset pagination): items are returned in
+//! ascending order of a stable sort key, and the continuation cursor encodes
+//! the last key returned. The next page starts strictly after that key, so
+//! cursors stay valid while the registry changes underneath them:
+//!
+//! - an item is never returned twice, even if items are inserted or removed
+//!   between page fetches;
+//! - items inserted *before* the cursor position are not returned by later
+//!   pages; items inserted after it are;
+//! - removed items simply stop appearing.
+//!
+//! Cursors are opaque to callers; only pass back what a previous [`Page`]
+//! returned.
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+use crate::error::{Error, Result};
+
+/// Page size used when a request does not ask for one
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+
+/// Largest page a backend will return, whatever the request asks for
+pub const MAX_PAGE_LIMIT: usize = 1000;
+
+/// Prefix identifying the cursor encoding, so it can change without
+/// misreading old cursors
+const CURSOR_PREFIX: &str = "k1.";
+
+/// Which page of a list to return
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageRequest {
+    /// Continuation cursor from the previous page; `None` for the first page
+    pub cursor: Option<String>,
+    /// Maximum number of items; clamped to `1..=MAX_PAGE_LIMIT`
+    pub limit: usize,
+    /// Only keys matching this glob (`*` matches any run of characters)
+    pub pattern: Option<String>,
+}
+
+impl PageRequest {
+    /// First page of up to `limit` items
+    pub fn new(limit: usize) -> Self {
+        Self {
+            cursor: None,
+            limit,
+            pattern: None,
+        }
+    }
+
+    pub fn with_cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
+    pub fn with_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
+    /// Page size actually served
+    pub fn effective_limit(&self) -> usize {
+        self.limit.clamp(1, MAX_PAGE_LIMIT)
+    }
+
+    /// Key the page starts after, decoded from the cursor
+    pub fn after_key(&self) -> Result<Option<String>> {
+        self.cursor.as_deref().map(decode_cursor).transpose()
+    }
+
+    /// Whether `key` passes the pattern filter
+    pub fn matches(&self, key: &str) -> bool {
+        self.pattern
+            .as_deref()
+            .map_or(true, |pattern| glob_match(pattern, key))
+    }
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self::new(DEFAULT_PAGE_LIMIT)
+    }
+}
+
+/// One page of a list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor for the next page; `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// An empty last page
+    pub fn empty() -> Self {
+        Self {
+            items: Vec::new(),
+            next_cursor: None,
+        }
+    }
+
+    /// Whether there are no more pages after this one
+    pub fn is_last(&self) -> bool {
+        self.next_cursor.is_none()
+    }
+}
+
+impl<T> Default for Page<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// Opaque cursor for a sort key
+pub fn encode_cursor(key: &str) -> String {
+    format!("{}{}", CURSOR_PREFIX, hex::encode(key))
+}
+
+/// Sort key of a cursor produced by [`encode_cursor`]
+pub fn decode_cursor(cursor: &str) -> Result<String> {
+    let invalid = || Error::ValidationError(format!("invalid page cursor '{}'", cursor));
+    let encoded = cursor.strip_prefix(CURSOR_PREFIX).ok_or_else(invalid)?;
+    let bytes = hex::decode(encoded).map_err(|_| invalid())?;
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+/// Cut one page out of items sorted ascending by `key`
+///
+/// Backends that can't push the cursor down into their query use this over
+/// an ordered iterator; the input must not contain duplicate keys.
+pub fn paginate<T, I, F>(sorted: I, page: &PageRequest, key: F) -> Result<Page<T>>
+where
+    I: IntoIterator<Item = T>,
+    F: Fn(&T) -> &str,
+{
+    let after = page.after_key()?;
+    let limit = page.effective_limit();
+
+    let mut items: Vec<T> = sorted
+        .into_iter()
+        .filter(|item| after.as_deref().map_or(true, |after| key(item) > after))
+        .filter(|item| page.matches(key(item)))
+        .take(limit + 1)
+        .collect();
+
+    let next_cursor = if items.len() > limit {
+        items.truncate(limit);
+        items.last().map(|item| encode_cursor(key(item)))
+    } else {
+        None
+    };
+    Ok(Page { items, next_cursor })
+}
+
+/// Follow cursors from `first` until the last page, collecting every item
+pub async fn collect_all<T, F, Fut>(first: PageRequest, mut fetch: F) -> Result<Vec<T>>
+where
+    F: FnMut(PageRequest) -> Fut,
+    Fut: Future<Output = Result<Page<T>>>,
+{
+    let mut items = Vec::new();
+    let mut request = first;
+    loop {
+        let page = fetch(request.clone()).await?;
+        items.extend(page.items);
+        match page.next_cursor {
+            Some(cursor) => request.cursor = Some(cursor),
+            None => return Ok(items),
+        }
+    }
+}
+
+/// Match `text` against a glob where `*` matches any (possibly empty) run
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !text.starts_with(first) || text.len() < first.len() + last.len() || !text.ends_with(last) {
+        return false;
+    }
+
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("com.example.s{:03}", i)).collect()
+    }
+
+    #[test]
+    fn test_paginate_walks_every_key_once() {
+        let all = keys(25);
+        let mut request = PageRequest::new(10);
+        let mut seen = Vec::new();
+        loop {
+            let page = paginate(all.iter().cloned(), &request, |k| k.as_str()).unwrap();
+            assert!(page.items.len() <= 10);
+            seen.extend(page.items);
+            match page.next_cursor {
+                Some(cursor) => request = request.with_cursor(cursor),
+                None => break,
+            }
+        }
+        assert_eq!(seen, all);
+    }
+
+    #[test]
+    fn test_limit_is_clamped() {
+        assert_eq!(PageRequest::new(0).effective_limit(), 1);
+        assert_eq!(
+            PageRequest::new(1_000_000).effective_limit(),
+            MAX_PAGE_LIMIT
+        );
+    }
+
+    #[test]
+    fn test_exact_fit_has_no_next_cursor() {
+        let page = paginate(keys(10), &PageRequest::new(10), |k| k.as_str()).unwrap();
+        assert_eq!(page.items.len(), 10);
+        assert!(page.is_last());
+    }
+
+    #[test]
+    fn test_cursor_round_trip_and_rejects_garbage() {
+        let cursor = encode_cursor("payments.invoice");
+        assert_eq!(decode_cursor(&cursor).unwrap(), "payments.invoice");
+        assert!(decode_cursor("payments.invoice")
+            .unwrap_err()
+            .is_validation_error());
+        assert!(decode_cursor("k1.zz").is_err());
+    }
+
+    #[test]
+    fn test_pattern_filter() {
+        let request = PageRequest::new(10).with_pattern("com.*.s00*");
+        let page = paginate(keys(25), &request, |k| k.as_str()).unwrap();
+        assert_eq!(page.items.len(), 10);
+
+        assert!(glob_match("*", ""));
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(!glob_match("a*b*c", "axxcyyb"));
+        assert!(!glob_match("ab*ba", "aba"));
+        assert!(glob_match("exact", "exact"));
+    }
+
+    #[tokio::test]
+    async fn test_collect_all_follows_cursors() {
+        let all = keys(23);
+        let mut fetches = 0;
+        let collected = collect_all(PageRequest::new(5), |request| {
+            fetches += 1;
+            let page = paginate(all.iter().cloned(), &request, |k| k.as_str());
+            async move { page }
+        })
+        .await
+        .unwrap();
+        assert_eq!(collected, all);
+        assert_eq!(fetches, 5);
+    }
+}
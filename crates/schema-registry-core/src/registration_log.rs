@@ -0,0 +1,639 @@
+//! Replayable registration log
+//!
+//! A [`RegistrationLog`] records every registration the pipeline handles: the
+//! request as received, its outcome, and when it was requested and completed.
+//! Combined with a storage backup it allows point-in-time recovery: restore
+//! the backup, then replay the registrations logged after the checkpoint whose
+//! fingerprint matches the restored state.
+//!
+//! # Format
+//!
+//! The log is a sequence of append-only segment files. The active segment is
+//! the configured path (`reg.wal`); full segments are renamed to
+//! `reg.wal.000001`, `reg.wal.000002`, ... Each segment is a series of frames:
+//!
+//! ```text
+//! u32 LE payload length | u32 LE CRC-32 of payload | payload (JSON LogEntry)
+//! ```
+//!
+//! A frame whose CRC doesn't match is corruption and fails the read. An
+//! incomplete frame at the end of the active segment is a write torn by a
+//! crash; readers drop it and report [`LogContents::truncated_tail`], and
+//! [`RegistrationLog::open`] truncates it before appending.
+//!
+//! # Checkpoints
+//!
+//! A [`Checkpoint`] records the [`fingerprint`] of the storage state before
+//! the registrations that follow it. Every segment starts with one, and the
+//! registry writes another after every
+//! [`RegistrationLogConfig::checkpoint_every`] registrations.
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::schema::{RegisteredSchema, SchemaInput};
+use crate::versioning::SemanticVersion;
+
+/// Bytes of frame header before the payload
+const FRAME_HEADER_LEN: usize = 8;
+
+/// Frames claiming a larger payload are treated as corruption
+pub const MAX_FRAME_BYTES: usize = 64 * 1024 * 1024;
+
+/// Default size at which the active segment is rotated
+pub const DEFAULT_MAX_SEGMENT_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Default number of registrations between checkpoints
+pub const DEFAULT_CHECKPOINT_EVERY: u64 = 1000;
+
+/// One record in the log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LogEntry {
+    Registration(RegistrationRecord),
+    Checkpoint(Checkpoint),
+}
+
+impl LogEntry {
+    /// Position of the entry in the log
+    pub fn sequence(&self) -> u64 {
+        match self {
+            LogEntry::Registration(record) => record.sequence,
+            LogEntry::Checkpoint(checkpoint) => checkpoint.sequence,
+        }
+    }
+}
+
+/// A registration request and what became of it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrationRecord {
+    pub sequence: u64,
+    pub requested_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+    /// The request as received, before any policy rewrote it
+    pub request: SchemaInput,
+    pub outcome: LoggedOutcome,
+}
+
+/// Result of a logged registration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum LoggedOutcome {
+    /// A version was registered, or an identical one already existed
+    Registered {
+        id: Uuid,
+        version: SemanticVersion,
+        content_hash: String,
+        /// Content actually stored, when a policy rewrote the request's content
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stored_content: Option<String>,
+    },
+    /// The registration failed; replay skips it
+    Rejected { code: String, message: String },
+}
+
+/// Storage state at a point in the log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub sequence: u64,
+    pub at: DateTime<Utc>,
+    /// [`fingerprint`] of every stored version at this point
+    pub fingerprint: String,
+    pub schema_count: usize,
+}
+
+/// Fingerprint of a storage state
+///
+/// SHA-256 over the id, version and content hash of every stored version, in
+/// (id, version) order, so two backends holding the same versions agree
+/// regardless of how they were populated.
+pub fn fingerprint<'a>(schemas: impl IntoIterator<Item = &'a RegisteredSchema>) -> String {
+    let mut keys: Vec<_> = schemas
+        .into_iter()
+        .map(|schema| (schema.id, schema.version.clone(), schema.content_hash.as_str()))
+        .collect();
+    keys.sort();
+
+    let mut hasher = Sha256::new();
+    for (id, version, content_hash) in keys {
+        hasher.update(format!("{}\t{}\t{}\n", id, version, content_hash).as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Settings for a [`RegistrationLog`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistrationLogConfig {
+    /// Active segment; rotated segments get a numeric suffix
+    pub path: PathBuf,
+    /// Size at which the active segment is rotated
+    pub max_segment_bytes: u64,
+    /// Registrations between checkpoints
+    pub checkpoint_every: u64,
+    /// `fsync` after every frame; slower, but survives power loss
+    pub sync_each_write: bool,
+}
+
+impl RegistrationLogConfig {
+    /// Log at `path` with the default rotation and checkpoint intervals
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_segment_bytes: DEFAULT_MAX_SEGMENT_BYTES,
+            checkpoint_every: DEFAULT_CHECKPOINT_EVERY,
+            sync_each_write: false,
+        }
+    }
+
+    /// Rotate the active segment once it reaches `bytes`
+    pub fn with_max_segment_bytes(mut self, bytes: u64) -> Self {
+        self.max_segment_bytes = bytes;
+        self
+    }
+
+    /// Write a checkpoint every `registrations` registrations
+    pub fn with_checkpoint_every(mut self, registrations: u64) -> Self {
+        self.checkpoint_every = registrations.max(1);
+        self
+    }
+
+    /// `fsync` after every frame
+    pub fn with_sync_each_write(mut self, sync: bool) -> Self {
+        self.sync_each_write = sync;
+        self
+    }
+}
+
+struct Writer {
+    file: File,
+    segment_bytes: u64,
+    next_sequence: u64,
+    /// Registrations since the last checkpoint, `None` if the segment has none yet
+    since_checkpoint: Option<u64>,
+}
+
+/// Append-only writer of the registration log
+pub struct RegistrationLog {
+    config: RegistrationLogConfig,
+    writer: Mutex<Writer>,
+}
+
+impl RegistrationLog {
+    /// Open the log for appending, creating it if needed
+    ///
+    /// Existing segments are read to continue the sequence; a torn frame at
+    /// the end of the active segment is truncated.
+    pub fn open(config: RegistrationLogConfig) -> Result<Self> {
+        if let Some(parent) = config.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut next_sequence = 0;
+        if let Some(segment) = rotated_segments(&config.path)?.last() {
+            if let Some(last) = read_segment(segment, false)?.entries.last() {
+                next_sequence = last.sequence() + 1;
+            }
+        }
+
+        let mut since_checkpoint = None;
+        let mut valid_len = 0;
+        if config.path.exists() {
+            let frames = read_segment(&config.path, true)?;
+            valid_len = frames.valid_len;
+            for entry in &frames.entries {
+                next_sequence = entry.sequence() + 1;
+                since_checkpoint = match entry {
+                    LogEntry::Checkpoint(_) => Some(0),
+                    LogEntry::Registration(_) => since_checkpoint.map(|n| n + 1),
+                };
+            }
+        }
+
+        if valid_len >= config.max_segment_bytes {
+            // Rotate at the next checkpoint
+            since_checkpoint = None;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&config.path)?;
+        if file.metadata()?.len() > valid_len {
+            file.set_len(valid_len)?;
+            file.seek(SeekFrom::End(0))?;
+        }
+
+        Ok(Self {
+            config,
+            writer: Mutex::new(Writer {
+                file,
+                segment_bytes: valid_len,
+                next_sequence,
+                since_checkpoint,
+            }),
+        })
+    }
+
+    /// Configuration the log was opened with
+    pub fn config(&self) -> &RegistrationLogConfig {
+        &self.config
+    }
+
+    /// Whether a checkpoint is due before the next registration
+    pub fn needs_checkpoint(&self) -> bool {
+        match self.writer.lock().since_checkpoint {
+            None => true,
+            Some(count) => count >= self.config.checkpoint_every,
+        }
+    }
+
+    /// Append a checkpoint for the current storage state; returns its sequence
+    pub fn checkpoint(&self, fingerprint: String, schema_count: usize, at: DateTime<Utc>) -> Result<u64> {
+        let mut writer = self.writer.lock();
+        let sequence = writer.next_sequence;
+        let entry = LogEntry::Checkpoint(Checkpoint {
+            sequence,
+            at,
+            fingerprint,
+            schema_count,
+        });
+        self.append(&mut writer, &entry)?;
+        writer.since_checkpoint = Some(0);
+        Ok(sequence)
+    }
+
+    /// Append a registration; returns its sequence
+    pub fn record(
+        &self,
+        requested_at: DateTime<Utc>,
+        completed_at: DateTime<Utc>,
+        request: SchemaInput,
+        outcome: LoggedOutcome,
+    ) -> Result<u64> {
+        let mut writer = self.writer.lock();
+        let sequence = writer.next_sequence;
+        let entry = LogEntry::Registration(RegistrationRecord {
+            sequence,
+            requested_at,
+            completed_at,
+            request,
+            outcome,
+        });
+        self.append(&mut writer, &entry)?;
+        writer.since_checkpoint = writer.since_checkpoint.map(|n| n + 1);
+        Ok(sequence)
+    }
+
+    fn append(&self, writer: &mut Writer, entry: &LogEntry) -> Result<()> {
+        let frame = encode_frame(entry)?;
+        let is_checkpoint = matches!(entry, LogEntry::Checkpoint(_));
+        // A new segment must open with a checkpoint, so only rotate in front of one
+        if is_checkpoint && writer.segment_bytes > 0 && writer.segment_bytes + frame.len() as u64 > self.config.max_segment_bytes {
+            self.rotate(writer)?;
+        }
+        writer.file.write_all(&frame)?;
+        if self.config.sync_each_write {
+            writer.file.sync_data()?;
+        }
+        writer.segment_bytes += frame.len() as u64;
+        writer.next_sequence += 1;
+        if !is_checkpoint && writer.segment_bytes >= self.config.max_segment_bytes {
+            // Rotate at the next checkpoint
+            writer.since_checkpoint = None;
+        }
+        Ok(())
+    }
+
+    fn rotate(&self, writer: &mut Writer) -> Result<()> {
+        writer.file.sync_all()?;
+        let index = rotated_segments(&self.config.path)?
+            .last()
+            .and_then(|path| segment_index(&self.config.path, path))
+            .unwrap_or(0)
+            + 1;
+        fs::rename(&self.config.path, segment_path(&self.config.path, index))?;
+        writer.file = OpenOptions::new().create(true).append(true).open(&self.config.path)?;
+        writer.segment_bytes = 0;
+        Ok(())
+    }
+}
+
+/// Everything read from a log
+#[derive(Debug, Clone, Default)]
+pub struct LogContents {
+    /// Entries of every segment, in order
+    pub entries: Vec<LogEntry>,
+    /// Segment files read, oldest first
+    pub segments: Vec<PathBuf>,
+    /// Whether an incomplete frame at the end of the active segment was dropped
+    pub truncated_tail: bool,
+}
+
+impl LogContents {
+    /// Latest checkpoint whose fingerprint is `fingerprint`
+    pub fn checkpoint_matching(&self, fingerprint: &str) -> Option<&Checkpoint> {
+        self.checkpoints().filter(|c| c.fingerprint == fingerprint).last()
+    }
+
+    /// Every checkpoint, in order
+    pub fn checkpoints(&self) -> impl Iterator<Item = &Checkpoint> {
+        self.entries.iter().filter_map(|entry| match entry {
+            LogEntry::Checkpoint(checkpoint) => Some(checkpoint),
+            LogEntry::Registration(_) => None,
+        })
+    }
+
+    /// Entries logged after the entry with `sequence`
+    pub fn after(&self, sequence: u64) -> &[LogEntry] {
+        let start = self.entries.partition_point(|entry| entry.sequence() <= sequence);
+        &self.entries[start..]
+    }
+}
+
+/// Read every segment of the log at `path`
+pub fn read_log(path: &Path) -> Result<LogContents> {
+    let mut contents = LogContents::default();
+    for segment in rotated_segments(path)? {
+        contents.entries.extend(read_segment(&segment, false)?.entries);
+        contents.segments.push(segment);
+    }
+    if path.exists() {
+        let frames = read_segment(path, true)?;
+        contents.entries.extend(frames.entries);
+        contents.truncated_tail = frames.truncated_tail;
+        contents.segments.push(path.to_path_buf());
+    }
+    if contents.segments.is_empty() {
+        return Err(Error::StorageError(format!("registration log not found: {}", path.display())));
+    }
+    Ok(contents)
+}
+
+struct SegmentFrames {
+    entries: Vec<LogEntry>,
+    /// Length of the complete frames
+    valid_len: u64,
+    truncated_tail: bool,
+}
+
+/// Frames of one segment; `tolerate_tail` accepts an incomplete last frame
+fn read_segment(path: &Path, tolerate_tail: bool) -> Result<SegmentFrames> {
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+
+    let mut entries = Vec::new();
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let corrupt = |reason: &str| {
+            Error::StorageError(format!(
+                "corrupt registration log {} at byte {}: {}",
+                path.display(),
+                offset,
+                reason
+            ))
+        };
+        let rest = &bytes[offset..];
+        if rest.len() < FRAME_HEADER_LEN {
+            if tolerate_tail {
+                break;
+            }
+            return Err(corrupt("truncated frame header"));
+        }
+        let len = u32::from_le_bytes(rest[..4].try_into().expect("4 bytes")) as usize;
+        let crc = u32::from_le_bytes(rest[4..8].try_into().expect("4 bytes"));
+        if len > MAX_FRAME_BYTES {
+            return Err(corrupt(&format!("frame length {} exceeds {}", len, MAX_FRAME_BYTES)));
+        }
+        if rest.len() < FRAME_HEADER_LEN + len {
+            if tolerate_tail {
+                break;
+            }
+            return Err(corrupt("truncated frame"));
+        }
+        let payload = &rest[FRAME_HEADER_LEN..FRAME_HEADER_LEN + len];
+        if crc32(payload) != crc {
+            return Err(corrupt("checksum mismatch"));
+        }
+        let entry: LogEntry = serde_json::from_slice(payload).map_err(|e| corrupt(&e.to_string()))?;
+        entries.push(entry);
+        offset += FRAME_HEADER_LEN + len;
+    }
+
+    Ok(SegmentFrames {
+        entries,
+        valid_len: offset as u64,
+        truncated_tail: offset < bytes.len(),
+    })
+}
+
+fn encode_frame(entry: &LogEntry) -> Result<Vec<u8>> {
+    let payload = serde_json::to_vec(entry)?;
+    if payload.len() > MAX_FRAME_BYTES {
+        return Err(Error::StorageError(format!(
+            "registration log entry of {} bytes exceeds the {} byte frame limit",
+            payload.len(),
+            MAX_FRAME_BYTES
+        )));
+    }
+    let mut frame = Vec::with_capacity(FRAME_HEADER_LEN + payload.len());
+    frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&crc32(&payload).to_le_bytes());
+    frame.extend_from_slice(&payload);
+    Ok(frame)
+}
+
+fn segment_path(active: &Path, index: u64) -> PathBuf {
+    let mut name = active.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{:06}", index));
+    active.with_file_name(name)
+}
+
+/// Rotation index of `path` if it is a rotated segment of `active`
+fn segment_index(active: &Path, path: &Path) -> Option<u64> {
+    let base = active.file_name()?.to_str()?;
+    let suffix = path.file_name()?.to_str()?.strip_prefix(base)?.strip_prefix('.')?;
+    if suffix.is_empty() || !suffix.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    suffix.parse().ok()
+}
+
+/// Rotated segments of `active`, oldest first
+fn rotated_segments(active: &Path) -> Result<Vec<PathBuf>> {
+    let dir = match active.parent().filter(|p| !p.as_os_str().is_empty()) {
+        Some(dir) => dir.to_path_buf(),
+        None => PathBuf::from("."),
+    };
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut segments: Vec<(u64, PathBuf)> = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter_map(|path| segment_index(active, &path).map(|index| (index, path)))
+        .collect();
+    segments.sort();
+    Ok(segments.into_iter().map(|(_, path)| path).collect())
+}
+
+/// CRC-32 (IEEE) of `bytes`
+fn crc32(bytes: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut n = 0;
+        while n < 256 {
+            let mut c = n as u32;
+            let mut k = 0;
+            while k < 8 {
+                c = if c & 1 != 0 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+                k += 1;
+            }
+            table[n] = c;
+            n += 1;
+        }
+        table
+    };
+    !bytes
+        .iter()
+        .fold(!0u32, |crc, &b| TABLE[((crc ^ b as u32) & 0xFF) as usize] ^ (crc >> 8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SerializationFormat;
+
+    fn request(name: &str) -> SchemaInput {
+        SchemaInput::new("com.example", name, SerializationFormat::JsonSchema, r#"{"type":"object"}"#)
+    }
+
+    fn rejected() -> LoggedOutcome {
+        LoggedOutcome::Rejected {
+            code: "SCHEMA_VALIDATION_FAILED".to_string(),
+            message: "bad".to_string(),
+        }
+    }
+
+    fn empty_fingerprint() -> String {
+        fingerprint(&[] as &[RegisteredSchema])
+    }
+
+    fn temp_log(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("reg-log-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("reg.wal")
+    }
+
+    #[test]
+    fn test_crc32_matches_reference() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn test_append_reopen_and_read() {
+        let path = temp_log("reopen");
+        let now = Utc::now();
+        {
+            let log = RegistrationLog::open(RegistrationLogConfig::new(&path)).unwrap();
+            assert!(log.needs_checkpoint());
+            log.checkpoint(empty_fingerprint(), 0, now).unwrap();
+            assert!(!log.needs_checkpoint());
+            log.record(now, now, request("a"), rejected()).unwrap();
+        }
+        let log = RegistrationLog::open(RegistrationLogConfig::new(&path)).unwrap();
+        assert_eq!(log.record(now, now, request("b"), rejected()).unwrap(), 2);
+
+        let contents = read_log(&path).unwrap();
+        let sequences: Vec<_> = contents.entries.iter().map(LogEntry::sequence).collect();
+        assert_eq!(sequences, vec![0, 1, 2]);
+        assert!(!contents.truncated_tail);
+        assert_eq!(contents.after(0).len(), 2);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_torn_tail_is_tolerated_and_truncated() {
+        let path = temp_log("torn");
+        let now = Utc::now();
+        let log = RegistrationLog::open(RegistrationLogConfig::new(&path)).unwrap();
+        log.checkpoint(empty_fingerprint(), 0, now).unwrap();
+        log.record(now, now, request("a"), rejected()).unwrap();
+        drop(log);
+
+        // Half of a frame, as left by a crash mid-write
+        let frame = encode_frame(&LogEntry::Registration(RegistrationRecord {
+            sequence: 2,
+            requested_at: now,
+            completed_at: now,
+            request: request("b"),
+            outcome: rejected(),
+        }))
+        .unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&frame[..frame.len() / 2]).unwrap();
+        drop(file);
+
+        let contents = read_log(&path).unwrap();
+        assert!(contents.truncated_tail);
+        assert_eq!(contents.entries.len(), 2);
+
+        let log = RegistrationLog::open(RegistrationLogConfig::new(&path)).unwrap();
+        assert_eq!(log.record(now, now, request("c"), rejected()).unwrap(), 2);
+        let contents = read_log(&path).unwrap();
+        assert!(!contents.truncated_tail);
+        assert_eq!(contents.entries.len(), 3);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_checksum_mismatch_is_corruption() {
+        let path = temp_log("crc");
+        let now = Utc::now();
+        let log = RegistrationLog::open(RegistrationLogConfig::new(&path)).unwrap();
+        log.checkpoint(empty_fingerprint(), 0, now).unwrap();
+        log.record(now, now, request("a"), rejected()).unwrap();
+        drop(log);
+
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.len() - 3;
+        bytes[last] ^= 0xFF;
+        fs::write(&path, bytes).unwrap();
+
+        let err = read_log(&path).unwrap_err().to_string();
+        assert!(err.contains("checksum mismatch"), "{err}");
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_segments_rotate_at_checkpoints() {
+        let path = temp_log("rotate");
+        let now = Utc::now();
+        let config = RegistrationLogConfig::new(&path)
+            .with_max_segment_bytes(600)
+            .with_checkpoint_every(100);
+        let log = RegistrationLog::open(config).unwrap();
+        for n in 0..6 {
+            if log.needs_checkpoint() {
+                log.checkpoint(empty_fingerprint(), 0, now).unwrap();
+            }
+            log.record(now, now, request(&format!("s{n}")), rejected()).unwrap();
+        }
+
+        let contents = read_log(&path).unwrap();
+        assert!(contents.segments.len() > 1, "{:?}", contents.segments);
+        assert_eq!(contents.segments[0], segment_path(&path, 1));
+        // Every segment opens with a checkpoint and sequences stay contiguous
+        for segment in &contents.segments {
+            let first = &read_segment(segment, true).unwrap().entries[0];
+            assert!(matches!(first, LogEntry::Checkpoint(_)));
+        }
+        let sequences: Vec<_> = contents.entries.iter().map(LogEntry::sequence).collect();
+        assert_eq!(sequences, (0..sequences.len() as u64).collect::<Vec<_>>());
+        assert_eq!(contents.entries.iter().filter(|e| matches!(e, LogEntry::Registration(_))).count(), 6);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
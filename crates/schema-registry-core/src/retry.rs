@@ -0,0 +1,381 @@
+//! Retry layer for storage operations
+//!
+//! [`RetryingStorage`] wraps any [`SchemaStorage`] and retries calls that fail
+//! with a retryable error (see [`Error::is_retryable`]) using exponential
+//! backoff. Retries are bounded by the caller's [`Deadline`], which is carried
+//! as a task-local so every storage call made while handling a request shares
+//! the same budget.
+//!
+//! [`EmbeddedRegistry`](crate::embedded::EmbeddedRegistry) wraps its storage
+//! with it. The HTTP server queries PostgreSQL directly and doesn't go
+//! through this layer.
+
+use crate::{
+    config_manager_adapter::ServerConfig,
+    error::{Error, Result},
+    pagination::{Page, PageRequest},
+    schema::RegisteredSchema,
+    traits::SchemaStorage,
+    versioning::SemanticVersion,
+};
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+tokio::task_local! {
+    static DEADLINE: Deadline;
+}
+
+/// Point in time by which a request must complete
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deadline(Instant);
+
+impl Deadline {
+    /// Deadline `timeout` from now
+    pub fn after(timeout: Duration) -> Self {
+        Self(Instant::now() + timeout)
+    }
+
+    /// Deadline derived from the server's request timeout
+    pub fn from_server_config(config: &ServerConfig) -> Self {
+        Self::after(Duration::from_secs(config.timeout_seconds))
+    }
+
+    /// Time left before the deadline, zero once it has passed
+    pub fn remaining(&self) -> Duration {
+        self.0.saturating_duration_since(Instant::now())
+    }
+
+    /// Whether the deadline has passed
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    /// Deadline of the current request, if one is in scope
+    pub fn current() -> Option<Self> {
+        DEADLINE.try_with(|d| *d).ok()
+    }
+
+    /// Run `fut` with this deadline in scope
+    ///
+    /// A nested scope never extends an outer deadline: the earlier one wins.
+    pub async fn scope<F: Future>(self, fut: F) -> F::Output {
+        let effective = match Self::current() {
+            Some(outer) if outer.0 < self.0 => outer,
+            _ => self,
+        };
+        DEADLINE.scope(effective, fut).await
+    }
+}
+
+/// Backoff parameters for [`RetryingStorage`]
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum attempts, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound for a single delay
+    pub max_backoff: Duration,
+    /// Factor applied to the delay after each retry
+    pub multiplier: u32,
+    /// Budget used when no [`Deadline`] is in scope
+    pub default_timeout: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(500),
+            multiplier: 2,
+            default_timeout: Duration::from_secs(ServerConfig::default().timeout_seconds),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Default policy whose fallback budget is the server request timeout
+    pub fn from_server_config(config: &ServerConfig) -> Self {
+        Self {
+            default_timeout: Duration::from_secs(config.timeout_seconds),
+            ..Self::default()
+        }
+    }
+
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.saturating_pow(retry);
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+/// Counters maintained by [`RetryingStorage`]
+///
+/// Exported through the observability crate's `MetricsCollector::observe_storage_retries`.
+#[derive(Debug, Default)]
+pub struct RetryMetrics {
+    retries: AtomicU64,
+    deadline_exhaustions: AtomicU64,
+}
+
+impl RetryMetrics {
+    /// Retries performed (not counting first attempts)
+    pub fn retries(&self) -> u64 {
+        self.retries.load(Ordering::Relaxed)
+    }
+
+    /// Calls abandoned because the deadline left no room for another attempt
+    pub fn deadline_exhaustions(&self) -> u64 {
+        self.deadline_exhaustions.load(Ordering::Relaxed)
+    }
+}
+
+/// Storage wrapper retrying transient failures within the request deadline
+pub struct RetryingStorage<S> {
+    inner: S,
+    policy: RetryPolicy,
+    metrics: RetryMetrics,
+}
+
+impl<S: SchemaStorage> RetryingStorage<S> {
+    /// Wrap `inner` with `policy`
+    pub fn new(inner: S, policy: RetryPolicy) -> Self {
+        Self {
+            inner,
+            policy,
+            metrics: RetryMetrics::default(),
+        }
+    }
+
+    /// Retry counters
+    pub fn metrics(&self) -> &RetryMetrics {
+        &self.metrics
+    }
+
+    /// Wrapped storage
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    async fn with_retry<T, F, Fut>(&self, operation: &str, mut call: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let deadline = Deadline::current().unwrap_or_else(|| Deadline::after(self.policy.default_timeout));
+        let mut attempt = 1;
+
+        loop {
+            let remaining = deadline.remaining();
+            if remaining.is_zero() {
+                self.metrics.deadline_exhaustions.fetch_add(1, Ordering::Relaxed);
+                return Err(Error::DeadlineExceeded(format!("storage {} before attempt {}", operation, attempt)));
+            }
+
+            let err = match tokio::time::timeout(remaining, call()).await {
+                Ok(Ok(value)) => return Ok(value),
+                Ok(Err(err)) => err,
+                Err(_) => {
+                    self.metrics.deadline_exhaustions.fetch_add(1, Ordering::Relaxed);
+                    return Err(Error::DeadlineExceeded(format!("storage {} attempt {}", operation, attempt)));
+                }
+            };
+
+            if !err.is_retryable() || attempt >= self.policy.max_attempts {
+                return Err(err);
+            }
+
+            let delay = self.policy.backoff(attempt - 1);
+            if delay >= deadline.remaining() {
+                self.metrics.deadline_exhaustions.fetch_add(1, Ordering::Relaxed);
+                tracing::debug!(operation, attempt, "deadline leaves no room for another storage retry");
+                return Err(err);
+            }
+
+            tracing::debug!(operation, attempt, error = %err, ?delay, "retrying storage operation");
+            self.metrics.retries.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl<S: SchemaStorage> SchemaStorage for RetryingStorage<S> {
+    async fn store(&self, schema: RegisteredSchema) -> Result<()> {
+        self.with_retry("store", || self.inner.store(schema.clone())).await
+    }
+
+    async fn retrieve(&self, id: Uuid, version: Option<SemanticVersion>) -> Result<RegisteredSchema> {
+        self.with_retry("retrieve", || self.inner.retrieve(id, version.clone())).await
+    }
+
+    async fn retrieve_by_hash(&self, content_hash: &str) -> Result<Option<RegisteredSchema>> {
+        self.with_retry("retrieve_by_hash", || self.inner.retrieve_by_hash(content_hash)).await
+    }
+
+    async fn update(&self, schema: RegisteredSchema) -> Result<()> {
+        self.with_retry("update", || self.inner.update(schema.clone())).await
+    }
+
+    async fn delete(&self, id: Uuid, version: SemanticVersion) -> Result<()> {
+        self.with_retry("delete", || self.inner.delete(id, version.clone())).await
+    }
+
+    async fn list_versions(&self, id: Uuid) -> Result<Vec<SemanticVersion>> {
+        self.with_retry("list_versions", || self.inner.list_versions(id)).await
+    }
+
+    async fn find_by_name(&self, namespace: &str, name: &str) -> Result<Vec<RegisteredSchema>> {
+        self.with_retry("find_by_name", || self.inner.find_by_name(namespace, name)).await
+    }
+
+    async fn list_subjects(&self, page: &PageRequest) -> Result<Page<String>> {
+        self.with_retry("list_subjects", || self.inner.list_subjects(page)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    /// Fault-injecting storage: fails `transient_failures` times with a
+    /// retryable error, then either succeeds or fails permanently
+    struct FlakyStorage {
+        transient_failures: u32,
+        persistent: bool,
+        calls: AtomicU32,
+    }
+
+    impl FlakyStorage {
+        fn new(transient_failures: u32, persistent: bool) -> Self {
+            Self {
+                transient_failures,
+                persistent,
+                calls: AtomicU32::new(0),
+            }
+        }
+
+        fn next(&self) -> Result<()> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.transient_failures {
+                Err(Error::StorageBusy("database is locked".to_string()))
+            } else if self.persistent {
+                Err(Error::StorageError("disk full".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SchemaStorage for FlakyStorage {
+        async fn store(&self, _schema: RegisteredSchema) -> Result<()> {
+            self.next()
+        }
+
+        async fn retrieve(&self, id: Uuid, _version: Option<SemanticVersion>) -> Result<RegisteredSchema> {
+            self.next()?;
+            Err(Error::SchemaNotFound(id.to_string()))
+        }
+
+        async fn retrieve_by_hash(&self, _content_hash: &str) -> Result<Option<RegisteredSchema>> {
+            self.next().map(|_| None)
+        }
+
+        async fn update(&self, _schema: RegisteredSchema) -> Result<()> {
+            self.next()
+        }
+
+        async fn delete(&self, _id: Uuid, _version: SemanticVersion) -> Result<()> {
+            self.next()
+        }
+
+        async fn list_versions(&self, _id: Uuid) -> Result<Vec<SemanticVersion>> {
+            self.next().map(|_| Vec::new())
+        }
+
+        async fn find_by_name(&self, _namespace: &str, _name: &str) -> Result<Vec<RegisteredSchema>> {
+            self.next().map(|_| Vec::new())
+        }
+
+        async fn list_subjects(&self, _page: &PageRequest) -> Result<Page<String>> {
+            self.next().map(|_| Page::empty())
+        }
+    }
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transient_errors_are_absorbed() {
+        let storage = RetryingStorage::new(FlakyStorage::new(2, false), fast_policy());
+
+        assert!(storage.list_versions(Uuid::new_v4()).await.is_ok());
+        assert_eq!(storage.inner().calls.load(Ordering::SeqCst), 3);
+        assert_eq!(storage.metrics().retries(), 2);
+        assert_eq!(storage.metrics().deadline_exhaustions(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_persistent_errors_fail_without_retry() {
+        let storage = RetryingStorage::new(FlakyStorage::new(0, true), fast_policy());
+
+        let err = storage.list_versions(Uuid::new_v4()).await.unwrap_err();
+        assert!(matches!(err, Error::StorageError(_)));
+        assert_eq!(storage.inner().calls.load(Ordering::SeqCst), 1);
+        assert_eq!(storage.metrics().retries(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_retries_stop_at_max_attempts() {
+        let storage = RetryingStorage::new(FlakyStorage::new(u32::MAX, false), fast_policy());
+
+        let err = storage.list_versions(Uuid::new_v4()).await.unwrap_err();
+        assert!(err.is_retryable());
+        assert_eq!(storage.inner().calls.load(Ordering::SeqCst), 5);
+        assert_eq!(storage.metrics().retries(), 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retries_never_exceed_deadline() {
+        let policy = RetryPolicy {
+            max_attempts: 100,
+            initial_backoff: Duration::from_millis(30),
+            max_backoff: Duration::from_millis(30),
+            ..RetryPolicy::default()
+        };
+        let storage = RetryingStorage::new(FlakyStorage::new(u32::MAX, false), policy);
+
+        let start = Instant::now();
+        let result = Deadline::after(Duration::from_millis(100))
+            .scope(storage.list_versions(Uuid::new_v4()))
+            .await;
+
+        // The paused clock only moves through the backoff sleeps: 3 x 30ms, leaving 10ms, too little for a fourth
+        assert!(result.unwrap_err().is_retryable());
+        assert_eq!(start.elapsed(), Duration::from_millis(90));
+        assert_eq!(storage.metrics().retries(), 3);
+        assert_eq!(storage.metrics().deadline_exhaustions(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_nested_deadline_scope_keeps_earlier_deadline() {
+        let outer = Deadline::after(Duration::from_millis(10));
+        let inner = Deadline::after(Duration::from_secs(60));
+
+        let effective = outer.scope(inner.scope(async { Deadline::current() })).await;
+        assert_eq!(effective, Some(outer));
+        assert_eq!(Deadline::current(), None);
+    }
+}
@@ -129,6 +129,42 @@ pub struct SchemaReference {
     pub name: String,
 }
 
+impl SchemaInput {
+    /// Create an input with backward compatibility and no optional fields set
+    pub fn new(
+        namespace: impl Into<String>,
+        name: impl Into<String>,
+        format: SerializationFormat,
+        content: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            namespace: namespace.into(),
+            format,
+            content: content.into(),
+            description: String::new(),
+            compatibility_mode: CompatibilityMode::Backward,
+            auto_activate: false,
+            version: None,
+            metadata: HashMap::new(),
+            tags: Vec::new(),
+            examples: Vec::new(),
+        }
+    }
+
+    /// Set the compatibility mode to enforce
+    pub fn with_compatibility_mode(mut self, mode: CompatibilityMode) -> Self {
+        self.compatibility_mode = mode;
+        self
+    }
+
+    /// Add a metadata entry
+    pub fn with_metadata(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.metadata.insert(key.into(), value);
+        self
+    }
+}
+
 impl RegisteredSchema {
     /// Calculate the content hash using SHA-256
     pub fn calculate_content_hash(content: &str) -> String {
@@ -11,16 +11,43 @@
 //! - Storage paths (primary, cache, archive configurations)
 //! - Versioning policies (strategies, retention, compatibility)
 //! - Validation settings (comprehensive validation configuration)
+//!
+//! # Startup deadline
+//!
+//! All loads are independent, so they run concurrently and are bounded by
+//! [`StartupConfig::startup_deadline`]. Async consumers load natively; sync
+//! Config Manager reads run on the blocking pool. Loads still pending
+//! at the deadline fall back to defaults and the context reports
+//! [`StartupStatus::Degraded`]; per-phase timings land in [`StartupReport`].
 
 use crate::config_manager_adapter::{
-    ConfigConsumer, ConfigConsumerExt, ConfigManagerAdapter, GlobalConfig, SchemaPolicies, ConfigError,
-    SchemaSourcesConfig, StoragePathsConfig, VersioningPoliciesConfig, ValidationSettingsConfig,
+    AsyncConfigConsumer, BlockingConsumer, ConfigConsumer, ConfigConsumerExt, ConfigManagerAdapter, GlobalConfig,
+    SchemaPolicies, ConfigError, SchemaSourcesConfig, StoragePathsConfig, VersioningPoliciesConfig,
+    ValidationSettingsConfig,
 };
+use crate::config_refresh::RefreshStrictness;
 use llm_config_core::Environment;
+use std::future::Future;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
 use tracing::{info, warn, debug};
 
+/// Default bound on the whole configuration loading sequence
+pub const DEFAULT_STARTUP_DEADLINE: Duration = Duration::from_secs(30);
+
+/// Phase names recorded in [`StartupReport`]
+pub mod phases {
+    pub const ADAPTER: &str = "adapter";
+    pub const GLOBAL_CONFIG: &str = "global_config";
+    pub const SCHEMA_POLICIES: &str = "schema_policies";
+    pub const SCHEMA_SOURCES: &str = "schema_sources";
+    pub const STORAGE_PATHS: &str = "storage_paths";
+    pub const VERSIONING_POLICIES: &str = "versioning_policies";
+    pub const VALIDATION_SETTINGS: &str = "validation_settings";
+}
+
 /// Startup configuration for Schema Registry
 #[derive(Debug, Clone)]
 pub struct StartupConfig {
@@ -32,6 +59,12 @@ pub struct StartupConfig {
 
     /// Whether to fail if config loading fails
     pub require_config: bool,
+
+    /// Overall bound on configuration loading; pending loads fall back to defaults
+    pub startup_deadline: Duration,
+
+    /// Whether a runtime refresh with an invalid section is rejected as a whole
+    pub refresh_strictness: RefreshStrictness,
 }
 
 impl Default for StartupConfig {
@@ -40,6 +73,65 @@ impl Default for StartupConfig {
             config_storage_path: PathBuf::from("./config"),
             environment: Environment::Development,
             require_config: false,
+            startup_deadline: DEFAULT_STARTUP_DEADLINE,
+            refresh_strictness: RefreshStrictness::default(),
+        }
+    }
+}
+
+/// Overall outcome of startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StartupStatus {
+    /// Every phase loaded from Config Manager
+    #[default]
+    Ready,
+    /// At least one phase fell back to defaults
+    Degraded,
+}
+
+/// Outcome of a single startup phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhaseOutcome {
+    /// Loaded from Config Manager
+    Loaded,
+    /// Load failed; defaults were used
+    Defaulted,
+    /// Startup deadline elapsed first; defaults were used
+    TimedOut,
+}
+
+/// Timing and outcome of a single startup phase
+#[derive(Debug, Clone)]
+pub struct PhaseReport {
+    pub name: &'static str,
+    pub duration: Duration,
+    pub outcome: PhaseOutcome,
+}
+
+/// Per-phase durations and overall status of startup
+#[derive(Debug, Clone, Default)]
+pub struct StartupReport {
+    pub status: StartupStatus,
+    pub total: Duration,
+    pub phases: Vec<PhaseReport>,
+}
+
+impl StartupReport {
+    /// Look up a phase by name
+    pub fn phase(&self, name: &str) -> Option<&PhaseReport> {
+        self.phases.iter().find(|phase| phase.name == name)
+    }
+
+    fn record(&mut self, name: &'static str, duration: Duration, outcome: PhaseOutcome) {
+        if outcome != PhaseOutcome::Loaded {
+            self.status = StartupStatus::Degraded;
+        }
+        self.phases.push(PhaseReport { name, duration, outcome });
+    }
+
+    fn absorb(&mut self, other: StartupReport) {
+        for phase in other.phases {
+            self.record(phase.name, phase.duration, phase.outcome);
         }
     }
 }
@@ -60,6 +152,9 @@ pub struct StartupContext {
     /// Config adapter for runtime refresh
     pub config_adapter: Option<Arc<dyn ConfigConsumer>>,
 
+    /// Strictness runtime refreshes apply, from [`StartupConfig::refresh_strictness`]
+    pub refresh_strictness: RefreshStrictness,
+
     // Phase 2B: Additional configuration domains
 
     /// Schema sources configuration (Phase 2B)
@@ -73,6 +168,9 @@ pub struct StartupContext {
 
     /// Validation settings configuration (Phase 2B)
     pub validation_settings: ValidationSettingsConfig,
+
+    /// Per-phase timings and overall status
+    pub report: StartupReport,
 }
 
 impl Default for StartupContext {
@@ -81,10 +179,12 @@ impl Default for StartupContext {
             global_config: GlobalConfig::default(),
             schema_policies: SchemaPolicies::default(),
             config_adapter: None,
+            refresh_strictness: RefreshStrictness::default(),
             schema_sources: SchemaSourcesConfig::default(),
             storage_paths: StoragePathsConfig::default(),
             versioning_policies: VersioningPoliciesConfig::default(),
             validation_settings: ValidationSettingsConfig::default(),
+            report: StartupReport::default(),
         }
     }
 }
@@ -106,7 +206,11 @@ impl StartupContext {
 /// 1. Initializes Config Manager adapter
 /// 2. Loads global configuration
 /// 3. Ingests schema validation policies
-/// 4. Prepares optional runtime refresh hooks
+/// 4. Loads the Phase 2B configuration domains
+/// 5. Prepares optional runtime refresh hooks
+///
+/// Steps 2-4 run concurrently and are bounded by
+/// [`StartupConfig::startup_deadline`].
 ///
 /// # Arguments
 ///
@@ -127,6 +231,7 @@ impl StartupContext {
 ///     config_storage_path: "./config".into(),
 ///     environment: Environment::Production,
 ///     require_config: false,
+///     ..Default::default()
 /// };
 ///
 /// let context = initialize_with_config_manager(config).await?;
@@ -140,123 +245,177 @@ pub async fn initialize_with_config_manager(
     info!("Initializing Schema Registry with Config Manager integration");
     info!("Environment: {:?}, Config path: {:?}", config.environment, config.config_storage_path);
 
+    let started = Instant::now();
+    let deadline = started + config.startup_deadline;
+    let mut report = StartupReport::default();
+
     // Create Config Manager adapter
-    let adapter = match ConfigManagerAdapter::new(&config.config_storage_path, config.environment) {
+    let adapter = match ConfigManagerAdapter::open(config.config_storage_path.clone(), config.environment).await {
         Ok(adapter) => {
             info!("Config Manager adapter initialized successfully");
-            adapter
+            report.record(phases::ADAPTER, started.elapsed(), PhaseOutcome::Loaded);
+            Arc::new(adapter)
         }
         Err(e) => {
             if config.require_config {
                 return Err(e);
             } else {
                 warn!("Failed to initialize Config Manager, using defaults: {}", e);
-                return Ok(StartupContext::default());
-            }
-        }
-    };
-
-    // Load global configuration
-    let global_config = match adapter.load_global_config() {
-        Ok(config) => {
-            info!("Global configuration loaded from Config Manager");
-            config
-        }
-        Err(e) => {
-            if config.require_config {
-                return Err(e);
-            } else {
-                warn!("Failed to load global config, using defaults: {}", e);
-                GlobalConfig::default()
+                report.record(phases::ADAPTER, started.elapsed(), PhaseOutcome::Defaulted);
+                report.total = started.elapsed();
+                return Ok(StartupContext {
+                    refresh_strictness: config.refresh_strictness,
+                    report,
+                    ..Default::default()
+                });
             }
         }
     };
 
-    // Load schema validation policies
-    let schema_policies = match adapter.load_schema_policies() {
-        Ok(policies) => {
-            info!("Schema policies loaded from Config Manager");
-            policies
-        }
-        Err(e) => {
-            if config.require_config {
-                return Err(e);
-            } else {
-                warn!("Failed to load schema policies, using defaults: {}", e);
-                SchemaPolicies::default()
-            }
-        }
-    };
-
-    // Phase 2B: Load schema sources configuration
-    let schema_sources = match adapter.load_schema_sources() {
-        Ok(sources) => {
-            info!("Schema sources configuration loaded ({} sources)", sources.sources.len());
-            sources
-        }
-        Err(e) => {
-            debug!("Failed to load schema sources, using defaults: {}", e);
-            SchemaSourcesConfig::default()
-        }
-    };
-
-    // Phase 2B: Load storage paths configuration
-    let storage_paths = match adapter.load_storage_paths() {
-        Ok(paths) => {
-            info!("Storage paths configuration loaded (primary: {:?})", paths.primary.backend);
-            paths
-        }
-        Err(e) => {
-            debug!("Failed to load storage paths, using defaults: {}", e);
-            StoragePathsConfig::default()
-        }
-    };
-
-    // Phase 2B: Load versioning policies configuration
-    let versioning_policies = match adapter.load_versioning_policies() {
-        Ok(policies) => {
-            info!("Versioning policies loaded (strategy: {:?})", policies.default_strategy);
-            policies
-        }
-        Err(e) => {
-            debug!("Failed to load versioning policies, using defaults: {}", e);
-            VersioningPoliciesConfig::default()
-        }
-    };
-
-    // Phase 2B: Load validation settings configuration
-    let validation_settings = match adapter.load_validation_settings() {
-        Ok(settings) => {
-            info!("Validation settings loaded (LLM validation: {})", settings.llm.enabled);
-            settings
-        }
-        Err(e) => {
-            debug!("Failed to load validation settings, using defaults: {}", e);
-            ValidationSettingsConfig::default()
-        }
-    };
-
-    info!("Schema Registry initialization complete (Phase 2B)");
+    // Global config and policies honour `require_config`; the Phase 2B
+    // domains always fall back to defaults. All six loads run concurrently.
+    let (core, schema_sources, storage_paths, versioning_policies, validation_settings) = tokio::join!(
+        load_core(adapter.as_ref(), config.require_config, deadline),
+        run_phase(deadline, adapter.load_blocking(ConfigManagerAdapter::load_schema_sources)),
+        run_phase(deadline, adapter.load_blocking(ConfigManagerAdapter::load_storage_paths)),
+        run_phase(deadline, adapter.load_blocking(ConfigManagerAdapter::load_versioning_policies)),
+        run_phase(deadline, adapter.load_blocking(ConfigManagerAdapter::load_validation_settings)),
+    );
+
+    let (global_config, schema_policies, core_report) = core?;
+    report.absorb(core_report);
+    let schema_sources: SchemaSourcesConfig = settle(phases::SCHEMA_SOURCES, schema_sources, false, &mut report)?;
+    let storage_paths: StoragePathsConfig = settle(phases::STORAGE_PATHS, storage_paths, false, &mut report)?;
+    let versioning_policies: VersioningPoliciesConfig =
+        settle(phases::VERSIONING_POLICIES, versioning_policies, false, &mut report)?;
+    let validation_settings: ValidationSettingsConfig =
+        settle(phases::VALIDATION_SETTINGS, validation_settings, false, &mut report)?;
+    report.total = started.elapsed();
+
+    info!("Schema Registry initialization complete (Phase 2B) in {:?} ({:?})", report.total, report.status);
     info!("Server will listen on {}:{}", global_config.server.host, global_config.server.port);
     info!("Validation: max_schema_size={} bytes, strict_mode={}",
           global_config.validation.max_schema_size,
           global_config.validation.strict_mode);
-    info!("Phase 2B: schema_sources={}, versioning={:?}, llm_validation={}",
+    info!("Phase 2B: schema_sources={}, storage={:?}, versioning={:?}, llm_validation={}",
           schema_sources.sources.len(),
+          storage_paths.primary.backend,
           versioning_policies.default_strategy,
           validation_settings.llm.enabled);
 
     Ok(StartupContext {
         global_config,
         schema_policies,
-        config_adapter: Some(Arc::new(adapter)),
+        config_adapter: Some(adapter),
+        refresh_strictness: config.refresh_strictness,
         schema_sources,
         storage_paths,
         versioning_policies,
         validation_settings,
+        report,
     })
 }
 
+/// Load global configuration and schema policies concurrently from a sync `consumer`
+///
+/// The consumer runs on the blocking pool, see [`load_core_config_async`].
+pub async fn load_core_config(
+    consumer: Arc<dyn ConfigConsumer>,
+    config: &StartupConfig,
+) -> Result<(GlobalConfig, SchemaPolicies, StartupReport), ConfigError> {
+    load_core_config_async(&BlockingConsumer::new(consumer), config).await
+}
+
+/// Load global configuration and schema policies concurrently from `consumer`
+///
+/// Both loads are bounded by [`StartupConfig::startup_deadline`]; a load still
+/// pending at the deadline falls back to defaults (or fails when
+/// `require_config` is set).
+pub async fn load_core_config_async<C: AsyncConfigConsumer + ?Sized>(
+    consumer: &C,
+    config: &StartupConfig,
+) -> Result<(GlobalConfig, SchemaPolicies, StartupReport), ConfigError> {
+    let started = Instant::now();
+    let (global_config, schema_policies, mut report) =
+        load_core(consumer, config.require_config, started + config.startup_deadline).await?;
+    report.total = started.elapsed();
+    Ok((global_config, schema_policies, report))
+}
+
+async fn load_core<C: AsyncConfigConsumer + ?Sized>(
+    consumer: &C,
+    require_config: bool,
+    deadline: Instant,
+) -> Result<(GlobalConfig, SchemaPolicies, StartupReport), ConfigError> {
+    let (global_config, schema_policies) = tokio::join!(
+        run_phase(deadline, consumer.load_global_config()),
+        run_phase(deadline, consumer.load_schema_policies()),
+    );
+
+    let mut report = StartupReport::default();
+    let global_config = settle(phases::GLOBAL_CONFIG, global_config, require_config, &mut report)?;
+    let schema_policies = settle(phases::SCHEMA_POLICIES, schema_policies, require_config, &mut report)?;
+    Ok((global_config, schema_policies, report))
+}
+
+enum PhaseResult<T> {
+    Loaded(T),
+    Failed(ConfigError),
+    TimedOut,
+}
+
+/// Run a load, bounded by `deadline`
+///
+/// A load that misses the deadline is dropped. Loads on the blocking pool
+/// keep running on their thread; their result is simply discarded.
+async fn run_phase<T>(
+    deadline: Instant,
+    load: impl Future<Output = Result<T, ConfigError>>,
+) -> (PhaseResult<T>, Duration) {
+    let started = Instant::now();
+    let result = match tokio::time::timeout_at(deadline, load).await {
+        Ok(Ok(value)) => PhaseResult::Loaded(value),
+        Ok(Err(e)) => PhaseResult::Failed(e),
+        Err(_) => PhaseResult::TimedOut,
+    };
+    (result, started.elapsed())
+}
+
+/// Turn a phase result into a value, recording it in `report`
+fn settle<T: Default>(
+    name: &'static str,
+    (result, duration): (PhaseResult<T>, Duration),
+    require_config: bool,
+    report: &mut StartupReport,
+) -> Result<T, ConfigError> {
+    match result {
+        PhaseResult::Loaded(value) => {
+            debug!("Loaded {} in {:?}", name, duration);
+            report.record(name, duration, PhaseOutcome::Loaded);
+            Ok(value)
+        }
+        PhaseResult::Failed(e) => {
+            if require_config {
+                return Err(e);
+            }
+            warn!("Failed to load {}, using defaults: {}", name, e);
+            report.record(name, duration, PhaseOutcome::Defaulted);
+            Ok(T::default())
+        }
+        PhaseResult::TimedOut => {
+            if require_config {
+                return Err(ConfigError::ConfigManager(format!(
+                    "Startup deadline exceeded while loading {}",
+                    name
+                )));
+            }
+            warn!("Startup deadline exceeded while loading {}, using defaults", name);
+            report.record(name, duration, PhaseOutcome::TimedOut);
+            Ok(T::default())
+        }
+    }
+}
+
 /// Quick initialization for development
 pub async fn initialize_dev() -> Result<StartupContext, ConfigError> {
     initialize_with_config_manager(StartupConfig {
@@ -273,6 +432,7 @@ pub async fn initialize_prod(config_path: PathBuf) -> Result<StartupContext, Con
         config_storage_path: config_path,
         environment: Environment::Production,
         require_config: true,
+        ..Default::default()
     })
     .await
 }
@@ -323,10 +483,126 @@ mod tests {
             config_storage_path: PathBuf::from("/custom/path"),
             environment: Environment::Production,
             require_config: true,
+            startup_deadline: Duration::from_secs(5),
+            refresh_strictness: RefreshStrictness::AllOrNothing,
         };
 
         assert_eq!(config.config_storage_path, PathBuf::from("/custom/path"));
         assert_eq!(config.environment, Environment::Production);
         assert!(config.require_config);
+        assert_eq!(config.startup_deadline, Duration::from_secs(5));
+        assert_eq!(config.refresh_strictness, RefreshStrictness::AllOrNothing);
+    }
+
+    struct SlowConsumer {
+        global_delay: Duration,
+        policies_delay: Duration,
+    }
+
+    impl ConfigConsumer for SlowConsumer {
+        fn load_global_config(&self) -> Result<GlobalConfig, ConfigError> {
+            std::thread::sleep(self.global_delay);
+            let mut config = GlobalConfig::default();
+            config.server.port = 9090;
+            Ok(config)
+        }
+
+        fn load_schema_policies(&self) -> Result<SchemaPolicies, ConfigError> {
+            std::thread::sleep(self.policies_delay);
+            let mut policies = SchemaPolicies::default();
+            policies.field_naming.convention = "camelCase".to_string();
+            Ok(policies)
+        }
+
+        fn refresh(&self) -> Result<(), ConfigError> {
+            Ok(())
+        }
+    }
+
+    fn slow_consumer(global_ms: u64, policies_ms: u64) -> Arc<dyn ConfigConsumer> {
+        Arc::new(SlowConsumer {
+            global_delay: Duration::from_millis(global_ms),
+            policies_delay: Duration::from_millis(policies_ms),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_core_loads_run_concurrently() {
+        let started = std::time::Instant::now();
+        let (global, policies, report) = load_core_config(slow_consumer(300, 400), &StartupConfig::default())
+            .await
+            .unwrap();
+        let elapsed = started.elapsed();
+
+        // Bounded by the slower load (400ms), not the sum (700ms)
+        assert!(elapsed >= Duration::from_millis(400));
+        assert!(elapsed < Duration::from_millis(650), "startup took {:?}", elapsed);
+
+        assert_eq!(global.server.port, 9090);
+        assert_eq!(policies.field_naming.convention, "camelCase");
+        assert_eq!(report.status, StartupStatus::Ready);
+        assert!(report.phase(phases::GLOBAL_CONFIG).unwrap().duration >= Duration::from_millis(300));
+        assert!(report.phase(phases::SCHEMA_POLICIES).unwrap().duration >= Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn test_startup_deadline_falls_back_to_defaults() {
+        let config = StartupConfig {
+            startup_deadline: Duration::from_millis(200),
+            ..Default::default()
+        };
+
+        let started = std::time::Instant::now();
+        let (global, policies, report) = load_core_config(slow_consumer(20, 1000), &config).await.unwrap();
+        assert!(started.elapsed() < Duration::from_millis(800));
+
+        assert_eq!(global.server.port, 9090);
+        assert_eq!(policies.field_naming.convention, "snake_case");
+        assert_eq!(report.status, StartupStatus::Degraded);
+        assert_eq!(report.phase(phases::GLOBAL_CONFIG).unwrap().outcome, PhaseOutcome::Loaded);
+        assert_eq!(report.phase(phases::SCHEMA_POLICIES).unwrap().outcome, PhaseOutcome::TimedOut);
+    }
+
+    /// Native async consumer that never finishes loading policies
+    struct StalledConsumer;
+
+    #[async_trait::async_trait]
+    impl AsyncConfigConsumer for StalledConsumer {
+        async fn load_global_config(&self) -> Result<GlobalConfig, ConfigError> {
+            Ok(GlobalConfig::default())
+        }
+
+        async fn load_schema_policies(&self) -> Result<SchemaPolicies, ConfigError> {
+            std::future::pending().await
+        }
+
+        async fn refresh(&self) -> Result<(), ConfigError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_consumer_bounded_by_deadline() {
+        let config = StartupConfig {
+            startup_deadline: Duration::from_millis(100),
+            ..Default::default()
+        };
+
+        let (_, policies, report) = load_core_config_async(&StalledConsumer, &config).await.unwrap();
+        assert_eq!(policies.field_naming.convention, "snake_case");
+        assert_eq!(report.phase(phases::GLOBAL_CONFIG).unwrap().outcome, PhaseOutcome::Loaded);
+        assert_eq!(report.phase(phases::SCHEMA_POLICIES).unwrap().outcome, PhaseOutcome::TimedOut);
+    }
+
+    #[tokio::test]
+    async fn test_startup_deadline_with_required_config_fails() {
+        let config = StartupConfig {
+            require_config: true,
+            startup_deadline: Duration::from_millis(100),
+            ..Default::default()
+        };
+
+        let result = load_core_config(slow_consumer(500, 20), &config).await;
+        assert!(matches!(result, Err(ConfigError::ConfigManager(msg)) if msg.contains(phases::GLOBAL_CONFIG)));
     }
 }
@@ -0,0 +1,470 @@
+//! Style linting of schema content
+//!
+//! A schema can be valid and still hard to use: properties nobody described,
+//! no example payload, titles like `object1`. The checks here look for that
+//! kind of documentation debt, as configured by a [`StylePolicy`]:
+//!
+//! - [`StyleCheck::Description`]: every JSON Schema property and Avro field
+//!   has a description of at least `min_description_length` characters;
+//! - [`StyleCheck::Example`]: a top-level JSON Schema has at least one entry
+//!   in `examples`;
+//! - [`StyleCheck::TitleCase`]: JSON Schema titles are in Title Case and are
+//!   not placeholders such as `object1`;
+//! - [`StyleCheck::EnumDocs`]: every JSON Schema enum value has an entry in
+//!   `x-enum-descriptions`, and every Avro enum has a `doc`.
+//!
+//! Protobuf is not checked. Paths follow [`doc_policy`](crate::doc_policy):
+//! the JSON location of the object at fault (`$.properties.amount`,
+//! `$.fields[0]`).
+//!
+//! Findings report documentation debt, not invalid schemas. They weigh little
+//! in quality scores ([`StyleCheck::weight`]), and [`StyleCoverage`] totals
+//! them per team.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::config_manager_adapter::StylePolicy;
+use crate::doc_policy::{data_keys, doc_key, parse_json};
+use crate::error::Result;
+use crate::types::SerializationFormat;
+
+/// JSON Schema keyword mapping enum values to their descriptions
+pub const ENUM_DESCRIPTIONS_KEY: &str = "x-enum-descriptions";
+
+/// Most that one schema's style findings take off a 100-point quality score
+pub const MAX_STYLE_PENALTY: f64 = 10.0;
+
+/// Words left lowercase in Title Case unless they come first
+const MINOR_WORDS: &[&str] = &["a", "an", "and", "as", "at", "by", "for", "in", "of", "on", "or", "the", "to", "with"];
+
+/// Titles that say nothing about the schema, ignoring case and a trailing number
+const PLACEHOLDER_TITLES: &[&str] = &["data", "item", "new", "object", "record", "schema", "test", "type", "untitled"];
+
+/// A style check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StyleCheck {
+    Description,
+    Example,
+    TitleCase,
+    EnumDocs,
+}
+
+impl StyleCheck {
+    pub const ALL: [StyleCheck; 4] = [
+        StyleCheck::Description,
+        StyleCheck::Example,
+        StyleCheck::TitleCase,
+        StyleCheck::EnumDocs,
+    ];
+
+    /// Rule id reported in findings and named by waivers
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            StyleCheck::Description => "style-description",
+            StyleCheck::Example => "style-example",
+            StyleCheck::TitleCase => "style-title-case",
+            StyleCheck::EnumDocs => "style-enum-docs",
+        }
+    }
+
+    /// Points one finding takes off a 100-point quality score
+    ///
+    /// Kept small so style debt shows in scores without outweighing a real
+    /// defect.
+    pub fn weight(&self) -> f64 {
+        match self {
+            StyleCheck::Description => 0.5,
+            StyleCheck::Example => 1.0,
+            StyleCheck::TitleCase => 0.25,
+            StyleCheck::EnumDocs => 0.5,
+        }
+    }
+
+    fn is_enabled(&self, policy: &StylePolicy) -> bool {
+        match self {
+            StyleCheck::Description => policy.descriptions,
+            StyleCheck::Example => policy.examples,
+            StyleCheck::TitleCase => policy.title_case,
+            StyleCheck::EnumDocs => policy.enum_docs,
+        }
+    }
+}
+
+/// A place where a schema falls short of the style policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StyleFinding {
+    pub check: StyleCheck,
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for StyleFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.path)
+    }
+}
+
+/// Quality score points `findings` take off, capped at [`MAX_STYLE_PENALTY`]
+pub fn penalty(findings: &[StyleFinding]) -> f64 {
+    findings
+        .iter()
+        .map(|finding| finding.check.weight())
+        .sum::<f64>()
+        .min(MAX_STYLE_PENALTY)
+}
+
+/// Check `schema` against the enabled checks of `policy`
+pub fn lint(schema: &str, format: SerializationFormat, policy: &StylePolicy) -> Result<Vec<StyleFinding>> {
+    let Some(doc_key) = doc_key(format).filter(|_| policy.is_enabled()) else {
+        return Ok(Vec::new());
+    };
+    let value = parse_json(schema, format)?;
+
+    let mut linter = Linter {
+        format,
+        doc_key,
+        data_keys: data_keys(format),
+        policy,
+        findings: Vec::new(),
+    };
+    if format == SerializationFormat::JsonSchema && !has_examples(&value) {
+        linter.report(StyleCheck::Example, "$", "schema has no examples".to_string());
+    }
+    linter.visit(&value, "$");
+    Ok(linter.findings)
+}
+
+fn has_examples(value: &Value) -> bool {
+    value
+        .get("examples")
+        .and_then(Value::as_array)
+        .is_some_and(|examples| !examples.is_empty())
+}
+
+struct Linter<'a> {
+    format: SerializationFormat,
+    doc_key: &'static str,
+    data_keys: &'static [&'static str],
+    policy: &'a StylePolicy,
+    findings: Vec<StyleFinding>,
+}
+
+impl Linter<'_> {
+    fn report(&mut self, check: StyleCheck, path: &str, message: String) {
+        if check.is_enabled(self.policy) {
+            self.findings.push(StyleFinding {
+                check,
+                path: path.to_string(),
+                message,
+            });
+        }
+    }
+
+    fn visit(&mut self, value: &Value, path: &str) {
+        match value {
+            Value::Object(obj) => {
+                match self.format {
+                    SerializationFormat::JsonSchema => self.check_json_schema(obj, path),
+                    SerializationFormat::Avro => self.check_avro(obj, path),
+                    SerializationFormat::Protobuf => {}
+                }
+                for (name, child) in obj {
+                    if name == self.doc_key || self.data_keys.contains(&name.as_str()) {
+                        continue;
+                    }
+                    self.visit(child, &format!("{}.{}", path, name));
+                }
+            }
+            Value::Array(items) => {
+                for (idx, item) in items.iter().enumerate() {
+                    self.visit(item, &format!("{}[{}]", path, idx));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_json_schema(&mut self, obj: &Map<String, Value>, path: &str) {
+        if let Some(properties) = obj.get("properties").and_then(Value::as_object) {
+            for (name, property) in properties {
+                // A bare reference is documented where it points
+                let Some(property) = property.as_object().filter(|p| !p.contains_key("$ref")) else {
+                    continue;
+                };
+                self.check_description(property, &format!("{}.properties.{}", path, name));
+            }
+        }
+
+        if let Some(title) = obj.get("title").and_then(Value::as_str) {
+            if let Some(problem) = title_problem(title) {
+                self.report(StyleCheck::TitleCase, path, problem);
+            }
+        }
+
+        if let Some(values) = obj.get("enum").and_then(Value::as_array) {
+            let described = obj.get(ENUM_DESCRIPTIONS_KEY).and_then(Value::as_object);
+            let undocumented: Vec<String> = values
+                .iter()
+                .map(|value| value.as_str().map_or_else(|| value.to_string(), str::to_string))
+                .filter(|key| !described.is_some_and(|described| described.contains_key(key)))
+                .collect();
+            if !undocumented.is_empty() {
+                self.report(
+                    StyleCheck::EnumDocs,
+                    path,
+                    format!("enum values without an entry in {}: {}", ENUM_DESCRIPTIONS_KEY, undocumented.join(", ")),
+                );
+            }
+        }
+    }
+
+    fn check_avro(&mut self, obj: &Map<String, Value>, path: &str) {
+        match obj.get("type").and_then(Value::as_str) {
+            Some("record") | Some("error") => {
+                let fields = obj.get("fields").and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+                for (idx, field) in fields.iter().enumerate() {
+                    if let Some(field) = field.as_object() {
+                        self.check_description(field, &format!("{}.fields[{}]", path, idx));
+                    }
+                }
+            }
+            Some("enum") if !obj.get(self.doc_key).is_some_and(Value::is_string) => {
+                self.report(StyleCheck::EnumDocs, path, "enum has no doc describing its symbols".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn check_description(&mut self, obj: &Map<String, Value>, path: &str) {
+        let min = self.policy.min_description_length;
+        let length = obj
+            .get(self.doc_key)
+            .and_then(Value::as_str)
+            .map(|text| text.trim().chars().count());
+        match length {
+            None | Some(0) => self.report(StyleCheck::Description, path, "no description".to_string()),
+            Some(length) if length < min => self.report(
+                StyleCheck::Description,
+                path,
+                format!("description is {} characters, minimum is {}", length, min),
+            ),
+            Some(_) => {}
+        }
+    }
+}
+
+/// Why `title` is not a good title, if it isn't
+fn title_problem(title: &str) -> Option<String> {
+    let trimmed = title.trim();
+    let stem = trimmed
+        .trim_end_matches(|c: char| c.is_ascii_digit())
+        .trim_end_matches(['_', '-', ' ']);
+    if stem.is_empty() || PLACEHOLDER_TITLES.contains(&stem.to_ascii_lowercase().as_str()) {
+        return Some(format!("title '{}' does not describe the schema", title));
+    }
+
+    trimmed
+        .split_whitespace()
+        .enumerate()
+        .find(|(i, word)| word.starts_with(char::is_lowercase) && (*i == 0 || !MINOR_WORDS.contains(word)))
+        .map(|(_, word)| format!("title '{}' is not in Title Case ('{}' should be capitalised)", title, word))
+}
+
+/// Style findings of a batch of schemas, totalled per team
+///
+/// Makes documentation debt comparable between teams: how many schemas are
+/// free of findings, which checks fail most, and what the findings cost in
+/// quality score.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StyleCoverage {
+    pub teams: BTreeMap<String, TeamStyleCoverage>,
+}
+
+/// Style findings of one team's schemas
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TeamStyleCoverage {
+    /// Schemas linted
+    pub schemas: usize,
+    /// Schemas without findings
+    pub clean: usize,
+    /// Findings by check
+    pub findings: BTreeMap<StyleCheck, usize>,
+    /// Sum of each schema's [`penalty`]
+    pub penalty: f64,
+}
+
+impl TeamStyleCoverage {
+    /// Share of schemas without findings, as a percentage; 100 when there are none
+    pub fn clean_percent(&self) -> f64 {
+        if self.schemas == 0 {
+            100.0
+        } else {
+            self.clean as f64 / self.schemas as f64 * 100.0
+        }
+    }
+
+    /// Mean quality score points lost per schema
+    pub fn mean_penalty(&self) -> f64 {
+        if self.schemas == 0 {
+            0.0
+        } else {
+            self.penalty / self.schemas as f64
+        }
+    }
+
+    fn absorb(&mut self, other: &TeamStyleCoverage) {
+        self.schemas += other.schemas;
+        self.clean += other.clean;
+        for (check, count) in &other.findings {
+            *self.findings.entry(*check).or_default() += count;
+        }
+        self.penalty += other.penalty;
+    }
+}
+
+impl StyleCoverage {
+    /// Count the `findings` of one schema owned by `team`
+    pub fn record(&mut self, team: &str, findings: &[StyleFinding]) {
+        let coverage = self.teams.entry(team.to_string()).or_default();
+        coverage.schemas += 1;
+        if findings.is_empty() {
+            coverage.clean += 1;
+        }
+        for finding in findings {
+            *coverage.findings.entry(finding.check).or_default() += 1;
+        }
+        coverage.penalty += penalty(findings);
+    }
+
+    /// All teams together
+    pub fn total(&self) -> TeamStyleCoverage {
+        let mut total = TeamStyleCoverage::default();
+        for coverage in self.teams.values() {
+            total.absorb(coverage);
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON_SCHEMA: &str = r#"{
+        "title": "object1",
+        "type": "object",
+        "properties": {
+            "amount": {"type": "number", "description": "Amount charged, in minor units"},
+            "note": {"type": "string", "description": "Note"},
+            "status": {
+                "title": "payment status",
+                "type": "string",
+                "description": "Where the payment is in its lifecycle",
+                "enum": ["pending", "settled"],
+                "x-enum-descriptions": {"pending": "Awaiting settlement"}
+            },
+            "customer": {"$ref": "#/definitions/Customer"},
+            "tags": {"type": "array"}
+        }
+    }"#;
+
+    fn checks(findings: &[StyleFinding]) -> Vec<(StyleCheck, &str)> {
+        findings.iter().map(|f| (f.check, f.path.as_str())).collect()
+    }
+
+    #[test]
+    fn test_json_schema_findings() {
+        let findings = lint(JSON_SCHEMA, SerializationFormat::JsonSchema, &StylePolicy::default()).unwrap();
+
+        assert_eq!(
+            checks(&findings),
+            vec![
+                (StyleCheck::Example, "$"),
+                (StyleCheck::Description, "$.properties.note"),
+                (StyleCheck::Description, "$.properties.tags"),
+                (StyleCheck::TitleCase, "$"),
+                (StyleCheck::TitleCase, "$.properties.status"),
+                (StyleCheck::EnumDocs, "$.properties.status"),
+            ]
+        );
+        assert!(findings[2].message.contains("no description"));
+        assert!(findings[5].message.ends_with("settled"));
+    }
+
+    #[test]
+    fn test_checks_can_be_disabled() {
+        let policy = StylePolicy {
+            examples: false,
+            title_case: false,
+            enum_docs: false,
+            min_description_length: 4,
+            ..StylePolicy::default()
+        };
+        let findings = lint(JSON_SCHEMA, SerializationFormat::JsonSchema, &policy).unwrap();
+        assert_eq!(checks(&findings), vec![(StyleCheck::Description, "$.properties.tags")]);
+
+        let off = StylePolicy {
+            descriptions: false,
+            ..policy
+        };
+        assert!(lint(JSON_SCHEMA, SerializationFormat::JsonSchema, &off).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_avro_fields_and_enums() {
+        let schema = r#"{
+            "type": "record", "name": "Payment", "doc": "A payment",
+            "fields": [
+                {"name": "amount", "type": "long", "doc": "Amount in minor units"},
+                {"name": "status", "type": {"type": "enum", "name": "Status", "symbols": ["PENDING", "SETTLED"]}}
+            ]
+        }"#;
+        let findings = lint(schema, SerializationFormat::Avro, &StylePolicy::default()).unwrap();
+        assert_eq!(
+            checks(&findings),
+            vec![(StyleCheck::Description, "$.fields[1]"), (StyleCheck::EnumDocs, "$.fields[1].type")]
+        );
+    }
+
+    #[test]
+    fn test_titles() {
+        assert!(title_problem("Payment Event").is_none());
+        assert!(title_problem("Terms of Service").is_none());
+        assert!(title_problem("Schema_2").is_some());
+        assert!(title_problem("of Service").is_some());
+        assert!(title_problem("payment").is_some());
+    }
+
+    #[test]
+    fn test_coverage_per_team() {
+        let findings = lint(JSON_SCHEMA, SerializationFormat::JsonSchema, &StylePolicy::default()).unwrap();
+        let mut coverage = StyleCoverage::default();
+        coverage.record("payments", &findings);
+        coverage.record("payments", &[]);
+        coverage.record("orders", &findings[..1]);
+
+        let payments = &coverage.teams["payments"];
+        assert_eq!(payments.schemas, 2);
+        assert_eq!(payments.clean_percent(), 50.0);
+        assert_eq!(payments.findings[&StyleCheck::Description], 2);
+        assert_eq!(payments.penalty, penalty(&findings));
+        assert!(penalty(&findings) <= MAX_STYLE_PENALTY);
+
+        let total = coverage.total();
+        assert_eq!(total.schemas, 3);
+        assert_eq!(total.findings[&StyleCheck::Example], 2);
+        assert_eq!(total.penalty, penalty(&findings) + StyleCheck::Example.weight());
+    }
+
+    #[test]
+    fn test_rule_ids_are_known_policy_rules() {
+        let policies = crate::config_manager_adapter::SchemaPolicies::default();
+        for check in StyleCheck::ALL {
+            assert!(policies.has_rule(check.rule_id()), "{}", check.rule_id());
+        }
+    }
+}
@@ -4,10 +4,12 @@
 //! and event publishing.
 
 use async_trait::async_trait;
+use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::error::Result;
 use crate::events::SchemaEvent;
+use crate::pagination::{Page, PageRequest};
 use crate::schema::{RegisteredSchema, SchemaInput};
 use crate::types::CompatibilityMode;
 use crate::versioning::SemanticVersion;
@@ -35,6 +37,47 @@ pub trait SchemaStorage: Send + Sync {
 
     /// Find schemas by namespace and name
     async fn find_by_name(&self, namespace: &str, name: &str) -> Result<Vec<RegisteredSchema>>;
+
+    /// List distinct subjects (`namespace.name`) in ascending order, one page at a time
+    ///
+    /// See [`crate::pagination`] for how cursors behave under concurrent writes.
+    async fn list_subjects(&self, page: &PageRequest) -> Result<Page<String>>;
+}
+
+/// Storage shared between owners, e.g. an embedded registry and a read cache in front of it
+#[async_trait]
+impl<S: SchemaStorage + ?Sized> SchemaStorage for Arc<S> {
+    async fn store(&self, schema: RegisteredSchema) -> Result<()> {
+        (**self).store(schema).await
+    }
+
+    async fn retrieve(&self, id: Uuid, version: Option<SemanticVersion>) -> Result<RegisteredSchema> {
+        (**self).retrieve(id, version).await
+    }
+
+    async fn retrieve_by_hash(&self, content_hash: &str) -> Result<Option<RegisteredSchema>> {
+        (**self).retrieve_by_hash(content_hash).await
+    }
+
+    async fn update(&self, schema: RegisteredSchema) -> Result<()> {
+        (**self).update(schema).await
+    }
+
+    async fn delete(&self, id: Uuid, version: SemanticVersion) -> Result<()> {
+        (**self).delete(id, version).await
+    }
+
+    async fn list_versions(&self, id: Uuid) -> Result<Vec<SemanticVersion>> {
+        (**self).list_versions(id).await
+    }
+
+    async fn find_by_name(&self, namespace: &str, name: &str) -> Result<Vec<RegisteredSchema>> {
+        (**self).find_by_name(namespace, name).await
+    }
+
+    async fn list_subjects(&self, page: &PageRequest) -> Result<Page<String>> {
+        (**self).list_subjects(page).await
+    }
 }
 
 /// Validation result
@@ -93,23 +136,61 @@ pub struct CompatibilityResult {
     pub checked_versions: Vec<SemanticVersion>,
 }
 
-/// Compatibility violation
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// Compatibility violation located in a schema
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct CompatibilityViolation {
-    /// Type of violation
-    pub violation_type: crate::types::ViolationType,
-    /// Field path where violation occurred
+    /// What went wrong
+    pub violation: crate::types::CompatViolation,
+    /// Path to the offending node (`$` is the schema root)
     pub field_path: String,
-    /// Old value
-    pub old_value: Option<serde_json::Value>,
-    /// New value
-    pub new_value: Option<serde_json::Value>,
+    /// Version of the existing schema, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_version: Option<SemanticVersion>,
+    /// Version of the candidate schema, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_version: Option<SemanticVersion>,
     /// Severity
     pub severity: crate::types::ViolationSeverity,
-    /// Description of the violation
+    /// Human-readable rendering of the violation
     pub description: String,
 }
 
+impl CompatibilityViolation {
+    /// Create a violation at `field_path`, deriving severity and description
+    pub fn new(violation: crate::types::CompatViolation, field_path: impl Into<String>) -> Self {
+        Self {
+            severity: violation.severity(),
+            description: violation.to_string(),
+            violation,
+            field_path: field_path.into(),
+            old_version: None,
+            new_version: None,
+        }
+    }
+
+    /// Attach the versions that were compared
+    pub fn with_versions(mut self, old_version: SemanticVersion, new_version: SemanticVersion) -> Self {
+        self.old_version = Some(old_version);
+        self.new_version = Some(new_version);
+        self
+    }
+
+    /// Stable discriminant of the underlying violation
+    pub fn code(&self) -> &'static str {
+        self.violation.code()
+    }
+}
+
+impl std::fmt::Display for CompatibilityViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field_path, self.description)?;
+        if let (Some(old), Some(new)) = (&self.old_version, &self.new_version) {
+            write!(f, " ({} -> {})", old, new)?;
+        }
+        Ok(())
+    }
+}
+
 /// Trait for compatibility checking
 #[async_trait]
 pub trait CompatibilityChecker: Send + Sync {
@@ -167,4 +248,23 @@ mod tests {
         assert!(result.is_compatible);
         assert!(result.violations.is_empty());
     }
+
+    #[test]
+    fn test_compatibility_violation_rendering() {
+        let violation = CompatibilityViolation::new(
+            crate::types::CompatViolation::RequiredFieldAdded { field: "email".to_string() },
+            "$.user",
+        )
+        .with_versions(SemanticVersion::new(1, 0, 0), SemanticVersion::new(1, 1, 0));
+
+        assert_eq!(violation.code(), "REQUIRED_FIELD_ADDED");
+        assert_eq!(
+            violation.to_string(),
+            "$.user: required field 'email' was added without a default (1.0.0 -> 1.1.0)"
+        );
+
+        let json = serde_json::to_value(&violation).unwrap();
+        assert_eq!(json["violation"]["kind"], "REQUIRED_FIELD_ADDED");
+        assert_eq!(json["old_version"]["major"], 1);
+    }
 }
@@ -100,6 +100,204 @@ pub enum ViolationSeverity {
     Info,
 }
 
+/// Structured description of a single compatibility violation
+///
+/// Serialized with a stable `kind` discriminant so that tooling consuming JSON
+/// output can match on the variant instead of parsing the human message.
+/// Types are rendered as strings (`"string|null"`, `"long"`, ...) so the same
+/// variants cover every schema format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CompatViolation {
+    /// Field the old schema requires, without a default, is gone from the new schema
+    FieldRemovedWithoutDefault {
+        /// Field name
+        field: String,
+    },
+    /// Field still exists in the new schema but is no longer required
+    FieldMadeOptional {
+        /// Field name
+        field: String,
+    },
+    /// New schema requires a field that old data may not contain
+    RequiredFieldAdded {
+        /// Field name
+        field: String,
+    },
+    /// Field was removed from a schema that rejects unknown fields
+    FieldRemoved {
+        /// Field name
+        field: String,
+    },
+    /// Field was added while the old schema rejects unknown fields
+    FieldAdded {
+        /// Field name
+        field: String,
+    },
+    /// New schema accepts a strict subset of the old type
+    TypeNarrowed {
+        /// Old type
+        from: String,
+        /// New type
+        to: String,
+    },
+    /// New schema accepts a strict superset of the old type
+    TypeWidened {
+        /// Old type
+        from: String,
+        /// New type
+        to: String,
+    },
+    /// Types are unrelated
+    TypeChanged {
+        /// Old type
+        from: String,
+        /// New type
+        to: String,
+    },
+    /// Enum symbol present in the old schema was removed
+    EnumSymbolRemoved {
+        /// Symbol
+        symbol: String,
+    },
+    /// Enum symbol was added that old readers cannot decode
+    EnumSymbolAdded {
+        /// Symbol
+        symbol: String,
+    },
+    /// Union branches changed so that some values no longer resolve
+    IncompatibleUnionChange {
+        /// Old branches
+        from: Vec<String>,
+        /// New branches
+        to: Vec<String>,
+    },
+    /// Validation constraint was made stricter
+    ConstraintTightened {
+        /// Constraint keyword
+        constraint: String,
+        /// Old value (null when absent)
+        from: serde_json::Value,
+        /// New value (null when absent)
+        to: serde_json::Value,
+    },
+    /// Validation constraint was relaxed
+    ConstraintRelaxed {
+        /// Constraint keyword
+        constraint: String,
+        /// Old value (null when absent)
+        from: serde_json::Value,
+        /// New value (null when absent)
+        to: serde_json::Value,
+    },
+    /// Schema format changed between versions
+    FormatChanged {
+        /// Old format
+        from: SerializationFormat,
+        /// New format
+        to: SerializationFormat,
+    },
+}
+
+impl CompatViolation {
+    /// Stable discriminant, identical to the serialized `kind`
+    pub fn code(&self) -> &'static str {
+        match self {
+            CompatViolation::FieldRemovedWithoutDefault { .. } => "FIELD_REMOVED_WITHOUT_DEFAULT",
+            CompatViolation::FieldMadeOptional { .. } => "FIELD_MADE_OPTIONAL",
+            CompatViolation::RequiredFieldAdded { .. } => "REQUIRED_FIELD_ADDED",
+            CompatViolation::FieldRemoved { .. } => "FIELD_REMOVED",
+            CompatViolation::FieldAdded { .. } => "FIELD_ADDED",
+            CompatViolation::TypeNarrowed { .. } => "TYPE_NARROWED",
+            CompatViolation::TypeWidened { .. } => "TYPE_WIDENED",
+            CompatViolation::TypeChanged { .. } => "TYPE_CHANGED",
+            CompatViolation::EnumSymbolRemoved { .. } => "ENUM_SYMBOL_REMOVED",
+            CompatViolation::EnumSymbolAdded { .. } => "ENUM_SYMBOL_ADDED",
+            CompatViolation::IncompatibleUnionChange { .. } => "INCOMPATIBLE_UNION_CHANGE",
+            CompatViolation::ConstraintTightened { .. } => "CONSTRAINT_TIGHTENED",
+            CompatViolation::ConstraintRelaxed { .. } => "CONSTRAINT_RELAXED",
+            CompatViolation::FormatChanged { .. } => "FORMAT_CHANGED",
+        }
+    }
+
+    /// Coarse category of the violation
+    pub fn violation_type(&self) -> ViolationType {
+        match self {
+            CompatViolation::FieldRemovedWithoutDefault { .. }
+            | CompatViolation::FieldRemoved { .. } => ViolationType::FieldRemoved,
+            CompatViolation::FieldMadeOptional { .. }
+            | CompatViolation::FieldAdded { .. }
+            | CompatViolation::ConstraintTightened { .. }
+            | CompatViolation::ConstraintRelaxed { .. } => ViolationType::ConstraintAdded,
+            CompatViolation::RequiredFieldAdded { .. } => ViolationType::RequiredAdded,
+            CompatViolation::TypeNarrowed { .. }
+            | CompatViolation::TypeWidened { .. }
+            | CompatViolation::TypeChanged { .. }
+            | CompatViolation::IncompatibleUnionChange { .. } => ViolationType::TypeChanged,
+            CompatViolation::EnumSymbolRemoved { .. }
+            | CompatViolation::EnumSymbolAdded { .. } => ViolationType::EnumValueRemoved,
+            CompatViolation::FormatChanged { .. } => ViolationType::FormatChanged,
+        }
+    }
+
+    /// Severity of the violation
+    ///
+    /// Every variant breaks at least one reader in the mode it was reported
+    /// for, so all are breaking today; the hook exists for advisory kinds.
+    pub fn severity(&self) -> ViolationSeverity {
+        ViolationSeverity::Breaking
+    }
+}
+
+impl std::fmt::Display for CompatViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompatViolation::FieldRemovedWithoutDefault { field } => {
+                write!(f, "field '{}' was removed but has no default in the old schema", field)
+            }
+            CompatViolation::FieldMadeOptional { field } => {
+                write!(f, "field '{}' is no longer required", field)
+            }
+            CompatViolation::RequiredFieldAdded { field } => {
+                write!(f, "required field '{}' was added without a default", field)
+            }
+            CompatViolation::FieldRemoved { field } => {
+                write!(f, "field '{}' was removed and unknown fields are rejected", field)
+            }
+            CompatViolation::FieldAdded { field } => {
+                write!(f, "field '{}' was added but the old schema rejects unknown fields", field)
+            }
+            CompatViolation::TypeNarrowed { from, to } => {
+                write!(f, "type narrowed from {} to {}", from, to)
+            }
+            CompatViolation::TypeWidened { from, to } => {
+                write!(f, "type widened from {} to {}", from, to)
+            }
+            CompatViolation::TypeChanged { from, to } => {
+                write!(f, "type changed from {} to {}", from, to)
+            }
+            CompatViolation::EnumSymbolRemoved { symbol } => {
+                write!(f, "enum symbol '{}' was removed", symbol)
+            }
+            CompatViolation::EnumSymbolAdded { symbol } => {
+                write!(f, "enum symbol '{}' was added", symbol)
+            }
+            CompatViolation::IncompatibleUnionChange { from, to } => {
+                write!(f, "union changed from [{}] to [{}]", from.join(", "), to.join(", "))
+            }
+            CompatViolation::ConstraintTightened { constraint, from, to } => {
+                write!(f, "constraint '{}' tightened from {} to {}", constraint, from, to)
+            }
+            CompatViolation::ConstraintRelaxed { constraint, from, to } => {
+                write!(f, "constraint '{}' relaxed from {} to {}", constraint, from, to)
+            }
+            CompatViolation::FormatChanged { from, to } => {
+                write!(f, "schema format changed from {} to {}", from, to)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,4 +319,19 @@ mod tests {
         assert!(CompatibilityMode::ForwardTransitive.is_transitive());
         assert!(CompatibilityMode::FullTransitive.is_transitive());
     }
+
+    #[test]
+    fn test_compat_violation_stable_discriminant() {
+        let violation = CompatViolation::TypeNarrowed {
+            from: "long".to_string(),
+            to: "int".to_string(),
+        };
+        let json = serde_json::to_value(&violation).unwrap();
+        assert_eq!(json["kind"], violation.code());
+        assert_eq!(json["from"], "long");
+
+        let back: CompatViolation = serde_json::from_value(json).unwrap();
+        assert_eq!(back, violation);
+        assert_eq!(violation.to_string(), "type narrowed from long to int");
+    }
 }
@@ -12,7 +12,7 @@ pub mod metrics;
 pub mod middleware;
 pub mod tracing_setup;
 
-pub use metrics::MetricsCollector;
+pub use metrics::{CacheCounters, MetricsCollector, RetryCounters};
 pub use tracing_setup::{
     init_tracing, setup_tracing, shutdown_tracing, TracingConfig,
     context as trace_context, correlation,
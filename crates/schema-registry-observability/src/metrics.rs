@@ -5,14 +5,46 @@
 //! - Business metrics (schemas, validations, compatibility checks)
 //! - Storage metrics (cache hit rate, DB connections, query duration)
 //! - System metrics (memory, CPU, goroutines)
+//!
+//! Metric names and help text come from [`schema_registry_core::metric_names`].
 
 use prometheus::{
     register_counter_vec, register_gauge_vec, register_histogram_vec, register_int_counter_vec,
     register_int_gauge_vec, CounterVec, GaugeVec, HistogramVec, IntCounterVec, IntGaugeVec,
     Registry, TextEncoder,
 };
+use schema_registry_core::metric_names;
 use std::sync::Arc;
 
+/// Cumulative counters reported by one cache tier
+///
+/// Caches keep their own totals; [`MetricsCollector::observe_cache`] brings
+/// the exported series up to date with them.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheCounters {
+    pub hits: u64,
+    pub misses: u64,
+    /// Misses answered with an expired entry
+    pub stale_serves: u64,
+    /// Entries dropped on expiry or for capacity
+    pub evictions: u64,
+    /// Entries dropped because their subject changed
+    pub invalidations: u64,
+    pub entries: u64,
+}
+
+/// Cumulative counters reported by a retrying storage wrapper
+///
+/// Like [`CacheCounters`], these are the wrapper's own totals;
+/// [`MetricsCollector::observe_storage_retries`] exports them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetryCounters {
+    /// Retries performed, not counting first attempts
+    pub retries: u64,
+    /// Calls abandoned because the deadline left no room for another attempt
+    pub deadline_exhaustions: u64,
+}
+
 /// Comprehensive metrics collector for the schema registry
 pub struct MetricsCollector {
     pub registry: Registry,
@@ -75,6 +107,10 @@ pub struct MetricsCollector {
     pub s3_errors_total: IntCounterVec,
     pub s3_bytes_transferred_total: IntCounterVec,
 
+    // Storage metrics - Retries
+    pub storage_retries_total: IntCounterVec,
+    pub storage_deadline_exhaustions_total: IntCounterVec,
+
     // System metrics
     pub process_cpu_seconds_total: CounterVec,
     pub process_memory_bytes: GaugeVec,
@@ -91,298 +127,311 @@ impl MetricsCollector {
 
         // Request metrics
         let http_requests_total = register_int_counter_vec!(
-            "schema_registry_http_requests_total",
-            "Total HTTP requests by method, path, and status",
+            metric_names::HTTP_REQUESTS_TOTAL.name,
+            metric_names::HTTP_REQUESTS_TOTAL.description,
             &["method", "path", "status"]
         )?;
 
         let http_request_duration_seconds = register_histogram_vec!(
-            "schema_registry_http_request_duration_seconds",
-            "HTTP request duration in seconds",
+            metric_names::HTTP_REQUEST_DURATION_SECONDS.name,
+            metric_names::HTTP_REQUEST_DURATION_SECONDS.description,
             &["method", "path"],
             vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
         )?;
 
         let http_requests_in_flight = register_int_gauge_vec!(
-            "schema_registry_http_requests_in_flight",
-            "Current number of HTTP requests being processed",
+            metric_names::HTTP_REQUESTS_IN_FLIGHT.name,
+            metric_names::HTTP_REQUESTS_IN_FLIGHT.description,
             &["method", "path"]
         )?;
 
         let http_request_size_bytes = register_histogram_vec!(
-            "schema_registry_http_request_size_bytes",
-            "HTTP request size in bytes",
+            metric_names::HTTP_REQUEST_SIZE_BYTES.name,
+            metric_names::HTTP_REQUEST_SIZE_BYTES.description,
             &["method", "path"],
             vec![100.0, 1000.0, 10000.0, 100000.0, 1000000.0, 10000000.0]
         )?;
 
         let http_response_size_bytes = register_histogram_vec!(
-            "schema_registry_http_response_size_bytes",
-            "HTTP response size in bytes",
+            metric_names::HTTP_RESPONSE_SIZE_BYTES.name,
+            metric_names::HTTP_RESPONSE_SIZE_BYTES.description,
             &["method", "path"],
             vec![100.0, 1000.0, 10000.0, 100000.0, 1000000.0, 10000000.0]
         )?;
 
         // gRPC metrics
         let grpc_requests_total = register_int_counter_vec!(
-            "schema_registry_grpc_requests_total",
-            "Total gRPC requests by service, method, and status",
+            metric_names::GRPC_REQUESTS_TOTAL.name,
+            metric_names::GRPC_REQUESTS_TOTAL.description,
             &["service", "method", "status"]
         )?;
 
         let grpc_request_duration_seconds = register_histogram_vec!(
-            "schema_registry_grpc_request_duration_seconds",
-            "gRPC request duration in seconds",
+            metric_names::GRPC_REQUEST_DURATION_SECONDS.name,
+            metric_names::GRPC_REQUEST_DURATION_SECONDS.description,
             &["service", "method"],
             vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
         )?;
 
         let grpc_requests_in_flight = register_int_gauge_vec!(
-            "schema_registry_grpc_requests_in_flight",
-            "Current number of gRPC requests being processed",
+            metric_names::GRPC_REQUESTS_IN_FLIGHT.name,
+            metric_names::GRPC_REQUESTS_IN_FLIGHT.description,
             &["service", "method"]
         )?;
 
         // Business metrics - Schemas
         let schemas_registered_total = register_int_counter_vec!(
-            "schema_registry_schemas_registered_total",
-            "Total schemas registered by format and state",
+            metric_names::SCHEMAS_REGISTERED_TOTAL.name,
+            metric_names::SCHEMAS_REGISTERED_TOTAL.description,
             &["format", "state"]
         )?;
 
         let schemas_active_total = register_int_gauge_vec!(
-            "schema_registry_schemas_active_total",
-            "Total active schemas by format",
+            metric_names::SCHEMAS_ACTIVE_TOTAL.name,
+            metric_names::SCHEMAS_ACTIVE_TOTAL.description,
             &["format"]
         )?;
 
         let schemas_deprecated_total = register_int_gauge_vec!(
-            "schema_registry_schemas_deprecated_total",
-            "Total deprecated schemas by format",
+            metric_names::SCHEMAS_DEPRECATED_TOTAL.name,
+            metric_names::SCHEMAS_DEPRECATED_TOTAL.description,
             &["format"]
         )?;
 
         let schemas_deleted_total = register_int_gauge_vec!(
-            "schema_registry_schemas_deleted_total",
-            "Total deleted schemas by format",
+            metric_names::SCHEMAS_DELETED_TOTAL.name,
+            metric_names::SCHEMAS_DELETED_TOTAL.description,
             &["format"]
         )?;
 
         let schema_versions_total = register_int_gauge_vec!(
-            "schema_registry_schema_versions_total",
-            "Total schema versions by subject",
+            metric_names::SCHEMA_VERSIONS_TOTAL.name,
+            metric_names::SCHEMA_VERSIONS_TOTAL.description,
             &["subject"]
         )?;
 
         let schema_size_bytes = register_histogram_vec!(
-            "schema_registry_schema_size_bytes",
-            "Schema size in bytes",
+            metric_names::SCHEMA_SIZE_BYTES.name,
+            metric_names::SCHEMA_SIZE_BYTES.description,
             &["format"],
             vec![100.0, 500.0, 1000.0, 5000.0, 10000.0, 50000.0, 100000.0]
         )?;
 
         // Business metrics - Validation
         let validations_total = register_int_counter_vec!(
-            "schema_registry_validations_total",
-            "Total validations by format and result",
+            metric_names::VALIDATIONS_TOTAL.name,
+            metric_names::VALIDATIONS_TOTAL.description,
             &["format", "result"]
         )?;
 
         let validation_duration_seconds = register_histogram_vec!(
-            "schema_registry_validation_duration_seconds",
-            "Validation duration in seconds",
+            metric_names::VALIDATION_DURATION_SECONDS.name,
+            metric_names::VALIDATION_DURATION_SECONDS.description,
             &["format"],
             vec![0.0001, 0.0005, 0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5]
         )?;
 
         let validation_errors_total = register_int_counter_vec!(
-            "schema_registry_validation_errors_total",
-            "Total validation errors by format and error type",
+            metric_names::VALIDATION_ERRORS_TOTAL.name,
+            metric_names::VALIDATION_ERRORS_TOTAL.description,
             &["format", "error_type"]
         )?;
 
         // Business metrics - Compatibility
         let compatibility_checks_total = register_int_counter_vec!(
-            "schema_registry_compatibility_checks_total",
-            "Total compatibility checks by mode and result",
+            metric_names::COMPATIBILITY_CHECKS_TOTAL.name,
+            metric_names::COMPATIBILITY_CHECKS_TOTAL.description,
             &["mode", "result"]
         )?;
 
         let compatibility_check_duration_seconds = register_histogram_vec!(
-            "schema_registry_compatibility_check_duration_seconds",
-            "Compatibility check duration in seconds",
+            metric_names::COMPATIBILITY_CHECK_DURATION_SECONDS.name,
+            metric_names::COMPATIBILITY_CHECK_DURATION_SECONDS.description,
             &["mode"],
             vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]
         )?;
 
         let compatibility_violations_total = register_int_counter_vec!(
-            "schema_registry_compatibility_violations_total",
-            "Total compatibility violations by mode and type",
+            metric_names::COMPATIBILITY_VIOLATIONS_TOTAL.name,
+            metric_names::COMPATIBILITY_VIOLATIONS_TOTAL.description,
             &["mode", "violation_type"]
         )?;
 
         // Storage metrics - Cache
         let cache_operations_total = register_int_counter_vec!(
-            "schema_registry_cache_operations_total",
-            "Total cache operations by operation, tier, and result",
+            metric_names::CACHE_OPERATIONS_TOTAL.name,
+            metric_names::CACHE_OPERATIONS_TOTAL.description,
             &["operation", "tier", "result"]
         )?;
 
         let cache_hit_rate = register_gauge_vec!(
-            "schema_registry_cache_hit_rate",
-            "Cache hit rate by tier (0.0 to 1.0)",
+            metric_names::CACHE_HIT_RATE.name,
+            metric_names::CACHE_HIT_RATE.description,
             &["tier"]
         )?;
 
         let cache_size_bytes = register_gauge_vec!(
-            "schema_registry_cache_size_bytes",
-            "Cache size in bytes by tier",
+            metric_names::CACHE_SIZE_BYTES.name,
+            metric_names::CACHE_SIZE_BYTES.description,
             &["tier"]
         )?;
 
         let cache_items_total = register_int_gauge_vec!(
-            "schema_registry_cache_items_total",
-            "Total items in cache by tier",
+            metric_names::CACHE_ITEMS_TOTAL.name,
+            metric_names::CACHE_ITEMS_TOTAL.description,
             &["tier"]
         )?;
 
         let cache_evictions_total = register_int_counter_vec!(
-            "schema_registry_cache_evictions_total",
-            "Total cache evictions by tier and reason",
+            metric_names::CACHE_EVICTIONS_TOTAL.name,
+            metric_names::CACHE_EVICTIONS_TOTAL.description,
             &["tier", "reason"]
         )?;
 
         // Storage metrics - Database
         let db_connections_active = register_int_gauge_vec!(
-            "schema_registry_db_connections_active",
-            "Active database connections by pool",
+            metric_names::DB_CONNECTIONS_ACTIVE.name,
+            metric_names::DB_CONNECTIONS_ACTIVE.description,
             &["pool"]
         )?;
 
         let db_connections_idle = register_int_gauge_vec!(
-            "schema_registry_db_connections_idle",
-            "Idle database connections by pool",
+            metric_names::DB_CONNECTIONS_IDLE.name,
+            metric_names::DB_CONNECTIONS_IDLE.description,
             &["pool"]
         )?;
 
         let db_connections_max = register_int_gauge_vec!(
-            "schema_registry_db_connections_max",
-            "Maximum database connections by pool",
+            metric_names::DB_CONNECTIONS_MAX.name,
+            metric_names::DB_CONNECTIONS_MAX.description,
             &["pool"]
         )?;
 
         let db_query_duration_seconds = register_histogram_vec!(
-            "schema_registry_db_query_duration_seconds",
-            "Database query duration in seconds",
+            metric_names::DB_QUERY_DURATION_SECONDS.name,
+            metric_names::DB_QUERY_DURATION_SECONDS.description,
             &["query", "operation"],
             vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0]
         )?;
 
         let db_queries_total = register_int_counter_vec!(
-            "schema_registry_db_queries_total",
-            "Total database queries by query and result",
+            metric_names::DB_QUERIES_TOTAL.name,
+            metric_names::DB_QUERIES_TOTAL.description,
             &["query", "operation", "result"]
         )?;
 
         let db_errors_total = register_int_counter_vec!(
-            "schema_registry_db_errors_total",
-            "Total database errors by type",
+            metric_names::DB_ERRORS_TOTAL.name,
+            metric_names::DB_ERRORS_TOTAL.description,
             &["error_type"]
         )?;
 
         let db_pool_wait_duration_seconds = register_histogram_vec!(
-            "schema_registry_db_pool_wait_duration_seconds",
-            "Time waiting for database connection from pool",
+            metric_names::DB_POOL_WAIT_DURATION_SECONDS.name,
+            metric_names::DB_POOL_WAIT_DURATION_SECONDS.description,
             &["pool"],
             vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]
         )?;
 
         // Storage metrics - Redis
         let redis_operations_total = register_int_counter_vec!(
-            "schema_registry_redis_operations_total",
-            "Total Redis operations by command and result",
+            metric_names::REDIS_OPERATIONS_TOTAL.name,
+            metric_names::REDIS_OPERATIONS_TOTAL.description,
             &["command", "result"]
         )?;
 
         let redis_operation_duration_seconds = register_histogram_vec!(
-            "schema_registry_redis_operation_duration_seconds",
-            "Redis operation duration in seconds",
+            metric_names::REDIS_OPERATION_DURATION_SECONDS.name,
+            metric_names::REDIS_OPERATION_DURATION_SECONDS.description,
             &["command"],
             vec![0.0001, 0.0005, 0.001, 0.005, 0.01, 0.025, 0.05, 0.1]
         )?;
 
         let redis_errors_total = register_int_counter_vec!(
-            "schema_registry_redis_errors_total",
-            "Total Redis errors by type",
+            metric_names::REDIS_ERRORS_TOTAL.name,
+            metric_names::REDIS_ERRORS_TOTAL.description,
             &["error_type"]
         )?;
 
         let redis_connections_active = register_int_gauge_vec!(
-            "schema_registry_redis_connections_active",
-            "Active Redis connections",
+            metric_names::REDIS_CONNECTIONS_ACTIVE.name,
+            metric_names::REDIS_CONNECTIONS_ACTIVE.description,
             &["pool"]
         )?;
 
         // Storage metrics - S3
         let s3_operations_total = register_int_counter_vec!(
-            "schema_registry_s3_operations_total",
-            "Total S3 operations by operation and result",
+            metric_names::S3_OPERATIONS_TOTAL.name,
+            metric_names::S3_OPERATIONS_TOTAL.description,
             &["operation", "result"]
         )?;
 
         let s3_operation_duration_seconds = register_histogram_vec!(
-            "schema_registry_s3_operation_duration_seconds",
-            "S3 operation duration in seconds",
+            metric_names::S3_OPERATION_DURATION_SECONDS.name,
+            metric_names::S3_OPERATION_DURATION_SECONDS.description,
             &["operation"],
             vec![0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
         )?;
 
         let s3_errors_total = register_int_counter_vec!(
-            "schema_registry_s3_errors_total",
-            "Total S3 errors by type",
+            metric_names::S3_ERRORS_TOTAL.name,
+            metric_names::S3_ERRORS_TOTAL.description,
             &["error_type"]
         )?;
 
         let s3_bytes_transferred_total = register_int_counter_vec!(
-            "schema_registry_s3_bytes_transferred_total",
-            "Total bytes transferred to/from S3",
+            metric_names::S3_BYTES_TRANSFERRED_TOTAL.name,
+            metric_names::S3_BYTES_TRANSFERRED_TOTAL.description,
             &["direction"]
         )?;
 
+        // Storage metrics - Retries
+        let storage_retries_total = register_int_counter_vec!(
+            metric_names::STORAGE_RETRIES_TOTAL.name,
+            metric_names::STORAGE_RETRIES_TOTAL.description,
+            &["storage"]
+        )?;
+
+        let storage_deadline_exhaustions_total = register_int_counter_vec!(
+            metric_names::STORAGE_DEADLINE_EXHAUSTIONS_TOTAL.name,
+            metric_names::STORAGE_DEADLINE_EXHAUSTIONS_TOTAL.description,
+            &["storage"]
+        )?;
+
         // System metrics
         let process_cpu_seconds_total = register_counter_vec!(
-            "schema_registry_process_cpu_seconds_total",
-            "Total CPU time consumed by the process",
+            metric_names::PROCESS_CPU_SECONDS_TOTAL.name,
+            metric_names::PROCESS_CPU_SECONDS_TOTAL.description,
             &["mode"]
         )?;
 
         let process_memory_bytes = register_gauge_vec!(
-            "schema_registry_process_memory_bytes",
-            "Process memory usage in bytes",
+            metric_names::PROCESS_MEMORY_BYTES.name,
+            metric_names::PROCESS_MEMORY_BYTES.description,
             &["type"]
         )?;
 
         let process_open_fds = register_int_gauge_vec!(
-            "schema_registry_process_open_fds",
-            "Number of open file descriptors",
+            metric_names::PROCESS_OPEN_FDS.name,
+            metric_names::PROCESS_OPEN_FDS.description,
             &[]
         )?;
 
         let process_threads_total = register_int_gauge_vec!(
-            "schema_registry_process_threads_total",
-            "Total number of threads",
+            metric_names::PROCESS_THREADS_TOTAL.name,
+            metric_names::PROCESS_THREADS_TOTAL.description,
             &[]
         )?;
 
         let tokio_tasks_total = register_int_gauge_vec!(
-            "schema_registry_tokio_tasks_total",
-            "Total number of Tokio tasks",
+            metric_names::TOKIO_TASKS_TOTAL.name,
+            metric_names::TOKIO_TASKS_TOTAL.description,
             &["state"]
         )?;
 
         let tokio_tasks_active = register_int_gauge_vec!(
-            "schema_registry_tokio_tasks_active",
-            "Number of active Tokio tasks",
+            metric_names::TOKIO_TASKS_ACTIVE.name,
+            metric_names::TOKIO_TASKS_ACTIVE.description,
             &[]
         )?;
 
@@ -436,6 +485,9 @@ impl MetricsCollector {
         registry.register(Box::new(s3_errors_total.clone()))?;
         registry.register(Box::new(s3_bytes_transferred_total.clone()))?;
 
+        registry.register(Box::new(storage_retries_total.clone()))?;
+        registry.register(Box::new(storage_deadline_exhaustions_total.clone()))?;
+
         registry.register(Box::new(process_cpu_seconds_total.clone()))?;
         registry.register(Box::new(process_memory_bytes.clone()))?;
         registry.register(Box::new(process_open_fds.clone()))?;
@@ -485,6 +537,8 @@ impl MetricsCollector {
             s3_operation_duration_seconds,
             s3_errors_total,
             s3_bytes_transferred_total,
+            storage_retries_total,
+            storage_deadline_exhaustions_total,
             process_cpu_seconds_total,
             process_memory_bytes,
             process_open_fds,
@@ -505,6 +559,59 @@ impl MetricsCollector {
     pub fn metric_count(&self) -> usize {
         self.registry.gather().len()
     }
+
+    /// Publish a cache tier's counters
+    ///
+    /// Lookups are exported as `cache_operations_total{operation="get"}` with
+    /// result `hit`, `miss` or `stale`, drops as `cache_evictions_total` with
+    /// reason `expired` or `invalidated`. Counters only move forward, so
+    /// totals lower than what was already exported (after a cache restart)
+    /// leave them unchanged.
+    pub fn observe_cache(&self, tier: &str, counters: CacheCounters) {
+        for (result, total) in [
+            ("hit", counters.hits),
+            ("miss", counters.misses),
+            ("stale", counters.stale_serves),
+        ] {
+            advance(self.cache_operations_total.with_label_values(&["get", tier, result]), total);
+        }
+        advance(self.cache_evictions_total.with_label_values(&[tier, "expired"]), counters.evictions);
+        advance(
+            self.cache_evictions_total.with_label_values(&[tier, "invalidated"]),
+            counters.invalidations,
+        );
+
+        let lookups = counters.hits + counters.misses;
+        if lookups > 0 {
+            self.cache_hit_rate
+                .with_label_values(&[tier])
+                .set(counters.hits as f64 / lookups as f64);
+        }
+        self.cache_items_total
+            .with_label_values(&[tier])
+            .set(counters.entries as i64);
+    }
+
+    /// Publish a retrying storage wrapper's counters under `storage`
+    ///
+    /// Exported as `storage_retries_total` and
+    /// `storage_deadline_exhaustions_total`; as with [`observe_cache`](Self::observe_cache),
+    /// lower totals leave the series unchanged.
+    pub fn observe_storage_retries(&self, storage: &str, counters: RetryCounters) {
+        advance(self.storage_retries_total.with_label_values(&[storage]), counters.retries);
+        advance(
+            self.storage_deadline_exhaustions_total.with_label_values(&[storage]),
+            counters.deadline_exhaustions,
+        );
+    }
+}
+
+/// Move `counter` up to `total`, never back
+fn advance(counter: prometheus::IntCounter, total: u64) {
+    let current = counter.get();
+    if total > current {
+        counter.inc_by(total - current);
+    }
 }
 
 // Note: Default trait removed as MetricsCollector returns Arc<Self>
@@ -537,4 +644,108 @@ mod tests {
         let export = collector.export().unwrap();
         assert!(export.contains("schema_registry_http_requests_total"));
     }
+
+    #[test]
+    fn test_observe_cache_advances_counters() {
+        let collector = MetricsCollector::new().unwrap();
+        let mut counters = CacheCounters {
+            hits: 3,
+            misses: 1,
+            entries: 2,
+            ..CacheCounters::default()
+        };
+        collector.observe_cache("read", counters);
+        counters.hits = 5;
+        collector.observe_cache("read", counters);
+
+        let hits = collector.cache_operations_total.with_label_values(&["get", "read", "hit"]);
+        assert_eq!(hits.get(), 5);
+        assert_eq!(collector.cache_hit_rate.with_label_values(&["read"]).get(), 5.0 / 6.0);
+        assert_eq!(collector.cache_items_total.with_label_values(&["read"]).get(), 2);
+    }
+
+    #[test]
+    fn test_observe_storage_retries_advances_counters() {
+        let collector = MetricsCollector::new().unwrap();
+        collector.observe_storage_retries("postgres", RetryCounters { retries: 4, deadline_exhaustions: 1 });
+        collector.observe_storage_retries("postgres", RetryCounters { retries: 2, deadline_exhaustions: 3 });
+
+        assert_eq!(collector.storage_retries_total.with_label_values(&["postgres"]).get(), 4);
+        assert_eq!(collector.storage_deadline_exhaustions_total.with_label_values(&["postgres"]).get(), 3);
+    }
+
+    #[test]
+    fn test_every_metric_name_is_catalogued() {
+        use prometheus::core::Collector;
+
+        let collector = MetricsCollector::new().unwrap();
+        macro_rules! descs {
+            ($($field:ident),* $(,)?) => {
+                vec![$(collector.$field.desc()),*].concat()
+            };
+        }
+        let descs = descs!(
+            http_requests_total,
+            http_request_duration_seconds,
+            http_requests_in_flight,
+            http_request_size_bytes,
+            http_response_size_bytes,
+            grpc_requests_total,
+            grpc_request_duration_seconds,
+            grpc_requests_in_flight,
+            schemas_registered_total,
+            schemas_active_total,
+            schemas_deprecated_total,
+            schemas_deleted_total,
+            schema_versions_total,
+            schema_size_bytes,
+            validations_total,
+            validation_duration_seconds,
+            validation_errors_total,
+            compatibility_checks_total,
+            compatibility_check_duration_seconds,
+            compatibility_violations_total,
+            cache_operations_total,
+            cache_hit_rate,
+            cache_size_bytes,
+            cache_items_total,
+            cache_evictions_total,
+            db_connections_active,
+            db_connections_idle,
+            db_connections_max,
+            db_query_duration_seconds,
+            db_queries_total,
+            db_errors_total,
+            db_pool_wait_duration_seconds,
+            redis_operations_total,
+            redis_operation_duration_seconds,
+            redis_errors_total,
+            redis_connections_active,
+            s3_operations_total,
+            s3_operation_duration_seconds,
+            s3_errors_total,
+            s3_bytes_transferred_total,
+            storage_retries_total,
+            storage_deadline_exhaustions_total,
+            process_cpu_seconds_total,
+            process_memory_bytes,
+            process_open_fds,
+            process_threads_total,
+            tokio_tasks_total,
+            tokio_tasks_active,
+        );
+
+        let registry_metrics = metric_names::catalog()
+            .iter()
+            .filter(|m| m.kind != metric_names::MetricKind::Statistic)
+            .count();
+        assert_eq!(descs.len(), registry_metrics);
+        for desc in descs {
+            assert!(
+                metric_names::is_catalogued(&desc.fq_name),
+                "{} is not in metric_names::catalog()",
+                desc.fq_name
+            );
+        }
+    }
 }
@@ -1,16 +1,19 @@
 use axum::{
-    extract::{Path, State},
+    async_trait,
+    extract::{FromRequest, Path, Request, State},
     http::StatusCode,
     response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
 use chrono::Utc;
+use futures::StreamExt;
 use prometheus::{Encoder, TextEncoder};
 use redis::aio::ConnectionManager;
 use schema_registry_compatibility::CompatibilityCheckerImpl;
 use schema_registry_core::{
     error::Result as CoreResult,
+    ingest::{CappedCollector, DEFAULT_MAX_SCHEMA_SIZE},
     schema::{RegisteredSchema, SchemaMetadata},
     state::{SchemaLifecycle, SchemaState},
     traits::{CompatibilityChecker, SchemaValidator},
@@ -18,7 +21,7 @@ use schema_registry_core::{
     versioning::SemanticVersion,
 };
 use schema_registry_validation::ValidationEngine;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use sqlx::postgres::PgPoolOptions;
 use sqlx::PgPool;
 use std::collections::HashMap;
@@ -39,6 +42,8 @@ struct AppState {
     redis: ConnectionManager,
     validator: Arc<ValidationEngine>,
     compatibility_checker: Arc<CompatibilityCheckerImpl>,
+    /// Largest request body accepted by schema endpoints, in bytes
+    max_schema_size: usize,
 }
 
 // ============================================================================
@@ -153,11 +158,28 @@ enum AppError {
     Redis(redis::RedisError),
     NotFound(String),
     InvalidInput(String),
+    PayloadTooLarge(String),
     Internal(String),
 }
 
+schema_registry_core::error_codes!(AppError {
+    Database => "DATABASE_ERROR",
+    Redis => "CACHE_ERROR",
+    NotFound => "NOT_FOUND",
+    InvalidInput => "BAD_REQUEST",
+    PayloadTooLarge => "PAYLOAD_TOO_LARGE",
+    Internal => "INTERNAL_ERROR",
+});
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let code = self.code();
+        debug_assert!(
+            llm_schema_api::error_codes::is_api_error_code(code),
+            "HTTP error code {} is not in API_ERRORS",
+            code
+        );
+
         let (status, message) = match self {
             AppError::Database(e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -169,11 +191,13 @@ impl IntoResponse for AppError {
             ),
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
             AppError::InvalidInput(msg) => (StatusCode::BAD_REQUEST, msg),
+            AppError::PayloadTooLarge(msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg),
             AppError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
         };
 
         let body = Json(serde_json::json!({
             "error": message,
+            "code": code,
         }));
 
         (status, body).into_response()
@@ -192,6 +216,36 @@ impl From<redis::RedisError> for AppError {
     }
 }
 
+// ============================================================================
+// Size-capped request bodies
+// ============================================================================
+
+/// JSON body extractor that stops reading once the body passes `max_schema_size`
+///
+/// Unlike `Json`, the body is consumed chunk by chunk, so an oversized upload
+/// is rejected without being buffered.
+struct CappedJson<T>(T);
+
+#[async_trait]
+impl<T: DeserializeOwned> FromRequest<AppState> for CappedJson<T> {
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &AppState) -> Result<Self, Self::Rejection> {
+        let mut collector = CappedCollector::new(state.max_schema_size);
+        let mut body = req.into_body().into_data_stream();
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk.map_err(|e| AppError::InvalidInput(format!("Failed to read request body: {}", e)))?;
+            collector
+                .push(&chunk)
+                .map_err(|e| AppError::PayloadTooLarge(e.to_string()))?;
+        }
+
+        serde_json::from_slice(&collector.finish().bytes)
+            .map(CappedJson)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid JSON body: {}", e)))
+    }
+}
+
 // ============================================================================
 // Handlers
 // ============================================================================
@@ -258,7 +312,7 @@ async fn metrics_handler() -> impl IntoResponse {
 
 async fn register_schema(
     State(state): State<AppState>,
-    Json(req): Json<RegisterSchemaRequest>,
+    CappedJson(req): CappedJson<RegisterSchemaRequest>,
 ) -> Result<(StatusCode, Json<RegisterSchemaResponse>), AppError> {
     // Parse subject into namespace and name (format: namespace.name or just name)
     let (namespace, name) = if let Some(dot_pos) = req.subject.rfind('.') {
@@ -590,7 +644,7 @@ async fn validate_data(
 
 async fn check_compatibility(
     State(state): State<AppState>,
-    Json(req): Json<CompatibilityCheckRequest>,
+    CappedJson(req): CappedJson<CompatibilityCheckRequest>,
 ) -> Result<Json<CompatibilityCheckResponse>, AppError> {
     tracing::debug!(
         schema_id = %req.schema_id,
@@ -658,11 +712,15 @@ async fn main() -> anyhow::Result<()> {
     let metrics_port = std::env::var("METRICS_PORT")
         .unwrap_or_else(|_| "9091".to_string())
         .parse::<u16>()?;
+    let max_schema_size = std::env::var("MAX_SCHEMA_SIZE_BYTES")
+        .map(|v| v.parse::<usize>())
+        .unwrap_or(Ok(DEFAULT_MAX_SCHEMA_SIZE))?;
 
     tracing::info!("Database URL: {}", database_url);
     tracing::info!("Redis URL: {}", redis_url);
     tracing::info!("Server will listen on {}:{}", server_host, server_port);
     tracing::info!("Metrics will be available on port {}", metrics_port);
+    tracing::info!("Schema request bodies limited to {} bytes", max_schema_size);
 
     // Create PostgreSQL connection pool
     tracing::info!("Connecting to PostgreSQL...");
@@ -697,6 +755,7 @@ async fn main() -> anyhow::Result<()> {
         redis,
         validator,
         compatibility_checker,
+        max_schema_size,
     };
 
     // Build API router
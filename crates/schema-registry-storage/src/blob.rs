@@ -0,0 +1,373 @@
+//! Schema blob stores and codec migration
+//!
+//! A [`BlobStore`] holds encoded schema blobs by key. [`FsBlobStore`] keeps one
+//! file per blob under a root directory, either flat or sharded by subject
+//! (see [`crate::fs_layout`]). [`recompress`] walks any store and
+//! rewrites blobs whose codec differs from what a [`CompressionPolicy`] would
+//! pick today, one blob at a time, so it can run against a live registry.
+
+use async_trait::async_trait;
+use schema_registry_core::error::Result;
+use schema_registry_core::pagination::{paginate, Page, PageRequest};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::compression::{blob_codec, decode_blob, encode_blob, CompressionPolicy};
+use crate::fs_layout::{read_layout, FsLayout, LAYOUT_FILE, SHARDED_DIR};
+
+/// Suffix of in-progress writes in [`FsBlobStore`]
+const TMP_SUFFIX: &str = ".tmp";
+
+/// Key-addressed storage for encoded schema blobs
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Every key in the store, in a stable order
+    async fn keys(&self) -> Result<Vec<String>>;
+
+    /// Raw (encoded) bytes stored under `key`
+    async fn read(&self, key: &str) -> Result<Vec<u8>>;
+
+    /// Replace the bytes stored under `key`
+    async fn write(&self, key: &str, blob: &[u8]) -> Result<()>;
+}
+
+/// Blob store with one file per blob below a root directory
+///
+/// Keys are `/`-separated paths relative to the root; keys starting with `.`
+/// are reserved for the store's own files. Writes go to a sibling temporary
+/// file that is renamed into place, so readers never see a partially written
+/// blob.
+///
+/// A sharded store also reads blobs from their flat location, so it can serve
+/// a store whose [`migrate_layout`](crate::fs_layout::migrate_layout) is still
+/// in progress; writing a blob moves it to its sharded location.
+#[derive(Debug, Clone)]
+pub struct FsBlobStore {
+    root: PathBuf,
+    layout: FsLayout,
+}
+
+impl FsBlobStore {
+    /// Flat-layout store at `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            layout: FsLayout::Flat,
+        }
+    }
+
+    /// Store at `root` using the layout recorded there
+    pub async fn open(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        let layout = read_layout(&root).await?;
+        Ok(Self { root, layout })
+    }
+
+    pub fn with_layout(mut self, layout: FsLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn layout(&self) -> FsLayout {
+        self.layout
+    }
+
+    /// One page of keys, in the order of [`keys`](BlobStore::keys)
+    pub async fn list(&self, page: &PageRequest) -> Result<Page<String>> {
+        paginate(self.keys().await?, page, |key| key.as_str())
+    }
+
+    fn flat_path(&self, key: &str) -> PathBuf {
+        key.split('/').fold(self.root.clone(), |path, part| path.join(part))
+    }
+
+    /// Where `key` is written in this store's layout
+    fn path(&self, key: &str) -> PathBuf {
+        match self.layout.shard_dirs(key) {
+            Some([first, second]) => key
+                .split('/')
+                .fold(self.root.join(SHARDED_DIR).join(first).join(second), |path, part| path.join(part)),
+            None => self.flat_path(key),
+        }
+    }
+
+    /// Keys stored in the flat layout and in the sharded layout, unsorted
+    async fn stored_keys(&self) -> Result<(Vec<String>, Vec<String>)> {
+        let mut flat = Vec::new();
+        let mut sharded = Vec::new();
+        let mut pending = vec![self.root.clone()];
+
+        while let Some(dir) = pending.pop() {
+            let mut entries = match tokio::fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                // A fan-out directory emptied by a concurrent migration
+                Err(e) if e.kind() == ErrorKind::NotFound && dir != self.root => continue,
+                Err(e) => return Err(e.into()),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if entry.file_type().await?.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                let Ok(relative) = path.strip_prefix(&self.root) else {
+                    continue;
+                };
+                let parts: Vec<_> = relative
+                    .components()
+                    .map(|part| part.as_os_str().to_string_lossy())
+                    .collect();
+                if parts.last().is_some_and(|name| name.ends_with(TMP_SUFFIX)) || parts == [LAYOUT_FILE] {
+                    continue;
+                }
+                if parts[0] == SHARDED_DIR {
+                    // Skip the fan-out directories in front of the key
+                    if parts.len() > 3 {
+                        sharded.push(parts[3..].join("/"));
+                    }
+                } else {
+                    flat.push(parts.join("/"));
+                }
+            }
+        }
+
+        Ok((flat, sharded))
+    }
+
+    /// Keys still stored in the flat layout, sorted
+    ///
+    /// In a sharded store these are the blobs a layout migration has yet to move.
+    pub async fn flat_keys(&self) -> Result<Vec<String>> {
+        let (mut flat, _) = self.stored_keys().await?;
+        flat.sort();
+        Ok(flat)
+    }
+
+    /// Whether `key` is stored at its sharded location
+    pub(crate) async fn has_sharded(&self, key: &str) -> Result<bool> {
+        if self.layout == FsLayout::Flat {
+            return Ok(false);
+        }
+        Ok(tokio::fs::try_exists(self.path(key)).await?)
+    }
+
+    /// Remove the flat copy of `key`, if any
+    pub(crate) async fn remove_flat(&self, key: &str) -> Result<()> {
+        match tokio::fs::remove_file(self.flat_path(key)).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Remove the directories left empty by moving flat blobs away
+    pub(crate) async fn remove_empty_flat_dirs(&self) -> Result<()> {
+        let mut dirs = Vec::new();
+        let mut pending = vec![self.root.clone()];
+        while let Some(dir) = pending.pop() {
+            let mut entries = tokio::fs::read_dir(&dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                if entry.file_type().await?.is_dir() && entry.file_name() != SHARDED_DIR {
+                    pending.push(entry.path());
+                    dirs.push(entry.path());
+                }
+            }
+        }
+        // Deepest first, so parents are empty by the time they are tried
+        dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+        for dir in dirs {
+            // Fails for directories that still hold blobs, which stay
+            let _ = tokio::fs::remove_dir(&dir).await;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BlobStore for FsBlobStore {
+    async fn keys(&self) -> Result<Vec<String>> {
+        let (flat, sharded) = self.stored_keys().await?;
+        let mut keys: Vec<String> = flat.into_iter().chain(sharded).collect();
+        // Mid-migration a blob can briefly exist in both layouts
+        keys.sort();
+        keys.dedup();
+        Ok(keys)
+    }
+
+    async fn read(&self, key: &str) -> Result<Vec<u8>> {
+        match tokio::fs::read(self.path(key)).await {
+            Err(e) if e.kind() == ErrorKind::NotFound && self.layout != FsLayout::Flat => {
+                Ok(tokio::fs::read(self.flat_path(key)).await?)
+            }
+            read => Ok(read?),
+        }
+    }
+
+    async fn write(&self, key: &str, blob: &[u8]) -> Result<()> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(TMP_SUFFIX);
+        tokio::fs::write(&tmp, blob).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+        if self.layout != FsLayout::Flat {
+            self.remove_flat(key).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Running totals of a [`recompress`] pass
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecompressReport {
+    /// Blobs in the store when the pass started
+    pub total: usize,
+    /// Blobs examined so far
+    pub scanned: usize,
+    /// Blobs rewritten with a different encoding
+    pub rewritten: usize,
+    /// Encoded size of the scanned blobs before the pass
+    pub bytes_before: u64,
+    /// Encoded size of the scanned blobs after the pass
+    pub bytes_after: u64,
+}
+
+impl RecompressReport {
+    /// Blobs that already matched the policy
+    pub fn unchanged(&self) -> usize {
+        self.scanned - self.rewritten
+    }
+}
+
+/// Re-encode every blob in `store` according to `policy`
+///
+/// Blobs are processed one at a time: read, decoded with the codec named in
+/// their header, re-encoded and written back only if the encoding changed.
+/// `on_progress` is called after each blob with the running totals.
+pub async fn recompress<S, F>(store: &S, policy: &CompressionPolicy, mut on_progress: F) -> Result<RecompressReport>
+where
+    S: BlobStore + ?Sized,
+    F: FnMut(&RecompressReport),
+{
+    let keys = store.keys().await?;
+    let mut report = RecompressReport {
+        total: keys.len(),
+        ..Default::default()
+    };
+
+    for key in keys {
+        let blob = store.read(&key).await?;
+        let content = decode_blob(&blob)?;
+        let target = policy.codec_for(content.len());
+
+        // Re-encoding can still land on the current codec (e.g. gzip falls
+        // back to none for incompressible content); only write real changes
+        let mut after = blob.len();
+        if blob_codec(&blob)? != target {
+            let encoded = encode_blob(&content, policy)?;
+            if encoded != blob {
+                store.write(&key, &encoded).await?;
+                report.rewritten += 1;
+                after = encoded.len();
+            }
+        }
+
+        report.scanned += 1;
+        report.bytes_before += blob.len() as u64;
+        report.bytes_after += after as u64;
+        on_progress(&report);
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::{encode_blob_with, Codec};
+
+    fn large_schema(fields: usize) -> Vec<u8> {
+        let properties: Vec<String> = (0..fields)
+            .map(|n| format!("\"field_{n}\": {{\"type\": \"integer\"}}"))
+            .collect();
+        format!("{{\"type\": \"object\", \"properties\": {{{}}}}}", properties.join(", ")).into_bytes()
+    }
+
+    #[tokio::test]
+    async fn test_fs_store_round_trip_and_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsBlobStore::new(dir.path());
+
+        store.write("payments/invoice/1.0.0", b"one").await.unwrap();
+        store.write("payments/invoice/1.1.0", b"two").await.unwrap();
+        store.write("orders/1.0.0", b"three").await.unwrap();
+
+        assert_eq!(
+            store.keys().await.unwrap(),
+            vec!["orders/1.0.0", "payments/invoice/1.0.0", "payments/invoice/1.1.0"]
+        );
+        assert_eq!(store.read("payments/invoice/1.1.0").await.unwrap(), b"two");
+    }
+
+    #[tokio::test]
+    async fn test_sharded_store_lists_and_pages_across_shards() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsBlobStore::new(dir.path()).with_layout(FsLayout::sharded(1).unwrap());
+        for n in 0..30 {
+            store.write(&format!("subject_{n:02}/1.0.0"), b"blob").await.unwrap();
+        }
+
+        // Subjects are spread over the fan-out, not stored at the root
+        let top: Vec<_> = std::fs::read_dir(dir.path()).unwrap().map(|e| e.unwrap().file_name()).collect();
+        assert_eq!(top, vec![SHARDED_DIR]);
+        assert!(std::fs::read_dir(dir.path().join(SHARDED_DIR)).unwrap().count() > 1);
+
+        let keys = store.keys().await.unwrap();
+        assert_eq!(keys.len(), 30);
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let first = store.list(&PageRequest::new(20)).await.unwrap();
+        let cursor = first.next_cursor.clone().unwrap();
+        let second = store.list(&PageRequest::new(20).with_cursor(cursor)).await.unwrap();
+        assert_eq!(first.items.len() + second.items.len(), 30);
+        assert!(second.is_last());
+        assert_eq!(first.items[..], keys[..20]);
+    }
+
+    #[tokio::test]
+    async fn test_recompress_migrates_mixed_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsBlobStore::new(dir.path());
+        let large = large_schema(300);
+        let small = br#"{"type": "string"}"#.to_vec();
+
+        // Legacy headerless, already gzip, and a small blob gzip would not touch
+        store.write("legacy", &large).await.unwrap();
+        store.write("gzipped", &encode_blob_with(&large, Codec::Gzip).unwrap()).await.unwrap();
+        store.write("small", &encode_blob_with(&small, Codec::None).unwrap()).await.unwrap();
+
+        let policy = CompressionPolicy::new(Codec::Gzip, 4096);
+        let mut progress = Vec::new();
+        let report = recompress(&store, &policy, |r| progress.push(r.scanned)).await.unwrap();
+
+        assert_eq!(progress, vec![1, 2, 3]);
+        assert_eq!(report.total, 3);
+        assert_eq!(report.rewritten, 1);
+        assert_eq!(report.unchanged(), 2);
+        assert!(report.bytes_after < report.bytes_before);
+
+        let legacy = store.read("legacy").await.unwrap();
+        assert_eq!(blob_codec(&legacy).unwrap(), Codec::Gzip);
+        assert_eq!(decode_blob(&legacy).unwrap(), large);
+        assert_eq!(decode_blob(&store.read("small").await.unwrap()).unwrap(), small);
+
+        // A second pass has nothing left to do
+        let again = recompress(&store, &policy, |_| {}).await.unwrap();
+        assert_eq!(again.rewritten, 0);
+    }
+}
@@ -0,0 +1,334 @@
+//! Compression codecs for stored schema blobs
+//!
+//! Blobs written by [`encode_blob`] start with a five byte header (a magic
+//! prefix and the [`Codec`] id), so reads are self-describing and a registry
+//! can hold blobs in several codecs at once, e.g. while
+//! [`recompress`](crate::blob::recompress) migrates it. Blobs without the
+//! header decode as uncompressed, so content stored before codecs existed
+//! stays readable.
+//!
+//! Which codec a blob gets is decided per blob by [`CompressionPolicy`]:
+//! anything smaller than `min_size` is stored as-is, since small Avro and
+//! Protobuf schemas gain little and pay the header and CPU cost.
+
+use schema_registry_core::config_manager_adapter::StorageConfig;
+use schema_registry_core::error::Error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{Read, Write};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Prefix of every encoded blob; schema text never starts with a NUL byte
+pub const BLOB_MAGIC: [u8; 4] = *b"\0SRB";
+
+/// Length of the magic prefix plus the codec id
+pub const BLOB_HEADER_LEN: usize = BLOB_MAGIC.len() + 1;
+
+/// Default size below which blobs are stored uncompressed
+pub const DEFAULT_MIN_COMPRESS_SIZE: usize = 1024;
+
+/// Compression codec for a stored blob
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Codec {
+    /// Stored as-is
+    #[default]
+    None,
+    /// DEFLATE with a gzip wrapper
+    Gzip,
+    /// Zstandard; needs the `zstd` feature to encode or decode
+    Zstd,
+}
+
+impl Codec {
+    /// Every codec, whether or not it is compiled in
+    pub const ALL: [Codec; 3] = [Codec::None, Codec::Gzip, Codec::Zstd];
+
+    /// Id written to the blob header; stable across releases
+    pub fn id(self) -> u8 {
+        match self {
+            Codec::None => 0,
+            Codec::Gzip => 1,
+            Codec::Zstd => 2,
+        }
+    }
+
+    /// Codec for a header id
+    pub fn from_id(id: u8) -> Result<Self, CompressionError> {
+        Self::ALL
+            .into_iter()
+            .find(|codec| codec.id() == id)
+            .ok_or(CompressionError::UnknownCodecId(id))
+    }
+
+    /// Name used in configuration and on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            Codec::None => "none",
+            Codec::Gzip => "gzip",
+            Codec::Zstd => "zstd",
+        }
+    }
+
+    /// Whether this build can encode and decode the codec
+    pub fn is_available(self) -> bool {
+        match self {
+            Codec::None | Codec::Gzip => true,
+            Codec::Zstd => cfg!(feature = "zstd"),
+        }
+    }
+
+    /// Codecs this build can encode and decode
+    pub fn available() -> Vec<Codec> {
+        Self::ALL.into_iter().filter(|codec| codec.is_available()).collect()
+    }
+
+    fn compress(self, content: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        let io_err = |source| CompressionError::Codec { codec: self, source };
+        match self {
+            Codec::None => Ok(content.to_vec()),
+            Codec::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(content).map_err(io_err)?;
+                encoder.finish().map_err(io_err)
+            }
+            #[cfg(feature = "zstd")]
+            Codec::Zstd => zstd::encode_all(content, 0).map_err(io_err),
+            #[cfg(not(feature = "zstd"))]
+            Codec::Zstd => Err(CompressionError::Unavailable(self)),
+        }
+    }
+
+    fn decompress(self, payload: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        let io_err = |source| CompressionError::Codec { codec: self, source };
+        match self {
+            Codec::None => Ok(payload.to_vec()),
+            Codec::Gzip => {
+                let mut content = Vec::new();
+                flate2::read::GzDecoder::new(payload)
+                    .read_to_end(&mut content)
+                    .map_err(io_err)?;
+                Ok(content)
+            }
+            #[cfg(feature = "zstd")]
+            Codec::Zstd => zstd::decode_all(payload).map_err(io_err),
+            #[cfg(not(feature = "zstd"))]
+            Codec::Zstd => Err(CompressionError::Unavailable(self)),
+        }
+    }
+}
+
+impl fmt::Display for Codec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Codec {
+    type Err = CompressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|codec| codec.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| CompressionError::UnknownCodec(s.to_string()))
+    }
+}
+
+/// Errors raised while encoding or decoding blobs
+#[derive(Debug, Error)]
+pub enum CompressionError {
+    #[error("unknown codec '{0}' (expected none, gzip or zstd)")]
+    UnknownCodec(String),
+
+    #[error("unknown codec id {0} in blob header")]
+    UnknownCodecId(u8),
+
+    #[error("codec {0} is not available in this build (enable the `zstd` feature)")]
+    Unavailable(Codec),
+
+    #[error("blob header is truncated")]
+    TruncatedHeader,
+
+    #[error("{codec} codec failed: {source}")]
+    Codec {
+        codec: Codec,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+impl From<CompressionError> for Error {
+    fn from(err: CompressionError) -> Self {
+        Error::StorageError(err.to_string())
+    }
+}
+
+/// Chooses a codec for each blob from its size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionPolicy {
+    /// Codec for blobs of at least `min_size` bytes
+    pub codec: Codec,
+    /// Blobs smaller than this are stored uncompressed
+    pub min_size: usize,
+}
+
+impl CompressionPolicy {
+    pub fn new(codec: Codec, min_size: usize) -> Self {
+        Self { codec, min_size }
+    }
+
+    /// Store every blob uncompressed
+    pub fn disabled() -> Self {
+        Self::new(Codec::None, 0)
+    }
+
+    /// Policy from the storage section of the registry configuration
+    pub fn from_config(config: &StorageConfig) -> Result<Self, CompressionError> {
+        if !config.enable_compression {
+            return Ok(Self::disabled());
+        }
+        Ok(Self::new(config.compression_codec.parse()?, config.compression_min_size))
+    }
+
+    /// Codec for a blob of `len` bytes
+    pub fn codec_for(&self, len: usize) -> Codec {
+        if len < self.min_size {
+            Codec::None
+        } else {
+            self.codec
+        }
+    }
+}
+
+impl Default for CompressionPolicy {
+    fn default() -> Self {
+        Self::new(Codec::Gzip, DEFAULT_MIN_COMPRESS_SIZE)
+    }
+}
+
+/// Encode `content` with the codec `policy` picks for its size
+pub fn encode_blob(content: &[u8], policy: &CompressionPolicy) -> Result<Vec<u8>, CompressionError> {
+    encode_blob_with(content, policy.codec_for(content.len()))
+}
+
+/// Encode `content` with `codec`
+///
+/// Falls back to [`Codec::None`] when compression would not make the blob
+/// smaller, so the header always names the codec actually used.
+pub fn encode_blob_with(content: &[u8], codec: Codec) -> Result<Vec<u8>, CompressionError> {
+    let compressed = codec.compress(content)?;
+    let (codec, payload) = if codec != Codec::None && compressed.len() >= content.len() {
+        (Codec::None, content)
+    } else {
+        (codec, compressed.as_slice())
+    };
+
+    let mut blob = Vec::with_capacity(BLOB_HEADER_LEN + payload.len());
+    blob.extend_from_slice(&BLOB_MAGIC);
+    blob.push(codec.id());
+    blob.extend_from_slice(payload);
+    Ok(blob)
+}
+
+/// Codec a blob was written with; headerless blobs are [`Codec::None`]
+pub fn blob_codec(blob: &[u8]) -> Result<Codec, CompressionError> {
+    if !blob.starts_with(&BLOB_MAGIC) {
+        return Ok(Codec::None);
+    }
+    match blob.get(BLOB_MAGIC.len()) {
+        Some(&id) => Codec::from_id(id),
+        None => Err(CompressionError::TruncatedHeader),
+    }
+}
+
+/// Decode a blob written by [`encode_blob`], or a legacy headerless blob
+pub fn decode_blob(blob: &[u8]) -> Result<Vec<u8>, CompressionError> {
+    if !blob.starts_with(&BLOB_MAGIC) {
+        return Ok(blob.to_vec());
+    }
+    let codec = blob_codec(blob)?;
+    codec.decompress(&blob[BLOB_HEADER_LEN..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn large_schema() -> Vec<u8> {
+        let properties: Vec<String> = (0..200)
+            .map(|n| format!("\"field_{n}\": {{\"type\": \"string\", \"description\": \"field number {n}\"}}"))
+            .collect();
+        format!("{{\"type\": \"object\", \"properties\": {{{}}}}}", properties.join(", ")).into_bytes()
+    }
+
+    #[test]
+    fn test_round_trip_each_available_codec() {
+        let content = large_schema();
+        for codec in Codec::available() {
+            let blob = encode_blob_with(&content, codec).unwrap();
+            assert_eq!(blob_codec(&blob).unwrap(), codec);
+            assert_eq!(decode_blob(&blob).unwrap(), content, "{codec}");
+            if codec != Codec::None {
+                assert!(blob.len() < content.len(), "{codec} did not shrink the blob");
+            }
+        }
+    }
+
+    #[test]
+    fn test_policy_leaves_small_blobs_uncompressed() {
+        let policy = CompressionPolicy::new(Codec::Gzip, 4096);
+        let small = br#"{"type": "string"}"#;
+
+        let blob = encode_blob(small, &policy).unwrap();
+        assert_eq!(blob_codec(&blob).unwrap(), Codec::None);
+        assert_eq!(&blob[BLOB_HEADER_LEN..], small);
+
+        let blob = encode_blob(&large_schema(), &policy).unwrap();
+        assert_eq!(blob_codec(&blob).unwrap(), Codec::Gzip);
+    }
+
+    #[test]
+    fn test_incompressible_content_falls_back_to_none() {
+        let noise: Vec<u8> = (0..64u32).map(|n| (n.wrapping_mul(2654435761) >> 13) as u8).collect();
+        let blob = encode_blob_with(&noise, Codec::Gzip).unwrap();
+        assert_eq!(blob_codec(&blob).unwrap(), Codec::None);
+        assert_eq!(decode_blob(&blob).unwrap(), noise);
+    }
+
+    #[test]
+    fn test_legacy_and_malformed_blobs() {
+        let legacy = br#"{"type": "object"}"#;
+        assert_eq!(blob_codec(legacy).unwrap(), Codec::None);
+        assert_eq!(decode_blob(legacy).unwrap(), legacy);
+
+        assert!(matches!(decode_blob(b"\0SRB"), Err(CompressionError::TruncatedHeader)));
+        assert!(matches!(decode_blob(b"\0SRB\x09"), Err(CompressionError::UnknownCodecId(9))));
+    }
+
+    #[test]
+    fn test_codec_names() {
+        assert_eq!("ZSTD".parse::<Codec>().unwrap(), Codec::Zstd);
+        assert!("lz4".parse::<Codec>().is_err());
+        assert_eq!(Codec::Zstd.is_available(), cfg!(feature = "zstd"));
+    }
+
+    #[cfg(not(feature = "zstd"))]
+    #[test]
+    fn test_zstd_blob_without_feature_is_reported() {
+        let blob = [&BLOB_MAGIC[..], &[Codec::Zstd.id()], b"payload"].concat();
+        assert!(matches!(decode_blob(&blob), Err(CompressionError::Unavailable(Codec::Zstd))));
+    }
+
+    #[test]
+    fn test_policy_from_config() {
+        let mut config = StorageConfig::default();
+        config.compression_codec = "gzip".to_string();
+        config.compression_min_size = 4096;
+        assert_eq!(CompressionPolicy::from_config(&config).unwrap(), CompressionPolicy::new(Codec::Gzip, 4096));
+
+        config.enable_compression = false;
+        assert_eq!(CompressionPolicy::from_config(&config).unwrap(), CompressionPolicy::disabled());
+    }
+}
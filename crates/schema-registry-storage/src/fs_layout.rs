@@ -0,0 +1,262 @@
+//! On-disk layouts of [`FsBlobStore`]
+//!
+//! Layout version 1 ([`FsLayout::Flat`]) stores each blob at its key below
+//! the root, so the top-level directory gains an entry per subject. With
+//! hundreds of thousands of subjects, listing and backing up that directory
+//! is slow on some filesystems.
+//!
+//! Layout version 2 ([`FsLayout::Sharded`]) fans subjects out over two levels
+//! of directories: a blob is stored at `.sharded/<aa>/<bb>/<key>`, where `aa`
+//! and `bb` are the first two groups of `prefix_len` hex digits of a hash of
+//! the key's subject (everything before its last `/`). All versions of a
+//! subject therefore share a directory.
+//!
+//! The root's [`LAYOUT_FILE`] records the layout; a root without one is flat.
+//! [`migrate_layout`] moves a flat store to the sharded layout. It records the
+//! new layout before moving anything, and sharded stores read keys that are
+//! not moved yet from their flat location, so the registry keeps working
+//! during the migration. An interrupted migration resumes where it stopped
+//! when run again.
+
+use schema_registry_core::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::blob::{BlobStore, FsBlobStore};
+
+/// File at the store root recording its [`FsLayout`]
+pub const LAYOUT_FILE: &str = ".layout";
+
+/// Directory at the store root holding the sharded layout's fan-out
+pub(crate) const SHARDED_DIR: &str = ".sharded";
+
+/// Hex digits per directory level when none are configured: 256 × 256 directories
+pub const DEFAULT_PREFIX_LEN: usize = 2;
+
+/// Most hex digits per directory level
+pub const MAX_PREFIX_LEN: usize = 4;
+
+/// How an [`FsBlobStore`] arranges blobs below its root
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "layout", rename_all = "snake_case")]
+pub enum FsLayout {
+    /// One file per blob at its key (layout version 1)
+    Flat,
+    /// Subjects hashed into two directory levels of `prefix_len` hex digits each (layout version 2)
+    Sharded { prefix_len: usize },
+}
+
+impl FsLayout {
+    /// Sharded layout with `prefix_len` hex digits per level, between 1 and [`MAX_PREFIX_LEN`]
+    pub fn sharded(prefix_len: usize) -> Result<Self> {
+        if !(1..=MAX_PREFIX_LEN).contains(&prefix_len) {
+            return Err(Error::ConfigError(format!(
+                "shard prefix length must be between 1 and {}, got {}",
+                MAX_PREFIX_LEN, prefix_len
+            )));
+        }
+        Ok(Self::Sharded { prefix_len })
+    }
+
+    /// Version number recorded in [`LAYOUT_FILE`]
+    pub fn version(&self) -> u32 {
+        match self {
+            Self::Flat => 1,
+            Self::Sharded { .. } => 2,
+        }
+    }
+
+    /// Fan-out directories in front of `key`, `None` in the flat layout
+    pub(crate) fn shard_dirs(&self, key: &str) -> Option<[String; 2]> {
+        let Self::Sharded { prefix_len } = *self else {
+            return None;
+        };
+        let subject = key.rsplit_once('/').map_or(key, |(subject, _)| subject);
+        let hash = format!("{:016x}", fnv1a(subject.as_bytes()));
+        Some([hash[..prefix_len].to_string(), hash[prefix_len..2 * prefix_len].to_string()])
+    }
+}
+
+/// Contents of [`LAYOUT_FILE`]
+#[derive(Debug, Serialize, Deserialize)]
+struct LayoutMarker {
+    version: u32,
+    #[serde(flatten)]
+    layout: FsLayout,
+}
+
+/// 64-bit FNV-1a, stable across platforms and Rust versions unlike `std`'s hasher
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes
+        .iter()
+        .fold(OFFSET_BASIS, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME))
+}
+
+/// Layout recorded at `root`, flat when there is no [`LAYOUT_FILE`]
+pub async fn read_layout(root: &Path) -> Result<FsLayout> {
+    let content = match tokio::fs::read(root.join(LAYOUT_FILE)).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(FsLayout::Flat),
+        Err(e) => return Err(e.into()),
+    };
+    let marker: LayoutMarker = serde_json::from_slice(&content)?;
+    if marker.version != marker.layout.version() {
+        return Err(Error::StorageError(format!(
+            "{} at {} records layout version {} for a {:?} layout",
+            LAYOUT_FILE,
+            root.display(),
+            marker.version,
+            marker.layout
+        )));
+    }
+    Ok(marker.layout)
+}
+
+/// Record `layout` at `root`, replacing the marker atomically
+pub async fn write_layout(root: &Path, layout: FsLayout) -> Result<()> {
+    let marker = LayoutMarker {
+        version: layout.version(),
+        layout,
+    };
+    FsBlobStore::new(root).write(LAYOUT_FILE, &serde_json::to_vec_pretty(&marker)?).await
+}
+
+/// Running totals of a [`migrate_layout`] pass
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayoutMigrationReport {
+    /// Flat-layout blobs found when the pass started
+    pub total: usize,
+    /// Blobs moved so far
+    pub moved: usize,
+    /// Flat copies removed because the blob was already in the sharded layout,
+    /// e.g. after an interrupted pass
+    pub deduplicated: usize,
+}
+
+/// Move every blob of the store at `root` into the sharded layout
+///
+/// Safe to run against a live store and to run again after an interruption:
+/// each blob is written to its sharded path before its flat copy is removed,
+/// and a blob already present in the sharded layout keeps that (newer) copy.
+/// `on_progress` is called after each blob. Stores that already use a
+/// different sharded layout are rejected.
+pub async fn migrate_layout<F>(root: &Path, layout: FsLayout, mut on_progress: F) -> Result<LayoutMigrationReport>
+where
+    F: FnMut(&LayoutMigrationReport),
+{
+    if layout == FsLayout::Flat {
+        return Err(Error::ConfigError("can only migrate to a sharded layout".to_string()));
+    }
+    match read_layout(root).await? {
+        FsLayout::Flat => write_layout(root, layout).await?,
+        current if current == layout => {}
+        current => {
+            return Err(Error::ConfigError(format!(
+                "store at {} already uses {:?}; re-sharding is not supported",
+                root.display(),
+                current
+            )))
+        }
+    }
+
+    let store = FsBlobStore::new(root).with_layout(layout);
+    let keys = store.flat_keys().await?;
+    let mut report = LayoutMigrationReport {
+        total: keys.len(),
+        ..Default::default()
+    };
+
+    for key in keys {
+        if store.has_sharded(&key).await? {
+            store.remove_flat(&key).await?;
+            report.deduplicated += 1;
+        } else {
+            // Writing to the sharded path removes the flat copy
+            let blob = store.read(&key).await?;
+            store.write(&key, &blob).await?;
+            report.moved += 1;
+        }
+        on_progress(&report);
+    }
+
+    store.remove_empty_flat_dirs().await?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_of_a_subject_share_a_shard() {
+        let layout = FsLayout::sharded(2).unwrap();
+        let dirs = layout.shard_dirs("payments/invoice/1.0.0").unwrap();
+        assert_eq!(dirs, layout.shard_dirs("payments/invoice/2.0.0").unwrap());
+        assert_eq!(dirs[0].len(), 2);
+        assert_eq!(dirs[1].len(), 2);
+        assert_eq!(FsLayout::Flat.shard_dirs("orders/1.0.0"), None);
+
+        assert!(FsLayout::sharded(0).is_err());
+        assert!(FsLayout::sharded(MAX_PREFIX_LEN + 1).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_layout_marker_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(read_layout(dir.path()).await.unwrap(), FsLayout::Flat);
+
+        let layout = FsLayout::sharded(3).unwrap();
+        write_layout(dir.path(), layout).await.unwrap();
+        assert_eq!(read_layout(dir.path()).await.unwrap(), layout);
+
+        let marker: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(dir.path().join(LAYOUT_FILE)).unwrap()).unwrap();
+        assert_eq!(marker["version"], 2);
+        assert_eq!(marker["prefix_len"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_migration_moves_flat_blobs_and_resumes() {
+        let dir = tempfile::tempdir().unwrap();
+        let flat = FsBlobStore::new(dir.path());
+        for n in 0..20 {
+            flat.write(&format!("subject_{n}/1.0.0"), format!("blob {n}").as_bytes()).await.unwrap();
+        }
+        let layout = FsLayout::sharded(1).unwrap();
+
+        // Interrupted pass: marker written and one blob moved, plus a blob
+        // written to both layouts by a crash between write and cleanup
+        write_layout(dir.path(), layout).await.unwrap();
+        let sharded = FsBlobStore::open(dir.path()).await.unwrap();
+        assert_eq!(sharded.layout(), layout);
+        sharded.write("subject_0/1.0.0", b"blob 0").await.unwrap();
+        let [a, b] = layout.shard_dirs("subject_1/1.0.0").unwrap();
+        let stale = dir.path().join(SHARDED_DIR).join(a).join(b).join("subject_1");
+        std::fs::create_dir_all(&stale).unwrap();
+        std::fs::write(stale.join("1.0.0"), b"blob 1 updated").unwrap();
+
+        // Reads and listing see every blob exactly once mid-migration
+        assert_eq!(sharded.keys().await.unwrap().len(), 20);
+        assert_eq!(sharded.read("subject_5/1.0.0").await.unwrap(), b"blob 5");
+
+        let mut progress = 0;
+        let report = migrate_layout(dir.path(), layout, |_| progress += 1).await.unwrap();
+        assert_eq!(report.total, 19);
+        assert_eq!(report.moved, 18);
+        assert_eq!(report.deduplicated, 1);
+        assert_eq!(progress, 19);
+
+        assert!(sharded.flat_keys().await.unwrap().is_empty());
+        assert!(!dir.path().join("subject_5").exists());
+        assert_eq!(sharded.keys().await.unwrap().len(), 20);
+        assert_eq!(sharded.read("subject_1/1.0.0").await.unwrap(), b"blob 1 updated");
+
+        // Nothing left to do, and a different prefix length is refused
+        let again = migrate_layout(dir.path(), layout, |_| {}).await.unwrap();
+        assert_eq!(again.total, 0);
+        assert!(migrate_layout(dir.path(), FsLayout::sharded(2).unwrap(), |_| {}).await.is_err());
+    }
+}
@@ -2,14 +2,20 @@
 //!
 //! Storage abstraction layer for PostgreSQL, Redis, and S3.
 //! Implements the SchemaStorage trait from schema-registry-core.
+//! Schema blobs are encoded with per-blob compression codecs (see [`compression`]).
 
+pub mod blob;
 pub mod cache_warmer;
+pub mod compression;
+pub mod fs_layout;
 pub mod postgres;
+pub mod read_cache;
 pub mod redis_cache;
+pub mod retry;
 pub mod s3;
 
 use async_trait::async_trait;
-use schema_registry_core::{error::Result, schema::RegisteredSchema, traits::SchemaStorage, versioning::SemanticVersion};
+use schema_registry_core::{error::Result, pagination::{Page, PageRequest}, schema::RegisteredSchema, traits::SchemaStorage, versioning::SemanticVersion};
 use uuid::Uuid;
 
 /// Storage backend configuration
@@ -98,6 +104,10 @@ impl SchemaStorage for MultiTierStorage {
     async fn find_by_name(&self, namespace: &str, name: &str) -> Result<Vec<RegisteredSchema>> {
         self.postgres.find_by_name(namespace, name).await
     }
+
+    async fn list_subjects(&self, page: &PageRequest) -> Result<Page<String>> {
+        self.postgres.list_subjects(page).await
+    }
 }
 
 #[cfg(test)]
@@ -1,7 +1,7 @@
 //! PostgreSQL storage implementation
 
 use async_trait::async_trait;
-use schema_registry_core::{error::{Error, Result}, schema::RegisteredSchema, traits::SchemaStorage, versioning::SemanticVersion};
+use schema_registry_core::{error::{Error, Result}, pagination::{Page, PageRequest}, schema::RegisteredSchema, traits::SchemaStorage, versioning::SemanticVersion};
 use uuid::Uuid;
 
 use crate::StorageConfig;
@@ -46,6 +46,12 @@ impl SchemaStorage for PostgresStorage {
     async fn find_by_name(&self, _namespace: &str, _name: &str) -> Result<Vec<RegisteredSchema>> {
         Ok(vec![])
     }
+
+    async fn list_subjects(&self, _page: &PageRequest) -> Result<Page<String>> {
+        // Keyset query once the pool lands: SELECT DISTINCT subject ... WHERE
+        // subject > $after AND subject LIKE $pattern ORDER BY subject LIMIT $limit + 1
+        Ok(Page::empty())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,677 @@
+//! Read cache for schema lookups
+//!
+//! [`CachingStorage`] wraps any [`SchemaStorage`] and serves repeated
+//! [`retrieve`](SchemaStorage::retrieve) calls from memory. How long an entry
+//! stays fresh is decided by the [`TtlPolicy`]: either one fixed TTL
+//! (`cache_ttl_seconds`), or an [`AdaptiveTtl`] chosen per subject from how
+//! often that subject is written, so frequently updated subjects are re-read
+//! sooner and rarely updated ones stay cached longer.
+//!
+//! Writes made through the wrapper invalidate the written subject; writes
+//! made elsewhere can be propagated with [`CachingStorage::invalidate_subject`].
+//! When refreshing an expired entry fails with a retryable error, the expired
+//! copy is served instead and counted as a stale serve.
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use schema_registry_core::{
+    config_manager_adapter::StorageConfig,
+    error::{Error, Result},
+    pagination::{Page, PageRequest},
+    schema::RegisteredSchema,
+    traits::SchemaStorage,
+    versioning::SemanticVersion,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+/// Default maximum number of cached entries
+pub const DEFAULT_MAX_ENTRIES: usize = 10_000;
+
+/// Default share of a subject's write interval used as its adaptive TTL
+pub const DEFAULT_WRITE_INTERVAL_FRACTION: f64 = 0.5;
+
+/// Weight of the newest interval in a subject's moving average
+const INTERVAL_SMOOTHING: f64 = 0.3;
+
+/// Bounds and tuning for per-subject TTLs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveTtl {
+    /// Shortest TTL, used for the most frequently written subjects
+    pub min_ttl: Duration,
+    /// Longest TTL, used for subjects with no observed write interval
+    pub max_ttl: Duration,
+    /// Share of the expected time until the next write used as the TTL
+    pub write_interval_fraction: f64,
+}
+
+impl AdaptiveTtl {
+    /// Adaptive TTLs between `min_ttl` and `max_ttl`
+    pub fn new(min_ttl: Duration, max_ttl: Duration) -> Result<Self> {
+        if min_ttl.is_zero() || min_ttl > max_ttl {
+            return Err(Error::ConfigError(format!(
+                "adaptive cache TTL bounds must satisfy 0 < min <= max, got {:?}..{:?}",
+                min_ttl, max_ttl
+            )));
+        }
+        Ok(Self {
+            min_ttl,
+            max_ttl,
+            write_interval_fraction: DEFAULT_WRITE_INTERVAL_FRACTION,
+        })
+    }
+
+    /// TTL for a subject with `history`, as of `now`
+    ///
+    /// A subject that has gone quiet is expected to stay quiet at least as
+    /// long as it already has, so its TTL grows back towards `max_ttl`.
+    pub fn ttl(&self, history: Option<&WriteHistory>, now: Instant) -> Duration {
+        let Some(interval) = history.and_then(|h| h.expected_interval(now)) else {
+            return self.max_ttl;
+        };
+        interval
+            .mul_f64(self.write_interval_fraction)
+            .clamp(self.min_ttl, self.max_ttl)
+    }
+}
+
+/// How entries are given their TTL
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TtlPolicy {
+    /// Every entry lives for the same time
+    Fixed(Duration),
+    /// Each subject's TTL follows its observed write frequency
+    Adaptive(AdaptiveTtl),
+}
+
+impl TtlPolicy {
+    /// Policy from the storage section of the registry configuration
+    pub fn from_config(config: &StorageConfig) -> Result<Self> {
+        if !config.adaptive_cache_ttl {
+            return Ok(Self::Fixed(Duration::from_secs(config.cache_ttl_seconds)));
+        }
+        AdaptiveTtl::new(
+            Duration::from_secs(config.cache_min_ttl_seconds),
+            Duration::from_secs(config.cache_max_ttl_seconds),
+        )
+        .map(Self::Adaptive)
+    }
+
+    /// TTL for a subject with `history`, as of `now`
+    pub fn ttl(&self, history: Option<&WriteHistory>, now: Instant) -> Duration {
+        match self {
+            TtlPolicy::Fixed(ttl) => *ttl,
+            TtlPolicy::Adaptive(adaptive) => adaptive.ttl(history, now),
+        }
+    }
+}
+
+impl Default for TtlPolicy {
+    fn default() -> Self {
+        Self::Fixed(Duration::from_secs(StorageConfig::default().cache_ttl_seconds))
+    }
+}
+
+/// Observed writes to one subject
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WriteHistory {
+    last_write: Instant,
+    mean_interval: Option<Duration>,
+    writes: u64,
+}
+
+impl WriteHistory {
+    /// History of a subject first written at `at`
+    pub fn new(at: Instant) -> Self {
+        Self {
+            last_write: at,
+            mean_interval: None,
+            writes: 1,
+        }
+    }
+
+    /// Record a write at `at`
+    pub fn record(&mut self, at: Instant) {
+        let interval = at.saturating_duration_since(self.last_write);
+        self.mean_interval = Some(match self.mean_interval {
+            None => interval,
+            Some(mean) => mean.mul_f64(1.0 - INTERVAL_SMOOTHING) + interval.mul_f64(INTERVAL_SMOOTHING),
+        });
+        self.last_write = at;
+        self.writes += 1;
+    }
+
+    /// Writes observed, including the first
+    pub fn writes(&self) -> u64 {
+        self.writes
+    }
+
+    /// Smoothed time between writes, once there have been two
+    pub fn mean_interval(&self) -> Option<Duration> {
+        self.mean_interval
+    }
+
+    /// Expected time between writes as of `now`
+    fn expected_interval(&self, now: Instant) -> Option<Duration> {
+        let quiet_for = now.saturating_duration_since(self.last_write);
+        self.mean_interval.map(|mean| mean.max(quiet_for))
+    }
+}
+
+/// Counters maintained by [`CachingStorage`]
+#[derive(Debug, Default)]
+pub struct CacheMetrics {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    stale_serves: AtomicU64,
+    evictions: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+impl CacheMetrics {
+    /// Lookups answered from a fresh entry
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Lookups that went to the wrapped storage
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Misses answered from an expired entry because the refresh failed
+    pub fn stale_serves(&self) -> u64 {
+        self.stale_serves.load(Ordering::Relaxed)
+    }
+
+    /// Entries dropped because they expired or the cache was full
+    pub fn evictions(&self) -> u64 {
+        self.evictions.load(Ordering::Relaxed)
+    }
+
+    /// Entries dropped because their subject was written or invalidated
+    pub fn invalidations(&self) -> u64 {
+        self.invalidations.load(Ordering::Relaxed)
+    }
+
+    /// Share of lookups answered from the cache, 0.0 before the first lookup
+    pub fn hit_rate(&self) -> f64 {
+        let hits = self.hits();
+        let lookups = hits + self.misses();
+        if lookups == 0 {
+            0.0
+        } else {
+            hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// Point-in-time view of a cache, for reporting
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub stale_serves: u64,
+    pub evictions: u64,
+    pub invalidations: u64,
+    pub hit_rate: f64,
+    /// Current TTL of every subject with write history, in seconds
+    pub subject_ttls: BTreeMap<String, f64>,
+}
+
+type CacheKey = (Uuid, Option<SemanticVersion>);
+
+struct CacheEntry {
+    schema: RegisteredSchema,
+    expires_at: Instant,
+}
+
+/// Storage wrapper caching `retrieve` results in memory
+///
+/// Only `retrieve` is cached; other reads go straight to the wrapped storage.
+pub struct CachingStorage<S> {
+    inner: S,
+    policy: TtlPolicy,
+    max_entries: usize,
+    entries: Mutex<HashMap<CacheKey, CacheEntry>>,
+    writes: Mutex<HashMap<String, WriteHistory>>,
+    /// Bumped on every invalidation, so a read that raced a write doesn't
+    /// cache what it read
+    generation: AtomicU64,
+    metrics: CacheMetrics,
+}
+
+impl<S: SchemaStorage> CachingStorage<S> {
+    /// Wrap `inner` with `policy`
+    pub fn new(inner: S, policy: TtlPolicy) -> Self {
+        Self {
+            inner,
+            policy,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            entries: Mutex::new(HashMap::new()),
+            writes: Mutex::new(HashMap::new()),
+            generation: AtomicU64::new(0),
+            metrics: CacheMetrics::default(),
+        }
+    }
+
+    /// Cap the number of cached entries
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Cache counters
+    pub fn metrics(&self) -> &CacheMetrics {
+        &self.metrics
+    }
+
+    /// Wrapped storage
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// TTL policy
+    pub fn policy(&self) -> &TtlPolicy {
+        &self.policy
+    }
+
+    /// Number of cached entries, fresh or expired
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Whether nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+
+    /// TTL entries of `subject` currently get
+    pub fn ttl_for(&self, subject: &str) -> Duration {
+        self.policy.ttl(self.writes.lock().get(subject), Instant::now())
+    }
+
+    /// Counters plus the current TTL of every subject with write history
+    pub fn stats(&self) -> CacheStats {
+        let now = Instant::now();
+        let subject_ttls = self
+            .writes
+            .lock()
+            .iter()
+            .map(|(subject, history)| (subject.clone(), self.policy.ttl(Some(history), now).as_secs_f64()))
+            .collect();
+        CacheStats {
+            entries: self.len(),
+            hits: self.metrics.hits(),
+            misses: self.metrics.misses(),
+            stale_serves: self.metrics.stale_serves(),
+            evictions: self.metrics.evictions(),
+            invalidations: self.metrics.invalidations(),
+            hit_rate: self.metrics.hit_rate(),
+            subject_ttls,
+        }
+    }
+
+    /// Drop every cached version of `subject` (`namespace.name`)
+    ///
+    /// Returns the number of entries dropped.
+    pub fn invalidate_subject(&self, subject: &str) -> usize {
+        self.invalidate(|entry| entry.schema.fully_qualified_name() == subject)
+    }
+
+    /// Drop every cached entry, returning how many there were
+    pub fn clear(&self) -> usize {
+        self.invalidate(|_| true)
+    }
+
+    fn invalidate(&self, mut matches: impl FnMut(&CacheEntry) -> bool) -> usize {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        let mut entries = self.entries.lock();
+        let before = entries.len();
+        entries.retain(|_, entry| !matches(entry));
+        let dropped = before - entries.len();
+        self.metrics.invalidations.fetch_add(dropped as u64, Ordering::Relaxed);
+        dropped
+    }
+
+    /// Record a write to `subject` and drop its cached entries
+    fn note_write(&self, subject: &str) {
+        let now = Instant::now();
+        self.writes
+            .lock()
+            .entry(subject.to_string())
+            .and_modify(|history| history.record(now))
+            .or_insert_with(|| WriteHistory::new(now));
+        self.invalidate_subject(subject);
+    }
+
+    fn insert(&self, key: CacheKey, schema: RegisteredSchema, generation: u64) {
+        let now = Instant::now();
+        let ttl = self.policy.ttl(self.writes.lock().get(&schema.fully_qualified_name()), now);
+
+        let mut entries = self.entries.lock();
+        if self.generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            let before = entries.len();
+            entries.retain(|_, entry| entry.expires_at > now);
+            let mut evicted = before - entries.len();
+            if entries.len() >= self.max_entries {
+                let soonest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.expires_at)
+                    .map(|(key, _)| key.clone());
+                if let Some(soonest) = soonest {
+                    entries.remove(&soonest);
+                    evicted += 1;
+                }
+            }
+            self.metrics.evictions.fetch_add(evicted as u64, Ordering::Relaxed);
+        }
+        entries.insert(
+            key,
+            CacheEntry {
+                schema,
+                expires_at: now + ttl,
+            },
+        );
+    }
+}
+
+#[async_trait]
+impl<S: SchemaStorage> SchemaStorage for CachingStorage<S> {
+    async fn store(&self, schema: RegisteredSchema) -> Result<()> {
+        let subject = schema.fully_qualified_name();
+        self.inner.store(schema).await?;
+        self.note_write(&subject);
+        Ok(())
+    }
+
+    async fn retrieve(&self, id: Uuid, version: Option<SemanticVersion>) -> Result<RegisteredSchema> {
+        let key = (id, version.clone());
+        let stale = {
+            let mut entries = self.entries.lock();
+            match entries.get(&key) {
+                Some(entry) if entry.expires_at > Instant::now() => {
+                    self.metrics.hits.fetch_add(1, Ordering::Relaxed);
+                    return Ok(entry.schema.clone());
+                }
+                Some(_) => {
+                    self.metrics.evictions.fetch_add(1, Ordering::Relaxed);
+                    entries.remove(&key)
+                }
+                None => None,
+            }
+        };
+        self.metrics.misses.fetch_add(1, Ordering::Relaxed);
+
+        let generation = self.generation.load(Ordering::SeqCst);
+        match self.inner.retrieve(id, version).await {
+            Ok(schema) => {
+                self.insert(key, schema.clone(), generation);
+                Ok(schema)
+            }
+            Err(err) if err.is_retryable() => match stale {
+                Some(entry) => {
+                    tracing::debug!(%id, error = %err, "serving expired cache entry after failed refresh");
+                    self.metrics.stale_serves.fetch_add(1, Ordering::Relaxed);
+                    Ok(entry.schema)
+                }
+                None => Err(err),
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn retrieve_by_hash(&self, content_hash: &str) -> Result<Option<RegisteredSchema>> {
+        self.inner.retrieve_by_hash(content_hash).await
+    }
+
+    async fn update(&self, schema: RegisteredSchema) -> Result<()> {
+        let subject = schema.fully_qualified_name();
+        self.inner.update(schema).await?;
+        self.note_write(&subject);
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid, version: SemanticVersion) -> Result<()> {
+        self.inner.delete(id, version).await?;
+        // Only the cache knows which subject the id belonged to
+        let subjects: Vec<String> = self
+            .entries
+            .lock()
+            .values()
+            .filter(|entry| entry.schema.id == id)
+            .map(|entry| entry.schema.fully_qualified_name())
+            .collect();
+        self.invalidate(|entry| entry.schema.id == id);
+        for subject in subjects {
+            self.note_write(&subject);
+        }
+        Ok(())
+    }
+
+    async fn list_versions(&self, id: Uuid) -> Result<Vec<SemanticVersion>> {
+        self.inner.list_versions(id).await
+    }
+
+    async fn find_by_name(&self, namespace: &str, name: &str) -> Result<Vec<RegisteredSchema>> {
+        self.inner.find_by_name(namespace, name).await
+    }
+
+    async fn list_subjects(&self, page: &PageRequest) -> Result<Page<String>> {
+        self.inner.list_subjects(page).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schema_registry_core::embedded::InMemoryStorage;
+    use schema_registry_core::{CompatibilityMode, SchemaLifecycle, SchemaMetadata, SchemaState, SerializationFormat};
+    use std::sync::atomic::AtomicBool;
+
+    /// In-memory storage counting retrieves, optionally failing them
+    #[derive(Default)]
+    struct CountingStorage {
+        inner: InMemoryStorage,
+        retrieves: AtomicU64,
+        busy: AtomicBool,
+    }
+
+    #[async_trait]
+    impl SchemaStorage for CountingStorage {
+        async fn store(&self, schema: RegisteredSchema) -> Result<()> {
+            self.inner.store(schema).await
+        }
+
+        async fn retrieve(&self, id: Uuid, version: Option<SemanticVersion>) -> Result<RegisteredSchema> {
+            self.retrieves.fetch_add(1, Ordering::SeqCst);
+            if self.busy.load(Ordering::SeqCst) {
+                return Err(Error::StorageBusy("database is locked".to_string()));
+            }
+            self.inner.retrieve(id, version).await
+        }
+
+        async fn retrieve_by_hash(&self, content_hash: &str) -> Result<Option<RegisteredSchema>> {
+            self.inner.retrieve_by_hash(content_hash).await
+        }
+
+        async fn update(&self, schema: RegisteredSchema) -> Result<()> {
+            self.inner.update(schema).await
+        }
+
+        async fn delete(&self, id: Uuid, version: SemanticVersion) -> Result<()> {
+            self.inner.delete(id, version).await
+        }
+
+        async fn list_versions(&self, id: Uuid) -> Result<Vec<SemanticVersion>> {
+            self.inner.list_versions(id).await
+        }
+
+        async fn find_by_name(&self, namespace: &str, name: &str) -> Result<Vec<RegisteredSchema>> {
+            self.inner.find_by_name(namespace, name).await
+        }
+
+        async fn list_subjects(&self, page: &PageRequest) -> Result<Page<String>> {
+            self.inner.list_subjects(page).await
+        }
+    }
+
+    fn schema(name: &str) -> RegisteredSchema {
+        let id = Uuid::new_v4();
+        RegisteredSchema {
+            id,
+            namespace: "test".to_string(),
+            name: name.to_string(),
+            version: SemanticVersion::new(1, 0, 0),
+            format: SerializationFormat::JsonSchema,
+            content: "{}".to_string(),
+            content_hash: id.to_string(),
+            description: String::new(),
+            compatibility_mode: CompatibilityMode::Backward,
+            state: SchemaState::Active,
+            metadata: SchemaMetadata {
+                created_at: chrono::Utc::now(),
+                created_by: "test".to_string(),
+                updated_at: chrono::Utc::now(),
+                updated_by: "test".to_string(),
+                activated_at: None,
+                deprecation: None,
+                deletion: None,
+                custom: HashMap::new(),
+            },
+            tags: vec![],
+            examples: vec![],
+            lifecycle: SchemaLifecycle::new(id),
+        }
+    }
+
+    fn adaptive() -> TtlPolicy {
+        TtlPolicy::Adaptive(AdaptiveTtl::new(Duration::from_secs(5), Duration::from_secs(3600)).unwrap())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_hits_until_ttl_expires() {
+        let cache = CachingStorage::new(CountingStorage::default(), TtlPolicy::Fixed(Duration::from_secs(60)));
+        let schema = schema("user");
+        cache.store(schema.clone()).await.unwrap();
+
+        cache.retrieve(schema.id, None).await.unwrap();
+        cache.retrieve(schema.id, None).await.unwrap();
+        assert_eq!(cache.inner().retrieves.load(Ordering::SeqCst), 1);
+
+        tokio::time::advance(Duration::from_secs(61)).await;
+        cache.retrieve(schema.id, None).await.unwrap();
+
+        assert_eq!(cache.inner().retrieves.load(Ordering::SeqCst), 2);
+        assert_eq!(cache.metrics().hits(), 1);
+        assert_eq!(cache.metrics().misses(), 2);
+        assert_eq!(cache.metrics().evictions(), 1);
+        assert!((cache.metrics().hit_rate() - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_expired_entry_served_when_refresh_is_busy() {
+        let cache = CachingStorage::new(CountingStorage::default(), TtlPolicy::Fixed(Duration::from_secs(60)));
+        let schema = schema("user");
+        cache.store(schema.clone()).await.unwrap();
+        cache.retrieve(schema.id, None).await.unwrap();
+
+        cache.inner().busy.store(true, Ordering::SeqCst);
+        tokio::time::advance(Duration::from_secs(61)).await;
+
+        let served = cache.retrieve(schema.id, None).await.unwrap();
+        assert_eq!(served.id, schema.id);
+        assert_eq!(cache.metrics().stale_serves(), 1);
+
+        // The stale copy is served once, not re-cached
+        assert!(cache.retrieve(schema.id, None).await.unwrap_err().is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_writes_and_manual_invalidation_drop_subject_entries() {
+        let cache = CachingStorage::new(CountingStorage::default(), TtlPolicy::default());
+        let user = schema("user");
+        let order = schema("order");
+        cache.store(user.clone()).await.unwrap();
+        cache.store(order.clone()).await.unwrap();
+        cache.retrieve(user.id, None).await.unwrap();
+        cache.retrieve(order.id, None).await.unwrap();
+        assert_eq!(cache.len(), 2);
+
+        let mut updated = user.clone();
+        updated.description = "changed".to_string();
+        cache.update(updated).await.unwrap();
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.retrieve(user.id, None).await.unwrap().description, "changed");
+
+        assert_eq!(cache.invalidate_subject("test.order"), 1);
+        assert_eq!(cache.invalidate_subject("test.order"), 0);
+        assert_eq!(cache.metrics().invalidations(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_capacity_evicts_soonest_expiring_entry() {
+        let cache = CachingStorage::new(CountingStorage::default(), TtlPolicy::default()).with_max_entries(2);
+        let schemas: Vec<_> = ["a", "b", "c"].into_iter().map(schema).collect();
+        for schema in &schemas {
+            cache.store(schema.clone()).await.unwrap();
+            cache.retrieve(schema.id, None).await.unwrap();
+        }
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.metrics().evictions(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_adaptive_ttl_tracks_write_frequency() {
+        let cache = CachingStorage::new(CountingStorage::default(), adaptive());
+        let hot = schema("hot");
+        let cold = schema("cold");
+        cache.store(hot.clone()).await.unwrap();
+        cache.store(cold.clone()).await.unwrap();
+
+        // No interval observed yet
+        assert_eq!(cache.ttl_for("test.hot"), Duration::from_secs(3600));
+
+        for tick in 1..=60 {
+            tokio::time::advance(Duration::from_secs(20)).await;
+            cache.update(hot.clone()).await.unwrap();
+            if tick % 30 == 0 {
+                cache.update(cold.clone()).await.unwrap();
+            }
+        }
+
+        // Written every 20s: half the interval
+        assert!((cache.ttl_for("test.hot").as_secs_f64() - 10.0).abs() < 1e-6);
+        // Written every 10 minutes, so between the bounds
+        let cold_ttl = cache.ttl_for("test.cold");
+        assert!(cold_ttl > Duration::from_secs(60) && cold_ttl < Duration::from_secs(3600), "{:?}", cold_ttl);
+
+        // A subject that goes quiet drifts back towards the upper bound
+        tokio::time::advance(Duration::from_secs(600)).await;
+        assert_eq!(cache.ttl_for("test.hot"), Duration::from_secs(300));
+
+        let stats = cache.stats();
+        assert_eq!(stats.subject_ttls["test.hot"], 300.0);
+    }
+
+    #[test]
+    fn test_policy_from_config() {
+        let mut config = StorageConfig::default();
+        assert_eq!(TtlPolicy::from_config(&config).unwrap(), TtlPolicy::Fixed(Duration::from_secs(300)));
+
+        config.adaptive_cache_ttl = true;
+        assert_eq!(TtlPolicy::from_config(&config).unwrap(), adaptive());
+
+        config.cache_min_ttl_seconds = 7200;
+        assert!(matches!(TtlPolicy::from_config(&config), Err(Error::ConfigError(_))));
+    }
+}
@@ -1,7 +1,7 @@
 //! Redis caching layer
 
 use async_trait::async_trait;
-use schema_registry_core::{error::{Error, Result}, schema::RegisteredSchema, traits::SchemaStorage, versioning::SemanticVersion};
+use schema_registry_core::{error::{Error, Result}, pagination::{Page, PageRequest}, schema::RegisteredSchema, traits::SchemaStorage, versioning::SemanticVersion};
 use uuid::Uuid;
 
 use crate::StorageConfig;
@@ -44,6 +44,10 @@ impl SchemaStorage for RedisCache {
     async fn find_by_name(&self, _namespace: &str, _name: &str) -> Result<Vec<RegisteredSchema>> {
         Ok(vec![])
     }
+
+    async fn list_subjects(&self, _page: &PageRequest) -> Result<Page<String>> {
+        Ok(Page::empty())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,6 @@
+//! Retry layer for storage operations
+//!
+//! Defined in [`schema_registry_core::retry`], where the embedded registry
+//! wraps its own storage with it, and re-exported here for the backends.
+
+pub use schema_registry_core::retry::{Deadline, RetryMetrics, RetryPolicy, RetryingStorage};
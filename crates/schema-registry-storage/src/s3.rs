@@ -1,7 +1,7 @@
 //! S3 storage for schema archives
 
 use async_trait::async_trait;
-use schema_registry_core::{error::{Error, Result}, schema::RegisteredSchema, traits::SchemaStorage, versioning::SemanticVersion};
+use schema_registry_core::{error::{Error, Result}, pagination::{Page, PageRequest}, schema::RegisteredSchema, traits::SchemaStorage, versioning::SemanticVersion};
 use uuid::Uuid;
 
 use crate::StorageConfig;
@@ -44,6 +44,10 @@ impl SchemaStorage for S3Storage {
     async fn find_by_name(&self, _namespace: &str, _name: &str) -> Result<Vec<RegisteredSchema>> {
         Ok(vec![])
     }
+
+    async fn list_subjects(&self, _page: &PageRequest) -> Result<Page<String>> {
+        Ok(Page::empty())
+    }
 }
 
 #[cfg(test)]
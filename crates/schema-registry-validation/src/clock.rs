@@ -0,0 +1,62 @@
+//! Time source for validation metrics
+//!
+//! `std::time::Instant` panics on `wasm32-unknown-unknown`, so the engine
+//! reads time through a [`Clock`] instead. [`SystemClock`] uses `Instant`
+//! natively and the JavaScript `Date` clock in wasm builds; embedders can
+//! plug in their own with [`ValidationEngine::with_clock`].
+//!
+//! [`ValidationEngine::with_clock`]: crate::engine::ValidationEngine::with_clock
+
+use std::time::Duration;
+
+/// Monotonic-enough time source used to measure validation duration
+pub trait Clock: Send + Sync {
+    /// Time elapsed since an arbitrary, fixed origin
+    fn now(&self) -> Duration;
+}
+
+/// Default clock for the build target
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        use once_cell::sync::Lazy;
+        use std::time::Instant;
+
+        static ORIGIN: Lazy<Instant> = Lazy::new(Instant::now);
+        ORIGIN.elapsed()
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        // Milliseconds since the epoch; wall-clock, but the only source
+        // available without a browser `performance` object
+        Duration::from_secs_f64(js_sys::Date::now().max(0.0) / 1000.0)
+    }
+}
+
+/// Without the `wasm` feature there is no host clock to call, so wasm
+/// builds report zero durations unless a clock is plugged in
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_clock_does_not_go_backwards() {
+        let clock = SystemClock;
+        let first = clock.now();
+        let second = clock.now();
+        assert!(second >= first);
+    }
+}
@@ -6,11 +6,17 @@
 
 use crate::types::{ValidationError, Severity, SchemaFormat};
 use crate::engine::ValidationRule;
+use schema_registry_core::annotations::{self, AnnotationMap};
 use schema_registry_core::config_manager_adapter::{
-    SchemaPolicies, FieldNamingPolicy,
+    ConfigUpdateListener, DocumentationPolicy, GlobalConfig, PolicyWaiver, SchemaPolicies, FieldNamingPolicy,
+    StylePolicy, StyleSeverity,
 };
+use schema_registry_core::doc_policy::{self, DocPolicyOutcome};
+use schema_registry_core::style_lint::{self, StyleCheck};
 use anyhow::Result;
+use chrono::NaiveDate;
 use regex::Regex;
+use std::sync::RwLock;
 use tracing::{debug, info};
 
 /// Policy-based validation rule that consumes policies from Config Manager
@@ -31,6 +37,18 @@ impl PolicyBasedValidationRule {
         self.policies = policies;
     }
 
+    /// Validate `schema` for `subject`, applying the policies' waivers as of `today`
+    pub fn evaluate(
+        &self,
+        subject: &str,
+        schema: &str,
+        format: SchemaFormat,
+        today: NaiveDate,
+    ) -> Result<PolicyEvaluation> {
+        let findings = self.validate(schema, format)?;
+        Ok(apply_waivers(findings, subject, &self.policies.waivers, today))
+    }
+
     /// Validate field naming conventions
     fn validate_field_naming(&self, schema: &str, format: SchemaFormat) -> Vec<ValidationError> {
         let mut errors = Vec::new();
@@ -117,6 +135,74 @@ impl PolicyBasedValidationRule {
         }
     }
 
+    /// Validate required annotations and declared annotation types
+    ///
+    /// Required annotations must be on the schema root; every annotation whose
+    /// key has a declared type is type-checked wherever it appears. Both checks
+    /// share a single extraction pass. `required_metadata` names registration
+    /// metadata rather than annotations, so the registry checks it instead.
+    fn validate_metadata(&self, schema: &str, format: SchemaFormat) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        if self.policies.required_annotations.is_empty() && self.policies.annotation_types.is_empty() {
+            return errors;
+        }
+
+        let annotations = match annotations::extract(schema, format.into()) {
+            Ok(annotations) => annotations,
+            Err(e) => {
+                debug!("Skipping metadata policy, annotations not extractable: {}", e);
+                return errors;
+            }
+        };
+
+        for key in &self.policies.required_annotations {
+            if annotations.value("$", key).is_none() {
+                errors.push(
+                    ValidationError::new(
+                        "metadata-policy",
+                        format!("Required annotation '{}' is missing", key),
+                    )
+                    .with_location("$")
+                    .with_suggestion(format!("Add a '{}' annotation to the schema root", key)),
+                );
+            }
+        }
+
+        errors.extend(self.check_annotation_types(&annotations));
+        errors
+    }
+
+    fn check_annotation_types(&self, annotations: &AnnotationMap) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        for (path, key, value) in annotations.iter() {
+            let Some(expected) = self.policies.annotation_types.get(key) else {
+                continue;
+            };
+            let matches = match expected.as_str() {
+                "string" => value.is_string(),
+                "number" => value.is_number(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "boolean" => value.is_boolean(),
+                "array" => value.is_array(),
+                "object" => value.is_object(),
+                _ => true, // Unknown type, allow all
+            };
+            if !matches {
+                errors.push(
+                    ValidationError::new(
+                        "metadata-policy",
+                        format!("Annotation '{}' must be of type {}, found {}", key, expected, value),
+                    )
+                    .with_location(path.to_string()),
+                );
+            }
+        }
+
+        errors
+    }
+
     /// Apply custom policy rules
     fn apply_custom_rules(&self, schema: &str) -> Vec<ValidationError> {
         let mut errors = Vec::new();
@@ -158,6 +244,9 @@ impl ValidationRule for PolicyBasedValidationRule {
         // Validate field naming
         errors.extend(self.validate_field_naming(schema, format));
 
+        // Validate metadata annotations
+        errors.extend(self.validate_metadata(schema, format));
+
         // Apply custom rules
         errors.extend(self.apply_custom_rules(schema));
 
@@ -165,6 +254,244 @@ impl ValidationRule for PolicyBasedValidationRule {
     }
 }
 
+/// Policy findings for one subject, after waivers
+#[derive(Debug, Clone, Default)]
+pub struct PolicyEvaluation {
+    /// Findings; waived ones are downgraded to [`Severity::Info`]
+    pub findings: Vec<ValidationError>,
+    /// Findings each waiver downgraded, in the order of `SchemaPolicies::waivers`
+    pub waiver_usage: Vec<usize>,
+}
+
+impl PolicyEvaluation {
+    /// Number of findings downgraded by any waiver
+    pub fn waived(&self) -> usize {
+        self.waiver_usage.iter().sum()
+    }
+
+    /// Findings that were not waived
+    ///
+    /// Includes findings reported as [`Severity::Info`] in the first place,
+    /// such as style findings.
+    pub fn unwaived(&self) -> impl Iterator<Item = &ValidationError> {
+        self.findings.iter().filter(|finding| !finding.context.contains_key(WAIVER_APPROVER))
+    }
+}
+
+/// Context key naming the approver of the waiver that downgraded a finding
+pub const WAIVER_APPROVER: &str = "waiver_approver";
+
+/// Downgrade findings on `subject` covered by an active waiver
+///
+/// Waived findings become [`Severity::Info`] and carry the waiver's approver,
+/// expiry and reason in their context. The first matching waiver is used.
+/// Expired waivers for the subject are inactive; they produce a single
+/// `waiver-expired` warning listing them.
+pub fn apply_waivers(
+    findings: Vec<ValidationError>,
+    subject: &str,
+    waivers: &[PolicyWaiver],
+    today: NaiveDate,
+) -> PolicyEvaluation {
+    let mut waiver_usage = vec![0; waivers.len()];
+    let mut findings: Vec<ValidationError> = findings
+        .into_iter()
+        .map(|mut finding| {
+            let waiver = waivers.iter().position(|waiver| {
+                waiver.is_active(today) && waiver.covers_subject(subject) && waiver.covers_rule(&finding.rule)
+            });
+            if let Some(index) = waiver {
+                let waiver = &waivers[index];
+                waiver_usage[index] += 1;
+                finding.severity = Severity::Info;
+                finding = finding
+                    .with_context(WAIVER_APPROVER, waiver.approver.clone())
+                    .with_context("waiver_expires", waiver.expires.to_string())
+                    .with_context("waiver_reason", waiver.reason.clone());
+            }
+            finding
+        })
+        .collect();
+
+    let expired: Vec<String> = waivers
+        .iter()
+        .filter(|waiver| !waiver.is_active(today) && waiver.covers_subject(subject))
+        .map(|waiver| format!("{} (approved by {}, expired {})", waiver.rules.join(", "), waiver.approver, waiver.expires))
+        .collect();
+    if !expired.is_empty() {
+        let mut warning = ValidationError::new(
+            "waiver-expired",
+            format!("Expired waivers for '{}' no longer apply: {}", subject, expired.join("; ")),
+        )
+        .with_suggestion("Renew the waiver or fix the findings it covered");
+        warning.severity = Severity::Warning;
+        findings.push(warning);
+    }
+
+    PolicyEvaluation {
+        findings,
+        waiver_usage,
+    }
+}
+
+/// Documentation content policy rule
+///
+/// Checks JSON Schema `description` and Avro `doc` strings against the
+/// policy's size limits and forbidden patterns. The policy sits behind a lock
+/// so the rule can be refreshed after it has been added to an engine, either
+/// directly or by registering it as a [`ConfigUpdateListener`].
+pub struct DocumentationPolicyRule {
+    policy: RwLock<DocumentationPolicy>,
+}
+
+impl DocumentationPolicyRule {
+    /// Create a documentation rule from the policies' `documentation` section
+    pub fn new(policies: &SchemaPolicies) -> Self {
+        Self {
+            policy: RwLock::new(policies.documentation.clone()),
+        }
+    }
+
+    /// Update the documentation policy (for runtime refresh)
+    pub fn update_policies(&self, policies: &SchemaPolicies) {
+        info!("Updating documentation policy ({} forbidden patterns)", policies.documentation.forbidden_patterns.len());
+        *self.policy.write().unwrap() = policies.documentation.clone();
+    }
+
+    /// Transform step: strip oversized documentation when the policy allows it
+    ///
+    /// Run before storing a schema; `violations` in the outcome are what
+    /// [`ValidationRule::validate`] would still report for the new content.
+    pub fn transform(&self, schema: &str, format: SchemaFormat) -> Result<DocPolicyOutcome> {
+        let policy = self.policy.read().unwrap().clone();
+        Ok(doc_policy::apply(schema, format.into(), &policy)?)
+    }
+}
+
+impl ValidationRule for DocumentationPolicyRule {
+    fn name(&self) -> &str {
+        "documentation-policy"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn validate(&self, schema: &str, format: SchemaFormat) -> Result<Vec<ValidationError>> {
+        let policy = self.policy.read().unwrap().clone();
+        let violations = match doc_policy::check(schema, format.into(), &policy) {
+            Ok(violations) => violations,
+            Err(e @ schema_registry_core::Error::ParseError(_)) => {
+                debug!("Skipping documentation policy, schema not parseable: {}", e);
+                return Ok(Vec::new());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(violations
+            .into_iter()
+            .map(|violation| {
+                let suggestion = if policy.strip_oversized {
+                    "Oversized documentation will be stripped at registration"
+                } else {
+                    "Shorten the documentation or move it to external docs"
+                };
+                ValidationError::new("documentation-policy", violation.to_string())
+                    .with_location(violation.path().to_string())
+                    .with_suggestion(suggestion)
+            })
+            .collect())
+    }
+}
+
+impl ConfigUpdateListener for DocumentationPolicyRule {
+    fn on_config_updated(&self, _config: &GlobalConfig) {}
+
+    fn on_policies_updated(&self, policies: &SchemaPolicies) {
+        self.update_policies(policies);
+    }
+}
+
+/// Style lint rule
+///
+/// Reports the documentation debt found by
+/// [`style_lint`](schema_registry_core::style_lint) with the style policy's
+/// severity, informational by default, and a `category: style` context entry.
+/// Findings use one rule id per check (`style-description`, ...) so waivers can
+/// suppress them individually.
+pub struct StyleLintRule {
+    policy: RwLock<StylePolicy>,
+}
+
+impl StyleLintRule {
+    /// Create a style rule from the policies' `style` section
+    pub fn new(policies: &SchemaPolicies) -> Self {
+        Self {
+            policy: RwLock::new(policies.style.clone()),
+        }
+    }
+
+    /// Update the style policy (for runtime refresh)
+    pub fn update_policies(&self, policies: &SchemaPolicies) {
+        info!("Updating style lint policy (severity {:?})", policies.style.severity);
+        *self.policy.write().unwrap() = policies.style.clone();
+    }
+}
+
+impl ValidationRule for StyleLintRule {
+    fn name(&self) -> &str {
+        "style-lint"
+    }
+
+    fn severity(&self) -> Severity {
+        match self.policy.read().unwrap().severity {
+            StyleSeverity::Info => Severity::Info,
+            StyleSeverity::Warning => Severity::Warning,
+            StyleSeverity::Error => Severity::Error,
+        }
+    }
+
+    fn validate(&self, schema: &str, format: SchemaFormat) -> Result<Vec<ValidationError>> {
+        let policy = self.policy.read().unwrap().clone();
+        let findings = match style_lint::lint(schema, format.into(), &policy) {
+            Ok(findings) => findings,
+            Err(e @ schema_registry_core::Error::ParseError(_)) => {
+                debug!("Skipping style lint, schema not parseable: {}", e);
+                return Ok(Vec::new());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let severity = self.severity();
+        Ok(findings
+            .into_iter()
+            .map(|finding| {
+                let suggestion = match finding.check {
+                    StyleCheck::Description => "Describe what the value means, its unit and constraints",
+                    StyleCheck::Example => "Add an example payload to `examples`",
+                    StyleCheck::TitleCase => "Use a descriptive title in Title Case",
+                    StyleCheck::EnumDocs => "Document every enum value",
+                };
+                let mut error = ValidationError::new(finding.check.rule_id(), finding.message)
+                    .with_location(finding.path)
+                    .with_suggestion(suggestion)
+                    .with_context("category", "style")
+                    .with_context("quality_weight", finding.check.weight().to_string());
+                error.severity = severity;
+                error
+            })
+            .collect())
+    }
+}
+
+impl ConfigUpdateListener for StyleLintRule {
+    fn on_config_updated(&self, _config: &GlobalConfig) {}
+
+    fn on_policies_updated(&self, policies: &SchemaPolicies) {
+        self.update_policies(policies);
+    }
+}
+
 /// Extension trait for ValidationEngine to support Config Manager policies
 pub trait ValidationEngineExt {
     /// Configure validation engine with policies from Config Manager
@@ -212,4 +539,144 @@ mod tests {
         assert_eq!(rule.name(), "config-manager-policy");
         assert_eq!(rule.severity(), Severity::Warning);
     }
+
+    #[test]
+    fn test_metadata_policy_required_and_typed_annotations() {
+        let mut policies = SchemaPolicies::default();
+        policies.required_annotations = vec!["x-owner".to_string(), "x-tier".to_string()];
+        policies.annotation_types.insert("x-go-type".to_string(), "string".to_string());
+        let rule = PolicyBasedValidationRule::new(policies);
+
+        let schema = r#"{
+            "type": "object",
+            "x-owner": "payments",
+            "properties": {"amount": {"type": "number", "x-go-type": 42}}
+        }"#;
+        let errors = rule.validate_metadata(schema, SchemaFormat::JsonSchema);
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.rule == "metadata-policy"));
+        assert!(errors[0].message.contains("x-tier"));
+        assert_eq!(errors[1].location.as_deref(), Some("$.amount"));
+    }
+
+    #[test]
+    fn test_required_metadata_is_not_read_as_annotations() {
+        let policies: SchemaPolicies = serde_json::from_value(serde_json::json!({
+            "field_naming": {"convention": "snake_case", "enforce": false},
+            "type_restrictions": [],
+            "required_metadata": ["description", "owner"],
+            "custom_rules": []
+        }))
+        .unwrap();
+        assert!(policies.required_annotations.is_empty());
+        let rule = PolicyBasedValidationRule::new(policies);
+
+        let errors = rule.validate_metadata(r#"{"type": "object", "description": "An order"}"#, SchemaFormat::JsonSchema);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_waivers_downgrade_findings_until_expiry() {
+        let date = |day| NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+        let mut policies = SchemaPolicies::default();
+        policies.field_naming.enforce = true;
+        policies.required_annotations = vec!["x-owner".to_string()];
+        policies.waivers = vec![
+            PolicyWaiver {
+                subject: "payments-*".to_string(),
+                rules: vec!["field-naming-policy".to_string()],
+                expires: date(10),
+                approver: "governance".to_string(),
+                reason: "legacy producer".to_string(),
+            },
+            PolicyWaiver {
+                subject: "payments-value".to_string(),
+                rules: vec!["metadata-policy".to_string()],
+                expires: date(1),
+                approver: "platform".to_string(),
+                reason: "owner being assigned".to_string(),
+            },
+        ];
+        let rule = PolicyBasedValidationRule::new(policies);
+        let schema = r#"{"userName": "string"}"#;
+
+        let evaluation = rule.evaluate("payments-value", schema, SchemaFormat::JsonSchema, date(5)).unwrap();
+        assert_eq!(evaluation.waiver_usage, vec![1, 0]);
+        let naming = evaluation.findings.iter().find(|f| f.rule == "field-naming-policy").unwrap();
+        assert_eq!(naming.severity, Severity::Info);
+        assert_eq!(naming.context["waiver_approver"], "governance");
+        let unwaived: Vec<_> = evaluation.unwaived().map(|f| f.rule.as_str()).collect();
+        assert_eq!(unwaived, vec!["metadata-policy", "waiver-expired"]);
+
+        let evaluation = rule.evaluate("payments-value", schema, SchemaFormat::JsonSchema, date(10)).unwrap();
+        assert_eq!(evaluation.waived(), 0);
+        assert_eq!(evaluation.findings.iter().filter(|f| f.rule == "waiver-expired").count(), 1);
+
+        let evaluation = rule.evaluate("orders-value", schema, SchemaFormat::JsonSchema, date(5)).unwrap();
+        assert_eq!(evaluation.waived(), 0);
+        assert!(evaluation.findings.iter().all(|f| f.rule != "waiver-expired"));
+    }
+
+    #[test]
+    fn test_documentation_rule_refresh() {
+        let schema = r#"{"type": "record", "name": "Host", "doc": "served from db1.corp.internal"}"#;
+        let mut policies = SchemaPolicies::default();
+        let rule = DocumentationPolicyRule::new(&policies);
+        assert!(rule.validate(schema, SchemaFormat::Avro).unwrap().is_empty());
+
+        policies.documentation.forbidden_patterns = vec![r"\.corp\.internal".to_string()];
+        rule.on_policies_updated(&policies);
+        let errors = rule.validate(schema, SchemaFormat::Avro).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rule, "documentation-policy");
+        assert_eq!(errors[0].location.as_deref(), Some("$"));
+    }
+
+    #[test]
+    fn test_style_rule_reports_info_findings_that_waivers_suppress() {
+        let date = |day| NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+        let mut policies = SchemaPolicies::default();
+        policies.waivers = vec![PolicyWaiver {
+            subject: "orders-*".to_string(),
+            rules: vec!["style-description".to_string()],
+            expires: date(10),
+            approver: "docs-team".to_string(),
+            reason: "descriptions being written".to_string(),
+        }];
+        let schema = r#"{"type": "object", "examples": [{}], "properties": {"id": {"type": "string"}}}"#;
+
+        let rule = StyleLintRule::new(&policies);
+        let findings = rule.validate(schema, SchemaFormat::JsonSchema).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, "style-description");
+        assert_eq!(findings[0].severity, Severity::Info);
+        assert_eq!(findings[0].context["category"], "style");
+
+        let evaluation = apply_waivers(findings.clone(), "payments-value", &policies.waivers, date(5));
+        assert_eq!(evaluation.unwaived().count(), 1);
+        let evaluation = apply_waivers(findings, "orders-value", &policies.waivers, date(5));
+        assert_eq!(evaluation.unwaived().count(), 0);
+        assert_eq!(evaluation.waived(), 1);
+
+        policies.style.severity = StyleSeverity::Warning;
+        rule.on_policies_updated(&policies);
+        assert_eq!(rule.severity(), Severity::Warning);
+        assert_eq!(rule.validate(schema, SchemaFormat::JsonSchema).unwrap()[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_documentation_rule_transform_strips_oversized() {
+        let mut policies = SchemaPolicies::default();
+        policies.documentation.max_doc_length = Some(8);
+        policies.documentation.strip_oversized = true;
+        let rule = DocumentationPolicyRule::new(&policies);
+
+        let schema = r#"{"type": "string", "description": "far too long for the limit"}"#;
+        assert_eq!(rule.validate(schema, SchemaFormat::JsonSchema).unwrap().len(), 1);
+
+        let outcome = rule.transform(schema, SchemaFormat::JsonSchema).unwrap();
+        assert_eq!(outcome.stripped.len(), 1);
+        assert!(rule.validate(&outcome.content, SchemaFormat::JsonSchema).unwrap().is_empty());
+    }
 }
@@ -9,12 +9,12 @@
 //! 6. Performance validation (complexity limits)
 //! 7. Custom rule validation (extensible rules)
 
+use crate::clock::{Clock, SystemClock};
 use crate::types::{
     SchemaFormat, ValidationConfig, ValidationError, ValidationResult, ValidationWarning, Severity,
 };
 use anyhow::Result;
 use std::sync::Arc;
-use std::time::Instant;
 
 /// A validation rule that can be applied to a schema
 pub trait ValidationRule: Send + Sync {
@@ -34,6 +34,8 @@ pub struct ValidationEngine {
     config: ValidationConfig,
     /// Custom validation rules
     custom_rules: Vec<Arc<dyn ValidationRule>>,
+    /// Time source for `ValidationMetrics::duration`
+    clock: Arc<dyn Clock>,
 }
 
 impl ValidationEngine {
@@ -42,6 +44,7 @@ impl ValidationEngine {
         Self {
             config: ValidationConfig::default(),
             custom_rules: Vec::new(),
+            clock: Arc::new(SystemClock),
         }
     }
 
@@ -50,9 +53,16 @@ impl ValidationEngine {
         Self {
             config,
             custom_rules: Vec::new(),
+            clock: Arc::new(SystemClock),
         }
     }
 
+    /// Measures validation duration with `clock` instead of the [`SystemClock`]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Adds a custom validation rule
     pub fn add_rule(&mut self, rule: Arc<dyn ValidationRule>) {
         self.custom_rules.push(rule);
@@ -60,7 +70,7 @@ impl ValidationEngine {
 
     /// Validates a schema using the 7-step pipeline
     pub async fn validate(&self, schema: &str, format: SchemaFormat) -> Result<ValidationResult> {
-        let start = Instant::now();
+        let start = self.clock.now();
         let mut result = ValidationResult::success(format);
 
         // Check schema size
@@ -84,7 +94,7 @@ impl ValidationEngine {
         if let Err(errors) = self.validate_structure(schema, format).await {
             result.merge(errors);
             if self.config.fail_fast && result.has_errors() {
-                result.metrics.duration = start.elapsed();
+                result.metrics.duration = self.clock.now().saturating_sub(start);
                 return Ok(result);
             }
         }
@@ -93,7 +103,7 @@ impl ValidationEngine {
         if let Err(errors) = self.validate_types(schema, format).await {
             result.merge(errors);
             if self.config.fail_fast && result.has_errors() {
-                result.metrics.duration = start.elapsed();
+                result.metrics.duration = self.clock.now().saturating_sub(start);
                 return Ok(result);
             }
         }
@@ -102,7 +112,7 @@ impl ValidationEngine {
         if let Err(errors) = self.validate_semantics(schema, format).await {
             result.merge(errors);
             if self.config.fail_fast && result.has_errors() {
-                result.metrics.duration = start.elapsed();
+                result.metrics.duration = self.clock.now().saturating_sub(start);
                 return Ok(result);
             }
         }
@@ -115,7 +125,7 @@ impl ValidationEngine {
             if let Err(errors) = self.validate_security(schema, format).await {
                 result.merge(errors);
                 if self.config.fail_fast && result.has_errors() {
-                    result.metrics.duration = start.elapsed();
+                    result.metrics.duration = self.clock.now().saturating_sub(start);
                     return Ok(result);
                 }
             }
@@ -126,7 +136,7 @@ impl ValidationEngine {
             if let Err(errors) = self.validate_performance(schema, format).await {
                 result.merge(errors);
                 if self.config.fail_fast && result.has_errors() {
-                    result.metrics.duration = start.elapsed();
+                    result.metrics.duration = self.clock.now().saturating_sub(start);
                     return Ok(result);
                 }
             }
@@ -136,13 +146,18 @@ impl ValidationEngine {
         for rule in &self.custom_rules {
             match rule.validate(schema, format) {
                 Ok(errors) => {
+                    // Findings below error severity, such as style findings, don't invalidate the schema
                     for error in errors {
-                        result.add_error(error);
+                        if error.severity < Severity::Error {
+                            result.add_warning(error.into());
+                        } else {
+                            result.add_error(error);
+                        }
                     }
                     result.metrics.rules_applied += 1;
 
                     if self.config.fail_fast && result.has_errors() {
-                        result.metrics.duration = start.elapsed();
+                        result.metrics.duration = self.clock.now().saturating_sub(start);
                         return Ok(result);
                     }
                 }
@@ -159,7 +174,7 @@ impl ValidationEngine {
             result.warnings.clear();
         }
 
-        result.metrics.duration = start.elapsed();
+        result.metrics.duration = self.clock.now().saturating_sub(start);
         Ok(result)
     }
 
@@ -651,4 +666,21 @@ mod tests {
         let result = engine.validate(schema, SchemaFormat::JsonSchema).await.unwrap();
         assert!(!result.is_valid);
     }
+
+    /// Advances 5ms on every reading
+    struct SteppingClock(std::sync::atomic::AtomicU64);
+
+    impl Clock for SteppingClock {
+        fn now(&self) -> std::time::Duration {
+            let step = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            std::time::Duration::from_millis(step * 5)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_duration_is_measured_with_plugged_clock() {
+        let engine = ValidationEngine::new().with_clock(Arc::new(SteppingClock(Default::default())));
+        let result = engine.validate(r#"{"type": "string"}"#, SchemaFormat::JsonSchema).await.unwrap();
+        assert_eq!(result.metrics.duration, std::time::Duration::from_millis(5));
+    }
 }
@@ -0,0 +1,801 @@
+//! Registry-wide governance report
+//!
+//! [`GovernanceScanner`] walks every subject in a [`SchemaStorage`] backend,
+//! one page of subject names and one subject's versions at a time, and runs
+//! the current policies against each subject's latest version in audit mode:
+//! findings are counted, never enforced. The resulting [`GovernanceReport`]
+//! rolls up per owning team and for the whole registry:
+//!
+//! - subjects and versions;
+//! - policy findings by severity, after waivers, and how often each waiver
+//!   was used;
+//! - the distribution of [`quality_score`]s;
+//! - deprecated subjects, and those already past their sunset date;
+//! - which compatibility levels are in use.
+//!
+//! Ownership comes from the schema's custom metadata or root annotations
+//! ([`OWNER_KEYS`]); subjects without either are [`UNOWNED`].
+//!
+//! With a time budget, a registry too large to scan in time is sampled: once
+//! the subjects left would not fit in the remaining time at the pace so far,
+//! only every n-th remaining subject is scanned. The report's
+//! [`ScanCoverage`] records where sampling started and at what stride, and
+//! the markdown rendering states it above the tables.
+
+use crate::clock::{Clock, SystemClock};
+use crate::config_integration::{
+    apply_waivers, DocumentationPolicyRule, PolicyBasedValidationRule, StyleLintRule, WAIVER_APPROVER,
+};
+use crate::engine::ValidationRule;
+use crate::types::{SchemaFormat, Severity, ValidationError};
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use schema_registry_core::annotations;
+use schema_registry_core::config_manager_adapter::SchemaPolicies;
+use schema_registry_core::pagination::{PageRequest, DEFAULT_PAGE_LIMIT};
+use schema_registry_core::style_lint::MAX_STYLE_PENALTY;
+use schema_registry_core::traits::SchemaStorage;
+use schema_registry_core::RegisteredSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Metadata keys naming a schema's owning team, in order of precedence
+///
+/// Looked up in the schema's custom metadata first, then in the annotations
+/// on its root.
+pub const OWNER_KEYS: &[&str] = &["team", "owner", "x-owner"];
+
+/// Team of subjects without ownership metadata
+pub const UNOWNED: &str = "unowned";
+
+/// Quality score points one unwaived error takes off
+pub const ERROR_PENALTY: f64 = 10.0;
+
+/// Quality score points one unwaived warning takes off
+pub const WARNING_PENALTY: f64 = 3.0;
+
+/// Lower bounds of the quality score buckets, highest first
+const QUALITY_BUCKETS: [(f64, &str); 5] = [
+    (90.0, "90-100"),
+    (80.0, "80-89"),
+    (70.0, "70-79"),
+    (60.0, "60-69"),
+    (0.0, "0-59"),
+];
+
+/// Rule id of the finding recorded when a subject's policies can't be evaluated
+const EVALUATION_FAILED: &str = "governance-evaluation-failed";
+
+/// Team owning `schema`, [`UNOWNED`] when no [`OWNER_KEYS`] entry names one
+pub fn owner_of(schema: &RegisteredSchema) -> String {
+    let from_metadata = OWNER_KEYS
+        .iter()
+        .find_map(|key| schema.metadata.custom.get(*key)?.as_str().map(str::to_string));
+    from_metadata
+        .or_else(|| {
+            let annotations = annotations::extract(&schema.content, schema.format).ok()?;
+            OWNER_KEYS
+                .iter()
+                .find_map(|key| annotations.value("$", key)?.as_str().map(str::to_string))
+        })
+        .filter(|owner| !owner.trim().is_empty())
+        .unwrap_or_else(|| UNOWNED.to_string())
+}
+
+/// 100-point quality score of a schema with `findings`
+///
+/// Unwaived errors and warnings cost [`ERROR_PENALTY`] and
+/// [`WARNING_PENALTY`]; style findings cost their `quality_weight`, at most
+/// [`MAX_STYLE_PENALTY`] in total, whatever their severity. Waived findings
+/// cost nothing.
+pub fn quality_score(findings: &[ValidationError]) -> f64 {
+    let mut penalty = 0.0;
+    let mut style = 0.0;
+    for finding in findings {
+        if finding.context.contains_key(WAIVER_APPROVER) {
+            continue;
+        }
+        if let Some(weight) = finding.context.get("quality_weight").and_then(|w| w.parse::<f64>().ok()) {
+            style += weight;
+            continue;
+        }
+        penalty += match finding.severity {
+            Severity::Error => ERROR_PENALTY,
+            Severity::Warning => WARNING_PENALTY,
+            Severity::Info => 0.0,
+        };
+    }
+    (100.0 - penalty - style.min(MAX_STYLE_PENALTY)).clamp(0.0, 100.0)
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    }
+}
+
+/// Quality scores of a set of subjects
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QualityDistribution {
+    /// Subjects per score range, e.g. `"90-100"`
+    pub buckets: BTreeMap<String, usize>,
+    pub scored: usize,
+    pub total: f64,
+    pub min: Option<f64>,
+}
+
+impl QualityDistribution {
+    fn record(&mut self, score: f64) {
+        let (_, bucket) = QUALITY_BUCKETS
+            .iter()
+            .find(|(floor, _)| score >= *floor)
+            .unwrap_or(&QUALITY_BUCKETS[QUALITY_BUCKETS.len() - 1]);
+        *self.buckets.entry(bucket.to_string()).or_default() += 1;
+        self.scored += 1;
+        self.total += score;
+        self.min = Some(self.min.map_or(score, |min| min.min(score)));
+    }
+
+    fn absorb(&mut self, other: &QualityDistribution) {
+        for (bucket, count) in &other.buckets {
+            *self.buckets.entry(bucket.clone()).or_default() += count;
+        }
+        self.scored += other.scored;
+        self.total += other.total;
+        self.min = match (self.min, other.min) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+    }
+
+    /// Mean score, `None` when nothing was scored
+    pub fn mean(&self) -> Option<f64> {
+        (self.scored > 0).then(|| self.total / self.scored as f64)
+    }
+
+    /// Subjects in the range labelled `bucket`
+    pub fn count(&self, bucket: &str) -> usize {
+        self.buckets.get(bucket).copied().unwrap_or(0)
+    }
+}
+
+/// A subject past the sunset date of its deprecation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SunsetOverdue {
+    pub subject: String,
+    pub sunset_date: DateTime<Utc>,
+}
+
+/// Governance figures of one team's subjects, or of the whole registry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GovernanceRollup {
+    pub subjects: usize,
+    /// Stored versions of those subjects, deleted ones excluded
+    pub versions: usize,
+    /// Policy findings on the latest versions by severity (`error`, `warning`,
+    /// `info`), after waivers
+    pub findings: BTreeMap<String, usize>,
+    /// Findings downgraded by a waiver
+    pub waived_findings: usize,
+    pub quality: QualityDistribution,
+    /// Subjects whose latest version is deprecated
+    pub deprecated: usize,
+    /// Deprecated subjects past their sunset date, most overdue first
+    pub past_sunset: Vec<SunsetOverdue>,
+    /// Subjects per compatibility level of their latest version
+    pub compatibility: BTreeMap<String, usize>,
+}
+
+impl GovernanceRollup {
+    /// Findings of `severity` (`error`, `warning` or `info`)
+    pub fn findings_of(&self, severity: &str) -> usize {
+        self.findings.get(severity).copied().unwrap_or(0)
+    }
+
+    fn absorb(&mut self, other: &GovernanceRollup) {
+        self.subjects += other.subjects;
+        self.versions += other.versions;
+        for (severity, count) in &other.findings {
+            *self.findings.entry(severity.clone()).or_default() += count;
+        }
+        self.waived_findings += other.waived_findings;
+        self.quality.absorb(&other.quality);
+        self.deprecated += other.deprecated;
+        self.past_sunset.extend(other.past_sunset.iter().cloned());
+        self.past_sunset.sort_by(|a, b| a.sunset_date.cmp(&b.sunset_date).then(a.subject.cmp(&b.subject)));
+        for (level, count) in &other.compatibility {
+            *self.compatibility.entry(level.clone()).or_default() += count;
+        }
+    }
+}
+
+/// How often one waiver downgraded a finding during the scan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WaiverUsage {
+    pub subject: String,
+    pub rules: Vec<String>,
+    pub approver: String,
+    pub expires: NaiveDate,
+    pub active: bool,
+    /// Findings the waiver downgraded
+    pub findings: usize,
+}
+
+/// How much of the registry a report covers
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanCoverage {
+    /// Subjects in the registry
+    pub subjects_listed: usize,
+    /// Subjects whose schemas were read and evaluated
+    pub subjects_scanned: usize,
+    /// Position in the subject list where sampling began, if it did
+    pub sampled_from: Option<usize>,
+    /// Every `sample_stride`-th subject from `sampled_from` on was scanned
+    pub sample_stride: usize,
+    /// The time budget ran out before the end of the subject list
+    pub stopped_early: bool,
+    pub elapsed_ms: u64,
+}
+
+impl ScanCoverage {
+    /// Whether every subject was scanned
+    pub fn is_complete(&self) -> bool {
+        self.subjects_scanned == self.subjects_listed
+    }
+
+    /// Sentence describing a partial scan, `None` for a complete one
+    pub fn describe(&self) -> Option<String> {
+        if self.is_complete() {
+            return None;
+        }
+        let mut text = format!(
+            "Sampled: {} of {} subjects were scanned within the time budget.",
+            self.subjects_scanned, self.subjects_listed
+        );
+        if let Some(from) = self.sampled_from {
+            text.push_str(&format!(
+                " The first {} were scanned in full, then every {}th of the rest.",
+                from, self.sample_stride
+            ));
+        }
+        if self.stopped_early {
+            text.push_str(" The budget ran out before the end of the subject list.");
+        }
+        text.push_str(" Counts cover scanned subjects only.");
+        Some(text)
+    }
+}
+
+/// Progress of a running scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanProgress {
+    pub listed: usize,
+    /// Position in the subject list reached so far
+    pub position: usize,
+    pub scanned: usize,
+    pub sampling: bool,
+}
+
+/// Governance snapshot of a registry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GovernanceReport {
+    pub generated_at: DateTime<Utc>,
+    pub coverage: ScanCoverage,
+    /// Rollups by owning team
+    pub teams: BTreeMap<String, GovernanceRollup>,
+    /// Registry-wide rollup
+    pub totals: GovernanceRollup,
+    /// Every waiver in the policies, used or not
+    pub waivers: Vec<WaiverUsage>,
+}
+
+impl GovernanceReport {
+    /// Markdown with summary tables, for sharing as-is
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Schema Governance Report\n\n");
+        out.push_str(&format!(
+            "Generated {} | {} subjects | {} versions\n\n",
+            self.generated_at.format("%Y-%m-%d %H:%M UTC"),
+            self.totals.subjects,
+            self.totals.versions
+        ));
+        if let Some(note) = self.coverage.describe() {
+            out.push_str(&format!("> {}\n\n", note));
+        }
+
+        let rows: Vec<(&str, &GovernanceRollup)> = self
+            .teams
+            .iter()
+            .map(|(team, rollup)| (team.as_str(), rollup))
+            .chain(std::iter::once(("**Total**", &self.totals)))
+            .collect();
+        let mean = |rollup: &GovernanceRollup| {
+            rollup.quality.mean().map_or_else(|| "-".to_string(), |mean| format!("{:.1}", mean))
+        };
+
+        out.push_str("## Teams\n\n");
+        out.push_str("| Team | Subjects | Versions | Errors | Warnings | Info | Waived | Mean quality | Deprecated | Past sunset |\n");
+        out.push_str("|------|----------|----------|--------|----------|------|--------|--------------|------------|-------------|\n");
+        for (team, rollup) in &rows {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+                team,
+                rollup.subjects,
+                rollup.versions,
+                rollup.findings_of("error"),
+                rollup.findings_of("warning"),
+                rollup.findings_of("info"),
+                rollup.waived_findings,
+                mean(rollup),
+                rollup.deprecated,
+                rollup.past_sunset.len()
+            ));
+        }
+
+        out.push_str("\n## Quality Scores\n\n| Team |");
+        for (_, bucket) in QUALITY_BUCKETS {
+            out.push_str(&format!(" {} |", bucket));
+        }
+        out.push_str(" Lowest |\n|------|");
+        out.push_str(&"---|".repeat(QUALITY_BUCKETS.len()));
+        out.push_str("--------|\n");
+        for (team, rollup) in &rows {
+            out.push_str(&format!("| {} |", team));
+            for (_, bucket) in QUALITY_BUCKETS {
+                out.push_str(&format!(" {} |", rollup.quality.count(bucket)));
+            }
+            let lowest = rollup.quality.min.map_or_else(|| "-".to_string(), |min| format!("{:.1}", min));
+            out.push_str(&format!(" {} |\n", lowest));
+        }
+
+        let levels: Vec<&String> = self.totals.compatibility.keys().collect();
+        if !levels.is_empty() {
+            out.push_str("\n## Compatibility Levels\n\n| Team |");
+            for level in &levels {
+                out.push_str(&format!(" {} |", level));
+            }
+            out.push_str("\n|------|");
+            out.push_str(&"---|".repeat(levels.len()));
+            out.push('\n');
+            for (team, rollup) in &rows {
+                out.push_str(&format!("| {} |", team));
+                for level in &levels {
+                    out.push_str(&format!(" {} |", rollup.compatibility.get(*level).copied().unwrap_or(0)));
+                }
+                out.push('\n');
+            }
+        }
+
+        if !self.totals.past_sunset.is_empty() {
+            out.push_str("\n## Deprecated Past Sunset\n\n| Subject | Team | Sunset |\n|---------|------|--------|\n");
+            for (team, rollup) in &self.teams {
+                for overdue in &rollup.past_sunset {
+                    out.push_str(&format!(
+                        "| {} | {} | {} |\n",
+                        overdue.subject,
+                        team,
+                        overdue.sunset_date.format("%Y-%m-%d")
+                    ));
+                }
+            }
+        }
+
+        if !self.waivers.is_empty() {
+            out.push_str("\n## Waivers\n\n| Subject | Rules | Approver | Expires | Status | Findings waived |\n");
+            out.push_str("|---------|-------|----------|---------|--------|-----------------|\n");
+            for waiver in &self.waivers {
+                out.push_str(&format!(
+                    "| {} | {} | {} | {} | {} | {} |\n",
+                    waiver.subject,
+                    waiver.rules.join(", "),
+                    waiver.approver,
+                    waiver.expires,
+                    if waiver.active { "active" } else { "expired" },
+                    waiver.findings
+                ));
+            }
+        }
+
+        out
+    }
+}
+
+/// Builds a [`GovernanceReport`] from a storage backend
+pub struct GovernanceScanner {
+    policies: SchemaPolicies,
+    page_size: usize,
+    time_budget: Option<Duration>,
+    clock: Arc<dyn Clock>,
+    now: Option<DateTime<Utc>>,
+}
+
+impl GovernanceScanner {
+    /// Scanner applying `policies`, with no time budget
+    pub fn new(policies: SchemaPolicies) -> Self {
+        Self {
+            policies,
+            page_size: DEFAULT_PAGE_LIMIT,
+            time_budget: None,
+            clock: Arc::new(SystemClock),
+            now: None,
+        }
+    }
+
+    /// Subject names fetched per page
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Sample instead of scanning every subject when a full scan would take longer
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Clock measuring the time budget
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Evaluate sunsets and waiver expiry as of `now` instead of the current time
+    pub fn at(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Scan every subject in `storage`
+    pub async fn scan(&self, storage: &dyn SchemaStorage) -> Result<GovernanceReport> {
+        self.scan_with_progress(storage, |_| {}).await
+    }
+
+    /// Scan every subject in `storage`, reporting progress after each page and subject
+    pub async fn scan_with_progress(
+        &self,
+        storage: &dyn SchemaStorage,
+        mut on_progress: impl FnMut(ScanProgress),
+    ) -> Result<GovernanceReport> {
+        let now = self.now.unwrap_or_else(Utc::now);
+        let rules = Rules::new(&self.policies);
+
+        let mut subjects = Vec::new();
+        let mut request = PageRequest::new(self.page_size);
+        loop {
+            let page = storage.list_subjects(&request).await?;
+            subjects.extend(page.items);
+            on_progress(ScanProgress {
+                listed: subjects.len(),
+                position: 0,
+                scanned: 0,
+                sampling: false,
+            });
+            match page.next_cursor {
+                Some(cursor) => request.cursor = Some(cursor),
+                None => break,
+            }
+        }
+
+        let start = self.clock.now();
+        let mut coverage = ScanCoverage {
+            subjects_listed: subjects.len(),
+            sample_stride: 1,
+            ..Default::default()
+        };
+        let mut teams: BTreeMap<String, GovernanceRollup> = BTreeMap::new();
+        let mut waiver_usage = vec![0; self.policies.waivers.len()];
+
+        let mut next = 0;
+        for (position, subject) in subjects.iter().enumerate() {
+            if position < next {
+                continue;
+            }
+            if let Some(budget) = self.time_budget {
+                let elapsed = self.clock.now().saturating_sub(start);
+                if elapsed >= budget {
+                    coverage.stopped_early = true;
+                    break;
+                }
+                // Widen the stride when the subjects left won't fit at the pace so far
+                if coverage.subjects_scanned > 0 {
+                    let per_subject = elapsed.as_secs_f64() / coverage.subjects_scanned as f64;
+                    let left = (subjects.len() - position) as f64;
+                    let remaining = (budget - elapsed).as_secs_f64();
+                    let stride = (per_subject * left / remaining).ceil() as usize;
+                    if stride > coverage.sample_stride {
+                        coverage.sample_stride = stride;
+                        coverage.sampled_from.get_or_insert(position);
+                    }
+                }
+            }
+
+            if let Some((team, rollup)) = self.scan_subject(storage, subject, &rules, now, &mut waiver_usage).await? {
+                teams.entry(team).or_default().absorb(&rollup);
+            }
+            coverage.subjects_scanned += 1;
+            next = position + coverage.sample_stride;
+            on_progress(ScanProgress {
+                listed: subjects.len(),
+                position: position + 1,
+                scanned: coverage.subjects_scanned,
+                sampling: coverage.sampled_from.is_some(),
+            });
+        }
+        coverage.elapsed_ms = self.clock.now().saturating_sub(start).as_millis() as u64;
+
+        let mut totals = GovernanceRollup::default();
+        for rollup in teams.values() {
+            totals.absorb(rollup);
+        }
+        let today = now.date_naive();
+        let waivers = self
+            .policies
+            .waivers
+            .iter()
+            .zip(waiver_usage)
+            .map(|(waiver, findings)| WaiverUsage {
+                subject: waiver.subject.clone(),
+                rules: waiver.rules.clone(),
+                approver: waiver.approver.clone(),
+                expires: waiver.expires,
+                active: waiver.is_active(today),
+                findings,
+            })
+            .collect();
+
+        Ok(GovernanceReport {
+            generated_at: now,
+            coverage,
+            teams,
+            totals,
+            waivers,
+        })
+    }
+
+    /// Owning team and rollup of one subject, `None` if all its versions are deleted
+    async fn scan_subject(
+        &self,
+        storage: &dyn SchemaStorage,
+        subject: &str,
+        rules: &Rules,
+        now: DateTime<Utc>,
+        waiver_usage: &mut [usize],
+    ) -> Result<Option<(String, GovernanceRollup)>> {
+        let Some((namespace, name)) = subject.rsplit_once('.') else {
+            return Ok(None);
+        };
+        let versions: Vec<RegisteredSchema> = storage
+            .find_by_name(namespace, name)
+            .await?
+            .into_iter()
+            .filter(|schema| schema.metadata.deletion.is_none())
+            .collect();
+        let Some(latest) = versions.iter().max_by(|a, b| a.version.cmp(&b.version)) else {
+            return Ok(None);
+        };
+
+        let evaluation = apply_waivers(
+            rules.findings(latest),
+            subject,
+            &self.policies.waivers,
+            now.date_naive(),
+        );
+        for (total, used) in waiver_usage.iter_mut().zip(&evaluation.waiver_usage) {
+            *total += used;
+        }
+
+        let mut rollup = GovernanceRollup {
+            subjects: 1,
+            versions: versions.len(),
+            waived_findings: evaluation.waived(),
+            ..Default::default()
+        };
+        for finding in &evaluation.findings {
+            *rollup.findings.entry(severity_name(finding.severity).to_string()).or_default() += 1;
+        }
+        rollup.quality.record(quality_score(&evaluation.findings));
+        if let Some(deprecation) = &latest.metadata.deprecation {
+            rollup.deprecated = 1;
+            if deprecation.sunset_date < now {
+                rollup.past_sunset.push(SunsetOverdue {
+                    subject: subject.to_string(),
+                    sunset_date: deprecation.sunset_date,
+                });
+            }
+        }
+        rollup.compatibility.insert(latest.compatibility_mode.to_string(), 1);
+
+        Ok(Some((owner_of(latest), rollup)))
+    }
+}
+
+/// The policy rules a scan evaluates
+struct Rules {
+    rules: Vec<Box<dyn ValidationRule>>,
+}
+
+impl Rules {
+    fn new(policies: &SchemaPolicies) -> Self {
+        Self {
+            rules: vec![
+                Box::new(PolicyBasedValidationRule::new(policies.clone())),
+                Box::new(DocumentationPolicyRule::new(policies)),
+                Box::new(StyleLintRule::new(policies)),
+            ],
+        }
+    }
+
+    /// Findings of every rule on `schema`, before waivers
+    ///
+    /// A rule that fails outright is reported as an error finding rather than
+    /// aborting the scan.
+    fn findings(&self, schema: &RegisteredSchema) -> Vec<ValidationError> {
+        let format = SchemaFormat::from(schema.format);
+        let mut findings = Vec::new();
+        for rule in &self.rules {
+            match rule.validate(&schema.content, format) {
+                Ok(found) => findings.extend(found),
+                Err(e) => findings.push(ValidationError::new(
+                    EVALUATION_FAILED,
+                    format!("{} could not be evaluated: {}", rule.name(), e),
+                )),
+            }
+        }
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schema_registry_core::config_manager_adapter::PolicyWaiver;
+    use schema_registry_core::embedded::EmbeddedRegistry;
+    use schema_registry_core::schema::DeprecationInfo;
+    use schema_registry_core::types::{CompatibilityMode, SerializationFormat};
+    use schema_registry_core::SchemaInput;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    const DOCUMENTED: &str = r#"{"type": "object", "description": "An order", "properties": {"id": {"type": "string", "description": "Unique identifier of the order"}}}"#;
+    const UNDOCUMENTED: &str = r#"{"type": "object", "properties": {"id": {"type": "string"}}}"#;
+
+    fn now() -> DateTime<Utc> {
+        "2025-07-01T00:00:00Z".parse().unwrap()
+    }
+
+    fn policies() -> SchemaPolicies {
+        let mut policies = SchemaPolicies::default();
+        policies.required_annotations = vec!["x-tier".to_string()];
+        policies.waivers = vec![PolicyWaiver {
+            subject: "payments.*".to_string(),
+            rules: vec!["metadata-policy".to_string()],
+            expires: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            approver: "governance".to_string(),
+            reason: "migration in progress".to_string(),
+        }];
+        policies
+    }
+
+    async fn registry() -> EmbeddedRegistry {
+        let registry = EmbeddedRegistry::new();
+        let input = |namespace: &str, name: &str, content: &str, team: Option<&str>| {
+            let input = SchemaInput::new(namespace, name, SerializationFormat::JsonSchema, content);
+            match team {
+                Some(team) => input.with_metadata("team", json!(team)),
+                None => input,
+            }
+        };
+        registry.register(input("payments", "invoice", DOCUMENTED, Some("billing"))).await.unwrap();
+        registry
+            .register(
+                input("orders", "order", UNDOCUMENTED, Some("commerce"))
+                    .with_compatibility_mode(CompatibilityMode::Full),
+            )
+            .await
+            .unwrap();
+        let mut legacy = registry.register(input("orders", "legacy", DOCUMENTED, None)).await.unwrap();
+        legacy.metadata.deprecation = Some(DeprecationInfo {
+            reason: "replaced by orders.order".to_string(),
+            deprecated_at: "2025-01-01T00:00:00Z".parse().unwrap(),
+            deprecated_by: "commerce".to_string(),
+            sunset_date: "2025-06-01T00:00:00Z".parse().unwrap(),
+            migration_guide: None,
+            replacement_schema: None,
+        });
+        registry.storage().update(legacy).await.unwrap();
+        registry
+    }
+
+    #[test]
+    fn test_quality_score_penalties() {
+        let error = ValidationError::new("metadata-policy", "missing");
+        let mut warning = ValidationError::new("field-naming-policy", "camelCase");
+        warning.severity = Severity::Warning;
+        let style = ValidationError::new("style-description", "undocumented").with_context("quality_weight", "0.5");
+        let waived = ValidationError::new("metadata-policy", "missing").with_context("waiver_approver", "governance");
+
+        assert_eq!(quality_score(&[]), 100.0);
+        assert_eq!(quality_score(&[error.clone(), warning, style.clone(), waived]), 86.5);
+        let many = |finding: ValidationError, n: usize| (0..n).map(|_| finding.clone()).collect::<Vec<_>>();
+        assert_eq!(quality_score(&many(style, 40)), 100.0 - MAX_STYLE_PENALTY);
+        assert_eq!(quality_score(&many(error, 20)), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_report_rolls_up_per_team() {
+        let registry = registry().await;
+        let storage = registry.storage();
+        let report = GovernanceScanner::new(policies())
+            .with_page_size(1)
+            .at(now())
+            .scan(storage.as_ref())
+            .await
+            .unwrap();
+
+        assert!(report.coverage.is_complete());
+        assert_eq!(report.coverage.subjects_listed, 3);
+        let teams: Vec<_> = report.teams.keys().map(String::as_str).collect();
+        assert_eq!(teams, vec!["billing", "commerce", UNOWNED]);
+
+        let billing = &report.teams["billing"];
+        assert_eq!(billing.waived_findings, 1);
+        assert_eq!(billing.findings_of("error"), 0);
+        assert_eq!(report.waivers[0].findings, 1);
+
+        let commerce = &report.teams["commerce"];
+        assert_eq!(commerce.findings_of("error"), 1);
+        assert_eq!(commerce.compatibility.get("FULL"), Some(&1));
+        assert_eq!(commerce.quality.mean(), Some(90.0 - 0.5 - 1.0));
+
+        let unowned = &report.teams[UNOWNED];
+        assert_eq!(unowned.deprecated, 1);
+        assert_eq!(unowned.past_sunset[0].subject, "orders.legacy");
+
+        assert_eq!(report.totals.subjects, 3);
+        assert_eq!(report.totals.findings_of("error"), 2);
+        assert_eq!(report.totals.compatibility.get("BACKWARD"), Some(&2));
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("| commerce | 1 | 1 | 1 |"));
+        assert!(markdown.contains("| orders.legacy | unowned | 2025-06-01 |"));
+        assert!(markdown.contains("| payments.* | metadata-policy | governance | 2026-01-01 | active | 1 |"));
+        assert!(!markdown.contains("Sampled:"));
+    }
+
+    /// Clock advancing one second per reading
+    struct TickingClock(AtomicU64);
+
+    impl Clock for TickingClock {
+        fn now(&self) -> Duration {
+            Duration::from_secs(self.0.fetch_add(1, Ordering::SeqCst))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_time_budget_degrades_to_sampling() {
+        let registry = EmbeddedRegistry::new();
+        for i in 0..40 {
+            let input = SchemaInput::new("load", format!("s{:02}", i), SerializationFormat::JsonSchema, DOCUMENTED);
+            registry.register(input).await.unwrap();
+        }
+        let storage = registry.storage();
+        let report = GovernanceScanner::new(SchemaPolicies::default())
+            .with_time_budget(Duration::from_secs(30))
+            .with_clock(Arc::new(TickingClock(AtomicU64::new(0))))
+            .at(now())
+            .scan(storage.as_ref())
+            .await
+            .unwrap();
+
+        let coverage = &report.coverage;
+        assert!(!coverage.is_complete());
+        assert!(coverage.sampled_from.is_some());
+        assert!(coverage.sample_stride > 1);
+        assert_eq!(report.totals.subjects, coverage.subjects_scanned);
+        assert!(report.to_markdown().contains("> Sampled: "));
+    }
+}
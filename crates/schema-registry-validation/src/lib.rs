@@ -2,14 +2,20 @@
 use async_trait::async_trait;
 use schema_registry_core::{error::Result, schema::SchemaInput, traits::{SchemaValidator, ValidationResult}, types::SerializationFormat};
 
+pub mod clock;
 pub mod engine;
 pub mod format_detection;
+pub mod governance;
 pub mod types;
 pub mod validators;
 
 // Config Manager integration for policy-based validation (Phase 2B)
 pub mod config_integration;
 
+// JavaScript bindings for browser and edge builds
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 pub struct ValidationEngine {}
 
 impl ValidationEngine {
@@ -29,6 +29,27 @@ impl SchemaFormat {
     }
 }
 
+impl From<SchemaFormat> for schema_registry_core::types::SerializationFormat {
+    fn from(format: SchemaFormat) -> Self {
+        match format {
+            SchemaFormat::JsonSchema => Self::JsonSchema,
+            SchemaFormat::Avro => Self::Avro,
+            SchemaFormat::Protobuf => Self::Protobuf,
+        }
+    }
+}
+
+impl From<schema_registry_core::types::SerializationFormat> for SchemaFormat {
+    fn from(format: schema_registry_core::types::SerializationFormat) -> Self {
+        use schema_registry_core::types::SerializationFormat;
+        match format {
+            SerializationFormat::JsonSchema => Self::JsonSchema,
+            SerializationFormat::Avro => Self::Avro,
+            SerializationFormat::Protobuf => Self::Protobuf,
+        }
+    }
+}
+
 /// Severity level for validation issues
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Severity {
@@ -139,6 +160,17 @@ impl ValidationWarning {
     }
 }
 
+impl From<ValidationError> for ValidationWarning {
+    fn from(error: ValidationError) -> Self {
+        Self {
+            rule: error.rule,
+            message: error.message,
+            location: error.location,
+            suggestion: error.suggestion,
+        }
+    }
+}
+
 /// Metrics collected during validation
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ValidationMetrics {
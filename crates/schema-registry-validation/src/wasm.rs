@@ -0,0 +1,53 @@
+//! JavaScript bindings (`wasm` feature)
+//!
+//! Exposes the validation engine to browsers and edge runtimes so editors can
+//! lint schemas client-side with the same rules the registry applies on
+//! registration:
+//!
+//! ```js
+//! import init, { validate } from "schema_registry_validation";
+//! await init();
+//! const result = JSON.parse(await validate(schema, "avro", policiesJson));
+//! ```
+
+use crate::config_integration::PolicyBasedValidationRule;
+use crate::engine::ValidationEngine;
+use crate::types::SchemaFormat;
+use schema_registry_core::config_manager_adapter::SchemaPolicies;
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+
+/// Parses a format name as written by [`SchemaFormat::as_str`]
+fn parse_format(format: &str) -> Option<SchemaFormat> {
+    [SchemaFormat::JsonSchema, SchemaFormat::Avro, SchemaFormat::Protobuf]
+        .into_iter()
+        .find(|candidate| candidate.as_str().eq_ignore_ascii_case(format))
+}
+
+/// Validates `schema` in `format` ("json-schema", "avro" or "protobuf")
+///
+/// `policies_json` is a serialized `SchemaPolicies`; an empty string applies
+/// the default policies. Resolves to the `ValidationResult` as JSON and
+/// rejects with a message when the format or policies can't be parsed.
+#[wasm_bindgen]
+pub async fn validate(schema: String, format: String, policies_json: String) -> Result<String, JsValue> {
+    validate_json(&schema, &format, &policies_json)
+        .await
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+async fn validate_json(schema: &str, format: &str, policies_json: &str) -> Result<String, String> {
+    let format = parse_format(format).ok_or_else(|| {
+        format!("unknown schema format '{}', expected json-schema, avro or protobuf", format)
+    })?;
+    let policies: SchemaPolicies = if policies_json.trim().is_empty() {
+        SchemaPolicies::default()
+    } else {
+        serde_json::from_str(policies_json).map_err(|e| format!("invalid policies: {}", e))?
+    };
+
+    let mut engine = ValidationEngine::new();
+    engine.add_rule(Arc::new(PolicyBasedValidationRule::new(policies)));
+    let result = engine.validate(schema, format).await.map_err(|e| e.to_string())?;
+    serde_json::to_string(&result).map_err(|e| e.to_string())
+}
@@ -0,0 +1,62 @@
+//! Validation under wasm32 through the JavaScript bindings
+//!
+//! Run with `wasm-pack test --node crates/schema-registry-validation --features wasm`.
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use schema_registry_core::config_manager_adapter::SchemaPolicies;
+use schema_registry_validation::wasm::validate;
+use serde_json::Value;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// Schema, format and expected validity
+const CORPUS: &[(&str, &str, bool)] = &[
+    (
+        r#"{"type": "object", "description": "A user", "properties": {"name": {"type": "string", "description": "Name"}}}"#,
+        "json-schema",
+        true,
+    ),
+    (r#"{ invalid json }"#, "json-schema", false),
+    (
+        r#"{"type": "record", "name": "User", "namespace": "com.example", "doc": "A user", "fields": [{"name": "id", "type": "long", "doc": "Id"}]}"#,
+        "avro",
+        true,
+    ),
+    (r#"{"type": "record", "name": }"#, "avro", false),
+    (
+        "syntax = \"proto3\";\npackage example;\n\nmessage User {\n  int64 id = 1;\n}\n",
+        "protobuf",
+        true,
+    ),
+];
+
+async fn run(schema: &str, format: &str, policies: &str) -> Value {
+    let json = validate(schema.to_string(), format.to_string(), policies.to_string())
+        .await
+        .unwrap_or_else(|e| panic!("{format} schema was rejected: {e:?}"));
+    serde_json::from_str(&json).unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn corpus_matches_expected_validity() {
+    for (schema, format, valid) in CORPUS {
+        let result = run(schema, format, "").await;
+        assert_eq!(result["is_valid"], *valid, "{format}: {schema}");
+    }
+}
+
+#[wasm_bindgen_test]
+async fn policies_json_is_applied() {
+    let default = run(CORPUS[0].0, "json-schema", "").await;
+    let policies = serde_json::to_string(&SchemaPolicies::default()).unwrap();
+    let explicit = run(CORPUS[0].0, "json-schema", &policies).await;
+    assert_eq!(default["is_valid"], explicit["is_valid"]);
+    assert!(validate(CORPUS[0].0.to_string(), "json-schema".to_string(), "not json".to_string())
+        .await
+        .is_err());
+}
+
+#[wasm_bindgen_test]
+async fn unknown_format_is_rejected() {
+    assert!(validate("{}".to_string(), "thrift".to_string(), String::new()).await.is_err());
+}